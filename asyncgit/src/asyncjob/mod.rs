@@ -1,7 +1,13 @@
 //! provides `AsyncJob` trait and `AsyncSingleJob` struct
+//!
+//! `AsyncQueueJob` runs jobs keyed by their params
 
 #![deny(clippy::expect_used)]
 
+mod queue;
+
+pub use queue::{AsyncQueueJob, JobContext};
+
 use crate::error::Result;
 use crossbeam_channel::Sender;
 use std::sync::{Arc, Mutex, RwLock};
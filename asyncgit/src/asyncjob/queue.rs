@@ -0,0 +1,491 @@
+//! provides `AsyncQueueJob`
+
+use crate::{
+	error::Result,
+	hash,
+	job_errors::{self, ErrorId},
+	tasks,
+};
+use crossbeam_channel::Sender;
+use std::{
+	hash::Hash,
+	sync::{
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+};
+
+type JobFn<P, R, T> =
+	dyn Fn(&P, &JobContext<P, R, T>) -> Result<R> + Send + Sync;
+
+struct Results<P, R> {
+	/// hash of the params requested last (0: none), the id of the
+	/// job for them and its result
+	current: Mutex<(u64, usize, Option<R>)>,
+	/// last result published, even if superseded by now, and the
+	/// id of the job publishing it
+	last: Mutex<(usize, Option<(P, R)>)>,
+}
+
+/// handed to the job function while it runs
+pub struct JobContext<P, R, T> {
+	results: Arc<Results<P, R>>,
+	id: usize,
+	params: P,
+	cancelled: Arc<AtomicBool>,
+	sender: Sender<T>,
+	notification: T,
+}
+
+impl<P: Clone, R: Clone, T: Copy> JobContext<P, R, T> {
+	/// `true` once the job got cancelled or superseded
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
+
+	/// publishes an intermediate result via `last` (never as
+	/// `current`) and notifies, as long as the job is still current
+	pub fn publish(&self, partial: &R) -> Result<()> {
+		if self.results.current.lock()?.1 != self.id {
+			return Ok(());
+		}
+
+		*self.results.last.lock()? =
+			(self.id, Some((self.params.clone(), partial.clone())));
+
+		self.sender.send(self.notification)?;
+
+		Ok(())
+	}
+
+	/// stores `result`, returns `true` if it is the current one
+	fn finish(&self, result: R) -> Result<bool> {
+		if self.is_cancelled() {
+			let mut current = self.results.current.lock()?;
+			if current.1 == self.id {
+				*current = (0, 0, None);
+			}
+			drop(current);
+
+			// dont leave an unfinished result behind
+			let mut last = self.results.last.lock()?;
+			if last.0 == self.id {
+				*last = (0, None);
+			}
+			drop(last);
+
+			return Ok(false);
+		}
+
+		let mut notify = false;
+		{
+			let mut current = self.results.current.lock()?;
+			if current.1 == self.id {
+				current.2 = Some(result.clone());
+				notify = true;
+			}
+			drop(current);
+		}
+
+		*self.results.last.lock()? =
+			(self.id, Some((self.params.clone(), result)));
+
+		Ok(notify)
+	}
+}
+
+/// runs a job for requested params on the threadpool
+///
+/// only the latest request is of interest: requesting new params
+/// cancels a still running job for previous ones.
+/// keeps the result for the current params (returned right away
+/// when they are requested again) and the last one finished.
+pub struct AsyncQueueJob<P, R, T> {
+	results: Arc<Results<P, R>>,
+	run: Arc<JobFn<P, R, T>>,
+	sender: Sender<T>,
+	/// sent when the current job finished (or failed)
+	notification: T,
+	/// sent when a superseded or cancelled job finished
+	unchanged: T,
+	pending: Arc<AtomicUsize>,
+	cancelled: Arc<AtomicBool>,
+	/// id of the job requested last (0: none)
+	last_id: usize,
+	/// kind of job (e.g. `diff`) in the task list and error reports
+	kind: &'static str,
+	describe: Option<fn(&P) -> String>,
+}
+
+impl<P, R, T> AsyncQueueJob<P, R, T>
+where
+	P: 'static + Hash + Clone + PartialEq + Send + Sync,
+	R: 'static + Clone + Send + Sync,
+	T: 'static + Copy + Send + From<ErrorId>,
+{
+	///
+	pub fn new(
+		kind: &'static str,
+		sender: Sender<T>,
+		notification: T,
+		unchanged: T,
+		run: impl Fn(&P, &JobContext<P, R, T>) -> Result<R>
+			+ Send
+			+ Sync
+			+ 'static,
+	) -> Self {
+		Self {
+			results: Arc::new(Results {
+				current: Mutex::new((0, 0, None)),
+				last: Mutex::new((0, None)),
+			}),
+			run: Arc::new(run),
+			sender,
+			notification,
+			unchanged,
+			pending: Arc::new(AtomicUsize::new(0)),
+			cancelled: Arc::new(AtomicBool::new(false)),
+			last_id: 0,
+			kind,
+			describe: None,
+		}
+	}
+
+	/// describes the params of running jobs in the task list
+	/// (jobs without description are not listed there) and
+	/// gives errors some context. listed jobs can get cancelled
+	/// there, so their `run` has to stop once
+	/// `JobContext::is_cancelled`
+	#[must_use]
+	pub fn with_description(
+		mut self,
+		describe: fn(&P) -> String,
+	) -> Self {
+		self.describe = Some(describe);
+		self
+	}
+
+	/// a job is running, even a cancelled one that did not stop yet
+	pub fn is_pending(&self) -> bool {
+		self.pending.load(Ordering::Relaxed) > 0
+	}
+
+	/// last result published together with its params
+	pub fn last(&self) -> Result<Option<(P, R)>> {
+		Ok(self.results.last.lock()?.1.clone())
+	}
+
+	/// drops the last result if `f` says so
+	pub fn discard_last_if(
+		&self,
+		f: impl FnOnce(&P, &R) -> bool,
+	) -> Result<()> {
+		let mut last = self.results.last.lock()?;
+		if last
+			.1
+			.as_ref()
+			.map_or(false, |(params, result)| f(params, result))
+		{
+			*last = (0, None);
+		}
+		drop(last);
+
+		Ok(())
+	}
+
+	/// returns the result right away if `params` are the current
+	/// ones and already done, otherwise spawns a job for them
+	pub fn request(&mut self, params: P) -> Result<Option<R>> {
+		let hash = hash(&params);
+
+		let previous = {
+			let mut current = self.results.current.lock()?;
+
+			if current.0 == hash {
+				return Ok(current.2.clone());
+			}
+
+			self.last_id += 1;
+			let previous = current.0;
+			*current = (hash, self.last_id, None);
+			previous
+		};
+
+		// a job for other params still running is not of interest
+		// anymore (`rerun` cancels it itself to redo the same one)
+		if previous != 0 {
+			self.cancelled.store(true, Ordering::Relaxed);
+		}
+		self.cancelled = Arc::new(AtomicBool::new(false));
+
+		let description =
+			self.describe.map(|describe| describe(&params));
+		let task = description.clone().map(|description| {
+			tasks::register(
+				self.kind,
+				description,
+				Arc::clone(&self.cancelled),
+			)
+		});
+		let kind = self.kind;
+
+		let ctx = JobContext {
+			results: Arc::clone(&self.results),
+			id: self.last_id,
+			params,
+			cancelled: Arc::clone(&self.cancelled),
+			sender: self.sender.clone(),
+			notification: self.notification,
+		};
+		let run = Arc::clone(&self.run);
+		let pending = Arc::clone(&self.pending);
+		let sender = self.sender.clone();
+		let (notification, unchanged) =
+			(self.notification, self.unchanged);
+
+		self.pending.fetch_add(1, Ordering::Relaxed);
+
+		rayon_core::spawn(move || {
+			let notify = run(&ctx.params, &ctx)
+				.and_then(|result| ctx.finish(result))
+				.unwrap_or_else(|e| {
+					let id = job_errors::report(
+						kind,
+						description.unwrap_or_default(),
+						&e,
+					);
+					if let Err(e) = sender.send(T::from(id)) {
+						log::error!(
+							"async job notification error: {}",
+							e
+						);
+					}
+					true
+				});
+
+			drop(task);
+			pending.fetch_sub(1, Ordering::Relaxed);
+
+			if let Err(e) = sender.send(if notify {
+				notification
+			} else {
+				unchanged
+			}) {
+				log::error!("async job notification error: {}", e);
+			}
+		});
+
+		Ok(None)
+	}
+
+	/// runs the job for the last params again
+	pub fn refresh(&mut self) -> Result<()> {
+		let params = self.last()?.map(|(params, _)| params);
+		if let Some(params) = params {
+			self.rerun(params)?;
+		}
+
+		Ok(())
+	}
+
+	/// runs the job for `params` even if their result is known,
+	/// a still running job for them would report outdated data
+	pub fn rerun(&mut self, params: P) -> Result<()> {
+		self.cancel()?;
+		self.request(params)?;
+
+		Ok(())
+	}
+
+	/// stops a running job, e.g. once the user moved on
+	pub fn cancel(&mut self) -> Result<()> {
+		self.cancelled.store(true, Ordering::Relaxed);
+		self.clear_current()
+	}
+
+	fn clear_current(&self) -> Result<()> {
+		*self.results.current.lock()? = (0, 0, None);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crossbeam_channel::{unbounded, Receiver};
+	use pretty_assertions::assert_eq;
+	use std::{thread, time::Duration};
+
+	#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+	enum Notification {
+		Done,
+		Unchanged,
+		Failed(ErrorId),
+	}
+
+	impl From<ErrorId> for Notification {
+		fn from(id: ErrorId) -> Self {
+			Self::Failed(id)
+		}
+	}
+
+	type Job = AsyncQueueJob<u32, u32, Notification>;
+
+	fn job(gate: Arc<AtomicBool>) -> (Job, Receiver<Notification>) {
+		let (sender, receiver) = unbounded();
+		let job = AsyncQueueJob::new(
+			"test",
+			sender,
+			Notification::Done,
+			Notification::Unchanged,
+			move |params: &u32, ctx| {
+				if *params == 0 {
+					return Err(crate::Error::Generic(String::from(
+						"zero",
+					)));
+				}
+				ctx.publish(&0)?;
+				while !gate.load(Ordering::SeqCst) {
+					thread::sleep(Duration::from_millis(1));
+				}
+				Ok(params * 2)
+			},
+		)
+		.with_description(ToString::to_string);
+
+		(job, receiver)
+	}
+
+	#[test]
+	fn test_request_and_cache() {
+		let gate = Arc::new(AtomicBool::new(true));
+		let (mut job, receiver) = job(gate);
+
+		assert_eq!(job.request(1).unwrap(), None);
+		assert_eq!(receiver.recv().unwrap(), Notification::Done);
+		assert_eq!(receiver.recv().unwrap(), Notification::Done);
+
+		assert_eq!(job.request(1).unwrap(), Some(2));
+		assert_eq!(job.last().unwrap(), Some((1, 2)));
+		assert!(receiver.is_empty());
+	}
+
+	#[test]
+	fn test_supersede() {
+		let gate = Arc::new(AtomicBool::new(false));
+		let (mut job, receiver) = job(Arc::clone(&gate));
+
+		assert_eq!(job.request(1).unwrap(), None);
+		assert_eq!(receiver.recv().unwrap(), Notification::Done);
+
+		assert_eq!(job.request(2).unwrap(), None);
+		gate.store(true, Ordering::SeqCst);
+
+		// partial and final of the second, the first got superseded
+		let mut notifications: Vec<_> =
+			(0..3).map(|_| receiver.recv().unwrap()).collect();
+		notifications.sort_by_key(|n| *n == Notification::Done);
+		assert_eq!(
+			notifications,
+			vec![
+				Notification::Unchanged,
+				Notification::Done,
+				Notification::Done
+			]
+		);
+
+		assert_eq!(job.last().unwrap(), Some((2, 4)));
+		assert_eq!(job.request(2).unwrap(), Some(4));
+	}
+
+	#[test]
+	fn test_cancel_drops_partial() {
+		let gate = Arc::new(AtomicBool::new(false));
+		let (mut job, receiver) = job(Arc::clone(&gate));
+
+		job.request(1).unwrap();
+		assert_eq!(receiver.recv().unwrap(), Notification::Done);
+		assert_eq!(job.last().unwrap(), Some((1, 0)));
+
+		job.cancel().unwrap();
+		// still running until it notices
+		assert!(job.is_pending());
+		gate.store(true, Ordering::SeqCst);
+
+		assert_eq!(receiver.recv().unwrap(), Notification::Unchanged);
+		assert!(!job.is_pending());
+		assert_eq!(job.last().unwrap(), None);
+	}
+
+	#[test]
+	fn test_refresh_while_running() {
+		let gate = Arc::new(AtomicBool::new(false));
+		let value = Arc::new(AtomicUsize::new(1));
+		let (sender, receiver) = unbounded();
+		let mut job = {
+			let (gate, value) =
+				(Arc::clone(&gate), Arc::clone(&value));
+			AsyncQueueJob::new(
+				"test",
+				sender,
+				Notification::Done,
+				Notification::Unchanged,
+				move |_: &u32, ctx: &JobContext<_, _, _>| {
+					let value = value.load(Ordering::SeqCst);
+					ctx.publish(&0)?;
+					while !gate.load(Ordering::SeqCst) {
+						thread::sleep(Duration::from_millis(1));
+					}
+					Ok(value)
+				},
+			)
+		};
+
+		job.request(1).unwrap();
+		assert_eq!(receiver.recv().unwrap(), Notification::Done);
+
+		// the running job read the old state already
+		value.store(2, Ordering::SeqCst);
+		job.refresh().unwrap();
+		gate.store(true, Ordering::SeqCst);
+
+		// partial and final of the refresh, the first got cancelled
+		let mut notifications: Vec<_> =
+			(0..3).map(|_| receiver.recv().unwrap()).collect();
+		notifications.sort_by_key(|n| *n == Notification::Done);
+		assert_eq!(
+			notifications,
+			vec![
+				Notification::Unchanged,
+				Notification::Done,
+				Notification::Done
+			]
+		);
+
+		assert!(!job.is_pending());
+		assert_eq!(job.request(1).unwrap(), Some(2));
+		assert_eq!(job.last().unwrap(), Some((1, 2)));
+	}
+
+	#[test]
+	fn test_error() {
+		let gate = Arc::new(AtomicBool::new(true));
+		let (mut job, receiver) = job(gate);
+
+		job.request(0).unwrap();
+
+		let failed = receiver.recv().unwrap();
+		assert_eq!(receiver.recv().unwrap(), Notification::Done);
+
+		let error = if let Notification::Failed(id) = failed {
+			job_errors::take(id)
+		} else {
+			None
+		};
+		assert_eq!(
+			error.map(|error| error.to_string()),
+			Some(String::from("test failed (0): `zero`"))
+		);
+		assert_eq!(job.last().unwrap(), None);
+	}
+}
@@ -0,0 +1,55 @@
+use crate::{
+	asyncjob::AsyncQueueJob,
+	error::Result,
+	sync::{self, BranchActivity, RepoPath},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use std::collections::HashMap;
+
+/// `BranchActivity` by reference
+pub type BranchesActivity = HashMap<String, BranchActivity>;
+
+/// compares the local (or remote) branches to their upstreams
+pub struct AsyncBranchActivity {
+	job: AsyncQueueJob<bool, BranchesActivity, AsyncGitNotification>,
+}
+
+impl AsyncBranchActivity {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		Self {
+			job: AsyncQueueJob::new(
+				"branch activity",
+				sender.clone(),
+				AsyncGitNotification::BranchActivity,
+				AsyncGitNotification::FinishUnchanged,
+				move |local: &bool, _ctx| {
+					sync::get_branches_activity(&repo, *local)
+				},
+			),
+		}
+	}
+
+	/// looks at the local (or remote) branches again in the
+	/// background, branches might have moved since the last time
+	pub fn request(&mut self, local: bool) -> Result<()> {
+		self.job.cancel()?;
+		self.job.request(local)?;
+
+		Ok(())
+	}
+
+	/// last result and whether it is about the local branches
+	pub fn last(&self) -> Result<Option<(bool, BranchesActivity)>> {
+		self.job.last()
+	}
+
+	///
+	pub fn is_pending(&self) -> bool {
+		self.job.is_pending()
+	}
+}
@@ -0,0 +1,45 @@
+use crate::{
+	error::Result,
+	sync::{self, CommitId, DescribeOptions, RepoPathRef},
+};
+
+/// `git describe --tags` of HEAD, only redone once HEAD or any tag
+/// moved
+pub struct HeadDescription {
+	last_result: Option<((CommitId, u64), Option<String>)>,
+	repo: RepoPathRef,
+}
+
+impl HeadDescription {
+	///
+	pub const fn new(repo: RepoPathRef) -> Self {
+		Self {
+			repo,
+			last_result: None,
+		}
+	}
+
+	///
+	pub fn lookup(&mut self) -> Result<Option<String>> {
+		let repo = self.repo.borrow().clone();
+		let state =
+			(sync::get_head(&repo)?, sync::get_tags_state(&repo)?);
+
+		if let Some((last_state, description)) =
+			self.last_result.as_ref()
+		{
+			if *last_state == state {
+				return Ok(description.clone());
+			}
+		}
+
+		let description = sync::describe(
+			&repo,
+			state.0,
+			&DescribeOptions::default(),
+		)?;
+		self.last_result = Some((state, description.clone()));
+
+		Ok(description)
+	}
+}
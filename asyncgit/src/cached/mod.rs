@@ -3,5 +3,9 @@
 //! to compute but change seldom so doing them async might be overkill
 
 mod branchname;
+mod head_description;
+mod stash_count;
 
 pub use branchname::BranchName;
+pub use head_description::HeadDescription;
+pub use stash_count::StashCount;
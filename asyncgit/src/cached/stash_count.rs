@@ -0,0 +1,43 @@
+use crate::{
+	error::Result,
+	sync::{self, RepoPathRef},
+};
+use std::{fs, time::SystemTime};
+
+/// number of stashes, only counted again once the reflog of
+/// `refs/stash` they are listed in changed
+pub struct StashCount {
+	last_result: Option<(Option<(u64, SystemTime)>, usize)>,
+	repo: RepoPathRef,
+}
+
+impl StashCount {
+	///
+	pub const fn new(repo: RepoPathRef) -> Self {
+		Self {
+			repo,
+			last_result: None,
+		}
+	}
+
+	///
+	pub fn lookup(&mut self) -> Result<usize> {
+		let repo = self.repo.borrow().clone();
+		let state = fs::metadata(
+			sync::utils::repo_dir(&repo)?.join("logs/refs/stash"),
+		)
+		.and_then(|meta| Ok((meta.len(), meta.modified()?)))
+		.ok();
+
+		if let Some((last_state, count)) = self.last_result {
+			if last_state == state {
+				return Ok(count);
+			}
+		}
+
+		let count = sync::get_stashes(&repo)?.len();
+		self.last_result = Some((state, count));
+
+		Ok(count)
+	}
+}
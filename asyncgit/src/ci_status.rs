@@ -0,0 +1,76 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		ci::{get_ci_status, CiStatus},
+		CommitId, RepoPath,
+	},
+	AsyncGitNotification,
+};
+
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(Vec<CommitId>),
+	Response(Result<Vec<(CommitId, CiStatus)>>),
+}
+
+/// fetches the ci status of commits
+#[derive(Clone)]
+pub struct AsyncCiStatusJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+impl AsyncCiStatusJob {
+	///
+	pub fn new(repo: RepoPath, commits: Vec<CommitId>) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				commits,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(
+		&self,
+	) -> Option<Result<Vec<(CommitId, CiStatus)>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncCiStatusJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(commits) => JobState::Response(
+					get_ci_status(&self.repo, &commits),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::CiStatus)
+	}
+}
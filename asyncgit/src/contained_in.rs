@@ -0,0 +1,64 @@
+use crate::{
+	asyncjob::AsyncQueueJob,
+	error::Result,
+	sync::{self, CommitId, RepoPath},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+
+/// refs whose history includes a commit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContainedIn {
+	///
+	pub branches: Vec<String>,
+	/// oldest first
+	pub tags: Vec<String>,
+}
+
+///
+pub struct AsyncContainedIn {
+	job: AsyncQueueJob<CommitId, ContainedIn, AsyncGitNotification>,
+}
+
+impl AsyncContainedIn {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		Self {
+			job: AsyncQueueJob::new(
+				"contained in",
+				sender.clone(),
+				AsyncGitNotification::ContainedIn,
+				AsyncGitNotification::FinishUnchanged,
+				move |commit: &CommitId, ctx| {
+					let branches =
+						sync::branches_containing(&repo, *commit)?;
+					if ctx.is_cancelled() {
+						return Ok(ContainedIn::default());
+					}
+
+					Ok(ContainedIn {
+						branches,
+						tags: sync::tags_containing(&repo, *commit)?,
+					})
+				},
+			),
+		}
+	}
+
+	/// result for `commit` if it is already known,
+	/// otherwise looks it up in the background
+	pub fn request(
+		&mut self,
+		commit: CommitId,
+	) -> Result<Option<ContainedIn>> {
+		self.job.request(commit)
+	}
+
+	///
+	pub fn is_pending(&self) -> bool {
+		self.job.is_pending()
+	}
+}
@@ -0,0 +1,83 @@
+use crate::{
+	asyncjob::AsyncQueueJob,
+	error::Result,
+	sync::{self, notes, CommitId, DescribeOptions, RepoPath},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct DescribeParams {
+	///
+	pub commit: CommitId,
+	/// `notes::notes_tip`, a note changed once it moved
+	pub notes_tip: Option<CommitId>,
+}
+
+/// what gets shown about a commit besides its details
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitDescription {
+	/// `git describe` of the commit
+	pub describe: Option<String>,
+	/// `git notes` of the commit
+	pub note: Option<String>,
+}
+
+///
+pub struct AsyncDescribe {
+	job: AsyncQueueJob<
+		DescribeParams,
+		CommitDescription,
+		AsyncGitNotification,
+	>,
+}
+
+impl AsyncDescribe {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		Self {
+			job: AsyncQueueJob::new(
+				"describe",
+				sender.clone(),
+				AsyncGitNotification::Describe,
+				AsyncGitNotification::FinishUnchanged,
+				move |params: &DescribeParams, ctx| {
+					// the commit just shows without them if these fail
+					let describe = sync::describe(
+						&repo,
+						params.commit,
+						&DescribeOptions::default(),
+					)
+					.unwrap_or(None);
+					if ctx.is_cancelled() {
+						return Ok(CommitDescription::default());
+					}
+
+					Ok(CommitDescription {
+						describe,
+						note: notes::get_note(&repo, params.commit)
+							.unwrap_or(None),
+					})
+				},
+			),
+		}
+	}
+
+	/// result for `params` if it is already known,
+	/// otherwise looks it up in the background
+	pub fn request(
+		&mut self,
+		params: DescribeParams,
+	) -> Result<Option<CommitDescription>> {
+		self.job.request(params)
+	}
+
+	///
+	pub fn is_pending(&self) -> bool {
+		self.job.is_pending()
+	}
+}
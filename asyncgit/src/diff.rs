@@ -1,17 +1,11 @@
 use crate::{
+	asyncjob::AsyncQueueJob,
 	error::Result,
-	hash,
 	sync::{self, diff::DiffOptions, CommitId, RepoPath},
 	AsyncGitNotification, FileDiff,
 };
 use crossbeam_channel::Sender;
-use std::{
-	hash::Hash,
-	sync::{
-		atomic::{AtomicUsize, Ordering},
-		Arc, Mutex,
-	},
-};
+use std::hash::Hash;
 
 ///
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
@@ -37,21 +31,9 @@ pub struct DiffParams {
 	pub options: DiffOptions,
 }
 
-struct Request<R, A>(R, Option<A>);
-
-#[derive(Default, Clone)]
-struct LastResult<P, R> {
-	params: P,
-	result: R,
-}
-
 ///
 pub struct AsyncDiff {
-	current: Arc<Mutex<Request<u64, FileDiff>>>,
-	last: Arc<Mutex<Option<LastResult<DiffParams, FileDiff>>>>,
-	sender: Sender<AsyncGitNotification>,
-	pending: Arc<AtomicUsize>,
-	repo: RepoPath,
+	job: AsyncQueueJob<DiffParams, FileDiff, AsyncGitNotification>,
 }
 
 impl AsyncDiff {
@@ -61,33 +43,32 @@ impl AsyncDiff {
 		sender: &Sender<AsyncGitNotification>,
 	) -> Self {
 		Self {
-			repo,
-			current: Arc::new(Mutex::new(Request(0, None))),
-			last: Arc::new(Mutex::new(None)),
-			sender: sender.clone(),
-			pending: Arc::new(AtomicUsize::new(0)),
+			job: AsyncQueueJob::new(
+				"diff",
+				sender.clone(),
+				AsyncGitNotification::Diff,
+				AsyncGitNotification::FinishUnchanged,
+				move |params: &DiffParams, ctx| {
+					get_diff(&repo, params, &|| ctx.is_cancelled())
+				},
+			)
+			.with_description(|params| params.path.clone()),
 		}
 	}
 
 	///
 	pub fn last(&mut self) -> Result<Option<(DiffParams, FileDiff)>> {
-		let last = self.last.lock()?;
-
-		Ok(last.clone().map(|res| (res.params, res.result)))
+		self.job.last()
 	}
 
 	///
 	pub fn refresh(&mut self) -> Result<()> {
-		if let Ok(Some(param)) = self.get_last_param() {
-			self.clear_current()?;
-			self.request(param)?;
-		}
-		Ok(())
+		self.job.refresh()
 	}
 
 	///
 	pub fn is_pending(&self) -> bool {
-		self.pending.load(Ordering::Relaxed) > 0
+		self.job.is_pending()
 	}
 
 	///
@@ -97,122 +78,47 @@ impl AsyncDiff {
 	) -> Result<Option<FileDiff>> {
 		log::trace!("request {:?}", params);
 
-		let hash = hash(&params);
-
-		{
-			let mut current = self.current.lock()?;
-
-			if current.0 == hash {
-				return Ok(current.1.clone());
-			}
-
-			current.0 = hash;
-			current.1 = None;
-		}
-
-		let arc_current = Arc::clone(&self.current);
-		let arc_last = Arc::clone(&self.last);
-		let sender = self.sender.clone();
-		let arc_pending = Arc::clone(&self.pending);
-		let repo = self.repo.clone();
-
-		self.pending.fetch_add(1, Ordering::Relaxed);
-
-		rayon_core::spawn(move || {
-			let notify = Self::get_diff_helper(
-				&repo,
-				params,
-				&arc_last,
-				&arc_current,
-				hash,
-			);
-
-			let notify = match notify {
-				Err(err) => {
-					log::error!("get_diff_helper error: {}", err);
-					true
-				}
-				Ok(notify) => notify,
-			};
-
-			arc_pending.fetch_sub(1, Ordering::Relaxed);
-
-			sender
-				.send(if notify {
-					AsyncGitNotification::Diff
-				} else {
-					AsyncGitNotification::FinishUnchanged
-				})
-				.expect("error sending diff");
-		});
-
-		Ok(None)
+		self.job.request(params)
 	}
+}
 
-	fn get_diff_helper(
-		repo_path: &RepoPath,
-		params: DiffParams,
-		arc_last: &Arc<
-			Mutex<Option<LastResult<DiffParams, FileDiff>>>,
-		>,
-		arc_current: &Arc<Mutex<Request<u64, FileDiff>>>,
-		hash: u64,
-	) -> Result<bool> {
-		let res = match params.diff_type {
-			DiffType::Stage => sync::diff::get_diff(
-				repo_path,
-				&params.path,
-				true,
-				Some(params.options),
-			)?,
-			DiffType::WorkDir => sync::diff::get_diff(
-				repo_path,
-				&params.path,
-				false,
-				Some(params.options),
-			)?,
-			DiffType::Commit(id) => sync::diff::get_diff_commit(
+fn get_diff(
+	repo_path: &RepoPath,
+	params: &DiffParams,
+	is_cancelled: &dyn Fn() -> bool,
+) -> Result<FileDiff> {
+	match params.diff_type {
+		DiffType::Stage => sync::diff::get_diff_cancellable(
+			repo_path,
+			&params.path,
+			true,
+			Some(params.options),
+			is_cancelled,
+		),
+		DiffType::WorkDir => sync::diff::get_diff_cancellable(
+			repo_path,
+			&params.path,
+			false,
+			Some(params.options),
+			is_cancelled,
+		),
+		DiffType::Commit(id) => {
+			sync::diff::get_diff_commit_cancellable(
 				repo_path,
 				id,
 				params.path.clone(),
 				Some(params.options),
-			)?,
-			DiffType::Commits(ids) => sync::diff::get_diff_commits(
+				is_cancelled,
+			)
+		}
+		DiffType::Commits(ids) => {
+			sync::diff::get_diff_commits_cancellable(
 				repo_path,
 				ids,
 				params.path.clone(),
 				Some(params.options),
-			)?,
-		};
-
-		let mut notify = false;
-		{
-			let mut current = arc_current.lock()?;
-			if current.0 == hash {
-				current.1 = Some(res.clone());
-				notify = true;
-			}
-		}
-
-		{
-			let mut last = arc_last.lock()?;
-			*last = Some(LastResult {
-				result: res,
-				params,
-			});
+				is_cancelled,
+			)
 		}
-
-		Ok(notify)
-	}
-
-	fn get_last_param(&self) -> Result<Option<DiffParams>> {
-		Ok(self.last.lock()?.clone().map(|e| e.params))
-	}
-
-	fn clear_current(&mut self) -> Result<()> {
-		let mut current = self.current.lock()?;
-		current.0 = 0;
-		current.1 = None;
-		Ok(())
 	}
 }
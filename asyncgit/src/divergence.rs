@@ -0,0 +1,93 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		cred::BasicAuthCredential, divergence_preview, Divergence,
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request {
+		remote: String,
+		branch: String,
+		basic_credential: Option<BasicAuthCredential>,
+	},
+	Response(Result<Divergence>),
+}
+
+/// looks up the remote commits a force push would overwrite, see
+/// `sync::divergence_preview`
+#[derive(Clone)]
+pub struct AsyncDivergenceJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+impl AsyncDivergenceJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		remote: String,
+		branch: String,
+		basic_credential: Option<BasicAuthCredential>,
+	) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				remote,
+				branch,
+				basic_credential,
+			}))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<Divergence>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncDivergenceJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request {
+					remote,
+					branch,
+					basic_credential,
+				} => JobState::Response(divergence_preview(
+					&self.repo,
+					&remote,
+					&branch,
+					basic_credential,
+				)),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Divergence)
+	}
+}
@@ -0,0 +1,109 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		cred::BasicAuthCredential,
+		remotes::dry_run::{
+			fetch_all_dry_run, push_dry_run, RefUpdate,
+		},
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+
+use std::sync::{Arc, Mutex};
+
+/// the operation to preview
+#[derive(Clone, Debug)]
+pub enum DryRun {
+	/// pushing a refspec to a remote
+	Push {
+		///
+		remote: String,
+		///
+		refspec: String,
+	},
+	/// fetching from all remotes
+	Fetch,
+}
+
+enum JobState {
+	Request(DryRun, Option<BasicAuthCredential>),
+	Response(Result<Vec<RefUpdate>>),
+}
+
+/// lists the refs a push or fetch would change
+#[derive(Clone)]
+pub struct AsyncDryRunJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+impl AsyncDryRunJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		dry_run: DryRun,
+		basic_credential: Option<BasicAuthCredential>,
+	) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				dry_run,
+				basic_credential,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<Vec<RefUpdate>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(..) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncDryRunJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(
+					DryRun::Push { remote, refspec },
+					basic_credential,
+				) => JobState::Response(push_dry_run(
+					&self.repo,
+					&remote,
+					&refspec,
+					basic_credential,
+				)),
+				JobState::Request(
+					DryRun::Fetch,
+					basic_credential,
+				) => JobState::Response(fetch_all_dry_run(
+					&self.repo,
+					&basic_credential,
+				)),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::DryRun)
+	}
+}
@@ -73,9 +73,33 @@ pub enum Error {
 	#[error("shellexpand error:{0}")]
 	Shell(#[from] shellexpand::LookupError<std::env::VarError>),
 
+	///
+	#[error("regex error:{0}")]
+	Regex(#[from] regex::Error),
+
 	///
 	#[error("path string error")]
 	PathString,
+
+	///
+	#[error("proxy '{0}' failed: {1}\n(check `http.proxy` and the `https_proxy`/`http_proxy` env vars)")]
+	Proxy(String, String),
+
+	///
+	#[error("TLS certificate verification failed: {0}\n(a custom CA bundle can be configured with `http.sslCAInfo`)")]
+	Tls(String),
+
+	///
+	#[error("remote did not respond for {0}s, giving up\n(the timeout can be configured with `gitui.remoteTimeout`)")]
+	Timeout(u64),
+
+	///
+	#[error("gpg: passphrase required")]
+	SignPassphraseRequired,
+
+	///
+	#[error("gpg: signing failed: {0}")]
+	Sign(String),
 }
 
 ///
@@ -0,0 +1,177 @@
+use crate::{
+	error::{Error, Result},
+	sync::{
+		cred::BasicAuthCredential,
+		remotes::{fetch_all, push::ProgressNotification},
+		RepoPath,
+	},
+	tasks, AsyncGitNotification, RemoteProgress,
+};
+use crossbeam_channel::{unbounded, Sender};
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	thread,
+};
+
+//TODO: since this is empty we can go with a simple AtomicBool to mark that we are fetching or not
+#[derive(Default, Clone, Debug)]
+struct FetchAllState {}
+
+/// fetches all remotes, see `fetch_all`
+pub struct AsyncFetch {
+	state: Arc<Mutex<Option<FetchAllState>>>,
+	last_result: Arc<Mutex<Option<String>>>,
+	progress: Arc<Mutex<Option<ProgressNotification>>>,
+	cancel: Arc<AtomicBool>,
+	sender: Sender<AsyncGitNotification>,
+	repo: RepoPath,
+}
+
+impl AsyncFetch {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(None)),
+			last_result: Arc::new(Mutex::new(None)),
+			progress: Arc::new(Mutex::new(None)),
+			cancel: Arc::new(AtomicBool::new(false)),
+			sender: sender.clone(),
+		}
+	}
+
+	/// aborts the pending request at the next chance the transfer allows
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	/// `true` if the last request was cancelled
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+
+	///
+	pub fn is_pending(&self) -> Result<bool> {
+		let state = self.state.lock()?;
+		Ok(state.is_some())
+	}
+
+	/// the error of the last request, `None` if it succeeded
+	pub fn last_result(&self) -> Result<Option<String>> {
+		let res = self.last_result.lock()?;
+		Ok(res.clone())
+	}
+
+	///
+	pub fn progress(&self) -> Result<Option<RemoteProgress>> {
+		let res = self.progress.lock()?;
+		Ok(res.as_ref().map(|progress| progress.clone().into()))
+	}
+
+	///
+	pub fn request(
+		&mut self,
+		basic_credential: Option<BasicAuthCredential>,
+	) -> Result<()> {
+		log::trace!("request");
+
+		if self.is_pending()? {
+			return Ok(());
+		}
+
+		self.set_request()?;
+		RemoteProgress::set_progress(&self.progress, None)?;
+		self.cancel.store(false, Ordering::Relaxed);
+
+		let arc_state = Arc::clone(&self.state);
+		let arc_res = Arc::clone(&self.last_result);
+		let arc_progress = Arc::clone(&self.progress);
+		let arc_cancel = Arc::clone(&self.cancel);
+		let task = tasks::register(
+			"fetch",
+			String::from("all remotes"),
+			Arc::clone(&self.cancel),
+		);
+		let sender = self.sender.clone();
+		let repo = self.repo.clone();
+
+		thread::spawn(move || {
+			let (progress_sender, receiver) = unbounded();
+
+			let handle = RemoteProgress::spawn_receiver_thread(
+				AsyncGitNotification::Fetch,
+				sender.clone(),
+				receiver,
+				arc_progress,
+			);
+
+			let res = fetch_all(
+				&repo,
+				&basic_credential,
+				&Some(progress_sender.clone()),
+				&Some(arc_cancel),
+			);
+
+			progress_sender
+				.send(ProgressNotification::Done)
+				.expect("closing send failed");
+
+			handle.join().expect("joining thread failed");
+
+			Self::set_result(&arc_res, res).expect("result error");
+
+			Self::clear_request(&arc_state).expect("clear error");
+			drop(task);
+
+			sender
+				.send(AsyncGitNotification::Fetch)
+				.expect("AsyncNotification error");
+		});
+
+		Ok(())
+	}
+
+	fn set_request(&self) -> Result<()> {
+		let mut state = self.state.lock()?;
+
+		if state.is_some() {
+			return Err(Error::Generic("pending request".into()));
+		}
+
+		*state = Some(FetchAllState {});
+		drop(state);
+
+		Ok(())
+	}
+
+	fn clear_request(
+		state: &Arc<Mutex<Option<FetchAllState>>>,
+	) -> Result<()> {
+		*state.lock()? = None;
+
+		Ok(())
+	}
+
+	fn set_result(
+		arc_result: &Arc<Mutex<Option<String>>>,
+		res: Result<()>,
+	) -> Result<()> {
+		let mut last_res = arc_result.lock()?;
+
+		*last_res = match res {
+			Ok(()) => None,
+			Err(e) => {
+				log::error!("fetch error: {}", e);
+				Some(e.to_string())
+			}
+		};
+
+		Ok(())
+	}
+}
@@ -0,0 +1,86 @@
+use crate::{
+	asyncjob::AsyncQueueJob,
+	error::Result,
+	sync::{
+		grep::{grep, GrepOptions, GrepResult, GrepSource},
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use std::sync::Arc;
+
+///
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct GrepParams {
+	///
+	pub source: GrepSource,
+	///
+	pub options: GrepOptions,
+}
+
+///
+pub struct AsyncGrep {
+	job: AsyncQueueJob<
+		GrepParams,
+		Arc<GrepResult>,
+		AsyncGitNotification,
+	>,
+}
+
+impl AsyncGrep {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		Self {
+			job: AsyncQueueJob::new(
+				"search",
+				sender.clone(),
+				AsyncGitNotification::Grep,
+				AsyncGitNotification::FinishUnchanged,
+				move |params: &GrepParams, ctx| {
+					grep(
+						&repo,
+						params.source,
+						&params.options,
+						&|| ctx.is_cancelled(),
+					)
+					.map(Arc::new)
+				},
+			)
+			.with_description(|params| {
+				params.options.pattern.clone()
+			}),
+		}
+	}
+
+	/// last finished search
+	pub fn last(
+		&self,
+	) -> Result<Option<(GrepParams, Arc<GrepResult>)>> {
+		self.job.last()
+	}
+
+	///
+	pub fn is_pending(&self) -> bool {
+		self.job.is_pending()
+	}
+
+	/// starts searching, even if `params` were searched before
+	/// since the workdir might have changed since
+	pub fn request(&mut self, params: GrepParams) -> Result<()> {
+		log::trace!("request {:?}", params);
+
+		self.job.cancel()?;
+		self.job.request(params)?;
+
+		Ok(())
+	}
+
+	/// stops a running search
+	pub fn cancel(&mut self) -> Result<()> {
+		self.job.cancel()
+	}
+}
@@ -0,0 +1,83 @@
+use crate::{
+	error::Result,
+	sync::{hooks_post, HookOutput, PostHook, RepoPath},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use std::{
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+	thread,
+};
+
+/// runs post-* hooks in the background, each one to completion
+pub struct AsyncHooks {
+	finished: Arc<Mutex<Vec<HookOutput>>>,
+	running: Arc<AtomicUsize>,
+	sender: Sender<AsyncGitNotification>,
+	repo: RepoPath,
+}
+
+impl AsyncHooks {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		Self {
+			repo,
+			finished: Arc::new(Mutex::new(Vec::new())),
+			running: Arc::new(AtomicUsize::new(0)),
+			sender: sender.clone(),
+		}
+	}
+
+	///
+	pub fn is_pending(&self) -> bool {
+		self.running.load(Ordering::Relaxed) > 0
+	}
+
+	/// hooks that finished since the last call, oldest first
+	pub fn take_finished(&self) -> Result<Vec<HookOutput>> {
+		let mut finished = self.finished.lock()?;
+		Ok(std::mem::take(&mut *finished))
+	}
+
+	/// starts `hook` unless the repo does not have it, hooks
+	/// started in a row do not wait for each other
+	pub fn run(&self, hook: PostHook) {
+		log::trace!("run hook: {}", hook.name());
+
+		let finished = Arc::clone(&self.finished);
+		let running = Arc::clone(&self.running);
+		let sender = self.sender.clone();
+		let repo = self.repo.clone();
+
+		running.fetch_add(1, Ordering::Relaxed);
+
+		thread::spawn(move || {
+			let output =
+				hooks_post(&repo, &hook).unwrap_or_else(|e| {
+					Some(HookOutput {
+						hook: hook.name(),
+						success: false,
+						output: e.to_string(),
+					})
+				});
+
+			if let Some(output) = output {
+				if let Ok(mut finished) = finished.lock() {
+					finished.push(output);
+				}
+			}
+
+			running.fetch_sub(1, Ordering::Relaxed);
+
+			sender
+				.send(AsyncGitNotification::Hooks)
+				.expect("error sending hooks");
+		});
+	}
+}
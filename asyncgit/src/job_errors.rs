@@ -0,0 +1,97 @@
+//! errors of background jobs, kept until the ui picks them up
+
+use once_cell::sync::Lazy;
+use std::{
+	fmt,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Mutex,
+	},
+};
+
+/// errors nobody picked up get dropped beyond this
+const MAX_ERRORS: usize = 32;
+
+static ERRORS: Lazy<Mutex<Vec<(ErrorId, JobError)>>> =
+	Lazy::new(|| Mutex::new(Vec::new()));
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// identifies a reported error, see `take`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ErrorId(usize);
+
+/// error a background job failed with
+#[derive(Clone, Debug)]
+pub struct JobError {
+	/// kind of job (e.g. `diff`)
+	pub kind: &'static str,
+	/// what the job was working on (e.g. the file path)
+	pub context: String,
+	///
+	pub message: String,
+}
+
+impl fmt::Display for JobError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.context.is_empty() {
+			write!(f, "{} failed: {}", self.kind, self.message)
+		} else {
+			write!(
+				f,
+				"{} failed ({}): {}",
+				self.kind, self.context, self.message
+			)
+		}
+	}
+}
+
+/// stores the error for the ui to `take` once it
+/// received the id (via `AsyncGitNotification::Error`)
+pub fn report(
+	kind: &'static str,
+	context: String,
+	err: &dyn fmt::Display,
+) -> ErrorId {
+	let id = ErrorId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+	let error = JobError {
+		kind,
+		context,
+		message: err.to_string(),
+	};
+
+	log::error!("{}", error);
+
+	if let Ok(mut errors) = ERRORS.lock() {
+		errors.push((id, error));
+		let overflow = errors.len().saturating_sub(MAX_ERRORS);
+		errors.drain(..overflow);
+	}
+
+	id
+}
+
+/// removes error `id` from the registry
+pub fn take(id: ErrorId) -> Option<JobError> {
+	let mut errors = ERRORS.lock().ok()?;
+	let idx =
+		errors.iter().position(|(error_id, _)| *error_id == id)?;
+	Some(errors.remove(idx).1)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_report_take() {
+		let id =
+			report("diff", String::from("foo.txt"), &"not found");
+
+		let error = take(id).unwrap();
+		assert_eq!(
+			error.to_string(),
+			"diff failed (foo.txt): not found"
+		);
+		assert!(take(id).is_none());
+	}
+}
@@ -24,28 +24,47 @@
 
 pub mod asyncjob;
 mod blame;
+mod branch_activity;
 pub mod cached;
+pub mod ci_status;
 mod commit_files;
+mod contained_in;
+mod describe;
 mod diff;
+pub mod divergence;
+pub mod dry_run;
 mod error;
-mod fetch_job;
+mod fetch;
+mod grep;
+mod hooks;
+pub mod issues;
+pub mod job_errors;
+pub mod maintenance;
 mod progress;
 mod pull;
+pub mod pull_requests;
 mod push;
 mod push_tags;
 pub mod remote_progress;
 pub mod remote_tags;
+pub mod repo_stats;
 mod revlog;
 mod status;
 pub mod sync;
 mod tags;
+pub mod tasks;
 
 pub use crate::{
 	blame::{AsyncBlame, BlameParams},
+	branch_activity::{AsyncBranchActivity, BranchesActivity},
 	commit_files::{AsyncCommitFiles, CommitFilesParams},
+	contained_in::{AsyncContainedIn, ContainedIn},
+	describe::{AsyncDescribe, CommitDescription, DescribeParams},
 	diff::{AsyncDiff, DiffParams, DiffType},
 	error::{Error, Result},
-	fetch_job::AsyncFetchJob,
+	fetch::AsyncFetch,
+	grep::{AsyncGrep, GrepParams},
+	hooks::AsyncHooks,
 	progress::ProgressPercent,
 	pull::{AsyncPull, FetchRequest},
 	push::{AsyncPush, PushRequest},
@@ -95,6 +114,38 @@ pub enum AsyncGitNotification {
 	RemoteTags,
 	///
 	Fetch,
+	///
+	PullRequests,
+	///
+	Issues,
+	///
+	CiStatus,
+	///
+	DryRun,
+	///
+	Divergence,
+	///
+	RepoStats,
+	/// a maintenance task finished or reached its next step
+	Maintenance,
+	///
+	Grep,
+	///
+	ContainedIn,
+	///
+	Describe,
+	///
+	BranchActivity,
+	/// a post-* hook finished, see `AsyncHooks::take_finished`
+	Hooks,
+	/// a background job failed, details via `job_errors::take`
+	Error(job_errors::ErrorId),
+}
+
+impl From<job_errors::ErrorId> for AsyncGitNotification {
+	fn from(id: job_errors::ErrorId) -> Self {
+		Self::Error(id)
+	}
 }
 
 /// helper function to calculate the hash of an arbitrary type that implements the `Hash` trait
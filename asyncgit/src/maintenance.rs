@@ -0,0 +1,86 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		run_maintenance, MaintenanceProgress, MaintenanceTask,
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(MaintenanceTask),
+	Response(Result<()>),
+}
+
+/// runs a `MaintenanceTask` in the background, notifying
+/// `Maintenance` with each step
+#[derive(Clone)]
+pub struct AsyncMaintenanceJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+impl AsyncMaintenanceJob {
+	///
+	pub fn new(repo: RepoPath, task: MaintenanceTask) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				task,
+			)))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<()>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncMaintenanceJob {
+	type Notification = AsyncGitNotification;
+	type Progress = MaintenanceProgress;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state =
+				state.take().map(|state| match state {
+					JobState::Request(task) => {
+						JobState::Response(run_maintenance(
+							&self.repo,
+							task,
+							|progress| {
+								// a missed step only delays the gauge
+								params.set_progress(progress).ok();
+								params
+								.send(AsyncGitNotification::Maintenance)
+								.ok();
+							},
+						))
+					}
+					JobState::Response(result) => {
+						JobState::Response(result)
+					}
+				});
+		}
+
+		Ok(AsyncGitNotification::Maintenance)
+	}
+}
@@ -5,11 +5,14 @@ use crate::{
 		remotes::{fetch, push::ProgressNotification},
 		RepoPath,
 	},
-	AsyncGitNotification, RemoteProgress,
+	tasks, AsyncGitNotification, RemoteProgress,
 };
 use crossbeam_channel::{unbounded, Sender};
 use std::{
-	sync::{Arc, Mutex},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
 	thread,
 };
 
@@ -33,6 +36,7 @@ pub struct AsyncPull {
 	state: Arc<Mutex<Option<FetchState>>>,
 	last_result: Arc<Mutex<Option<(usize, String)>>>,
 	progress: Arc<Mutex<Option<ProgressNotification>>>,
+	cancel: Arc<AtomicBool>,
 	sender: Sender<AsyncGitNotification>,
 	repo: RepoPath,
 }
@@ -48,10 +52,21 @@ impl AsyncPull {
 			state: Arc::new(Mutex::new(None)),
 			last_result: Arc::new(Mutex::new(None)),
 			progress: Arc::new(Mutex::new(None)),
+			cancel: Arc::new(AtomicBool::new(false)),
 			sender: sender.clone(),
 		}
 	}
 
+	/// aborts the pending request at the next chance the transfer allows
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	/// `true` if the last request was cancelled
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+
 	///
 	pub fn is_pending(&self) -> Result<bool> {
 		let state = self.state.lock()?;
@@ -80,10 +95,17 @@ impl AsyncPull {
 
 		self.set_request(&params)?;
 		RemoteProgress::set_progress(&self.progress, None)?;
+		self.cancel.store(false, Ordering::Relaxed);
 
 		let arc_state = Arc::clone(&self.state);
 		let arc_res = Arc::clone(&self.last_result);
 		let arc_progress = Arc::clone(&self.progress);
+		let arc_cancel = Arc::clone(&self.cancel);
+		let task = tasks::register(
+			"fetch",
+			params.branch.clone(),
+			Arc::clone(&self.cancel),
+		);
 		let sender = self.sender.clone();
 		let repo = self.repo.clone();
 
@@ -102,6 +124,7 @@ impl AsyncPull {
 				&params.branch,
 				params.basic_credential,
 				Some(progress_sender.clone()),
+				Some(arc_cancel),
 			);
 
 			progress_sender
@@ -113,6 +136,7 @@ impl AsyncPull {
 			Self::set_result(&arc_res, res).expect("result error");
 
 			Self::clear_request(&arc_state).expect("clear error");
+			drop(task);
 
 			sender
 				.send(AsyncGitNotification::Pull)
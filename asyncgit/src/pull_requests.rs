@@ -0,0 +1,72 @@
+//!
+
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		github::{get_pull_requests, PullRequest},
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request,
+	Response(Result<Vec<PullRequest>>),
+}
+
+/// fetches the open pull requests of the default remote
+#[derive(Clone)]
+pub struct AsyncPullRequestsJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	repo: RepoPath,
+}
+
+impl AsyncPullRequestsJob {
+	///
+	pub fn new(repo: RepoPath) -> Self {
+		Self {
+			repo,
+			state: Arc::new(Mutex::new(Some(JobState::Request))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<Vec<PullRequest>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncPullRequestsJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request => {
+					JobState::Response(get_pull_requests(&self.repo))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::PullRequests)
+	}
+}
@@ -2,15 +2,19 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		cred::BasicAuthCredential,
-		remotes::push::push_raw,
+		remotes::push::{push_branch_leased, push_raw, push_refspec},
 		remotes::push::{ProgressNotification, PushType},
-		RepoPath,
+		remotes::push_cli::{push_branch_cli, PushCliOptions},
+		rename_remote_branch, CommitId, PushSummary, RepoPath,
 	},
-	AsyncGitNotification, RemoteProgress,
+	tasks, AsyncGitNotification, RemoteProgress,
 };
 use crossbeam_channel::{unbounded, Sender};
 use std::{
-	sync::{Arc, Mutex},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
 	thread,
 };
 
@@ -29,6 +33,35 @@ pub struct PushRequest {
 	pub delete: bool,
 	///
 	pub basic_credential: Option<BasicAuthCredential>,
+	/// explicit refspec to push instead of `branch`
+	pub refspec: Option<String>,
+	/// the name `branch` had on `remote` before being renamed
+	/// locally, see `rename_remote_branch`
+	pub rename_from: Option<String>,
+	/// force push `branch` only if it is still at this tip on
+	/// `remote`, see `push_branch_leased`
+	pub lease: Option<CommitId>,
+	/// push via the `git` cli if `PushCliOptions::needs_cli`
+	pub cli_options: PushCliOptions,
+}
+
+impl PushRequest {
+	/// git asks the credential helpers itself and only gets a plain
+	/// push of `branch` handed
+	fn check_cli(&self) -> Result<()> {
+		if self.cli_options.needs_cli()
+			&& (self.basic_credential.is_some()
+				|| self.refspec.is_some()
+				|| self.rename_from.is_some()
+				|| self.lease.is_some())
+		{
+			return Err(Error::Generic(String::from(
+				"push options and signed pushes only support plain pushes with credentials from a git credential helper",
+			)));
+		}
+
+		Ok(())
+	}
 }
 
 //TODO: since this is empty we can go with a simple AtomicBool to mark that we are fetching or not
@@ -39,7 +72,9 @@ struct PushState {}
 pub struct AsyncPush {
 	state: Arc<Mutex<Option<PushState>>>,
 	last_result: Arc<Mutex<Option<String>>>,
+	last_summary: Arc<Mutex<Option<PushSummary>>>,
 	progress: Arc<Mutex<Option<ProgressNotification>>>,
+	cancel: Arc<AtomicBool>,
 	sender: Sender<AsyncGitNotification>,
 	repo: RepoPath,
 }
@@ -54,11 +89,23 @@ impl AsyncPush {
 			repo,
 			state: Arc::new(Mutex::new(None)),
 			last_result: Arc::new(Mutex::new(None)),
+			last_summary: Arc::new(Mutex::new(None)),
 			progress: Arc::new(Mutex::new(None)),
+			cancel: Arc::new(AtomicBool::new(false)),
 			sender: sender.clone(),
 		}
 	}
 
+	/// aborts the pending request at the next chance the transfer allows
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	/// `true` if the last request was cancelled
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+
 	///
 	pub fn is_pending(&self) -> Result<bool> {
 		let state = self.state.lock()?;
@@ -71,6 +118,12 @@ impl AsyncPush {
 		Ok(res.clone())
 	}
 
+	/// what the last successful request did
+	pub fn last_summary(&self) -> Result<Option<PushSummary>> {
+		let res = self.last_summary.lock()?;
+		Ok(res.clone())
+	}
+
 	///
 	pub fn progress(&self) -> Result<Option<RemoteProgress>> {
 		let res = self.progress.lock()?;
@@ -85,12 +138,26 @@ impl AsyncPush {
 			return Ok(());
 		}
 
+		params.check_cli()?;
+
 		self.set_request(&params)?;
 		RemoteProgress::set_progress(&self.progress, None)?;
+		self.cancel.store(false, Ordering::Relaxed);
 
 		let arc_state = Arc::clone(&self.state);
 		let arc_res = Arc::clone(&self.last_result);
+		let arc_summary = Arc::clone(&self.last_summary);
 		let arc_progress = Arc::clone(&self.progress);
+		let arc_cancel = Arc::clone(&self.cancel);
+		let task = tasks::register(
+			"push",
+			format!(
+				"{}/{}",
+				params.remote,
+				params.refspec.as_ref().unwrap_or(&params.branch)
+			),
+			Arc::clone(&self.cancel),
+		);
 		let sender = self.sender.clone();
 		let repo = self.repo.clone();
 
@@ -104,16 +171,59 @@ impl AsyncPush {
 				arc_progress,
 			);
 
-			let res = push_raw(
-				&repo,
-				params.remote.as_str(),
-				params.branch.as_str(),
-				params.push_type,
-				params.force,
-				params.delete,
-				params.basic_credential.clone(),
-				Some(progress_sender.clone()),
-			);
+			let res = if let Some(old) = &params.rename_from {
+				rename_remote_branch(
+					&repo,
+					params.remote.as_str(),
+					old,
+					params.branch.as_str(),
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+					Some(arc_cancel),
+				)
+			} else if let Some(expected) = params.lease {
+				push_branch_leased(
+					&repo,
+					params.remote.as_str(),
+					params.branch.as_str(),
+					expected,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+					Some(arc_cancel),
+				)
+			} else if params.cli_options.needs_cli() {
+				push_branch_cli(
+					&repo,
+					params.remote.as_str(),
+					params.branch.as_str(),
+					params.push_type,
+					params.force,
+					params.delete,
+					&params.cli_options,
+					Some(&arc_cancel),
+				)
+			} else if let Some(refspec) = &params.refspec {
+				push_refspec(
+					&repo,
+					params.remote.as_str(),
+					refspec,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+					Some(arc_cancel),
+				)
+			} else {
+				push_raw(
+					&repo,
+					params.remote.as_str(),
+					params.branch.as_str(),
+					params.push_type,
+					params.force,
+					params.delete,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+					Some(arc_cancel),
+				)
+			};
 
 			progress_sender
 				.send(ProgressNotification::Done)
@@ -121,9 +231,11 @@ impl AsyncPush {
 
 			handle.join().expect("joining thread failed");
 
-			Self::set_result(&arc_res, res).expect("result error");
+			Self::set_result(&arc_res, &arc_summary, res)
+				.expect("result error");
 
 			Self::clear_request(&arc_state).expect("clear error");
+			drop(task);
 
 			sender
 				.send(AsyncGitNotification::Push)
@@ -157,18 +269,20 @@ impl AsyncPush {
 
 	fn set_result(
 		arc_result: &Arc<Mutex<Option<String>>>,
-		res: Result<()>,
+		arc_summary: &Arc<Mutex<Option<PushSummary>>>,
+		res: Result<PushSummary>,
 	) -> Result<()> {
-		let mut last_res = arc_result.lock()?;
-
-		*last_res = match res {
-			Ok(_) => None,
+		let (last_res, last_summary) = match res {
+			Ok(summary) => (None, Some(summary)),
 			Err(e) => {
 				log::error!("push error: {}", e);
-				Some(e.to_string())
+				(Some(e.to_string()), None)
 			}
 		};
 
+		*arc_result.lock()? = last_res;
+		*arc_summary.lock()? = last_summary;
+
 		Ok(())
 	}
 }
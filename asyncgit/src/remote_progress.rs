@@ -24,6 +24,8 @@ pub enum RemoteProgressState {
 	Pushing,
 	/// fetch progress
 	Transfer,
+	/// fetch is resolving deltas of the received objects
+	Resolving,
 	/// remote progress done
 	Done,
 }
@@ -35,6 +37,8 @@ pub struct RemoteProgress {
 	pub state: RemoteProgressState,
 	///
 	pub progress: ProgressPercent,
+	/// bytes transferred so far
+	pub bytes: usize,
 }
 
 impl RemoteProgress {
@@ -47,9 +51,16 @@ impl RemoteProgress {
 		Self {
 			state,
 			progress: ProgressPercent::new(current, total),
+			bytes: 0,
 		}
 	}
 
+	#[must_use]
+	const fn with_bytes(mut self, bytes: usize) -> Self {
+		self.bytes = bytes;
+		self
+	}
+
 	///
 	pub const fn get_progress_percent(&self) -> u8 {
 		self.progress.progress
@@ -128,21 +139,33 @@ impl From<ProgressNotification> for RemoteProgress {
 			ProgressNotification::PushTransfer {
 				current,
 				total,
-				..
+				bytes,
 			} => Self::new(
 				RemoteProgressState::Pushing,
 				current,
 				total,
-			),
+			)
+			.with_bytes(bytes),
 			ProgressNotification::Transfer {
 				objects,
 				total_objects,
-				..
-			} => Self::new(
-				RemoteProgressState::Transfer,
-				objects,
-				total_objects,
-			),
+				bytes,
+				indexed_deltas,
+				total_deltas,
+			} => if objects == total_objects && total_deltas > 0 {
+				Self::new(
+					RemoteProgressState::Resolving,
+					indexed_deltas,
+					total_deltas,
+				)
+			} else {
+				Self::new(
+					RemoteProgressState::Transfer,
+					objects,
+					total_objects,
+				)
+			}
+			.with_bytes(bytes),
 			_ => Self::new(RemoteProgressState::Done, 1, 1),
 		}
 	}
@@ -1,27 +1,14 @@
 use crate::{
+	asyncjob::AsyncQueueJob,
 	error::Result,
-	hash,
 	sync::{
 		self, status::StatusType, RepoPath, ShowUntrackedFilesConfig,
+		StatusOrder,
 	},
 	AsyncGitNotification, StatusItem,
 };
 use crossbeam_channel::Sender;
-use std::{
-	hash::Hash,
-	sync::{
-		atomic::{AtomicUsize, Ordering},
-		Arc, Mutex,
-	},
-	time::{SystemTime, UNIX_EPOCH},
-};
-
-fn current_tick() -> u128 {
-	SystemTime::now()
-		.duration_since(UNIX_EPOCH)
-		.expect("time before unix epoch!")
-		.as_millis()
-}
+use std::hash::Hash;
 
 #[derive(Default, Hash, Clone)]
 pub struct Status {
@@ -31,9 +18,9 @@ pub struct Status {
 ///
 #[derive(Default, Hash, Copy, Clone, PartialEq, Eq)]
 pub struct StatusParams {
-	tick: u128,
 	status_type: StatusType,
 	config: Option<ShowUntrackedFilesConfig>,
+	order: StatusOrder,
 }
 
 impl StatusParams {
@@ -43,22 +30,22 @@ impl StatusParams {
 		config: Option<ShowUntrackedFilesConfig>,
 	) -> Self {
 		Self {
-			tick: current_tick(),
 			status_type,
 			config,
+			order: StatusOrder::default(),
 		}
 	}
-}
 
-struct Request<R, A>(R, Option<A>);
+	/// sorts the items, see `sync::sort_status`
+	#[must_use]
+	pub const fn order(self, order: StatusOrder) -> Self {
+		Self { order, ..self }
+	}
+}
 
 ///
 pub struct AsyncStatus {
-	current: Arc<Mutex<Request<u64, Status>>>,
-	last: Arc<Mutex<Status>>,
-	sender: Sender<AsyncGitNotification>,
-	pending: Arc<AtomicUsize>,
-	repo: RepoPath,
+	job: AsyncQueueJob<StatusParams, Status, AsyncGitNotification>,
 }
 
 impl AsyncStatus {
@@ -68,23 +55,30 @@ impl AsyncStatus {
 		sender: Sender<AsyncGitNotification>,
 	) -> Self {
 		Self {
-			repo,
-			current: Arc::new(Mutex::new(Request(0, None))),
-			last: Arc::new(Mutex::new(Status::default())),
-			sender,
-			pending: Arc::new(AtomicUsize::new(0)),
+			job: AsyncQueueJob::new(
+				"status",
+				sender,
+				AsyncGitNotification::Status,
+				AsyncGitNotification::Status,
+				move |params: &StatusParams, ctx| {
+					get_status(&repo, *params, || ctx.is_cancelled())
+				},
+			),
 		}
 	}
 
 	///
 	pub fn last(&mut self) -> Result<Status> {
-		let last = self.last.lock()?;
-		Ok(last.clone())
+		Ok(self
+			.job
+			.last()?
+			.map(|(_, status)| status)
+			.unwrap_or_default())
 	}
 
 	///
 	pub fn is_pending(&self) -> bool {
-		self.pending.load(Ordering::Relaxed) > 0
+		self.job.is_pending()
 	}
 
 	///
@@ -97,98 +91,42 @@ impl AsyncStatus {
 			return Ok(None);
 		}
 
-		let hash_request = hash(&params);
-
-		log::trace!(
-			"request: [hash: {}] (type: {:?})",
-			hash_request,
-			params.status_type,
-		);
-
-		{
-			let mut current = self.current.lock()?;
-
-			if current.0 == hash_request {
-				return Ok(current.1.clone());
-			}
-
-			current.0 = hash_request;
-			current.1 = None;
-		}
+		log::trace!("request: (type: {:?})", params.status_type);
 
-		let arc_current = Arc::clone(&self.current);
-		let arc_last = Arc::clone(&self.last);
-		let sender = self.sender.clone();
-		let arc_pending = Arc::clone(&self.pending);
-		let status_type = params.status_type;
-		let config = params.config;
-		let repo = self.repo.clone();
-
-		self.pending.fetch_add(1, Ordering::Relaxed);
-
-		rayon_core::spawn(move || {
-			if let Err(e) = Self::fetch_helper(
-				&repo,
-				status_type,
-				config,
-				hash_request,
-				&arc_current,
-				&arc_last,
-			) {
-				log::error!("fetch_helper: {}", e);
-			}
-
-			arc_pending.fetch_sub(1, Ordering::Relaxed);
-
-			sender
-				.send(AsyncGitNotification::Status)
-				.expect("error sending status");
-		});
+		// the worktree may have changed since, always fetch again
+		self.job.rerun(*params)?;
 
 		Ok(None)
 	}
+}
 
-	fn fetch_helper(
-		repo: &RepoPath,
-		status_type: StatusType,
-		config: Option<ShowUntrackedFilesConfig>,
-		hash_request: u64,
-		arc_current: &Arc<Mutex<Request<u64, Status>>>,
-		arc_last: &Arc<Mutex<Status>>,
-	) -> Result<()> {
-		let res = Self::get_status(repo, status_type, config)?;
-		log::trace!(
-			"status fetched: {} (type: {:?})",
-			hash_request,
-			status_type,
-		);
-
-		{
-			let mut current = arc_current.lock()?;
-			if current.0 == hash_request {
-				current.1 = Some(res.clone());
-			}
-		}
+fn get_status(
+	repo: &RepoPath,
+	params: StatusParams,
+	is_cancelled: impl Fn() -> bool,
+) -> Result<Status> {
+	let mut items = sync::status::get_status(
+		repo,
+		params.status_type,
+		params.config,
+	)?;
+
+	if is_cancelled() {
+		return Ok(Status::default());
+	}
 
-		{
-			let mut last = arc_last.lock()?;
-			*last = res;
-		}
+	sync::sort_status(
+		repo,
+		&mut items,
+		params.status_type,
+		params.order,
+	)?;
 
-		Ok(())
-	}
+	log::trace!(
+		"status fetched: {} (type: {:?})",
+		items.len(),
+		params.status_type,
+	);
 
-	fn get_status(
-		repo: &RepoPath,
-		status_type: StatusType,
-		config: Option<ShowUntrackedFilesConfig>,
-	) -> Result<Status> {
-		Ok(Status {
-			items: sync::status::get_status(
-				repo,
-				status_type,
-				config,
-			)?,
-		})
-	}
+	Ok(Status { items })
 }
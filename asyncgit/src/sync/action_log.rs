@@ -0,0 +1,153 @@
+//! audit trail of the git operations gitui performed, one line per
+//! operation (`time<TAB>action<TAB>details`) appended to
+//! `gitui_actions.log` in the git dir
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use git2::Repository;
+use scopetime::scope_time;
+use std::{
+	fs::{self, OpenOptions},
+	io::{ErrorKind, Write},
+	path::PathBuf,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+const LOG_FILE: &str = "gitui_actions.log";
+
+/// a performed operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionLogEntry {
+	/// seconds since the epoch
+	pub time: i64,
+	/// what was done, like `commit` or `stage`
+	pub action: String,
+	/// the parameters, like paths, branch names or commit ids
+	pub details: String,
+}
+
+fn log_file(repo: &Repository) -> PathBuf {
+	repo.path().join(LOG_FILE)
+}
+
+/// appends `action` with `details` to the log of `repo`, a log that
+/// can't be written is only reported in the debug log so it never
+/// fails the operation itself
+pub fn log_action(
+	repo: &Repository,
+	action: &str,
+	details: &str,
+) {
+	let time = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|time| time.as_secs())
+		.unwrap_or_default();
+
+	let line = format!(
+		"{}\t{}\t{}\n",
+		time,
+		action,
+		details.replace(['\t', '\n', '\r'], " ")
+	);
+
+	let res = OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(log_file(repo))
+		.and_then(|mut file| file.write_all(line.as_bytes()));
+
+	if let Err(e) = res {
+		log::error!("action log error: {}", e);
+	}
+}
+
+/// short id and subject of a commit as logged
+pub fn commit_details(id: CommitId, msg: &str) -> String {
+	format!(
+		"{} {}",
+		id.get_short_string(),
+		msg.lines().next().unwrap_or_default()
+	)
+}
+
+/// all logged operations of the repository, newest first
+pub fn action_log(
+	repo_path: &RepoPath,
+) -> Result<Vec<ActionLogEntry>> {
+	scope_time!("action_log");
+
+	let repo = repo(repo_path)?;
+
+	let content = match fs::read_to_string(log_file(&repo)) {
+		Ok(content) => content,
+		Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(content.lines().rev().filter_map(parse_line).collect())
+}
+
+fn parse_line(line: &str) -> Option<ActionLogEntry> {
+	let mut parts = line.splitn(3, '\t');
+
+	Some(ActionLogEntry {
+		time: parts.next()?.parse().ok()?,
+		action: parts.next()?.to_string(),
+		details: parts.next().unwrap_or_default().to_string(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit, stage_add_file,
+		tests::repo_init,
+		utils::{get_head_repo, repo_write_file},
+	};
+	use std::path::Path;
+
+	#[test]
+	fn test_action_log() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(action_log(repo_path).unwrap().is_empty());
+
+		repo_write_file(&repo, "a.txt", "a").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		commit(repo_path, "c1\n\nbody").unwrap();
+
+		let log = action_log(repo_path).unwrap();
+
+		assert_eq!(
+			log.iter()
+				.map(|entry| entry.action.as_str())
+				.collect::<Vec<_>>(),
+			vec!["commit", "stage"]
+		);
+		assert_eq!(log[1].details, "a.txt");
+		assert_eq!(
+			log[0].details,
+			format!(
+				"{} c1",
+				get_head_repo(&repo).unwrap().get_short_string()
+			)
+		);
+	}
+
+	#[test]
+	fn test_parse_line() {
+		assert_eq!(
+			parse_line("12\tpush\torigin master\textra"),
+			Some(ActionLogEntry {
+				time: 12,
+				action: String::from("push"),
+				details: String::from("origin master\textra"),
+			})
+		);
+		assert_eq!(parse_line("garbage"), None);
+	}
+}
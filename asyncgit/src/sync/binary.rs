@@ -0,0 +1,126 @@
+//! what a diff can tell about binary files
+
+/// enough bytes for all the signatures in `file_type`
+pub const SIGNATURE_LEN: u64 = 16;
+
+/// one side of a binary change
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct BinaryFile {
+	/// blob id, hashed from the file for the workdir side
+	pub id: String,
+	/// size in bytes
+	pub size: u64,
+	/// detected from the first bytes, e.g. `PNG image`
+	pub file_type: Option<&'static str>,
+}
+
+impl BinaryFile {
+	/// id shortened like a commit hash
+	pub fn short_id(&self) -> &str {
+		self.id.get(..7).unwrap_or(&self.id)
+	}
+}
+
+/// summary of a binary change, diffs of binary files have no hunks
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
+pub struct BinaryDiff {
+	/// `None` if the file was added
+	pub old: Option<BinaryFile>,
+	/// `None` if the file was deleted
+	pub new: Option<BinaryFile>,
+}
+
+impl BinaryDiff {
+	/// size delta in bytes
+	pub fn size_delta(&self) -> i64 {
+		let size = |file: &Option<BinaryFile>| {
+			file.as_ref().map_or(0, |file| {
+				i64::try_from(file.size).unwrap_or(i64::MAX)
+			})
+		};
+
+		size(&self.new).saturating_sub(size(&self.old))
+	}
+}
+
+/// file type by the magic number at the start of `content`
+pub fn file_type(content: &[u8]) -> Option<&'static str> {
+	const SIGNATURES: &[(&[u8], &str)] = &[
+		(b"\x89PNG\r\n\x1a\n", "PNG image"),
+		(b"\xff\xd8\xff", "JPEG image"),
+		(b"GIF87a", "GIF image"),
+		(b"GIF89a", "GIF image"),
+		(b"%PDF-", "PDF document"),
+		(b"PK\x03\x04", "ZIP archive"),
+		(b"\x1f\x8b", "gzip archive"),
+		(b"7z\xbc\xaf\x27\x1c", "7-Zip archive"),
+		(b"\x7fELF", "ELF executable"),
+		(b"MZ", "Windows executable"),
+		(b"\xcf\xfa\xed\xfe", "Mach-O executable"),
+		(b"\0asm", "WebAssembly module"),
+		(b"SQLite format 3\0", "SQLite database"),
+		(b"OggS", "Ogg media"),
+		(b"ID3", "MP3 audio"),
+	];
+
+	if content.get(4..8) == Some(b"ftyp") {
+		return Some("MP4 media");
+	}
+	if content.starts_with(b"RIFF")
+		&& content.get(8..12) == Some(b"WEBP")
+	{
+		return Some("WebP image");
+	}
+
+	SIGNATURES
+		.iter()
+		.find(|(magic, _)| content.starts_with(magic))
+		.map(|(_, file_type)| *file_type)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_file_type() {
+		assert_eq!(
+			file_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+			Some("PNG image")
+		);
+		assert_eq!(
+			file_type(b"\0\0\0\x18ftypmp42"),
+			Some("MP4 media")
+		);
+		assert_eq!(
+			file_type(b"RIFF\0\0\0\0WEBPVP8 "),
+			Some("WebP image")
+		);
+		assert_eq!(file_type(b"\0\x01\x02"), None);
+		assert_eq!(file_type(b""), None);
+	}
+
+	#[test]
+	fn test_size_delta() {
+		let file = |size| BinaryFile {
+			id: String::from(
+				"e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+			),
+			size,
+			file_type: None,
+		};
+
+		let diff = BinaryDiff {
+			old: Some(file(10)),
+			new: Some(file(4)),
+		};
+		assert_eq!(diff.size_delta(), -6);
+		assert_eq!(diff.old.unwrap().short_id(), "e69de29");
+
+		let diff = BinaryDiff {
+			old: None,
+			new: Some(file(4)),
+		};
+		assert_eq!(diff.size_delta(), 4);
+	}
+}
@@ -3,13 +3,27 @@
 use super::{utils, CommitId, RepoPath};
 use crate::{
 	error::{Error, Result},
-	sync::{get_commits_info, repository::repo},
+	sync::{
+		config::get_config_string_repo, get_commits_info,
+		repository::repo,
+	},
 };
-use git2::BlameOptions;
+use easy_cast::Conv;
+use git2::{Blame, BlameOptions, Oid, Patch, Repository};
 use scopetime::scope_time;
 use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+/// default file name used to list revisions to skip in blames
+const IGNORE_REVS_FILE: &str = ".git-blame-ignore-revs";
+
+/// how many ignored commits in a row we follow back for a single line
+const IGNORE_REVS_MAX_DEPTH: usize = 16;
+
+/// lines blamed in the first chunk of `blame_file_progress`
+const BLAME_FIRST_CHUNK_LINES: usize = 1000;
 
 /// A `BlameHunk` contains all the information that will be shown to the user.
 #[derive(Clone, Hash, Debug, PartialEq, Eq)]
@@ -38,6 +52,9 @@ pub struct FileBlame {
 	pub path: String,
 	///
 	pub lines: Vec<(Option<BlameHunk>, String)>,
+	/// number of lines (from the top) blamed so far,
+	/// less than `lines.len()` for partial results
+	pub lines_blamed: usize,
 }
 
 /// fixup `\` windows path seperators to git compatible `/`
@@ -59,6 +76,44 @@ pub fn blame_file(
 	file_path: &str,
 	commit_id: Option<CommitId>,
 ) -> Result<FileBlame> {
+	blame_file_ext(repo_path, file_path, commit_id, false)
+}
+
+/// like `blame_file` but optionally skips the revisions listed in
+/// `blame.ignoreRevsFile` (or `.git-blame-ignore-revs` as a fallback)
+pub fn blame_file_ext(
+	repo_path: &RepoPath,
+	file_path: &str,
+	commit_id: Option<CommitId>,
+	ignore_revs: bool,
+) -> Result<FileBlame> {
+	blame_file_progress(
+		repo_path,
+		file_path,
+		commit_id,
+		ignore_revs,
+		|_| true,
+	)
+}
+
+/// like `blame_file_ext` but reports the partial blame as it goes
+///
+/// blames the file top to bottom in chunks, handing the partial
+/// result to `progress` after each of them. stops early (returning
+/// the partial blame) once `progress` returns false.
+///
+/// chunks double in size so the first lines show up quickly while
+/// the history of the file is only walked a logarithmic number of times
+pub fn blame_file_progress<F>(
+	repo_path: &RepoPath,
+	file_path: &str,
+	commit_id: Option<CommitId>,
+	ignore_revs: bool,
+	mut progress: F,
+) -> Result<FileBlame>
+where
+	F: FnMut(&FileBlame) -> bool,
+{
 	scope_time!("blame_file");
 
 	let repo = repo(repo_path)?;
@@ -82,71 +137,327 @@ pub fn blame_file(
 		return Err(Error::NoBlameOnBinaryFile);
 	}
 
-	let mut opts = BlameOptions::new();
-	opts.newest_commit(commit_id.into());
-
-	let blame =
-		repo.blame_file(Path::new(file_path), Some(&mut opts))?;
-
-	let reader = BufReader::new(blob.content());
+	let ignored = if ignore_revs {
+		get_ignore_revs_repo(&repo)?
+	} else {
+		HashSet::new()
+	};
 
-	let unique_commit_ids: HashSet<_> = blame
-		.iter()
-		.map(|hunk| CommitId::new(hunk.final_commit_id()))
-		.collect();
-	let mut commit_ids = Vec::with_capacity(unique_commit_ids.len());
-	commit_ids.extend(unique_commit_ids);
+	let mut file_blame = FileBlame {
+		commit_id,
+		path: file_path.into(),
+		lines: BufReader::new(blob.content())
+			.lines()
+			.map(|line| {
+				(None, line.unwrap_or_else(|_| String::new()))
+			})
+			.collect(),
+		lines_blamed: 0,
+	};
 
-	let commit_infos = get_commits_info(repo_path, &commit_ids, 0)?;
-	let unique_commit_infos: HashMap<_, _> = commit_infos
-		.iter()
-		.map(|commit_info| (commit_info.id, commit_info))
-		.collect();
+	let lines_count = file_blame.lines.len();
+	let mut commit_infos: HashMap<CommitId, (String, i64)> =
+		HashMap::new();
+	let mut chunk = BLAME_FIRST_CHUNK_LINES;
+
+	while file_blame.lines_blamed < lines_count {
+		// line indices in a `Blame` are 1-based
+		let first = file_blame.lines_blamed + 1;
+		let last = lines_count.min(first + chunk - 1);
+
+		let mut opts = BlameOptions::new();
+		opts.newest_commit(commit_id.into());
+		opts.min_line(first);
+		opts.max_line(last);
+
+		let blame =
+			repo.blame_file(Path::new(file_path), Some(&mut opts))?;
+
+		let skipped =
+			skip_ignored_lines(&repo, &blame, first..=last, &ignored);
+
+		let new_ids: Vec<_> = blame
+			.iter()
+			.map(|hunk| CommitId::new(hunk.final_commit_id()))
+			.chain(skipped.values().map(|id| CommitId::new(*id)))
+			.filter(|id| !commit_infos.contains_key(id))
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect();
+
+		commit_infos.extend(
+			get_commits_info(repo_path, &new_ids, 0)?
+				.into_iter()
+				.map(|info| (info.id, (info.author, info.time))),
+		);
+
+		for line in first..=last {
+			file_blame.lines[line - 1].0 =
+				blame_hunk(&blame, &skipped, &commit_infos, line);
+		}
+
+		file_blame.lines_blamed = last;
+		chunk = chunk.saturating_mul(2);
+
+		if !progress(&file_blame) {
+			break;
+		}
+	}
 
-	let lines: Vec<(Option<BlameHunk>, String)> = reader
-		.lines()
-		.enumerate()
-		.map(|(i, line)| {
-			// Line indices in a `FileBlame` are 1-based.
-			let corresponding_hunk = blame.get_line(i + 1);
+	Ok(file_blame)
+}
 
-			if let Some(hunk) = corresponding_hunk {
-				let commit_id = CommitId::new(hunk.final_commit_id());
+/// hunk shown for the 1-based `line`
+fn blame_hunk(
+	blame: &Blame,
+	skipped: &HashMap<usize, Oid>,
+	commit_infos: &HashMap<CommitId, (String, i64)>,
+	line: usize,
+) -> Option<BlameHunk> {
+	let hunk = blame.get_line(line)?;
+
+	let (commit_id, start_line, end_line) =
+		skipped.get(&line).map_or_else(
+			|| {
 				// Line indices in a `BlameHunk` are 1-based.
 				let start_line =
 					hunk.final_start_line().saturating_sub(1);
-				let end_line =
-					start_line.saturating_add(hunk.lines_in_hunk());
-
-				if let Some(commit_info) =
-					unique_commit_infos.get(&commit_id)
-				{
-					let hunk = BlameHunk {
-						commit_id,
-						author: commit_info.author.clone(),
-						time: commit_info.time,
-						start_line,
-						end_line,
-					};
-
-					return (
-						Some(hunk),
-						line.unwrap_or_else(|_| String::new()),
-					);
-				}
+				(
+					CommitId::new(hunk.final_commit_id()),
+					start_line,
+					start_line.saturating_add(hunk.lines_in_hunk()),
+				)
+			},
+			// lines skipped over are treated as their own hunk
+			|id| (CommitId::new(*id), line - 1, line),
+		);
+
+	let (author, time) = commit_infos.get(&commit_id)?;
+
+	Some(BlameHunk {
+		commit_id,
+		author: author.clone(),
+		time: *time,
+		start_line,
+		end_line,
+	})
+}
+
+/// maps (1-based) lines attributed to an ignored commit
+/// to the commit they originate from
+fn skip_ignored_lines(
+	repo: &Repository,
+	blame: &Blame,
+	lines: RangeInclusive<usize>,
+	ignored: &HashSet<Oid>,
+) -> HashMap<usize, Oid> {
+	if ignored.is_empty() {
+		return HashMap::new();
+	}
+
+	let mut resolver = IgnoredRevsResolver::new(repo, ignored);
+
+	lines
+		.filter_map(|line| {
+			let hunk = blame.get_line(line)?;
+			if !ignored.contains(&hunk.final_commit_id()) {
+				return None;
 			}
 
-			(None, line.unwrap_or_else(|_| String::new()))
+			let line_in_commit = hunk.orig_start_line()
+				+ (line - hunk.final_start_line());
+
+			resolver
+				.resolve(
+					hunk.final_commit_id(),
+					hunk.path()?.to_path_buf(),
+					line_in_commit,
+				)
+				.map(|id| (line, id))
 		})
-		.collect();
+		.collect()
+}
 
-	let file_blame = FileBlame {
-		commit_id,
-		path: file_path.into(),
-		lines,
-	};
+/// returns the revisions to skip when blaming as configured
+/// by `blame.ignoreRevsFile` or in a `.git-blame-ignore-revs` file
+pub fn get_ignore_revs(
+	repo_path: &RepoPath,
+) -> Result<Vec<CommitId>> {
+	let repo = repo(repo_path)?;
 
-	Ok(file_blame)
+	Ok(get_ignore_revs_repo(&repo)?
+		.into_iter()
+		.map(CommitId::new)
+		.collect())
+}
+
+fn get_ignore_revs_repo(repo: &Repository) -> Result<HashSet<Oid>> {
+	let work_dir = utils::work_dir(repo)?;
+
+	let file = get_config_string_repo(repo, "blame.ignoreRevsFile")?
+		.map_or_else(
+			|| work_dir.join(IGNORE_REVS_FILE),
+			|path| work_dir.join(path),
+		);
+
+	if !file.exists() {
+		return Ok(HashSet::new());
+	}
+
+	let content = std::fs::read_to_string(file)?;
+
+	Ok(content
+		.lines()
+		.filter_map(|line| {
+			let rev = line.split('#').next()?.trim();
+			if rev.is_empty() {
+				return None;
+			}
+
+			repo.revparse_single(rev)
+				.and_then(|obj| obj.peel_to_commit())
+				.map(|commit| commit.id())
+				.map_err(|e| {
+					log::warn!("invalid ignore rev '{}': {}", rev, e);
+				})
+				.ok()
+		})
+		.collect())
+}
+
+/// follows lines attributed to ignored commits back through
+/// their parents until a commit outside the ignore list is found
+struct IgnoredRevsResolver<'a> {
+	repo: &'a Repository,
+	ignored: &'a HashSet<Oid>,
+	blames: HashMap<(Oid, PathBuf), Option<Blame<'a>>>,
+}
+
+impl<'a> IgnoredRevsResolver<'a> {
+	fn new(repo: &'a Repository, ignored: &'a HashSet<Oid>) -> Self {
+		Self {
+			repo,
+			ignored,
+			blames: HashMap::new(),
+		}
+	}
+
+	/// `line` is the 1-based line number inside `path` at `commit`
+	fn resolve(
+		&mut self,
+		commit: Oid,
+		path: PathBuf,
+		line: usize,
+	) -> Option<Oid> {
+		let (mut commit, mut path, mut line) = (commit, path, line);
+
+		for _ in 0..IGNORE_REVS_MAX_DEPTH {
+			let parent = self
+				.repo
+				.find_commit(commit)
+				.ok()?
+				.parent_id(0)
+				.ok()?;
+			let parent_line =
+				self.map_line_to_parent(commit, parent, &path, line)?;
+
+			let blame = self
+				.blames
+				.entry((parent, path.clone()))
+				.or_insert_with(|| {
+					let mut opts = BlameOptions::new();
+					opts.newest_commit(parent);
+					self.repo.blame_file(&path, Some(&mut opts)).ok()
+				})
+				.as_ref()?;
+
+			let hunk = blame.get_line(parent_line)?;
+			let origin = hunk.final_commit_id();
+
+			if !self.ignored.contains(&origin) {
+				return Some(origin);
+			}
+
+			line = hunk.orig_start_line()
+				+ (parent_line - hunk.final_start_line());
+			path = hunk.path()?.to_path_buf();
+			commit = origin;
+		}
+
+		None
+	}
+
+	/// maps `line` of `path` in `commit` to the corresponding line in
+	/// `parent`. lines changed in a hunk get mapped to the same offset
+	/// inside the old side of the hunk, purely added lines are unmappable.
+	fn map_line_to_parent(
+		&self,
+		commit: Oid,
+		parent: Oid,
+		path: &Path,
+		line: usize,
+	) -> Option<usize> {
+		let blob_at = |id: Oid| {
+			self.repo
+				.find_commit(id)
+				.ok()?
+				.tree()
+				.ok()?
+				.get_path(path)
+				.ok()?
+				.to_object(self.repo)
+				.ok()?
+				.into_blob()
+				.ok()
+		};
+
+		let new_blob = blob_at(commit)?;
+		let old_blob = blob_at(parent)?;
+
+		let mut opts = git2::DiffOptions::new();
+		opts.context_lines(0);
+
+		let diff = Patch::from_blobs(
+			&old_blob,
+			None,
+			&new_blob,
+			None,
+			Some(&mut opts),
+		)
+		.ok()?;
+
+		let (mut old_total, mut new_total) = (0_usize, 0_usize);
+
+		for idx in 0..diff.num_hunks() {
+			let (hunk, _) = diff.hunk(idx).ok()?;
+			let new_start = usize::conv(hunk.new_start());
+			let new_lines = usize::conv(hunk.new_lines());
+			let old_start = usize::conv(hunk.old_start());
+			let old_lines = usize::conv(hunk.old_lines());
+
+			if new_lines == 0 {
+				// pure deletion happens after `new_start`
+				if line <= new_start {
+					break;
+				}
+			} else if line < new_start {
+				break;
+			} else if line < new_start + new_lines {
+				if old_lines == 0 {
+					return None;
+				}
+
+				return Some(
+					old_start + (line - new_start).min(old_lines - 1),
+				);
+			}
+
+			old_total += old_lines;
+			new_total += new_lines;
+		}
+
+		(line + old_total).checked_sub(new_total)
+	}
 }
 
 #[cfg(test)]
@@ -246,6 +557,104 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_blame_ignore_revs() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?
+			.write_all(b"line 1\nline 2\n")?;
+		stage_add_file(repo_path, file_path)?;
+		let first = commit(repo_path, "first commit")?;
+
+		File::create(root.join(file_path))?
+			.write_all(b"line  1\nline 2\nline 3\n")?;
+		stage_add_file(repo_path, file_path)?;
+		let reformat = commit(repo_path, "reformat")?;
+
+		File::create(root.join(".git-blame-ignore-revs"))?
+			.write_all(
+				format!("# formatting\n{}\n", reformat.to_string())
+					.as_bytes(),
+			)?;
+
+		assert_eq!(get_ignore_revs(repo_path)?, vec![reformat]);
+
+		let blame = blame_file_ext(repo_path, "foo", None, false)?;
+		assert_eq!(
+			blame.lines[0].0.as_ref().map(|h| h.commit_id),
+			Some(reformat)
+		);
+
+		let blame = blame_file_ext(repo_path, "foo", None, true)?;
+		let ids: Vec<_> = blame
+			.lines
+			.iter()
+			.map(|(hunk, _)| hunk.as_ref().map(|h| h.commit_id))
+			.collect();
+
+		// reformatted line goes back to the original commit
+		// while the purely added line stays with the ignored one
+		assert_eq!(
+			ids,
+			vec![Some(first), Some(first), Some(reformat)]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_blame_progress() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?
+			.write_all("line\n".repeat(2500).as_bytes())?;
+		stage_add_file(repo_path, file_path)?;
+		let id = commit(repo_path, "first commit")?;
+
+		let mut steps = Vec::new();
+		let blame = blame_file_progress(
+			repo_path,
+			"foo",
+			None,
+			false,
+			|b| {
+				steps.push(b.lines_blamed);
+				true
+			},
+		)?;
+
+		assert_eq!(steps, vec![1000, 2500]);
+		assert!(blame
+			.lines
+			.iter()
+			.all(|(hunk, _)| hunk.as_ref().map(|h| h.commit_id)
+				== Some(id)));
+
+		// stopping early leaves the remaining lines unblamed
+		let blame = blame_file_progress(
+			repo_path,
+			"foo",
+			None,
+			false,
+			|_| false,
+		)?;
+
+		assert_eq!(blame.lines.len(), 2500);
+		assert_eq!(blame.lines_blamed, 1000);
+		assert!(blame.lines[999].0.is_some());
+		assert!(blame.lines[1000].0.is_none());
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_blame_windows_path_dividers() {
 		let file_path = Path::new("bar\\foo");
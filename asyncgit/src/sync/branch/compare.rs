@@ -0,0 +1,194 @@
+//! what merging a branch would bring in
+
+use crate::{
+	error::Result,
+	sync::{
+		repository::repo, status::StatusItemType, CommitId, RepoPath,
+	},
+};
+use git2::{Oid, Patch, Repository, Sort};
+use scopetime::scope_time;
+
+/// lists of commits get cut off after this many
+pub const COMPARE_MAX_COMMITS: usize = 1000;
+
+/// changes of a single file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiffStat {
+	///
+	pub path: String,
+	///
+	pub status: StatusItemType,
+	/// added lines
+	pub additions: usize,
+	/// removed lines
+	pub deletions: usize,
+}
+
+///
+#[derive(Debug, Default, Clone)]
+pub struct BranchComparison {
+	/// `None` for unrelated histories
+	pub merge_base: Option<CommitId>,
+	/// commits only reachable from `head` (newest first, at
+	/// most `COMPARE_MAX_COMMITS`)
+	pub only_in_head: Vec<CommitId>,
+	/// commits only reachable from `other`, these would be merged
+	pub only_in_other: Vec<CommitId>,
+	/// number of commits only in `head` (including cut off ones)
+	pub ahead: usize,
+	/// number of commits only in `other` (including cut off ones)
+	pub behind: usize,
+	/// changes of `other` since the merge base
+	pub files: Vec<FileDiffStat>,
+}
+
+/// compares `head` with `other` (usually the heads of the current
+/// and some other branch): the commits only found in either of them
+/// and the files `other` changed since they diverged
+pub fn compare_branches(
+	repo_path: &RepoPath,
+	head: CommitId,
+	other: CommitId,
+) -> Result<BranchComparison> {
+	scope_time!("compare_branches");
+
+	let repo = repo(repo_path)?;
+
+	let merge_base = repo.merge_base(head.into(), other.into()).ok();
+
+	let (ahead, behind) =
+		repo.graph_ahead_behind(head.into(), other.into())?;
+
+	Ok(BranchComparison {
+		merge_base: merge_base.map(CommitId::new),
+		only_in_head: commits_not_in(&repo, head, other)?,
+		only_in_other: commits_not_in(&repo, other, head)?,
+		ahead,
+		behind,
+		files: diff_stats(&repo, merge_base, other)?,
+	})
+}
+
+/// commits reachable from `from` but not from `exclude`
+fn commits_not_in(
+	repo: &Repository,
+	from: CommitId,
+	exclude: CommitId,
+) -> Result<Vec<CommitId>> {
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+	walk.push(from.into())?;
+	walk.hide(exclude.into())?;
+
+	let mut commits = Vec::new();
+	for id in walk.take(COMPARE_MAX_COMMITS) {
+		commits.push(CommitId::new(id?));
+	}
+
+	Ok(commits)
+}
+
+fn diff_stats(
+	repo: &Repository,
+	base: Option<Oid>,
+	other: CommitId,
+) -> Result<Vec<FileDiffStat>> {
+	let base_tree = match base {
+		Some(base) => Some(repo.find_commit(base)?.tree()?),
+		None => None,
+	};
+	let other_tree = repo.find_commit(other.into())?.tree()?;
+
+	let diff = repo.diff_tree_to_tree(
+		base_tree.as_ref(),
+		Some(&other_tree),
+		None,
+	)?;
+
+	let mut files = Vec::with_capacity(diff.deltas().len());
+	for (idx, delta) in diff.deltas().enumerate() {
+		let path = delta
+			.new_file()
+			.path()
+			.or_else(|| delta.old_file().path())
+			.map(|path| path.to_string_lossy().to_string())
+			.unwrap_or_default();
+
+		let (additions, deletions) =
+			match Patch::from_diff(&diff, idx)? {
+				Some(file_patch) => {
+					let (_, additions, deletions) =
+						file_patch.line_stats()?;
+					(additions, deletions)
+				}
+				None => (0, 0),
+			};
+
+		files.push(FileDiffStat {
+			path,
+			status: StatusItemType::from(delta.status()),
+			additions,
+			deletions,
+		});
+	}
+
+	Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_compare_branches() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a\nb\n", "c1");
+		let master =
+			write_commit_file(&repo, "b.txt", "master", "c2");
+
+		// checks out the new branch
+		create_branch(repo_path, "feature").unwrap();
+		repo.reset(
+			&repo.find_object(base.into(), None).unwrap(),
+			git2::ResetType::Hard,
+			None,
+		)
+		.unwrap();
+		let f1 = write_commit_file(&repo, "a.txt", "a\nc\nd\n", "f1");
+		let f2 = write_commit_file(&repo, "c.txt", "new", "f2");
+
+		let res = compare_branches(repo_path, master, f2).unwrap();
+
+		assert_eq!(res.merge_base, Some(base));
+		assert_eq!(res.only_in_head, vec![master]);
+		assert_eq!(res.only_in_other, vec![f2, f1]);
+		assert_eq!((res.ahead, res.behind), (1, 2));
+		assert_eq!(
+			res.files,
+			vec![
+				FileDiffStat {
+					path: String::from("a.txt"),
+					status: StatusItemType::Modified,
+					additions: 2,
+					deletions: 1,
+				},
+				FileDiffStat {
+					path: String::from("c.txt"),
+					status: StatusItemType::New,
+					additions: 1,
+					deletions: 0,
+				},
+			]
+		);
+	}
+}
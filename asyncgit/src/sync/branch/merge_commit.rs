@@ -3,7 +3,10 @@
 use super::BranchType;
 use crate::{
 	error::{Error, Result},
-	sync::{merge_msg, repository::repo, CommitId, RepoPath},
+	sync::{
+		action_log::log_action, merge_msg, repository::repo,
+		CommitId, RepoPath,
+	},
 };
 use git2::Commit;
 use scopetime::scope_time;
@@ -49,6 +52,8 @@ pub fn merge_upstream_commit(
 
 	repo.merge(&[&annotated_upstream], None, None)?;
 
+	log_action(&repo, "merge upstream", branch_name);
+
 	if !repo.index()?.has_conflicts() {
 		let msg = merge_msg(repo_path)?;
 
@@ -164,7 +169,8 @@ mod test {
 
 		//lets fetch from origin
 		let bytes =
-			fetch(&clone2_dir.into(), "master", None, None).unwrap();
+			fetch(&clone2_dir.into(), "master", None, None, None)
+				.unwrap();
 		assert!(bytes > 0);
 
 		//we should be one commit behind
@@ -253,6 +259,7 @@ mod test {
 			"master",
 			None,
 			None,
+			None,
 		)
 		.unwrap();
 		assert!(bytes > 0);
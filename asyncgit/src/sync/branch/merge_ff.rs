@@ -3,20 +3,20 @@
 use super::BranchType;
 use crate::{
 	error::{Error, Result},
-	sync::{repository::repo, RepoPath},
+	sync::{action_log::log_action, repository::repo, RepoPath},
 };
 use scopetime::scope_time;
 
 ///
 pub fn branch_merge_upstream_fastforward(
 	repo_path: &RepoPath,
-	branch: &str,
+	branch_name: &str,
 ) -> Result<()> {
 	scope_time!("branch_merge_upstream");
 
 	let repo = repo(repo_path)?;
 
-	let branch = repo.find_branch(branch, BranchType::Local)?;
+	let branch = repo.find_branch(branch_name, BranchType::Local)?;
 	let upstream = branch.upstream()?;
 
 	let upstream_commit =
@@ -46,6 +46,8 @@ pub fn branch_merge_upstream_fastforward(
 
 	repo.head()?.set_target(annotated.id(), "")?;
 
+	log_action(&repo, "fast-forward", branch_name);
+
 	Ok(())
 }
 
@@ -117,6 +119,7 @@ pub mod test {
 			"master",
 			None,
 			None,
+			None,
 		)
 		.unwrap();
 		assert!(bytes > 0);
@@ -126,6 +129,7 @@ pub mod test {
 			"master",
 			None,
 			None,
+			None,
 		)
 		.unwrap();
 		assert_eq!(bytes, 0);
@@ -3,8 +3,8 @@
 use crate::{
 	error::{Error, Result},
 	sync::{
-		rebase::conflict_free_rebase, repository::repo, CommitId,
-		RepoPath,
+		action_log::log_action, rebase::conflict_free_rebase,
+		repository::repo, CommitId, RepoPath,
 	},
 };
 use git2::BranchType;
@@ -30,7 +30,11 @@ pub fn merge_upstream_rebase(
 	let annotated_upstream =
 		repo.find_annotated_commit(upstream_commit.id())?;
 
-	conflict_free_rebase(&repo, &annotated_upstream)
+	let id = conflict_free_rebase(&repo, &annotated_upstream)?;
+
+	log_action(&repo, "rebase on upstream", branch_name);
+
+	Ok(id)
 }
 
 #[cfg(test)]
@@ -138,7 +142,8 @@ mod test {
 
 		//lets fetch from origin
 		let bytes =
-			fetch(&clone1_dir.into(), "master", None, None).unwrap();
+			fetch(&clone1_dir.into(), "master", None, None, None)
+				.unwrap();
 		assert!(bytes > 0);
 
 		//we should be one commit behind
@@ -249,7 +254,8 @@ mod test {
 
 		//lets fetch from origin
 
-		fetch(&clone1_dir.into(), "master", None, None).unwrap();
+		fetch(&clone1_dir.into(), "master", None, None, None)
+			.unwrap();
 
 		merge_upstream_rebase(&clone1_dir.into(), "master").unwrap();
 
@@ -329,7 +335,8 @@ mod test {
 			write_commit_file(&clone1, "test2.txt", "foo", "commit3");
 
 		let bytes =
-			fetch(&clone1_dir.into(), "master", None, None).unwrap();
+			fetch(&clone1_dir.into(), "master", None, None, None)
+				.unwrap();
 		assert!(bytes > 0);
 
 		assert_eq!(
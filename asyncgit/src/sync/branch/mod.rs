@@ -1,5 +1,6 @@
 //! branch functions
 
+pub mod compare;
 pub mod merge_commit;
 pub mod merge_ff;
 pub mod merge_rebase;
@@ -11,15 +12,18 @@ use super::{
 };
 use crate::{
 	error::{Error, Result},
-	sync::{repository::repo, utils::get_head_repo, CommitId},
+	sync::{
+		action_log::log_action, repository::repo,
+		utils::get_head_repo, CommitId,
+	},
 };
 use git2::{Branch, BranchType, Repository};
 use scopetime::scope_time;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// returns the branch-name head is currently pointing to
 /// this might be expensive, see `cached::BranchName`
-pub(crate) fn get_branch_name(
+pub fn get_branch_name(
 	repo_path: &RepoPath,
 ) -> Result<String> {
 	let repo = repo(repo_path)?;
@@ -186,7 +190,7 @@ pub fn get_branches_info(
 }
 
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct BranchCompare {
 	///
 	pub ahead: usize,
@@ -268,6 +272,70 @@ pub fn branch_compare_upstream(
 	Ok(BranchCompare { ahead, behind })
 }
 
+/// how a branch compares to its upstream and when it last changed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BranchActivity {
+	/// `None` if the branch has no upstream
+	pub upstream: Option<BranchCompare>,
+	/// unix time of the top commit
+	pub last_commit_time: i64,
+}
+
+/// `BranchActivity` of all local (or remote) branches by reference,
+/// branches that cannot be looked at are left out
+pub fn get_branches_activity(
+	repo_path: &RepoPath,
+	local: bool,
+) -> Result<HashMap<String, BranchActivity>> {
+	scope_time!("get_branches_activity");
+
+	let repo = repo(repo_path)?;
+
+	let filter = if local {
+		BranchType::Local
+	} else {
+		BranchType::Remote
+	};
+
+	let activity = repo
+		.branches(Some(filter))?
+		.filter_map(|b| {
+			let branch = b.ok()?.0;
+			let reference = bytes2string(branch.get().name_bytes()).ok()?;
+
+			branch_activity(&repo, &branch)
+				.ok()
+				.map(|activity| (reference, activity))
+		})
+		.collect();
+
+	Ok(activity)
+}
+
+fn branch_activity(
+	repo: &Repository,
+	branch: &Branch,
+) -> Result<BranchActivity> {
+	let top_commit = branch.get().peel_to_commit()?;
+
+	let upstream = if let Ok(upstream) = branch.upstream() {
+		let upstream_commit =
+			upstream.into_reference().peel_to_commit()?.id();
+
+		let (ahead, behind) =
+			repo.graph_ahead_behind(top_commit.id(), upstream_commit)?;
+
+		Some(BranchCompare { ahead, behind })
+	} else {
+		None
+	};
+
+	Ok(BranchActivity {
+		upstream,
+		last_commit_time: top_commit.time().seconds(),
+	})
+}
+
 /// Modify HEAD to point to a branch then checkout head, does not work if there are uncommitted changes
 pub fn checkout_branch(
 	repo_path: &RepoPath,
@@ -295,6 +363,9 @@ pub fn checkout_branch(
 			)?;
 			return Err(Error::Git(e));
 		}
+
+		log_action(&repo, "checkout", branch_ref);
+
 		Ok(())
 	} else {
 		Err(Error::UncommittedChanges)
@@ -341,6 +412,9 @@ pub fn checkout_remote_branch(
 		repo.set_head(bytes2string(cur_ref.name_bytes())?.as_str())?;
 		return Err(Error::Git(e));
 	}
+
+	log_action(&repo, "checkout", &branch.reference);
+
 	Ok(())
 }
 
@@ -358,6 +432,9 @@ pub fn delete_branch(
 		return Err(Error::Generic("You cannot be on the branch you want to delete, switch branch, then delete this branch".to_string()));
 	}
 	branch.delete()?;
+
+	log_action(&repo, "delete branch", branch_ref);
+
 	Ok(())
 }
 
@@ -378,6 +455,74 @@ pub fn create_branch(
 	let branch_ref_name = bytes2string(branch_ref.name_bytes())?;
 	repo.set_head(branch_ref_name.as_str())?;
 
+	log_action(&repo, "create branch", &branch_ref_name);
+
+	Ok(branch_ref_name)
+}
+
+/// creates a branch pointing at `commit`, HEAD stays where it is
+pub fn create_branch_at(
+	repo_path: &RepoPath,
+	name: &str,
+	commit: CommitId,
+) -> Result<String> {
+	scope_time!("create_branch_at");
+
+	let repo = repo(repo_path)?;
+
+	let commit = repo.find_commit(commit.into())?;
+	let branch_ref = repo.branch(name, &commit, false)?.into_reference();
+	let branch_ref_name = bytes2string(branch_ref.name_bytes())?;
+
+	log_action(
+		&repo,
+		"create branch",
+		&format!(
+			"{} {}",
+			branch_ref_name,
+			CommitId::from(commit.id()).get_short_string()
+		),
+	);
+
+	Ok(branch_ref_name)
+}
+
+/// points HEAD to a new branch without history
+///
+/// like `git checkout --orphan` but empties the index, the worktree is
+/// left alone so its files show up as untracked, the branch only
+/// exists after the first commit
+pub fn create_orphan_branch(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<String> {
+	scope_time!("create_orphan_branch");
+
+	let repo = repo(repo_path)?;
+
+	let branch_ref_name = format!("refs/heads/{}", name);
+
+	if !Branch::name_is_valid(name)? {
+		return Err(Error::Generic(format!(
+			"invalid branch name: {}",
+			name
+		)));
+	}
+	if repo.find_reference(&branch_ref_name).is_ok() {
+		return Err(Error::Generic(format!(
+			"branch {} already exists",
+			name
+		)));
+	}
+
+	repo.set_head(&branch_ref_name)?;
+
+	let mut index = repo.index()?;
+	index.clear()?;
+	index.write()?;
+
+	log_action(&repo, "create orphan branch", &branch_ref_name);
+
 	Ok(branch_ref_name)
 }
 
@@ -416,7 +561,10 @@ mod tests_branch_name {
 #[cfg(test)]
 mod tests_create_branch {
 	use super::*;
-	use crate::sync::tests::repo_init;
+	use crate::sync::{
+		commit,
+		tests::{repo_init, write_commit_file},
+	};
 
 	#[test]
 	fn test_smoke() {
@@ -432,12 +580,82 @@ mod tests_create_branch {
 			"branch1"
 		);
 	}
+
+	#[test]
+	fn test_create_at() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let first = get_head_repo(&repo).unwrap();
+		write_commit_file(&repo, "test.txt", "test", "c1");
+
+		assert_eq!(
+			create_branch_at(repo_path, "old", first).unwrap(),
+			"refs/heads/old"
+		);
+		assert!(create_branch_at(repo_path, "old", first).is_err());
+
+		assert_eq!(
+			get_branch_name(repo_path).unwrap().as_str(),
+			"master"
+		);
+		assert_eq!(
+			repo.find_branch("old", BranchType::Local)
+				.unwrap()
+				.get()
+				.target(),
+			Some(first.into())
+		);
+	}
+
+	#[test]
+	fn test_orphan() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "test", "c1");
+
+		assert!(create_orphan_branch(repo_path, "master").is_err());
+		assert!(create_orphan_branch(repo_path, "a..b").is_err());
+
+		assert_eq!(
+			create_orphan_branch(repo_path, "gh-pages").unwrap(),
+			"refs/heads/gh-pages"
+		);
+
+		assert_eq!(
+			repo.find_reference("HEAD").unwrap().symbolic_target(),
+			Some("refs/heads/gh-pages")
+		);
+		assert!(repo.index().unwrap().is_empty());
+		assert!(root.join("test.txt").exists());
+		assert!(get_head_repo(&repo).is_err());
+
+		let id = commit(repo_path, "first").unwrap();
+
+		assert_eq!(
+			get_branch_name(repo_path).unwrap().as_str(),
+			"gh-pages"
+		);
+		assert_eq!(
+			repo.find_commit(id.into()).unwrap().parent_count(),
+			0
+		);
+		assert_eq!(
+			get_branches_info(repo_path, true).unwrap().len(),
+			2
+		);
+	}
 }
 
 #[cfg(test)]
 mod tests_branch_compare {
 	use super::*;
-	use crate::sync::tests::repo_init;
+	use crate::sync::tests::{repo_init, write_commit_file};
 
 	#[test]
 	fn test_smoke() {
@@ -452,6 +670,35 @@ mod tests_branch_compare {
 
 		assert_eq!(res.is_err(), true);
 	}
+
+	#[test]
+	fn test_activity() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "base").unwrap();
+		checkout_branch(repo_path, "refs/heads/master").unwrap();
+		repo.find_branch("master", BranchType::Local)
+			.unwrap()
+			.set_upstream(Some("base"))
+			.unwrap();
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+
+		let activity = get_branches_activity(repo_path, true).unwrap();
+
+		assert_eq!(activity.len(), 2);
+		assert_eq!(
+			activity["refs/heads/master"].upstream,
+			Some(BranchCompare {
+				ahead: 1,
+				behind: 0
+			})
+		);
+		assert_eq!(activity["refs/heads/base"].upstream, None);
+		assert!(activity["refs/heads/master"].last_commit_time > 0);
+	}
 }
 
 #[cfg(test)]
@@ -1,10 +1,27 @@
 //! renaming of branches
 
 use crate::{
-	error::Result,
-	sync::{repository::repo, RepoPath},
+	error::{Error, Result},
+	sync::{
+		action_log::log_action,
+		config::get_config_string_repo,
+		cred::BasicAuthCredential,
+		remotes::{
+			dry_run::remote_heads,
+			push::{
+				delete_branch_leased, push_refspec,
+				ProgressNotification,
+			},
+		},
+		repository::repo,
+		utils::bytes2string,
+		CommitId, PushSummary, RepoPath,
+	},
 };
+use crossbeam_channel::Sender;
+use git2::{Direction, Repository};
 use scopetime::scope_time;
+use std::sync::{atomic::AtomicBool, Arc};
 
 /// Rename the branch reference
 pub fn rename_branch(
@@ -19,14 +36,145 @@ pub fn rename_branch(
 	let mut branch = git2::Branch::wrap(branch_as_ref);
 	branch.rename(new_name, true)?;
 
+	log_action(
+		&repo,
+		"rename branch",
+		&format!("{} -> {}", branch_ref, new_name),
+	);
+
 	Ok(())
 }
 
+/// the remote `branch_ref` tracks a branch of and that branch's
+/// name, `None` without upstream or with a local one
+pub fn branch_upstream_name(
+	repo_path: &RepoPath,
+	branch_ref: &str,
+) -> Result<Option<(String, String)>> {
+	let repo = repo(repo_path)?;
+	let remote = match repo.branch_upstream_remote(branch_ref) {
+		Ok(remote) => bytes2string(&remote)?,
+		Err(_) => return Ok(None),
+	};
+	if remote == "." {
+		return Ok(None);
+	}
+
+	let branch = branch_ref.trim_start_matches("refs/heads/");
+	let merge = get_config_string_repo(
+		&repo,
+		&format!("branch.{}.merge", branch),
+	)?
+	.unwrap_or_default();
+
+	Ok(merge
+		.strip_prefix("refs/heads/")
+		.map(|name| (remote, name.to_string())))
+}
+
+/// follows up renaming the local branch to `new_name`: pushes it
+/// under that name to `remote`, makes the branch track it and
+/// deletes `old_name` there unless that has commits `new_name` lacks
+#[allow(clippy::too_many_arguments)]
+pub fn rename_remote_branch(
+	repo_path: &RepoPath,
+	remote: &str,
+	old_name: &str,
+	new_name: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+) -> Result<PushSummary> {
+	scope_time!("rename_remote_branch");
+
+	// the new name first so nothing is lost if the delete fails
+	let mut summary = push_refspec(
+		repo_path,
+		remote,
+		&format!("refs/heads/{0}:refs/heads/{0}", new_name),
+		basic_credential.clone(),
+		progress_sender.clone(),
+		cancel.clone(),
+	)?;
+
+	let repo = repo(repo_path)?;
+	let mut config = repo.config()?;
+	config.set_str(&format!("branch.{}.remote", new_name), remote)?;
+	config.set_str(
+		&format!("branch.{}.merge", new_name),
+		&format!("refs/heads/{}", new_name),
+	)?;
+
+	log_action(
+		&repo,
+		"rename remote branch",
+		&format!("{} {} -> {}", remote, old_name, new_name),
+	);
+
+	let old_ref = format!("refs/heads/{}", old_name);
+	let old_tip = remote_heads(
+		repo_path,
+		remote,
+		Direction::Push,
+		basic_credential.clone(),
+	)?
+	.into_iter()
+	.find(|(name, _)| *name == old_ref)
+	.map(|(_, tip)| tip);
+
+	if let Some(old_tip) = old_tip {
+		let new_tip: CommitId = repo
+			.refname_to_id(&format!("refs/heads/{}", new_name))?
+			.into();
+		if !contains(&repo, new_tip, old_tip) {
+			return Err(Error::Generic(format!(
+				"'{0}/{1}' was pushed and is tracked now, '{0}/{2}' has commits '{1}' lacks and was kept",
+				remote, new_name, old_name
+			)));
+		}
+
+		// fails if someone pushed to it in the meantime
+		let deleted = delete_branch_leased(
+			repo_path,
+			remote,
+			old_name,
+			old_tip,
+			basic_credential,
+			progress_sender,
+			cancel,
+		)?;
+		summary.refs.extend(deleted.refs);
+		summary.messages.extend(deleted.messages);
+	}
+
+	Ok(summary)
+}
+
+/// whether `tip` has all of `commit`, `false` if `commit` is
+/// not even known locally
+fn contains(
+	repo: &Repository,
+	tip: CommitId,
+	commit: CommitId,
+) -> bool {
+	tip == commit
+		|| repo
+			.graph_descendant_of(tip.into(), commit.into())
+			.unwrap_or_default()
+}
+
 #[cfg(test)]
 mod test {
 	use super::super::*;
-	use super::rename_branch;
-	use crate::sync::tests::repo_init;
+	use super::{
+		branch_upstream_name, rename_branch, rename_remote_branch,
+	};
+	use crate::sync::{
+		remotes::push::push_branch,
+		tests::{
+			repo_clone, repo_init, repo_init_bare, write_commit_file,
+		},
+	};
 
 	#[test]
 	fn test_rename_branch() {
@@ -68,4 +216,82 @@ mod test {
 			"AnotherName"
 		);
 	}
+
+	#[test]
+	fn test_rename_remote_branch() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir, repo) = repo_clone(upstream_path).unwrap();
+		let repo_path: &RepoPath =
+			&dir.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "first");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		rename_branch(repo_path, "refs/heads/master", "main")
+			.unwrap();
+		assert_eq!(
+			branch_upstream_name(repo_path, "refs/heads/main")
+				.unwrap(),
+			Some(("origin".to_string(), "master".to_string()))
+		);
+
+		let summary = rename_remote_branch(
+			repo_path, "origin", "master", "main", None, None, None,
+		)
+		.unwrap();
+
+		assert_eq!(summary.refs.len(), 2);
+		assert!(upstream.find_reference("refs/heads/main").is_ok());
+		assert!(upstream
+			.find_reference("refs/heads/master")
+			.is_err());
+		assert_eq!(
+			branch_upstream_name(repo_path, "refs/heads/main")
+				.unwrap(),
+			Some(("origin".to_string(), "main".to_string()))
+		);
+	}
+
+	#[test]
+	fn test_rename_remote_branch_keeps_unmerged() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir1, repo1) = repo_clone(upstream_path).unwrap();
+		let path1: &RepoPath = &dir1.path().to_str().unwrap().into();
+
+		write_commit_file(&repo1, "a.txt", "a", "first");
+		push_branch(
+			path1, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		// someone else pushes while this clone is behind
+		let (dir2, repo2) = repo_clone(upstream_path).unwrap();
+		let path2: &RepoPath = &dir2.path().to_str().unwrap().into();
+		let other = write_commit_file(&repo2, "a.txt", "b", "other");
+		push_branch(
+			path2, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		rename_branch(path1, "refs/heads/master", "main").unwrap();
+		assert!(rename_remote_branch(
+			path1, "origin", "master", "main", None, None, None,
+		)
+		.is_err());
+
+		assert!(upstream.find_reference("refs/heads/main").is_ok());
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/master").unwrap(),
+			other.into()
+		);
+		assert_eq!(
+			branch_upstream_name(path1, "refs/heads/main").unwrap(),
+			Some(("origin".to_string(), "main".to_string()))
+		);
+	}
 }
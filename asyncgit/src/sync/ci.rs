@@ -0,0 +1,464 @@
+//! ci status of commits: the check runs and commit statuses github
+//! has for the default remote, or those of the commit status
+//! endpoint configured with `gitui.ciStatusUrl`
+
+use super::{
+	config::get_config_string_repo, github::github_repo,
+	repository::repo, CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::Repository;
+use scopetime::scope_time;
+
+/// replaced by the commit id in `gitui.ciStatusUrl`
+const SHA_PLACEHOLDER: &str = "{sha}";
+
+/// state of a single check or of all checks of a commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CiState {
+	///
+	Pending,
+	///
+	Success,
+	///
+	Failure,
+}
+
+impl CiState {
+	/// `state` of a commit status: `error`, `failure`, `pending` or
+	/// `success`
+	pub fn from_status(state: &str) -> Self {
+		match state {
+			"success" => Self::Success,
+			"pending" => Self::Pending,
+			_ => Self::Failure,
+		}
+	}
+
+	/// a github check run is `completed` once done, its
+	/// `conclusion` telling how
+	pub fn from_check_run(
+		status: &str,
+		conclusion: Option<&str>,
+	) -> Self {
+		match (status, conclusion) {
+			(
+				"completed",
+				Some("success" | "neutral" | "skipped"),
+			) => Self::Success,
+			("completed", _) => Self::Failure,
+			_ => Self::Pending,
+		}
+	}
+
+	/// a failure if any failed, else pending while any is, `None`
+	/// without any states
+	pub fn combine(
+		states: impl Iterator<Item = Self>,
+	) -> Option<Self> {
+		states.fold(None, |res, state| match (res, state) {
+			(Some(Self::Failure), _) | (_, Self::Failure) => {
+				Some(Self::Failure)
+			}
+			(Some(Self::Pending), _) | (_, Self::Pending) => {
+				Some(Self::Pending)
+			}
+			_ => Some(Self::Success),
+		})
+	}
+}
+
+/// a check run or commit status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiCheck {
+	///
+	pub name: String,
+	///
+	pub state: CiState,
+	/// details page
+	pub url: Option<String>,
+}
+
+/// the checks of a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiStatus {
+	/// all checks combined
+	pub state: CiState,
+	///
+	pub checks: Vec<CiCheck>,
+}
+
+impl CiStatus {
+	/// `None` without checks
+	pub fn from_checks(checks: Vec<CiCheck>) -> Option<Self> {
+		CiState::combine(checks.iter().map(|check| check.state))
+			.map(|state| Self { state, checks })
+	}
+}
+
+/// `gitui.ciStatus` is on or `gitui.ciStatusUrl` set
+pub fn ci_status_enabled(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(repo.config()?.get_bool("gitui.ciStatus").unwrap_or(false)
+		|| get_config_string_repo(&repo, "gitui.ciStatusUrl")?
+			.is_some())
+}
+
+/// a failed request, the rest of the batch waits after a
+/// `rate_limited` one
+struct RequestError {
+	error: Error,
+	rate_limited: bool,
+}
+
+/// the ci status of those of `commits` that have checks
+///
+/// asks `gitui.ciStatusUrl` with `{sha}` replaced by the commit id if
+/// set (answering like github's combined commit status, authenticated
+/// with `gitui.ciStatusToken`), otherwise github.
+///
+/// commits no remote knows yet are skipped, as are those the request
+/// fails for, unless it fails for all of them
+pub fn get_ci_status(
+	repo_path: &RepoPath,
+	commits: &[CommitId],
+) -> Result<Vec<(CommitId, CiStatus)>> {
+	scope_time!("get_ci_status");
+
+	let repo = repo(repo_path)?;
+	let url = get_config_string_repo(&repo, "gitui.ciStatusUrl")?;
+	let (github, token) = if url.is_some() {
+		(None, get_config_string_repo(&repo, "gitui.ciStatusToken")?)
+	} else {
+		(
+			Some(github_repo(repo_path)?),
+			get_config_string_repo(&repo, "gitui.githubToken")?,
+		)
+	};
+
+	let remote_tips = remote_tips(&repo)?;
+	let mut res = Vec::with_capacity(commits.len());
+	let mut first_error = None;
+	let mut requested = 0;
+	for id in commits
+		.iter()
+		.filter(|id| is_on_remote(&repo, &remote_tips, **id))
+	{
+		requested += 1;
+		let sha = id.to_string();
+		let checks = match (&url, &github) {
+			(Some(url), _) => api::commit_statuses(
+				&url.replace(SHA_PLACEHOLDER, &sha),
+				token.as_deref(),
+			),
+			(None, Some((owner, name))) => api::github_checks(
+				owner,
+				name,
+				&sha,
+				token.as_deref(),
+			),
+			(None, None) => Ok(Vec::new()),
+		};
+
+		match checks {
+			Ok(checks) => {
+				if let Some(status) = CiStatus::from_checks(checks) {
+					res.push((*id, status));
+				}
+			}
+			Err(e) => {
+				log::warn!(
+					"ci status of {} failed: {}",
+					sha,
+					e.error
+				);
+				let rate_limited = e.rate_limited;
+				first_error.get_or_insert(e.error);
+				if rate_limited {
+					break;
+				}
+			}
+		}
+	}
+
+	match first_error {
+		Some(e) if res.is_empty() && requested > 0 => Err(e),
+		_ => Ok(res),
+	}
+}
+
+/// the commits the remote tracking refs point to
+fn remote_tips(repo: &Repository) -> Result<Vec<git2::Oid>> {
+	Ok(repo
+		.references_glob("refs/remotes/*")?
+		.flatten()
+		.filter_map(|reference| reference.peel_to_commit().ok())
+		.map(|commit| commit.id())
+		.collect())
+}
+
+/// whether a remote tracking ref contains `id`, ci knows nothing
+/// about local commits
+fn is_on_remote(
+	repo: &Repository,
+	remote_tips: &[git2::Oid],
+	id: CommitId,
+) -> bool {
+	let id = id.into();
+	remote_tips.iter().any(|tip| {
+		*tip == id
+			|| repo.graph_descendant_of(*tip, id).unwrap_or_default()
+	})
+}
+
+#[cfg(feature = "github")]
+mod api {
+	use super::{CiCheck, CiState, RequestError};
+	use crate::error::Error;
+	use serde::Deserialize;
+	use std::thread;
+
+	type Result<T> = std::result::Result<T, RequestError>;
+
+	#[derive(Deserialize)]
+	struct Status {
+		context: String,
+		state: String,
+		target_url: Option<String>,
+	}
+
+	#[derive(Deserialize)]
+	struct CombinedStatus {
+		statuses: Vec<Status>,
+	}
+
+	#[derive(Deserialize)]
+	struct CheckRun {
+		name: String,
+		status: String,
+		conclusion: Option<String>,
+		html_url: Option<String>,
+	}
+
+	#[derive(Deserialize)]
+	struct CheckRuns {
+		check_runs: Vec<CheckRun>,
+	}
+
+	fn get<T: serde::de::DeserializeOwned>(
+		url: &str,
+		token: Option<&str>,
+	) -> Result<T> {
+		let mut request = ureq::get(url)
+			.set("Accept", "application/vnd.github+json")
+			.set("User-Agent", "gitui");
+		if let Some(token) = token {
+			request = request
+				.set("Authorization", &format!("Bearer {}", token));
+		}
+
+		let response = request.call().map_err(|e| RequestError {
+			// github answers 403 once the rate limit is exceeded
+			rate_limited: matches!(
+				e,
+				ureq::Error::Status(403 | 429, _)
+			),
+			error: Error::Generic(format!(
+				"ci status request failed: {}",
+				e
+			)),
+		})?;
+
+		response.into_json().map_err(|e| RequestError {
+			error: e.into(),
+			rate_limited: false,
+		})
+	}
+
+	fn statuses(combined: CombinedStatus) -> Vec<CiCheck> {
+		combined
+			.statuses
+			.into_iter()
+			.map(|status| CiCheck {
+				state: CiState::from_status(&status.state),
+				name: status.context,
+				url: status.target_url,
+			})
+			.collect()
+	}
+
+	pub fn commit_statuses(
+		url: &str,
+		token: Option<&str>,
+	) -> Result<Vec<CiCheck>> {
+		Ok(statuses(get(url, token)?))
+	}
+
+	/// check runs (github actions and apps) and commit statuses
+	/// (older integrations) of `sha`, requested at the same time
+	pub fn github_checks(
+		owner: &str,
+		name: &str,
+		sha: &str,
+		token: Option<&str>,
+	) -> Result<Vec<CiCheck>> {
+		let commit = format!(
+			"https://api.github.com/repos/{}/{}/commits/{}",
+			owner, name, sha
+		);
+
+		let combined = {
+			let (url, token) = (
+				format!("{}/status", commit),
+				token.map(ToString::to_string),
+			);
+			thread::spawn(move || {
+				get::<CombinedStatus>(&url, token.as_deref())
+			})
+		};
+		let runs: CheckRuns = get(
+			&format!("{}/check-runs?per_page=100", commit),
+			token,
+		)?;
+		let combined =
+			combined.join().map_err(|_| RequestError {
+				error: Error::Generic(
+					"ci status request panicked".into(),
+				),
+				rate_limited: false,
+			})??;
+
+		Ok(runs
+			.check_runs
+			.into_iter()
+			.map(|run| CiCheck {
+				state: CiState::from_check_run(
+					&run.status,
+					run.conclusion.as_deref(),
+				),
+				name: run.name,
+				url: run.html_url,
+			})
+			.chain(statuses(combined))
+			.collect())
+	}
+}
+
+#[cfg(not(feature = "github"))]
+mod api {
+	use super::{CiCheck, RequestError};
+	use crate::error::Error;
+
+	type Result<T> = std::result::Result<T, RequestError>;
+
+	fn unsupported() -> RequestError {
+		RequestError {
+			error: Error::Generic(
+				"built without ci status support (feature `github`)"
+					.into(),
+			),
+			rate_limited: false,
+		}
+	}
+
+	pub fn commit_statuses(
+		_url: &str,
+		_token: Option<&str>,
+	) -> Result<Vec<CiCheck>> {
+		Err(unsupported())
+	}
+
+	pub fn github_checks(
+		_owner: &str,
+		_name: &str,
+		_sha: &str,
+		_token: Option<&str>,
+	) -> Result<Vec<CiCheck>> {
+		Err(unsupported())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init;
+
+	#[test]
+	fn test_ci_state() {
+		assert_eq!(
+			CiState::from_check_run("completed", Some("skipped")),
+			CiState::Success
+		);
+		assert_eq!(
+			CiState::from_check_run("completed", Some("timed_out")),
+			CiState::Failure
+		);
+		assert_eq!(
+			CiState::from_check_run("in_progress", None),
+			CiState::Pending
+		);
+		assert_eq!(CiState::from_status("error"), CiState::Failure);
+
+		assert_eq!(CiState::combine(std::iter::empty()), None);
+		assert_eq!(
+			CiState::combine(
+				[CiState::Success, CiState::Pending].into_iter()
+			),
+			Some(CiState::Pending)
+		);
+		assert_eq!(
+			CiState::combine(
+				[CiState::Failure, CiState::Pending].into_iter()
+			),
+			Some(CiState::Failure)
+		);
+	}
+
+	#[test]
+	fn test_ci_status_enabled() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+
+		assert!(!ci_status_enabled(repo_path).unwrap());
+
+		repo.config()
+			.unwrap()
+			.set_str("gitui.ciStatusUrl", "https://ci/{sha}")
+			.unwrap();
+
+		assert!(ci_status_enabled(repo_path).unwrap());
+	}
+
+	#[test]
+	fn test_ci_status_remote_commits_only() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+		let head: CommitId =
+			repo.head().unwrap().target().unwrap().into();
+
+		// nothing listens there
+		repo.config()
+			.unwrap()
+			.set_str("gitui.ciStatusUrl", "http://127.0.0.1:1/{sha}")
+			.unwrap();
+
+		// no remote has it, so it is not asked for
+		assert_eq!(
+			get_ci_status(repo_path, &[head]).unwrap(),
+			vec![]
+		);
+
+		repo.reference(
+			"refs/remotes/origin/master",
+			head.into(),
+			false,
+			"",
+		)
+		.unwrap();
+
+		assert!(get_ci_status(repo_path, &[head]).is_err());
+	}
+}
@@ -1,16 +1,70 @@
 use super::{CommitId, RepoPath};
 use crate::{
 	error::Result,
-	sync::{repository::repo, utils::get_head_repo},
+	sync::{
+		action_log::{commit_details, log_action},
+		repository::repo,
+		sign::sign_buffer,
+		utils::{get_head_repo, work_dir},
+	},
+};
+use git2::{
+	Commit, ErrorCode, Index, ObjectType, Repository, Signature, Time,
 };
-use git2::{ErrorCode, ObjectType, Repository, Signature};
 use scopetime::scope_time;
+use std::path::Path;
+
+/// overrides for the author of a commit (like `git commit --author
+/// --date`), the committer is always the configured user
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CommitAuthor {
+	/// name and email instead of `user.name` and `user.email`
+	/// (or those of the amended commit)
+	pub identity: Option<(String, String)>,
+	/// seconds since the epoch and utc offset in minutes instead of
+	/// now (or the date of the amended commit)
+	pub time: Option<(i64, i32)>,
+}
+
+impl CommitAuthor {
+	/// `base` with the overrides applied
+	fn signature(
+		&self,
+		base: &Signature,
+	) -> Result<Signature<'static>> {
+		let time = self.time.map_or_else(
+			|| base.when(),
+			|(seconds, offset)| Time::new(seconds, offset),
+		);
+		let (name, email) = self.identity.as_ref().map_or_else(
+			|| {
+				(
+					base.name().unwrap_or_default(),
+					base.email().unwrap_or_default(),
+				)
+			},
+			|(name, email)| (name.as_str(), email.as_str()),
+		);
+
+		Ok(Signature::new(name, email, &time)?)
+	}
+}
 
 ///
 pub fn amend(
 	repo_path: &RepoPath,
 	id: CommitId,
 	msg: &str,
+) -> Result<CommitId> {
+	amend_with_author(repo_path, id, msg, &CommitAuthor::default())
+}
+
+/// like `amend`, the original author is kept unless overridden
+pub fn amend_with_author(
+	repo_path: &RepoPath,
+	id: CommitId,
+	msg: &str,
+	author: &CommitAuthor,
 ) -> Result<CommitId> {
 	scope_time!("amend");
 
@@ -21,16 +75,25 @@ pub fn amend(
 	let tree_id = index.write_tree()?;
 	let tree = repo.find_tree(tree_id)?;
 
+	let author = if author == &CommitAuthor::default() {
+		None
+	} else {
+		Some(author.signature(&commit.author())?)
+	};
+
 	let new_id = commit.amend(
 		Some("HEAD"),
-		None,
+		author.as_ref(),
 		None,
 		None,
 		Some(msg),
 		Some(&tree),
 	)?;
+	let new_id = CommitId::new(new_id);
+
+	log_action(&repo, "amend", &commit_details(new_id, msg));
 
-	Ok(CommitId::new(new_id))
+	Ok(new_id)
 }
 
 /// Wrap `Repository::signature` to allow unknown user.name.
@@ -62,33 +125,172 @@ pub(crate) fn signature_allow_undefined_name(
 
 /// this does not run any git hooks, git-hooks have to be executed manually, checkout `hooks_commit_msg` for example
 pub fn commit(repo_path: &RepoPath, msg: &str) -> Result<CommitId> {
+	commit_with_author(repo_path, msg, &CommitAuthor::default())
+}
+
+/// like `commit` on behalf of someone else or with another date
+pub fn commit_with_author(
+	repo_path: &RepoPath,
+	msg: &str,
+	author: &CommitAuthor,
+) -> Result<CommitId> {
 	scope_time!("commit");
 
 	let repo = repo(repo_path)?;
 
 	let signature = signature_allow_undefined_name(&repo)?;
+	let author = author.signature(&signature)?;
 	let mut index = repo.index()?;
 	let tree_id = index.write_tree()?;
 	let tree = repo.find_tree(tree_id)?;
 
-	let parents = if let Ok(id) = get_head_repo(&repo) {
-		vec![repo.find_commit(id.into())?]
-	} else {
-		Vec::new()
-	};
-
+	let parents = head_parents(&repo)?;
 	let parents = parents.iter().collect::<Vec<_>>();
 
-	Ok(repo
+	let id = repo
 		.commit(
 			Some("HEAD"),
-			&signature,
+			&author,
 			&signature,
 			msg,
 			&tree,
 			parents.as_slice(),
 		)?
-		.into())
+		.into();
+
+	log_action(&repo, "commit", &commit_details(id, msg));
+
+	Ok(id)
+}
+
+/// commits `paths` as they are in the workdir on top of `HEAD` (like
+/// `git commit --only`), staged changes of other files stay staged
+pub fn commit_paths(
+	repo_path: &RepoPath,
+	msg: &str,
+	author: &CommitAuthor,
+	paths: &[String],
+) -> Result<CommitId> {
+	scope_time!("commit_paths");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let signature = signature_allow_undefined_name(&repo)?;
+	let author = author.signature(&signature)?;
+	let parents = head_parents(&repo)?;
+
+	// `HEAD` plus `paths`, only lives in memory
+	let mut temp_index = Index::new()?;
+	if let Some(parent) = parents.first() {
+		temp_index.read_tree(&parent.tree()?)?;
+	}
+
+	// the real index gets the same entries but is only written once
+	// the commit exists
+	let mut index = repo.index()?;
+	for path in paths {
+		let path = Path::new(path);
+
+		if work_dir.join(path).symlink_metadata().is_ok() {
+			index.add_path(path)?;
+			if let Some(entry) = index.get_path(path, 0) {
+				temp_index.add(&entry)?;
+			}
+		} else {
+			index.remove_path(path)?;
+			temp_index.remove_path(path)?;
+		}
+	}
+
+	let tree_id = temp_index.write_tree_to(&repo)?;
+	let tree = repo.find_tree(tree_id)?;
+	let parents = parents.iter().collect::<Vec<_>>();
+
+	let id = repo.commit(
+		Some("HEAD"),
+		&author,
+		&signature,
+		msg,
+		&tree,
+		parents.as_slice(),
+	)?;
+
+	index.write()?;
+
+	let id = id.into();
+	log_action(
+		&repo,
+		"commit",
+		&format!(
+			"{} ({})",
+			commit_details(id, msg),
+			paths.join(", ")
+		),
+	);
+
+	Ok(id)
+}
+
+/// like `commit` but signs the commit with gpg (see `commit.gpgsign`),
+/// `passphrase` is only needed if the key is not unlocked in the agent
+pub fn commit_signed(
+	repo_path: &RepoPath,
+	msg: &str,
+	author: &CommitAuthor,
+	passphrase: Option<&str>,
+) -> Result<CommitId> {
+	scope_time!("commit_signed");
+
+	let repo = repo(repo_path)?;
+
+	let signature = signature_allow_undefined_name(&repo)?;
+	let author = author.signature(&signature)?;
+	let mut index = repo.index()?;
+	let tree_id = index.write_tree()?;
+	let tree = repo.find_tree(tree_id)?;
+
+	let parents = head_parents(&repo)?;
+	let initial = parents.is_empty();
+	let parents = parents.iter().collect::<Vec<_>>();
+
+	let buffer = repo.commit_create_buffer(
+		&author,
+		&signature,
+		msg,
+		&tree,
+		parents.as_slice(),
+	)?;
+	let content = String::from_utf8(buffer.to_vec())?;
+
+	let gpgsig = sign_buffer(&repo, &content, passphrase)?;
+	let id = repo.commit_signed(&content, &gpgsig, None)?;
+
+	// `commit_signed` does not move any ref, so do what `commit` does
+	let reflog_msg = format!(
+		"commit{}: {}",
+		if initial { " (initial)" } else { "" },
+		msg.lines().next().unwrap_or_default()
+	);
+	let head = repo.find_reference("HEAD")?;
+	if let Some(target) = head.symbolic_target() {
+		repo.reference(target, id, true, &reflog_msg)?;
+	} else {
+		repo.set_head_detached(id)?;
+	}
+
+	let id = id.into();
+	log_action(&repo, "commit", &commit_details(id, msg));
+
+	Ok(id)
+}
+
+fn head_parents(repo: &Repository) -> Result<Vec<Commit<'_>>> {
+	Ok(if let Ok(id) = get_head_repo(repo) {
+		vec![repo.find_commit(id.into())?]
+	} else {
+		Vec::new()
+	})
 }
 
 /// Tag a commit.
@@ -116,13 +318,19 @@ pub fn tag_commit(
 		repo.tag_lightweight(tag, &target, false)?.into()
 	};
 
+	log_action(
+		&repo,
+		"tag",
+		&format!("{} {}", tag, commit_id.get_short_string()),
+	);
+
 	Ok(c)
 }
 
 #[cfg(test)]
 mod tests {
 
-	use crate::error::Result;
+	use crate::error::{Error, Result};
 	use crate::sync::tags::Tag;
 	use crate::sync::RepoPath;
 	use crate::sync::{
@@ -132,7 +340,10 @@ mod tests {
 		utils::get_head,
 		LogWalker,
 	};
-	use commit::{amend, tag_commit};
+	use commit::{
+		amend, amend_with_author, commit_paths, commit_signed,
+		commit_with_author, tag_commit, CommitAuthor,
+	};
 	use git2::Repository;
 	use std::{fs::File, io::Write, path::Path};
 
@@ -231,6 +442,201 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_commit_with_author() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(&root.join(file_path))?.write_all(b"test1")?;
+		stage_add_file(repo_path, file_path)?;
+
+		let author = CommitAuthor {
+			identity: Some((
+				String::from("other"),
+				String::from("other@example.com"),
+			)),
+			time: Some((1_600_000_000, 120)),
+		};
+		let id = commit_with_author(repo_path, "msg", &author)?;
+
+		let commit = repo.find_commit(id.into())?;
+		assert_eq!(commit.author().name(), Some("other"));
+		assert_eq!(commit.author().when().seconds(), 1_600_000_000);
+		assert_eq!(commit.author().when().offset_minutes(), 120);
+		assert_eq!(commit.committer().name(), Some("name"));
+
+		let date_only = CommitAuthor {
+			identity: None,
+			time: Some((1_500_000_000, 0)),
+		};
+		let id = amend_with_author(repo_path, id, "msg", &date_only)?;
+
+		let commit = repo.find_commit(id.into())?;
+		assert_eq!(commit.author().name(), Some("other"));
+		assert_eq!(commit.author().when().seconds(), 1_500_000_000);
+
+		let id = amend(repo_path, id, "amended")?;
+		let commit = repo.find_commit(id.into())?;
+		assert_eq!(commit.author().when().seconds(), 1_500_000_000);
+
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_commit_paths() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(&root.join("foo"))?.write_all(b"foo")?;
+		File::create(&root.join("bar"))?.write_all(b"bar")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+		stage_add_file(repo_path, Path::new("bar"))?;
+		commit(repo_path, "add foo and bar")?;
+
+		File::create(&root.join("foo"))?.write_all(b"staged")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+		File::create(&root.join("baz"))?.write_all(b"baz")?;
+		std::fs::remove_file(root.join("bar"))?;
+
+		let id = commit_paths(
+			repo_path,
+			"baz and bar only",
+			&CommitAuthor::default(),
+			&[String::from("baz"), String::from("bar")],
+		)?;
+
+		let files = get_commit_files(repo_path, id, None)?;
+		let mut paths =
+			files.iter().map(|f| f.path.as_str()).collect::<Vec<_>>();
+		paths.sort_unstable();
+		assert_eq!(paths, vec!["bar", "baz"]);
+
+		// foo is still staged, nothing else is left
+		assert_eq!(get_statuses(repo_path), (0, 1));
+
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	fn fake_gpg(dir: &Path, script: &str) -> String {
+		use std::os::unix::fs::PermissionsExt;
+
+		let path = dir.join("fake-gpg");
+		std::fs::write(&path, script).unwrap();
+		std::fs::set_permissions(
+			&path,
+			std::fs::Permissions::from_mode(0o755),
+		)
+		.unwrap();
+
+		path.to_str().unwrap().to_string()
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_commit_signed() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let gpg = fake_gpg(
+			td.path(),
+			"#!/bin/sh\n\
+			cat > /dev/null\n\
+			echo '[GNUPG:] SIG_CREATED D 1 8 00 0 FP' >&2\n\
+			echo 'FAKE SIGNATURE'\n",
+		);
+		repo.config()?.set_str("gpg.program", &gpg)?;
+
+		File::create(&root.join(file_path))?.write_all(b"test")?;
+		stage_add_file(repo_path, file_path)?;
+
+		let id = commit_signed(
+			repo_path,
+			"signed",
+			&CommitAuthor::default(),
+			None,
+		)?;
+
+		assert_eq!(get_head(repo_path)?, id);
+		assert_eq!(count_commits(&repo, 10), 1);
+
+		let (signature, _) =
+			repo.extract_signature(&id.into(), None)?;
+		assert_eq!(signature.as_str(), Some("FAKE SIGNATURE\n"));
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_commit_signed_passphrase() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let gpg = fake_gpg(
+			td.path(),
+			"#!/bin/sh\n\
+			case \"$*\" in\n\
+			*loopback*) read pass\n\
+			[ \"$pass\" = secret ] || \
+			{ cat > /dev/null; echo 'gpg: Bad passphrase' >&2; exit 2; };;\n\
+			*) cat > /dev/null\n\
+			echo 'gpg: signing failed: No pinentry' >&2; exit 2;;\n\
+			esac\n\
+			cat > /dev/null\n\
+			echo '[GNUPG:] SIG_CREATED D 1 8 00 0 FP' >&2\n\
+			echo 'FAKE SIGNATURE'\n",
+		);
+		repo.config()?.set_str("gpg.program", &gpg)?;
+
+		File::create(&root.join(file_path))?.write_all(b"test")?;
+		stage_add_file(repo_path, file_path)?;
+
+		assert!(matches!(
+			commit_signed(
+				repo_path,
+				"signed",
+				&CommitAuthor::default(),
+				None,
+			),
+			Err(Error::SignPassphraseRequired)
+		));
+		assert!(matches!(
+			commit_signed(
+				repo_path,
+				"signed",
+				&CommitAuthor::default(),
+				Some("wrong"),
+			),
+			Err(Error::SignPassphraseRequired)
+		));
+		assert_eq!(count_commits(&repo, 10), 1);
+
+		let id = commit_signed(
+			repo_path,
+			"signed",
+			&CommitAuthor::default(),
+			Some("secret"),
+		)?;
+
+		assert_eq!(get_head(repo_path)?, id);
+		assert_eq!(count_commits(&repo, 10), 2);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_tag() -> Result<()> {
 		let file_path = Path::new("foo");
@@ -0,0 +1,285 @@
+//! commit message checks run before committing, configured via
+//! `gitui.lint*` in git-config
+
+use super::{repository::repo, RepoPath};
+use crate::error::Result;
+use regex::Regex;
+use scopetime::scope_time;
+
+/// default for `gitui.lintSubjectLength`
+pub const SUBJECT_MAX_LENGTH: usize = 50;
+/// default for `gitui.lintBodyLength`
+pub const BODY_MAX_LENGTH: usize = 72;
+
+/// a problem found in a commit message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintWarning {
+	/// subject longer than `CommitLint::subject_max_length`
+	SubjectTooLong(usize),
+	/// the subject is not followed by an empty line
+	NoBlankLine,
+	/// body line (1-based line number in the message, length)
+	/// longer than `CommitLint::body_max_length`
+	BodyLineTooLong(usize, usize),
+	/// first word of the subject, looks like `Added`/`Adds`/`Adding`
+	/// instead of `Add`
+	NotImperative(String),
+	/// a `gitui.lintForbid` pattern that matched
+	Forbidden(String),
+}
+
+///
+#[derive(Debug, Clone)]
+pub struct CommitLint {
+	/// `gitui.lint`, all checks are off if `false`
+	pub enabled: bool,
+	/// `gitui.lintSubjectLength`, `0` to not check
+	pub subject_max_length: usize,
+	/// `gitui.lintBodyLength`, `0` to not check
+	pub body_max_length: usize,
+	/// `gitui.lintImperative`
+	pub imperative: bool,
+	/// `gitui.lintForbid` regexes (multi valued)
+	pub forbidden: Vec<Regex>,
+}
+
+impl Default for CommitLint {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			subject_max_length: SUBJECT_MAX_LENGTH,
+			body_max_length: BODY_MAX_LENGTH,
+			imperative: true,
+			forbidden: Vec::new(),
+		}
+	}
+}
+
+impl CommitLint {
+	/// warnings for `msg`, comment lines (`#`) are ignored
+	pub fn lint(&self, msg: &str) -> Vec<LintWarning> {
+		let mut warnings = Vec::new();
+
+		if !self.enabled {
+			return warnings;
+		}
+
+		let lines = msg
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| !line.starts_with('#'))
+			.collect::<Vec<_>>();
+
+		let subject = lines.first().map_or("", |(_, line)| line);
+		let subject_length = subject.chars().count();
+
+		if self.subject_max_length > 0
+			&& subject_length > self.subject_max_length
+		{
+			warnings
+				.push(LintWarning::SubjectTooLong(subject_length));
+		}
+
+		if lines.get(1).map_or(false, |(_, line)| !line.is_empty()) {
+			warnings.push(LintWarning::NoBlankLine);
+		}
+
+		if self.body_max_length > 0 {
+			warnings.extend(lines.iter().skip(1).filter_map(
+				|(idx, line)| {
+					let length = line.chars().count();
+					(length > self.body_max_length).then(|| {
+						LintWarning::BodyLineTooLong(idx + 1, length)
+					})
+				},
+			));
+		}
+
+		if self.imperative {
+			if let Some(word) = first_word(subject) {
+				if !is_imperative(word) {
+					warnings.push(LintWarning::NotImperative(
+						word.to_string(),
+					));
+				}
+			}
+		}
+
+		warnings.extend(
+			self.forbidden
+				.iter()
+				.filter(|pattern| pattern.is_match(msg))
+				.map(|pattern| {
+					LintWarning::Forbidden(pattern.to_string())
+				}),
+		);
+
+		warnings
+	}
+}
+
+/// reads the `gitui.lint*` settings, unset ones keep their default,
+/// invalid `gitui.lintForbid` patterns are skipped
+pub fn commit_lint_config(
+	repo_path: &RepoPath,
+) -> Result<CommitLint> {
+	scope_time!("commit_lint_config");
+
+	let repo = repo(repo_path)?;
+	let cfg = repo.config()?;
+	let default = CommitLint::default();
+
+	let length = |key: &str, default: usize| {
+		cfg.get_i64(key)
+			.ok()
+			.and_then(|length| usize::try_from(length).ok())
+			.unwrap_or(default)
+	};
+
+	let mut forbidden = Vec::new();
+	if let Ok(entries) = cfg.multivar("gitui.lintForbid", None) {
+		entries.for_each(|entry| {
+			if let Some(pattern) = entry.value() {
+				match Regex::new(pattern) {
+					Ok(regex) => forbidden.push(regex),
+					Err(e) => log::error!(
+						"invalid gitui.lintForbid pattern: {}",
+						e
+					),
+				}
+			}
+		})?;
+	}
+
+	Ok(CommitLint {
+		enabled: cfg
+			.get_bool("gitui.lint")
+			.unwrap_or(default.enabled),
+		subject_max_length: length(
+			"gitui.lintSubjectLength",
+			default.subject_max_length,
+		),
+		body_max_length: length(
+			"gitui.lintBodyLength",
+			default.body_max_length,
+		),
+		imperative: cfg
+			.get_bool("gitui.lintImperative")
+			.unwrap_or(default.imperative),
+		forbidden,
+	})
+}
+
+/// first word of the subject after a `type(scope): ` prefix as used
+/// by conventional commits
+fn first_word(subject: &str) -> Option<&str> {
+	let subject = subject
+		.split_once(": ")
+		.filter(|(prefix, _)| !prefix.contains(' '))
+		.map_or(subject, |(_, subject)| subject);
+
+	subject
+		.split_whitespace()
+		.next()
+		.map(|word| word.trim_matches(|c: char| !c.is_alphabetic()))
+		.filter(|word| !word.is_empty())
+}
+
+/// catches past tense, gerund and third person forms
+fn is_imperative(word: &str) -> bool {
+	const EXCEPTIONS: &[&str] = &[
+		"alias", "bring", "embed", "exceed", "feed", "need", "ping",
+		"proceed", "seed", "speed", "string", "succeed",
+	];
+
+	let word = word.to_lowercase();
+
+	if EXCEPTIONS.contains(&word.as_str()) || word.len() <= 3 {
+		return true;
+	}
+
+	let third_person = word.ends_with('s')
+		&& !word.ends_with("ss")
+		&& !word.ends_with("us")
+		&& !word.ends_with("is");
+
+	!(word.ends_with("ed") || word.ends_with("ing") || third_person)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init_empty;
+
+	#[test]
+	fn test_lint() {
+		let lint = CommitLint::default();
+
+		assert_eq!(lint.lint("Add lint\n\nbody"), vec![]);
+		assert_eq!(
+			lint.lint("fix(diff): Show binary summary"),
+			vec![]
+		);
+		assert_eq!(
+			lint.lint("Added lint\nbody\n# comment"),
+			vec![
+				LintWarning::NoBlankLine,
+				LintWarning::NotImperative(String::from("Added")),
+			]
+		);
+		assert_eq!(
+			lint.lint(&format!(
+				"Fixes {}\n\n{}",
+				"x".repeat(50),
+				"y".repeat(73)
+			)),
+			vec![
+				LintWarning::SubjectTooLong(56),
+				LintWarning::BodyLineTooLong(3, 73),
+				LintWarning::NotImperative(String::from("Fixes")),
+			]
+		);
+
+		let lint = CommitLint {
+			forbidden: vec![Regex::new("(?i)wip").unwrap()],
+			..CommitLint::default()
+		};
+		assert_eq!(
+			lint.lint("Update docs (WIP)"),
+			vec![LintWarning::Forbidden(String::from("(?i)wip"))]
+		);
+
+		let lint = CommitLint {
+			enabled: false,
+			..lint
+		};
+		assert_eq!(lint.lint("Updating docs (WIP)"), vec![]);
+	}
+
+	#[test]
+	fn test_config() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let mut cfg = repo.config().unwrap();
+		cfg.set_i64("gitui.lintSubjectLength", 0).unwrap();
+		cfg.set_bool("gitui.lintImperative", false).unwrap();
+		cfg.set_multivar("gitui.lintForbid", "^$", "fixup!")
+			.unwrap();
+		cfg.set_multivar("gitui.lintForbid", "^$", "TODO").unwrap();
+		cfg.set_multivar("gitui.lintForbid", "^$", "(").unwrap();
+
+		let lint = commit_lint_config(repo_path).unwrap();
+
+		assert_eq!(lint.subject_max_length, 0);
+		assert_eq!(lint.body_max_length, BODY_MAX_LENGTH);
+		assert!(!lint.imperative);
+		assert_eq!(lint.forbidden.len(), 2);
+		assert_eq!(
+			lint.lint(&format!("fixup! {}", "x".repeat(60))),
+			vec![LintWarning::Forbidden(String::from("fixup!"))]
+		);
+	}
+}
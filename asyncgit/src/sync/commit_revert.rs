@@ -1,7 +1,9 @@
 use super::{CommitId, RepoPath};
 use crate::{
 	error::Result,
-	sync::{repository::repo, utils::read_file},
+	sync::{
+		action_log::log_action, repository::repo, utils::read_file,
+	},
 };
 use scopetime::scope_time;
 
@@ -20,6 +22,12 @@ pub fn revert_commit(
 
 	repo.revert(&commit, None)?;
 
+	log_action(
+		&repo,
+		"revert",
+		&CommitId::from(commit.id()).get_short_string(),
+	);
+
 	Ok(())
 }
 
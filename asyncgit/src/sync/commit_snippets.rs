@@ -0,0 +1,235 @@
+//! abbreviations expanded in commit messages and the prefix of new
+//! ones, configured via `gitui.snippet`, `gitui.commitPrefix` and
+//! `gitui.branchPattern` in git-config
+
+use super::{repository::repo, RepoPath};
+use crate::error::Result;
+use regex::{Captures, Regex};
+use scopetime::scope_time;
+
+/// `gitui.snippet` entry, `<abbreviation> <template>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+	/// e.g. `;t`
+	pub abbreviation: String,
+	/// text with `${...}` variables, see `CommitSnippets::expand`
+	pub template: String,
+}
+
+impl Snippet {
+	fn parse(entry: &str) -> Option<Self> {
+		let (abbreviation, template) =
+			entry.trim_start().split_once(char::is_whitespace)?;
+
+		Some(Self {
+			abbreviation: abbreviation.to_string(),
+			template: template.trim_start().to_string(),
+		})
+	}
+}
+
+///
+#[derive(Debug, Clone, Default)]
+pub struct CommitSnippets {
+	/// `gitui.snippet` (multi valued)
+	pub snippets: Vec<Snippet>,
+	/// `gitui.branchPattern`, its capture groups are the variables
+	/// taken from the branch name
+	pub branch_pattern: Option<Regex>,
+	/// `gitui.commitPrefix`, template new commit messages start with,
+	/// e.g. `${1}: `
+	pub prefix: Option<String>,
+}
+
+impl CommitSnippets {
+	/// replaces `${branch}` with the branch name and `${1}` or
+	/// `${name}` with the groups `branch_pattern` captured,
+	/// variables without a value are left empty
+	pub fn expand(&self, template: &str, branch: &str) -> String {
+		let captures = self
+			.branch_pattern
+			.as_ref()
+			.and_then(|pattern| pattern.captures(branch));
+
+		let mut res = String::new();
+		let mut rest = template;
+
+		while let Some(start) = rest.find("${") {
+			let (name, after) =
+				match rest[start + 2..].split_once('}') {
+					Some(variable) => variable,
+					None => break,
+				};
+
+			res.push_str(&rest[..start]);
+			res.push_str(variable(name, branch, captures.as_ref()));
+			rest = after;
+		}
+
+		res.push_str(rest);
+		res
+	}
+
+	/// expanded `prefix`, `None` if `branch` does not match
+	/// `branch_pattern`
+	pub fn prefix(&self, branch: &str) -> Option<String> {
+		let template = self.prefix.as_ref()?;
+
+		if let Some(pattern) = &self.branch_pattern {
+			if !pattern.is_match(branch) {
+				return None;
+			}
+		}
+
+		Some(self.expand(template, branch))
+	}
+}
+
+fn variable<'a>(
+	name: &str,
+	branch: &'a str,
+	captures: Option<&Captures<'a>>,
+) -> &'a str {
+	if name == "branch" {
+		return branch;
+	}
+
+	captures
+		.and_then(|captures| {
+			name.parse::<usize>().map_or_else(
+				|_| captures.name(name),
+				|idx| captures.get(idx),
+			)
+		})
+		.map_or("", |value| value.as_str())
+}
+
+/// reads `gitui.snippet`, `gitui.commitPrefix` and
+/// `gitui.branchPattern`, invalid entries are skipped
+pub fn commit_snippets_config(
+	repo_path: &RepoPath,
+) -> Result<CommitSnippets> {
+	scope_time!("commit_snippets_config");
+
+	let repo = repo(repo_path)?;
+	let cfg = repo.config()?;
+
+	let mut snippets = Vec::new();
+	if let Ok(entries) = cfg.multivar("gitui.snippet", None) {
+		entries.for_each(|entry| {
+			if let Some(snippet) =
+				entry.value().and_then(Snippet::parse)
+			{
+				snippets.push(snippet);
+			} else {
+				log::error!(
+					"invalid gitui.snippet: {:?}",
+					entry.value()
+				);
+			}
+		})?;
+	}
+
+	let branch_pattern = cfg
+		.get_string("gitui.branchPattern")
+		.ok()
+		.and_then(|pattern| {
+			Regex::new(&pattern)
+				.map_err(|e| {
+					log::error!("invalid gitui.branchPattern: {}", e);
+				})
+				.ok()
+		});
+
+	Ok(CommitSnippets {
+		snippets,
+		branch_pattern,
+		prefix: cfg
+			.get_string("gitui.commitPrefix")
+			.ok()
+			.filter(|prefix| !prefix.is_empty()),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init_empty;
+
+	#[test]
+	fn test_expand() {
+		let snippets = CommitSnippets {
+			snippets: Vec::new(),
+			branch_pattern: Some(
+				Regex::new(r"^(?P<kind>\w+)/(\w+-\d+)").unwrap(),
+			),
+			prefix: None,
+		};
+
+		assert_eq!(
+			snippets.expand(
+				"[${2}] ${kind} on ${branch}${3}",
+				"feature/ABC-12-login"
+			),
+			"[ABC-12] feature on feature/ABC-12-login"
+		);
+		assert_eq!(snippets.expand("[${2}]", "master"), "[]");
+		assert_eq!(snippets.expand("${2", "feature/A-1"), "${2");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let snippets = CommitSnippets {
+			snippets: Vec::new(),
+			branch_pattern: Some(
+				Regex::new(r"([A-Z]+-\d+)").unwrap(),
+			),
+			prefix: Some(String::from("${1}: ")),
+		};
+
+		assert_eq!(
+			snippets.prefix("feature/ABC-12-login"),
+			Some(String::from("ABC-12: "))
+		);
+		assert_eq!(snippets.prefix("master"), None);
+
+		let snippets = CommitSnippets {
+			branch_pattern: None,
+			prefix: Some(String::from("[${branch}] ")),
+			..snippets
+		};
+		assert_eq!(
+			snippets.prefix("docs"),
+			Some(String::from("[docs] "))
+		);
+		assert_eq!(CommitSnippets::default().prefix("docs"), None);
+	}
+
+	#[test]
+	fn test_config() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let mut cfg = repo.config().unwrap();
+		cfg.set_multivar("gitui.snippet", "^$", ";t  ${1}: ")
+			.unwrap();
+		cfg.set_multivar("gitui.snippet", "^$", ";broken").unwrap();
+		cfg.set_str("gitui.branchPattern", r"(\d+)").unwrap();
+
+		let snippets = commit_snippets_config(repo_path).unwrap();
+
+		assert_eq!(
+			snippets.snippets,
+			vec![Snippet {
+				abbreviation: String::from(";t"),
+				template: String::from("${1}: "),
+			}]
+		);
+		assert_eq!(
+			snippets.expand(&snippets.snippets[0].template, "fix-42"),
+			"42: "
+		);
+	}
+}
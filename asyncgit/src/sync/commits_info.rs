@@ -1,6 +1,6 @@
 use super::RepoPath;
 use crate::{error::Result, sync::repository::repo};
-use git2::{Commit, Error, Oid};
+use git2::{Commit, Error, Oid, Sort};
 use scopetime::scope_time;
 use unicode_truncate::UnicodeTruncateStr;
 
@@ -52,7 +52,7 @@ impl From<Oid> for CommitId {
 }
 
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CommitInfo {
 	///
 	pub message: String,
@@ -61,6 +61,8 @@ pub struct CommitInfo {
 	///
 	pub author: String,
 	///
+	pub author_email: String,
+	///
 	pub id: CommitId,
 }
 
@@ -83,13 +85,18 @@ pub fn get_commits_info(
 	let res = commits
 		.map(|c: Commit| {
 			let message = get_message(&c, Some(message_length_limit));
-			let author = c.author().name().map_or_else(
+			let signature = c.author();
+			let author = signature.name().map_or_else(
 				|| String::from("<unknown>"),
 				String::from,
 			);
 			CommitInfo {
 				message,
 				author,
+				author_email: signature
+					.email()
+					.unwrap_or_default()
+					.to_string(),
 				time: c.time().seconds(),
 				id: CommitId(c.id()),
 			}
@@ -99,6 +106,38 @@ pub fn get_commits_info(
 	Ok(res)
 }
 
+/// the last `count` commits of HEAD in log order, empty if HEAD
+/// does not point to a commit yet
+pub fn get_recent_commits_info(
+	repo_path: &RepoPath,
+	count: usize,
+	message_length_limit: usize,
+) -> Result<Vec<CommitInfo>> {
+	scope_time!("get_recent_commits_info");
+
+	let ids = {
+		let repo = repo(repo_path)?;
+
+		if count == 0
+			|| repo
+				.head()
+				.and_then(|head| head.peel_to_commit())
+				.is_err()
+		{
+			return Ok(Vec::new());
+		}
+
+		let mut walk = repo.revwalk()?;
+		walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+		walk.push_head()?;
+		walk.take(count)
+			.map(|id| id.map(CommitId::new))
+			.collect::<std::result::Result<Vec<_>, Error>>()?
+	};
+
+	get_commits_info(repo_path, &ids, message_length_limit)
+}
+
 ///
 pub fn get_commit_info(
 	repo_path: &RepoPath,
@@ -114,6 +153,7 @@ pub fn get_commit_info(
 	Ok(CommitInfo {
 		message: commit.message().unwrap_or("").into(),
 		author: author.name().unwrap_or("<unknown>").into(),
+		author_email: author.email().unwrap_or_default().into(),
 		time: commit.time().seconds(),
 		id: CommitId(commit.id()),
 	})
@@ -139,7 +179,7 @@ pub fn get_message(
 
 #[cfg(test)]
 mod tests {
-	use super::get_commits_info;
+	use super::{get_commits_info, get_recent_commits_info};
 	use crate::{
 		error::Result,
 		sync::{
@@ -175,6 +215,33 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_recent_commits() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(get_recent_commits_info(repo_path, 2, 50)?.is_empty());
+
+		for msg in ["commit1", "commit2", "commit3"] {
+			File::create(&root.join(file_path))?
+				.write_all(msg.as_bytes())?;
+			stage_add_file(repo_path, file_path).unwrap();
+			commit(repo_path, msg).unwrap();
+		}
+
+		let res = get_recent_commits_info(repo_path, 2, 50)?;
+
+		assert_eq!(res.len(), 2);
+		assert_eq!(res[0].message.as_str(), "commit3");
+		assert_eq!(res[1].message.as_str(), "commit2");
+		assert!(get_recent_commits_info(repo_path, 0, 50)?.is_empty());
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_log_first_msg_line() -> Result<()> {
 		let file_path = Path::new("foo");
@@ -2,7 +2,7 @@ use crate::error::Result;
 use git2::Repository;
 use scopetime::scope_time;
 
-use super::{repository::repo, RepoPath};
+use super::{diff::DiffAlgorithm, repository::repo, RepoPath};
 
 // see https://git-scm.com/docs/git-config#Documentation/git-config.txt-statusshowUntrackedFiles
 /// represents the `status.showUntrackedFiles` git config state
@@ -64,6 +64,19 @@ pub fn untracked_files_config(
 	untracked_files_config_repo(&repo)
 }
 
+/// `diff.algorithm`, myers if unset or unknown
+pub fn diff_algorithm_config(
+	repo_path: &RepoPath,
+) -> Result<DiffAlgorithm> {
+	let repo = repo(repo_path)?;
+	let algorithm = get_config_string_repo(&repo, "diff.algorithm")?;
+
+	Ok(algorithm
+		.as_deref()
+		.and_then(DiffAlgorithm::from_name)
+		.unwrap_or_default())
+}
+
 /// get string from config
 pub fn get_config_string(
 	repo_path: &RepoPath,
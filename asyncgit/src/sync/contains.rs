@@ -0,0 +1,158 @@
+//! ancestry queries: merge bases and refs containing a commit
+
+use super::{get_tags_lazy, CommitId, RepoPath};
+use crate::{
+	error::Result,
+	sync::{repository::repo, utils::bytes2string},
+};
+use git2::{BranchType, ErrorCode, Oid, Repository};
+use scopetime::scope_time;
+
+/// best common ancestor of `a` and `b`, `None` for unrelated histories
+pub fn merge_base(
+	repo_path: &RepoPath,
+	a: CommitId,
+	b: CommitId,
+) -> Result<Option<CommitId>> {
+	scope_time!("merge_base");
+
+	let repo = repo(repo_path)?;
+
+	match repo.merge_base(a.into(), b.into()) {
+		Ok(id) => Ok(Some(CommitId::new(id))),
+		Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// names of the local and remote (like `origin/master`) branches
+/// whose history includes `commit`, local ones first
+pub fn branches_containing(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<Vec<String>> {
+	scope_time!("branches_containing");
+
+	let repo = repo(repo_path)?;
+	let mut res = Vec::new();
+
+	for branch_type in [BranchType::Local, BranchType::Remote] {
+		let mut names = Vec::new();
+
+		for branch in repo.branches(Some(branch_type))? {
+			let (branch, _) = branch?;
+
+			// symbolic refs like `origin/HEAD` have no target
+			if let Some(target) = branch.get().target() {
+				if contains(&repo, target, commit)? {
+					names.push(bytes2string(branch.name_bytes()?)?);
+				}
+			}
+		}
+
+		names.sort();
+		res.extend(names);
+	}
+
+	Ok(res)
+}
+
+/// names of the tags whose history includes `commit`, the oldest
+/// (by commit time) first: the first release containing `commit`
+pub fn tags_containing(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<Vec<String>> {
+	scope_time!("tags_containing");
+
+	let repo = repo(repo_path)?;
+	let mut res = Vec::new();
+
+	for (target, tags) in get_tags_lazy(repo_path)? {
+		if contains(&repo, target.into(), commit)? {
+			let time =
+				repo.find_commit(target.into())?.time().seconds();
+			res.extend(tags.into_iter().map(|tag| (time, tag.name)));
+		}
+	}
+
+	res.sort();
+
+	Ok(res.into_iter().map(|(_, name)| name).collect())
+}
+
+fn contains(
+	repo: &Repository,
+	tip: Oid,
+	commit: CommitId,
+) -> Result<bool> {
+	let commit = commit.into();
+	Ok(tip == commit || repo.graph_descendant_of(tip, commit)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		create_branch, tag_commit,
+		tests::{repo_init, repo_init_empty, write_commit_file},
+	};
+	use git2::ResetType;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_merge_base() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+		create_branch(repo_path, "other").unwrap();
+		repo.reset(
+			&repo.find_object(c1.into(), None).unwrap(),
+			ResetType::Hard,
+			None,
+		)
+		.unwrap();
+		let c3 = write_commit_file(&repo, "b.txt", "c", "c3");
+
+		assert_eq!(merge_base(repo_path, c2, c3).unwrap(), Some(c1));
+		assert_eq!(merge_base(repo_path, c1, c2).unwrap(), Some(c1));
+	}
+
+	#[test]
+	fn test_containing() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		tag_commit(repo_path, &c1, "v1.0", None).unwrap();
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+		tag_commit(repo_path, &c2, "v1.1", None).unwrap();
+
+		create_branch(repo_path, "feature").unwrap();
+		let c3 = write_commit_file(&repo, "b.txt", "c", "c3");
+
+		assert_eq!(
+			branches_containing(repo_path, c1).unwrap(),
+			vec![String::from("feature"), String::from("master")]
+		);
+		assert_eq!(
+			branches_containing(repo_path, c3).unwrap(),
+			vec![String::from("feature")]
+		);
+		assert_eq!(
+			tags_containing(repo_path, c1).unwrap(),
+			vec![String::from("v1.0"), String::from("v1.1")]
+		);
+		assert_eq!(
+			tags_containing(repo_path, c2).unwrap(),
+			vec![String::from("v1.1")]
+		);
+		assert!(tags_containing(repo_path, c3).unwrap().is_empty());
+	}
+}
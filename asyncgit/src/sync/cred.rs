@@ -1,10 +1,25 @@
 //! credentials git helper
 
 use super::{
-	remotes::get_default_remote_in_repo, repository::repo, RepoPath,
+	remotes::get_default_remote_in_repo, repository::repo,
+	shell::posix_shell, RepoPath,
 };
 use crate::error::{Error, Result};
-use git2::CredentialHelper;
+use git2::{CredentialHelper, Repository};
+use std::{
+	io::Write,
+	path::{Path, PathBuf},
+	process::{Command, Stdio},
+};
+
+/// credential store of the platform that is asked if no
+/// `credential.helper` is configured or it did not know the remote
+#[cfg(windows)]
+const PLATFORM_HELPER: &str = "wincred";
+#[cfg(target_os = "macos")]
+const PLATFORM_HELPER: &str = "osxkeychain";
+#[cfg(all(unix, not(target_os = "macos")))]
+const PLATFORM_HELPER: &str = "libsecret";
 
 /// basic Authentication Credentials
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -63,12 +78,154 @@ pub fn extract_username_password(
 		helper.config(&config);
 	}
 
-	Ok(match helper.execute() {
+	let cred = match helper.execute() {
 		Some((username, password)) => {
 			BasicAuthCredential::new(Some(username), Some(password))
 		}
 		None => extract_cred_from_url(&url),
-	})
+	};
+
+	if cred.is_complete() {
+		return Ok(cred);
+	}
+
+	Ok(run_helper(PLATFORM_HELPER, "get", &url, &cred)
+		.ok()
+		.filter(BasicAuthCredential::is_complete)
+		.unwrap_or(cred))
+}
+
+/// hands credentials the user entered to the credential helpers
+///
+/// the configured `credential.helper`s (or the platform credential
+/// store if there are none) remember them for the url of `remote`,
+/// like git does after a successful authentication
+pub fn store_username_password(
+	repo_path: &RepoPath,
+	remote: &str,
+	cred: &BasicAuthCredential,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let url = repo
+		.find_remote(remote)?
+		.url()
+		.ok_or(Error::UnknownRemote)?
+		.to_owned();
+
+	for helper in credential_helpers(&repo)? {
+		run_helper(&helper, "store", &url, cred)?;
+	}
+
+	Ok(())
+}
+
+fn credential_helpers(repo: &Repository) -> Result<Vec<String>> {
+	let config = repo.config()?;
+	let mut helpers = Vec::new();
+
+	config.multivar("credential.helper", None)?.for_each(
+		|entry| {
+			match entry.value() {
+				// an empty value resets the list (see git-credential)
+				Some("") => helpers.clear(),
+				Some(helper) => helpers.push(helper.to_string()),
+				None => (),
+			}
+		},
+	)?;
+
+	if helpers.is_empty() {
+		helpers.push(PLATFORM_HELPER.to_string());
+	}
+
+	Ok(helpers)
+}
+
+/// talks the `git credential` helper protocol with `helper`:
+/// a `!` prefix runs a shell snippet, an absolute path the program
+/// and anything else `git credential-<helper>`
+fn run_helper(
+	helper: &str,
+	action: &str,
+	url: &str,
+	cred: &BasicAuthCredential,
+) -> Result<BasicAuthCredential> {
+	let url = url::Url::parse(url)
+		.map_err(|e| Error::Generic(format!("invalid url: {}", e)))?;
+
+	let mut cmd = if helper.starts_with('!')
+		|| Path::new(helper).is_absolute()
+	{
+		let snippet = helper.strip_prefix('!').unwrap_or(helper);
+		let shell =
+			posix_shell().unwrap_or_else(|| PathBuf::from("sh"));
+		let mut cmd = Command::new(shell);
+		cmd.arg("-c").arg(format!("{} {}", snippet, action));
+		cmd
+	} else {
+		let mut cmd = Command::new("git");
+		cmd.arg(format!("credential-{}", helper)).arg(action);
+		cmd
+	};
+
+	let mut child = cmd
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		stdin.write_all(helper_input(&url, cred).as_bytes())?;
+	}
+
+	let output = child.wait_with_output()?;
+	if !output.status.success() {
+		return Err(Error::Generic(format!(
+			"credential helper '{}' failed",
+			helper
+		)));
+	}
+
+	let mut res = cred.clone();
+	for line in String::from_utf8_lossy(&output.stdout).lines() {
+		match line.split_once('=') {
+			Some(("username", value)) => {
+				res.username = Some(value.to_string());
+			}
+			Some(("password", value)) => {
+				res.password = Some(value.to_string());
+			}
+			_ => (),
+		}
+	}
+
+	Ok(res)
+}
+
+fn helper_input(
+	url: &url::Url,
+	cred: &BasicAuthCredential,
+) -> String {
+	let mut lines = vec![format!("protocol={}", url.scheme())];
+
+	if let Some(host) = url.host_str() {
+		lines.push(url.port().map_or_else(
+			|| format!("host={}", host),
+			|port| format!("host={}:{}", host, port),
+		));
+	}
+	lines.extend(
+		cred.username.iter().map(|u| format!("username={}", u)),
+	);
+	lines.extend(
+		cred.password.iter().map(|p| format!("password={}", p)),
+	);
+
+	// an empty line ends the input
+	lines.push(String::new());
+	lines.push(String::new());
+
+	lines.join("\n")
 }
 
 /// extract credentials from url
@@ -93,7 +250,8 @@ mod tests {
 	use crate::sync::{
 		cred::{
 			extract_cred_from_url, extract_username_password,
-			need_username_password, BasicAuthCredential,
+			helper_input, need_username_password,
+			store_username_password, BasicAuthCredential,
 		},
 		remotes::DEFAULT_REMOTE_NAME,
 		tests::repo_init,
@@ -276,4 +434,87 @@ mod tests {
 
 		extract_username_password(repo_path).unwrap();
 	}
+
+	#[test]
+	fn test_helper_input() {
+		let url = url::Url::parse("https://github.com:8080/foo/bar")
+			.unwrap();
+
+		assert_eq!(
+			helper_input(
+				&url,
+				&BasicAuthCredential::new(
+					Some("user".to_owned()),
+					None
+				)
+			),
+			"protocol=https\nhost=github.com:8080\nusername=user\n\n"
+		);
+	}
+
+	#[test]
+	#[serial]
+	#[cfg(unix)]
+	fn test_credential_helper_get_and_store() {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+		let stored = td.path().join("stored");
+
+		repo.remote(DEFAULT_REMOTE_NAME, "http://github.com")
+			.unwrap();
+		repo.remote("fork", "https://gitlab.com").unwrap();
+		repo.config()
+			.unwrap()
+			.set_str(
+				"credential.helper",
+				&format!(
+					"!f() {{ if [ \"$1\" = get ]; then \
+					echo username=user; echo password=pass; \
+					else cat > {}; fi; }}; f",
+					stored.to_str().unwrap()
+				),
+			)
+			.unwrap();
+
+		assert_eq!(
+			extract_username_password(repo_path).unwrap(),
+			BasicAuthCredential::new(
+				Some("user".to_owned()),
+				Some("pass".to_owned())
+			)
+		);
+
+		store_username_password(
+			repo_path,
+			DEFAULT_REMOTE_NAME,
+			&BasicAuthCredential::new(
+				Some("new".to_owned()),
+				Some("secret".to_owned()),
+			),
+		)
+		.unwrap();
+
+		assert_eq!(
+			std::fs::read_to_string(&stored).unwrap(),
+			"protocol=http\nhost=github.com\nusername=new\npassword=secret\n\n"
+		);
+
+		// stored for the remote they were typed in for
+		store_username_password(
+			repo_path,
+			"fork",
+			&BasicAuthCredential::new(
+				Some("new".to_owned()),
+				Some("secret".to_owned()),
+			),
+		)
+		.unwrap();
+
+		assert_eq!(
+			std::fs::read_to_string(&stored).unwrap(),
+			"protocol=https\nhost=gitlab.com\nusername=new\npassword=secret\n\n"
+		);
+	}
 }
@@ -0,0 +1,138 @@
+//! `git describe` for commits
+
+use super::{CommitId, RepoPath};
+use crate::{error::Result, sync::repository::repo};
+use git2::{DescribeFormatOptions, ErrorClass};
+use scopetime::scope_time;
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescribeOptions {
+	/// use lightweight tags too, not only annotated ones (`--tags`)
+	pub tags: bool,
+	/// always add the distance and hash, even on a tag (`--long`)
+	pub long: bool,
+	/// fall back to the abbreviated hash if no tag is
+	/// reachable (`--always`)
+	pub always: bool,
+	/// minimum length of the abbreviated hash (`--abbrev`)
+	pub abbrev: u32,
+}
+
+impl Default for DescribeOptions {
+	/// like `git describe --tags`
+	fn default() -> Self {
+		Self {
+			tags: true,
+			long: false,
+			always: false,
+			abbrev: 7,
+		}
+	}
+}
+
+/// describes `commit` relative to the closest tag (e.g.
+/// `v1.2-3-gabc1234`), `None` if there is no tag to describe it with
+pub fn describe(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	options: &DescribeOptions,
+) -> Result<Option<String>> {
+	scope_time!("describe");
+
+	let repo = repo(repo_path)?;
+	let object = repo.find_object(commit.into(), None)?;
+
+	let mut describe_options = git2::DescribeOptions::new();
+	if options.tags {
+		describe_options.describe_tags();
+	}
+	describe_options.show_commit_oid_as_fallback(options.always);
+
+	let description = match object.describe(&describe_options) {
+		Ok(description) => description,
+		// no tag reachable
+		Err(e) if e.class() == ErrorClass::Describe => {
+			return Ok(None)
+		}
+		Err(e) => return Err(e.into()),
+	};
+
+	let mut format_options = DescribeFormatOptions::new();
+	format_options
+		.always_use_long_format(options.long)
+		.abbreviated_size(options.abbrev);
+
+	Ok(Some(description.format(Some(&format_options))?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		tag_commit,
+		tests::{repo_init_empty, write_commit_file},
+	};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_describe() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let options = DescribeOptions::default();
+		assert_eq!(describe(repo_path, c1, &options).unwrap(), None);
+		assert_eq!(
+			describe(
+				repo_path,
+				c1,
+				&DescribeOptions {
+					always: true,
+					..options
+				}
+			)
+			.unwrap(),
+			Some(c1.get_short_string())
+		);
+
+		tag_commit(repo_path, &c1, "v1.0", None).unwrap();
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+
+		assert_eq!(
+			describe(repo_path, c1, &options).unwrap(),
+			Some(String::from("v1.0"))
+		);
+		assert_eq!(
+			describe(repo_path, c2, &options).unwrap(),
+			Some(format!("v1.0-1-g{}", c2.get_short_string()))
+		);
+		assert_eq!(
+			describe(
+				repo_path,
+				c1,
+				&DescribeOptions {
+					long: true,
+					..options
+				}
+			)
+			.unwrap(),
+			Some(format!("v1.0-0-g{}", c1.get_short_string()))
+		);
+		assert_eq!(
+			describe(
+				repo_path,
+				c1,
+				&DescribeOptions {
+					tags: false,
+					..options
+				}
+			)
+			.unwrap(),
+			None
+		);
+	}
+}
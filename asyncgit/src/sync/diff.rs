@@ -1,7 +1,10 @@
 //! sync git api for fetching a diff
 
 use super::{
+	binary::{file_type, BinaryDiff, BinaryFile, SIGNATURE_LEN},
 	commit_files::{get_commit_diff, get_compare_commits_diff},
+	lfs::{LfsPointer, POINTER_MAX_SIZE},
+	textconv::{textconv, textconv_command},
 	utils::{get_head_repo, work_dir},
 	CommitId, RepoPath,
 };
@@ -10,10 +13,17 @@ use crate::{
 };
 use easy_cast::Conv;
 use git2::{
-	Delta, Diff, DiffDelta, DiffFormat, DiffHunk, Patch, Repository,
+	Delta, Diff, DiffDelta, DiffFile, DiffFormat, DiffHunk,
+	ObjectType, Oid, Patch, Repository,
 };
 use scopetime::scope_time;
-use std::{cell::RefCell, fs, path::Path, rc::Rc};
+use std::{
+	cell::RefCell,
+	fs::{self, File},
+	io::Read,
+	path::{Path, PathBuf},
+	rc::Rc,
+};
 
 /// type of diff of a single line
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -124,6 +134,100 @@ pub struct FileDiff {
 	pub sizes: (u64, u64),
 	/// size delta in bytes
 	pub size_delta: i64,
+	/// stopped after `DiffOptions::max_lines` lines,
+	/// see `DiffOptions::load_more`
+	pub truncated: bool,
+	/// binary or bigger than `DiffOptions::max_file_size`,
+	/// no hunks are generated for those
+	pub binary: bool,
+	/// old and new side if they are git lfs pointers,
+	/// the pointer text is not diffed
+	pub lfs: (Option<LfsPointer>, Option<LfsPointer>),
+	/// set for binary changes (not for those only treated as
+	/// binary for exceeding `DiffOptions::max_file_size`)
+	pub binary_summary: Option<BinaryDiff>,
+	/// set if the hunks are of both sides converted before diffing,
+	/// those can't be staged
+	pub conversion: Option<DiffConversion>,
+}
+
+/// how both sides of a file were converted before diffing them
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum DiffConversion {
+	/// by the `diff.<driver>.textconv` `.gitattributes` assign
+	Textconv,
+	/// json or a jupyter notebook pretty printed
+	Pretty,
+}
+
+/// default for `DiffOptions::max_lines`
+pub const DIFF_MAX_LINES: usize = 20_000;
+/// default for `DiffOptions::max_file_size` (20 MB)
+pub const DIFF_MAX_FILE_SIZE: u64 = 20 * 1024 * 1024;
+
+/// how the `\r` of CRLF line endings ends up in the diff
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum LineEndings {
+	/// trimmed just like the `\n`
+	Hide,
+	/// kept at the end of `DiffLine::content` to be marked by the ui
+	Show,
+	/// lines only differing in a trailing `\r` are not changed
+	Normalize,
+}
+
+impl Default for LineEndings {
+	fn default() -> Self {
+		Self::Hide
+	}
+}
+
+/// how lines of the old and new side are matched up,
+/// see `diff.algorithm` in git-config
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum DiffAlgorithm {
+	///
+	Myers,
+	/// myers spending extra time to find the smallest diff
+	Minimal,
+	/// anchors on lines unique to both sides, keeps moved
+	/// blocks together
+	Patience,
+	/// libgit2 has no histogram implementation, this runs patience
+	/// (which histogram refines) instead
+	Histogram,
+}
+
+impl Default for DiffAlgorithm {
+	fn default() -> Self {
+		Self::Myers
+	}
+}
+
+impl DiffAlgorithm {
+	///
+	pub const ALL: [Self; 4] =
+		[Self::Myers, Self::Minimal, Self::Patience, Self::Histogram];
+
+	/// name as used by `diff.algorithm`
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Myers => "myers",
+			Self::Minimal => "minimal",
+			Self::Patience => "patience",
+			Self::Histogram => "histogram",
+		}
+	}
+
+	/// parses a `diff.algorithm` value, `default` being myers
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name.to_lowercase().as_str() {
+			"default" => Some(Self::Myers),
+			name => Self::ALL
+				.into_iter()
+				.find(|algorithm| algorithm.name() == name),
+		}
+	}
 }
 
 /// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
@@ -135,6 +239,14 @@ pub struct DiffOptions {
 	pub context: u32,
 	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
 	pub interhunk_lines: u32,
+	/// stop generating the diff after this many lines
+	pub max_lines: usize,
+	/// files bigger than this (in bytes) are treated as binary
+	pub max_file_size: u64,
+	///
+	pub line_endings: LineEndings,
+	///
+	pub algorithm: DiffAlgorithm,
 }
 
 impl Default for DiffOptions {
@@ -143,6 +255,42 @@ impl Default for DiffOptions {
 			ignore_whitespace: false,
 			context: 3,
 			interhunk_lines: 0,
+			max_lines: DIFF_MAX_LINES,
+			max_file_size: DIFF_MAX_FILE_SIZE,
+			line_endings: LineEndings::default(),
+			algorithm: DiffAlgorithm::default(),
+		}
+	}
+}
+
+impl DiffOptions {
+	/// options to continue a truncated diff: doubles the line limit
+	#[must_use]
+	pub const fn load_more(self) -> Self {
+		Self {
+			max_lines: self.max_lines.saturating_mul(2),
+			..self
+		}
+	}
+
+	pub(crate) fn apply(self, opt: &mut git2::DiffOptions) {
+		opt.context_lines(self.context);
+		opt.ignore_whitespace(self.ignore_whitespace);
+		opt.interhunk_lines(self.interhunk_lines);
+		opt.ignore_whitespace_eol(
+			self.line_endings == LineEndings::Normalize,
+		);
+		opt.max_size(
+			i64::try_from(self.max_file_size).unwrap_or(i64::MAX),
+		);
+		match self.algorithm {
+			DiffAlgorithm::Myers => (),
+			DiffAlgorithm::Minimal => {
+				opt.minimal(true);
+			}
+			DiffAlgorithm::Patience | DiffAlgorithm::Histogram => {
+				opt.patience(true);
+			}
 		}
 	}
 }
@@ -158,9 +306,7 @@ pub(crate) fn get_diff_raw<'a>(
 
 	let mut opt = git2::DiffOptions::new();
 	if let Some(options) = options {
-		opt.context_lines(options.context);
-		opt.ignore_whitespace(options.ignore_whitespace);
-		opt.interhunk_lines(options.interhunk_lines);
+		options.apply(&mut opt);
 	}
 	opt.pathspec(p);
 	opt.reverse(reverse);
@@ -198,6 +344,18 @@ pub fn get_diff(
 	p: &str,
 	stage: bool,
 	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	get_diff_cancellable(repo_path, p, stage, options, &|| false)
+}
+
+/// `get_diff` that stops early once `is_cancelled`, returning an
+/// incomplete diff
+pub(crate) fn get_diff_cancellable(
+	repo_path: &RepoPath,
+	p: &str,
+	stage: bool,
+	options: Option<DiffOptions>,
+	is_cancelled: &dyn Fn() -> bool,
 ) -> Result<FileDiff> {
 	scope_time!("get_diff");
 
@@ -205,7 +363,13 @@ pub fn get_diff(
 	let work_dir = work_dir(&repo)?;
 	let diff = get_diff_raw(&repo, p, stage, false, options)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	raw_diff_to_file_diff(
+		&repo,
+		&diff,
+		work_dir,
+		options.unwrap_or_default(),
+		is_cancelled,
+	)
 }
 
 /// returns diff of a specific file inside a commit
@@ -215,6 +379,17 @@ pub fn get_diff_commit(
 	id: CommitId,
 	p: String,
 	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	get_diff_commit_cancellable(repo_path, id, p, options, &|| false)
+}
+
+/// `get_diff_commit` that stops early once `is_cancelled`
+pub(crate) fn get_diff_commit_cancellable(
+	repo_path: &RepoPath,
+	id: CommitId,
+	p: String,
+	options: Option<DiffOptions>,
+	is_cancelled: &dyn Fn() -> bool,
 ) -> Result<FileDiff> {
 	scope_time!("get_diff_commit");
 
@@ -223,7 +398,13 @@ pub fn get_diff_commit(
 	let diff =
 		get_commit_diff(repo_path, &repo, id, Some(p), options)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	raw_diff_to_file_diff(
+		&repo,
+		&diff,
+		work_dir,
+		options.unwrap_or_default(),
+		is_cancelled,
+	)
 }
 
 /// get file changes of a diff between two commits
@@ -232,6 +413,19 @@ pub fn get_diff_commits(
 	ids: (CommitId, CommitId),
 	p: String,
 	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	get_diff_commits_cancellable(repo_path, ids, p, options, &|| {
+		false
+	})
+}
+
+/// `get_diff_commits` that stops early once `is_cancelled`
+pub(crate) fn get_diff_commits_cancellable(
+	repo_path: &RepoPath,
+	ids: (CommitId, CommitId),
+	p: String,
+	options: Option<DiffOptions>,
+	is_cancelled: &dyn Fn() -> bool,
 ) -> Result<FileDiff> {
 	scope_time!("get_diff_commits");
 
@@ -244,20 +438,30 @@ pub fn get_diff_commits(
 		options,
 	)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	raw_diff_to_file_diff(
+		&repo,
+		&diff,
+		work_dir,
+		options.unwrap_or_default(),
+		is_cancelled,
+	)
 }
 
 ///
 //TODO: refactor into helper type with the inline closures as dedicated functions
 #[allow(clippy::too_many_lines)]
 fn raw_diff_to_file_diff<'a>(
+	repo: &Repository,
 	diff: &'a Diff,
 	work_dir: &Path,
+	options: DiffOptions,
+	is_cancelled: &dyn Fn() -> bool,
 ) -> Result<FileDiff> {
 	let res = Rc::new(RefCell::new(FileDiff::default()));
 	{
 		let mut current_lines = Vec::new();
 		let mut current_hunk: Option<HunkHeader> = None;
+		let mut lines_total = 0_usize;
 
 		let res_cell = Rc::clone(&res);
 		let adder = move |header: &HunkHeader,
@@ -283,8 +487,22 @@ fn raw_diff_to_file_diff<'a>(
 				//TODO: use try_conv
 				res.size_delta = (i64::conv(res.sizes.1))
 					.saturating_sub(i64::conv(res.sizes.0));
+				res.binary |= delta.flags().is_binary();
+			}
+			if is_cancelled() {
+				// aborts like truncating, the caller drops what
+				// got this far
+				res_cell.borrow_mut().truncated = true;
+				return false;
 			}
 			if let Some(hunk) = hunk {
+				if lines_total >= options.max_lines {
+					// aborts the diff generation
+					res_cell.borrow_mut().truncated = true;
+					return false;
+				}
+				lines_total += 1;
+
 				let hunk_header = HunkHeader::from(hunk);
 
 				match current_hunk {
@@ -300,18 +518,50 @@ fn raw_diff_to_file_diff<'a>(
 
 				let diff_line = DiffLine {
 					position: DiffLinePosition::from(&line),
-					content: String::from_utf8_lossy(line.content())
-						//Note: trim await trailing newline characters
-						.trim_matches(is_newline)
-						.into(),
+					content: trim_newline(
+						&String::from_utf8_lossy(line.content()),
+						options.line_endings,
+					)
+					.into(),
 					line_type: line.origin_value().into(),
 				};
 
 				current_lines.push(diff_line);
 			}
+
+			true
 		};
 
-		let new_file_diff = if diff.deltas().len() == 1 {
+		let conversion = converted_buffers(repo, diff, work_dir)?;
+		let converted =
+			if let Some((_, (old, new, path))) = &conversion {
+				let mut opt = git2::DiffOptions::new();
+				options.apply(&mut opt);
+
+				let mut patch = Patch::from_buffers(
+					old,
+					Some(path),
+					new,
+					Some(path),
+					Some(&mut opt),
+				)?;
+
+				let printed =
+					patch.print(&mut |delta,
+					                  hunk: Option<DiffHunk>,
+					                  line| {
+						put(delta, hunk, line)
+					});
+				ignore_truncation(printed, &res)?;
+
+				true
+			} else {
+				false
+			};
+
+		let new_file_diff = if converted {
+			false
+		} else if diff.deltas().len() == 1 {
 			if let Some(delta) = diff.deltas().next() {
 				if delta.status() == Delta::Untracked {
 					let relative_path =
@@ -324,9 +574,10 @@ fn raw_diff_to_file_diff<'a>(
 
 					let newfile_path = work_dir.join(relative_path);
 
-					if let Some(newfile_content) =
-						new_file_content(&newfile_path)
-					{
+					if let Some(newfile_content) = new_file_content(
+						&newfile_path,
+						options.max_file_size,
+					) {
 						let mut patch = Patch::from_buffers(
 							&[],
 							None,
@@ -335,14 +586,14 @@ fn raw_diff_to_file_diff<'a>(
 							None,
 						)?;
 
-						patch.print(
+						let printed = patch.print(
 							&mut |delta,
 							      hunk: Option<DiffHunk>,
 							      line: git2::DiffLine| {
-								put(delta, hunk, line);
-								true
+								put(delta, hunk, line)
 							},
-						)?;
+						);
+						ignore_truncation(printed, &res)?;
 
 						true
 					} else {
@@ -358,14 +609,14 @@ fn raw_diff_to_file_diff<'a>(
 			false
 		};
 
-		if !new_file_diff {
-			diff.print(
+		if !converted && !new_file_diff {
+			let printed = diff.print(
 				DiffFormat::Patch,
 				move |delta, hunk, line: git2::DiffLine| {
-					put(delta, hunk, line);
-					true
+					put(delta, hunk, line)
 				},
-			)?;
+			);
+			ignore_truncation(printed, &res)?;
 		}
 
 		if !current_lines.is_empty() {
@@ -381,17 +632,198 @@ fn raw_diff_to_file_diff<'a>(
 		if new_file_diff {
 			res.borrow_mut().untracked = true;
 		}
+		res.borrow_mut().conversion =
+			conversion.map(|(conversion, _)| conversion);
+	}
+	let mut res = Rc::try_unwrap(res)
+		.map_err(|_| Error::Generic("rc unwrap error".to_owned()))?
+		.into_inner();
+
+	if let Some(delta) = diff.deltas().next() {
+		res.untracked |= res.conversion.is_some()
+			&& delta.status() == Delta::Untracked;
+		res.lfs = (
+			lfs_pointer(repo, &delta.old_file(), work_dir),
+			lfs_pointer(repo, &delta.new_file(), work_dir),
+		);
+
+		if res.lfs.0.is_some() || res.lfs.1.is_some() {
+			res.hunks.clear();
+			res.lines = 0;
+		} else if delta.flags().is_binary()
+			&& res.conversion.is_none()
+		{
+			res.binary_summary = Some(BinaryDiff {
+				old: binary_file(repo, &delta.old_file(), work_dir),
+				new: binary_file(repo, &delta.new_file(), work_dir),
+			});
+		}
+	}
+
+	Ok(res)
+}
+
+/// old and new content and the path they are of
+type ConvertedFile = (Vec<u8>, Vec<u8>, PathBuf);
+
+/// both sides of the only file in `diff` converted by its textconv
+/// command or else pretty printed, `None` if neither applies
+fn converted_buffers(
+	repo: &Repository,
+	diff: &Diff,
+	work_dir: &Path,
+) -> Result<Option<(DiffConversion, ConvertedFile)>> {
+	let delta = match diff.deltas().next() {
+		Some(delta) if diff.deltas().len() == 1 => delta,
+		_ => return Ok(None),
+	};
+	let path = match delta
+		.new_file()
+		.path()
+		.or_else(|| delta.old_file().path())
+	{
+		Some(path) => path.to_path_buf(),
+		None => return Ok(None),
+	};
+
+	let content = |file: &DiffFile| -> Result<Vec<u8>> {
+		if !file.exists() {
+			return Ok(Vec::new());
+		}
+
+		Ok(match repo.find_blob(file.id()) {
+			Ok(blob) => blob.content().to_vec(),
+			Err(_) => fs::read(work_dir.join(&path))?,
+		})
+	};
+
+	if let Some(command) = textconv_command(repo, &path) {
+		let convert = |file: &DiffFile| {
+			textconv(&command, &content(file)?, &path, work_dir)
+		};
+
+		return Ok(Some((
+			DiffConversion::Textconv,
+			(
+				convert(&delta.old_file())?,
+				convert(&delta.new_file())?,
+				path,
+			),
+		)));
+	}
+
+	#[cfg(feature = "pretty-diff")]
+	if super::pretty_diff::is_candidate(&path) {
+		if let Some((old, new)) = super::pretty_diff::pretty_json(
+			&path,
+			&content(&delta.old_file())?,
+			&content(&delta.new_file())?,
+		) {
+			return Ok(Some((
+				DiffConversion::Pretty,
+				(old, new, path),
+			)));
+		}
+	}
+
+	Ok(None)
+}
+
+/// like `lfs_pointer` the workdir side is read from disk
+fn binary_file(
+	repo: &Repository,
+	file: &DiffFile,
+	work_dir: &Path,
+) -> Option<BinaryFile> {
+	if !file.exists() {
+		return None;
+	}
+
+	if let Ok(blob) = repo.find_blob(file.id()) {
+		return Some(BinaryFile {
+			id: blob.id().to_string(),
+			size: file.size(),
+			file_type: file_type(blob.content()),
+		});
+	}
+
+	let path = work_dir.join(file.path()?);
+	let id = Oid::hash_file(ObjectType::Blob, &path).ok()?;
+
+	let mut signature = Vec::new();
+	File::open(&path)
+		.ok()?
+		.take(SIGNATURE_LEN)
+		.read_to_end(&mut signature)
+		.ok()?;
+
+	Some(BinaryFile {
+		id: id.to_string(),
+		size: fs::metadata(&path).ok()?.len(),
+		file_type: file_type(&signature),
+	})
+}
+
+/// the workdir side of a diff is not in the odb
+fn lfs_pointer(
+	repo: &Repository,
+	file: &DiffFile,
+	work_dir: &Path,
+) -> Option<LfsPointer> {
+	if !file.exists() {
+		return None;
+	}
+
+	let odb = repo.odb().ok()?;
+	if let Ok((size, ObjectType::Blob)) = odb.read_header(file.id()) {
+		if u64::try_from(size).ok()? >= POINTER_MAX_SIZE {
+			return None;
+		}
+
+		return LfsPointer::parse(
+			repo.find_blob(file.id()).ok()?.content(),
+		);
+	}
+
+	let path = work_dir.join(file.path()?);
+	if fs::metadata(&path).ok()?.len() >= POINTER_MAX_SIZE {
+		return None;
+	}
+
+	LfsPointer::parse(&fs::read(path).ok()?)
+}
+
+/// aborting the diff generation on `max_lines` makes libgit2 report an error
+fn ignore_truncation(
+	res: std::result::Result<(), git2::Error>,
+	diff: &RefCell<FileDiff>,
+) -> Result<()> {
+	match res {
+		Err(_) if diff.borrow().truncated => Ok(()),
+		res => Ok(res?),
 	}
-	let res = Rc::try_unwrap(res)
-		.map_err(|_| Error::Generic("rc unwrap error".to_owned()))?;
-	Ok(res.into_inner())
 }
 
 const fn is_newline(c: char) -> bool {
 	c == '\n' || c == '\r'
 }
 
-fn new_file_content(path: &Path) -> Option<Vec<u8>> {
+/// trims away trailing newline characters, a `\r` before the final
+/// `\n` survives if line endings are to be shown
+fn trim_newline(content: &str, line_endings: LineEndings) -> &str {
+	let trimmed = content.trim_matches(is_newline);
+	let end = content.trim_end_matches('\n');
+
+	if line_endings == LineEndings::Show && end.ends_with('\r') {
+		let start = content.len()
+			- content.trim_start_matches(is_newline).len();
+		end.get(start.min(end.len() - 1)..).unwrap_or(trimmed)
+	} else {
+		trimmed
+	}
+}
+
+fn new_file_content(path: &Path, max_size: u64) -> Option<Vec<u8>> {
 	if let Ok(meta) = fs::symlink_metadata(path) {
 		if meta.file_type().is_symlink() {
 			if let Ok(path) = fs::read_link(path) {
@@ -399,7 +831,8 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 					path.to_str()?.to_string().as_bytes().into(),
 				);
 			}
-		} else if !meta.file_type().is_dir() {
+		} else if !meta.file_type().is_dir() && meta.len() <= max_size
+		{
 			if let Ok(content) = fs::read(path) {
 				return Some(content);
 			}
@@ -411,7 +844,11 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-	use super::{get_diff, get_diff_commit};
+	use super::{
+		get_diff, get_diff_cancellable, get_diff_commit,
+		trim_newline, DiffAlgorithm, DiffConversion, DiffOptions,
+		LineEndings,
+	};
 	use crate::{
 		error::Result,
 		sync::{
@@ -634,6 +1071,50 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_binary_summary() -> Result<()> {
+		let file_path = Path::new("img");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+		let gif = b"GIF89a\x01\0\x01\0\0\0\0";
+
+		File::create(&root.join(file_path))?.write_all(png)?;
+		stage_add_file(repo_path, file_path)?;
+		commit(repo_path, "")?;
+
+		File::create(&root.join(file_path))?.write_all(gif)?;
+
+		let diff = get_diff(repo_path, "img", false, None)?;
+		let summary = diff.binary_summary.unwrap();
+		let old = summary.old.unwrap();
+		let new = summary.new.unwrap();
+
+		assert!(diff.hunks.is_empty());
+		assert_eq!(old.file_type, Some("PNG image"));
+		assert_eq!(new.file_type, Some("GIF image"));
+		assert_eq!((old.size, new.size), (16, 13));
+		assert_eq!(
+			new.id,
+			git2::Oid::hash_object(git2::ObjectType::Blob, gif)?
+				.to_string()
+		);
+
+		fs::remove_file(root.join(file_path))?;
+
+		let summary = get_diff(repo_path, "img", false, None)?
+			.binary_summary
+			.unwrap();
+		assert_eq!(summary.size_delta(), -16);
+		assert_eq!(summary.old.unwrap().id, old.id);
+		assert_eq!(summary.new, None);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_diff_delta_size_commit() -> Result<()> {
 		let file_path = Path::new("bar");
@@ -665,4 +1146,257 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_diff_limits() -> Result<()> {
+		let file_path = Path::new("bar.txt");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?
+			.write_all("line\n".repeat(100).as_bytes())?;
+
+		let options = DiffOptions {
+			max_lines: 10,
+			..DiffOptions::default()
+		};
+
+		let diff =
+			get_diff(repo_path, "bar.txt", false, Some(options))?;
+		assert!(diff.truncated);
+		assert_eq!(diff.lines, 10);
+
+		let diff = get_diff(
+			repo_path,
+			"bar.txt",
+			false,
+			Some(options.load_more().load_more().load_more()),
+		)?;
+		assert!(diff.truncated);
+		assert_eq!(diff.lines, 80);
+
+		let diff = get_diff(
+			repo_path,
+			"bar.txt",
+			false,
+			Some(
+				options
+					.load_more()
+					.load_more()
+					.load_more()
+					.load_more(),
+			),
+		)?;
+		assert!(!diff.truncated);
+		assert_eq!(diff.lines, 101);
+
+		stage_add_file(repo_path, file_path)?;
+
+		let options = DiffOptions {
+			max_file_size: 100,
+			..DiffOptions::default()
+		};
+		let diff =
+			get_diff(repo_path, "bar.txt", true, Some(options))?;
+		assert!(diff.binary);
+		assert!(diff.hunks.is_empty());
+		assert_eq!(diff.sizes, (0, 500));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_diff_cancelled() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("bar.txt"))?
+			.write_all("line\n".repeat(100).as_bytes())?;
+		stage_add_file(repo_path, Path::new("bar.txt"))?;
+
+		let diff = get_diff_cancellable(
+			repo_path,
+			"bar.txt",
+			true,
+			None,
+			&|| true,
+		)?;
+		assert_eq!(diff.lines, 0);
+
+		Ok(())
+	}
+	#[test]
+	fn test_diff_algorithm() -> Result<()> {
+		let file_path = Path::new("bar.txt");
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?
+			.write_all(b"\n\nfn a\ny\n")?;
+		stage_add_file(repo_path, file_path)?;
+		commit(repo_path, "")?;
+
+		File::create(root.join(file_path))?
+			.write_all(b"y\n\n}\n\nx\n}\n")?;
+
+		let hunks = |algorithm| -> Result<usize> {
+			let options = DiffOptions {
+				context: 0,
+				algorithm,
+				..DiffOptions::default()
+			};
+			Ok(get_diff(repo_path, "bar.txt", false, Some(options))?
+				.hunks
+				.len())
+		};
+
+		assert_eq!(hunks(DiffAlgorithm::Myers)?, 3);
+		assert_eq!(hunks(DiffAlgorithm::Patience)?, 2);
+		assert_eq!(hunks(DiffAlgorithm::Histogram)?, 2);
+
+		assert_eq!(
+			DiffAlgorithm::from_name("Histogram"),
+			Some(DiffAlgorithm::Histogram)
+		);
+		assert_eq!(
+			DiffAlgorithm::from_name("default"),
+			Some(DiffAlgorithm::Myers)
+		);
+		assert_eq!(DiffAlgorithm::from_name("foo"), None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_trim_newline() {
+		assert_eq!(trim_newline("foo\r\n", LineEndings::Hide), "foo");
+		assert_eq!(
+			trim_newline("foo\r\n", LineEndings::Show),
+			"foo\r"
+		);
+		assert_eq!(trim_newline("foo\n", LineEndings::Show), "foo");
+		assert_eq!(trim_newline("\r\n", LineEndings::Show), "\r");
+	}
+
+	#[test]
+	fn test_diff_line_endings() -> Result<()> {
+		let file_path = Path::new("bar.txt");
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a\nb\n")?;
+		stage_add_file(repo_path, file_path)?;
+		File::create(root.join(file_path))?
+			.write_all(b"a\r\nb\r\n")?;
+
+		let options = |line_endings| DiffOptions {
+			line_endings,
+			..DiffOptions::default()
+		};
+
+		let diff = get_diff(
+			repo_path,
+			"bar.txt",
+			false,
+			Some(options(LineEndings::Show)),
+		)?;
+		assert_eq!(&*diff.hunks[0].lines[3].content, "a\r");
+
+		let diff = get_diff(
+			repo_path,
+			"bar.txt",
+			false,
+			Some(options(LineEndings::Normalize)),
+		)?;
+		assert!(diff.hunks.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_diff_lfs_pointer() -> Result<()> {
+		let file_path = Path::new("model.bin");
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let pointer = |size: u64| {
+			format!(
+				"version https://git-lfs.github.com/spec/v1\noid sha256:{}\nsize {}\n",
+				"a".repeat(64),
+				size
+			)
+		};
+
+		File::create(&root.join(file_path))?
+			.write_all(pointer(100).as_bytes())?;
+		stage_add_file(repo_path, file_path).unwrap();
+		commit(repo_path, "").unwrap();
+
+		File::create(&root.join(file_path))?
+			.write_all(pointer(200).as_bytes())?;
+
+		let diff = get_diff(
+			repo_path,
+			file_path.to_str().unwrap(),
+			false,
+			None,
+		)?;
+
+		assert!(diff.hunks.is_empty());
+		assert_eq!(diff.lfs.0.map(|p| p.size), Some(100));
+		assert_eq!(diff.lfs.1.map(|p| p.size), Some(200));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_diff_textconv() -> Result<()> {
+		let file_path = Path::new("foo.txt");
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(&root.join(".gitattributes"))?
+			.write_all(b"*.txt diff=upper\n")?;
+		repo.config()?
+			.set_str("diff.upper.textconv", "tr a-z A-Z <")?;
+
+		File::create(&root.join(file_path))?.write_all(b"a\n")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		commit(repo_path, "").unwrap();
+
+		File::create(&root.join(file_path))?.write_all(b"a\nb\n")?;
+
+		let diff = get_diff(
+			repo_path,
+			file_path.to_str().unwrap(),
+			false,
+			None,
+		)?;
+
+		assert_eq!(diff.conversion, Some(DiffConversion::Textconv));
+		assert_eq!(&*diff.hunks[0].lines[1].content, "A");
+		assert_eq!(&*diff.hunks[0].lines[2].content, "B");
+
+		File::create(&root.join("bar.txt"))?.write_all(b"c\n")?;
+
+		let diff = get_diff(repo_path, "bar.txt", false, None)?;
+
+		assert_eq!(diff.conversion, Some(DiffConversion::Textconv));
+		assert!(diff.untracked);
+		assert_eq!(&*diff.hunks[0].lines[1].content, "C");
+
+		Ok(())
+	}
 }
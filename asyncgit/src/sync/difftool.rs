@@ -0,0 +1,348 @@
+//! sync git api for launching external diff and merge tools
+
+use super::{
+	config::get_config_string_repo, repository::repo, RepoPath,
+};
+use crate::{
+	error::{Error, Result},
+	sync::utils::{get_head_repo, work_dir},
+};
+use git2::{Oid, Repository};
+use scopetime::scope_time;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use tempfile::TempDir;
+
+/// temporary copies of blobs handed to an external tool,
+/// the files get removed once this is dropped
+#[derive(Debug)]
+pub struct ToolFiles {
+	/// removes the files when dropped
+	_dir: TempDir,
+	/// `$LOCAL`: the old side of a diff or "ours" during a merge
+	pub local: PathBuf,
+	/// `$REMOTE`: the new side of a diff or "theirs" during a merge
+	pub remote: PathBuf,
+	/// `$BASE`: common ancestor (only set for merges)
+	pub base: Option<PathBuf>,
+	/// `$MERGED`: the file in the workdir the merge result goes to
+	pub merged: Option<PathBuf>,
+}
+
+impl ToolFiles {
+	/// the `LOCAL`, `REMOTE`, `BASE` and `MERGED` environment the
+	/// tool command runs in like git sets it: the command references
+	/// them as shell variables so file names never become shell code
+	pub fn env(&self) -> Vec<(&'static str, &Path)> {
+		let mut env = vec![
+			("LOCAL", self.local.as_path()),
+			("REMOTE", self.remote.as_path()),
+		];
+
+		if let Some(base) = &self.base {
+			env.push(("BASE", base));
+		}
+		if let Some(merged) = &self.merged {
+			env.push(("MERGED", merged));
+		}
+
+		env
+	}
+}
+
+/// external tool command template configured for diffs
+///
+/// checks `difftool.<diff.tool>.cmd` and falls back to
+/// calling `diff.tool` with `$LOCAL $REMOTE`
+pub fn difftool_cmd(repo_path: &RepoPath) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+	tool_cmd(&repo, "diff", r#""$LOCAL" "$REMOTE""#)
+}
+
+/// external tool command template configured for merges
+///
+/// checks `mergetool.<merge.tool>.cmd` and falls back to
+/// calling `merge.tool` with `$LOCAL $BASE $REMOTE $MERGED`
+pub fn mergetool_cmd(repo_path: &RepoPath) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+	tool_cmd(
+		&repo,
+		"merge",
+		r#""$LOCAL" "$BASE" "$REMOTE" "$MERGED""#,
+	)
+}
+
+fn tool_cmd(
+	repo: &Repository,
+	kind: &str,
+	default_args: &str,
+) -> Result<Option<String>> {
+	let tool = match get_config_string_repo(
+		repo,
+		&format!("{}.tool", kind),
+	)? {
+		Some(tool) => tool,
+		None => return Ok(None),
+	};
+
+	let cmd = get_config_string_repo(
+		repo,
+		&format!("{}tool.{}.cmd", kind, tool),
+	)?;
+
+	Ok(Some(
+		cmd.unwrap_or_else(|| format!("{} {}", tool, default_args)),
+	))
+}
+
+/// extracts old and new version of `file_path` to use with a difftool
+///
+/// `stage` diffs HEAD against the index, otherwise the index is diffed
+/// against the file in the workdir (which is passed as is so edits stick)
+pub fn difftool_files(
+	repo_path: &RepoPath,
+	file_path: &str,
+	stage: bool,
+) -> Result<ToolFiles> {
+	scope_time!("difftool_files");
+
+	let repo = repo(repo_path)?;
+	let path = Path::new(file_path);
+	let dir = create_temp_dir()?;
+
+	let old_id = if stage {
+		head_blob_id(&repo, path)?
+	} else {
+		index_blob_id(&repo, path, 0)?
+	};
+	let local = write_blob(&repo, old_id, dir.path(), path, "LOCAL")?;
+
+	let remote = if stage {
+		let new_id = index_blob_id(&repo, path, 0)?;
+		write_blob(&repo, new_id, dir.path(), path, "REMOTE")?
+	} else {
+		work_dir(&repo)?.join(path)
+	};
+
+	Ok(ToolFiles {
+		_dir: dir,
+		local,
+		remote,
+		base: None,
+		merged: None,
+	})
+}
+
+/// extracts the conflicting versions of `file_path` from the index
+/// to resolve them with a mergetool
+pub fn mergetool_files(
+	repo_path: &RepoPath,
+	file_path: &str,
+) -> Result<ToolFiles> {
+	scope_time!("mergetool_files");
+
+	let repo = repo(repo_path)?;
+	let path = Path::new(file_path);
+
+	let ours = index_blob_id(&repo, path, 2)?;
+	let theirs = index_blob_id(&repo, path, 3)?;
+	if ours.is_none() && theirs.is_none() {
+		return Err(Error::Generic(format!(
+			"no conflict found for: {}",
+			file_path
+		)));
+	}
+
+	let base = index_blob_id(&repo, path, 1)?;
+	let dir = create_temp_dir()?;
+
+	Ok(ToolFiles {
+		local: write_blob(&repo, ours, dir.path(), path, "LOCAL")?,
+		remote: write_blob(
+			&repo,
+			theirs,
+			dir.path(),
+			path,
+			"REMOTE",
+		)?,
+		base: Some(write_blob(
+			&repo,
+			base,
+			dir.path(),
+			path,
+			"BASE",
+		)?),
+		merged: Some(work_dir(&repo)?.join(path)),
+		_dir: dir,
+	})
+}
+
+/// private to the user and with an unpredictable name
+fn create_temp_dir() -> Result<TempDir> {
+	Ok(tempfile::Builder::new().prefix("gitui-tool-").tempdir()?)
+}
+
+fn head_blob_id(
+	repo: &Repository,
+	path: &Path,
+) -> Result<Option<Oid>> {
+	let head = match get_head_repo(repo) {
+		Ok(head) => head,
+		Err(_) => return Ok(None),
+	};
+
+	let tree = repo.find_commit(head.into())?.tree()?;
+
+	Ok(tree.get_path(path).ok().map(|entry| entry.id()))
+}
+
+fn index_blob_id(
+	repo: &Repository,
+	path: &Path,
+	stage: i32,
+) -> Result<Option<Oid>> {
+	let index = repo.index()?;
+
+	Ok(index.get_path(path, stage).map(|entry| entry.id))
+}
+
+/// writes blob (or an empty file if there is none) into `dir`
+/// keeping the extension intact so tools can pick up syntax highlighting
+fn write_blob(
+	repo: &Repository,
+	id: Option<Oid>,
+	dir: &Path,
+	path: &Path,
+	suffix: &str,
+) -> Result<PathBuf> {
+	let stem = path
+		.file_stem()
+		.map(|s| s.to_string_lossy().to_string())
+		.unwrap_or_default();
+
+	let file_name = path.extension().map_or_else(
+		|| format!("{}_{}", stem, suffix),
+		|ext| {
+			format!("{}_{}.{}", stem, suffix, ext.to_string_lossy())
+		},
+	);
+
+	let target = dir.join(file_name);
+
+	match id {
+		Some(id) => {
+			fs::write(&target, repo.find_blob(id)?.content())?;
+		}
+		None => fs::write(&target, [])?,
+	}
+
+	Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use std::fs;
+
+	#[test]
+	fn test_difftool_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo.txt", "a\n", "c1");
+		repo_write_file(&repo, "foo.txt", "b\n").unwrap();
+
+		let files =
+			difftool_files(repo_path, "foo.txt", false).unwrap();
+		assert_eq!(fs::read_to_string(&files.local).unwrap(), "a\n");
+		assert_eq!(fs::read_to_string(&files.remote).unwrap(), "b\n");
+		assert!(files.base.is_none());
+
+		stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+
+		let files =
+			difftool_files(repo_path, "foo.txt", true).unwrap();
+		assert_eq!(fs::read_to_string(&files.local).unwrap(), "a\n");
+		assert_eq!(fs::read_to_string(&files.remote).unwrap(), "b\n");
+		assert!(files.local.to_string_lossy().ends_with(".txt"));
+
+		let dir = files.local.parent().unwrap().to_path_buf();
+		drop(files);
+		assert!(!dir.exists());
+	}
+
+	#[test]
+	fn test_difftool_cmd() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(difftool_cmd(repo_path).unwrap(), None);
+
+		repo.config().unwrap().set_str("diff.tool", "meld").unwrap();
+		assert_eq!(
+			difftool_cmd(repo_path).unwrap().as_deref(),
+			Some(r#"meld "$LOCAL" "$REMOTE""#)
+		);
+
+		repo.config()
+			.unwrap()
+			.set_str(
+				"difftool.meld.cmd",
+				"meld --diff $LOCAL $REMOTE",
+			)
+			.unwrap();
+		assert_eq!(
+			difftool_cmd(repo_path).unwrap().as_deref(),
+			Some("meld --diff $LOCAL $REMOTE")
+		);
+	}
+
+	fn tool_files() -> ToolFiles {
+		ToolFiles {
+			_dir: create_temp_dir().unwrap(),
+			local: PathBuf::from("$(touch pwned).txt"),
+			remote: PathBuf::from("b"),
+			base: None,
+			merged: Some(PathBuf::from("c")),
+		}
+	}
+
+	#[test]
+	fn test_env() {
+		let files = tool_files();
+
+		assert_eq!(
+			files.env(),
+			vec![
+				("LOCAL", Path::new("$(touch pwned).txt")),
+				("REMOTE", Path::new("b")),
+				("MERGED", Path::new("c")),
+			]
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_env_not_expanded() {
+		let files = tool_files();
+
+		// the shell expands the variable but not what is in it
+		let output = std::process::Command::new("sh")
+			.args(["-c", r#"printf %s "$LOCAL""#])
+			.envs(files.env())
+			.output()
+			.unwrap();
+		assert_eq!(output.stdout, b"$(touch pwned).txt");
+	}
+}
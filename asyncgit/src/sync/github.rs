@@ -0,0 +1,456 @@
+//! github pull requests and issues of the default remote
+
+use super::{
+	action_log::log_action,
+	branch::checkout_branch,
+	config::get_config_string_repo,
+	cred::BasicAuthCredential,
+	remotes::{get_default_remote_in_repo, Callbacks, Transport},
+	repository::repo,
+	RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::FetchOptions;
+use scopetime::scope_time;
+
+/// open pull request on github
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+	///
+	pub number: u64,
+	///
+	pub title: String,
+	///
+	pub author: String,
+	/// name of the branch the pull request was opened from
+	pub head_ref: String,
+}
+
+/// issue or pull request as referenced by `#<number>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+	///
+	pub number: u64,
+	///
+	pub title: String,
+	///
+	pub is_pull_request: bool,
+}
+
+/// `(owner, repository)` of a remote url pointing to github
+pub fn github_repo_from_url(url: &str) -> Option<(String, String)> {
+	let path = url
+		.strip_prefix("https://github.com/")
+		.or_else(|| url.strip_prefix("http://github.com/"))
+		.or_else(|| url.strip_prefix("git@github.com:"))
+		.or_else(|| url.strip_prefix("ssh://git@github.com/"))?;
+	let path = path.trim_end_matches('/');
+	let path = path.strip_suffix(".git").unwrap_or(path);
+
+	let (owner, name) = path.split_once('/')?;
+
+	(!owner.is_empty() && !name.is_empty() && !name.contains('/'))
+		.then(|| (owner.to_string(), name.to_string()))
+}
+
+fn github_remote(
+	repo: &git2::Repository,
+) -> Result<(String, String)> {
+	let remote_name = get_default_remote_in_repo(repo)?;
+	let remote = repo.find_remote(&remote_name)?;
+
+	remote.url().and_then(github_repo_from_url).ok_or_else(|| {
+		Error::Generic(format!(
+			"remote '{}' is not hosted on github",
+			remote_name
+		))
+	})
+}
+
+/// `(owner, repository)` of the default remote
+pub fn github_repo(repo_path: &RepoPath) -> Result<(String, String)> {
+	github_remote(&repo(repo_path)?)
+}
+
+/// lists the open pull requests of the default remote,
+/// authenticating with `gitui.githubToken` if configured
+pub fn get_pull_requests(
+	repo_path: &RepoPath,
+) -> Result<Vec<PullRequest>> {
+	scope_time!("get_pull_requests");
+
+	let repo = repo(repo_path)?;
+	let (owner, name) = github_remote(&repo)?;
+	let token = get_config_string_repo(&repo, "gitui.githubToken")?;
+
+	api::open_pull_requests(&owner, &name, token.as_deref())
+}
+
+/// lists the most recently updated issues and pull requests
+/// of the default remote
+pub fn get_recent_issues(repo_path: &RepoPath) -> Result<Vec<Issue>> {
+	scope_time!("get_recent_issues");
+
+	let repo = repo(repo_path)?;
+	let (owner, name) = github_remote(&repo)?;
+	let token = get_config_string_repo(&repo, "gitui.githubToken")?;
+
+	api::recent_issues(&owner, &name, token.as_deref())
+}
+
+fn pull_request_config_key(branch: &str) -> String {
+	format!("branch.{}.gitui-pr", branch)
+}
+
+/// fetches the head of pull request `number` from the default
+/// remote into `refs/remotes/<remote>/pr/<number>` and checks it out
+/// as local branch `pr-<number>`, which only ever gets fast-forwarded
+pub fn checkout_pull_request(
+	repo_path: &RepoPath,
+	number: u64,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<String> {
+	scope_time!("checkout_pull_request");
+
+	let repo = repo(repo_path)?;
+	let remote_name = get_default_remote_in_repo(&repo)?;
+	let mut remote = repo.find_remote(&remote_name)?;
+	let transport = Transport::new(&repo, &remote)?;
+
+	let branch = format!("pr-{}", number);
+	let branch_ref = format!("refs/heads/{}", branch);
+	let tracking =
+		format!("refs/remotes/{}/pr/{}", remote_name, number);
+
+	let mut options = FetchOptions::new();
+	let callbacks = Callbacks::new(None, basic_credential);
+	options.remote_callbacks(callbacks.callbacks());
+	options.proxy_options(transport.proxy_options());
+
+	remote
+		.fetch(
+			&[format!("+refs/pull/{}/head:{}", number, tracking)],
+			Some(&mut options),
+			None,
+		)
+		.map_err(|e| transport.map_err(e))?;
+
+	log_action(
+		&repo,
+		"fetch pull request",
+		&format!("{} #{}", remote_name, number),
+	);
+
+	let head = repo.find_commit(repo.refname_to_id(&tracking)?)?;
+	let checked_out = repo
+		.head()
+		.map_or(false, |r| r.name() == Some(branch_ref.as_str()));
+
+	match repo.refname_to_id(&branch_ref) {
+		Ok(local) if local == head.id() => (),
+		Ok(local)
+			if repo.graph_descendant_of(head.id(), local)? =>
+		{
+			if checked_out {
+				// safe checkout, refuses to touch local changes
+				repo.checkout_tree(head.as_object(), None)?;
+			}
+			repo.find_reference(&branch_ref)?
+				.set_target(head.id(), "pull request fast-forward")?;
+			log_action(&repo, "fast-forward", &branch);
+		}
+		Ok(_) => {
+			return Err(Error::Generic(format!(
+				"'{}' has commits pull request #{} lacks, it was fetched to '{}' only",
+				branch, number, tracking
+			)));
+		}
+		Err(_) => {
+			repo.branch(&branch, &head, false)?;
+		}
+	}
+
+	repo.config()?.set_str(
+		&pull_request_config_key(&branch),
+		&number.to_string(),
+	)?;
+
+	if !checked_out {
+		checkout_branch(repo_path, &branch_ref)?;
+	}
+
+	Ok(branch)
+}
+
+/// pull request number local `branch` was checked out from
+pub fn branch_pull_request(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Option<u64>> {
+	let repo = repo(repo_path)?;
+
+	Ok(get_config_string_repo(
+		&repo,
+		&pull_request_config_key(branch),
+	)?
+	.and_then(|number| number.parse().ok()))
+}
+
+#[cfg(feature = "github")]
+mod api {
+	use super::{Issue, PullRequest};
+	use crate::error::{Error, Result};
+	use serde::{de::IgnoredAny, Deserialize};
+
+	#[derive(Deserialize)]
+	struct User {
+		login: String,
+	}
+
+	#[derive(Deserialize)]
+	struct Head {
+		#[serde(rename = "ref")]
+		head_ref: String,
+	}
+
+	#[derive(Deserialize)]
+	struct ApiPullRequest {
+		number: u64,
+		title: String,
+		user: User,
+		head: Head,
+	}
+
+	#[derive(Deserialize)]
+	struct ApiIssue {
+		number: u64,
+		title: String,
+		pull_request: Option<IgnoredAny>,
+	}
+
+	fn get<T: serde::de::DeserializeOwned>(
+		url: &str,
+		token: Option<&str>,
+	) -> Result<T> {
+		let mut request = ureq::get(url)
+			.set("Accept", "application/vnd.github+json")
+			.set("User-Agent", "gitui");
+		if let Some(token) = token {
+			request = request
+				.set("Authorization", &format!("Bearer {}", token));
+		}
+
+		Ok(request
+			.call()
+			.map_err(|e| {
+				Error::Generic(format!(
+					"github request failed: {}",
+					e
+				))
+			})?
+			.into_json()?)
+	}
+
+	pub fn open_pull_requests(
+		owner: &str,
+		name: &str,
+		token: Option<&str>,
+	) -> Result<Vec<PullRequest>> {
+		let list: Vec<ApiPullRequest> = get(
+			&format!(
+				"https://api.github.com/repos/{}/{}/pulls?state=open&per_page=100",
+				owner, name
+			),
+			token,
+		)?;
+
+		Ok(list
+			.into_iter()
+			.map(|pr| PullRequest {
+				number: pr.number,
+				title: pr.title,
+				author: pr.user.login,
+				head_ref: pr.head.head_ref,
+			})
+			.collect())
+	}
+
+	pub fn recent_issues(
+		owner: &str,
+		name: &str,
+		token: Option<&str>,
+	) -> Result<Vec<Issue>> {
+		let list: Vec<ApiIssue> = get(
+			&format!(
+				"https://api.github.com/repos/{}/{}/issues?state=all&sort=updated&per_page=100",
+				owner, name
+			),
+			token,
+		)?;
+
+		Ok(list
+			.into_iter()
+			.map(|issue| Issue {
+				number: issue.number,
+				title: issue.title,
+				is_pull_request: issue.pull_request.is_some(),
+			})
+			.collect())
+	}
+}
+
+#[cfg(not(feature = "github"))]
+mod api {
+	use super::{Issue, PullRequest};
+	use crate::error::{Error, Result};
+
+	fn unsupported() -> Error {
+		Error::Generic(
+			"built without github support (feature `github`)".into(),
+		)
+	}
+
+	pub fn open_pull_requests(
+		_owner: &str,
+		_name: &str,
+		_token: Option<&str>,
+	) -> Result<Vec<PullRequest>> {
+		Err(unsupported())
+	}
+
+	pub fn recent_issues(
+		_owner: &str,
+		_name: &str,
+		_token: Option<&str>,
+	) -> Result<Vec<Issue>> {
+		Err(unsupported())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{
+		repo_clone, repo_init, write_commit_file,
+	};
+
+	#[test]
+	fn test_github_repo_from_url() {
+		let expected =
+			Some((String::from("extrawurst"), String::from("gitui")));
+
+		assert_eq!(
+			github_repo_from_url(
+				"https://github.com/extrawurst/gitui"
+			),
+			expected
+		);
+		assert_eq!(
+			github_repo_from_url(
+				"https://github.com/extrawurst/gitui.git/"
+			),
+			expected
+		);
+		assert_eq!(
+			github_repo_from_url(
+				"git@github.com:extrawurst/gitui.git"
+			),
+			expected
+		);
+		assert_eq!(
+			github_repo_from_url(
+				"ssh://git@github.com/extrawurst/gitui.git"
+			),
+			expected
+		);
+		assert_eq!(
+			github_repo_from_url("https://gitlab.com/foo/bar"),
+			None
+		);
+		assert_eq!(
+			github_repo_from_url("https://github.com/foo"),
+			None
+		);
+	}
+
+	#[test]
+	fn test_github_repo() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+
+		assert!(github_repo(repo_path).is_err());
+
+		repo.remote("origin", "git@github.com:extrawurst/gitui.git")
+			.unwrap();
+
+		assert_eq!(
+			github_repo(repo_path).unwrap(),
+			(String::from("extrawurst"), String::from("gitui"))
+		);
+	}
+
+	#[test]
+	fn test_checkout_pull_request() {
+		let (upstream_dir, upstream) = repo_init().unwrap();
+		let commit =
+			write_commit_file(&upstream, "pr.txt", "change", "pr");
+		upstream
+			.reference("refs/pull/7/head", commit.into(), false, "")
+			.unwrap();
+
+		let (clone_dir, clone) =
+			repo_clone(upstream_dir.path().to_str().unwrap())
+				.unwrap();
+		let clone_path: &RepoPath =
+			&clone_dir.path().to_str().unwrap().into();
+
+		let branch =
+			checkout_pull_request(clone_path, 7, None).unwrap();
+
+		assert_eq!(branch, "pr-7");
+		assert_eq!(
+			branch_pull_request(clone_path, &branch).unwrap(),
+			Some(7)
+		);
+		assert_eq!(
+			branch_pull_request(clone_path, "master").unwrap(),
+			None
+		);
+		assert_eq!(
+			clone.refname_to_id("refs/remotes/origin/pr/7").unwrap(),
+			commit.into()
+		);
+
+		// the pull request got more commits, `pr-7` is fast-forwarded
+		let update =
+			write_commit_file(&upstream, "pr.txt", "update", "pr2");
+		upstream
+			.reference("refs/pull/7/head", update.into(), true, "")
+			.unwrap();
+		checkout_pull_request(clone_path, 7, None).unwrap();
+		assert_eq!(
+			clone.refname_to_id("refs/heads/pr-7").unwrap(),
+			update.into()
+		);
+		assert_eq!(
+			std::fs::read_to_string(clone_dir.path().join("pr.txt"))
+				.unwrap(),
+			"update"
+		);
+
+		// local commits on `pr-7` are never overwritten
+		let local =
+			write_commit_file(&clone, "local.txt", "mine", "local");
+		let rewritten =
+			write_commit_file(&upstream, "pr.txt", "force", "pr3");
+		upstream
+			.reference("refs/pull/7/head", rewritten.into(), true, "")
+			.unwrap();
+		assert!(checkout_pull_request(clone_path, 7, None).is_err());
+		assert_eq!(
+			clone.refname_to_id("refs/heads/pr-7").unwrap(),
+			local.into()
+		);
+	}
+}
@@ -0,0 +1,351 @@
+//! content search over the files of a commit or the workdir
+
+use super::{utils::work_dir, CommitId, RepoPath};
+use crate::{
+	error::{Error, Result},
+	sync::repository::repo,
+};
+use git2::{
+	ObjectType, Repository, Status, StatusOptions, TreeWalkMode,
+	TreeWalkResult,
+};
+use regex::{Regex, RegexBuilder};
+use scopetime::scope_time;
+use std::{fs, ops::Range};
+
+/// search stops after this many matches
+pub const GREP_MAX_MATCHES: usize = 1000;
+
+/// like git we treat files with a nul byte in here as binary
+const BINARY_CHECK_LEN: usize = 8000;
+
+/// what to search in
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum GrepSource {
+	/// tracked and untracked (but not ignored) files in the workdir
+	WorkDir,
+	/// files in the tree of a commit
+	Commit(CommitId),
+}
+
+///
+#[derive(Debug, Hash, Clone, Default, PartialEq, Eq)]
+pub struct GrepOptions {
+	///
+	pub pattern: String,
+	/// treat `pattern` as regular expression instead of plain text
+	pub regex: bool,
+	///
+	pub ignore_case: bool,
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepMatch {
+	/// path relative to the repo root
+	pub path: String,
+	/// 1-based
+	pub line_number: usize,
+	/// content of the line (lossy utf8)
+	pub line: String,
+	/// byte range of the first match in `line`
+	pub range: Range<usize>,
+}
+
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GrepResult {
+	///
+	pub matches: Vec<GrepMatch>,
+	/// `true` if the search stopped at `GREP_MAX_MATCHES`
+	pub truncated: bool,
+}
+
+/// searches all text files of `source` for lines matching `options`.
+/// stops early (returning what was found so far) once `is_cancelled`
+pub fn grep(
+	repo_path: &RepoPath,
+	source: GrepSource,
+	options: &GrepOptions,
+	is_cancelled: &dyn Fn() -> bool,
+) -> Result<GrepResult> {
+	scope_time!("grep");
+
+	let regex = build_regex(options)?;
+	let repo = repo(repo_path)?;
+
+	let mut result = GrepResult::default();
+
+	for path in source_files(&repo, source)? {
+		if is_cancelled() {
+			break;
+		}
+
+		let content = match file_content(&repo, source, &path) {
+			Ok(content) => content,
+			// deleted in the workdir or binary
+			Err(Error::Io(_) | Error::BinaryFile) => continue,
+			Err(e) => return Err(e),
+		};
+
+		if grep_content(&regex, &path, &content, &mut result) {
+			break;
+		}
+	}
+
+	Ok(result)
+}
+
+/// content of `path` in `source` as shown in search results
+pub fn grep_file_content(
+	repo_path: &RepoPath,
+	source: GrepSource,
+	path: &str,
+) -> Result<String> {
+	let repo = repo(repo_path)?;
+	file_content(&repo, source, path)
+}
+
+fn build_regex(options: &GrepOptions) -> Result<Regex> {
+	let pattern = if options.regex {
+		options.pattern.clone()
+	} else {
+		regex::escape(&options.pattern)
+	};
+
+	Ok(RegexBuilder::new(&pattern)
+		.case_insensitive(options.ignore_case)
+		.build()?)
+}
+
+/// adds the matching lines of `content` to `result`,
+/// returns `true` once `GREP_MAX_MATCHES` got reached
+fn grep_content(
+	regex: &Regex,
+	path: &str,
+	content: &str,
+	result: &mut GrepResult,
+) -> bool {
+	for (idx, line) in content.lines().enumerate() {
+		if let Some(m) = regex.find(line) {
+			if result.matches.len() >= GREP_MAX_MATCHES {
+				result.truncated = true;
+				return true;
+			}
+
+			result.matches.push(GrepMatch {
+				path: path.to_string(),
+				line_number: idx + 1,
+				line: line.to_string(),
+				range: m.range(),
+			});
+		}
+	}
+
+	false
+}
+
+/// sorted paths of all files to search in
+fn source_files(
+	repo: &Repository,
+	source: GrepSource,
+) -> Result<Vec<String>> {
+	let mut files = Vec::new();
+
+	match source {
+		GrepSource::Commit(id) => {
+			let tree = repo.find_commit(id.into())?.tree()?;
+			tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+				if entry.kind() == Some(ObjectType::Blob) {
+					if let Some(name) = entry.name() {
+						files.push(format!("{}{}", root, name));
+					}
+				}
+				TreeWalkResult::Ok
+			})?;
+		}
+		GrepSource::WorkDir => {
+			// submodules show up as commits in the index
+			const GIT_FILEMODE_COMMIT: u32 = 0o160_000;
+
+			for entry in repo.index()?.iter() {
+				if entry.mode != GIT_FILEMODE_COMMIT {
+					files.push(
+						String::from_utf8_lossy(&entry.path)
+							.to_string(),
+					);
+				}
+			}
+
+			let statuses = repo.statuses(Some(
+				StatusOptions::new()
+					.include_untracked(true)
+					.recurse_untracked_dirs(true)
+					.include_ignored(false),
+			))?;
+			for entry in statuses.iter() {
+				if entry.status().contains(Status::WT_NEW) {
+					if let Some(path) = entry.path() {
+						files.push(path.to_string());
+					}
+				}
+			}
+		}
+	}
+
+	files.sort();
+	files.dedup();
+
+	Ok(files)
+}
+
+fn file_content(
+	repo: &Repository,
+	source: GrepSource,
+	path: &str,
+) -> Result<String> {
+	let bytes = match source {
+		GrepSource::Commit(id) => {
+			let tree = repo.find_commit(id.into())?.tree()?;
+			let entry = tree.get_path(path.as_ref())?;
+			repo.find_blob(entry.id())?.content().to_vec()
+		}
+		GrepSource::WorkDir => fs::read(work_dir(repo)?.join(path))?,
+	};
+
+	if bytes.iter().take(BINARY_CHECK_LEN).any(|b| *b == 0) {
+		return Err(Error::BinaryFile);
+	}
+
+	Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use pretty_assertions::assert_eq;
+	use std::{fs::File, io::Write};
+
+	fn search(
+		repo_path: &RepoPath,
+		source: GrepSource,
+		pattern: &str,
+		regex: bool,
+		ignore_case: bool,
+	) -> Vec<(String, usize)> {
+		grep(
+			repo_path,
+			source,
+			&GrepOptions {
+				pattern: pattern.to_string(),
+				regex,
+				ignore_case,
+			},
+			&|| false,
+		)
+		.unwrap()
+		.matches
+		.into_iter()
+		.map(|m| (m.path, m.line_number))
+		.collect()
+	}
+
+	#[test]
+	fn test_grep() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(
+			&repo,
+			"foo.txt",
+			"first\nSecond line\nthird",
+			"c1",
+		);
+		File::create(root.join(".gitignore"))
+			.unwrap()
+			.write_all(b"ignored.txt\n")
+			.unwrap();
+		File::create(root.join("ignored.txt"))
+			.unwrap()
+			.write_all(b"second")
+			.unwrap();
+		File::create(root.join("new.txt"))
+			.unwrap()
+			.write_all(b"a second\0")
+			.unwrap();
+		File::create(root.join("untracked.txt"))
+			.unwrap()
+			.write_all(b"xx\nsecond")
+			.unwrap();
+
+		assert_eq!(
+			search(
+				repo_path,
+				GrepSource::WorkDir,
+				"second",
+				false,
+				true
+			),
+			vec![
+				(String::from("foo.txt"), 2),
+				(String::from("untracked.txt"), 2)
+			]
+		);
+		assert_eq!(
+			search(
+				repo_path,
+				GrepSource::WorkDir,
+				"second",
+				false,
+				false
+			),
+			vec![(String::from("untracked.txt"), 2)]
+		);
+		assert_eq!(
+			search(
+				repo_path,
+				GrepSource::Commit(c1),
+				"^(f|t)",
+				true,
+				false
+			),
+			vec![
+				(String::from("foo.txt"), 1),
+				(String::from("foo.txt"), 3)
+			]
+		);
+		assert!(matches!(
+			grep(
+				repo_path,
+				GrepSource::WorkDir,
+				&GrepOptions {
+					pattern: String::from("("),
+					regex: true,
+					ignore_case: false,
+				},
+				&|| false,
+			),
+			Err(Error::Regex(_))
+		));
+	}
+
+	#[test]
+	fn test_grep_range() {
+		let regex = build_regex(&GrepOptions {
+			pattern: String::from("a.c"),
+			regex: false,
+			ignore_case: false,
+		})
+		.unwrap();
+
+		let mut result = GrepResult::default();
+		grep_content(&regex, "f", "abc\nxa.cx", &mut result);
+
+		assert_eq!(result.matches.len(), 1);
+		assert_eq!(result.matches[0].line_number, 2);
+		assert_eq!(result.matches[0].range, 1..4);
+	}
+}
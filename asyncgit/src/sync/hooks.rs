@@ -1,5 +1,8 @@
-use super::{repository::repo, RepoPath};
+use super::{
+	repository::repo, shell::posix_shell, CommitId, RepoPath,
+};
 use crate::error::{self, Result};
+use git2::{ErrorCode, Repository};
 use scopetime::scope_time;
 use std::{
 	fs::File,
@@ -10,6 +13,8 @@ use std::{
 };
 
 const HOOK_POST_COMMIT: &str = "post-commit";
+const HOOK_POST_CHECKOUT: &str = "post-checkout";
+const HOOK_POST_MERGE: &str = "post-merge";
 const HOOK_PRE_COMMIT: &str = "pre-commit";
 const HOOK_COMMIT_MSG: &str = "commit-msg";
 const HOOK_COMMIT_MSG_TEMP_FILE: &str = "COMMIT_EDITMSG";
@@ -29,27 +34,22 @@ impl HookPaths {
 			.to_path_buf();
 
 		let git_dir = repo.path().to_path_buf();
-		let hooks_path = repo
+		let hooks_path = match repo
 			.config()
 			.and_then(|config| config.get_string("core.hooksPath"))
-			.map_or_else(
-				|e| {
+		{
+			Ok(hooks_path) => Self::expand(&hooks_path)?,
+			Err(e) => {
+				if e.code() != ErrorCode::NotFound {
 					log::error!("hookspath error: {}", e);
-					repo.path().to_path_buf().join("hooks/")
-				},
-				PathBuf::from,
-			);
-
-		let hook = hooks_path.join(hook);
-
-		let hook = shellexpand::full(
-			hook.as_os_str()
-				.to_str()
-				.ok_or(error::Error::PathString)?,
-		)?;
+				}
+				common_dir(&repo).join("hooks")
+			}
+		};
 
-		let hook = PathBuf::from_str(hook.as_ref())
-			.map_err(|_| error::Error::PathString)?;
+		// relative to where hooks run like git does, husky for
+		// example sets it to `.husky`
+		let hook = pwd.join(hooks_path).join(hook);
 
 		Ok(Self {
 			git: git_dir,
@@ -58,6 +58,13 @@ impl HookPaths {
 		})
 	}
 
+	fn expand(path: &str) -> Result<PathBuf> {
+		let path = shellexpand::full(path)?;
+
+		PathBuf::from_str(path.as_ref())
+			.map_err(|_| error::Error::PathString)
+	}
+
 	pub fn is_executable(&self) -> bool {
 		self.hook.exists() && is_executable(&self.hook)
 	}
@@ -65,33 +72,59 @@ impl HookPaths {
 	/// this function calls hook scripts based on conventions documented here
 	/// see <https://git-scm.com/docs/githooks>
 	pub fn run_hook(&self, args: &[&str]) -> Result<HookResult> {
-		let arg_str = format!("{:?} {}", self.hook, args.join(" "));
-		let bash_args = vec!["-c".to_string(), arg_str];
+		let (success, output) = self.run_hook_output(args)?;
+
+		if success {
+			Ok(HookResult::Ok)
+		} else {
+			Ok(HookResult::NotOk(output))
+		}
+	}
 
+	/// exit status and combined stdout/stderr of the hook
+	fn run_hook_output(
+		&self,
+		args: &[&str],
+	) -> Result<(bool, String)> {
 		log::trace!("run hook '{:?}' in '{:?}'", self.hook, self.pwd);
 
-		let output = Command::new("bash")
-			.args(bash_args)
+		// git's own shell first, a `bash` in `PATH` on windows might
+		// well be the one of WSL
+		let shell =
+			posix_shell().unwrap_or_else(|| PathBuf::from("bash"));
+
+		// the hook is `$0` so scripts can locate themselves (husky does),
+		// args are passed as is no matter what they contain
+		let output = Command::new(shell)
+			.arg("-c")
+			.arg(r#""$0" "$@""#)
+			.arg(&self.hook)
+			.args(args)
 			.current_dir(&self.pwd)
-			// This call forces Command to handle the Path environment correctly on windows,
-			// the specific env set here does not matter
-			// see https://github.com/rust-lang/rust/issues/37519
-			.env(
-				"DUMMY_ENV_TO_FIX_WINDOWS_CMD_RUNS",
-				"FixPathHandlingOnWindows",
-			)
+			.env("GIT_DIR", &self.git)
+			.env("GIT_INDEX_FILE", self.git.join("index"))
 			.output()?;
 
-		if output.status.success() {
-			Ok(HookResult::Ok)
-		} else {
-			let err = String::from_utf8_lossy(&output.stderr);
-			let out = String::from_utf8_lossy(&output.stdout);
-			let formatted = format!("{}{}", out, err);
+		let err = String::from_utf8_lossy(&output.stderr);
+		let out = String::from_utf8_lossy(&output.stdout);
+
+		Ok((output.status.success(), format!("{}{}", out, err)))
+	}
+}
 
-			Ok(HookResult::NotOk(formatted))
+/// where the default `hooks` dir is, shared by all linked worktrees
+fn common_dir(repo: &Repository) -> PathBuf {
+	let git_dir = repo.path();
+
+	if repo.is_worktree() {
+		if let Ok(common_dir) =
+			std::fs::read_to_string(git_dir.join("commondir"))
+		{
+			return git_dir.join(common_dir.trim());
 		}
 	}
+
+	git_dir.to_path_buf()
 }
 
 /// this hook is documented here <https://git-scm.com/docs/githooks#_commit_msg>
@@ -152,6 +185,91 @@ pub fn hooks_post_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	}
 }
 
+/// hooks run after the fact, they cannot change the outcome anymore
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostHook {
+	/// <https://git-scm.com/docs/githooks#_post_commit>
+	Commit,
+	/// <https://git-scm.com/docs/githooks#_post_checkout>
+	Checkout {
+		///
+		prev_head: CommitId,
+		///
+		new_head: CommitId,
+		/// `false` for checking out files only
+		branch: bool,
+	},
+	/// <https://git-scm.com/docs/githooks#_post_merge>
+	Merge {
+		///
+		squash: bool,
+	},
+}
+
+impl PostHook {
+	///
+	pub const fn name(&self) -> &'static str {
+		match self {
+			Self::Commit => HOOK_POST_COMMIT,
+			Self::Checkout { .. } => HOOK_POST_CHECKOUT,
+			Self::Merge { .. } => HOOK_POST_MERGE,
+		}
+	}
+
+	fn args(&self) -> Vec<String> {
+		match self {
+			Self::Commit => Vec::new(),
+			Self::Checkout {
+				prev_head,
+				new_head,
+				branch,
+			} => vec![
+				prev_head.to_string(),
+				new_head.to_string(),
+				String::from(if *branch { "1" } else { "0" }),
+			],
+			Self::Merge { squash } => {
+				vec![String::from(if *squash { "1" } else { "0" })]
+			}
+		}
+	}
+}
+
+/// outcome of a hook that ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOutput {
+	///
+	pub hook: &'static str,
+	///
+	pub success: bool,
+	/// stdout followed by stderr
+	pub output: String,
+}
+
+/// runs `hook`, `None` if the repo has no such hook
+pub fn hooks_post(
+	repo_path: &RepoPath,
+	hook: &PostHook,
+) -> Result<Option<HookOutput>> {
+	scope_time!("hooks_post");
+
+	let paths = HookPaths::new(repo_path, hook.name())?;
+
+	if !paths.is_executable() {
+		return Ok(None);
+	}
+
+	let args = hook.args();
+	let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+	let (success, output) = paths.run_hook_output(&args)?;
+
+	Ok(Some(HookOutput {
+		hook: hook.name(),
+		success,
+		output,
+	}))
+}
+
 ///
 #[derive(Debug, PartialEq, Eq)]
 pub enum HookResult {
@@ -318,6 +436,104 @@ exit 1
 		);
 	}
 
+	#[test]
+	fn test_pre_commit_husky_hookspath() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let husky = root.join(".husky");
+		fs::create_dir_all(husky.join("_")).unwrap();
+		File::create(husky.join("_").join("husky.sh"))
+			.unwrap()
+			.write_all(b"echo 'husky'\n")
+			.unwrap();
+
+		let hook = b"#!/bin/sh
+. \"$(dirname -- \"$0\")/_/husky.sh\"
+echo \"$1\"
+exit 1
+        ";
+
+		create_hook_in_path(&husky.join(HOOK_COMMIT_MSG), hook);
+		repo.config()
+			.unwrap()
+			.set_str("core.hooksPath", ".husky")
+			.unwrap();
+
+		let mut msg = String::from("test");
+		let res = hooks_commit_msg(repo_path, &mut msg).unwrap();
+
+		assert_eq!(
+			res,
+			HookResult::NotOk(format!(
+				"husky\n{}\n",
+				repo.path().join(HOOK_COMMIT_MSG_TEMP_FILE).display()
+			))
+		);
+	}
+
+	#[test]
+	fn test_pre_commit_env() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let hook = b"#!/bin/sh
+echo \"$GIT_DIR\"
+echo \"$GIT_INDEX_FILE\"
+exit 1
+        ";
+
+		create_hook(repo_path, HOOK_PRE_COMMIT, hook);
+		let res = hooks_pre_commit(repo_path).unwrap();
+
+		assert_eq!(
+			res,
+			HookResult::NotOk(format!(
+				"{}\n{}\n",
+				repo.path().display(),
+				repo.path().join("index").display()
+			))
+		);
+	}
+
+	#[test]
+	fn test_post_checkout() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let hook = PostHook::Checkout {
+			prev_head: CommitId::default(),
+			new_head: CommitId::default(),
+			branch: true,
+		};
+
+		assert_eq!(hooks_post(repo_path, &hook).unwrap(), None);
+
+		create_hook(
+			repo_path,
+			HOOK_POST_CHECKOUT,
+			b"#!/bin/sh
+echo \"$3\"
+exit 1
+        ",
+		);
+
+		assert_eq!(
+			hooks_post(repo_path, &hook).unwrap(),
+			Some(HookOutput {
+				hook: HOOK_POST_CHECKOUT,
+				success: false,
+				output: String::from("1\n"),
+			})
+		);
+	}
+
 	#[test]
 	fn test_pre_commit_fail_bare() {
 		let (git_root, _repo) = repo_init_bare().unwrap();
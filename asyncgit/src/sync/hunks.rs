@@ -5,7 +5,7 @@ use super::{
 use crate::{
 	error::{Error, Result},
 	hash,
-	sync::repository::repo,
+	sync::{action_log::log_action, repository::repo},
 };
 use git2::{ApplyLocation, ApplyOptions, Diff};
 use scopetime::scope_time;
@@ -32,6 +32,8 @@ pub fn stage_hunk(
 
 	repo.apply(&diff, ApplyLocation::Index, Some(&mut opt))?;
 
+	log_action(&repo, "stage hunk", file_path);
+
 	Ok(())
 }
 
@@ -61,6 +63,8 @@ pub fn reset_hunk(
 
 		repo.apply(&diff, ApplyLocation::WorkDir, Some(&mut opt))?;
 
+		log_action(&repo, "discard hunk", file_path);
+
 		Ok(())
 	} else {
 		Err(Error::Generic("hunk not found".to_string()))
@@ -142,6 +146,8 @@ pub fn unstage_hunk(
 		repo.apply(&diff, ApplyLocation::Index, Some(&mut opt))?;
 	}
 
+	log_action(&repo, "unstage hunk", file_path);
+
 	Ok(count == 1)
 }
 
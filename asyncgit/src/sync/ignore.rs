@@ -1,29 +1,153 @@
-use super::{utils::work_dir, RepoPath};
+use super::{action_log::log_action, utils::work_dir, RepoPath};
 use crate::{
 	error::{Error, Result},
 	sync::repository::repo,
 };
+use git2::{Status, StatusOptions, StatusShow};
 use scopetime::scope_time;
 use std::{
 	fs::{File, OpenOptions},
 	io::{Read, Seek, SeekFrom, Write},
 	path::Path,
+	process::{Command, Stdio},
 };
 
 static GITIGNORE: &str = ".gitignore";
 
+/// the rule that ignores a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoreSource {
+	/// the ignore file like `.gitignore`, `.git/info/exclude` or the
+	/// `core.excludesFile`
+	pub file: String,
+	/// 1-based
+	pub line: usize,
+	///
+	pub pattern: String,
+}
+
+/// a file or folder (ending with `/`) left out of the status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredFile {
+	///
+	pub path: String,
+	/// `None` if `git` is not available to tell
+	pub source: Option<IgnoreSource>,
+}
+
+/// the ignored files of the work dir, ignored folders are not
+/// descended into; their rules are looked up with
+/// `git check-ignore` as libgit2 can't tell which one matched
+pub fn get_ignored_files(
+	repo_path: &RepoPath,
+) -> Result<Vec<IgnoredFile>> {
+	scope_time!("get_ignored_files");
+
+	let repo = repo(repo_path)?;
+
+	let mut options = StatusOptions::default();
+	options
+		.show(StatusShow::Workdir)
+		.include_untracked(true)
+		.include_ignored(true)
+		.recurse_ignored_dirs(false);
+
+	let statuses = repo.statuses(Some(&mut options))?;
+	let mut paths = statuses
+		.iter()
+		.filter(|e| e.status().contains(Status::IGNORED))
+		.filter_map(|e| e.path().map(String::from))
+		.collect::<Vec<_>>();
+	paths.sort();
+
+	let mut sources = ignore_sources(work_dir(&repo)?, &paths)
+		.unwrap_or_else(|e| {
+			log::error!("check-ignore error: {}", e);
+			Vec::new()
+		});
+
+	Ok(paths
+		.into_iter()
+		.map(|path| {
+			let source = sources
+				.iter()
+				.position(|(p, _)| *p == path)
+				.map(|idx| sources.swap_remove(idx).1);
+
+			IgnoredFile { path, source }
+		})
+		.collect())
+}
+
+fn ignore_sources(
+	work_dir: &Path,
+	paths: &[String],
+) -> Result<Vec<(String, IgnoreSource)>> {
+	if paths.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let mut child = Command::new("git")
+		.args(["check-ignore", "--verbose", "-z", "--stdin"])
+		.current_dir(work_dir)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		for path in paths {
+			stdin.write_all(path.as_bytes())?;
+			stdin.write_all(b"\0")?;
+		}
+	}
+
+	let output = child.wait_with_output()?;
+
+	Ok(parse_check_ignore(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `git check-ignore -v -z` prints `source NUL line NUL pattern NUL
+/// path NUL` per path
+fn parse_check_ignore(output: &str) -> Vec<(String, IgnoreSource)> {
+	let fields = output.split('\0').collect::<Vec<_>>();
+
+	fields
+		.chunks_exact(4)
+		.filter_map(|entry| {
+			Some((
+				entry[3].to_string(),
+				IgnoreSource {
+					file: entry[0].to_string(),
+					line: entry[1].parse().ok()?,
+					pattern: entry[2].to_string(),
+				},
+			))
+		})
+		.collect()
+}
+
 /// add file or path to root ignore file
 pub fn add_to_ignore(
 	repo_path: &RepoPath,
 	path_to_ignore: &str,
 ) -> Result<()> {
-	scope_time!("add_to_ignore");
+	add_paths_to_ignore(repo_path, &[path_to_ignore])
+}
+
+/// like `add_to_ignore` for many paths in one write
+pub fn add_paths_to_ignore(
+	repo_path: &RepoPath,
+	paths_to_ignore: &[&str],
+) -> Result<()> {
+	scope_time!("add_paths_to_ignore");
 
 	let repo = repo(repo_path)?;
 
-	if Path::new(path_to_ignore).file_name()
-		== Path::new(GITIGNORE).file_name()
-	{
+	if paths_to_ignore.iter().any(|path| {
+		Path::new(path).file_name()
+			== Path::new(GITIGNORE).file_name()
+	}) {
 		return Err(Error::Generic(String::from(
 			"cannot ignore gitignore",
 		)));
@@ -43,9 +167,11 @@ pub fn add_to_ignore(
 		file,
 		"{}{}",
 		if optional_newline { "\n" } else { "" },
-		path_to_ignore
+		paths_to_ignore.join("\n")
 	)?;
 
+	log_action(&repo, "ignore", &paths_to_ignore.join(", "));
+
 	Ok(())
 }
 
@@ -63,7 +189,11 @@ fn file_ends_with_newline(file: &Path) -> Result<bool> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::sync::{tests::repo_init, utils::repo_write_file};
+	use crate::sync::{
+		status::{get_status, StatusType},
+		tests::repo_init,
+		utils::repo_write_file,
+	};
 	use io::BufRead;
 	use pretty_assertions::assert_eq;
 	use std::{fs::File, io, path::Path};
@@ -140,6 +270,117 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_append_paths() -> Result<()> {
+		let ignore_file_path = Path::new(".gitignore");
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(&root.join(ignore_file_path))?
+			.write_all(b"foo")?;
+
+		add_paths_to_ignore(repo_path, &["bar.txt", "baz/"])?;
+
+		let lines = read_lines(&root.join(ignore_file_path))?
+			.collect::<io::Result<Vec<_>>>()?;
+		assert_eq!(lines, vec!["foo", "bar.txt", "baz/"]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_check_ignore() {
+		assert_eq!(
+			parse_check_ignore(
+				".gitignore\x003\0*.log\0a.log\0/home/x/.gitignore_global\x001\0tmp/\0tmp/\0"
+			),
+			vec![
+				(
+					String::from("a.log"),
+					IgnoreSource {
+						file: String::from(".gitignore"),
+						line: 3,
+						pattern: String::from("*.log"),
+					}
+				),
+				(
+					String::from("tmp/"),
+					IgnoreSource {
+						file: String::from(
+							"/home/x/.gitignore_global"
+						),
+						line: 1,
+						pattern: String::from("tmp/"),
+					}
+				),
+			]
+		);
+	}
+
+	#[test]
+	fn test_ignored_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(&repo, ".gitignore", "*.log\n").unwrap();
+		repo_write_file(&repo, "a.log", "").unwrap();
+		repo_write_file(&repo, "b.txt", "").unwrap();
+
+		let ignored = get_ignored_files(repo_path).unwrap();
+
+		assert_eq!(ignored.len(), 1);
+		assert_eq!(ignored[0].path, "a.log");
+		if let Some(source) = &ignored[0].source {
+			assert_eq!(source.file, ".gitignore");
+			assert_eq!(source.line, 1);
+			assert_eq!(source.pattern, "*.log");
+		}
+	}
+
+	#[test]
+	fn test_status_honors_exclude_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		// outside of the work dir
+		let excludes_file = repo.path().join("global_excludes");
+		std::fs::write(&excludes_file, "b.txt\n").unwrap();
+		repo.config()
+			.unwrap()
+			.set_str(
+				"core.excludesfile",
+				excludes_file.to_str().unwrap(),
+			)
+			.unwrap();
+		std::fs::write(
+			repo.path().join("info").join("exclude"),
+			"a.txt\n",
+		)
+		.unwrap();
+
+		repo_write_file(&repo, "a.txt", "").unwrap();
+		repo_write_file(&repo, "b.txt", "").unwrap();
+		repo_write_file(&repo, "c.txt", "").unwrap();
+
+		let status =
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+
+		assert_eq!(
+			status
+				.iter()
+				.map(|s| s.path.as_str())
+				.collect::<Vec<_>>(),
+			vec!["c.txt"]
+		);
+	}
+
 	#[test]
 	fn test_ignore_ignore() {
 		let ignore_file_path = Path::new(".gitignore");
@@ -0,0 +1,161 @@
+//! an index libgit2 cannot read fails every status with an opaque
+//! error: `index_problem` tells what is wrong with it and
+//! `rebuild_index` gets the repository working again
+
+use super::{repository::repo, RepoPath};
+use crate::error::Result;
+use scopetime::scope_time;
+use std::{fs, io::ErrorKind};
+
+const SIGNATURE: &[u8] = b"DIRC";
+/// the header followed by the checksum of an empty index
+const MIN_SIZE: usize = 12 + 20;
+/// where `rebuild_index` moves the broken index
+const BROKEN_INDEX: &str = "index.broken";
+
+/// why the index cannot be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexProblem {
+	/// too short to even hold a header
+	Truncated,
+	/// no `DIRC` signature, so no index at all
+	BadSignature,
+	/// git writes versions 2 to 4 only
+	UnsupportedVersion(u32),
+	/// the header is fine but reading the entries or extensions
+	/// fails, the libgit2 error
+	Unreadable(String),
+}
+
+/// what is wrong with the index, `None` if it is fine or missing
+pub fn index_problem(
+	repo_path: &RepoPath,
+) -> Result<Option<IndexProblem>> {
+	scope_time!("index_problem");
+
+	let repo = repo(repo_path)?;
+	let content = match fs::read(repo.path().join("index")) {
+		Ok(content) => content,
+		Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+		Err(e) => return Err(e.into()),
+	};
+
+	if content.len() < MIN_SIZE {
+		return Ok(Some(IndexProblem::Truncated));
+	}
+	if &content[..4] != SIGNATURE {
+		return Ok(Some(IndexProblem::BadSignature));
+	}
+
+	let version = u32::from_be_bytes([
+		content[4], content[5], content[6], content[7],
+	]);
+	if !(2..=4).contains(&version) {
+		return Ok(Some(IndexProblem::UnsupportedVersion(version)));
+	}
+
+	Ok(repo
+		.index()
+		.err()
+		.map(|e| IndexProblem::Unreadable(e.message().to_string())))
+}
+
+/// replaces the index with the tree of `HEAD`
+///
+/// like `git reset --mixed` would if it could read the index: the
+/// working directory stays as it is, staged changes become unstaged.
+/// the broken index is kept as `index.broken` in the git dir
+pub fn rebuild_index(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("rebuild_index");
+
+	let repo = repo(repo_path)?;
+	let path = repo.path().join("index");
+	if path.exists() {
+		fs::rename(&path, repo.path().join(BROKEN_INDEX))?;
+	}
+
+	let mut index = repo.index()?;
+	// an unborn branch has no tree yet
+	if let Ok(tree) = repo.head().and_then(|head| head.peel_to_tree())
+	{
+		index.read_tree(&tree)?;
+	} else {
+		index.clear()?;
+	}
+	index.write()?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		status::{get_status, StatusType},
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_index_problem() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+		let index = repo.path().join("index");
+
+		assert_eq!(index_problem(repo_path).unwrap(), None);
+
+		fs::write(&index, b"DIRC").unwrap();
+		assert_eq!(
+			index_problem(repo_path).unwrap(),
+			Some(IndexProblem::Truncated)
+		);
+
+		fs::write(&index, [b'x'; MIN_SIZE]).unwrap();
+		assert_eq!(
+			index_problem(repo_path).unwrap(),
+			Some(IndexProblem::BadSignature)
+		);
+
+		let mut content = b"DIRC\0\0\0\x07".to_vec();
+		content.resize(MIN_SIZE, 0);
+		fs::write(&index, &content).unwrap();
+		assert_eq!(
+			index_problem(repo_path).unwrap(),
+			Some(IndexProblem::UnsupportedVersion(7))
+		);
+
+		// a valid header with a wrong checksum
+		content[7] = 2;
+		fs::write(&index, &content).unwrap();
+		assert!(matches!(
+			index_problem(repo_path).unwrap(),
+			Some(IndexProblem::Unreadable(_))
+		));
+	}
+
+	#[test]
+	fn test_rebuild_index() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "first");
+		fs::write(root.join("a.txt"), "b").unwrap();
+		let index = repo.path().join("index");
+		let content = fs::read(&index).unwrap();
+		fs::write(&index, &content[..content.len() / 2]).unwrap();
+
+		assert!(get_status(repo_path, StatusType::WorkingDir, None)
+			.is_err());
+
+		rebuild_index(repo_path).unwrap();
+
+		assert_eq!(index_problem(repo_path).unwrap(), None);
+		assert!(repo.path().join(BROKEN_INDEX).exists());
+		let status =
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+		assert_eq!(status.len(), 1);
+		assert_eq!(status[0].path, "a.txt");
+	}
+}
@@ -0,0 +1,147 @@
+//! git lfs pointer files, see
+//! <https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md>
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::{Error, Result};
+use once_cell::sync::Lazy;
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+const POINTER_VERSION: &str = "https://git-lfs.github.com/spec/v1";
+/// pointer files are always smaller than this
+pub const POINTER_MAX_SIZE: u64 = 1024;
+
+/// what git stores in place of a file tracked by lfs
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct LfsPointer {
+	/// `<hash method>:<hash>` of the actual content
+	pub oid: String,
+	/// size of the actual content in bytes
+	pub size: u64,
+}
+
+impl LfsPointer {
+	/// `None` unless `content` is a valid pointer file
+	pub fn parse(content: &[u8]) -> Option<Self> {
+		if u64::try_from(content.len()).ok()? >= POINTER_MAX_SIZE {
+			return None;
+		}
+
+		let content = std::str::from_utf8(content).ok()?;
+		let mut lines = content.lines();
+
+		if lines.next()?.strip_prefix("version ")? != POINTER_VERSION
+		{
+			return None;
+		}
+
+		let mut oid = None;
+		let mut size = None;
+		for line in lines {
+			let (key, value) = line.split_once(' ')?;
+			match key {
+				"oid" => oid = Some(value.to_string()),
+				"size" => size = Some(value.parse().ok()?),
+				_ => (),
+			}
+		}
+
+		Some(Self {
+			oid: oid?,
+			size: size?,
+		})
+	}
+
+	/// oid shortened like a commit hash, without the hash method
+	pub fn short_oid(&self) -> &str {
+		let hash = self
+			.oid
+			.split_once(':')
+			.map_or(self.oid.as_str(), |(_, hash)| hash);
+
+		hash.get(..8).unwrap_or(hash)
+	}
+
+	fn to_pointer_file(&self) -> String {
+		format!(
+			"version {}\noid {}\nsize {}\n",
+			POINTER_VERSION, self.oid, self.size
+		)
+	}
+}
+
+/// whether the `git lfs` cli is installed, checked once
+pub fn lfs_available() -> bool {
+	static AVAILABLE: Lazy<bool> = Lazy::new(|| {
+		Command::new("git")
+			.args(["lfs", "version"])
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.status()
+			.map_or(false, |status| status.success())
+	});
+
+	*AVAILABLE
+}
+
+/// actual content of the object `pointer` refers to via
+/// `git lfs smudge`, downloads it if it is not in the local lfs store
+pub fn lfs_smudge(
+	repo_path: &RepoPath,
+	pointer: &LfsPointer,
+) -> Result<Vec<u8>> {
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let mut child = Command::new("git")
+		.args(["lfs", "smudge"])
+		.current_dir(work_dir)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		stdin.write_all(pointer.to_pointer_file().as_bytes())?;
+	}
+
+	let output = child.wait_with_output()?;
+
+	if !output.status.success() {
+		return Err(Error::Generic(format!(
+			"git lfs smudge failed: {}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		)));
+	}
+
+	Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const POINTER: &str = "version https://git-lfs.github.com/spec/v1
+oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393
+size 12345
+";
+
+	#[test]
+	fn test_parse() {
+		let pointer = LfsPointer::parse(POINTER.as_bytes()).unwrap();
+
+		assert_eq!(pointer.size, 12345);
+		assert_eq!(pointer.short_oid(), "4d7a2146");
+		assert_eq!(pointer.to_pointer_file(), POINTER);
+
+		assert_eq!(LfsPointer::parse(b"size 12345\n"), None);
+		assert_eq!(
+			LfsPointer::parse(
+				POINTER.replace("size 12345\n", "").as_bytes()
+			),
+			None
+		);
+	}
+}
@@ -0,0 +1,194 @@
+//! housekeeping libgit2 has no support for, done by the git cli:
+//! packing loose objects and dropping those packed already
+
+use super::{repository::repo, RepoPath};
+use crate::{
+	error::{Error, Result},
+	progress::ProgressPercent,
+};
+use scopetime::scope_time;
+use std::process::{Command, Stdio};
+
+/// `gc.auto` unless configured: `git gc --auto` packs once there are
+/// more loose objects than this
+const GC_AUTO_DEFAULT: i64 = 6700;
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceTask {
+	/// see `gc`
+	Gc,
+	/// see `prune_packed`
+	PrunePacked,
+	/// `git maintenance run`, running the tasks configured for the
+	/// repository (needs git 2.29)
+	Maintenance,
+}
+
+impl MaintenanceTask {
+	///
+	pub const ALL: [Self; 3] =
+		[Self::Gc, Self::PrunePacked, Self::Maintenance];
+
+	/// the git commands run one after another
+	const fn steps(self) -> &'static [&'static [&'static str]] {
+		match self {
+			Self::Gc => &[&["gc", "--quiet"]],
+			Self::PrunePacked => &[&["prune-packed", "--quiet"]],
+			Self::Maintenance => {
+				&[&["maintenance", "run", "--quiet"]]
+			}
+		}
+	}
+}
+
+/// the step a maintenance task is at
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MaintenanceProgress {
+	/// the git command running, like `repack`
+	pub step: String,
+	///
+	pub progress: ProgressPercent,
+}
+
+/// runs `task`, calling `progress` before each step and once done
+pub fn run_maintenance(
+	repo_path: &RepoPath,
+	task: MaintenanceTask,
+	mut progress: impl FnMut(MaintenanceProgress),
+) -> Result<()> {
+	scope_time!("run_maintenance");
+
+	let repo = repo(repo_path)?;
+	let steps = task.steps();
+
+	for (index, args) in steps.iter().enumerate() {
+		let name = args.first().copied().unwrap_or_default();
+		progress(MaintenanceProgress {
+			step: name.to_string(),
+			progress: ProgressPercent::new(index, steps.len()),
+		});
+
+		let output = Command::new("git")
+			.arg("--git-dir")
+			.arg(repo.path())
+			.args(*args)
+			.stdin(Stdio::null())
+			.output()?;
+
+		if !output.status.success() {
+			return Err(Error::Generic(format!(
+				"git {} failed: {}",
+				name,
+				String::from_utf8_lossy(&output.stderr).trim()
+			)));
+		}
+	}
+
+	progress(MaintenanceProgress {
+		step: String::new(),
+		progress: ProgressPercent::full(),
+	});
+
+	Ok(())
+}
+
+/// packs loose objects and refs, expires reflogs and prunes
+/// unreachable objects older than `gc.pruneExpire` like `git gc`
+pub fn gc(repo_path: &RepoPath) -> Result<()> {
+	run_maintenance(repo_path, MaintenanceTask::Gc, |_| ())
+}
+
+/// removes loose objects that are in a pack already
+pub fn prune_packed(repo_path: &RepoPath) -> Result<()> {
+	run_maintenance(repo_path, MaintenanceTask::PrunePacked, |_| ())
+}
+
+/// whether `loose_objects` are more than `gc.auto` allows, never if
+/// that is `0`
+pub fn gc_suggested(
+	repo_path: &RepoPath,
+	loose_objects: usize,
+) -> Result<bool> {
+	let limit = repo(repo_path)?
+		.config()?
+		.get_i64("gc.auto")
+		.unwrap_or(GC_AUTO_DEFAULT);
+
+	Ok(usize::try_from(limit)
+		.map_or(false, |limit| limit > 0 && loose_objects > limit))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		repo_stats::get_repo_stats,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_gc() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "first");
+
+		let mut steps = Vec::new();
+		run_maintenance(repo_path, MaintenanceTask::Gc, |progress| {
+			steps.push((progress.step, progress.progress.progress));
+		})
+		.unwrap();
+
+		assert_eq!(
+			steps,
+			vec![("gc".to_string(), 0), (String::new(), 100)]
+		);
+
+		let stats = get_repo_stats(repo_path).unwrap();
+		assert_eq!(stats.loose_objects, 0);
+		assert_eq!(stats.packs, 1);
+	}
+
+	#[test]
+	fn test_prune_packed() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "first");
+		let loose = get_repo_stats(repo_path).unwrap().loose_objects;
+
+		// packs without removing the loose objects
+		Command::new("git")
+			.args(["repack", "-q"])
+			.current_dir(root)
+			.status()
+			.unwrap();
+		assert_eq!(
+			get_repo_stats(repo_path).unwrap().loose_objects,
+			loose
+		);
+
+		prune_packed(repo_path).unwrap();
+
+		assert_eq!(
+			get_repo_stats(repo_path).unwrap().loose_objects,
+			0
+		);
+	}
+
+	#[test]
+	fn test_gc_suggested() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+
+		assert!(!gc_suggested(repo_path, 6700).unwrap());
+		assert!(gc_suggested(repo_path, 6701).unwrap());
+
+		repo.config().unwrap().set_i64("gc.auto", 0).unwrap();
+		assert!(!gc_suggested(repo_path, 100_000).unwrap());
+	}
+}
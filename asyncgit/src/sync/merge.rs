@@ -1,6 +1,7 @@
 use crate::{
 	error::{Error, Result},
 	sync::{
+		action_log::{commit_details, log_action},
 		branch::merge_commit::commit_merge_with_head,
 		rebase::{
 			abort_rebase, continue_rebase, get_rebase_progress,
@@ -46,6 +47,8 @@ pub fn abort_pending_state(repo_path: &RepoPath) -> Result<()> {
 
 	repo.cleanup_state()?;
 
+	log_action(&repo, "abort merge", "");
+
 	Ok(())
 }
 
@@ -61,6 +64,8 @@ pub fn merge_branch(
 
 	merge_branch_repo(&repo, branch, branch_type)?;
 
+	log_action(&repo, "merge", branch);
+
 	Ok(())
 }
 
@@ -83,7 +88,11 @@ pub fn continue_pending_rebase(
 
 	let repo = repo(repo_path)?;
 
-	continue_rebase(&repo)
+	let state = continue_rebase(&repo)?;
+
+	log_action(&repo, "continue rebase", "");
+
+	Ok(state)
 }
 
 ///
@@ -92,7 +101,11 @@ pub fn abort_pending_rebase(repo_path: &RepoPath) -> Result<()> {
 
 	let repo = repo(repo_path)?;
 
-	abort_rebase(&repo)
+	abort_rebase(&repo)?;
+
+	log_action(&repo, "abort rebase", "");
+
+	Ok(())
 }
 
 ///
@@ -148,6 +161,8 @@ pub fn merge_commit(
 
 	let id = commit_merge_with_head(&repo, &commits, msg)?;
 
+	log_action(&repo, "commit", &commit_details(id, msg));
+
 	Ok(id)
 }
 
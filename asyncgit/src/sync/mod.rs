@@ -3,96 +3,178 @@
 //TODO: remove once we have this activated on the toplevel
 #![deny(clippy::expect_used)]
 
+mod action_log;
+pub mod binary;
 pub mod blame;
 pub mod branch;
+pub mod ci;
 mod commit;
 mod commit_details;
 pub mod commit_files;
+mod commit_lint;
 mod commit_revert;
+mod commit_snippets;
 mod commits_info;
 mod config;
+mod contains;
 pub mod cred;
+mod describe;
 pub mod diff;
+mod difftool;
+pub mod github;
+pub mod grep;
 mod hooks;
 mod hunks;
 mod ignore;
+mod index_recovery;
+pub mod lfs;
 mod logwalker;
+mod maintenance;
 mod merge;
+pub mod notes;
 mod patches;
+#[cfg(feature = "pretty-diff")]
+mod pretty_diff;
+mod protected;
 mod rebase;
 pub mod remotes;
+pub mod repo_stats;
 mod repository;
 mod reset;
+mod shell;
+mod sign;
 mod staging;
 mod stash;
 mod state;
 pub mod status;
+mod status_order;
 mod submodules;
 mod tags;
+mod textconv;
 mod tree;
+mod undo;
 pub mod utils;
 
-pub use blame::{blame_file, BlameHunk, FileBlame};
+pub use action_log::{action_log, ActionLogEntry};
+pub use blame::{
+	blame_file, blame_file_ext, blame_file_progress, get_ignore_revs,
+	BlameHunk, FileBlame,
+};
 pub use branch::{
-	branch_compare_upstream, checkout_branch, config_is_pull_rebase,
-	create_branch, delete_branch, get_branch_remote,
-	get_branches_info, merge_commit::merge_upstream_commit,
+	branch_compare_upstream, checkout_branch,
+	compare::{compare_branches, BranchComparison, FileDiffStat},
+	config_is_pull_rebase, create_branch, create_branch_at,
+	create_orphan_branch, delete_branch, get_branch_remote,
+	get_branches_activity, get_branches_info,
+	merge_commit::merge_upstream_commit,
 	merge_ff::branch_merge_upstream_fastforward,
-	merge_rebase::merge_upstream_rebase, rename::rename_branch,
-	validate_branch_name, BranchCompare, BranchInfo,
+	merge_rebase::merge_upstream_rebase,
+	rename::{branch_upstream_name, rename_branch, rename_remote_branch},
+	validate_branch_name, BranchActivity, BranchCompare, BranchInfo,
+};
+pub use commit::{
+	amend, amend_with_author, commit, commit_paths, commit_signed,
+	commit_with_author, tag_commit, CommitAuthor,
 };
-pub use commit::{amend, commit, tag_commit};
 pub use commit_details::{
 	get_commit_details, CommitDetails, CommitMessage, CommitSignature,
 };
 pub use commit_files::get_commit_files;
+pub use commit_lint::{commit_lint_config, CommitLint, LintWarning};
 pub use commit_revert::{commit_revert, revert_commit, revert_head};
+pub use commit_snippets::{
+	commit_snippets_config, CommitSnippets, Snippet,
+};
 pub use commits_info::{
-	get_commit_info, get_commits_info, CommitId, CommitInfo,
+	get_commit_info, get_commits_info, get_recent_commits_info,
+	CommitId, CommitInfo,
 };
 pub use config::{
-	get_config_string, untracked_files_config,
+	diff_algorithm_config, get_config_string, untracked_files_config,
 	ShowUntrackedFilesConfig,
 };
+pub use contains::{
+	branches_containing, merge_base, tags_containing,
+};
+pub use describe::{describe, DescribeOptions};
 pub use diff::get_diff_commit;
+pub use difftool::{
+	difftool_cmd, difftool_files, mergetool_cmd, mergetool_files,
+	ToolFiles,
+};
 pub use git2::BranchType;
 pub use hooks::{
-	hooks_commit_msg, hooks_post_commit, hooks_pre_commit, HookResult,
+	hooks_commit_msg, hooks_post, hooks_post_commit, hooks_pre_commit,
+	HookOutput, HookResult, PostHook,
 };
 pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
-pub use ignore::add_to_ignore;
+pub use ignore::{
+	add_paths_to_ignore, add_to_ignore, get_ignored_files,
+	IgnoreSource, IgnoredFile,
+};
+pub use index_recovery::{index_problem, rebuild_index, IndexProblem};
 pub use logwalker::{diff_contains_file, LogWalker, LogWalkerFilter};
+pub use maintenance::{
+	gc, gc_suggested, prune_packed, run_maintenance,
+	MaintenanceProgress, MaintenanceTask,
+};
 pub use merge::{
 	abort_pending_rebase, abort_pending_state,
 	continue_pending_rebase, merge_branch, merge_commit, merge_msg,
 	mergehead_ids, rebase_progress,
 };
+pub use protected::{
+	protected_branches_config, BranchProtection, ProtectedBranches,
+};
 pub use rebase::rebase_branch;
 pub use remotes::{
-	get_default_remote, get_remotes, push::AsyncProgress,
+	divergence_preview, get_default_remote, get_remotes,
+	push::{
+		push_raw_refspec, review_refspec, review_refspec_template,
+		review_topic_valid, AsyncProgress, PushSummary, PushedRef,
+	},
+	push_cli::PushCliOptions,
 	tags::PushTagsProgress,
+	Divergence,
 };
 pub(crate) use repository::repo;
 pub use repository::{RepoPath, RepoPathRef};
-pub use reset::{reset_stage, reset_workdir};
+pub use reset::{
+	reset_stage, reset_stage_paths, reset_workdir, reset_workdir_paths,
+};
+pub use shell::posix_shell;
+pub use sign::gpgsign_enabled;
 pub use staging::{discard_lines, stage_lines};
 pub use stash::{
-	get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
+	autostash_needed, autostash_pop, autostash_save, get_stashes,
+	stash_apply, stash_drop, stash_pop, stash_save,
 };
 pub use state::{repo_state, RepoState};
 pub use status::is_workdir_clean;
+pub use status_order::{
+	set_status_order_config, sort_status, status_order_config,
+	StatusGroup, StatusOrder, StatusSort,
+};
 pub use submodules::{
 	get_submodules, submodule_parent_info, update_submodule,
 	SubmoduleInfo, SubmoduleParentInfo, SubmoduleStatus,
 };
 pub use tags::{
-	delete_tag, get_tags, get_tags_with_metadata, CommitTags, Tag,
-	TagWithMetadata, Tags,
+	delete_tag, get_tag_annotation, get_tags, get_tags_lazy,
+	get_tags_state, get_tags_with_metadata, tag_exists, CommitTags,
+	Tag, TagWithMetadata, Tags,
+};
+pub use tree::{
+	commit_file_content, index_file_content, tree_file_content,
+	tree_files, TreeFile,
+};
+pub use undo::{
+	restore_discarded, undo, undo_entries, undo_last_entry,
+	undo_snapshot, UndoEntry, UndoOperation,
 };
-pub use tree::{tree_file_content, tree_files, TreeFile};
 pub use utils::{
 	get_head, get_head_tuple, is_repo, repo_dir, stage_add_all,
-	stage_add_file, stage_addremoved, Head,
+	stage_add_file, stage_add_files, stage_addremoved, Head,
 };
 
 #[cfg(test)]
@@ -0,0 +1,145 @@
+//! notes attached to commits (`git notes`), stored in the default
+//! notes ref (`core.notesRef` or `refs/notes/commits`)
+
+use super::{
+	action_log::log_action, commit::signature_allow_undefined_name,
+	CommitId, RepoPath,
+};
+use crate::{error::Result, sync::repository::repo};
+use git2::ErrorCode;
+use scopetime::scope_time;
+
+/// note attached to `commit`, `None` if there is none
+pub fn get_note(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<Option<String>> {
+	scope_time!("get_note");
+
+	let repo = repo(repo_path)?;
+
+	let note = match repo.find_note(None, commit.into()) {
+		Ok(note) => note,
+		Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(Some(
+		String::from_utf8_lossy(note.message_bytes()).to_string(),
+	))
+}
+
+/// commit the notes ref points to, it moves whenever a note gets
+/// set or removed. `None` while there are no notes
+pub fn notes_tip(repo_path: &RepoPath) -> Result<Option<CommitId>> {
+	scope_time!("notes_tip");
+
+	let repo = repo(repo_path)?;
+	let notes_ref = repo.note_default_ref()?;
+
+	match repo.refname_to_id(&notes_ref) {
+		Ok(id) => Ok(Some(id.into())),
+		Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// attaches `message` to `commit`, replacing an existing note.
+/// an empty `message` removes the note
+pub fn set_note(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	message: &str,
+) -> Result<()> {
+	scope_time!("set_note");
+
+	if message.trim().is_empty() {
+		return remove_note(repo_path, commit);
+	}
+
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	repo.note(
+		&signature,
+		&signature,
+		None,
+		commit.into(),
+		message,
+		true,
+	)?;
+
+	log_action(&repo, "set note", &commit.get_short_string());
+
+	Ok(())
+}
+
+/// removes the note of `commit` if it has one
+pub fn remove_note(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<()> {
+	scope_time!("remove_note");
+
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	match repo.note_delete(
+		commit.into(),
+		None,
+		&signature,
+		&signature,
+	) {
+		Err(e) if e.code() != ErrorCode::NotFound => Err(e.into()),
+		Err(_) => Ok(()),
+		Ok(()) => {
+			log_action(
+				&repo,
+				"remove note",
+				&commit.get_short_string(),
+			);
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_notes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		assert_eq!(get_note(repo_path, c1).unwrap(), None);
+		assert_eq!(notes_tip(repo_path).unwrap(), None);
+
+		set_note(repo_path, c1, "reviewed").unwrap();
+		assert_eq!(
+			get_note(repo_path, c1).unwrap().as_deref(),
+			Some("reviewed")
+		);
+		let tip = notes_tip(repo_path).unwrap();
+		assert!(tip.is_some());
+
+		set_note(repo_path, c1, "reviewed\n\nby someone").unwrap();
+		assert_eq!(
+			get_note(repo_path, c1).unwrap().as_deref(),
+			Some("reviewed\n\nby someone")
+		);
+		assert_ne!(notes_tip(repo_path).unwrap(), tip);
+
+		set_note(repo_path, c1, " ").unwrap();
+		assert_eq!(get_note(repo_path, c1).unwrap(), None);
+
+		// nothing to remove
+		remove_note(repo_path, c1).unwrap();
+	}
+}
@@ -0,0 +1,97 @@
+//! pretty printing json before diffing it: jupyter notebooks and
+//! minified json otherwise end up as one gigantic changed line
+
+use std::path::Path;
+
+/// a `.json` file gets pretty printed if a side has a line this long
+const LONG_LINE: usize = 1000;
+
+/// whether `path` might get pretty printed, to be checked before
+/// reading the content
+pub fn is_candidate(path: &Path) -> bool {
+	matches!(extension(path).as_deref(), Some("ipynb" | "json"))
+}
+
+/// both sides pretty printed if `path` is a notebook or a json file
+/// with long lines, `None` if a side is no valid json; keys come out
+/// sorted, a missing side stays empty
+pub fn pretty_json(
+	path: &Path,
+	old: &[u8],
+	new: &[u8],
+) -> Option<(Vec<u8>, Vec<u8>)> {
+	let applies = match extension(path).as_deref() {
+		Some("ipynb") => true,
+		Some("json") => has_long_line(old) || has_long_line(new),
+		_ => false,
+	};
+
+	if applies {
+		Some((pretty(old)?, pretty(new)?))
+	} else {
+		None
+	}
+}
+
+fn extension(path: &Path) -> Option<String> {
+	path.extension()
+		.map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+fn has_long_line(content: &[u8]) -> bool {
+	content
+		.split(|b| *b == b'\n')
+		.any(|line| line.len() >= LONG_LINE)
+}
+
+fn pretty(content: &[u8]) -> Option<Vec<u8>> {
+	if content.is_empty() {
+		return Some(Vec::new());
+	}
+
+	let value =
+		serde_json::from_slice::<serde_json::Value>(content).ok()?;
+	let mut res = serde_json::to_vec_pretty(&value).ok()?;
+	res.push(b'\n');
+
+	Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_pretty_json() {
+		let long =
+			format!(r#"{{"b":1,"a":"{}"}}"#, "x".repeat(LONG_LINE));
+
+		let (old, new) =
+			pretty_json(Path::new("data.JSON"), b"", long.as_bytes())
+				.unwrap();
+		assert_eq!(old, b"");
+		assert_eq!(
+			String::from_utf8(new).unwrap(),
+			format!(
+				"{{\n  \"a\": \"{}\",\n  \"b\": 1\n}}\n",
+				"x".repeat(LONG_LINE)
+			)
+		);
+
+		// short json stays as it is, notebooks get printed anyway
+		assert_eq!(
+			pretty_json(Path::new("a.json"), b"{}", b"[]"),
+			None
+		);
+		assert_eq!(
+			pretty_json(Path::new("a.ipynb"), b"{}", b"[1]"),
+			Some((b"{}\n".to_vec(), b"[\n  1\n]\n".to_vec()))
+		);
+		assert_eq!(
+			pretty_json(Path::new("a.ipynb"), b"{", b"{}"),
+			None
+		);
+		assert!(!is_candidate(Path::new("a.txt")));
+	}
+}
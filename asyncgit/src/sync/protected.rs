@@ -0,0 +1,160 @@
+//! branches guarded against commits, force pushes, resets and
+//! deletion, configured via `gitui.protectedBranch*` in git-config
+
+use super::{repository::repo, RepoPath};
+use crate::error::Result;
+use regex::Regex;
+use scopetime::scope_time;
+
+/// `gitui.protectedBranchAction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BranchProtection {
+	/// ask for an extra confirmation (default)
+	Confirm,
+	/// do not allow it at all
+	Refuse,
+}
+
+///
+#[derive(Debug, Clone)]
+pub struct ProtectedBranches {
+	/// `gitui.protectedBranch` patterns (multi valued)
+	patterns: Vec<Regex>,
+	///
+	pub action: BranchProtection,
+}
+
+impl Default for ProtectedBranches {
+	fn default() -> Self {
+		Self {
+			patterns: Vec::new(),
+			action: BranchProtection::Confirm,
+		}
+	}
+}
+
+impl ProtectedBranches {
+	/// `None` unless `branch` (without `refs/heads/`) is protected
+	pub fn protection(
+		&self,
+		branch: &str,
+	) -> Option<BranchProtection> {
+		self.patterns
+			.iter()
+			.any(|pattern| pattern.is_match(branch))
+			.then(|| self.action)
+	}
+}
+
+/// `*` matches anything but `/` and `**` anything at all
+fn pattern_to_regex(pattern: &str) -> Option<Regex> {
+	let mut regex = String::from("^");
+
+	for (idx, part) in pattern.split("**").enumerate() {
+		if idx > 0 {
+			regex.push_str(".*");
+		}
+
+		for (idx, part) in part.split('*').enumerate() {
+			if idx > 0 {
+				regex.push_str("[^/]*");
+			}
+			regex.push_str(&regex::escape(part));
+		}
+	}
+
+	regex.push('$');
+
+	Regex::new(&regex).ok()
+}
+
+/// reads the `gitui.protectedBranch*` settings, nothing is protected
+/// by default
+pub fn protected_branches_config(
+	repo_path: &RepoPath,
+) -> Result<ProtectedBranches> {
+	scope_time!("protected_branches_config");
+
+	let repo = repo(repo_path)?;
+	let cfg = repo.config()?;
+
+	let mut patterns = Vec::new();
+	if let Ok(entries) = cfg.multivar("gitui.protectedBranch", None) {
+		entries.for_each(|entry| {
+			if let Some(pattern) =
+				entry.value().and_then(pattern_to_regex)
+			{
+				patterns.push(pattern);
+			}
+		})?;
+	}
+
+	let action = match cfg
+		.get_string("gitui.protectedBranchAction")
+		.ok()
+		.as_deref()
+	{
+		Some("refuse") => BranchProtection::Refuse,
+		_ => BranchProtection::Confirm,
+	};
+
+	Ok(ProtectedBranches { patterns, action })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init_empty;
+
+	#[test]
+	fn test_pattern() {
+		let protected = ProtectedBranches {
+			patterns: ["main", "release/*", "team/**/stable"]
+				.iter()
+				.filter_map(|pattern| pattern_to_regex(pattern))
+				.collect(),
+			action: BranchProtection::Confirm,
+		};
+
+		let is_protected =
+			|branch| protected.protection(branch).is_some();
+
+		assert!(is_protected("main"));
+		assert!(is_protected("release/1.0"));
+		assert!(is_protected("team/a/b/stable"));
+		assert!(!is_protected("main2"));
+		assert!(!is_protected("release/1.0/fix"));
+		assert!(!is_protected("feature/main"));
+	}
+
+	#[test]
+	fn test_config() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(
+			protected_branches_config(repo_path)
+				.unwrap()
+				.protection("main"),
+			None
+		);
+
+		let mut cfg = repo.config().unwrap();
+		cfg.set_multivar("gitui.protectedBranch", "^$", "main")
+			.unwrap();
+		cfg.set_multivar("gitui.protectedBranch", "^$", "release/*")
+			.unwrap();
+		cfg.set_str("gitui.protectedBranchAction", "refuse")
+			.unwrap();
+
+		let protected = protected_branches_config(repo_path).unwrap();
+
+		assert_eq!(
+			protected.protection("release/2"),
+			Some(BranchProtection::Refuse)
+		);
+		assert_eq!(protected.protection("develop"), None);
+	}
+}
@@ -3,7 +3,7 @@ use scopetime::scope_time;
 
 use crate::{
 	error::{Error, Result},
-	sync::repository::repo,
+	sync::{action_log::log_action, repository::repo},
 };
 
 use super::{CommitId, RepoPath};
@@ -18,7 +18,11 @@ pub fn rebase_branch(
 
 	let repo = repo(repo_path)?;
 
-	rebase_branch_repo(&repo, branch, branch_type)
+	let state = rebase_branch_repo(&repo, branch, branch_type)?;
+
+	log_action(&repo, "rebase", branch);
+
+	Ok(state)
 }
 
 fn rebase_branch_repo(
@@ -1,5 +1,8 @@
-use super::push::ProgressNotification;
-use crate::{error::Result, sync::cred::BasicAuthCredential};
+use super::{push::ProgressNotification, retry::Watchdog};
+use crate::{
+	error::Result,
+	sync::{cred::BasicAuthCredential, CommitId},
+};
 use crossbeam_channel::Sender;
 use git2::{Cred, Error as GitError, RemoteCallbacks};
 use std::sync::{
@@ -10,7 +13,33 @@ use std::sync::{
 ///
 #[derive(Default, Clone)]
 pub struct CallbackStats {
-	pub push_rejected_msg: Option<(String, String)>,
+	/// remote refs a push reported, with the reason if rejected
+	pub pushed_refs: Vec<(String, Option<String>)>,
+	/// local refs updated: name, old and new target
+	pub updated_tips: Vec<(String, CommitId, CommitId)>,
+	/// what the server sent along (`remote:` in git)
+	pub sideband: String,
+}
+
+impl CallbackStats {
+	/// the first ref the remote refused and why
+	pub fn push_rejected_msg(&self) -> Option<(&str, &str)> {
+		self.pushed_refs.iter().find_map(|(reference, msg)| {
+			msg.as_deref().map(|msg| (reference.as_str(), msg))
+		})
+	}
+
+	/// the lines of `sideband`: progress output gets rewritten
+	/// using `\r`, only the final state of each line is kept
+	pub fn remote_messages(&self) -> Vec<String> {
+		self.sideband
+			.lines()
+			.filter_map(|line| line.rsplit('\r').next())
+			.map(str::trim_end)
+			.filter(|line| !line.is_empty())
+			.map(ToString::to_string)
+			.collect()
+	}
 }
 
 ///
@@ -20,6 +49,8 @@ pub struct Callbacks {
 	basic_credential: Option<BasicAuthCredential>,
 	stats: Arc<Mutex<CallbackStats>>,
 	first_call_to_credentials: Arc<AtomicBool>,
+	cancel: Option<Arc<AtomicBool>>,
+	watchdog: Option<Watchdog>,
 }
 
 impl Callbacks {
@@ -37,7 +68,53 @@ impl Callbacks {
 			first_call_to_credentials: Arc::new(AtomicBool::new(
 				true,
 			)),
+			cancel: None,
+			watchdog: None,
+		}
+	}
+
+	/// abort the transfer as soon as `cancel` gets set
+	#[must_use]
+	pub fn with_cancel(
+		mut self,
+		cancel: Option<Arc<AtomicBool>>,
+	) -> Self {
+		self.cancel = cancel;
+		self
+	}
+
+	/// report progress to `watchdog` and abort once it gave up
+	#[must_use]
+	pub fn with_watchdog(mut self, watchdog: Watchdog) -> Self {
+		self.watchdog = Some(watchdog);
+		self
+	}
+
+	fn is_cancelled(&self) -> bool {
+		self.cancel
+			.as_ref()
+			.map_or(false, |cancel| cancel.load(Ordering::Relaxed))
+			|| self
+				.watchdog
+				.as_ref()
+				.map_or(false, Watchdog::is_aborted)
+	}
+
+	/// the remote is still alive, `false` if the transfer should abort
+	fn keep_going(&self) -> bool {
+		if let Some(watchdog) = &self.watchdog {
+			watchdog.touch();
+		}
+		!self.is_cancelled()
+	}
+
+	/// like `keep_going` once objects or refs are on their way, after
+	/// which the attempt is no longer retried
+	fn keep_transferring(&self) -> bool {
+		if let Some(watchdog) = &self.watchdog {
+			watchdog.mark_transferred();
 		}
+		self.keep_going()
 	}
 
 	///
@@ -54,29 +131,34 @@ impl Callbacks {
 		callbacks.push_transfer_progress(
 			move |current, total, bytes| {
 				this.push_transfer_progress(current, total, bytes);
+				this.keep_transferring();
 			},
 		);
 
 		let this = self.clone();
 		callbacks.update_tips(move |name, a, b| {
 			this.update_tips(name, a, b);
-			true
+			this.keep_transferring()
 		});
 
 		let this = self.clone();
 		callbacks.transfer_progress(move |p| {
 			this.transfer_progress(&p);
-			true
+			this.keep_transferring()
 		});
 
 		let this = self.clone();
 		callbacks.pack_progress(move |stage, current, total| {
 			this.pack_progress(stage, total, current);
+			this.keep_going();
 		});
 
 		let this = self.clone();
 		callbacks.push_update_reference(move |reference, msg| {
 			this.push_update_reference(reference, msg);
+			if !this.keep_transferring() {
+				return Err(GitError::from_str("cancelled"));
+			}
 			Ok(())
 		});
 
@@ -91,12 +173,10 @@ impl Callbacks {
 			},
 		);
 
+		let this = self.clone();
 		callbacks.sideband_progress(move |data| {
-			log::debug!(
-				"sideband transfer: '{}'",
-				String::from_utf8_lossy(data).trim()
-			);
-			true
+			this.sideband_progress(data);
+			this.keep_going()
 		});
 
 		callbacks
@@ -114,8 +194,19 @@ impl Callbacks {
 		);
 
 		if let Ok(mut stats) = self.stats.lock() {
-			stats.push_rejected_msg = msg
-				.map(|msg| (reference.to_string(), msg.to_string()));
+			stats.pushed_refs.push((
+				reference.to_string(),
+				msg.map(ToString::to_string),
+			));
+		}
+	}
+
+	fn sideband_progress(&self, data: &[u8]) {
+		let data = String::from_utf8_lossy(data);
+		log::debug!("sideband transfer: '{}'", data.trim());
+
+		if let Ok(mut stats) = self.stats.lock() {
+			stats.sideband.push_str(&data);
 		}
 	}
 
@@ -145,12 +236,22 @@ impl Callbacks {
 			sender.send(ProgressNotification::Transfer {
 				objects: p.received_objects(),
 				total_objects: p.total_objects(),
+				bytes: p.received_bytes(),
+				indexed_deltas: p.indexed_deltas(),
+				total_deltas: p.total_deltas(),
 			})
 		});
 	}
 
 	fn update_tips(&self, name: &str, a: git2::Oid, b: git2::Oid) {
 		log::debug!("update tips: '{}' [{}] [{}]", name, a, b);
+		if let Ok(mut stats) = self.stats.lock() {
+			stats.updated_tips.push((
+				name.to_string(),
+				a.into(),
+				b.into(),
+			));
+		}
 		self.sender.clone().map(|sender| {
 			sender.send(ProgressNotification::UpdateTips {
 				name: name.to_string(),
@@ -193,6 +294,10 @@ impl Callbacks {
 			allowed_types
 		);
 
+		if !self.keep_going() {
+			return Err(GitError::from_str("cancelled"));
+		}
+
 		// This boolean is used to avoid multiple calls to credentials callback.
 		if self.first_call_to_credentials.load(Ordering::Relaxed) {
 			self.first_call_to_credentials
@@ -0,0 +1,214 @@
+//! the remote commits a force push throws away
+
+use super::{
+	dry_run::remote_heads, with_retry, Callbacks, Transport, Watchdog,
+};
+use crate::{
+	error::Result,
+	sync::{
+		action_log::log_action, cred::BasicAuthCredential,
+		get_commits_info, repository::repo, CommitId, CommitInfo,
+		RepoPath,
+	},
+};
+use git2::{AutotagOption, Direction, FetchOptions, Sort};
+use scopetime::scope_time;
+
+/// long enough for a subject line
+const MESSAGE_LENGTH_LIMIT: usize = 100;
+
+/// see `divergence_preview`
+#[derive(Debug, Default, Clone)]
+pub struct Divergence {
+	/// where the branch is on the remote, `None` if it is not there
+	pub tip: Option<CommitId>,
+	/// newest first
+	pub overwritten: Vec<CommitInfo>,
+}
+
+/// the commits on `branch` of `remote` that force pushing the local
+/// `branch` would overwrite; the remote tip gets fetched if it is
+/// missing locally, without touching any ref but `FETCH_HEAD`
+pub fn divergence_preview(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<Divergence> {
+	scope_time!("divergence_preview");
+
+	let branch_ref = format!("refs/heads/{}", branch);
+	let heads = remote_heads(
+		repo_path,
+		remote,
+		Direction::Fetch,
+		basic_credential.clone(),
+	)?;
+	let tip = if let Some((_, tip)) =
+		heads.into_iter().find(|(name, _)| *name == branch_ref)
+	{
+		tip
+	} else {
+		// nothing to overwrite on the remote
+		return Ok(Divergence::default());
+	};
+
+	if repo(repo_path)?.find_commit(tip.into()).is_err() {
+		fetch_remote_ref(
+			repo_path,
+			remote,
+			&branch_ref,
+			basic_credential,
+		)?;
+	}
+
+	let repo = repo(repo_path)?;
+	let local = repo.revparse_single(&branch_ref)?.id();
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+	walk.push(tip.into())?;
+	walk.hide(local)?;
+	let ids = walk
+		.map(|id| id.map(CommitId::from))
+		.collect::<std::result::Result<Vec<_>, _>>()?;
+
+	Ok(Divergence {
+		tip: Some(tip),
+		overwritten: get_commits_info(
+			repo_path,
+			&ids,
+			MESSAGE_LENGTH_LIMIT,
+		)?,
+	})
+}
+
+/// fetches `remote_ref` into `FETCH_HEAD` only, a preview must not
+/// move the remote tracking branch
+fn fetch_remote_ref(
+	repo_path: &RepoPath,
+	remote: &str,
+	remote_ref: &str,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<()> {
+	let (path, remote, remote_ref) = (
+		repo_path.clone(),
+		remote.to_string(),
+		remote_ref.to_string(),
+	);
+
+	with_retry(repo_path, None, move |watchdog| {
+		fetch_remote_ref_attempt(
+			&path,
+			&remote,
+			&remote_ref,
+			basic_credential.clone(),
+			watchdog,
+		)
+	})
+}
+
+fn fetch_remote_ref_attempt(
+	repo_path: &RepoPath,
+	remote: &str,
+	remote_ref: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	watchdog: Watchdog,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let named = repo.find_remote(remote)?;
+	let transport = Transport::new(&repo, &named)?;
+	// libgit2 updates the tracking refs of a named remote whose
+	// fetch refspecs match, an anonymous one has none
+	let mut anonymous =
+		repo.remote_anonymous(named.url().unwrap_or_default())?;
+
+	let mut options = FetchOptions::new();
+	let callbacks = Callbacks::new(None, basic_credential)
+		.with_watchdog(watchdog);
+	options.remote_callbacks(callbacks.callbacks());
+	options.proxy_options(transport.proxy_options());
+	options.download_tags(AutotagOption::None);
+	anonymous
+		.fetch(&[remote_ref], Some(&mut options), None)
+		.map_err(|e| transport.map_err(e))?;
+
+	log_action(&repo, "fetch", &format!("{} {}", remote, remote_ref));
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		remotes::push::push_branch,
+		tests::{repo_clone, repo_init_bare, write_commit_file},
+	};
+
+	#[test]
+	fn test_divergence_preview() {
+		let (upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir1, repo1) = repo_clone(upstream_path).unwrap();
+		let (dir2, repo2) = repo_clone(upstream_path).unwrap();
+		let path1: &RepoPath = &dir1.path().to_str().unwrap().into();
+		let path2: &RepoPath = &dir2.path().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo1, "a.txt", "a", "first");
+		push_branch(
+			path1, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		// the remote has nothing the local branch lacks
+		let divergence =
+			divergence_preview(path1, "origin", "master", None)
+				.unwrap();
+		assert_eq!(divergence.tip, Some(first));
+		assert!(divergence.overwritten.is_empty());
+		// and nothing at all on an unknown branch
+		let divergence =
+			divergence_preview(path1, "origin", "gone", None)
+				.unwrap();
+		assert_eq!(divergence.tip, None);
+		assert!(divergence.overwritten.is_empty());
+
+		let second =
+			write_commit_file(&repo1, "a.txt", "b", "second");
+		let third = write_commit_file(&repo1, "a.txt", "c", "third");
+		push_branch(
+			path1, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		// never fetched the remote commits
+		write_commit_file(&repo2, "b.txt", "x", "diverged");
+		assert!(repo2.find_commit(third.into()).is_err());
+
+		let divergence =
+			divergence_preview(path2, "origin", "master", None)
+				.unwrap();
+		assert_eq!(divergence.tip, Some(third));
+		let overwritten = divergence.overwritten;
+		assert_eq!(
+			overwritten
+				.iter()
+				.map(|commit| (commit.id, commit.message.as_str()))
+				.collect::<Vec<_>>(),
+			vec![
+				(third, "third"),
+				(second, "second"),
+				(first, "first")
+			]
+		);
+		assert_eq!(overwritten[0].author, "name");
+		// previewing leaves the tracking branch alone
+		assert!(repo2
+			.refname_to_id("refs/remotes/origin/master")
+			.is_err());
+		assert_eq!(
+			repo2.refname_to_id("FETCH_HEAD").unwrap(),
+			third.into()
+		);
+	}
+}
@@ -0,0 +1,437 @@
+//! what a push or fetch would change, judged from the refs the remote
+//! advertises without transferring any objects
+
+use super::{with_retry, Callbacks, Transport, Watchdog};
+use crate::{
+	error::Result,
+	sync::{
+		cred::BasicAuthCredential, repository::repo, CommitId,
+		RepoPath,
+	},
+};
+use git2::{Direction, ReferenceType, Repository};
+use scopetime::scope_time;
+use std::collections::HashSet;
+
+/// how a ref would change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefChange {
+	///
+	Create,
+	///
+	FastForward,
+	/// not a fast-forward, history gets rewritten
+	Forced,
+	/// not a fast-forward and not forced, so refused
+	Rejected,
+	/// brings in commits not known locally yet, so whether it is a
+	/// fast-forward only shows once they got fetched
+	Unknown,
+	///
+	Delete,
+	///
+	UpToDate,
+}
+
+/// a ref a push or fetch would touch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefUpdate {
+	/// the ref on the remote for a push, the local one for a fetch
+	pub name: String,
+	///
+	pub old: Option<CommitId>,
+	///
+	pub new: Option<CommitId>,
+	///
+	pub change: RefChange,
+}
+
+impl RefUpdate {
+	fn new(
+		repo: &Repository,
+		name: &str,
+		old: Option<CommitId>,
+		new: Option<CommitId>,
+		force: bool,
+	) -> Self {
+		let change = match (old, new) {
+			(None, Some(_)) => RefChange::Create,
+			(Some(_), None) => RefChange::Delete,
+			(old, new) if old == new => RefChange::UpToDate,
+			(Some(_), Some(new))
+				if repo.find_commit(new.into()).is_err() =>
+			{
+				RefChange::Unknown
+			}
+			(Some(old), Some(new))
+				if repo
+					.graph_descendant_of(new.into(), old.into())
+					.unwrap_or_default() =>
+			{
+				RefChange::FastForward
+			}
+			_ if force => RefChange::Forced,
+			_ => RefChange::Rejected,
+		};
+
+		Self {
+			name: name.to_string(),
+			old,
+			new,
+			change,
+		}
+	}
+}
+
+/// `gitui.dryRun`: preview what pushes and fetches change before
+/// running them
+pub fn dry_run_enabled(repo_path: &RepoPath) -> Result<bool> {
+	Ok(repo(repo_path)?
+		.config()?
+		.get_bool("gitui.dryRun")
+		.unwrap_or(false))
+}
+
+/// what pushing `refspec` to `remote` would do
+pub fn push_dry_run(
+	repo_path: &RepoPath,
+	remote: &str,
+	refspec: &str,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<Vec<RefUpdate>> {
+	scope_time!("push_dry_run");
+
+	let heads = remote_heads(
+		repo_path,
+		remote,
+		Direction::Push,
+		basic_credential,
+	)?;
+	let repo = repo(repo_path)?;
+
+	let force = refspec.starts_with('+');
+	// push options like gerrit's `%topic=` are no part of the ref
+	let refspec = refspec
+		.trim_start_matches('+')
+		.split('%')
+		.next()
+		.unwrap_or_default();
+	let (src, dst) =
+		refspec.split_once(':').unwrap_or((refspec, refspec));
+
+	let new = if src.is_empty() {
+		None
+	} else {
+		Some(repo.revparse_single(src)?.id().into())
+	};
+	let old = heads
+		.iter()
+		.find(|(name, _)| name == dst)
+		.map(|(_, id)| *id);
+
+	Ok(vec![RefUpdate::new(&repo, dst, old, new, force)])
+}
+
+/// what fetching from `remote` would do to the remote tracking refs
+/// and tags, pruning those gone on the remote like a fetch does
+pub fn fetch_dry_run(
+	repo_path: &RepoPath,
+	remote: &str,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<Vec<RefUpdate>> {
+	scope_time!("fetch_dry_run");
+
+	let heads = remote_heads(
+		repo_path,
+		remote,
+		Direction::Fetch,
+		basic_credential,
+	)?;
+	let repo = repo(repo_path)?;
+	let remote = repo.find_remote(remote)?;
+	let specs = remote
+		.refspecs()
+		.filter(|spec| matches!(spec.direction(), Direction::Fetch))
+		.collect::<Vec<_>>();
+	let target = |name: &str| {
+		repo.refname_to_id(name).ok().map(CommitId::from)
+	};
+
+	// tags get fetched using `refs/tags/*:refs/tags/*` as well
+	let local_ref = |name: &str| {
+		specs
+			.iter()
+			.find(|spec| spec.src_matches(name))
+			.and_then(|spec| {
+				let local = spec.transform(name).ok()?;
+				Some((local.as_str()?.to_string(), spec.is_force()))
+			})
+			.or_else(|| {
+				name.starts_with("refs/tags/")
+					.then(|| (name.to_string(), false))
+			})
+	};
+	let remote_ref = |name: &str| {
+		specs
+			.iter()
+			.find(|spec| spec.dst_matches(name))
+			.and_then(|spec| {
+				spec.rtransform(name)
+					.ok()?
+					.as_str()
+					.map(ToString::to_string)
+			})
+			.or_else(|| {
+				name.starts_with("refs/tags/")
+					.then(|| name.to_string())
+			})
+	};
+
+	let mut res = heads
+		.iter()
+		.filter_map(|(name, id)| {
+			let (local, force) = local_ref(name)?;
+			Some(RefUpdate::new(
+				&repo,
+				&local,
+				target(&local),
+				Some(*id),
+				force,
+			))
+		})
+		.collect::<Vec<_>>();
+
+	let advertised = heads
+		.iter()
+		.map(|(name, _)| name.as_str())
+		.collect::<HashSet<_>>();
+	// `origin/HEAD` and such are no tracking refs
+	for reference in
+		repo.references()?.flatten().filter(|reference| {
+			reference.kind() == Some(ReferenceType::Direct)
+		}) {
+		if let Some(name) = reference.name() {
+			if remote_ref(name).map_or(false, |src| {
+				!advertised.contains(src.as_str())
+			}) {
+				res.push(RefUpdate::new(
+					&repo,
+					name,
+					target(name),
+					None,
+					true,
+				));
+			}
+		}
+	}
+
+	Ok(res)
+}
+
+/// `fetch_dry_run` for all remotes like `fetch_all` fetches them
+pub fn fetch_all_dry_run(
+	repo_path: &RepoPath,
+	basic_credential: &Option<BasicAuthCredential>,
+) -> Result<Vec<RefUpdate>> {
+	let remotes = repo(repo_path)?
+		.remotes()?
+		.iter()
+		.flatten()
+		.map(String::from)
+		.collect::<Vec<_>>();
+
+	let mut res = Vec::new();
+	for remote in remotes {
+		for update in fetch_dry_run(
+			repo_path,
+			&remote,
+			basic_credential.clone(),
+		)? {
+			if !res.contains(&update) {
+				res.push(update);
+			}
+		}
+	}
+
+	Ok(res)
+}
+
+/// the refs `remote` advertises, without peeled tags
+pub(crate) fn remote_heads(
+	repo_path: &RepoPath,
+	remote: &str,
+	direction: Direction,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<Vec<(String, CommitId)>> {
+	let (path, remote) = (repo_path.clone(), remote.to_string());
+
+	with_retry(repo_path, None, move |watchdog| {
+		remote_heads_attempt(
+			&path,
+			&remote,
+			direction,
+			basic_credential.clone(),
+			watchdog,
+		)
+	})
+}
+
+fn remote_heads_attempt(
+	repo_path: &RepoPath,
+	remote: &str,
+	direction: Direction,
+	basic_credential: Option<BasicAuthCredential>,
+	watchdog: Watchdog,
+) -> Result<Vec<(String, CommitId)>> {
+	let repo = repo(repo_path)?;
+	let mut remote = repo.find_remote(remote)?;
+	let transport = Transport::new(&repo, &remote)?;
+	let callbacks = Callbacks::new(None, basic_credential)
+		.with_watchdog(watchdog);
+	let conn = remote
+		.connect_auth(
+			direction,
+			Some(callbacks.callbacks()),
+			Some(transport.proxy_options()),
+		)
+		.map_err(|e| transport.map_err(e))?;
+
+	Ok(conn
+		.list()?
+		.iter()
+		.filter(|head| !head.name().ends_with("^{}"))
+		.map(|head| (head.name().to_string(), head.oid().into()))
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		remotes::{fetch_all, push::push_branch},
+		tests::{repo_clone, repo_init_bare, write_commit_file},
+	};
+
+	#[test]
+	fn test_push_dry_run() {
+		let (upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir, repo) = repo_clone(upstream_path).unwrap();
+		let repo_path: &RepoPath =
+			&dir.path().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "a.txt", "a", "first");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			push_dry_run(
+				repo_path,
+				"origin",
+				"HEAD:refs/for/master%topic=x",
+				None
+			)
+			.unwrap(),
+			vec![RefUpdate {
+				name: "refs/for/master".into(),
+				old: None,
+				new: Some(first),
+				change: RefChange::Create,
+			}]
+		);
+
+		let second = write_commit_file(&repo, "a.txt", "b", "second");
+
+		let updates = push_dry_run(
+			repo_path,
+			"origin",
+			"refs/heads/master",
+			None,
+		)
+		.unwrap();
+		assert_eq!(updates[0].old, Some(first));
+		assert_eq!(updates[0].new, Some(second));
+		assert_eq!(updates[0].change, RefChange::FastForward);
+
+		assert_eq!(
+			push_dry_run(
+				repo_path,
+				"origin",
+				":refs/heads/master",
+				None
+			)
+			.unwrap()[0]
+				.change,
+			RefChange::Delete
+		);
+	}
+
+	#[test]
+	fn test_fetch_dry_run() {
+		let (upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir1, repo1) = repo_clone(upstream_path).unwrap();
+		let (dir2, repo2) = repo_clone(upstream_path).unwrap();
+		let path1: &RepoPath = &dir1.path().to_str().unwrap().into();
+		let path2: &RepoPath = &dir2.path().to_str().unwrap().into();
+
+		let commit = write_commit_file(&repo1, "a.txt", "a", "first");
+		push_branch(
+			path1, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let updates = fetch_all_dry_run(path2, &None).unwrap();
+		assert_eq!(
+			updates,
+			vec![RefUpdate {
+				name: "refs/remotes/origin/master".into(),
+				old: None,
+				new: Some(commit),
+				change: RefChange::Create,
+			}]
+		);
+
+		// nothing got fetched
+		assert!(fetch_all_dry_run(path2, &None).unwrap() == updates);
+		fetch_all(path2, &None, &None, &None).unwrap();
+		repo2
+			.reference(
+				"refs/remotes/origin/gone",
+				commit.into(),
+				false,
+				"",
+			)
+			.unwrap();
+		assert_eq!(
+			fetch_all_dry_run(path2, &None)
+				.unwrap()
+				.iter()
+				.map(|update| (update.name.as_str(), update.change))
+				.collect::<Vec<_>>(),
+			vec![
+				("refs/remotes/origin/master", RefChange::UpToDate),
+				("refs/remotes/origin/gone", RefChange::Delete)
+			]
+		);
+
+		// the remote advanced to a commit not fetched yet
+		let advanced =
+			write_commit_file(&repo1, "a.txt", "b", "second");
+		push_branch(
+			path1, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+		assert_eq!(
+			fetch_all_dry_run(path2, &None).unwrap()[0],
+			RefUpdate {
+				name: "refs/remotes/origin/master".into(),
+				old: Some(commit),
+				new: Some(advanced),
+				change: RefChange::Unknown,
+			}
+		);
+	}
+}
@@ -1,37 +1,38 @@
 //!
 
 mod callbacks;
+mod divergence;
+pub mod dry_run;
 pub(crate) mod push;
+pub(crate) mod push_cli;
+mod retry;
 pub(crate) mod tags;
+mod transport;
 
 use crate::{
 	error::{Error, Result},
 	sync::{
-		cred::BasicAuthCredential,
+		action_log::log_action, cred::BasicAuthCredential,
 		remotes::push::ProgressNotification, repository::repo, utils,
 	},
-	ProgressPercent,
 };
 use crossbeam_channel::Sender;
-use git2::{BranchType, FetchOptions, ProxyOptions, Repository};
+use git2::{BranchType, FetchOptions, Repository};
 use scopetime::scope_time;
+use std::sync::{atomic::AtomicBool, Arc};
 use utils::bytes2string;
 
 pub use callbacks::Callbacks;
+pub use divergence::{divergence_preview, Divergence};
+pub(crate) use retry::{with_retry, Watchdog};
 pub use tags::tags_missing_remote;
+pub(crate) use transport::Transport;
 
 use super::RepoPath;
 
 /// origin
 pub const DEFAULT_REMOTE_NAME: &str = "origin";
 
-///
-pub fn proxy_auto<'a>() -> ProxyOptions<'a> {
-	let mut proxy = ProxyOptions::new();
-	proxy.auto();
-	proxy
-}
-
 ///
 pub fn get_remotes(repo_path: &RepoPath) -> Result<Vec<String>> {
 	scope_time!("get_remotes");
@@ -91,33 +92,64 @@ fn fetch_from_remote(
 	remote: &str,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+) -> Result<()> {
+	let (path, remote) = (repo_path.clone(), remote.to_string());
+	let watch_cancel = cancel.clone();
+
+	with_retry(repo_path, watch_cancel.as_ref(), move |watchdog| {
+		fetch_from_remote_attempt(
+			&path,
+			&remote,
+			basic_credential.clone(),
+			progress_sender.clone(),
+			cancel.clone(),
+			watchdog,
+		)
+	})
+}
+
+fn fetch_from_remote_attempt(
+	repo_path: &RepoPath,
+	remote: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+	watchdog: Watchdog,
 ) -> Result<()> {
 	let repo = repo(repo_path)?;
 
 	let mut remote = repo.find_remote(remote)?;
+	let transport = Transport::new(&repo, &remote)?;
 
 	let mut options = FetchOptions::new();
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_cancel(cancel)
+		.with_watchdog(watchdog);
 	options.prune(git2::FetchPrune::On);
-	options.proxy_options(proxy_auto());
+	options.proxy_options(transport.proxy_options());
 	options.download_tags(git2::AutotagOption::All);
 	options.remote_callbacks(callbacks.callbacks());
-	remote.fetch(&[] as &[&str], Some(&mut options), None)?;
+	remote
+		.fetch(&[] as &[&str], Some(&mut options), None)
+		.map_err(|e| transport.map_err(e))?;
 	// fetch tags (also removing remotely deleted ones)
-	remote.fetch(
-		&["refs/tags/*:refs/tags/*"],
-		Some(&mut options),
-		None,
-	)?;
+	remote
+		.fetch(&["refs/tags/*:refs/tags/*"], Some(&mut options), None)
+		.map_err(|e| transport.map_err(e))?;
+
+	log_action(&repo, "fetch", remote.name().unwrap_or_default());
 
 	Ok(())
 }
 
-/// updates/prunes all branches from all remotes
+/// updates/prunes all branches from all remotes, stops at the next
+/// chance the transfer allows once `cancel` gets set
 pub fn fetch_all(
 	repo_path: &RepoPath,
 	basic_credential: &Option<BasicAuthCredential>,
-	progress_sender: &Option<Sender<ProgressPercent>>,
+	progress_sender: &Option<Sender<ProgressNotification>>,
+	cancel: &Option<Arc<AtomicBool>>,
 ) -> Result<()> {
 	scope_time!("fetch_all");
 
@@ -128,20 +160,15 @@ pub fn fetch_all(
 		.flatten()
 		.map(String::from)
 		.collect::<Vec<_>>();
-	let remotes_count = remotes.len();
 
-	for (idx, remote) in remotes.into_iter().enumerate() {
+	for remote in remotes {
 		fetch_from_remote(
 			repo_path,
 			&remote,
 			basic_credential.clone(),
-			None,
+			progress_sender.clone(),
+			cancel.clone(),
 		)?;
-
-		if let Some(sender) = progress_sender {
-			let progress = ProgressPercent::new(idx, remotes_count);
-			sender.send(progress)?;
-		}
 	}
 
 	Ok(())
@@ -153,9 +180,33 @@ pub(crate) fn fetch(
 	branch: &str,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
 ) -> Result<usize> {
 	scope_time!("fetch");
 
+	let (path, branch) = (repo_path.clone(), branch.to_string());
+	let watch_cancel = cancel.clone();
+
+	with_retry(repo_path, watch_cancel.as_ref(), move |watchdog| {
+		fetch_attempt(
+			&path,
+			&branch,
+			basic_credential.clone(),
+			progress_sender.clone(),
+			cancel.clone(),
+			watchdog,
+		)
+	})
+}
+
+fn fetch_attempt(
+	repo_path: &RepoPath,
+	branch: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+	watchdog: Watchdog,
+) -> Result<usize> {
 	let repo = repo(repo_path)?;
 	let branch_ref = repo
 		.find_branch(branch, BranchType::Local)?
@@ -164,14 +215,25 @@ pub(crate) fn fetch(
 	let remote_name = repo.branch_upstream_remote(&branch_ref)?;
 	let remote_name = bytes2string(&remote_name)?;
 	let mut remote = repo.find_remote(&remote_name)?;
+	let transport = Transport::new(&repo, &remote)?;
 
 	let mut options = FetchOptions::new();
 	options.download_tags(git2::AutotagOption::All);
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_cancel(cancel)
+		.with_watchdog(watchdog);
 	options.remote_callbacks(callbacks.callbacks());
-	options.proxy_options(proxy_auto());
+	options.proxy_options(transport.proxy_options());
+
+	remote
+		.fetch(&[branch], Some(&mut options), None)
+		.map_err(|e| transport.map_err(e))?;
 
-	remote.fetch(&[branch], Some(&mut options), None)?;
+	log_action(
+		&repo,
+		"fetch",
+		&format!("{} {}", remote_name, branch),
+	);
 
 	Ok(remote.stats().received_bytes())
 }
@@ -180,7 +242,7 @@ pub(crate) fn fetch(
 mod tests {
 	use super::*;
 	use crate::sync::tests::{
-		debug_cmd_print, repo_clone, repo_init,
+		debug_cmd_print, repo_clone, repo_init, write_commit_file,
 	};
 
 	#[test]
@@ -199,7 +261,48 @@ mod tests {
 
 		assert_eq!(remotes, vec![String::from("origin")]);
 
-		fetch(repo_path, "master", None, None).unwrap();
+		fetch(repo_path, "master", None, None, None).unwrap();
+	}
+
+	#[test]
+	fn test_fetch_cancelled() {
+		let (remote_dir, remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let (repo_dir, _repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&remote, "test.txt", "foo", "commit");
+
+		let cancel = Arc::new(AtomicBool::new(true));
+		assert!(fetch(repo_path, "master", None, None, Some(cancel))
+			.is_err());
+
+		let cancel = Some(Arc::new(AtomicBool::new(true)));
+		assert!(fetch_all(repo_path, &None, &None, &cancel).is_err());
+
+		let bytes =
+			fetch(repo_path, "master", None, None, None).unwrap();
+		assert!(bytes > 0);
+	}
+
+	#[test]
+	fn test_fetch_proxy_error() {
+		let (repo_dir, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		repo.remote("origin", "http://127.0.0.1:1/repo.git")
+			.unwrap();
+		repo.config()
+			.unwrap()
+			.set_str("http.proxy", "http://127.0.0.1:1")
+			.unwrap();
+
+		assert!(matches!(
+			fetch_all(repo_path, &None, &None, &None),
+			Err(Error::Proxy(..))
+		));
 	}
 
 	#[test]
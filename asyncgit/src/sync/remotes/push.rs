@@ -2,16 +2,21 @@ use crate::{
 	error::{Error, Result},
 	progress::ProgressPercent,
 	sync::{
+		action_log::log_action,
 		branch::branch_set_upstream,
+		config::get_config_string_repo,
 		cred::BasicAuthCredential,
-		remotes::{proxy_auto, Callbacks},
+		remotes::{with_retry, Callbacks, Transport, Watchdog},
 		repository::repo,
 		CommitId, RepoPath,
 	},
 };
 use crossbeam_channel::Sender;
-use git2::{PackBuilderStage, PushOptions};
+use git2::{
+	Direction, PackBuilderStage, PushOptions, Remote, Repository,
+};
 use scopetime::scope_time;
+use std::sync::{atomic::AtomicBool, Arc};
 
 ///
 pub trait AsyncProgress: Clone + Send + Sync {
@@ -39,6 +44,12 @@ pub enum ProgressNotification {
 		objects: usize,
 		///
 		total_objects: usize,
+		///
+		bytes: usize,
+		///
+		indexed_deltas: usize,
+		///
+		total_deltas: usize,
 	},
 	///
 	PushTransfer {
@@ -91,6 +102,46 @@ impl AsyncProgress for ProgressNotification {
 	}
 }
 
+/// a remote ref a push updated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushedRef {
+	/// on the remote, like `refs/heads/master`
+	pub name: String,
+	/// `None` if created or not known locally
+	pub old: Option<CommitId>,
+	/// `None` if deleted
+	pub new: Option<CommitId>,
+	/// `None` unless both `old` and `new` are known
+	pub fast_forward: Option<bool>,
+}
+
+impl PushedRef {
+	/// `old..new` using full ids, for handing to other tools
+	pub fn range(&self) -> Option<String> {
+		self.old.zip(self.new).map(|(old, new)| {
+			format!("{}..{}", old.to_string(), new.to_string())
+		})
+	}
+}
+
+/// what a push did on the remote
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PushSummary {
+	///
+	pub remote: String,
+	///
+	pub refs: Vec<PushedRef>,
+	/// what the server printed, like the output of its hooks
+	pub messages: Vec<String>,
+}
+
+impl PushSummary {
+	/// the commit range of the first ref updated with a known range
+	pub fn range(&self) -> Option<String> {
+		self.refs.iter().find_map(PushedRef::range)
+	}
+}
+
 ///
 #[derive(Copy, Clone, Debug)]
 pub enum PushType {
@@ -115,7 +166,7 @@ pub fn push_branch(
 	delete: bool,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
-) -> Result<()> {
+) -> Result<PushSummary> {
 	push_raw(
 		repo_path,
 		remote,
@@ -125,6 +176,7 @@ pub fn push_branch(
 		delete,
 		basic_credential,
 		progress_sender,
+		None,
 	)
 }
 
@@ -139,19 +191,97 @@ pub fn push_raw(
 	delete: bool,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
-) -> Result<()> {
+	cancel: Option<Arc<AtomicBool>>,
+) -> Result<PushSummary> {
 	scope_time!("push");
 
 	let repo = repo(repo_path)?;
-	let mut remote = repo.find_remote(remote)?;
 
-	let mut options = PushOptions::new();
-	options.proxy_options(proxy_auto());
+	let branch_name =
+		push_raw_refspec(branch, ref_type, force, delete);
+	let summary = push_to_remote(
+		repo_path,
+		remote,
+		&branch_name,
+		None,
+		basic_credential,
+		progress_sender,
+		cancel,
+	)?;
 
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
-	options.remote_callbacks(callbacks.callbacks());
-	options.packbuilder_parallelism(0);
+	if !delete {
+		branch_set_upstream(&repo, branch)?;
+	}
+
+	Ok(summary)
+}
+
+/// force pushes `branch` only if it is still at `expected` on
+/// `remote`, like `git push --force-with-lease=<branch>:<expected>`
+pub fn push_branch_leased(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	expected: CommitId,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+) -> Result<PushSummary> {
+	scope_time!("push_branch_leased");
+
+	let repo = repo(repo_path)?;
+
+	let summary = push_to_remote(
+		repo_path,
+		remote,
+		&push_raw_refspec(branch, PushType::Branch, true, false),
+		Some(Lease {
+			reference: format!("refs/heads/{}", branch),
+			expected,
+		}),
+		basic_credential,
+		progress_sender,
+		cancel,
+	)?;
+
+	branch_set_upstream(&repo, branch)?;
+
+	Ok(summary)
+}
+
+/// deletes `branch` on `remote` only if it is still at `expected`
+pub fn delete_branch_leased(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	expected: CommitId,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+) -> Result<PushSummary> {
+	scope_time!("delete_branch_leased");
+
+	push_to_remote(
+		repo_path,
+		remote,
+		&push_raw_refspec(branch, PushType::Branch, false, true),
+		Some(Lease {
+			reference: format!("refs/heads/{}", branch),
+			expected,
+		}),
+		basic_credential,
+		progress_sender,
+		cancel,
+	)
+}
 
+/// the refspec `push_raw` pushes
+pub fn push_raw_refspec(
+	branch: &str,
+	ref_type: PushType,
+	force: bool,
+	delete: bool,
+) -> String {
 	let branch_modifier = match (force, delete) {
 		(true, true) => "+:",
 		(false, true) => ":",
@@ -163,24 +293,291 @@ pub fn push_raw(
 		PushType::Tag => "tags",
 	};
 
-	let branch_name =
-		format!("{}refs/{}/{}", branch_modifier, ref_type, branch);
-	remote.push(&[branch_name.as_str()], Some(&mut options))?;
-
-	if let Some((reference, msg)) =
-		callbacks.get_stats()?.push_rejected_msg
-	{
-		return Err(Error::Generic(format!(
-			"push to '{}' rejected: {}",
-			reference, msg
-		)));
+	format!("{}refs/{}/{}", branch_modifier, ref_type, branch)
+}
+
+/// pushes an explicit `refspec` (e.g. `HEAD:refs/for/master`)
+/// without touching any upstream configuration
+pub fn push_refspec(
+	repo_path: &RepoPath,
+	remote: &str,
+	refspec: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+) -> Result<PushSummary> {
+	scope_time!("push_refspec");
+
+	push_to_remote(
+		repo_path,
+		remote,
+		refspec,
+		None,
+		basic_credential,
+		progress_sender,
+		cancel,
+	)
+}
+
+/// where the remote ref has to be for a push to go ahead
+#[derive(Clone)]
+struct Lease {
+	reference: String,
+	expected: CommitId,
+}
+
+fn push_to_remote(
+	repo_path: &RepoPath,
+	remote: &str,
+	refspec: &str,
+	lease: Option<Lease>,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+) -> Result<PushSummary> {
+	let (path, remote, refspec) =
+		(repo_path.clone(), remote.to_string(), refspec.to_string());
+	let watch_cancel = cancel.clone();
+
+	with_retry(repo_path, watch_cancel.as_ref(), move |watchdog| {
+		push_to_remote_attempt(
+			&path,
+			&remote,
+			&refspec,
+			lease.as_ref(),
+			basic_credential.clone(),
+			progress_sender.clone(),
+			cancel.clone(),
+			watchdog,
+		)
+	})
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_to_remote_attempt(
+	repo_path: &RepoPath,
+	remote: &str,
+	refspec: &str,
+	lease: Option<&Lease>,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+	cancel: Option<Arc<AtomicBool>>,
+	watchdog: Watchdog,
+) -> Result<PushSummary> {
+	let repo = repo(repo_path)?;
+	let mut remote = repo.find_remote(remote)?;
+	let transport = Transport::new(&repo, &remote)?;
+
+	let mut options = PushOptions::new();
+	options.proxy_options(transport.proxy_options());
+
+	let callbacks =
+		Callbacks::new(progress_sender, basic_credential.clone())
+			.with_cancel(cancel.clone())
+			.with_watchdog(watchdog.clone());
+	options.remote_callbacks(callbacks.callbacks());
+	options.packbuilder_parallelism(0);
+
+	if let Some(lease) = lease {
+		let connect_callbacks =
+			Callbacks::new(None, basic_credential)
+				.with_cancel(cancel)
+				.with_watchdog(watchdog);
+		let mut connection = remote
+			.connect_auth(
+				Direction::Push,
+				Some(connect_callbacks.callbacks()),
+				Some(transport.proxy_options()),
+			)
+			.map_err(|e| transport.map_err(e))?;
+
+		let current = connection
+			.list()?
+			.iter()
+			.find(|head| head.name() == lease.reference)
+			.map(|head| CommitId::from(head.oid()));
+		if current != Some(lease.expected) {
+			return Err(Error::Generic(format!(
+				"{} changed on the remote since its commits got listed, nothing was pushed",
+				lease.reference
+			)));
+		}
+
+		// libgit2 sends the tip the connection advertised as the
+		// old value, the remote only updates the ref while it still
+		// points there
+		connection
+			.remote()
+			.push(&[refspec], Some(&mut options))
+			.map_err(|e| transport.map_err(e))?;
+	} else {
+		remote
+			.push(&[refspec], Some(&mut options))
+			.map_err(|e| transport.map_err(e))?;
 	}
 
-	if !delete {
-		branch_set_upstream(&repo, branch)?;
+	let stats = callbacks.get_stats()?;
+	let messages = stats.remote_messages();
+
+	if let Some((reference, msg)) = stats.push_rejected_msg() {
+		let mut err =
+			format!("push to '{}' rejected: {}", reference, msg);
+		if !messages.is_empty() {
+			err.push_str("\n\nremote:\n");
+			err.push_str(&messages.join("\n"));
+		}
+		return Err(Error::Generic(err));
 	}
 
-	Ok(())
+	log_action(
+		&repo,
+		"push",
+		&format!("{} {}", remote.name().unwrap_or_default(), refspec),
+	);
+
+	Ok(PushSummary {
+		remote: remote.name().unwrap_or_default().to_string(),
+		refs: stats
+			.pushed_refs
+			.iter()
+			.map(|(name, _)| {
+				pushed_ref(
+					&repo,
+					&remote,
+					refspec,
+					name,
+					&stats.updated_tips,
+				)
+			})
+			.collect(),
+		messages,
+	})
+}
+
+/// `old` is only known from the remote tracking ref the push updated,
+/// without one `new` is what `refspec` pushed
+fn pushed_ref(
+	repo: &Repository,
+	remote: &Remote,
+	refspec: &str,
+	name: &str,
+	tips: &[(String, CommitId, CommitId)],
+) -> PushedRef {
+	let tip = remote
+		.refspecs()
+		.filter(|spec| {
+			matches!(spec.direction(), Direction::Fetch)
+				&& spec.src_matches(name)
+		})
+		.find_map(|spec| spec.transform(name).ok())
+		.and_then(|tracking| {
+			let tracking = tracking.as_str()?.to_string();
+			tips.iter().find(|(tip, _, _)| *tip == tracking)
+		});
+
+	let known = |id: CommitId| (!id.get_oid().is_zero()).then(|| id);
+	let (old, new) = tip.map_or_else(
+		|| (None, pushed_commit(repo, refspec)),
+		|(_, old, new)| (known(*old), known(*new)),
+	);
+
+	let fast_forward = old.zip(new).map(|(old, new)| {
+		old == new
+			|| repo
+				.graph_descendant_of(new.into(), old.into())
+				.unwrap_or_default()
+	});
+
+	PushedRef {
+		name: name.to_string(),
+		old,
+		new,
+		fast_forward,
+	}
+}
+
+/// the commit the source of `refspec` points to, `None` for deletes
+fn pushed_commit(
+	repo: &Repository,
+	refspec: &str,
+) -> Option<CommitId> {
+	let src = refspec.trim_start_matches('+').split(':').next()?;
+	if src.is_empty() {
+		return None;
+	}
+
+	repo.revparse_single(src)
+		.ok()?
+		.peel_to_commit()
+		.ok()
+		.map(|commit| commit.id().into())
+}
+
+/// gerrit style destination used when `gitui.reviewRefspec` is not set
+pub const DEFAULT_REVIEW_REFSPEC: &str =
+	"refs/for/{branch}%topic={topic}";
+
+/// destination template for "push for review", read from
+/// the `gitui.reviewRefspec` config
+pub fn review_refspec_template(
+	repo_path: &RepoPath,
+) -> Result<String> {
+	let repo = repo(repo_path)?;
+
+	Ok(get_config_string_repo(&repo, "gitui.reviewRefspec")?
+		.unwrap_or_else(|| DEFAULT_REVIEW_REFSPEC.to_string()))
+}
+
+/// replaced by the target branch in `gitui.reviewRefspec`
+const BRANCH_PLACEHOLDER: &str = "{branch}";
+/// replaced by the topic in `gitui.reviewRefspec`
+const TOPIC_PLACEHOLDER: &str = "{topic}";
+
+/// whether `topic` can be spliced into a review refspec, which
+/// separates its options by `,` after a `%`
+pub fn review_topic_valid(topic: &str) -> bool {
+	!topic
+		.chars()
+		.any(|c| c == ',' || c == '%' || c.is_whitespace())
+}
+
+/// expands `{branch}` and `{topic}` in `template` into a refspec
+/// pushing `HEAD`, options after `%` using an empty topic are dropped,
+/// see `review_topic_valid`
+pub fn review_refspec(
+	template: &str,
+	branch: &str,
+	topic: &str,
+) -> String {
+	let mut parts = template.splitn(2, '%');
+	let dest = parts
+		.next()
+		.unwrap_or_default()
+		.replace(BRANCH_PLACEHOLDER, branch);
+
+	let options = parts
+		.next()
+		.map(|options| {
+			options
+				.split(',')
+				.filter(|option| {
+					!(topic.is_empty()
+						&& option.contains(TOPIC_PLACEHOLDER))
+				})
+				.map(|option| {
+					option
+						.replace(BRANCH_PLACEHOLDER, branch)
+						.replace(TOPIC_PLACEHOLDER, topic)
+				})
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+
+	if options.is_empty() {
+		format!("HEAD:{}", dest)
+	} else {
+		format!("HEAD:{}%{}", dest, options.join(","))
+	}
 }
 
 #[cfg(test)]
@@ -196,6 +593,181 @@ mod tests {
 	use git2::Repository;
 	use std::{fs::File, io::Write, path::Path};
 
+	#[test]
+	fn test_push_branch_leased() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir1, repo1) = repo_clone(upstream_path).unwrap();
+		let (dir2, repo2) = repo_clone(upstream_path).unwrap();
+		let path1: &RepoPath = &dir1.path().to_str().unwrap().into();
+		let path2: &RepoPath = &dir2.path().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo1, "a.txt", "a", "first");
+		push_branch(
+			path1, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+		let diverged =
+			write_commit_file(&repo2, "b.txt", "b", "diverged");
+
+		// pushed after `first` got previewed as the remote tip
+		let second =
+			write_commit_file(&repo1, "a.txt", "b", "second");
+		push_branch(
+			path1, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		assert!(push_branch_leased(
+			path2, "origin", "master", first, None, None, None,
+		)
+		.is_err());
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/master").unwrap(),
+			second.into()
+		);
+
+		push_branch_leased(
+			path2, "origin", "master", second, None, None, None,
+		)
+		.unwrap();
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/master").unwrap(),
+			diverged.into()
+		);
+	}
+
+	#[test]
+	fn test_review_refspec() {
+		assert_eq!(
+			review_refspec(DEFAULT_REVIEW_REFSPEC, "master", "foo"),
+			"HEAD:refs/for/master%topic=foo"
+		);
+		assert_eq!(
+			review_refspec(DEFAULT_REVIEW_REFSPEC, "master", ""),
+			"HEAD:refs/for/master"
+		);
+		assert_eq!(
+			review_refspec(
+				"refs/for/{branch}%wip,topic={topic}",
+				"dev",
+				""
+			),
+			"HEAD:refs/for/dev%wip"
+		);
+	}
+
+	#[test]
+	fn test_review_topic_valid() {
+		assert!(review_topic_valid(""));
+		assert!(review_topic_valid("fix-login_2"));
+		assert!(!review_topic_valid("a,r=admin"));
+		assert!(!review_topic_valid("a%wip"));
+		assert!(!review_topic_valid("two words"));
+		assert!(!review_topic_valid("tab\t"));
+	}
+
+	#[test]
+	fn test_push_refspec() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_upstream_dir, upstream) = repo_init_bare().unwrap();
+
+		repo.remote(
+			"origin",
+			tmp_upstream_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+
+		let commit = write_commit_file(
+			&repo,
+			"temp_file.txt",
+			"content",
+			"commit",
+		);
+
+		let summary = push_refspec(
+			&tmp_repo_dir.path().to_str().unwrap().into(),
+			"origin",
+			"HEAD:refs/for/master",
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			upstream
+				.find_reference("refs/for/master")
+				.unwrap()
+				.target()
+				.unwrap(),
+			commit.into()
+		);
+		// no tracking ref to tell the old target
+		assert_eq!(
+			summary.refs,
+			vec![PushedRef {
+				name: "refs/for/master".into(),
+				old: None,
+				new: Some(commit),
+				fast_forward: None,
+			}]
+		);
+	}
+
+	#[test]
+	fn test_push_summary() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_upstream_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+
+		let first = write_commit_file(&repo, "a.txt", "a", "first");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let second = write_commit_file(&repo, "a.txt", "b", "second");
+		let summary = push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		assert_eq!(summary.remote, "origin");
+		assert_eq!(
+			summary.refs,
+			vec![PushedRef {
+				name: "refs/heads/master".into(),
+				old: Some(first),
+				new: Some(second),
+				fast_forward: Some(true),
+			}]
+		);
+		assert_eq!(
+			summary.range(),
+			Some(format!(
+				"{}..{}",
+				first.to_string(),
+				second.to_string()
+			))
+		);
+
+		let summary = push_branch(
+			repo_path, "origin", "master", false, true, None, None,
+		)
+		.unwrap();
+		assert_eq!(summary.refs[0].old, Some(second));
+		assert_eq!(summary.refs[0].new, None);
+		assert_eq!(summary.range(), None);
+	}
+
 	#[test]
 	fn test_force_push() {
 		// This test mimics the scenario of 2 people having 2
@@ -0,0 +1,375 @@
+//! pushes via the `git` cli for what libgit2 cannot do
+
+use crate::{
+	error::{Error, Result},
+	sync::{
+		action_log::log_action,
+		branch::branch_set_upstream,
+		remotes::push::{push_raw_refspec, PushType},
+		repository::repo,
+		CommitId, PushSummary, PushedRef, RepoPath,
+	},
+};
+use git2::Repository;
+use scopetime::scope_time;
+use std::{
+	io::Read,
+	process::{Command, Stdio},
+	sync::atomic::{AtomicBool, Ordering},
+	thread,
+	time::Duration,
+};
+
+/// what a push can ask for that libgit2 does not support
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PushCliOptions {
+	/// handed to the server hooks like `git push --push-option`
+	pub push_options: Vec<String>,
+	/// sign the push certificate like `git push --signed`
+	pub signed: bool,
+}
+
+impl PushCliOptions {
+	/// whether pushing has to go through the `git` cli
+	pub fn needs_cli(&self) -> bool {
+		self.signed || !self.push_options.is_empty()
+	}
+}
+
+/// pushes (or deletes) `branch` like `push_raw` but via `git push`
+/// to support `options`, credentials come from the configured git
+/// helpers
+#[allow(clippy::too_many_arguments)]
+pub fn push_branch_cli(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	ref_type: PushType,
+	force: bool,
+	delete: bool,
+	options: &PushCliOptions,
+	cancel: Option<&AtomicBool>,
+) -> Result<PushSummary> {
+	scope_time!("push_branch_cli");
+
+	let repo = repo(repo_path)?;
+	let refspec = push_raw_refspec(branch, ref_type, force, delete);
+
+	let mut cmd = Command::new("git");
+	cmd.arg("--git-dir")
+		.arg(repo.path())
+		.args(["push", "--porcelain"]);
+	if options.signed {
+		cmd.arg("--signed");
+	}
+	for option in &options.push_options {
+		cmd.arg("--push-option").arg(option);
+	}
+	cmd.arg(remote).arg(&refspec);
+
+	let (success, stdout, stderr) = run_cancellable(cmd, cancel)?;
+
+	let messages: Vec<String> = stderr
+		.lines()
+		.filter_map(|line| line.strip_prefix("remote: "))
+		.map(|line| line.trim_end().to_string())
+		.collect();
+	let refs: Vec<(char, PushedRef)> = stdout
+		.lines()
+		.filter_map(|line| porcelain_ref(&repo, line))
+		.collect();
+
+	if !success || refs.iter().any(|(flag, _)| *flag == REJECTED_FLAG)
+	{
+		return Err(Error::Generic(format!(
+			"push to '{}' failed: {}",
+			remote,
+			stderr.trim()
+		)));
+	}
+
+	log_action(&repo, "push", &format!("{} {}", remote, refspec));
+
+	if !delete && matches!(ref_type, PushType::Branch) {
+		branch_set_upstream(&repo, branch)?;
+	}
+
+	Ok(PushSummary {
+		remote: remote.to_string(),
+		refs: refs.into_iter().map(|(_, pushed)| pushed).collect(),
+		messages,
+	})
+}
+
+const REJECTED_FLAG: char = '!';
+
+/// waits for `cmd` to exit, kills it once `cancel` gets set
+fn run_cancellable(
+	mut cmd: Command,
+	cancel: Option<&AtomicBool>,
+) -> Result<(bool, String, String)> {
+	let mut child = cmd
+		.env("GIT_TERMINAL_PROMPT", "0")
+		.stdin(Stdio::null())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	let stdout = read_in_background(child.stdout.take());
+	let stderr = read_in_background(child.stderr.take());
+
+	let status = loop {
+		if let Some(status) = child.try_wait()? {
+			break status;
+		}
+
+		if cancel
+			.map_or(false, |cancel| cancel.load(Ordering::Relaxed))
+		{
+			child.kill().ok();
+			child.wait()?;
+			return Err(Error::Generic("push cancelled".into()));
+		}
+
+		thread::sleep(Duration::from_millis(50));
+	};
+
+	let join = |handle: thread::JoinHandle<String>| {
+		handle.join().map_err(|_| {
+			Error::Generic("reading git output failed".into())
+		})
+	};
+
+	Ok((status.success(), join(stdout)?, join(stderr)?))
+}
+
+/// reads `pipe` on its own thread so the child never blocks on
+/// a full pipe while we wait for it
+fn read_in_background<R: Read + Send + 'static>(
+	pipe: Option<R>,
+) -> thread::JoinHandle<String> {
+	thread::spawn(move || {
+		let mut out = Vec::new();
+		if let Some(mut pipe) = pipe {
+			pipe.read_to_end(&mut out).ok();
+		}
+		String::from_utf8_lossy(&out).into_owned()
+	})
+}
+
+/// parses a `<flag>\t<from>:<to>\t<summary>` line of
+/// `git push --porcelain`
+fn porcelain_ref(
+	repo: &Repository,
+	line: &str,
+) -> Option<(char, PushedRef)> {
+	let mut fields = line.split('\t');
+	let flag = fields.next()?.chars().next()?;
+	let (from, to) = fields.next()?.split_once(':')?;
+	let summary = fields.next().unwrap_or_default();
+
+	let commit = |spec: &str| -> Option<CommitId> {
+		repo.revparse_single(spec)
+			.ok()?
+			.peel_to_commit()
+			.ok()
+			.map(|commit| commit.id().into())
+	};
+
+	let new = if from.is_empty() { None } else { commit(from) };
+	let old = summary
+		.split_whitespace()
+		.next()
+		.and_then(|range| {
+			range.split_once("...").or_else(|| range.split_once(".."))
+		})
+		.and_then(|(old, _)| commit(old));
+
+	let fast_forward = old.zip(new).map(|(old, new)| {
+		old == new
+			|| repo
+				.graph_descendant_of(new.into(), old.into())
+				.unwrap_or_default()
+	});
+
+	Some((
+		flag,
+		PushedRef {
+			name: to.to_string(),
+			old,
+			new,
+			fast_forward,
+		},
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{
+		repo_clone, repo_init_bare, write_commit_file,
+	};
+
+	#[test]
+	fn test_push_options() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir, repo) = repo_clone(upstream_path).unwrap();
+		let path: &RepoPath = &dir.path().to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "a.txt", "a", "first");
+
+		let options = PushCliOptions {
+			push_options: vec![String::from("ci.skip")],
+			signed: false,
+		};
+		assert!(options.needs_cli());
+
+		// the remote has to advertise push options to receive any
+		assert!(push_branch_cli(
+			path,
+			"origin",
+			"master",
+			PushType::Branch,
+			false,
+			false,
+			&options,
+			None
+		)
+		.is_err());
+		assert!(upstream
+			.find_reference("refs/heads/master")
+			.is_err());
+
+		upstream
+			.config()
+			.unwrap()
+			.set_bool("receive.advertisePushOptions", true)
+			.unwrap();
+
+		let summary = push_branch_cli(
+			path,
+			"origin",
+			"master",
+			PushType::Branch,
+			false,
+			false,
+			&options,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/master").unwrap(),
+			first.into()
+		);
+		assert_eq!(summary.remote, "origin");
+		assert_eq!(summary.refs.len(), 1);
+		assert_eq!(summary.refs[0].name, "refs/heads/master");
+		assert_eq!(summary.refs[0].new, Some(first));
+		assert_eq!(summary.refs[0].old, None);
+		assert!(repo
+			.find_branch("master", git2::BranchType::Local)
+			.unwrap()
+			.upstream()
+			.is_ok());
+	}
+
+	#[test]
+	fn test_push_cli_old_tip() {
+		let (upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir, repo) = repo_clone(upstream_path).unwrap();
+		let path: &RepoPath = &dir.path().to_str().unwrap().into();
+
+		let options = PushCliOptions::default();
+		assert!(!options.needs_cli());
+
+		let first = write_commit_file(&repo, "a.txt", "a", "first");
+		push_branch_cli(
+			path,
+			"origin",
+			"master",
+			PushType::Branch,
+			false,
+			false,
+			&options,
+			None,
+		)
+		.unwrap();
+		let second = write_commit_file(&repo, "a.txt", "b", "second");
+
+		let summary = push_branch_cli(
+			path,
+			"origin",
+			"master",
+			PushType::Branch,
+			false,
+			false,
+			&options,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(summary.refs[0].old, Some(first));
+		assert_eq!(summary.refs[0].new, Some(second));
+		assert_eq!(summary.refs[0].fast_forward, Some(true));
+	}
+
+	#[test]
+	fn test_push_cli_tag_and_delete() {
+		let (upstream_dir, upstream) = repo_init_bare().unwrap();
+		let upstream_path = upstream_dir.path().to_str().unwrap();
+		let (dir, repo) = repo_clone(upstream_path).unwrap();
+		let path: &RepoPath = &dir.path().to_str().unwrap().into();
+
+		let options = PushCliOptions {
+			push_options: Vec::new(),
+			signed: false,
+		};
+
+		let first = write_commit_file(&repo, "a.txt", "a", "first");
+		repo.tag_lightweight(
+			"v1",
+			&repo.find_object(first.into(), None).unwrap(),
+			false,
+		)
+		.unwrap();
+
+		push_branch_cli(
+			path,
+			"origin",
+			"v1",
+			PushType::Tag,
+			false,
+			false,
+			&options,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			upstream.refname_to_id("refs/tags/v1").unwrap(),
+			first.into()
+		);
+		assert!(upstream
+			.find_reference("refs/heads/master")
+			.is_err());
+
+		let summary = push_branch_cli(
+			path,
+			"origin",
+			"v1",
+			PushType::Tag,
+			false,
+			true,
+			&options,
+			None,
+		)
+		.unwrap();
+
+		assert!(upstream.find_reference("refs/tags/v1").is_err());
+		assert_eq!(summary.refs[0].name, "refs/tags/v1");
+		assert_eq!(summary.refs[0].new, None);
+	}
+}
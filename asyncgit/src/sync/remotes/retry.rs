@@ -0,0 +1,383 @@
+//! timeouts and retries of remote operations
+
+use crate::{
+	error::{Error, Result},
+	sync::{repository::repo, RepoPath},
+};
+use crossbeam_channel::{bounded, RecvTimeoutError};
+use git2::{ErrorClass, Repository};
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	thread,
+	time::{Duration, Instant},
+};
+
+/// seconds without any progress before giving up on an attempt
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+/// attempts after the first one failed
+const DEFAULT_RETRIES: u32 = 2;
+/// delay before the first retry, doubled for every further one
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// `gitui.remoteTimeout` (seconds without progress, `0` disables it)
+/// and `gitui.remoteRetries` of the repo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RemoteTimeouts {
+	///
+	pub stall: Option<Duration>,
+	///
+	pub retries: u32,
+}
+
+impl RemoteTimeouts {
+	///
+	pub fn new(repo: &Repository) -> Result<Self> {
+		let cfg = repo.config()?;
+
+		let stall = cfg
+			.get_i64("gitui.remoteTimeout")
+			.map_or(Ok(DEFAULT_TIMEOUT_SECS), u64::try_from)?;
+		let retries = cfg
+			.get_i64("gitui.remoteRetries")
+			.map_or(Ok(DEFAULT_RETRIES), u32::try_from)?;
+
+		Ok(Self {
+			stall: (stall > 0).then(|| Duration::from_secs(stall)),
+			retries,
+		})
+	}
+
+	fn backoff(attempt: u32) -> Duration {
+		RETRY_BACKOFF * 2_u32.saturating_pow(attempt)
+	}
+}
+
+/// tracks the progress of one attempt, the transfer callbacks
+/// abort once it got `abort`ed
+#[derive(Clone)]
+pub struct Watchdog {
+	last_activity: Arc<Mutex<Instant>>,
+	aborted: Arc<AtomicBool>,
+	transferred: Arc<AtomicBool>,
+	finished: Arc<AtomicBool>,
+}
+
+impl Watchdog {
+	fn new() -> Self {
+		Self {
+			last_activity: Arc::new(Mutex::new(Instant::now())),
+			aborted: Arc::new(AtomicBool::new(false)),
+			transferred: Arc::new(AtomicBool::new(false)),
+			finished: Arc::new(AtomicBool::new(false)),
+		}
+	}
+
+	/// to be called on every sign of life of the remote
+	pub fn touch(&self) {
+		if let Ok(mut last_activity) = self.last_activity.lock() {
+			*last_activity = Instant::now();
+		}
+	}
+
+	/// to be called once the attempt got past connecting, from then on
+	/// it may have changed something on either side and is not retried
+	pub fn mark_transferred(&self) {
+		self.transferred.store(true, Ordering::Relaxed);
+	}
+
+	fn has_transferred(&self) -> bool {
+		self.transferred.load(Ordering::Relaxed)
+	}
+
+	/// the thread of the attempt is done, even if it got given up on
+	fn is_finished(&self) -> bool {
+		self.finished.load(Ordering::Relaxed)
+	}
+
+	///
+	pub fn is_aborted(&self) -> bool {
+		self.aborted.load(Ordering::Relaxed)
+	}
+
+	fn abort(&self) {
+		self.aborted.store(true, Ordering::Relaxed);
+	}
+
+	fn idle(&self) -> Duration {
+		self.last_activity
+			.lock()
+			.map(|last_activity| last_activity.elapsed())
+			.unwrap_or_default()
+	}
+}
+
+/// runs `op` retrying it with exponential backoff when it fails for
+/// network reasons or timed out while still connecting.
+///
+/// libgit2 blocks on a dead connection without ever calling back, so
+/// every attempt runs on its own thread that is given up on (and told to
+/// abort via its `Watchdog`) once it made no progress for the timeout.
+/// An attempt that got past connecting (see
+/// `Watchdog::mark_transferred`) or whose thread is still running after
+/// the backoff is not retried, so two never race each other.
+pub fn with_retry<T, F>(
+	repo_path: &RepoPath,
+	cancel: Option<&Arc<AtomicBool>>,
+	op: F,
+) -> Result<T>
+where
+	T: Send + 'static,
+	F: Fn(Watchdog) -> Result<T> + Send + Sync + 'static,
+{
+	let timeouts = RemoteTimeouts::new(&repo(repo_path)?)?;
+	let op = Arc::new(op);
+
+	let mut attempt = 0;
+	loop {
+		let watchdog = Watchdog::new();
+		match run_attempt(
+			timeouts.stall,
+			cancel,
+			Arc::clone(&op),
+			&watchdog,
+		) {
+			Err(e)
+				if attempt < timeouts.retries
+					&& is_retryable(&e)
+					&& !watchdog.has_transferred()
+					&& !is_cancelled(cancel) =>
+			{
+				let delay = RemoteTimeouts::backoff(attempt);
+				log::warn!(
+					"remote operation failed (attempt {}), retrying in {:?}: {}",
+					attempt + 1,
+					delay,
+					e
+				);
+
+				if !sleep_unless_cancelled(delay, cancel) {
+					return Err(e);
+				}
+
+				if !watchdog.is_finished() {
+					log::warn!(
+						"previous attempt still running, not retrying"
+					);
+					return Err(e);
+				}
+
+				attempt += 1;
+			}
+			res => return res,
+		}
+	}
+}
+
+fn run_attempt<T, F>(
+	stall: Option<Duration>,
+	cancel: Option<&Arc<AtomicBool>>,
+	op: Arc<F>,
+	watchdog: &Watchdog,
+) -> Result<T>
+where
+	T: Send + 'static,
+	F: Fn(Watchdog) -> Result<T> + Send + Sync + 'static,
+{
+	let (tx, rx) = bounded(1);
+
+	{
+		let watchdog = watchdog.clone();
+		thread::spawn(move || {
+			let res = op(watchdog.clone());
+			watchdog.finished.store(true, Ordering::Relaxed);
+			// the receiver is gone if we gave up on this attempt
+			tx.send(res).ok();
+		});
+	}
+
+	loop {
+		match rx.recv_timeout(POLL_INTERVAL) {
+			Ok(res) => return res,
+			Err(RecvTimeoutError::Disconnected) => {
+				return Err(Error::Generic(
+					"remote operation aborted".into(),
+				));
+			}
+			Err(RecvTimeoutError::Timeout) => {
+				if is_cancelled(cancel) {
+					watchdog.abort();
+					return Err(Error::Generic("cancelled".into()));
+				}
+
+				if let Some(stall) = stall {
+					if watchdog.idle() >= stall {
+						watchdog.abort();
+						return Err(Error::Timeout(stall.as_secs()));
+					}
+				}
+			}
+		}
+	}
+}
+
+/// timeouts and connection problems, no auth or rejected pushes
+fn is_retryable(e: &Error) -> bool {
+	match e {
+		Error::Timeout(_) => true,
+		Error::Git(e) => {
+			matches!(e.class(), ErrorClass::Net | ErrorClass::Os)
+		}
+		_ => false,
+	}
+}
+
+fn is_cancelled(cancel: Option<&Arc<AtomicBool>>) -> bool {
+	cancel.map_or(false, |cancel| cancel.load(Ordering::Relaxed))
+}
+
+/// returns `false` if `cancel` got set in the meantime
+fn sleep_unless_cancelled(
+	delay: Duration,
+	cancel: Option<&Arc<AtomicBool>>,
+) -> bool {
+	let until = Instant::now() + delay;
+	while Instant::now() < until {
+		if is_cancelled(cancel) {
+			return false;
+		}
+		thread::sleep(POLL_INTERVAL);
+	}
+	!is_cancelled(cancel)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init;
+	use git2::ErrorCode;
+	use std::sync::atomic::AtomicUsize;
+
+	fn repo_with_timeouts(
+		timeout: i64,
+		retries: i64,
+	) -> (tempfile::TempDir, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let mut cfg = repo.config().unwrap();
+		cfg.set_i64("gitui.remoteTimeout", timeout).unwrap();
+		cfg.set_i64("gitui.remoteRetries", retries).unwrap();
+		let repo_path = td.path().to_str().unwrap().into();
+		(td, repo_path)
+	}
+
+	#[test]
+	fn test_timeouts_config() {
+		let (_td, repo) = repo_init().unwrap();
+
+		assert_eq!(
+			RemoteTimeouts::new(&repo).unwrap(),
+			RemoteTimeouts {
+				stall: Some(Duration::from_secs(60)),
+				retries: 2
+			}
+		);
+
+		repo.config()
+			.unwrap()
+			.set_i64("gitui.remoteTimeout", 0)
+			.unwrap();
+
+		assert_eq!(RemoteTimeouts::new(&repo).unwrap().stall, None);
+	}
+
+	#[test]
+	fn test_stalled_attempt_times_out() {
+		let (_td, repo_path) = repo_with_timeouts(1, 0);
+
+		let res = with_retry(&repo_path, None, |watchdog| {
+			while !watchdog.is_aborted() {
+				thread::sleep(Duration::from_millis(10));
+			}
+			Ok(())
+		});
+
+		assert!(matches!(res, Err(Error::Timeout(1))));
+	}
+
+	#[test]
+	fn test_retry_network_errors_only() {
+		let (_td, repo_path) = repo_with_timeouts(0, 1);
+
+		let attempts = Arc::new(AtomicUsize::new(0));
+		let res = {
+			let attempts = Arc::clone(&attempts);
+			with_retry(&repo_path, None, move |_| {
+				if attempts.fetch_add(1, Ordering::Relaxed) == 0 {
+					return Err(git2::Error::new(
+						ErrorCode::GenericError,
+						ErrorClass::Net,
+						"connection reset",
+					)
+					.into());
+				}
+				Ok(42)
+			})
+		};
+		assert_eq!(res.unwrap(), 42);
+		assert_eq!(attempts.load(Ordering::Relaxed), 2);
+
+		let attempts = Arc::new(AtomicUsize::new(0));
+		let res: Result<()> = {
+			let attempts = Arc::clone(&attempts);
+			with_retry(&repo_path, None, move |_| {
+				attempts.fetch_add(1, Ordering::Relaxed);
+				Err(Error::Generic("rejected".into()))
+			})
+		};
+		assert!(res.is_err());
+		assert_eq!(attempts.load(Ordering::Relaxed), 1);
+	}
+
+	#[test]
+	fn test_no_retry_after_transfer() {
+		let (_td, repo_path) = repo_with_timeouts(0, 1);
+
+		let attempts = Arc::new(AtomicUsize::new(0));
+		let res: Result<()> = {
+			let attempts = Arc::clone(&attempts);
+			with_retry(&repo_path, None, move |watchdog| {
+				attempts.fetch_add(1, Ordering::Relaxed);
+				watchdog.mark_transferred();
+				Err(git2::Error::new(
+					ErrorCode::GenericError,
+					ErrorClass::Net,
+					"connection reset",
+				)
+				.into())
+			})
+		};
+		assert!(res.is_err());
+		assert_eq!(attempts.load(Ordering::Relaxed), 1);
+	}
+
+	#[test]
+	fn test_no_retry_while_attempt_running() {
+		let (_td, repo_path) = repo_with_timeouts(1, 1);
+
+		let attempts = Arc::new(AtomicUsize::new(0));
+		let res: Result<()> = {
+			let attempts = Arc::clone(&attempts);
+			// ignores being aborted like a blocked libgit2 call
+			with_retry(&repo_path, None, move |_| {
+				attempts.fetch_add(1, Ordering::Relaxed);
+				thread::sleep(Duration::from_secs(4));
+				Ok(())
+			})
+		};
+		assert!(matches!(res, Err(Error::Timeout(1))));
+		assert_eq!(attempts.load(Ordering::Relaxed), 1);
+	}
+}
@@ -5,8 +5,9 @@ use crate::{
 	error::Result,
 	progress::ProgressPercent,
 	sync::{
+		action_log::log_action,
 		cred::BasicAuthCredential,
-		remotes::{proxy_auto, Callbacks},
+		remotes::{with_retry, Callbacks, Transport, Watchdog},
 		repository::repo,
 		RepoPath,
 	},
@@ -55,14 +56,36 @@ fn remote_tag_refs(
 ) -> Result<Vec<String>> {
 	scope_time!("remote_tags");
 
+	let (path, remote) = (repo_path.clone(), remote.to_string());
+
+	with_retry(repo_path, None, move |watchdog| {
+		remote_tag_refs_attempt(
+			&path,
+			&remote,
+			basic_credential.clone(),
+			watchdog,
+		)
+	})
+}
+
+fn remote_tag_refs_attempt(
+	repo_path: &RepoPath,
+	remote: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	watchdog: Watchdog,
+) -> Result<Vec<String>> {
 	let repo = repo(repo_path)?;
 	let mut remote = repo.find_remote(remote)?;
-	let callbacks = Callbacks::new(None, basic_credential);
-	let conn = remote.connect_auth(
-		Direction::Fetch,
-		Some(callbacks.callbacks()),
-		Some(proxy_auto()),
-	)?;
+	let transport = Transport::new(&repo, &remote)?;
+	let callbacks = Callbacks::new(None, basic_credential)
+		.with_watchdog(watchdog);
+	let conn = remote
+		.connect_auth(
+			Direction::Fetch,
+			Some(callbacks.callbacks()),
+			Some(transport.proxy_options()),
+		)
+		.map_err(|e| transport.map_err(e))?;
 
 	let remote_heads = conn.list()?;
 	let remote_tags = remote_heads
@@ -120,9 +143,6 @@ pub fn push_tags(
 		basic_credential.clone(),
 	)?;
 
-	let repo = repo(repo_path)?;
-	let mut remote = repo.find_remote(remote)?;
-
 	let total = tags_missing.len();
 
 	progress_sender.as_ref().map(|sender| {
@@ -130,13 +150,21 @@ pub fn push_tags(
 	});
 
 	for (idx, tag) in tags_missing.into_iter().enumerate() {
-		let mut options = PushOptions::new();
-		let callbacks =
-			Callbacks::new(None, basic_credential.clone());
-		options.remote_callbacks(callbacks.callbacks());
-		options.packbuilder_parallelism(0);
-		options.proxy_options(proxy_auto());
-		remote.push(&[tag.as_str()], Some(&mut options))?;
+		let (path, remote, basic_credential) = (
+			repo_path.clone(),
+			remote.to_string(),
+			basic_credential.clone(),
+		);
+
+		with_retry(repo_path, None, move |watchdog| {
+			push_tag_attempt(
+				&path,
+				&remote,
+				&tag,
+				basic_credential.clone(),
+				watchdog,
+			)
+		})?;
 
 		progress_sender.as_ref().map(|sender| {
 			sender.send(PushTagsProgress::Push {
@@ -153,6 +181,36 @@ pub fn push_tags(
 	Ok(())
 }
 
+fn push_tag_attempt(
+	repo_path: &RepoPath,
+	remote: &str,
+	tag: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	watchdog: Watchdog,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let mut remote = repo.find_remote(remote)?;
+	let transport = Transport::new(&repo, &remote)?;
+
+	let mut options = PushOptions::new();
+	let callbacks = Callbacks::new(None, basic_credential)
+		.with_watchdog(watchdog);
+	options.remote_callbacks(callbacks.callbacks());
+	options.packbuilder_parallelism(0);
+	options.proxy_options(transport.proxy_options());
+	remote
+		.push(&[tag], Some(&mut options))
+		.map_err(|e| transport.map_err(e))?;
+
+	log_action(
+		&repo,
+		"push tag",
+		&format!("{} {}", remote.name().unwrap_or_default(), tag),
+	);
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -210,7 +268,8 @@ mod tests {
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 0);
 
 		//lets fetch from origin
-		let bytes = fetch(clone2_dir, "master", None, None).unwrap();
+		let bytes =
+			fetch(clone2_dir, "master", None, None, None).unwrap();
 		assert!(bytes > 0);
 
 		sync::merge_upstream_commit(clone2_dir, "master").unwrap();
@@ -325,7 +384,7 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch(clone2_dir, "master", None, None).unwrap();
+		fetch(clone2_dir, "master", None, None, None).unwrap();
 
 		let tags2 = sync::get_tags(clone2_dir).unwrap();
 
@@ -365,7 +424,7 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &None, &None, &None).unwrap();
 
 		let tags2 = sync::get_tags(clone2_dir).unwrap();
 
@@ -399,7 +458,7 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &None, &None, &None).unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 1);
 
 		// delete on clone 1
@@ -415,6 +474,7 @@ mod tests {
 			true,
 			None,
 			None,
+			None,
 		)
 		.unwrap();
 
@@ -422,7 +482,7 @@ mod tests {
 
 		// clone 2
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &None, &None, &None).unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 0);
 	}
 }
@@ -0,0 +1,295 @@
+//! proxy and tls settings of the libgit2 transport
+
+use crate::{
+	error::{Error, Result},
+	sync::config::get_config_string_repo,
+};
+use git2::{ErrorClass, ErrorCode, ProxyOptions, Remote, Repository};
+use once_cell::sync::Lazy;
+use std::{ffi::CString, os::raw::c_int, ptr, sync::Mutex};
+
+/// CA file and path last handed to libgit2 (this is a global setting)
+static SSL_CA_LOCATIONS: Lazy<
+	Mutex<(Option<String>, Option<String>)>,
+> = Lazy::new(|| Mutex::new((None, None)));
+
+/// transport settings for talking to one remote
+pub struct Transport {
+	proxy: Option<String>,
+}
+
+impl Transport {
+	/// applies `http.sslCAInfo`/`http.sslCAPath` (or `GIT_SSL_CAINFO`/
+	/// `GIT_SSL_CAPATH`) and looks up the proxy to use for `remote`
+	pub fn new(repo: &Repository, remote: &Remote) -> Result<Self> {
+		apply_ssl_ca_locations(repo)?;
+
+		let proxy = remote.url().and_then(|url| {
+			configured_proxy(repo, remote.name(), url)
+		});
+
+		if let Some(proxy) = &proxy {
+			log::info!("using proxy: {}", proxy);
+		}
+
+		Ok(Self { proxy })
+	}
+
+	///
+	pub fn proxy_options<'a>(&self) -> ProxyOptions<'a> {
+		let mut options = ProxyOptions::new();
+		match &self.proxy {
+			Some(proxy) => options.url(proxy),
+			None => options.auto(),
+		};
+		options
+	}
+
+	/// turns transport failures caused by the proxy or the
+	/// certificate check into errors saying so
+	pub fn map_err(&self, e: git2::Error) -> Error {
+		if e.class() == ErrorClass::Ssl
+			|| e.code() == ErrorCode::Certificate
+		{
+			return Error::Tls(e.message().to_string());
+		}
+
+		if let Some(proxy) = &self.proxy {
+			// connecting goes to the proxy, socket errors are its fault
+			if matches!(e.class(), ErrorClass::Net | ErrorClass::Os)
+				|| e.message().to_lowercase().contains("proxy")
+			{
+				return Error::Proxy(
+					proxy.clone(),
+					e.message().to_string(),
+				);
+			}
+		}
+
+		e.into()
+	}
+}
+
+/// `remote.<name>.proxy`, `http.proxy` or the `https_proxy`/`http_proxy`
+/// env vars (respecting `no_proxy`) like git, only for http(s) urls
+fn configured_proxy(
+	repo: &Repository,
+	remote_name: Option<&str>,
+	url: &str,
+) -> Option<String> {
+	let url = url::Url::parse(url).ok()?;
+	let https = match url.scheme() {
+		"https" => true,
+		"http" => false,
+		_ => return None,
+	};
+
+	let from_config = |key: &str| {
+		get_config_string_repo(repo, key)
+			.ok()
+			.flatten()
+			.filter(|proxy| !proxy.is_empty())
+	};
+
+	remote_name
+		.and_then(|name| {
+			from_config(&format!("remote.{}.proxy", name))
+		})
+		.or_else(|| from_config("http.proxy"))
+		.or_else(|| {
+			let no_proxy = env_var(&["no_proxy", "NO_PROXY"]);
+			if no_proxy.map_or(false, |no_proxy| {
+				url.host_str().map_or(false, |host| {
+					is_no_proxy_host(&no_proxy, host)
+				})
+			}) {
+				return None;
+			}
+
+			if https {
+				env_var(&["https_proxy", "HTTPS_PROXY"])
+			} else {
+				env_var(&["http_proxy", "HTTP_PROXY"])
+			}
+		})
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+	names
+		.iter()
+		.find_map(|name| std::env::var(name).ok())
+		.filter(|value| !value.is_empty())
+}
+
+/// `no_proxy` is a comma separated list of hosts or domain suffixes
+fn is_no_proxy_host(no_proxy: &str, host: &str) -> bool {
+	no_proxy.split(',').map(str::trim).any(|entry| {
+		let entry = entry.trim_start_matches('.');
+		entry == "*"
+			|| (!entry.is_empty()
+				&& (host == entry
+					|| host.ends_with(&format!(".{}", entry))))
+	})
+}
+
+fn apply_ssl_ca_locations(repo: &Repository) -> Result<()> {
+	let location = |env: &str, key: &str| -> Result<Option<String>> {
+		let value = match std::env::var(env) {
+			Ok(value) if !value.is_empty() => Some(value),
+			_ => get_config_string_repo(repo, key)?,
+		};
+		Ok(match value {
+			Some(value) => {
+				Some(shellexpand::full(&value)?.into_owned())
+			}
+			None => None,
+		})
+	};
+
+	let locations = (
+		location("GIT_SSL_CAINFO", "http.sslCAInfo")?,
+		location("GIT_SSL_CAPATH", "http.sslCAPath")?,
+	);
+
+	if locations.0.is_none() && locations.1.is_none() {
+		return Ok(());
+	}
+
+	let mut applied = SSL_CA_LOCATIONS.lock()?;
+	if *applied == locations {
+		return Ok(());
+	}
+
+	set_ssl_cert_locations(&locations)?;
+
+	*applied = locations;
+	drop(applied);
+
+	Ok(())
+}
+
+/// `git2::opts` (as of 0.15) has no setter for this, so it goes
+/// through `libgit2_sys`, which is pinned to the version git2 uses
+#[allow(unsafe_code)]
+fn set_ssl_cert_locations(
+	locations: &(Option<String>, Option<String>),
+) -> Result<()> {
+	let to_c =
+		|value: &Option<String>| {
+			value.as_deref().map(CString::new).transpose().map_err(
+				|_| Error::Tls(String::from("invalid CA path")),
+			)
+		};
+	let file = to_c(&locations.0)?;
+	let path = to_c(&locations.1)?;
+
+	let option =
+		c_int::try_from(libgit2_sys::GIT_OPT_SET_SSL_CERT_LOCATIONS)?;
+
+	// SAFETY: libgit2 copies both strings, null means unset
+	let res = unsafe {
+		libgit2_sys::git_libgit2_opts(
+			option,
+			file.as_ref().map_or(ptr::null(), |f| f.as_ptr()),
+			path.as_ref().map_or(ptr::null(), |p| p.as_ptr()),
+		)
+	};
+
+	if res < 0 {
+		let e = git2::Error::last_error(res).map_or_else(
+			|| String::from("unknown error"),
+			|e| e.message().to_string(),
+		);
+		return Err(Error::Tls(format!(
+			"could not use CA bundle {:?}: {}",
+			locations, e
+		)));
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init;
+
+	#[test]
+	fn test_no_proxy_hosts() {
+		assert!(is_no_proxy_host("localhost,.corp.com", "corp.com"));
+		assert!(is_no_proxy_host(
+			"localhost, corp.com",
+			"git.corp.com"
+		));
+		assert!(is_no_proxy_host("*", "github.com"));
+		assert!(!is_no_proxy_host("corp.com", "notcorp.com"));
+		assert!(!is_no_proxy_host("", "github.com"));
+	}
+
+	#[test]
+	fn test_configured_proxy() {
+		let (_td, repo) = repo_init().unwrap();
+		let url = "https://github.com/extrawurst/gitui";
+
+		repo.config()
+			.unwrap()
+			.set_str("http.proxy", "http://proxy:8080")
+			.unwrap();
+
+		assert_eq!(
+			configured_proxy(&repo, Some("origin"), url).as_deref(),
+			Some("http://proxy:8080")
+		);
+
+		repo.config()
+			.unwrap()
+			.set_str("remote.origin.proxy", "http://other:3128")
+			.unwrap();
+
+		assert_eq!(
+			configured_proxy(&repo, Some("origin"), url).as_deref(),
+			Some("http://other:3128")
+		);
+
+		assert_eq!(
+			configured_proxy(
+				&repo,
+				Some("origin"),
+				"git@github.com:a/b"
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn test_map_err() {
+		let transport = Transport {
+			proxy: Some(String::from("http://proxy:8080")),
+		};
+
+		assert!(matches!(
+			transport.map_err(git2::Error::new(
+				ErrorCode::Certificate,
+				ErrorClass::Ssl,
+				"the SSL certificate is invalid"
+			)),
+			Error::Tls(_)
+		));
+		assert!(matches!(
+			transport.map_err(git2::Error::new(
+				ErrorCode::GenericError,
+				ErrorClass::Net,
+				"failed to connect"
+			)),
+			Error::Proxy(..)
+		));
+		assert!(matches!(
+			Transport { proxy: None }.map_err(git2::Error::new(
+				ErrorCode::GenericError,
+				ErrorClass::Net,
+				"failed to connect"
+			)),
+			Error::Git(_)
+		));
+	}
+}
@@ -0,0 +1,224 @@
+//! counts and sizes explaining why git operations on a repository
+//! are slow, like `git count-objects -v` does
+
+use super::{repository::repo, RepoPath};
+use crate::error::Result;
+use git2::Repository;
+use scopetime::scope_time;
+use std::{
+	fs::{self, File},
+	io::Read,
+	path::{Path, PathBuf},
+};
+
+/// see `get_repo_stats`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RepoStats {
+	/// reachable from `HEAD`
+	pub commits: usize,
+	///
+	pub loose_objects: usize,
+	/// bytes
+	pub loose_size: u64,
+	///
+	pub packs: usize,
+	/// objects in several packs are counted for each
+	pub packed_objects: usize,
+	/// bytes
+	pub pack_size: u64,
+	///
+	pub refs: usize,
+	/// all files in the working directory, ignored ones included
+	pub worktree_files: usize,
+	/// files tracked in the index
+	pub index_entries: usize,
+	/// bytes
+	pub index_size: u64,
+}
+
+/// walks the whole history and working directory, meant to run in
+/// the background
+pub fn get_repo_stats(repo_path: &RepoPath) -> Result<RepoStats> {
+	scope_time!("get_repo_stats");
+
+	let repo = repo(repo_path)?;
+	let objects = common_dir(&repo).join("objects");
+
+	let mut stats = RepoStats {
+		commits: count_commits(&repo)?,
+		refs: repo.references()?.count(),
+		index_entries: repo.index()?.len(),
+		index_size: fs::metadata(repo.path().join("index"))
+			.map(|meta| meta.len())
+			.unwrap_or_default(),
+		worktree_files: repo
+			.workdir()
+			.map(count_files)
+			.unwrap_or_default(),
+		..RepoStats::default()
+	};
+
+	for dir in fs::read_dir(&objects)?.flatten() {
+		let name = dir.file_name();
+		let name = name.to_string_lossy();
+		if name.len() == 2
+			&& name.chars().all(|c| c.is_ascii_hexdigit())
+		{
+			for file in fs::read_dir(dir.path())?.flatten() {
+				stats.loose_objects += 1;
+				stats.loose_size += file
+					.metadata()
+					.map(|meta| meta.len())
+					.unwrap_or_default();
+			}
+		}
+	}
+
+	if let Ok(packs) = fs::read_dir(objects.join("pack")) {
+		for file in packs.flatten() {
+			let path = file.path();
+			match path.extension().and_then(|ext| ext.to_str()) {
+				Some("pack") => {
+					stats.packs += 1;
+					stats.pack_size += file
+						.metadata()
+						.map(|meta| meta.len())
+						.unwrap_or_default();
+				}
+				Some("idx") => {
+					stats.packed_objects +=
+						pack_index_objects(&path).unwrap_or_default();
+				}
+				_ => (),
+			}
+		}
+	}
+
+	Ok(stats)
+}
+
+/// the loose objects estimated from the one fan-out dir `objects/17`
+/// like `git gc --auto` does, cheap enough to run on startup
+pub fn count_loose_objects(repo_path: &RepoPath) -> Result<usize> {
+	scope_time!("count_loose_objects");
+
+	let repo = repo(repo_path)?;
+	let sampled = fs::read_dir(common_dir(&repo).join("objects/17"))
+		.map_or(0, |files| {
+			files
+				.flatten()
+				.filter(|file| {
+					let name = file.file_name();
+					let name = name.to_string_lossy();
+					name.len() == 38
+						&& name.chars().all(|c| c.is_ascii_hexdigit())
+				})
+				.count()
+		});
+
+	Ok(sampled * 256)
+}
+
+/// the `.git` dir objects and refs are in, the main one for linked
+/// worktrees
+fn common_dir(repo: &Repository) -> PathBuf {
+	let git_dir = repo.path();
+
+	fs::read_to_string(git_dir.join("commondir")).map_or_else(
+		|_| git_dir.to_path_buf(),
+		|dir| git_dir.join(dir.trim()),
+	)
+}
+
+fn count_commits(repo: &Repository) -> Result<usize> {
+	let mut walk = repo.revwalk()?;
+
+	// fails on an unborn branch
+	Ok(walk.push_head().map_or(0, |()| walk.count()))
+}
+
+/// files below `dir` without descending into `.git` or following
+/// symlinks
+fn count_files(dir: &Path) -> usize {
+	fs::read_dir(dir).map_or(0, |entries| {
+		entries
+			.flatten()
+			.filter(|entry| entry.file_name() != ".git")
+			.map(|entry| match entry.file_type() {
+				Ok(kind) if kind.is_dir() => {
+					count_files(&entry.path())
+				}
+				_ => 1,
+			})
+			.sum()
+	})
+}
+
+/// the object count of a pack index: the last entry of its fanout
+/// table, which follows a header since version 2
+fn pack_index_objects(path: &Path) -> Option<usize> {
+	const MAGIC: [u8; 4] = [0xff, b't', b'O', b'c'];
+	const FANOUT_LAST: usize = 255 * 4;
+
+	let mut buf = [0_u8; 8 + FANOUT_LAST + 4];
+	File::open(path).ok()?.read_exact(&mut buf).ok()?;
+
+	let offset = if buf[..4] == MAGIC { 8 } else { 0 };
+	let last = &buf[offset + FANOUT_LAST..offset + FANOUT_LAST + 4];
+
+	Some(u32::from_be_bytes([last[0], last[1], last[2], last[3]])
+		as usize)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use std::process::Command;
+
+	#[test]
+	fn test_repo_stats() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let repo_path: &RepoPath = &root.to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "first");
+		fs::write(root.join("untracked.txt"), "b").unwrap();
+
+		let stats = get_repo_stats(repo_path).unwrap();
+		// `repo_init` commits once already
+		assert_eq!(stats.commits, 2);
+		assert_eq!(stats.index_entries, 1);
+		assert_eq!(stats.worktree_files, 2);
+		assert_eq!(stats.refs, 1);
+		assert_eq!(stats.packs, 0);
+		// two commits, two trees, a blob
+		assert_eq!(stats.loose_objects, 5);
+		assert!(stats.index_size > 0);
+
+		Command::new("git")
+			.args(["gc", "--quiet"])
+			.current_dir(root)
+			.status()
+			.unwrap();
+
+		let stats = get_repo_stats(repo_path).unwrap();
+		assert_eq!(stats.packs, 1);
+		assert_eq!(stats.packed_objects, 5);
+		assert_eq!(stats.loose_objects, 0);
+	}
+
+	#[test]
+	fn test_count_loose_objects() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+
+		let sampled = repo.path().join("objects/17");
+		fs::create_dir_all(&sampled).unwrap();
+		fs::write(sampled.join("0".repeat(38)), "").unwrap();
+		fs::write(sampled.join("tmp_obj_x"), "").unwrap();
+
+		assert_eq!(count_loose_objects(repo_path).unwrap(), 256);
+	}
+}
@@ -1,5 +1,7 @@
 use std::{
+	borrow::Cow,
 	cell::RefCell,
+	env,
 	path::{Path, PathBuf},
 };
 
@@ -40,6 +42,26 @@ impl RepoPath {
 			Self::Workdir { workdir, .. } => Some(workdir.as_path()),
 		}
 	}
+
+	/// the repository `self` points into, found like git does by
+	/// searching the parent directories, as the root of its work dir
+	/// (or its git dir if bare) so it no longer depends on the
+	/// current dir
+	pub fn discover(&self) -> Result<Self> {
+		let repo = repo(self)?;
+		let gitdir = repo.path().to_path_buf();
+
+		Ok(match (self, repo.workdir()) {
+			(Self::Workdir { workdir, .. }, _) => Self::Workdir {
+				gitdir,
+				workdir: workdir.clone(),
+			},
+			(Self::Path(_), Some(workdir)) => {
+				Self::Path(workdir.to_path_buf())
+			}
+			(Self::Path(_), None) => Self::Path(gitdir),
+		})
+	}
 }
 
 impl From<&str> for RepoPath {
@@ -50,14 +72,85 @@ impl From<&str> for RepoPath {
 
 pub fn repo(repo_path: &RepoPath) -> Result<Repository> {
 	let repo = Repository::open_ext(
-		repo_path.gitpath(),
+		without_verbatim_prefix(repo_path.gitpath()),
 		RepositoryOpenFlags::empty(),
-		Vec::<&Path>::new(),
+		ceiling_dirs(),
 	)?;
 
 	if let Some(workdir) = repo_path.workdir() {
-		repo.set_workdir(workdir, false)?;
+		repo.set_workdir(&without_verbatim_prefix(workdir), false)?;
 	}
 
 	Ok(repo)
 }
+
+/// where the search for a repository in the parent directories stops
+/// (`GIT_CEILING_DIRECTORIES`)
+pub fn ceiling_dirs() -> Vec<PathBuf> {
+	env::var_os("GIT_CEILING_DIRECTORIES")
+		.map(|dirs| env::split_paths(&dirs).collect())
+		.unwrap_or_default()
+}
+
+/// libgit2 does its own long path handling on windows but fails on
+/// the verbatim paths (`\\?\C:\..`, `\\?\UNC\server\share\..`)
+/// that `canonicalize` returns there
+fn without_verbatim_prefix(path: &Path) -> Cow<'_, Path> {
+	if !cfg!(windows) {
+		return Cow::Borrowed(path);
+	}
+
+	path.to_str()
+		.and_then(strip_verbatim_prefix)
+		.map_or(Cow::Borrowed(path), |p| Cow::Owned(PathBuf::from(p)))
+}
+
+fn strip_verbatim_prefix(path: &str) -> Option<String> {
+	if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+		return Some(format!(r"\\{}", unc));
+	}
+
+	let path = path.strip_prefix(r"\\?\")?;
+	let mut chars = path.chars();
+	let is_drive =
+		chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+			&& chars.next() == Some(':');
+
+	is_drive.then(|| path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{strip_verbatim_prefix, RepoPath};
+	use crate::sync::tests::repo_init;
+	use std::fs;
+
+	#[test]
+	fn test_discover() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+		let sub = root.join("a").join("b");
+		fs::create_dir_all(&sub).unwrap();
+
+		let found = RepoPath::Path(sub).discover().unwrap();
+
+		assert_eq!(found.gitpath(), root);
+		assert_eq!(found.workdir(), None);
+	}
+
+	#[test]
+	fn test_strip_verbatim_prefix() {
+		assert_eq!(
+			strip_verbatim_prefix(r"\\?\C:\code\gitui").as_deref(),
+			Some(r"C:\code\gitui")
+		);
+		assert_eq!(
+			strip_verbatim_prefix(r"\\?\UNC\server\share\gitui")
+				.as_deref(),
+			Some(r"\\server\share\gitui")
+		);
+		assert_eq!(strip_verbatim_prefix(r"\\server\share"), None);
+		assert_eq!(strip_verbatim_prefix(r"\\?\Volume{1234}\"), None);
+		assert_eq!(strip_verbatim_prefix("/home/gitui"), None);
+	}
+}
@@ -1,11 +1,19 @@
-use super::{utils::get_head_repo, RepoPath};
+use super::{action_log::log_action, utils::get_head_repo, RepoPath};
 use crate::{error::Result, sync::repository::repo};
 use git2::{build::CheckoutBuilder, ObjectType};
 use scopetime::scope_time;
 
 ///
 pub fn reset_stage(repo_path: &RepoPath, path: &str) -> Result<()> {
-	scope_time!("reset_stage");
+	reset_stage_paths(repo_path, &[path])
+}
+
+/// like `reset_stage` for many paths in one go
+pub fn reset_stage_paths(
+	repo_path: &RepoPath,
+	paths: &[&str],
+) -> Result<()> {
+	scope_time!("reset_stage_paths");
 
 	let repo = repo(repo_path)?;
 
@@ -13,17 +21,27 @@ pub fn reset_stage(repo_path: &RepoPath, path: &str) -> Result<()> {
 		let obj =
 			repo.find_object(id.into(), Some(ObjectType::Commit))?;
 
-		repo.reset_default(Some(&obj), [path])?;
+		repo.reset_default(Some(&obj), paths.iter().copied())?;
 	} else {
-		repo.reset_default(None, [path])?;
+		repo.reset_default(None, paths.iter().copied())?;
 	}
 
+	log_action(&repo, "unstage", &paths.join(", "));
+
 	Ok(())
 }
 
 ///
 pub fn reset_workdir(repo_path: &RepoPath, path: &str) -> Result<()> {
-	scope_time!("reset_workdir");
+	reset_workdir_paths(repo_path, &[path])
+}
+
+/// like `reset_workdir` for many paths in one checkout
+pub fn reset_workdir_paths(
+	repo_path: &RepoPath,
+	paths: &[&str],
+) -> Result<()> {
+	scope_time!("reset_workdir_paths");
 
 	let repo = repo(repo_path)?;
 
@@ -31,16 +49,25 @@ pub fn reset_workdir(repo_path: &RepoPath, path: &str) -> Result<()> {
 	checkout_opts
 		.update_index(true) // windows: needs this to be true WTF?!
 		.remove_untracked(true)
-		.force()
-		.path(path);
+		.force();
+
+	for path in paths {
+		checkout_opts.path(path);
+	}
 
 	repo.checkout_index(None, Some(&mut checkout_opts))?;
+
+	log_action(&repo, "discard", &paths.join(", "));
+
 	Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{reset_stage, reset_workdir};
+	use super::{
+		reset_stage, reset_stage_paths, reset_workdir,
+		reset_workdir_paths,
+	};
 	use crate::error::Result;
 	use crate::sync::{
 		commit,
@@ -319,4 +346,30 @@ mod tests {
 
 		assert_eq!(get_statuses(repo_path), (0, 0));
 	}
+
+	#[test]
+	fn test_reset_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		for file in ["a.txt", "b.txt", "c.txt"] {
+			File::create(&root.join(file))
+				.unwrap()
+				.write_all(b"test")
+				.unwrap();
+		}
+
+		stage_add_all(repo_path, "*", None).unwrap();
+		assert_eq!(get_statuses(repo_path), (0, 3));
+
+		reset_stage_paths(repo_path, &["a.txt", "b.txt"]).unwrap();
+		assert_eq!(get_statuses(repo_path), (2, 1));
+
+		reset_workdir_paths(repo_path, &["a.txt", "b.txt"]).unwrap();
+		assert_eq!(get_statuses(repo_path), (0, 1));
+		assert!(!root.join("a.txt").exists());
+		assert!(root.join("c.txt").exists());
+	}
 }
@@ -0,0 +1,61 @@
+//! the posix shell git itself runs hooks and editors with
+
+use std::{
+	env,
+	path::{Path, PathBuf},
+};
+
+/// `sh` on unix, the `sh.exe` that comes with git for windows there
+/// (`None` if no git is found in `PATH`)
+pub fn posix_shell() -> Option<PathBuf> {
+	if !cfg!(windows) {
+		return Some(PathBuf::from("sh"));
+	}
+
+	let git = env::split_paths(&env::var_os("PATH")?)
+		.map(|dir| dir.join("git.exe"))
+		.find(|git| git.is_file())?;
+
+	git_for_windows_shells(&git)
+		.into_iter()
+		.find(|sh| sh.is_file())
+}
+
+/// git for windows puts `git.exe` into `cmd/`, `bin/` or
+/// `mingw64/bin/` of its install dir, the shell is in `bin/` or
+/// `usr/bin/` of it
+fn git_for_windows_shells(git: &Path) -> Vec<PathBuf> {
+	git.ancestors()
+		.skip(2)
+		.take(2)
+		.flat_map(|root| {
+			[
+				root.join("bin").join("sh.exe"),
+				root.join("usr").join("bin").join("sh.exe"),
+			]
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_git_for_windows_shells() {
+		let root = Path::new("git");
+
+		let shells = git_for_windows_shells(
+			&root.join("mingw64").join("bin").join("git.exe"),
+		);
+		assert!(shells.contains(&root.join("bin").join("sh.exe")));
+
+		let shells =
+			git_for_windows_shells(&root.join("cmd").join("git.exe"));
+		assert_eq!(shells[0], root.join("bin").join("sh.exe"));
+		assert_eq!(
+			shells[1],
+			root.join("usr").join("bin").join("sh.exe")
+		);
+	}
+}
@@ -0,0 +1,170 @@
+//! sync git api for signing commits with gpg
+
+use super::{
+	config::get_config_string_repo, repository::repo, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::Repository;
+use scopetime::scope_time;
+use std::{
+	io::{ErrorKind, Write},
+	process::{Command, Stdio},
+};
+
+/// what gpg (or its agent) reports when it needs a passphrase
+/// but has no way to ask for it
+const PINENTRY_FAILURES: [&str; 5] = [
+	"no pinentry",
+	"inappropriate ioctl for device",
+	"bad passphrase",
+	"no passphrase given",
+	"problem with the agent",
+];
+
+/// `commit.gpgsign` of the repo
+pub fn gpgsign_enabled(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+	let cfg = repo.config()?;
+
+	Ok(cfg.get_bool("commit.gpgsign").unwrap_or_default())
+}
+
+/// signs `buffer` (a raw commit object) with `gpg.program`
+/// using `user.signingkey` (or the committer identity like git does)
+/// and returns the armored detached signature
+///
+/// without a `passphrase` gpg is not allowed to launch a pinentry,
+/// a key that is not unlocked in the agent already results in
+/// `Error::SignPassphraseRequired`. a `passphrase` is handed over
+/// via loopback pinentry.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn sign_buffer(
+	repo: &Repository,
+	buffer: &str,
+	passphrase: Option<&str>,
+) -> Result<String> {
+	scope_time!("sign_buffer");
+
+	if let Some(format) = get_config_string_repo(repo, "gpg.format")?
+	{
+		if format != "openpgp" {
+			return Err(Error::Sign(format!(
+				"gpg.format '{}' not supported",
+				format
+			)));
+		}
+	}
+
+	let program = get_config_string_repo(repo, "gpg.program")?
+		.unwrap_or_else(|| String::from("gpg"));
+	let key = signing_key(repo)?;
+
+	let mut cmd = Command::new(&program);
+	cmd.arg("--status-fd=2");
+	if passphrase.is_some() {
+		cmd.args([
+			"--batch",
+			"--pinentry-mode",
+			"loopback",
+			"--passphrase-fd",
+			"0",
+		]);
+	} else {
+		cmd.args(["--pinentry-mode", "error"]);
+	}
+	cmd.args(["-bsau", &key])
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped());
+
+	let mut child = cmd.spawn().map_err(|e| {
+		Error::Sign(format!("failed to run '{}': {}", program, e))
+	})?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		// gpg reads only the first line of fd 0 as the passphrase
+		let mut input = Vec::with_capacity(buffer.len());
+		if let Some(passphrase) = passphrase {
+			input.extend_from_slice(passphrase.as_bytes());
+			input.push(b'\n');
+		}
+		input.extend_from_slice(buffer.as_bytes());
+
+		// gpg exits without reading its input when it fails early,
+		// that gets told by its exit status and stderr below
+		match stdin.write_all(&input) {
+			Err(e) if e.kind() != ErrorKind::BrokenPipe => {
+				return Err(e.into())
+			}
+			_ => (),
+		}
+	}
+
+	let output = child.wait_with_output()?;
+	let status = String::from_utf8_lossy(&output.stderr);
+
+	if output.status.success()
+		&& status.contains("[GNUPG:] SIG_CREATED ")
+	{
+		return Ok(String::from_utf8(output.stdout)?);
+	}
+
+	log::error!("gpg signing failed: {}", status);
+
+	if is_pinentry_failure(&status) {
+		return Err(Error::SignPassphraseRequired);
+	}
+
+	Err(Error::Sign(
+		status
+			.lines()
+			.filter(|line| !line.starts_with("[GNUPG:]"))
+			.collect::<Vec<_>>()
+			.join("\n"),
+	))
+}
+
+fn signing_key(repo: &Repository) -> Result<String> {
+	if let Some(key) =
+		get_config_string_repo(repo, "user.signingkey")?
+	{
+		return Ok(key);
+	}
+
+	let signature =
+		super::commit::signature_allow_undefined_name(repo)?;
+
+	Ok(format!(
+		"{} <{}>",
+		signature.name().unwrap_or_default(),
+		signature.email().unwrap_or_default()
+	))
+}
+
+fn is_pinentry_failure(status: &str) -> bool {
+	let status = status.to_lowercase();
+
+	PINENTRY_FAILURES
+		.iter()
+		.any(|failure| status.contains(failure))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_pinentry_failure() {
+		assert!(is_pinentry_failure(
+			"gpg: signing failed: No pinentry\n\
+			[GNUPG:] FAILURE sign 67108949"
+		));
+		assert!(is_pinentry_failure(
+			"gpg: signing failed: Inappropriate ioctl for device"
+		));
+		assert!(is_pinentry_failure("gpg: Bad passphrase"));
+		assert!(!is_pinentry_failure(
+			"gpg: skipped \"foo\": No secret key"
+		));
+	}
+}
@@ -2,7 +2,7 @@ use super::{apply_selection, load_file};
 use crate::{
 	error::Result,
 	sync::{
-		diff::DiffLinePosition,
+		action_log::log_action, diff::DiffLinePosition,
 		patches::get_file_diff_patch_and_hunklines, repository::repo,
 		utils::repo_write_file, RepoPath,
 	},
@@ -39,6 +39,8 @@ pub fn discard_lines(
 
 	repo_write_file(&repo, file_path, new_content.as_str())?;
 
+	log_action(&repo, "discard lines", file_path);
+
 	Ok(())
 }
 
@@ -2,7 +2,7 @@ use super::apply_selection;
 use crate::{
 	error::{Error, Result},
 	sync::{
-		diff::DiffLinePosition,
+		action_log::log_action, diff::DiffLinePosition,
 		patches::get_file_diff_patch_and_hunklines, repository::repo,
 		RepoPath,
 	},
@@ -57,6 +57,16 @@ pub fn stage_lines(
 	index.write()?;
 	index.read(true)?;
 
+	log_action(
+		&repo,
+		if is_stage {
+			"unstage lines"
+		} else {
+			"stage lines"
+		},
+		file_path,
+	);
+
 	Ok(())
 }
 
@@ -1,11 +1,11 @@
 use super::{CommitId, RepoPath};
 use crate::{
 	error::{Error, Result},
-	sync::repository::repo,
+	sync::{action_log::log_action, repository::repo},
 };
 use git2::{
-	build::CheckoutBuilder, Oid, Repository, StashApplyOptions,
-	StashFlags,
+	build::CheckoutBuilder, ErrorCode, Oid, Repository,
+	StashApplyOptions, StashFlags, StatusOptions,
 };
 use scopetime::scope_time;
 
@@ -47,6 +47,8 @@ pub fn stash_drop(
 
 	repo.stash_drop(index)?;
 
+	log_action(&repo, "drop stash", &stash_id.get_short_string());
+
 	Ok(())
 }
 
@@ -63,6 +65,8 @@ pub fn stash_pop(
 
 	repo.stash_pop(index, None)?;
 
+	log_action(&repo, "pop stash", &stash_id.get_short_string());
+
 	Ok(())
 }
 
@@ -85,6 +89,8 @@ pub fn stash_apply(
 	opt.checkout_options(checkout);
 	repo.stash_apply(index, Some(&mut opt))?;
 
+	log_action(&repo, "apply stash", &stash_id.get_short_string());
+
 	Ok(())
 }
 
@@ -130,9 +136,88 @@ pub fn stash_save(
 		options.insert(StashFlags::KEEP_INDEX);
 	}
 
-	let id = repo.stash_save2(&sig, message, Some(options))?;
+	let id = CommitId::new(repo.stash_save2(
+		&sig,
+		message,
+		Some(options),
+	)?);
+
+	log_action(
+		&repo,
+		"stash",
+		&format!(
+			"{} {}",
+			id.get_short_string(),
+			message.unwrap_or_default()
+		),
+	);
+
+	Ok(id)
+}
+
+/// whether there are staged or unstaged changes in the way of a
+/// checkout or rebase, untracked files are not considered
+pub fn autostash_needed(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+	let statuses = repo.statuses(Some(
+		StatusOptions::new()
+			.include_untracked(false)
+			.include_ignored(false),
+	))?;
+
+	Ok(!statuses.is_empty())
+}
+
+/// stashes staged and unstaged changes (like `git rebase --autostash`)
+/// so that `operation` can run on a clean worktree,
+/// `None` if there is nothing to stash
+pub fn autostash_save(
+	repo_path: &RepoPath,
+	operation: &str,
+) -> Result<Option<CommitId>> {
+	scope_time!("autostash_save");
+
+	if !autostash_needed(repo_path)? {
+		return Ok(None);
+	}
+
+	let msg = format!("autostash before {}", operation);
 
-	Ok(CommitId::new(id))
+	stash_save(repo_path, Some(&msg), false, false).map(Some)
+}
+
+/// pops a stash created by `autostash_save`, returns `false` and
+/// keeps the stash if it does not apply cleanly
+pub fn autostash_pop(
+	repo_path: &RepoPath,
+	stash_id: CommitId,
+) -> Result<bool> {
+	scope_time!("autostash_pop");
+
+	{
+		// libgit2 happily writes conflict markers into a clean
+		// worktree and drops the stash, so check for conflicts first
+		let repo = repo(repo_path)?;
+		let stash = repo.find_commit(stash_id.into())?;
+		let base = stash.parent(0)?.tree()?;
+		let head = repo.head()?.peel_to_tree()?;
+
+		if repo
+			.merge_trees(&base, &head, &stash.tree()?, None)?
+			.has_conflicts()
+		{
+			return Ok(false);
+		}
+	}
+
+	match stash_pop(repo_path, stash_id) {
+		Ok(()) => Ok(true),
+		Err(Error::Git(e)) if e.code() == ErrorCode::Conflict => {
+			log::warn!("autostash kept: {}", e);
+			Ok(false)
+		}
+		Err(e) => Err(e),
+	}
 }
 
 #[cfg(test)]
@@ -387,4 +472,71 @@ mod tests {
 			"test3"
 		);
 	}
+
+	#[test]
+	fn test_autostash() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "test", "c1");
+		repo_write_file(&repo, "untracked.txt", "test").unwrap();
+
+		assert!(!autostash_needed(repo_path).unwrap());
+
+		assert_eq!(
+			autostash_save(repo_path, "checkout").unwrap(),
+			None
+		);
+
+		repo_write_file(&repo, "test.txt", "test2").unwrap();
+
+		let id =
+			autostash_save(repo_path, "checkout").unwrap().unwrap();
+
+		assert_eq!(
+			get_commits_info(repo_path, &[id], 100).unwrap()[0]
+				.message,
+			"On master: autostash before checkout"
+		);
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			"test"
+		);
+		assert_eq!(
+			repo_read_file(&repo, "untracked.txt").unwrap(),
+			"test"
+		);
+
+		assert!(autostash_pop(repo_path, id).unwrap());
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			"test2"
+		);
+		assert!(get_stashes(repo_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_autostash_conflict() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "test", "c1");
+		repo_write_file(&repo, "test.txt", "test2").unwrap();
+
+		let id =
+			autostash_save(repo_path, "rebase").unwrap().unwrap();
+
+		write_commit_file(&repo, "test.txt", "test3", "c2");
+
+		assert!(!autostash_pop(repo_path, id).unwrap());
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			"test3"
+		);
+		assert_eq!(get_stashes(repo_path).unwrap(), vec![id]);
+	}
 }
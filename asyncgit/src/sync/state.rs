@@ -4,7 +4,7 @@ use git2::RepositoryState;
 use scopetime::scope_time;
 
 ///
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RepoState {
 	///
 	Clean,
@@ -15,6 +15,8 @@ pub enum RepoState {
 	///
 	Revert,
 	///
+	Bisect,
+	///
 	Other,
 }
 
@@ -25,6 +27,7 @@ impl From<RepositoryState> for RepoState {
 			RepositoryState::Merge => Self::Merge,
 			RepositoryState::Revert => Self::Revert,
 			RepositoryState::RebaseMerge => Self::Rebase,
+			RepositoryState::Bisect => Self::Bisect,
 			_ => {
 				log::warn!("state not supported yet: {:?}", state);
 				Self::Other
@@ -0,0 +1,361 @@
+//! order of the status file lists, configured per repository via
+//! `gitui.statusSort` and `gitui.statusGroup` in git-config
+
+use super::{
+	config::get_config_string_repo, repository::repo,
+	status::StatusType, RepoPath,
+};
+use crate::{error::Result, StatusItem, StatusItemType};
+use git2::{ConfigLevel, DiffOptions, Patch, Repository};
+use scopetime::scope_time;
+use std::{
+	cmp::Ordering, collections::HashMap, ffi::OsStr, fs, path::Path,
+	time::SystemTime,
+};
+
+/// order of the files within a group
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum StatusSort {
+	///
+	Path,
+	/// file extension, files without one first
+	Extension,
+	/// modification time in the workdir, newest first
+	ModificationTime,
+	/// added plus removed lines, biggest first
+	ChangeSize,
+}
+
+impl Default for StatusSort {
+	fn default() -> Self {
+		Self::Path
+	}
+}
+
+impl StatusSort {
+	///
+	pub const ALL: [Self; 4] = [
+		Self::Path,
+		Self::Extension,
+		Self::ModificationTime,
+		Self::ChangeSize,
+	];
+
+	/// name as used by `gitui.statusSort`
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Path => "path",
+			Self::Extension => "extension",
+			Self::ModificationTime => "mtime",
+			Self::ChangeSize => "size",
+		}
+	}
+
+	///
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|sort| sort.name() == name)
+	}
+}
+
+/// how files are grouped
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum StatusGroup {
+	/// the folder tree
+	Directory,
+	/// a flat list with new, modified, deleted, ... files together
+	ChangeType,
+}
+
+impl Default for StatusGroup {
+	fn default() -> Self {
+		Self::Directory
+	}
+}
+
+impl StatusGroup {
+	///
+	pub const ALL: [Self; 2] = [Self::Directory, Self::ChangeType];
+
+	/// name as used by `gitui.statusGroup`
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Directory => "directory",
+			Self::ChangeType => "type",
+		}
+	}
+
+	///
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL.into_iter().find(|group| group.name() == name)
+	}
+}
+
+///
+#[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct StatusOrder {
+	///
+	pub sort: StatusSort,
+	///
+	pub group: StatusGroup,
+}
+
+/// reads `gitui.statusSort` and `gitui.statusGroup`, unknown values
+/// fall back to the default
+pub fn status_order_config(
+	repo_path: &RepoPath,
+) -> Result<StatusOrder> {
+	let repo = repo(repo_path)?;
+
+	let sort = get_config_string_repo(&repo, "gitui.statusSort")?;
+	let group = get_config_string_repo(&repo, "gitui.statusGroup")?;
+
+	Ok(StatusOrder {
+		sort: sort
+			.as_deref()
+			.and_then(StatusSort::from_name)
+			.unwrap_or_default(),
+		group: group
+			.as_deref()
+			.and_then(StatusGroup::from_name)
+			.unwrap_or_default(),
+	})
+}
+
+/// stores `order` in the config of the repository
+pub fn set_status_order_config(
+	repo_path: &RepoPath,
+	order: StatusOrder,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let mut cfg = repo.config()?.open_level(ConfigLevel::Local)?;
+
+	cfg.set_str("gitui.statusSort", order.sort.name())?;
+	cfg.set_str("gitui.statusGroup", order.group.name())?;
+
+	Ok(())
+}
+
+/// sorts `items` (as returned by `get_status` for `status_type`),
+/// grouped by directory the files of a folder stay together so the
+/// tree can be built from the list
+pub fn sort_status(
+	repo_path: &RepoPath,
+	items: &mut [StatusItem],
+	status_type: StatusType,
+	order: StatusOrder,
+) -> Result<()> {
+	if order == StatusOrder::default() {
+		return Ok(());
+	}
+
+	scope_time!("sort_status");
+
+	let repo = repo(repo_path)?;
+
+	let times = if order.sort == StatusSort::ModificationTime {
+		modification_times(&repo, items)?
+	} else {
+		HashMap::new()
+	};
+	let sizes = if order.sort == StatusSort::ChangeSize {
+		change_sizes(&repo, status_type)?
+	} else {
+		HashMap::new()
+	};
+
+	let group =
+		|item: &StatusItem, other: &StatusItem| match order.group {
+			StatusGroup::Directory => Path::new(&item.path)
+				.parent()
+				.cmp(&Path::new(&other.path).parent()),
+			StatusGroup::ChangeType => {
+				type_rank(item.status).cmp(&type_rank(other.status))
+			}
+		};
+
+	let sort =
+		|item: &StatusItem, other: &StatusItem| match order.sort {
+			StatusSort::Path => Ordering::Equal,
+			StatusSort::Extension => {
+				extension(&item.path).cmp(&extension(&other.path))
+			}
+			StatusSort::ModificationTime => {
+				times.get(&other.path).cmp(&times.get(&item.path))
+			}
+			StatusSort::ChangeSize => {
+				sizes.get(&other.path).cmp(&sizes.get(&item.path))
+			}
+		};
+
+	items.sort_by(|item, other| {
+		group(item, other)
+			.then_with(|| sort(item, other))
+			.then_with(|| item.path.cmp(&other.path))
+	});
+
+	Ok(())
+}
+
+const fn type_rank(status: StatusItemType) -> u8 {
+	match status {
+		StatusItemType::Conflicted => 0,
+		StatusItemType::New => 1,
+		StatusItemType::Modified => 2,
+		StatusItemType::Renamed => 3,
+		StatusItemType::Typechange => 4,
+		StatusItemType::Deleted => 5,
+	}
+}
+
+fn extension(path: &str) -> Option<String> {
+	Path::new(path)
+		.extension()
+		.map(OsStr::to_string_lossy)
+		.map(|ext| ext.to_lowercase())
+}
+
+/// deleted files are missing
+fn modification_times(
+	repo: &Repository,
+	items: &[StatusItem],
+) -> Result<HashMap<String, SystemTime>> {
+	let work_dir = super::utils::work_dir(repo)?;
+
+	Ok(items
+		.iter()
+		.filter_map(|item| {
+			fs::symlink_metadata(work_dir.join(&item.path))
+				.and_then(|meta| meta.modified())
+				.ok()
+				.map(|time| (item.path.clone(), time))
+		})
+		.collect())
+}
+
+/// added plus removed lines per path, binary files are missing
+fn change_sizes(
+	repo: &Repository,
+	status_type: StatusType,
+) -> Result<HashMap<String, usize>> {
+	let mut opts = DiffOptions::new();
+	opts.include_untracked(true)
+		.recurse_untracked_dirs(true)
+		.show_untracked_content(true);
+
+	let head = repo.head().and_then(|head| head.peel_to_tree()).ok();
+
+	let diff = match status_type {
+		StatusType::WorkingDir => {
+			repo.diff_index_to_workdir(None, Some(&mut opts))?
+		}
+		StatusType::Stage => repo.diff_tree_to_index(
+			head.as_ref(),
+			None,
+			Some(&mut opts),
+		)?,
+		StatusType::Both => repo.diff_tree_to_workdir_with_index(
+			head.as_ref(),
+			Some(&mut opts),
+		)?,
+	};
+
+	let mut sizes = HashMap::new();
+
+	for idx in 0..diff.deltas().len() {
+		if let Some(patch) = Patch::from_diff(&diff, idx)? {
+			let (_, additions, deletions) = patch.line_stats()?;
+			let delta = patch.delta();
+
+			if let Some(path) = delta
+				.new_file()
+				.path()
+				.or_else(|| delta.old_file().path())
+			{
+				sizes.insert(
+					path.to_string_lossy().to_string(),
+					additions + deletions,
+				);
+			}
+		}
+	}
+
+	Ok(sizes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		status::get_status,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+
+	#[test]
+	fn test_sort_status() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "b.rs", "a\n", "c1");
+		repo_write_file(&repo, "b.rs", "a\nb\nc\n").unwrap();
+		repo_write_file(&repo, "a.txt", "a\n").unwrap();
+		fs::create_dir(root.join("d")).unwrap();
+		fs::create_dir(root.join("d-e")).unwrap();
+		repo_write_file(&repo, "d/c.rs", "a\nb\n").unwrap();
+		repo_write_file(&repo, "d-e/f.md", "a\nb\nc\nd\n").unwrap();
+
+		let mut items =
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+
+		let mut sort = |sort, group| {
+			sort_status(
+				repo_path,
+				&mut items,
+				StatusType::WorkingDir,
+				StatusOrder { sort, group },
+			)
+			.unwrap();
+			items
+				.iter()
+				.map(|item| item.path.clone())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			sort(StatusSort::Extension, StatusGroup::Directory),
+			vec!["b.rs", "a.txt", "d/c.rs", "d-e/f.md"]
+		);
+		assert_eq!(
+			sort(StatusSort::ChangeSize, StatusGroup::ChangeType),
+			vec!["d-e/f.md", "d/c.rs", "a.txt", "b.rs"]
+		);
+		assert_eq!(
+			sort(StatusSort::Path, StatusGroup::ChangeType),
+			vec!["a.txt", "d-e/f.md", "d/c.rs", "b.rs"]
+		);
+	}
+
+	#[test]
+	fn test_config() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(
+			status_order_config(repo_path).unwrap(),
+			StatusOrder::default()
+		);
+
+		let order = StatusOrder {
+			sort: StatusSort::ModificationTime,
+			group: StatusGroup::ChangeType,
+		};
+		set_status_order_config(repo_path, order).unwrap();
+
+		assert_eq!(status_order_config(repo_path).unwrap(), order);
+	}
+}
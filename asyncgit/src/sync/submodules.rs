@@ -6,7 +6,7 @@ use git2::{
 };
 use scopetime::scope_time;
 
-use super::{repo, CommitId, RepoPath};
+use super::{action_log::log_action, repo, CommitId, RepoPath};
 use crate::{error::Result, sync::utils::work_dir, Error};
 
 pub use git2::SubmoduleStatus;
@@ -103,6 +103,8 @@ pub fn update_submodule(
 
 	submodule.update(true, Some(&mut options))?;
 
+	log_action(&repo, "update submodule", name);
+
 	Ok(())
 }
 
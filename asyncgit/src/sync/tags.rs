@@ -1,11 +1,16 @@
 use super::{get_commits_info, CommitId, RepoPath};
 use crate::{
 	error::Result,
-	sync::{repository::repo, utils::bytes2string},
+	sync::{
+		action_log::log_action, repository::repo, utils::bytes2string,
+	},
 };
 use scopetime::scope_time;
 use std::{
-	collections::{BTreeMap, HashMap, HashSet},
+	collections::{
+		hash_map::DefaultHasher, BTreeMap, HashMap, HashSet,
+	},
+	hash::{Hash, Hasher},
 	ops::Not,
 };
 
@@ -45,14 +50,68 @@ pub struct TagWithMetadata {
 	pub message: String,
 	///
 	pub commit_id: CommitId,
-	///
-	pub annotation: Option<String>,
 }
 
 static MAX_MESSAGE_WIDTH: usize = 100;
 
+/// fingerprint of all tag refs (names and the objects they point to),
+/// changes whenever a tag gets added, removed or moved
+pub fn get_tags_state(repo_path: &RepoPath) -> Result<u64> {
+	scope_time!("get_tags_state");
+
+	let repo = repo(repo_path)?;
+	let mut hasher = DefaultHasher::new();
+
+	repo.tag_foreach(|id, name| {
+		id.hash(&mut hasher);
+		name.hash(&mut hasher);
+		true
+	})?;
+
+	Ok(hasher.finish())
+}
+
 /// returns `Tags` type filled with all tags found in repo
 pub fn get_tags(repo_path: &RepoPath) -> Result<Tags> {
+	get_tags_ext(repo_path, true)
+}
+
+/// like `get_tags` but without reading the annotations,
+/// use `get_tag_annotation` to load them where needed
+pub fn get_tags_lazy(repo_path: &RepoPath) -> Result<Tags> {
+	get_tags_ext(repo_path, false)
+}
+
+/// annotation of the tag `tag_name`, `None` for lightweight tags
+pub fn get_tag_annotation(
+	repo_path: &RepoPath,
+	tag_name: &str,
+) -> Result<Option<String>> {
+	scope_time!("get_tag_annotation");
+
+	let repo = repo(repo_path)?;
+	let reference =
+		repo.find_reference(&format!("refs/tags/{}", tag_name))?;
+
+	let annotation =
+		reference.peel_to_tag().ok().as_ref().and_then(annotation);
+
+	Ok(annotation)
+}
+
+fn annotation(tag: &git2::Tag) -> Option<String> {
+	tag.message_bytes().and_then(|msg| {
+		msg.is_empty()
+			.not()
+			.then(|| bytes2string(msg).ok())
+			.flatten()
+	})
+}
+
+fn get_tags_ext(
+	repo_path: &RepoPath,
+	with_annotations: bool,
+) -> Result<Tags> {
 	scope_time!("get_tags");
 
 	let mut res = Tags::new();
@@ -90,17 +149,11 @@ pub fn get_tags(repo_path: &RepoPath) -> Result<Tags> {
 					|commit| Some(CommitId::new(commit.id())),
 				);
 
-			let annotation = repo
-				.find_tag(id)
-				.ok()
-				.as_ref()
-				.and_then(git2::Tag::message_bytes)
-				.and_then(|msg| {
-					msg.is_empty()
-						.not()
-						.then(|| bytes2string(msg).ok())
-						.flatten()
-				});
+			let annotation = if with_annotations {
+				repo.find_tag(id).ok().as_ref().and_then(annotation)
+			} else {
+				None
+			};
 
 			if let Some(commit) = commit {
 				adder(commit, Tag { name, annotation });
@@ -120,20 +173,14 @@ pub fn get_tags_with_metadata(
 ) -> Result<Vec<TagWithMetadata>> {
 	scope_time!("get_tags_with_metadata");
 
-	let tags_grouped_by_commit_id = get_tags(repo_path)?;
+	let tags_grouped_by_commit_id = get_tags_lazy(repo_path)?;
 
-	let tags_with_commit_id: Vec<(&str, Option<&str>, &CommitId)> =
+	let tags_with_commit_id: Vec<(&str, &CommitId)> =
 		tags_grouped_by_commit_id
 			.iter()
 			.flat_map(|(commit_id, tags)| {
 				tags.iter()
-					.map(|tag| {
-						(
-							tag.name.as_ref(),
-							tag.annotation.as_deref(),
-							commit_id,
-						)
-					})
+					.map(|tag| (tag.name.as_ref(), commit_id))
 					.collect::<Vec<_>>()
 			})
 			.collect();
@@ -141,7 +188,7 @@ pub fn get_tags_with_metadata(
 	let unique_commit_ids: HashSet<_> = tags_with_commit_id
 		.iter()
 		.copied()
-		.map(|(_, _, &commit_id)| commit_id)
+		.map(|(_, &commit_id)| commit_id)
 		.collect();
 	let mut commit_ids = Vec::with_capacity(unique_commit_ids.len());
 	commit_ids.extend(unique_commit_ids);
@@ -155,7 +202,7 @@ pub fn get_tags_with_metadata(
 
 	let mut tags: Vec<TagWithMetadata> = tags_with_commit_id
 		.into_iter()
-		.filter_map(|(tag, annotation, commit_id)| {
+		.filter_map(|(tag, commit_id)| {
 			unique_commit_infos.get(commit_id).map(|commit_info| {
 				TagWithMetadata {
 					name: String::from(tag),
@@ -163,7 +210,6 @@ pub fn get_tags_with_metadata(
 					time: commit_info.time,
 					message: commit_info.message.clone(),
 					commit_id: *commit_id,
-					annotation: annotation.map(String::from),
 				}
 			})
 		})
@@ -184,9 +230,26 @@ pub fn delete_tag(
 	let repo = repo(repo_path)?;
 	repo.tag_delete(tag_name)?;
 
+	log_action(&repo, "delete tag", tag_name);
+
 	Ok(())
 }
 
+/// whether a tag named `tag_name` already exists
+pub fn tag_exists(
+	repo_path: &RepoPath,
+	tag_name: &str,
+) -> Result<bool> {
+	scope_time!("tag_exists");
+
+	let repo = repo(repo_path)?;
+	let exists = repo
+		.find_reference(&format!("refs/tags/{}", tag_name))
+		.is_ok();
+
+	Ok(exists)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -222,6 +285,9 @@ mod tests {
 		repo.tag("a", &target, &sig, "", false).unwrap();
 		repo.tag("b", &target, &sig, "", false).unwrap();
 
+		assert!(tag_exists(repo_path, "a").unwrap());
+		assert!(!tag_exists(repo_path, "c").unwrap());
+
 		assert_eq!(
 			get_tags(repo_path).unwrap()[&CommitId::new(head_id)]
 				.iter()
@@ -251,4 +317,43 @@ mod tests {
 
 		assert_eq!(tags.len(), 0);
 	}
+
+	#[test]
+	fn test_tags_state_and_lazy_annotations() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let sig = repo.signature().unwrap();
+		let head_id =
+			CommitId::new(repo.head().unwrap().target().unwrap());
+		let target = repo
+			.find_object(head_id.into(), Some(ObjectType::Commit))
+			.unwrap();
+
+		let empty = get_tags_state(repo_path).unwrap();
+
+		repo.tag("a", &target, &sig, "annotation", false).unwrap();
+		repo.tag_lightweight("b", &target, false).unwrap();
+
+		let state = get_tags_state(repo_path).unwrap();
+		assert_ne!(state, empty);
+		assert_eq!(get_tags_state(repo_path).unwrap(), state);
+
+		let tags = get_tags_lazy(repo_path).unwrap();
+		assert_eq!(tags[&head_id].len(), 2);
+		assert!(tags[&head_id]
+			.iter()
+			.all(|t| t.annotation.is_none()));
+
+		assert_eq!(
+			get_tag_annotation(repo_path, "a").unwrap().as_deref(),
+			Some("annotation")
+		);
+		assert_eq!(get_tag_annotation(repo_path, "b").unwrap(), None);
+
+		delete_tag(repo_path, "b").unwrap();
+		assert_ne!(get_tags_state(repo_path).unwrap(), state);
+	}
 }
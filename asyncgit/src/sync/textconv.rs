@@ -0,0 +1,118 @@
+//! `diff.<driver>.textconv` of the diff driver `.gitattributes` assign
+//! to a path, libgit2 only honors the `binary` settings of drivers
+
+use super::shell::posix_shell;
+use crate::error::{Error, Result};
+use git2::{AttrCheckFlags, AttrValue, Repository};
+use std::{
+	io::Write,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+/// the textconv command configured for the diff driver of `path`
+pub fn textconv_command(
+	repo: &Repository,
+	path: &Path,
+) -> Option<String> {
+	let attr = repo
+		.get_attr(path, "diff", AttrCheckFlags::default())
+		.ok()?;
+
+	if let AttrValue::String(driver) = AttrValue::from_string(attr) {
+		repo.config()
+			.ok()?
+			.get_string(&format!("diff.{}.textconv", driver))
+			.ok()
+			.filter(|command| !command.trim().is_empty())
+	} else {
+		None
+	}
+}
+
+/// runs `command` like git does: through the shell with a temporary
+/// file holding `content` as argument, named like `path` so tools
+/// looking at the extension work
+pub fn textconv(
+	command: &str,
+	content: &[u8],
+	path: &Path,
+	work_dir: &Path,
+) -> Result<Vec<u8>> {
+	let name = path
+		.file_name()
+		.map(|name| format!("_{}", name.to_string_lossy()))
+		.unwrap_or_default();
+
+	let mut file = tempfile::Builder::new()
+		.prefix("gitui-textconv-")
+		.suffix(&name)
+		.tempfile()?;
+	file.write_all(content)?;
+	file.flush()?;
+
+	let shell = posix_shell().unwrap_or_else(|| PathBuf::from("sh"));
+	let output = Command::new(shell)
+		.arg("-c")
+		.arg(format!(r#"{} "$@""#, command))
+		.arg(command)
+		.arg(file.path())
+		.current_dir(work_dir)
+		.output()?;
+
+	if !output.status.success() {
+		return Err(Error::Generic(format!(
+			"textconv `{}` failed: {}",
+			command,
+			String::from_utf8_lossy(&output.stderr).trim()
+		)));
+	}
+
+	Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init;
+	use std::fs;
+
+	#[test]
+	fn test_textconv_command() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+
+		fs::write(
+			root.join(".gitattributes"),
+			"*.ipynb diff=nb\n*.png diff=exif\n*.bin -diff\n",
+		)
+		.unwrap();
+		repo.config()
+			.unwrap()
+			.set_str("diff.nb.textconv", "nbconvert")
+			.unwrap();
+
+		assert_eq!(
+			textconv_command(&repo, Path::new("a/b.ipynb"))
+				.as_deref(),
+			Some("nbconvert")
+		);
+		// a driver without textconv
+		assert_eq!(textconv_command(&repo, Path::new("b.png")), None);
+		assert_eq!(textconv_command(&repo, Path::new("b.bin")), None);
+		assert_eq!(textconv_command(&repo, Path::new("b.txt")), None);
+	}
+
+	#[test]
+	fn test_textconv() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.workdir().unwrap();
+
+		assert_eq!(
+			textconv("sed s/a/b/", b"a\n", Path::new("x.txt"), root)
+				.unwrap(),
+			b"b\n"
+		);
+		assert!(textconv("false", b"", Path::new("x"), root).is_err());
+	}
+}
@@ -3,7 +3,7 @@ use crate::{
 	error::{Error, Result},
 	sync::repository::repo,
 };
-use git2::{Oid, Repository, Tree};
+use git2::{Blob, Oid, Repository, Tree};
 use scopetime::scope_time;
 use std::{
 	cmp::Ordering,
@@ -82,13 +82,54 @@ pub fn tree_file_content(
 
 	let blob = repo.find_blob(file.id)?;
 
+	blob_content(&blob)
+}
+
+/// content of `path` in the tree of `commit`,
+/// will only work on utf8 content
+pub fn commit_file_content(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	path: &str,
+) -> Result<String> {
+	scope_time!("commit_file_content");
+
+	let repo = repo(repo_path)?;
+
+	let tree = repo.find_commit(commit.into())?.tree()?;
+	let blob =
+		repo.find_blob(tree.get_path(Path::new(path))?.id())?;
+
+	blob_content(&blob)
+}
+
+/// content of `path` as staged in the index,
+/// will only work on utf8 content
+pub fn index_file_content(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<String> {
+	scope_time!("index_file_content");
+
+	let repo = repo(repo_path)?;
+
+	let entry = repo
+		.index()?
+		.get_path(Path::new(path), 0)
+		.ok_or_else(|| {
+			Error::Generic(format!("'{}' is not in the index", path))
+		})?;
+	let blob = repo.find_blob(entry.id)?;
+
+	blob_content(&blob)
+}
+
+fn blob_content(blob: &Blob) -> Result<String> {
 	if blob.is_binary() {
 		return Err(Error::BinaryFile);
 	}
 
-	let content = String::from_utf8_lossy(blob.content()).to_string();
-
-	Ok(content)
+	Ok(String::from_utf8_lossy(blob.content()).to_string())
 }
 
 ///
@@ -123,7 +164,11 @@ fn tree_recurse(
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::sync::tests::{repo_init, write_commit_file};
+	use crate::sync::{
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
 	use pretty_assertions::{assert_eq, assert_ne};
 
 	#[test]
@@ -154,6 +199,32 @@ mod tests {
 		assert_ne!(files_c2[0], files[0]);
 	}
 
+	#[test]
+	fn test_file_content() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::create_dir(root.join("a")).unwrap();
+		let c1 = write_commit_file(&repo, "a/b.txt", "c1", "c1");
+		write_commit_file(&repo, "a/b.txt", "c2", "c2");
+
+		repo_write_file(&repo, "a/b.txt", "staged").unwrap();
+		stage_add_file(repo_path, Path::new("a/b.txt")).unwrap();
+
+		assert_eq!(
+			commit_file_content(repo_path, c1, "a/b.txt").unwrap(),
+			"c1"
+		);
+		assert!(commit_file_content(repo_path, c1, "b.txt").is_err());
+		assert_eq!(
+			index_file_content(repo_path, "a/b.txt").unwrap(),
+			"staged"
+		);
+		assert!(index_file_content(repo_path, "b.txt").is_err());
+	}
+
 	#[test]
 	fn test_sorting() {
 		let mut list = vec!["file", "folder/file", "folder/afile"]
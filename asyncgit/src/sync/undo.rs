@@ -0,0 +1,795 @@
+//! snapshots taken before destructive operations so the last one can
+//! be undone, kept in the reflog of `refs/gitui/undo` (like stashes),
+//! the ones of discards also serve as backups of discarded changes
+
+use super::{
+	action_log::log_action,
+	commit::signature_allow_undefined_name,
+	repository::repo,
+	utils::{get_head_repo, work_dir},
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	IndexEntry, IndexTime, ObjectType, Repository, ResetType,
+	Signature, StatusOptions, Tree,
+};
+use scopetime::scope_time;
+use std::{fs, io::Write, path::Path};
+
+const UNDO_REF: &str = "refs/gitui/undo";
+/// older snapshots are dropped
+const MAX_ENTRIES: usize = 50;
+
+/// operation a snapshot was taken for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UndoOperation {
+	/// discarding the workdir changes of a file or folder
+	Discard(String),
+	/// discarding the workdir changes of the files marked in the
+	/// status tab, read back from the `UndoEntry::files`
+	DiscardMarked(Vec<String>),
+	/// deleting a local branch (name without `refs/heads/`)
+	DeleteBranch(String),
+	/// amending the `HEAD` commit
+	Amend,
+	/// undoing the `HEAD` commit, see `sync::utils::undo_last_commit`
+	UndoCommit,
+}
+
+impl UndoOperation {
+	fn encode(&self) -> String {
+		match self {
+			Self::Discard(path) => format!("discard {}", path),
+			Self::DiscardMarked(_) => String::from("discard marked"),
+			Self::DeleteBranch(name) => {
+				format!("delete branch {}", name)
+			}
+			Self::Amend => String::from("amend"),
+			Self::UndoCommit => String::from("undo commit"),
+		}
+	}
+
+	fn decode(subject: &str) -> Option<Self> {
+		match subject {
+			"amend" => Some(Self::Amend),
+			"undo commit" => Some(Self::UndoCommit),
+			"discard marked" => Some(Self::DiscardMarked(Vec::new())),
+			_ => subject
+				.strip_prefix("discard ")
+				.map(|path| Self::Discard(path.to_string()))
+				.or_else(|| {
+					subject.strip_prefix("delete branch ").map(
+						|name| Self::DeleteBranch(name.to_string()),
+					)
+				}),
+		}
+	}
+}
+
+/// a snapshot and what undoing it restores
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoEntry {
+	/// the snapshot commit
+	pub id: CommitId,
+	///
+	pub operation: UndoOperation,
+	/// files a discard touched, undoing writes back their content
+	/// or removes the ones that did not exist before
+	pub files: Vec<String>,
+	/// commit the deleted branch or `HEAD` pointed to
+	pub target: Option<CommitId>,
+	/// seconds since the epoch
+	pub time: i64,
+}
+
+impl UndoEntry {
+	/// file or folder of a discard snapshot
+	pub fn discarded_path(&self) -> Option<&str> {
+		match &self.operation {
+			UndoOperation::Discard(path) => Some(path),
+			UndoOperation::DiscardMarked(_) => Some("marked files"),
+			_ => None,
+		}
+	}
+}
+
+/// records the state `operation` is about to destroy, call it right
+/// before running the operation
+pub fn undo_snapshot(
+	repo_path: &RepoPath,
+	operation: &UndoOperation,
+) -> Result<CommitId> {
+	scope_time!("undo_snapshot");
+
+	let repo = repo(repo_path)?;
+
+	let (files, target) = match operation {
+		UndoOperation::Discard(path) => {
+			(discarded_files(&repo, &[path])?, None)
+		}
+		UndoOperation::DiscardMarked(paths) => {
+			(discarded_files(&repo, paths)?, None)
+		}
+		UndoOperation::DeleteBranch(name) => {
+			let branch =
+				repo.find_branch(name, git2::BranchType::Local)?;
+			(Vec::new(), Some(branch.get().peel_to_commit()?))
+		}
+		UndoOperation::Amend | UndoOperation::UndoCommit => {
+			let head = get_head_repo(&repo)?;
+			(Vec::new(), Some(repo.find_commit(head.into())?))
+		}
+	};
+
+	let tree = snapshot_tree(&repo, &files)?;
+	let signature = signature_allow_undefined_name(&repo)
+		.or_else(|_| Signature::now("gitui", "gitui"))?;
+
+	let subject = operation.encode();
+	let msg = if files.is_empty() {
+		subject.clone()
+	} else {
+		format!("{}\n\n{}", subject, files.join("\n"))
+	};
+
+	// the target stays reachable, a deleted branch could get
+	// garbage collected otherwise
+	let parents = target.iter().collect::<Vec<_>>();
+	let id = repo.commit(
+		None,
+		&signature,
+		&signature,
+		&msg,
+		&tree,
+		parents.as_slice(),
+	)?;
+
+	repo.reference_ensure_log(UNDO_REF)?;
+	repo.reference(UNDO_REF, id, true, &subject)?;
+
+	let mut reflog = repo.reflog(UNDO_REF)?;
+	if reflog.len() > MAX_ENTRIES {
+		for idx in (MAX_ENTRIES..reflog.len()).rev() {
+			reflog.remove(idx, false)?;
+		}
+		reflog.write()?;
+	}
+
+	Ok(id.into())
+}
+
+/// the snapshot an undo restores next
+pub fn undo_last_entry(
+	repo_path: &RepoPath,
+) -> Result<Option<UndoEntry>> {
+	scope_time!("undo_last_entry");
+
+	let repo = repo(repo_path)?;
+	let reflog = repo.reflog(UNDO_REF)?;
+
+	reflog
+		.get(0)
+		.map(|entry| parse_entry(&repo, &entry))
+		.transpose()
+}
+
+/// all snapshots, newest first
+pub fn undo_entries(repo_path: &RepoPath) -> Result<Vec<UndoEntry>> {
+	scope_time!("undo_entries");
+
+	let repo = repo(repo_path)?;
+	let reflog = repo.reflog(UNDO_REF)?;
+
+	reflog
+		.iter()
+		.map(|entry| parse_entry(&repo, &entry))
+		.collect()
+}
+
+fn parse_entry(
+	repo: &Repository,
+	entry: &git2::ReflogEntry,
+) -> Result<UndoEntry> {
+	let commit = repo.find_commit(entry.id_new())?;
+	let msg = commit.message().unwrap_or_default();
+	let mut lines = msg.lines();
+
+	let mut operation =
+		lines.next().and_then(UndoOperation::decode).ok_or_else(
+			|| Error::Generic(format!("unknown undo entry: {}", msg)),
+		)?;
+
+	let files = lines
+		.filter(|line| !line.is_empty())
+		.map(ToString::to_string)
+		.collect::<Vec<_>>();
+
+	if let UndoOperation::DiscardMarked(paths) = &mut operation {
+		paths.clone_from(&files);
+	}
+
+	Ok(UndoEntry {
+		id: commit.id().into(),
+		operation,
+		files,
+		target: commit.parent_id(0).ok().map(Into::into),
+		time: entry.committer().when().seconds(),
+	})
+}
+
+/// writes back the files of a discard snapshot, unlike `undo` this
+/// works for any of them and keeps the snapshot
+pub fn restore_discarded(
+	repo_path: &RepoPath,
+	entry: &UndoEntry,
+) -> Result<()> {
+	scope_time!("restore_discarded");
+
+	if entry.discarded_path().is_none() {
+		return Err(Error::Generic(String::from(
+			"not a discard snapshot",
+		)));
+	}
+
+	let repo = repo(repo_path)?;
+	let tree = repo.find_commit(entry.id.into())?.tree()?;
+
+	restore_files(&repo, &tree, &entry.files)?;
+
+	log_action(&repo, "restore discarded", &entry.files.join(", "));
+
+	Ok(())
+}
+
+/// restores the state before `entry` (which has to be the
+/// `undo_last_entry`) and drops it
+pub fn undo(repo_path: &RepoPath, entry: &UndoEntry) -> Result<()> {
+	scope_time!("undo");
+
+	let repo = repo(repo_path)?;
+
+	if undo_last_entry(repo_path)?.as_ref() != Some(entry) {
+		return Err(Error::Generic(String::from(
+			"not the last undo entry",
+		)));
+	}
+
+	match &entry.operation {
+		UndoOperation::Discard(_)
+		| UndoOperation::DiscardMarked(_) => {
+			let tree = repo.find_commit(entry.id.into())?.tree()?;
+			restore_files(&repo, &tree, &entry.files)?;
+		}
+		UndoOperation::DeleteBranch(name) => {
+			let target = target_commit(&repo, entry)?;
+			repo.branch(name, &target, false)?;
+		}
+		UndoOperation::Amend => {
+			let target = target_commit(&repo, entry)?;
+			let head =
+				repo.find_commit(get_head_repo(&repo)?.into())?;
+
+			if head.parent_ids().ne(target.parent_ids()) {
+				return Err(Error::Generic(String::from(
+					"HEAD moved since the amend",
+				)));
+			}
+
+			repo.reset(target.as_object(), ResetType::Soft, None)?;
+		}
+		UndoOperation::UndoCommit => {
+			let target = target_commit(&repo, entry)?;
+			let head =
+				get_head_repo(&repo).ok().map(CommitId::get_oid);
+
+			if head != target.parent_id(0).ok() {
+				return Err(Error::Generic(String::from(
+					"HEAD moved since the commit was undone",
+				)));
+			}
+
+			repo.reset(target.as_object(), ResetType::Soft, None)?;
+		}
+	}
+
+	drop_last(&repo)?;
+
+	log_action(&repo, "undo", &entry.operation.encode());
+
+	Ok(())
+}
+
+fn target_commit<'a>(
+	repo: &'a Repository,
+	entry: &UndoEntry,
+) -> Result<git2::Commit<'a>> {
+	let target = entry.target.ok_or_else(|| {
+		Error::Generic(String::from("undo entry without target"))
+	})?;
+
+	Ok(repo.find_commit(target.into())?)
+}
+
+/// like `git stash drop` for `stash@{0}`
+fn drop_last(repo: &Repository) -> Result<()> {
+	let mut reflog = repo.reflog(UNDO_REF)?;
+	reflog.remove(0, true)?;
+	reflog.write()?;
+
+	if let Some(id) = reflog.get(0).map(|entry| entry.id_new()) {
+		let entries = reflog.len();
+		repo.find_reference(UNDO_REF)?.set_target(id, "undo")?;
+
+		// moving the ref got logged, that is not a snapshot
+		let mut reflog = repo.reflog(UNDO_REF)?;
+		if reflog.len() > entries {
+			reflog.remove(0, false)?;
+			reflog.write()?;
+		}
+	} else {
+		repo.find_reference(UNDO_REF)?.delete()?;
+	}
+
+	Ok(())
+}
+
+/// workdir changes below `paths`, including untracked files
+fn discarded_files<T: AsRef<str>>(
+	repo: &Repository,
+	paths: &[T],
+) -> Result<Vec<String>> {
+	let mut options = StatusOptions::new();
+	for path in paths {
+		options.pathspec(path.as_ref());
+	}
+	options
+		.include_untracked(true)
+		.recurse_untracked_dirs(true)
+		.include_ignored(false);
+
+	let statuses = repo.statuses(Some(&mut options))?;
+
+	Ok(statuses
+		.iter()
+		.filter(|status| {
+			status.status().is_wt_new()
+				|| status.status().is_wt_modified()
+				|| status.status().is_wt_deleted()
+				|| status.status().is_wt_typechange()
+				|| status.status().is_wt_renamed()
+		})
+		.filter_map(|status| status.path().map(ToString::to_string))
+		.collect())
+}
+
+/// tree of the workdir content of `files`, missing ones are left out
+fn snapshot_tree<'a>(
+	repo: &'a Repository,
+	files: &[String],
+) -> Result<Tree<'a>> {
+	let work_dir = work_dir(repo)?;
+	let mut index = git2::Index::new()?;
+
+	for file in files {
+		if let Some(entry) = workdir_entry(repo, work_dir, file)? {
+			index.add(&entry)?;
+		}
+	}
+
+	Ok(repo.find_tree(index.write_tree_to(repo)?)?)
+}
+
+fn workdir_entry(
+	repo: &Repository,
+	work_dir: &Path,
+	file: &str,
+) -> Result<Option<IndexEntry>> {
+	let path = work_dir.join(file);
+	let meta = match path.symlink_metadata() {
+		Ok(meta) => meta,
+		Err(_) => return Ok(None),
+	};
+
+	let (mode, id) = if meta.file_type().is_symlink() {
+		let target = fs::read_link(&path)?;
+		(0o120_000, repo.blob(target.to_string_lossy().as_bytes())?)
+	} else if meta.is_file() {
+		let mode = if is_executable(&meta) {
+			0o100_755
+		} else {
+			0o100_644
+		};
+		(mode, repo.blob_path(&path)?)
+	} else {
+		return Ok(None);
+	};
+
+	Ok(Some(IndexEntry {
+		ctime: IndexTime::new(0, 0),
+		mtime: IndexTime::new(0, 0),
+		dev: 0,
+		ino: 0,
+		mode,
+		uid: 0,
+		gid: 0,
+		file_size: 0,
+		id,
+		flags: 0,
+		flags_extended: 0,
+		path: file.as_bytes().to_vec(),
+	}))
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &fs::Metadata) -> bool {
+	use std::os::unix::fs::PermissionsExt;
+
+	meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+const fn is_executable(_meta: &fs::Metadata) -> bool {
+	false
+}
+
+/// writes back `files` as they are in `tree`, files missing from it
+/// did not exist and get removed. whatever is at a path gets replaced,
+/// never written through
+fn restore_files(
+	repo: &Repository,
+	tree: &Tree,
+	files: &[String],
+) -> Result<()> {
+	let work_dir = work_dir(repo)?;
+	let root = work_dir.canonicalize()?;
+
+	for file in files {
+		let path = work_dir.join(file);
+
+		match tree.get_path(Path::new(file)) {
+			Ok(entry) => {
+				let blob = entry
+					.to_object(repo)?
+					.peel(ObjectType::Blob)?
+					.into_blob()
+					.map_err(|_| {
+						Error::Generic(format!(
+							"not a file: {}",
+							file
+						))
+					})?;
+
+				if let Some(parent) = path.parent() {
+					fs::create_dir_all(parent)?;
+					// a symlinked dir would lead out of the repo
+					if !parent.canonicalize()?.starts_with(&root) {
+						return Err(Error::Generic(format!(
+							"outside of the repository: {}",
+							file
+						)));
+					}
+				}
+				remove_file(&path)?;
+
+				if entry.filemode() == 0o120_000 {
+					write_symlink(&path, blob.content())?;
+				} else {
+					write_new_file(
+						&path,
+						blob.content(),
+						entry.filemode() == 0o100_755,
+					)?;
+				}
+			}
+			Err(_) => remove_file(&path)?,
+		}
+	}
+
+	Ok(())
+}
+
+/// removes a file or symlink (not what it points to) at `path`
+fn remove_file(path: &Path) -> Result<()> {
+	match path.symlink_metadata() {
+		Ok(meta) if meta.is_dir() => Err(Error::Generic(format!(
+			"a directory is in the way: {}",
+			path.display()
+		))),
+		Ok(_) => Ok(fs::remove_file(path)?),
+		Err(_) => Ok(()),
+	}
+}
+
+/// fails instead of following a symlink someone put there meanwhile
+fn write_new_file(
+	path: &Path,
+	content: &[u8],
+	executable: bool,
+) -> Result<()> {
+	let mut options = fs::OpenOptions::new();
+	options.write(true).create_new(true);
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::OpenOptionsExt;
+
+		options.mode(if executable { 0o777 } else { 0o666 });
+	}
+	#[cfg(not(unix))]
+	let _ = executable;
+
+	options.open(path)?.write_all(content)?;
+
+	Ok(())
+}
+
+#[cfg(unix)]
+fn write_symlink(path: &Path, target: &[u8]) -> Result<()> {
+	use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+	std::os::unix::fs::symlink(OsStr::from_bytes(target), path)?;
+
+	Ok(())
+}
+
+/// like git without `core.symlinks`: a file holding the target
+#[cfg(not(unix))]
+fn write_symlink(path: &Path, target: &[u8]) -> Result<()> {
+	write_new_file(path, target, false)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		amend, branch::create_branch, checkout_branch, commit,
+		delete_branch, reset_workdir, reset_workdir_paths,
+		stage_add_file, tests::repo_init, utils::undo_last_commit,
+	};
+	use std::{fs::File, io::Write};
+
+	#[test]
+	fn test_undo_discard() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("foo"))
+			.unwrap()
+			.write_all(b"foo")
+			.unwrap();
+		stage_add_file(repo_path, Path::new("foo")).unwrap();
+		commit(repo_path, "add foo").unwrap();
+
+		fs::create_dir(root.join("dir")).unwrap();
+		File::create(root.join("dir/new"))
+			.unwrap()
+			.write_all(b"new")
+			.unwrap();
+		File::create(root.join("foo"))
+			.unwrap()
+			.write_all(b"changed")
+			.unwrap();
+
+		undo_snapshot(
+			repo_path,
+			&UndoOperation::Discard("dir".into()),
+		)
+		.unwrap();
+		reset_workdir(repo_path, "dir").unwrap();
+		undo_snapshot(
+			repo_path,
+			&UndoOperation::Discard("foo".into()),
+		)
+		.unwrap();
+		reset_workdir(repo_path, "foo").unwrap();
+
+		assert!(!root.join("dir/new").exists());
+		assert_eq!(
+			fs::read_to_string(root.join("foo")).unwrap(),
+			"foo"
+		);
+
+		let entry = undo_last_entry(repo_path).unwrap().unwrap();
+		assert_eq!(
+			entry.operation,
+			UndoOperation::Discard("foo".into())
+		);
+		assert_eq!(entry.files, vec![String::from("foo")]);
+
+		undo(repo_path, &entry).unwrap();
+		assert_eq!(
+			fs::read_to_string(root.join("foo")).unwrap(),
+			"changed"
+		);
+
+		let entry = undo_last_entry(repo_path).unwrap().unwrap();
+		assert_eq!(entry.files, vec![String::from("dir/new")]);
+
+		undo(repo_path, &entry).unwrap();
+		assert_eq!(
+			fs::read_to_string(root.join("dir/new")).unwrap(),
+			"new"
+		);
+
+		assert_eq!(undo_last_entry(repo_path).unwrap(), None);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_undo_discard_symlink() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+		let other_dir = tempfile::TempDir::new().unwrap();
+		let outside = other_dir.path().join("outside");
+		fs::write(&outside, "outside").unwrap();
+
+		std::os::unix::fs::symlink("target", root.join("link"))
+			.unwrap();
+		undo_snapshot(
+			repo_path,
+			&UndoOperation::Discard("link".into()),
+		)
+		.unwrap();
+		fs::remove_file(root.join("link")).unwrap();
+		// something else took its place meanwhile
+		std::os::unix::fs::symlink(&outside, root.join("link"))
+			.unwrap();
+
+		let entry = undo_last_entry(repo_path).unwrap().unwrap();
+		undo(repo_path, &entry).unwrap();
+
+		assert_eq!(
+			fs::read_link(root.join("link")).unwrap(),
+			Path::new("target")
+		);
+		assert_eq!(fs::read_to_string(&outside).unwrap(), "outside");
+	}
+
+	#[test]
+	fn test_undo_discard_marked() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		for file in ["foo", "bar", "baz"] {
+			File::create(root.join(file))
+				.unwrap()
+				.write_all(file.as_bytes())
+				.unwrap();
+		}
+
+		let marked = vec![String::from("foo"), String::from("bar")];
+		undo_snapshot(
+			repo_path,
+			&UndoOperation::DiscardMarked(marked),
+		)
+		.unwrap();
+		reset_workdir_paths(repo_path, &["foo", "bar"]).unwrap();
+		assert!(!root.join("foo").exists());
+
+		let entry = undo_last_entry(repo_path).unwrap().unwrap();
+		assert_eq!(
+			entry.operation,
+			UndoOperation::DiscardMarked(vec![
+				String::from("bar"),
+				String::from("foo")
+			])
+		);
+		assert_eq!(entry.discarded_path(), Some("marked files"));
+
+		undo(repo_path, &entry).unwrap();
+		assert_eq!(
+			fs::read_to_string(root.join("foo")).unwrap(),
+			"foo"
+		);
+		assert_eq!(
+			fs::read_to_string(root.join("bar")).unwrap(),
+			"bar"
+		);
+		assert_eq!(
+			fs::read_to_string(root.join("baz")).unwrap(),
+			"baz"
+		);
+	}
+
+	#[test]
+	fn test_restore_discarded() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		for content in ["first", "second"] {
+			File::create(root.join("foo"))
+				.unwrap()
+				.write_all(content.as_bytes())
+				.unwrap();
+			undo_snapshot(
+				repo_path,
+				&UndoOperation::Discard("foo".into()),
+			)
+			.unwrap();
+			reset_workdir(repo_path, "foo").unwrap();
+		}
+		undo_snapshot(repo_path, &UndoOperation::UndoCommit).unwrap();
+
+		let entries = undo_entries(repo_path).unwrap();
+		assert_eq!(entries.len(), 3);
+		assert_eq!(entries[0].operation, UndoOperation::UndoCommit);
+		assert!(restore_discarded(repo_path, &entries[0]).is_err());
+
+		restore_discarded(repo_path, &entries[2]).unwrap();
+		assert_eq!(
+			fs::read_to_string(root.join("foo")).unwrap(),
+			"first"
+		);
+		assert_eq!(undo_entries(repo_path).unwrap().len(), 3);
+	}
+
+	#[test]
+	fn test_undo_delete_branch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "feature").unwrap();
+		File::create(root.join("foo")).unwrap();
+		stage_add_file(repo_path, Path::new("foo")).unwrap();
+		let id = commit(repo_path, "on feature").unwrap();
+		checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+		undo_snapshot(
+			repo_path,
+			&UndoOperation::DeleteBranch("feature".into()),
+		)
+		.unwrap();
+		delete_branch(repo_path, "refs/heads/feature").unwrap();
+
+		let entry = undo_last_entry(repo_path).unwrap().unwrap();
+		assert_eq!(entry.target, Some(id));
+
+		undo(repo_path, &entry).unwrap();
+
+		let branch = repo
+			.find_branch("feature", git2::BranchType::Local)
+			.unwrap();
+		assert_eq!(branch.get().target(), Some(id.get_oid()));
+	}
+
+	#[test]
+	fn test_undo_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("foo")).unwrap();
+		stage_add_file(repo_path, Path::new("foo")).unwrap();
+		let id = commit(repo_path, "add foo").unwrap();
+
+		undo_snapshot(repo_path, &UndoOperation::UndoCommit).unwrap();
+		undo_last_commit(repo_path).unwrap();
+
+		let entry = undo_last_entry(repo_path).unwrap().unwrap();
+		undo(repo_path, &entry).unwrap();
+
+		assert_eq!(get_head_repo(&repo).unwrap(), id);
+		assert!(undo(repo_path, &entry).is_err());
+
+		undo_snapshot(repo_path, &UndoOperation::Amend).unwrap();
+		amend(repo_path, id, "amended").unwrap();
+
+		let entry = undo_last_entry(repo_path).unwrap().unwrap();
+		assert_eq!(entry.operation, UndoOperation::Amend);
+		undo(repo_path, &entry).unwrap();
+
+		assert_eq!(get_head_repo(&repo).unwrap(), id);
+	}
+}
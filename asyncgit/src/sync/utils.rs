@@ -1,11 +1,14 @@
 //! sync git api (various methods)
 
 use super::{
-	repository::repo, CommitId, RepoPath, ShowUntrackedFilesConfig,
+	repository::{ceiling_dirs, repo},
+	CommitId, RepoPath, ShowUntrackedFilesConfig,
 };
 use crate::{
 	error::{Error, Result},
-	sync::config::untracked_files_config_repo,
+	sync::{
+		action_log::log_action, config::untracked_files_config_repo,
+	},
 };
 use git2::{IndexAddOption, Repository, RepositoryOpenFlags};
 use scopetime::scope_time;
@@ -29,7 +32,7 @@ pub fn is_repo(repo_path: &RepoPath) -> bool {
 	Repository::open_ext(
 		repo_path.gitpath(),
 		RepositoryOpenFlags::empty(),
-		Vec::<&Path>::new(),
+		ceiling_dirs(),
 	)
 	.is_ok()
 }
@@ -100,6 +103,44 @@ pub fn stage_add_file(
 	index.add_path(path)?;
 	index.write()?;
 
+	log_action(&repo, "stage", &path.to_string_lossy());
+
+	Ok(())
+}
+
+/// like `stage_add_file` for many files in one index write, files
+/// missing in the workdir get removed like in `stage_addremoved`
+pub fn stage_add_files(
+	repo_path: &RepoPath,
+	paths: &[&Path],
+) -> Result<()> {
+	scope_time!("stage_add_files");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let mut index = repo.index()?;
+
+	for path in paths {
+		if work_dir.join(path).symlink_metadata().is_ok() {
+			index.add_path(path)?;
+		} else {
+			index.remove_path(path)?;
+		}
+	}
+
+	index.write()?;
+
+	log_action(
+		&repo,
+		"stage",
+		&paths
+			.iter()
+			.map(|path| path.to_string_lossy())
+			.collect::<Vec<_>>()
+			.join(", "),
+	);
+
 	Ok(())
 }
 
@@ -133,12 +174,15 @@ pub fn stage_add_all(
 
 	index.write()?;
 
+	log_action(&repo, "stage", pattern);
+
 	Ok(())
 }
 
 /// Undo last commit in repo
 pub fn undo_last_commit(repo_path: &RepoPath) -> Result<()> {
 	let repo = repo(repo_path)?;
+	let head = get_head_repo(&repo)?;
 	let previous_commit = repo.revparse_single("HEAD~")?;
 
 	Repository::reset(
@@ -148,6 +192,8 @@ pub fn undo_last_commit(repo_path: &RepoPath) -> Result<()> {
 		None,
 	)?;
 
+	log_action(&repo, "undo commit", &head.get_short_string());
+
 	Ok(())
 }
 
@@ -165,6 +211,8 @@ pub fn stage_addremoved(
 	index.remove_path(path)?;
 	index.write()?;
 
+	log_action(&repo, "stage", &path.to_string_lossy());
+
 	Ok(())
 }
 
@@ -273,6 +321,34 @@ mod tests {
 		assert_eq!(get_statuses(repo_path), (1, 1));
 	}
 
+	#[test]
+	fn test_staging_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "gone.txt", "gone", "c1");
+		remove_file(root.join("gone.txt")).unwrap();
+
+		for file in ["file1.txt", "file2.txt"] {
+			File::create(&root.join(file))
+				.unwrap()
+				.write_all(b"test")
+				.unwrap();
+		}
+
+		assert_eq!(get_statuses(repo_path), (3, 0));
+
+		stage_add_files(
+			repo_path,
+			&[Path::new("file1.txt"), Path::new("gone.txt")],
+		)
+		.unwrap();
+
+		assert_eq!(get_statuses(repo_path), (1, 2));
+	}
+
 	#[test]
 	fn test_staging_folder() -> Result<()> {
 		let (_td, repo) = repo_init().unwrap();
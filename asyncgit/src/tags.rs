@@ -16,6 +16,8 @@ use sync::Tags;
 #[derive(Default, Clone)]
 pub struct TagsResult {
 	hash: u64,
+	/// see `sync::get_tags_state`
+	state: u64,
 	tags: Tags,
 }
 
@@ -80,15 +82,24 @@ impl AsyncTags {
 
 		if outdated {
 			self.job.spawn(AsyncTagsJob::new(
-				self.last
-					.as_ref()
-					.map_or(0, |(_, result)| result.hash),
+				self.last.as_ref().map_or((0, 0), |(_, result)| {
+					(result.hash, result.state)
+				}),
 				repo,
 			));
 
 			if let Some(job) = self.job.take_last() {
-				if let Some(Ok(result)) = job.result() {
-					self.last = Some(result);
+				match job.result() {
+					Some(Ok((time, Some(result)))) => {
+						self.last = Some((time, result));
+					}
+					// tag refs unchanged, keep the tags we have
+					Some(Ok((time, None))) => {
+						if let Some(last) = self.last.as_mut() {
+							last.0 = time;
+						}
+					}
+					_ => (),
 				}
 			}
 		} else {
@@ -101,8 +112,10 @@ impl AsyncTags {
 }
 
 enum JobState {
-	Request(u64, RepoPath),
-	Response(Result<(Instant, TagsResult)>),
+	/// hash of the last tags and state of the tag refs they came from
+	Request((u64, u64), RepoPath),
+	/// `None` if the tag refs did not change
+	Response(Result<(Instant, Option<TagsResult>)>),
 }
 
 ///
@@ -114,16 +127,18 @@ pub struct AsyncTagsJob {
 ///
 impl AsyncTagsJob {
 	///
-	pub fn new(last_hash: u64, repo: RepoPath) -> Self {
+	pub fn new(last: (u64, u64), repo: RepoPath) -> Self {
 		Self {
 			state: Arc::new(Mutex::new(Some(JobState::Request(
-				last_hash, repo,
+				last, repo,
 			)))),
 		}
 	}
 
 	///
-	pub fn result(&self) -> Option<Result<(Instant, TagsResult)>> {
+	pub fn result(
+		&self,
+	) -> Option<Result<(Instant, Option<TagsResult>)>> {
 		if let Ok(mut state) = self.state.lock() {
 			if let Some(state) = state.take() {
 				return match state {
@@ -137,6 +152,30 @@ impl AsyncTagsJob {
 	}
 }
 
+impl AsyncTagsJob {
+	/// only reads the tags (and their objects) again
+	/// if the tag refs changed since the last run
+	fn fetch(
+		repo: &RepoPath,
+		last_hash: u64,
+		last_state: u64,
+		notification: &mut AsyncGitNotification,
+	) -> Result<(Instant, Option<TagsResult>)> {
+		let state = sync::get_tags_state(repo)?;
+		if last_hash != 0 && state == last_state {
+			return Ok((Instant::now(), None));
+		}
+
+		let tags = sync::get_tags_lazy(repo)?;
+		let hash = hash(&tags);
+		if last_hash != hash {
+			*notification = AsyncGitNotification::Tags;
+		}
+
+		Ok((Instant::now(), Some(TagsResult { hash, state, tags })))
+	}
+}
+
 impl AsyncJob for AsyncTagsJob {
 	type Notification = AsyncGitNotification;
 	type Progress = ();
@@ -148,17 +187,13 @@ impl AsyncJob for AsyncTagsJob {
 		let mut notification = AsyncGitNotification::FinishUnchanged;
 		if let Ok(mut state) = self.state.lock() {
 			*state = state.take().map(|state| match state {
-				JobState::Request(last_hash, repo) => {
-					let tags = sync::get_tags(&repo);
-
-					JobState::Response(tags.map(|tags| {
-						let hash = hash(&tags);
-						if last_hash != hash {
-							notification = AsyncGitNotification::Tags;
-						}
-
-						(Instant::now(), TagsResult { hash, tags })
-					}))
+				JobState::Request((last_hash, last_state), repo) => {
+					JobState::Response(Self::fetch(
+						&repo,
+						last_hash,
+						last_state,
+						&mut notification,
+					))
 				}
 				JobState::Response(result) => {
 					JobState::Response(result)
@@ -0,0 +1,126 @@
+//! registry of the background tasks that are currently running
+
+use once_cell::sync::Lazy;
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant},
+};
+
+static TASKS: Lazy<Mutex<Vec<TaskInfo>>> =
+	Lazy::new(|| Mutex::new(Vec::new()));
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// snapshot of a running task
+#[derive(Clone, Debug)]
+pub struct TaskInfo {
+	///
+	pub id: usize,
+	/// kind of job (e.g. `blame`)
+	pub kind: &'static str,
+	/// human readable description of the job parameters
+	pub params: String,
+	started: Instant,
+	cancel: Arc<AtomicBool>,
+}
+
+impl TaskInfo {
+	///
+	pub fn runtime(&self) -> Duration {
+		self.started.elapsed()
+	}
+
+	///
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+}
+
+/// keeps a task listed in the registry until dropped
+pub struct TaskHandle {
+	id: usize,
+	cancel: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+	///
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+}
+
+impl Drop for TaskHandle {
+	fn drop(&mut self) {
+		if let Ok(mut tasks) = TASKS.lock() {
+			tasks.retain(|task| task.id != self.id);
+		}
+	}
+}
+
+/// lists a task in the registry, `cancel` gets set once
+/// the user asks to cancel it
+pub fn register(
+	kind: &'static str,
+	params: String,
+	cancel: Arc<AtomicBool>,
+) -> TaskHandle {
+	let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+	if let Ok(mut tasks) = TASKS.lock() {
+		tasks.push(TaskInfo {
+			id,
+			kind,
+			params,
+			started: Instant::now(),
+			cancel: Arc::clone(&cancel),
+		});
+	}
+
+	TaskHandle { id, cancel }
+}
+
+/// all tasks currently running, oldest first
+pub fn running() -> Vec<TaskInfo> {
+	TASKS.lock().map(|tasks| tasks.clone()).unwrap_or_default()
+}
+
+/// flags task `id` as cancelled, returns `false` if it already finished
+pub fn cancel(id: usize) -> bool {
+	TASKS.lock().map_or(false, |tasks| {
+		tasks
+			.iter()
+			.find(|task| task.id == id)
+			.map(|task| task.cancel.store(true, Ordering::Relaxed))
+			.is_some()
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_register_cancel() {
+		let handle = register(
+			"test",
+			String::from("foo"),
+			Arc::new(AtomicBool::new(false)),
+		);
+		let id = handle.id;
+
+		let task =
+			running().into_iter().find(|t| t.id == id).unwrap();
+		assert_eq!(task.kind, "test");
+		assert!(!task.is_cancelled());
+
+		assert!(cancel(id));
+		assert!(handle.is_cancelled());
+
+		drop(handle);
+
+		assert!(!running().iter().any(|t| t.id == id));
+		assert!(!cancel(id));
+	}
+}
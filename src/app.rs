@@ -2,50 +2,81 @@ use crate::{
 	accessors,
 	cmdbar::CommandBar,
 	components::{
-		event_pump, AppOption, BlameFileComponent,
-		BranchListComponent, CommandBlocking, CommandInfo,
-		CommitComponent, CompareCommitsComponent, Component,
-		ConfirmComponent, CreateBranchComponent, DrawableComponent,
-		ExternalEditorComponent, FetchComponent, FileFindPopup,
-		FileRevlogComponent, HelpComponent, InspectCommitComponent,
-		MsgComponent, OptionsPopupComponent, PullComponent,
-		PushComponent, PushTagsComponent, RenameBranchComponent,
-		RevisionFilesPopup, SharedOptions, StashMsgComponent,
+		event_pump, time_to_string, AppOption, BlameFileComponent,
+		BranchCompareComponent, BranchListComponent, CommandBlocking,
+		CommandInfo, CommitComponent, CompareCommitsComponent,
+		Component, ConfirmComponent, ConfirmPrefs,
+		CreateBranchComponent, DrawableComponent, EditNoteComponent,
+		ExternalEditorComponent, ExternalTool, FetchComponent,
+		FileFindPopup, FileRevlogComponent, FileTreeOpen,
+		HelpComponent, InspectCommitComponent, MaintenanceComponent,
+		MsgComponent, NumberInputComponent, Options,
+		OptionsPopupComponent, PullComponent, PullRequestsComponent,
+		PushComponent, PushOptionsComponent, PushReviewComponent,
+		PushTagsComponent, RenameBranchComponent, RevisionFilesPopup,
+		SelectListComponent, SelectListOpen, SharedCommitCache,
+		StashMsgComponent, StatusBarComponent,
 		SubmodulesListComponent, TagCommitComponent,
-		TagListComponent,
+		TagListComponent, TaskListComponent, ToastComponent,
+		ToastKind, TutorialComponent,
 	},
+	config_check::ConfigIssue,
 	input::{Input, InputEvent, InputState},
-	keys::{key_match, KeyConfig, SharedKeyConfig},
+	keys::{key_match, GituiKeyEvent, KeyConfig, SharedKeyConfig},
 	popup_stack::PopupStack,
 	queue::{
-		Action, InternalEvent, NeedsUpdate, Queue, StackablePopupOpen,
+		Action, InternalEvent, NeedsUpdate, PickTarget, Queue,
+		StackablePopupOpen,
 	},
-	setup_popups,
+	secrets::SecretPaths,
+	setup_popups, startup,
 	strings::{self, order},
-	tabs::{FilesTab, Revlog, StashList, Stashing, Status},
-	ui::style::{SharedTheme, Theme},
+	tabs::{
+		AppTab, FilesTab, Revlog, SearchTab, StashList, Stashing,
+		Status, TabsConfig,
+	},
+	ui::{
+		self,
+		style::{SharedTheme, Theme},
+		Size,
+	},
 	AsyncAppNotification, AsyncNotification,
 };
 use anyhow::{bail, Result};
 use asyncgit::{
-	sync::{self, utils::repo_work_dir, RepoPath, RepoPathRef},
-	AsyncGitNotification, PushType,
+	asyncjob::AsyncSingleJob,
+	job_errors::{self, JobError},
+	repo_stats::AsyncRepoStatsJob,
+	sync::{
+		self, diff::DiffOptions, utils::repo_work_dir, CommitId,
+		MaintenanceTask, RepoPath, RepoPathRef, UndoEntry,
+		UndoOperation,
+	},
+	AsyncGitNotification, AsyncHooks, PushType,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
 use std::{
 	cell::{Cell, RefCell},
-	path::Path,
+	path::{Path, PathBuf},
 	rc::Rc,
+	time::{Duration, Instant},
 };
 use tui::{
 	backend::Backend,
-	layout::{Constraint, Direction, Layout, Margin, Rect},
-	text::{Span, Spans},
-	widgets::{Block, Borders, Tabs},
+	layout::{
+		Alignment, Constraint, Direction, Layout, Margin, Rect,
+	},
+	text::Spans,
+	widgets::{Block, Borders, Paragraph, Tabs, Wrap},
 	Frame,
 };
 
+/// below this the regular layout does not fit anymore
+const MIN_SIZE: Size = Size::new(40, 10);
+/// rows the main area keeps when the command bar gets expanded
+const MIN_MAIN_HEIGHT: u16 = 4;
+
 #[derive(Clone)]
 pub enum QuitState {
 	None,
@@ -59,6 +90,18 @@ pub struct App {
 	do_quit: QuitState,
 	help: HelpComponent,
 	msg: MsgComponent,
+	toasts: ToastComponent,
+	task_list: TaskListComponent,
+	tutorial: TutorialComponent,
+	hooks: AsyncHooks,
+	repo_stats: AsyncSingleJob<AsyncRepoStatsJob>,
+	maintenance_popup: MaintenanceComponent,
+	/// asked once whether to rebuild an index status cannot read,
+	/// later failures get toasted like other job errors
+	index_recovery_offered: bool,
+	/// running an action confirmed to go ahead without undo snapshot
+	skip_undo_snapshot: bool,
+	pull_requests_popup: PullRequestsComponent,
 	reset: ConfirmComponent,
 	commit: CommitComponent,
 	blame_file_popup: BlameFileComponent,
@@ -66,6 +109,7 @@ pub struct App {
 	stashmsg_popup: StashMsgComponent,
 	inspect_commit_popup: InspectCommitComponent,
 	compare_commits_popup: CompareCommitsComponent,
+	branch_compare_popup: BranchCompareComponent,
 	external_editor_popup: ExternalEditorComponent,
 	revision_files_popup: RevisionFilesPopup,
 	find_file_popup: FileFindPopup,
@@ -74,19 +118,28 @@ pub struct App {
 	pull_popup: PullComponent,
 	fetch_popup: FetchComponent,
 	tag_commit_popup: TagCommitComponent,
+	edit_note_popup: EditNoteComponent,
 	create_branch_popup: CreateBranchComponent,
 	rename_branch_popup: RenameBranchComponent,
+	push_review_popup: PushReviewComponent,
+	push_options_popup: PushOptionsComponent,
 	select_branch_popup: BranchListComponent,
 	options_popup: OptionsPopupComponent,
+	select_list_popup: SelectListComponent,
+	number_input_popup: NumberInputComponent,
 	submodule_popup: SubmodulesListComponent,
 	tags_popup: TagListComponent,
 	cmdbar: RefCell<CommandBar>,
+	status_bar: StatusBarComponent,
 	tab: usize,
+	tabs: Vec<AppTab>,
+	tabs_warmup: Option<Duration>,
 	revlog: Revlog,
 	status_tab: Status,
 	stashing_tab: Stashing,
 	stashlist_tab: StashList,
 	files_tab: FilesTab,
+	search_tab: SearchTab,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
@@ -95,13 +148,20 @@ pub struct App {
 
 	// "Flags"
 	requires_redraw: Cell<bool>,
+	/// anything changed since the last `draw`
+	dirty: Cell<bool>,
 	file_to_open: Option<String>,
+	tool_to_open: Option<ExternalTool>,
+	suspend: bool,
+	last_input: Instant,
+	/// hidden tabs got warmed up since the last `update`
+	warmed_up: bool,
 }
 
 // public interface
 impl App {
 	///
-	#[allow(clippy::too_many_lines)]
+	#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 	pub fn new(
 		repo: RepoPathRef,
 		sender: &Sender<AsyncGitNotification>,
@@ -109,24 +169,43 @@ impl App {
 		input: Input,
 		theme: Theme,
 		key_config: KeyConfig,
+		tabs_config: TabsConfig,
+		secrets: SecretPaths,
 	) -> Self {
 		log::trace!("open repo at: {:?}", &repo);
 
 		let queue = Queue::new();
 		let theme = Rc::new(theme);
 		let key_config = Rc::new(key_config);
-		let options = SharedOptions::default();
-
-		Self {
+		let algorithm = sync::diff_algorithm_config(&repo.borrow())
+			.unwrap_or_default();
+		let status_order = sync::status_order_config(&repo.borrow())
+			.unwrap_or_default();
+		let options = Rc::new(RefCell::new(Options {
+			status_order,
+			diff: DiffOptions {
+				algorithm,
+				..DiffOptions::default()
+			},
+			secrets,
+			..Options::default()
+		}));
+		let commit_cache = SharedCommitCache::default();
+		let hooks = AsyncHooks::new(repo.borrow().clone(), sender);
+
+		let mut app = Self {
 			input,
 			reset: ConfirmComponent::new(
+				repo.clone(),
 				queue.clone(),
 				theme.clone(),
 				key_config.clone(),
+				ConfirmPrefs::load().unwrap_or_default(),
 			),
 			commit: CommitComponent::new(
 				repo.clone(),
 				queue.clone(),
+				sender,
 				theme.clone(),
 				key_config.clone(),
 			),
@@ -150,8 +229,10 @@ impl App {
 				repo.clone(),
 				&queue,
 				sender_app,
+				sender,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			stashmsg_popup: StashMsgComponent::new(
 				repo.clone(),
@@ -163,13 +244,23 @@ impl App {
 				&repo,
 				&queue,
 				sender,
+				&commit_cache,
 				theme.clone(),
 				key_config.clone(),
+				&options,
 			),
 			compare_commits_popup: CompareCommitsComponent::new(
 				&repo,
 				&queue,
 				sender,
+				&commit_cache,
+				theme.clone(),
+				key_config.clone(),
+				&options,
+			),
+			branch_compare_popup: BranchCompareComponent::new(
+				repo.clone(),
+				&queue,
 				theme.clone(),
 				key_config.clone(),
 			),
@@ -211,6 +302,12 @@ impl App {
 				theme.clone(),
 				key_config.clone(),
 			),
+			edit_note_popup: EditNoteComponent::new(
+				repo.clone(),
+				queue.clone(),
+				theme.clone(),
+				key_config.clone(),
+			),
 			create_branch_popup: CreateBranchComponent::new(
 				repo.clone(),
 				queue.clone(),
@@ -223,9 +320,21 @@ impl App {
 				theme.clone(),
 				key_config.clone(),
 			),
+			push_review_popup: PushReviewComponent::new(
+				repo.clone(),
+				queue.clone(),
+				theme.clone(),
+				key_config.clone(),
+			),
+			push_options_popup: PushOptionsComponent::new(
+				queue.clone(),
+				theme.clone(),
+				key_config.clone(),
+			),
 			select_branch_popup: BranchListComponent::new(
 				repo.clone(),
 				queue.clone(),
+				sender,
 				theme.clone(),
 				key_config.clone(),
 			),
@@ -242,6 +351,16 @@ impl App {
 				key_config.clone(),
 				options.clone(),
 			),
+			select_list_popup: SelectListComponent::new(
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
+			number_input_popup: NumberInputComponent::new(
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
 			submodule_popup: SubmodulesListComponent::new(
 				repo.clone(),
 				&queue,
@@ -254,22 +373,67 @@ impl App {
 				key_config.clone(),
 			),
 			do_quit: QuitState::None,
+			status_bar: StatusBarComponent::new(
+				repo.clone(),
+				theme.clone(),
+			),
 			cmdbar: RefCell::new(CommandBar::new(
 				theme.clone(),
 				key_config.clone(),
 			)),
 			help: HelpComponent::new(
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
+			msg: MsgComponent::new(
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
+			toasts: ToastComponent::new(
+				theme.clone(),
+				key_config.clone(),
+			),
+			task_list: TaskListComponent::new(
+				theme.clone(),
+				key_config.clone(),
+			),
+			tutorial: TutorialComponent::new(
+				&tabs_config.tabs,
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
+			hooks,
+			repo_stats: AsyncSingleJob::new(sender.clone()),
+			index_recovery_offered: false,
+			skip_undo_snapshot: false,
+			maintenance_popup: MaintenanceComponent::new(
+				repo.clone(),
+				&queue,
+				sender,
+				theme.clone(),
+				key_config.clone(),
+			),
+			pull_requests_popup: PullRequestsComponent::new(
+				repo.clone(),
+				&queue,
+				sender,
 				theme.clone(),
 				key_config.clone(),
 			),
-			msg: MsgComponent::new(theme.clone(), key_config.clone()),
 			tab: 0,
+			tabs: tabs_config.tabs,
+			tabs_warmup: tabs_config.warmup,
 			revlog: Revlog::new(
 				&repo,
 				&queue,
 				sender,
+				&commit_cache,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			status_tab: Status::new(
 				repo.clone(),
@@ -277,7 +441,7 @@ impl App {
 				sender,
 				theme.clone(),
 				key_config.clone(),
-				options,
+				options.clone(),
 			),
 			stashing_tab: Stashing::new(
 				&repo,
@@ -295,39 +459,80 @@ impl App {
 			files_tab: FilesTab::new(
 				repo.clone(),
 				sender_app,
+				sender,
 				&queue,
 				theme.clone(),
 				key_config.clone(),
+				options,
+			),
+			search_tab: SearchTab::new(
+				repo.clone(),
+				&queue,
+				sender,
+				theme.clone(),
+				key_config.clone(),
 			),
 			queue,
 			theme,
 			key_config,
 			requires_redraw: Cell::new(false),
+			dirty: Cell::new(true),
 			file_to_open: None,
+			tool_to_open: None,
+			suspend: false,
+			last_input: Instant::now(),
+			warmed_up: false,
 			repo,
 			popup_stack: PopupStack::default(),
+		};
+
+		if let Err(e) = app.set_tab(0) {
+			log::error!("initial tab error: {}", e);
 		}
+
+		app.suggest_gc();
+
+		if let Err(e) = app.tutorial.open_first_run() {
+			log::error!("tutorial error: {}", e);
+		}
+
+		app
 	}
 
 	///
 	pub fn draw<B: Backend>(&self, f: &mut Frame<B>) -> Result<()> {
 		let fsize = f.size();
 
+		if fsize.width < MIN_SIZE.width
+			|| fsize.height < MIN_SIZE.height
+		{
+			self.draw_too_small(f);
+			return Ok(());
+		}
+
 		self.cmdbar.borrow_mut().refresh_width(fsize.width);
 
+		// tabs, main area and status bar always stay visible
+		let cmdbar_height =
+			self.cmdbar.borrow().height().min(
+				fsize.height.saturating_sub(MIN_MAIN_HEIGHT + 3),
+			);
+
 		let chunks_main = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
 				[
 					Constraint::Length(2),
 					Constraint::Min(2),
-					Constraint::Length(self.cmdbar.borrow().height()),
+					Constraint::Length(1),
+					Constraint::Length(cmdbar_height),
 				]
 				.as_ref(),
 			)
 			.split(fsize);
 
-		self.cmdbar.borrow().draw(f, chunks_main[2]);
+		self.cmdbar.borrow().draw(f, chunks_main[3]);
+		self.status_bar.draw(f, chunks_main[2])?;
 
 		self.draw_tabs(f, chunks_main[0]);
 
@@ -337,21 +542,37 @@ impl App {
 			self.revision_files_popup.is_visible()
 				|| self.inspect_commit_popup.is_visible()
 				|| self.compare_commits_popup.is_visible()
+				|| self.branch_compare_popup.is_visible()
 				|| self.blame_file_popup.is_visible()
 				|| self.file_revlog_popup.is_visible();
 
 		if !fullscreen_popup_open {
 			//TODO: macro because of generic draw call
-			match self.tab {
-				0 => self.status_tab.draw(f, chunks_main[1])?,
-				1 => self.revlog.draw(f, chunks_main[1])?,
-				2 => self.files_tab.draw(f, chunks_main[1])?,
-				3 => self.stashing_tab.draw(f, chunks_main[1])?,
-				4 => self.stashlist_tab.draw(f, chunks_main[1])?,
-				_ => bail!("unknown tab"),
+			match self.tabs.get(self.tab) {
+				Some(AppTab::Status) => {
+					self.status_tab.draw(f, chunks_main[1])?;
+				}
+				Some(AppTab::Log) => {
+					self.revlog.draw(f, chunks_main[1])?;
+				}
+				Some(AppTab::Files) => {
+					self.files_tab.draw(f, chunks_main[1])?;
+				}
+				Some(AppTab::Stashing) => {
+					self.stashing_tab.draw(f, chunks_main[1])?;
+				}
+				Some(AppTab::Stashes) => {
+					self.stashlist_tab.draw(f, chunks_main[1])?;
+				}
+				Some(AppTab::Search) => {
+					self.search_tab.draw(f, chunks_main[1])?;
+				}
+				None => bail!("unknown tab"),
 			};
 		}
 
+		self.toasts.draw_toasts(f, chunks_main[1]);
+
 		self.draw_popups(f)?;
 
 		Ok(())
@@ -361,7 +582,11 @@ impl App {
 	pub fn event(&mut self, ev: InputEvent) -> Result<()> {
 		log::trace!("event: {:?}", ev);
 
+		self.dirty.set(true);
+
 		if let InputEvent::Input(ev) = ev {
+			self.last_input = Instant::now();
+
 			if self.check_hard_exit(&ev) || self.check_quit(&ev) {
 				return Ok(());
 			}
@@ -373,76 +598,50 @@ impl App {
 			{
 				flags.insert(NeedsUpdate::COMMANDS);
 			} else if let Event::Key(k) = &ev {
-				let new_flags = if key_match(
-					k,
-					self.key_config.keys.tab_toggle,
-				) {
-					self.toggle_tabs(false)?;
-					NeedsUpdate::COMMANDS
-				} else if key_match(
-					k,
-					self.key_config.keys.tab_toggle_reverse,
-				) {
-					self.toggle_tabs(true)?;
-					NeedsUpdate::COMMANDS
-				} else if key_match(
-					k,
-					self.key_config.keys.tab_status,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_log,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_files,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_stashing,
-				) || key_match(
-					k,
-					self.key_config.keys.tab_stashes,
-				) {
-					self.switch_tab(k)?;
-					NeedsUpdate::COMMANDS
-				} else if key_match(
-					k,
-					self.key_config.keys.cmd_bar_toggle,
-				) {
-					self.cmdbar.borrow_mut().toggle_more();
-					NeedsUpdate::empty()
-				} else if key_match(
-					k,
-					self.key_config.keys.open_options,
-				) {
-					self.options_popup.show()?;
-					NeedsUpdate::ALL
-				} else {
-					NeedsUpdate::empty()
-				};
-
-				flags.insert(new_flags);
+				flags.insert(self.check_global_keys(k)?);
 			}
 
 			self.process_queue(flags)?;
 		} else if let InputEvent::State(polling_state) = ev {
 			self.external_editor_popup.hide();
 			if let InputState::Paused = polling_state {
-				let result = match self.file_to_open.take() {
-					Some(path) => {
+				let head_before =
+					sync::get_head(&self.repo.borrow()).ok();
+
+				let result = if std::mem::take(&mut self.suspend) {
+					ExternalEditorComponent::suspend()
+				} else {
+					match (
+						self.tool_to_open.take(),
+						self.file_to_open.take(),
+					) {
+					(Some(tool), _) => {
+						ExternalEditorComponent::open_tool(
+							&self.repo.borrow(),
+							&tool,
+						)
+					}
+					(None, Some(path)) => {
 						ExternalEditorComponent::open_file_in_editor(
 							&self.repo.borrow(),
 							Path::new(&path),
 						)
 					}
-					None => self.commit.show_editor(),
+					(None, None) => self.commit.show_editor(),
+					}
 				};
 
 				if let Err(e) = result {
-					let msg =
-						format!("failed to launch editor:\n{}", e);
+					let msg = format!(
+						"failed to launch external tool:\n{}",
+						e
+					);
 					log::error!("{}", msg.as_str());
 					self.msg.show_error(msg.as_str())?;
 				}
 
+				self.refresh_after_external(head_before)?;
+
 				self.requires_redraw.set(true);
 				self.input.set_polling(true);
 			}
@@ -451,17 +650,39 @@ impl App {
 		Ok(())
 	}
 
+	/// external programs may have changed anything in the repo,
+	/// refresh right away instead of waiting for the watcher
+	fn refresh_after_external(
+		&mut self,
+		head_before: Option<CommitId>,
+	) -> Result<()> {
+		if sync::get_head(&self.repo.borrow()).ok() != head_before {
+			self.toasts.push(
+				ToastKind::Success,
+				strings::HEAD_CHANGED_EXTERNALLY.to_string(),
+			);
+		}
+
+		self.select_branch_popup.update_branches()?;
+		self.update()
+	}
+
 	//TODO: do we need this?
 	/// forward ticking to components that require it
 	pub fn update(&mut self) -> Result<()> {
 		log::trace!("update");
 
+		self.dirty.set(true);
+		self.warmed_up = false;
+
 		self.commit.update();
 		self.status_tab.update()?;
 		self.revlog.update()?;
 		self.files_tab.update()?;
 		self.stashing_tab.update()?;
 		self.stashlist_tab.update()?;
+		self.status_bar.update();
+		self.status_bar.set_summary(self.status_tab.summary());
 
 		self.update_commands();
 
@@ -475,9 +696,41 @@ impl App {
 	) -> Result<()> {
 		log::trace!("update_async: {:?}", ev);
 
+		self.dirty.set(true);
+
+		if let AsyncNotification::Git(AsyncGitNotification::Error(
+			id,
+		)) = ev
+		{
+			if let Some(error) = job_errors::take(id) {
+				if !self.offer_index_recovery(&error) {
+					self.toasts
+						.push(ToastKind::Warning, error.to_string());
+				}
+			}
+			return Ok(());
+		}
+
 		if let AsyncNotification::Git(ev) = ev {
+			match ev {
+				AsyncGitNotification::Status => {
+					startup::mark("status loaded");
+				}
+				AsyncGitNotification::Log => {
+					startup::mark("log loaded");
+				}
+				AsyncGitNotification::Hooks => {
+					self.hooks_finished()?;
+				}
+				AsyncGitNotification::RepoStats => {
+					self.repo_stats_finished();
+				}
+				_ => (),
+			}
+
 			self.status_tab.update_git(ev)?;
 			self.stashing_tab.update_git(ev)?;
+			self.search_tab.update_git(ev)?;
 			self.revlog.update_git(ev)?;
 			self.blame_file_popup.update_git(ev)?;
 			self.file_revlog_popup.update_git(ev)?;
@@ -487,12 +740,16 @@ impl App {
 			self.push_tags_popup.update_git(ev)?;
 			self.pull_popup.update_git(ev);
 			self.fetch_popup.update_git(ev);
+			self.maintenance_popup.update_git(ev);
 			self.select_branch_popup.update_git(ev)?;
+			self.status_bar.set_summary(self.status_tab.summary());
 		}
 
 		self.files_tab.update_async(ev);
 		self.revision_files_popup.update(ev);
 		self.tags_popup.update(ev);
+		self.pull_requests_popup.update(ev);
+		self.commit.update_async(ev);
 
 		//TODO: better system for this
 		// can we simply process the queue here and everyone just uses the queue to schedule a cmd update?
@@ -501,12 +758,34 @@ impl App {
 		Ok(())
 	}
 
+	/// problems found in the config files on startup
+	pub fn show_config_issues(
+		&mut self,
+		issues: &[ConfigIssue],
+	) -> Result<()> {
+		self.msg.show_error(&strings::config_issues_msg(issues))
+	}
+
 	///
 	pub fn is_quit(&self) -> bool {
 		!matches!(self.do_quit, QuitState::None)
 			|| self.input.is_aborted()
 	}
 
+	/// periodic refresh of time based ui (toasts, task list),
+	/// marks the app dirty if that changed anything
+	pub fn update_tick(&mut self) {
+		let toasts = self.toasts.expire();
+		let tasks = self.task_list.update();
+		if toasts || tasks {
+			self.dirty.set(true);
+		}
+
+		if let Err(e) = self.warmup_tabs() {
+			log::error!("tab warmup error: {}", e);
+		}
+	}
+
 	///
 	pub fn quit_state(&self) -> QuitState {
 		self.do_quit.clone()
@@ -518,6 +797,7 @@ impl App {
 			|| self.revlog.any_work_pending()
 			|| self.stashing_tab.anything_pending()
 			|| self.files_tab.anything_pending()
+			|| self.search_tab.anything_pending()
 			|| self.blame_file_popup.any_work_pending()
 			|| self.file_revlog_popup.any_work_pending()
 			|| self.inspect_commit_popup.any_work_pending()
@@ -527,8 +807,19 @@ impl App {
 			|| self.push_tags_popup.any_work_pending()
 			|| self.pull_popup.any_work_pending()
 			|| self.fetch_popup.any_work_pending()
+			|| self.maintenance_popup.any_work_pending()
+			|| self.pull_requests_popup.any_work_pending()
+			|| self.commit.any_work_pending()
 			|| self.revision_files_popup.any_work_pending()
 			|| self.tags_popup.any_work_pending()
+			|| self.select_branch_popup.any_work_pending()
+			|| self.hooks.is_pending()
+	}
+
+	/// whether anything changed since the last call, drawing is
+	/// skipped otherwise
+	pub fn take_dirty(&self) -> bool {
+		self.dirty.replace(false)
 	}
 
 	///
@@ -549,21 +840,32 @@ impl App {
 		[
 			find_file_popup,
 			msg,
+			tutorial,
+			toasts,
+			select_list_popup,
+			number_input_popup,
+			task_list,
+			pull_requests_popup,
 			reset,
 			commit,
+			tag_commit_popup,
+			create_branch_popup,
 			blame_file_popup,
 			file_revlog_popup,
 			stashmsg_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
+			branch_compare_popup,
 			external_editor_popup,
 			push_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
-			tag_commit_popup,
-			create_branch_popup,
+			maintenance_popup,
+			edit_note_popup,
 			rename_branch_popup,
+			push_review_popup,
+			push_options_popup,
 			select_branch_popup,
 			revision_files_popup,
 			submodule_popup,
@@ -574,7 +876,8 @@ impl App {
 			status_tab,
 			files_tab,
 			stashing_tab,
-			stashlist_tab
+			stashlist_tab,
+			search_tab
 		]
 	);
 
@@ -586,29 +889,307 @@ impl App {
 			help,
 			inspect_commit_popup,
 			compare_commits_popup,
+			branch_compare_popup,
 			blame_file_popup,
 			file_revlog_popup,
 			external_editor_popup,
 			tag_commit_popup,
+			edit_note_popup,
 			select_branch_popup,
 			submodule_popup,
 			tags_popup,
 			create_branch_popup,
 			rename_branch_popup,
+			push_review_popup,
+			push_options_popup,
 			revision_files_popup,
 			find_file_popup,
 			push_popup,
 			push_tags_popup,
 			pull_popup,
 			fetch_popup,
+			maintenance_popup,
 			options_popup,
+			select_list_popup,
+			number_input_popup,
 			reset,
+			pull_requests_popup,
+			task_list,
+			tutorial,
+			toasts,
 			msg
 		]
 	);
 
+	/// keys handled on app level when no component consumed them
+	fn check_global_keys(
+		&mut self,
+		k: &KeyEvent,
+	) -> Result<NeedsUpdate> {
+		let flags = if key_match(k, self.key_config.keys.tab_toggle) {
+			self.toggle_tabs(false)?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(
+			k,
+			self.key_config.keys.tab_toggle_reverse,
+		) {
+			self.toggle_tabs(true)?;
+			NeedsUpdate::COMMANDS
+		} else if let Some(tab) = self.tab_for_key(k) {
+			self.set_tab(tab)?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.cmd_bar_toggle) {
+			self.cmdbar.borrow_mut().toggle_more();
+			NeedsUpdate::empty()
+		} else if key_match(k, self.key_config.keys.open_options) {
+			self.options_popup.show()?;
+			NeedsUpdate::ALL
+		} else if key_match(
+			k,
+			self.key_config.keys.open_notifications,
+		) {
+			self.toasts.show()?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.open_tasks) {
+			self.task_list.show()?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.undo) {
+			self.undo()?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.open_discarded) {
+			self.open_discarded()?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.open_action_log) {
+			self.open_action_log()?;
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.open_repo_stats) {
+			self.open_repo_stats();
+			NeedsUpdate::empty()
+		} else if key_match(k, self.key_config.keys.open_maintenance)
+		{
+			self.open_maintenance();
+			NeedsUpdate::COMMANDS
+		} else if key_match(k, self.key_config.keys.open_shell) {
+			self.queue.push(InternalEvent::OpenExternalTool(
+				ExternalTool::Shell {
+					path: PathBuf::new(),
+				},
+			));
+			NeedsUpdate::empty()
+		} else if cfg!(unix)
+			&& key_match(k, self.key_config.keys.suspend)
+		{
+			self.suspend = true;
+			self.input.set_polling(false);
+			NeedsUpdate::empty()
+		} else if cfg!(feature = "github")
+			&& key_match(k, self.key_config.keys.open_pull_requests)
+		{
+			self.pull_requests_popup.show()?;
+			NeedsUpdate::COMMANDS
+		} else {
+			NeedsUpdate::empty()
+		};
+
+		Ok(flags)
+	}
+
+	fn undo(&self) -> Result<()> {
+		if let Some(entry) =
+			sync::undo_last_entry(&self.repo.borrow())?
+		{
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::Undo(entry),
+			));
+		} else {
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				strings::UNDO_NOTHING.to_string(),
+			));
+		}
+
+		Ok(())
+	}
+
+	fn open_discarded(&self) -> Result<()> {
+		let items = discarded(&self.repo.borrow())?
+			.iter()
+			.map(|entry| {
+				strings::discarded_item(
+					&time_to_string(entry.time, false),
+					entry.discarded_path().unwrap_or_default(),
+					entry.files.len(),
+				)
+			})
+			.collect::<Vec<_>>();
+
+		if items.is_empty() {
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				strings::DISCARDED_EMPTY.to_string(),
+			));
+		} else {
+			self.queue.push(InternalEvent::OpenSelectList(
+				SelectListOpen {
+					target: PickTarget::Discarded,
+					title: strings::DISCARDED_TITLE.to_string(),
+					items,
+					selected: 0,
+				},
+			));
+		}
+
+		Ok(())
+	}
+
+	fn open_action_log(&self) -> Result<()> {
+		let items = action_log_items(&self.repo.borrow())?;
+
+		if items.is_empty() {
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				strings::ACTION_LOG_EMPTY.to_string(),
+			));
+		} else {
+			self.queue.push(InternalEvent::OpenSelectList(
+				SelectListOpen {
+					target: PickTarget::ActionLog,
+					title: strings::ACTION_LOG_TITLE.to_string(),
+					items,
+					selected: 0,
+				},
+			));
+		}
+
+		Ok(())
+	}
+
+	/// warns about more loose objects than `gc.auto` allows
+	fn suggest_gc(&self) {
+		let repo = self.repo.borrow();
+		let loose_objects =
+			sync::repo_stats::count_loose_objects(&repo)
+				.unwrap_or_default();
+
+		if sync::gc_suggested(&repo, loose_objects)
+			.unwrap_or_default()
+		{
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				strings::gc_suggested_toast(
+					loose_objects,
+					&self.key_config,
+				),
+			));
+		}
+	}
+
+	/// shows the statistics once the background job gathered them
+	fn open_repo_stats(&mut self) {
+		if !self.repo_stats.is_pending() {
+			self.repo_stats.spawn(AsyncRepoStatsJob::new(
+				self.repo.borrow().clone(),
+			));
+		}
+
+		self.queue.push(InternalEvent::ShowToast(
+			ToastKind::Success,
+			strings::TOAST_REPO_STATS.to_string(),
+		));
+	}
+
+	fn repo_stats_finished(&self) {
+		let stats =
+			self.repo_stats.take_last().and_then(|job| job.result());
+
+		match stats {
+			Some(Ok(stats)) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::repo_stats_msg(&stats),
+				));
+			}
+			Some(Err(e)) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("repository statistics failed:\n{}", e),
+				));
+			}
+			None => (),
+		}
+	}
+
+	/// asks to rebuild the index if it is why status failed, returns
+	/// whether it did
+	fn offer_index_recovery(&mut self, error: &JobError) -> bool {
+		if error.kind != "status" || self.index_recovery_offered {
+			return false;
+		}
+
+		match sync::index_problem(&self.repo.borrow()) {
+			Ok(Some(problem)) => {
+				self.index_recovery_offered = true;
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::RebuildIndex(problem),
+				));
+				true
+			}
+			_ => false,
+		}
+	}
+
+	fn open_maintenance(&self) {
+		self.queue.push(InternalEvent::OpenSelectList(
+			SelectListOpen {
+				target: PickTarget::Maintenance,
+				title: strings::MAINTENANCE_TITLE.to_string(),
+				items: MaintenanceTask::ALL
+					.iter()
+					.map(|task| {
+						strings::maintenance_task(*task).to_string()
+					})
+					.collect(),
+				selected: 0,
+			},
+		));
+	}
+
+	/// copies a picked line, toasting `copied` or the error
+	fn copy_item(&self, item: &str, copied: &str) {
+		self.queue.push(match crate::clipboard::copy_string(item) {
+			Ok(()) => InternalEvent::ShowToast(
+				ToastKind::Success,
+				copied.to_string(),
+			),
+			Err(e) => InternalEvent::ShowToast(
+				ToastKind::Warning,
+				format!("copy to clipboard error: {}", e),
+			),
+		});
+	}
+
+	fn open_ignored_files(&self) -> Result<()> {
+		let items = ignored_file_items(&self.repo.borrow())?;
+
+		if items.is_empty() {
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				strings::IGNORED_FILES_EMPTY.to_string(),
+			));
+		} else {
+			self.queue.push(InternalEvent::OpenSelectList(
+				SelectListOpen {
+					target: PickTarget::IgnoredFiles,
+					title: strings::IGNORED_FILES_TITLE.to_string(),
+					items,
+					selected: 0,
+				},
+			));
+		}
+
+		Ok(())
+	}
+
 	fn check_quit(&mut self, ev: &Event) -> bool {
-		if self.any_popup_visible() {
+		if self.any_popup_visible() || self.search_tab.is_typing() {
 			return false;
 		}
 		if let Event::Key(e) = ev {
@@ -630,18 +1211,51 @@ impl App {
 		false
 	}
 
-	fn get_tabs(&mut self) -> Vec<&mut dyn Component> {
-		vec![
-			&mut self.status_tab,
-			&mut self.revlog,
-			&mut self.files_tab,
-			&mut self.stashing_tab,
-			&mut self.stashlist_tab,
-		]
+	fn tab_component(&mut self, tab: AppTab) -> &mut dyn Component {
+		match tab {
+			AppTab::Status => &mut self.status_tab,
+			AppTab::Log => &mut self.revlog,
+			AppTab::Files => &mut self.files_tab,
+			AppTab::Stashing => &mut self.stashing_tab,
+			AppTab::Stashes => &mut self.stashlist_tab,
+			AppTab::Search => &mut self.search_tab,
+		}
+	}
+
+	fn tab_key(&self, tab: AppTab) -> GituiKeyEvent {
+		let keys = &self.key_config.keys;
+		match tab {
+			AppTab::Status => keys.tab_status,
+			AppTab::Log => keys.tab_log,
+			AppTab::Files => keys.tab_files,
+			AppTab::Stashing => keys.tab_stashing,
+			AppTab::Stashes => keys.tab_stashes,
+			AppTab::Search => keys.tab_search,
+		}
+	}
+
+	fn tab_title(&self, tab: AppTab) -> String {
+		match tab {
+			AppTab::Status => strings::tab_status(&self.key_config),
+			AppTab::Log => strings::tab_log(&self.key_config),
+			AppTab::Files => strings::tab_files(&self.key_config),
+			AppTab::Stashing => {
+				strings::tab_stashing(&self.key_config)
+			}
+			AppTab::Stashes => strings::tab_stashes(&self.key_config),
+			AppTab::Search => strings::tab_search(&self.key_config),
+		}
+	}
+
+	/// position of the configured tab whose switch key is `k`
+	fn tab_for_key(&self, k: &KeyEvent) -> Option<usize> {
+		self.tabs
+			.iter()
+			.position(|tab| key_match(k, self.tab_key(*tab)))
 	}
 
 	fn toggle_tabs(&mut self, reverse: bool) -> Result<()> {
-		let tabs_len = self.get_tabs().len();
+		let tabs_len = self.tabs.len();
 		let new_tab = if reverse {
 			self.tab.wrapping_sub(1).min(tabs_len.saturating_sub(1))
 		} else {
@@ -651,29 +1265,81 @@ impl App {
 		self.set_tab(new_tab)
 	}
 
-	fn switch_tab(&mut self, k: &KeyEvent) -> Result<()> {
-		if key_match(k, self.key_config.keys.tab_status) {
-			self.set_tab(0)?;
-		} else if key_match(k, self.key_config.keys.tab_log) {
-			self.set_tab(1)?;
-		} else if key_match(k, self.key_config.keys.tab_files) {
-			self.set_tab(2)?;
-		} else if key_match(k, self.key_config.keys.tab_stashing) {
-			self.set_tab(3)?;
-		} else if key_match(k, self.key_config.keys.tab_stashes) {
-			self.set_tab(4)?;
+	/// selects `path` in the files tab, or in the file tree
+	/// of HEAD if that tab is not configured
+	fn jump_to_file(&mut self, path: String) -> Result<()> {
+		// the trees list paths relative to the root as `./path`
+		let file = Some(Path::new(".").join(path));
+
+		if let Some(tab) =
+			self.tabs.iter().position(|t| *t == AppTab::Files)
+		{
+			self.set_tab(tab)?;
+			self.files_tab.file_finder_update(&file);
+		} else {
+			let head = sync::get_head(&self.repo.borrow())?;
+			self.revision_files_popup
+				.open(FileTreeOpen::new(head))?;
+			self.revision_files_popup.file_finder_update(&file);
+		}
+
+		Ok(())
+	}
+
+	/// once the user is idle for `tabs_warmup` (see `tabs.ron`) the
+	/// hidden tabs start loading their data in the background,
+	/// otherwise tabs only start their jobs when first shown
+	fn warmup_tabs(&mut self) -> Result<()> {
+		let idle = self.tabs_warmup.map_or(false, |warmup| {
+			self.last_input.elapsed() >= warmup
+		});
+
+		if self.warmed_up || !idle || self.any_work_pending() {
+			return Ok(());
+		}
+
+		self.warmed_up = true;
+
+		// the other tabs are either cheap or load synchronously
+		if self.tabs.contains(&AppTab::Log) {
+			self.revlog.warmup()?;
+		}
+
+		Ok(())
+	}
+
+	/// hook output ends up in the notification log,
+	/// silent hooks that succeeded are not worth a toast
+	fn hooks_finished(&mut self) -> Result<()> {
+		for hook in self.hooks.take_finished()? {
+			let output = hook.output.trim();
+
+			if hook.success {
+				if !output.is_empty() {
+					log::info!("{} hook: {}", hook.hook, output);
+					self.toasts.push(
+						ToastKind::Success,
+						format!("{} hook: {}", hook.hook, output),
+					);
+				}
+			} else {
+				log::error!("{} hook error: {}", hook.hook, output);
+				self.toasts.push(
+					ToastKind::Warning,
+					format!("{} hook error: {}", hook.hook, output),
+				);
+			}
 		}
 
 		Ok(())
 	}
 
 	fn set_tab(&mut self, tab: usize) -> Result<()> {
-		let tabs = self.get_tabs();
-		for (i, t) in tabs.into_iter().enumerate() {
+		for (i, t) in self.tabs.clone().into_iter().enumerate() {
 			if tab == i {
-				t.show()?;
+				self.tab_component(t).show()?;
 			} else {
-				t.hide();
+				self.tab_component(t).hide();
 			}
 		}
 
@@ -683,10 +1349,13 @@ impl App {
 	}
 
 	fn update_commands(&mut self) {
+		let cmds = self.commands(false);
 		if self.help.is_visible() {
 			self.help.set_cmds(self.commands(true));
+		} else {
+			self.help.set_context_cmds(cmds.clone());
 		}
-		self.cmdbar.borrow_mut().set_cmds(self.commands(false));
+		self.cmdbar.borrow_mut().set_cmds(cmds);
 	}
 
 	fn process_queue(&mut self, flags: NeedsUpdate) -> Result<()> {
@@ -735,6 +1404,9 @@ impl App {
 			StackablePopupOpen::CompareCommits(param) => {
 				self.compare_commits_popup.open(param)?;
 			}
+			StackablePopupOpen::CompareBranches(param) => {
+				self.branch_compare_popup.open(param)?;
+			}
 		}
 
 		Ok(())
@@ -767,7 +1439,8 @@ impl App {
 				self.process_confirmed_action(action, &mut flags)?;
 			}
 			InternalEvent::ConfirmAction(action) => {
-				self.reset.open(action)?;
+				self.reset
+					.open(action, &self.status_tab.changed_files())?;
 				flags.insert(NeedsUpdate::COMMANDS);
 			}
 			InternalEvent::ShowErrorMsg(msg) => {
@@ -780,8 +1453,22 @@ impl App {
 				flags
 					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::ShowPushSummary(summary) => {
+				self.msg.show_push_summary(&summary)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::ShowToast(kind, msg) => {
+				self.toasts.push(kind, msg);
+			}
+			InternalEvent::RunPostHook(hook) => {
+				self.hooks.run(hook);
+			}
 			InternalEvent::Update(u) => flags.insert(u),
 			InternalEvent::OpenCommit => self.commit.show()?,
+			InternalEvent::OpenCommitPaths(paths) => {
+				self.commit.open_paths(paths)?;
+			}
 			InternalEvent::PopupStashing(opts) => {
 				self.stashmsg_popup.options(opts);
 				self.stashmsg_popup.show()?;
@@ -789,10 +1476,19 @@ impl App {
 			InternalEvent::TagCommit(id) => {
 				self.tag_commit_popup.open(id)?;
 			}
+			InternalEvent::EditNote(id) => {
+				self.edit_note_popup.open(id)?;
+			}
 
 			InternalEvent::CreateBranch => {
 				self.create_branch_popup.open()?;
 			}
+			InternalEvent::CreateOrphanBranch => {
+				self.create_branch_popup.open_orphan()?;
+			}
+			InternalEvent::CreateBranchAt(id) => {
+				self.create_branch_popup.open_at(id)?;
+			}
 			InternalEvent::RenameBranch(branch_ref, cur_name) => {
 				self.rename_branch_popup
 					.open(branch_ref, cur_name)?;
@@ -803,10 +1499,39 @@ impl App {
 			InternalEvent::ViewSubmodules => {
 				self.submodule_popup.open()?;
 			}
+			InternalEvent::ViewIgnored => {
+				self.open_ignored_files()?;
+			}
 			InternalEvent::Tags => {
 				self.tags_popup.open()?;
 			}
-			InternalEvent::TabSwitchStatus => self.set_tab(0)?,
+			InternalEvent::TabSwitchStatus => {
+				if let Some(tab) = self
+					.tabs
+					.iter()
+					.position(|t| *t == AppTab::Status)
+				{
+					self.select_branch_popup.hide();
+					self.set_tab(tab)?;
+				}
+			}
+			InternalEvent::TabSwitch(tab) => {
+				if let Some(tab) =
+					self.tabs.iter().position(|t| *t == tab)
+				{
+					self.set_tab(tab)?;
+				}
+			}
+			InternalEvent::TabSwitchStashes => {
+				if let Some(tab) = self
+					.tabs
+					.iter()
+					.position(|t| *t == AppTab::Stashes)
+				{
+					self.select_branch_popup.hide();
+					self.set_tab(tab)?;
+				}
+			}
 			InternalEvent::SelectCommitInRevlog(id) => {
 				if let Err(error) = self.revlog.select_commit(id) {
 					self.queue.push(InternalEvent::ShowErrorMsg(
@@ -823,11 +1548,35 @@ impl App {
 				self.file_to_open = path;
 				flags.insert(NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::OpenExternalTool(tool) => {
+				self.input.set_polling(false);
+				self.external_editor_popup.show()?;
+				self.tool_to_open = Some(tool);
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::Push(branch, push_type, force, delete) => {
 				self.push_popup
 					.push(branch, push_type, force, delete)?;
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::OpenPushReview(branch) => {
+				self.push_review_popup.open(branch)?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::PushReview(branch, refspec) => {
+				self.push_review_popup.hide();
+				self.push_popup.push_review(branch, refspec)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenPushOptions(branch) => {
+				self.push_options_popup.open(branch)?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::PushWithOptions(branch, options) => {
+				self.push_options_popup.hide();
+				self.push_popup.push_with_options(branch, options)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
 			InternalEvent::Pull(branch) => {
 				if let Err(error) = self.pull_popup.fetch(branch) {
 					self.queue.push(InternalEvent::ShowErrorMsg(
@@ -861,21 +1610,88 @@ impl App {
 					AppOption::StatusShowUntracked => {
 						self.status_tab.update()?;
 					}
+					AppOption::StatusSort
+					| AppOption::StatusGroup => {
+						self.status_tab.order_switched()?;
+					}
 					AppOption::DiffContextLines
 					| AppOption::DiffIgnoreWhitespaces
-					| AppOption::DiffInterhunkLines => {
+					| AppOption::DiffInterhunkLines
+					| AppOption::DiffLineEndings
+					| AppOption::DiffAlgorithm => {
 						self.status_tab.update_diff()?;
 					}
+					AppOption::LogShowAvatars => {}
 				}
 
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::OpenSelectList(open) => {
+				self.select_list_popup.open(open)?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenNumberInput(open) => {
+				self.number_input_popup.open(open)?;
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::Picked(target, value) => match target {
+				PickTarget::Option(option) => {
+					self.options_popup.picked(option, value);
+				}
+				PickTarget::Discarded => {
+					if let Some(entry) =
+						discarded(&self.repo.borrow())?.get(value)
+					{
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::RestoreDiscarded(
+									entry.clone(),
+								),
+							),
+						);
+					}
+				}
+				PickTarget::ActionLog => {
+					if let Some(item) =
+						action_log_items(&self.repo.borrow())?
+							.get(value)
+					{
+						self.copy_item(
+							item,
+							strings::ACTION_LOG_COPIED,
+						);
+					}
+				}
+				PickTarget::IgnoredFiles => {
+					if let Some(item) =
+						ignored_file_items(&self.repo.borrow())?
+							.get(value)
+					{
+						self.copy_item(
+							item,
+							strings::IGNORED_FILES_COPIED,
+						);
+					}
+				}
+				PickTarget::Maintenance => {
+					if let Some(task) =
+						MaintenanceTask::ALL.get(value)
+					{
+						self.maintenance_popup.run(*task)?;
+					}
+				}
+			},
 			InternalEvent::FileFinderChanged(file) => {
 				self.files_tab.file_finder_update(&file);
 				self.revision_files_popup.file_finder_update(&file);
 				flags
 					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::JumpToFile(path) => {
+				self.jump_to_file(path)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::OpenPopup(popup) => {
 				self.open_popup(popup)?;
 				flags
@@ -908,6 +1724,29 @@ impl App {
 		Ok(flags)
 	}
 
+	/// saves what `operation` changes so it can be undone, the error
+	/// message if that failed
+	fn undo_snapshot(
+		&self,
+		operation: &UndoOperation,
+	) -> std::result::Result<(), String> {
+		if self.skip_undo_snapshot {
+			return Ok(());
+		}
+
+		sync::undo_snapshot(&self.repo.borrow(), operation)
+			.map(|_| ())
+			.map_err(|e| e.to_string())
+	}
+
+	/// asks whether to run `action` without the undo snapshot that
+	/// failed with `error`
+	fn confirm_without_undo(&self, action: Action, error: String) {
+		self.queue.push(InternalEvent::ConfirmAction(
+			Action::WithoutUndo(Box::new(action), error),
+		));
+	}
+
 	#[allow(clippy::too_many_lines)]
 	fn process_confirmed_action(
 		&mut self,
@@ -916,7 +1755,23 @@ impl App {
 	) -> Result<()> {
 		match action {
 			Action::Reset(r) => {
-				if self.status_tab.reset(&r) {
+				if let Err(e) = self.undo_snapshot(
+					&UndoOperation::Discard(r.path.clone()),
+				) {
+					self.confirm_without_undo(Action::Reset(r), e);
+				} else if self.status_tab.reset(&r) {
+					flags.insert(NeedsUpdate::ALL);
+				}
+			}
+			Action::ResetMarked(paths) => {
+				if let Err(e) = self.undo_snapshot(
+					&UndoOperation::DiscardMarked(paths.clone()),
+				) {
+					self.confirm_without_undo(
+						Action::ResetMarked(paths),
+						e,
+					);
+				} else if self.status_tab.reset_marked(&paths) {
 					flags.insert(NeedsUpdate::ALL);
 				}
 			}
@@ -933,10 +1788,28 @@ impl App {
 				flags.insert(NeedsUpdate::ALL);
 			}
 			Action::ResetHunk(path, hash) => {
+				if let Err(e) = self.undo_snapshot(
+					&UndoOperation::Discard(path.clone()),
+				) {
+					self.confirm_without_undo(
+						Action::ResetHunk(path, hash),
+						e,
+					);
+					return Ok(());
+				}
 				sync::reset_hunk(&self.repo.borrow(), &path, hash)?;
 				flags.insert(NeedsUpdate::ALL);
 			}
 			Action::ResetLines(path, lines) => {
+				if let Err(e) = self.undo_snapshot(
+					&UndoOperation::Discard(path.clone()),
+				) {
+					self.confirm_without_undo(
+						Action::ResetLines(path, lines),
+						e,
+					);
+					return Ok(());
+				}
 				sync::discard_lines(
 					&self.repo.borrow(),
 					&path,
@@ -945,6 +1818,18 @@ impl App {
 				flags.insert(NeedsUpdate::ALL);
 			}
 			Action::DeleteLocalBranch(branch_ref) => {
+				if let Err(e) =
+					self.undo_snapshot(&UndoOperation::DeleteBranch(
+						branch_ref
+							.trim_start_matches("refs/heads/")
+							.to_string(),
+					)) {
+					self.confirm_without_undo(
+						Action::DeleteLocalBranch(branch_ref),
+						e,
+					);
+					return Ok(());
+				}
 				if let Err(e) = sync::delete_branch(
 					&self.repo.borrow(),
 					&branch_ref,
@@ -1007,13 +1892,17 @@ impl App {
 					true,
 				));
 			}
-			Action::ForcePush(branch, force) => {
-				self.queue.push(InternalEvent::Push(
-					branch,
-					PushType::Branch,
-					force,
-					false,
-				));
+			Action::ForcePush(..) => {
+				self.push_popup.force_push_confirmed()?;
+			}
+			Action::RenameRemoteBranch { branch, old, .. } => {
+				self.push_popup.rename_remote_branch(branch, old)?;
+			}
+			Action::PushPreviewed(_) => {
+				self.push_popup.push_previewed()?;
+			}
+			Action::FetchPreviewed(_) => {
+				self.fetch_popup.fetch_previewed()?;
 			}
 			Action::PullMerge { rebase, .. } => {
 				self.pull_popup.try_conflict_free_merge(rebase);
@@ -1027,6 +1916,110 @@ impl App {
 				self.status_tab.abort_rebase();
 				flags.insert(NeedsUpdate::ALL);
 			}
+			Action::StoreCredentials(remote, cred) => {
+				// a missing helper must not take the app down
+				if let Err(e) = sync::cred::store_username_password(
+					&self.repo.borrow(),
+					&remote,
+					&cred,
+				) {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("storing credentials failed:\n{}", e),
+					));
+				}
+			}
+			Action::CommitProtected(_) => {
+				if let Err(e) = self.commit.commit_confirmed() {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("commit error:\n{}", e),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::UndoCommitProtected(_) => {
+				self.status_tab.undo_last_commit_confirmed();
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::Amend => {
+				if let Err(e) = self
+					.commit
+					.amend_confirmed(self.skip_undo_snapshot)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("commit error:\n{}", e),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::AutoStash(operation) => {
+				if let Err(e) = self
+					.select_branch_popup
+					.run_autostashed(&operation)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						e.to_string(),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::WithoutUndo(action, _) => {
+				self.skip_undo_snapshot = true;
+				let res =
+					self.process_confirmed_action(*action, flags);
+				self.skip_undo_snapshot = false;
+				res?;
+			}
+			Action::RebuildIndex(_) => {
+				if let Err(e) =
+					sync::rebuild_index(&self.repo.borrow())
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!(
+							"rebuilding the index failed:\n{}",
+							e
+						),
+					));
+				} else {
+					self.index_recovery_offered = false;
+					self.queue.push(InternalEvent::ShowToast(
+						ToastKind::Success,
+						strings::TOAST_INDEX_REBUILT.to_string(),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::RestoreDiscarded(entry) => {
+				if let Err(e) = sync::restore_discarded(
+					&self.repo.borrow(),
+					&entry,
+				) {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("restore failed:\n{}", e),
+					));
+				} else {
+					self.queue.push(InternalEvent::ShowToast(
+						ToastKind::Success,
+						strings::DISCARDED_RESTORED.to_string(),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::Undo(entry) => {
+				if let Err(e) =
+					sync::undo(&self.repo.borrow(), &entry)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("undo failed:\n{}", e),
+					));
+				} else {
+					self.queue.push(InternalEvent::ShowToast(
+						ToastKind::Success,
+						strings::UNDO_DONE.to_string(),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+				self.select_branch_popup.update_branches()?;
+			}
 		};
 
 		Ok(())
@@ -1079,6 +2072,48 @@ impl App {
 			)
 			.order(order::NAV),
 		);
+		let rare_actions = [
+			strings::commands::notifications_open,
+			strings::commands::tasks_open,
+			strings::commands::open_discarded,
+			strings::commands::open_action_log,
+			strings::commands::open_repo_stats,
+			strings::commands::open_maintenance,
+			strings::commands::undo,
+			strings::commands::open_shell,
+		];
+		for command in rare_actions {
+			res.push(
+				CommandInfo::new(
+					command(&self.key_config),
+					true,
+					!self.any_popup_visible(),
+				)
+				.order(order::RARE_ACTION),
+			);
+		}
+		if cfg!(unix) {
+			res.push(
+				CommandInfo::new(
+					strings::commands::suspend(&self.key_config),
+					true,
+					!self.any_popup_visible(),
+				)
+				.order(order::RARE_ACTION),
+			);
+		}
+		if cfg!(feature = "github") {
+			res.push(
+				CommandInfo::new(
+					strings::commands::pull_requests_open(
+						&self.key_config,
+					),
+					true,
+					!self.any_popup_visible(),
+				)
+				.order(order::RARE_ACTION),
+			);
+		}
 
 		res.push(
 			CommandInfo::new(
@@ -1092,24 +2127,37 @@ impl App {
 		res
 	}
 
-	//TODO: make this dynamic
+	fn draw_too_small<B: Backend>(&self, f: &mut Frame<B>) {
+		let fsize = f.size();
+		let area = ui::centered_rect_absolute(
+			fsize.width,
+			3.min(fsize.height),
+			fsize,
+		);
+
+		f.render_widget(
+			Paragraph::new(strings::terminal_too_small(
+				(fsize.width, fsize.height),
+				(MIN_SIZE.width, MIN_SIZE.height),
+			))
+			.style(self.theme.text_danger())
+			.alignment(Alignment::Center)
+			.wrap(Wrap { trim: true }),
+			area,
+		);
+	}
+
 	fn draw_tabs<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
 		let r = r.inner(&Margin {
 			vertical: 0,
 			horizontal: 1,
 		});
 
-		let tabs = [
-			Span::raw(strings::tab_status(&self.key_config)),
-			Span::raw(strings::tab_log(&self.key_config)),
-			Span::raw(strings::tab_files(&self.key_config)),
-			Span::raw(strings::tab_stashing(&self.key_config)),
-			Span::raw(strings::tab_stashes(&self.key_config)),
-		]
-		.iter()
-		.cloned()
-		.map(Spans::from)
-		.collect();
+		let tabs = self
+			.tabs
+			.iter()
+			.map(|tab| Spans::from(self.tab_title(*tab)))
+			.collect();
 
 		f.render_widget(
 			Tabs::new(tabs)
@@ -1126,3 +2174,45 @@ impl App {
 		);
 	}
 }
+
+/// the undo snapshots of discards, see `PickTarget::Discarded`
+fn discarded(repo: &RepoPath) -> Result<Vec<UndoEntry>> {
+	Ok(sync::undo_entries(repo)?
+		.into_iter()
+		.filter(|entry| entry.discarded_path().is_some())
+		.collect())
+}
+
+/// the lines of `sync::get_ignored_files`, see
+/// `PickTarget::IgnoredFiles`
+fn ignored_file_items(repo: &RepoPath) -> Result<Vec<String>> {
+	Ok(sync::get_ignored_files(repo)?
+		.iter()
+		.map(|file| {
+			strings::ignored_file_item(
+				&file.path,
+				file.source.as_ref().map(|source| {
+					(
+						source.file.as_str(),
+						source.line,
+						source.pattern.as_str(),
+					)
+				}),
+			)
+		})
+		.collect())
+}
+
+/// the lines of `sync::action_log`, see `PickTarget::ActionLog`
+fn action_log_items(repo: &RepoPath) -> Result<Vec<String>> {
+	Ok(sync::action_log(repo)?
+		.iter()
+		.map(|entry| {
+			strings::action_log_item(
+				&time_to_string(entry.time, false),
+				&entry.action,
+				&entry.details,
+			)
+		})
+		.collect())
+}
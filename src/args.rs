@@ -1,30 +1,44 @@
-use crate::bug_report;
+use crate::{bug_report, print::PrintAction, startup};
 use anyhow::{anyhow, Result};
 use asyncgit::sync::RepoPath;
 use clap::{
 	crate_authors, crate_description, crate_name, crate_version,
-	App as ClapApp, Arg,
+	value_parser, App as ClapApp, Arg, ArgMatches,
 };
+use clap_complete::Shell;
 use simplelog::{Config, LevelFilter, WriteLogger};
 use std::{
 	env,
+	ffi::OsString,
 	fs::{self, File},
+	io,
 	path::PathBuf,
 };
 
 pub struct CliArgs {
 	pub theme: PathBuf,
 	pub repo_path: RepoPath,
+	/// only report problems in the config files
+	pub check_config: bool,
+	/// use the monochrome theme
+	pub no_color: bool,
+	/// lines to draw in below the prompt instead of the whole screen
+	pub inline: Option<u16>,
+	/// print instead of starting the ui
+	pub print: Option<PrintAction>,
 }
 
 pub fn process_cmdline() -> Result<CliArgs> {
 	let app = app();
 
 	let arg_matches = app.get_matches();
-	if arg_matches.is_present("bugreport") {
-		bug_report::generate_bugreport();
+	if let Some((name, matches)) = arg_matches.subcommand() {
+		generate(name, matches)?;
 		std::process::exit(0);
 	}
+	if arg_matches.is_present("debug-startup") {
+		startup::enable();
+	}
 	if arg_matches.is_present("logging") {
 		setup_logging()?;
 	}
@@ -41,22 +55,71 @@ pub fn process_cmdline() -> Result<CliArgs> {
 		RepoPath::Path(gitdir)
 	};
 
+	if arg_matches.is_present("bugreport") {
+		bug_report::generate_bugreport(&repo_path);
+		std::process::exit(0);
+	}
+
 	let arg_theme =
 		arg_matches.value_of("theme").unwrap_or("theme.ron");
+	let check_config = arg_matches.is_present("check-config");
+	let no_color = colors_disabled(
+		arg_matches.is_present("no-color"),
+		env::var_os("NO_COLOR"),
+		env::var_os("CLICOLOR_FORCE"),
+	);
+	let inline = arg_matches
+		.value_of("inline")
+		.map(|lines| {
+			lines.parse::<u16>().map_err(|e| {
+				anyhow!("invalid --inline lines {:?}: {}", lines, e)
+			})
+		})
+		.transpose()?;
+	let print = if arg_matches.is_present("print-branch") {
+		Some(PrintAction::Branch)
+	} else if arg_matches.is_present("print-status-porcelain") {
+		Some(PrintAction::StatusPorcelain)
+	} else {
+		None
+	};
 
 	if get_app_config_path()?.join(arg_theme).is_file() {
 		Ok(CliArgs {
 			theme: get_app_config_path()?.join(arg_theme),
 			repo_path,
+			check_config,
+			no_color,
+			inline,
+			print,
 		})
 	} else {
 		Ok(CliArgs {
 			theme: get_app_config_path()?.join("theme.ron"),
 			repo_path,
+			check_config,
+			no_color,
+			inline,
+			print,
 		})
 	}
 }
 
+/// `--no-color` always wins, otherwise a `CLICOLOR_FORCE` other than
+/// `0` keeps colors even with a non empty `NO_COLOR`
+/// (see <https://no-color.org> and <https://bixense.com/clicolors>)
+fn colors_disabled(
+	flag: bool,
+	no_color: Option<OsString>,
+	clicolor_force: Option<OsString>,
+) -> bool {
+	let is_set = |var: Option<OsString>, off: &str| {
+		var.map_or(false, |v| !v.is_empty() && v != off)
+	};
+
+	flag || (!is_set(clicolor_force, "0") && is_set(no_color, ""))
+}
+
 fn app() -> ClapApp<'static> {
 	let app = ClapApp::new(crate_name!())
 		.author(crate_authors!())
@@ -76,6 +139,43 @@ fn app() -> ClapApp<'static> {
 				.short('l')
 				.long("logging"),
 		)
+		.arg(
+			Arg::with_name("debug-startup")
+				.help(
+					"Print how long the startup phases took on exit",
+				)
+				.long("debug-startup"),
+		)
+		.arg(
+			Arg::with_name("check-config")
+				.help("Report problems in the config files and exit")
+				.long("check-config"),
+		)
+		.arg(
+			Arg::with_name("no-color")
+				.help("Do not use colors (also with NO_COLOR set)")
+				.long("no-color"),
+		)
+		.arg(
+			Arg::with_name("inline")
+				.help("Draw in LINES lines below the prompt instead of the whole screen, keeping the scrollback")
+				.long("inline")
+				.value_name("LINES")
+				.takes_value(true)
+				.min_values(0)
+				.default_missing_value("20"),
+		)
+		.arg(
+			Arg::with_name("print-branch")
+				.help("Print the checked out branch and exit")
+				.long("print-branch"),
+		)
+		.arg(
+			Arg::with_name("print-status-porcelain")
+				.help("Print the changed files like `git status --porcelain` and exit")
+				.long("print-status-porcelain")
+				.conflicts_with("print-branch"),
+		)
 		.arg(
 			Arg::with_name("bugreport")
 				.help("Generate a bug report")
@@ -83,9 +183,10 @@ fn app() -> ClapApp<'static> {
 		)
 		.arg(
 			Arg::with_name("directory")
-				.help("Set the git directory")
+				.help("Set the git directory, or a directory in the repository to search it from")
 				.short('d')
 				.long("directory")
+				.alias("git-dir")
 				.env("GIT_DIR")
 				.takes_value(true),
 		)
@@ -94,12 +195,47 @@ fn app() -> ClapApp<'static> {
 				.help("Set the working directory")
 				.short('w')
 				.long("workdir")
+				.alias("work-tree")
 				.env("GIT_WORK_TREE")
 				.takes_value(true),
-		);
+		)
+		.subcommand(
+			ClapApp::new("completions")
+				.about("Print the completion script for SHELL")
+				.arg(
+					Arg::with_name("shell")
+						.value_name("SHELL")
+						.required(true)
+						.value_parser(value_parser!(Shell)),
+				),
+		)
+		.subcommand(ClapApp::new("man").about("Print the man page"));
 	app
 }
 
+/// writes what the subcommand `name` generates from the cli
+/// definition to stdout
+fn generate(name: &str, matches: &ArgMatches) -> Result<()> {
+	let mut out = io::stdout();
+
+	match name {
+		"completions" => {
+			if let Some(shell) = matches.get_one::<Shell>("shell") {
+				clap_complete::generate(
+					*shell,
+					&mut app(),
+					crate_name!(),
+					&mut out,
+				);
+			}
+		}
+		"man" => clap_mangen::Man::new(app()).render(&mut out)?,
+		_ => (),
+	}
+
+	Ok(())
+}
+
 fn setup_logging() -> Result<()> {
 	let mut path = get_app_cache_path()?;
 	path.push("gitui.log");
@@ -113,7 +249,7 @@ fn setup_logging() -> Result<()> {
 	Ok(())
 }
 
-fn get_app_cache_path() -> Result<PathBuf> {
+pub fn get_app_cache_path() -> Result<PathBuf> {
 	let mut path = dirs_next::cache_dir()
 		.ok_or_else(|| anyhow!("failed to find os cache dir."))?;
 
@@ -139,3 +275,15 @@ pub fn get_app_config_path() -> Result<PathBuf> {
 fn verify_app() {
 	app().debug_assert();
 }
+
+#[test]
+fn test_colors_disabled() {
+	let var = |v: &str| Some(OsString::from(v));
+
+	assert!(!colors_disabled(false, None, None));
+	assert!(!colors_disabled(false, var(""), None));
+	assert!(colors_disabled(false, var("1"), None));
+	assert!(colors_disabled(false, var("1"), var("0")));
+	assert!(!colors_disabled(false, var("1"), var("1")));
+	assert!(colors_disabled(true, None, var("1")));
+}
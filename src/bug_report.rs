@@ -1,13 +1,43 @@
+use asyncgit::sync::{repo_stats::get_repo_stats, RepoPath};
 use bugreport::{
 	bugreport,
 	collector::{
-		CommandLine, CompileTimeInformation, EnvironmentVariables,
+		CollectionError, Collector, CommandLine,
+		CompileTimeInformation, EnvironmentVariables,
 		OperatingSystem, SoftwareVersion,
 	},
 	format::Markdown,
+	report::{Code, ReportEntry},
+	CrateInfo,
 };
 
-pub fn generate_bugreport() {
+/// the statistics of the repository gitui would open, helping with
+/// performance issues
+struct RepoStatistics(RepoPath);
+
+impl Collector for RepoStatistics {
+	fn description(&self) -> &'static str {
+		"Repository"
+	}
+
+	fn collect(
+		&mut self,
+		_: &CrateInfo,
+	) -> Result<ReportEntry, CollectionError> {
+		get_repo_stats(&self.0)
+			.map(|stats| {
+				ReportEntry::Code(Code {
+					language: None,
+					code: crate::strings::repo_stats_msg(&stats),
+				})
+			})
+			.map_err(|e| {
+				CollectionError::CouldNotRetrieve(e.to_string())
+			})
+	}
+}
+
+pub fn generate_bugreport(repo: &RepoPath) {
 	bugreport!()
 		.info(SoftwareVersion::default())
 		.info(OperatingSystem::default())
@@ -19,5 +49,6 @@ pub fn generate_bugreport() {
 			"VISUAL",
 		]))
 		.info(CommandLine::default())
+		.info(RepoStatistics(repo.clone()))
 		.print::<Markdown>();
 }
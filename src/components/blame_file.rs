@@ -4,16 +4,16 @@ use super::{
 	InspectCommitOpen,
 };
 use crate::{
-	components::{utils::string_width_align, ScrollType},
+	components::ScrollType,
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, Queue, StackablePopupOpen},
-	string_utils::tabs_to_spaces,
+	string_utils::{fit_to_width, tabs_to_spaces},
 	strings,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{BlameHunk, CommitId, FileBlame, RepoPathRef},
+	sync::{self, BlameHunk, CommitId, FileBlame, RepoPathRef},
 	AsyncBlame, AsyncGitNotification, BlameParams,
 };
 use crossbeam_channel::Sender;
@@ -22,6 +22,7 @@ use std::convert::TryInto;
 use tui::{
 	backend::Backend,
 	layout::{Constraint, Rect},
+	style::Style,
 	symbols::line::VERTICAL,
 	text::Span,
 	widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
@@ -44,8 +45,11 @@ pub struct BlameFileComponent {
 	title: String,
 	theme: SharedTheme,
 	queue: Queue,
+	repo: RepoPathRef,
 	async_blame: AsyncBlame,
 	visible: bool,
+	ignore_revs: bool,
+	has_ignore_revs: bool,
 	open_request: Option<BlameFileOpen>,
 	params: Option<BlameParams>,
 	file_blame: Option<FileBlame>,
@@ -176,6 +180,17 @@ impl Component for BlameFileComponent {
 				)
 				.order(1),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_toggle_ignore_revs(
+						&self.key_config,
+						self.ignore_revs,
+					),
+					true,
+					self.has_ignore_revs || force_all,
+				)
+				.order(1),
+			);
 		}
 
 		visibility_blocking(self)
@@ -249,6 +264,12 @@ impl Component for BlameFileComponent {
 							),
 						));
 					}
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_ignore_revs,
+				) && self.has_ignore_revs
+				{
+					self.toggle_ignore_revs()?;
 				}
 
 				return Ok(EventState::Consumed);
@@ -287,7 +308,10 @@ impl BlameFileComponent {
 				sender,
 			),
 			queue: queue.clone(),
+			repo: repo.clone(),
 			visible: false,
+			ignore_revs: true,
+			has_ignore_revs: false,
 			params: None,
 			file_blame: None,
 			open_request: None,
@@ -299,6 +323,11 @@ impl BlameFileComponent {
 
 	fn hide_stacked(&mut self, stack: bool) {
 		self.visible = false;
+		if self.async_blame.is_pending() {
+			if let Err(e) = self.async_blame.cancel() {
+				log::error!("blame cancel error: {}", e);
+			}
+		}
 		if stack {
 			if let Some(request) = self.open_request.clone() {
 				self.queue.push(InternalEvent::PopupStackPush(
@@ -317,9 +346,14 @@ impl BlameFileComponent {
 	///
 	pub fn open(&mut self, open: BlameFileOpen) -> Result<()> {
 		self.open_request = Some(open.clone());
+		self.has_ignore_revs =
+			sync::get_ignore_revs(&self.repo.borrow())
+				.map(|revs| !revs.is_empty())
+				.unwrap_or_default();
 		self.params = Some(BlameParams {
 			file_path: open.file_path,
 			commit_id: open.commit_id,
+			ignore_revs: self.ignore_revs && self.has_ignore_revs,
 		});
 		self.file_blame = None;
 		self.table_state.get_mut().select(Some(0));
@@ -335,6 +369,18 @@ impl BlameFileComponent {
 		self.async_blame.is_pending()
 	}
 
+	fn toggle_ignore_revs(&mut self) -> Result<()> {
+		self.ignore_revs = !self.ignore_revs;
+
+		if let Some(params) = self.params.as_mut() {
+			params.ignore_revs = self.ignore_revs;
+			self.file_blame = None;
+			self.update()?;
+		}
+
+		Ok(())
+	}
+
 	///
 	pub fn update_git(
 		&mut self,
@@ -377,7 +423,16 @@ impl BlameFileComponent {
 			self.params.as_ref(),
 			self.file_blame.as_ref(),
 		) {
-			(true, Some(params), _) => {
+			(true, Some(params), Some(file_blame)) => {
+				format!(
+					"{} -- {} -- <calculating.. {}%>",
+					self.title,
+					params.file_path,
+					(file_blame.lines_blamed * 100)
+						/ file_blame.lines.len().max(1)
+				)
+			}
+			(true, Some(params), None) => {
 				format!(
 					"{} -- {} -- <calculating.. (who is to blame?)>",
 					self.title, params.file_path
@@ -385,10 +440,15 @@ impl BlameFileComponent {
 			}
 			(false, Some(params), Some(file_blame)) => {
 				format!(
-					"{} -- {} -- {}",
+					"{} -- {} -- {}{}",
 					self.title,
 					params.file_path,
-					file_blame.commit_id.get_short_string()
+					file_blame.commit_id.get_short_string(),
+					if params.ignore_revs {
+						" (ignoring revs)"
+					} else {
+						""
+					}
 				)
 			}
 			(false, Some(params), None) => {
@@ -406,6 +466,8 @@ impl BlameFileComponent {
 		self.file_blame
 			.as_ref()
 			.map_or_else(Vec::new, |file_blame| {
+				let time_range = get_time_range(file_blame);
+
 				file_blame
 					.lines
 					.iter()
@@ -416,6 +478,7 @@ impl BlameFileComponent {
 							i,
 							(blame_hunk.as_ref(), line.as_ref()),
 							file_blame,
+							time_range,
 						)
 					})
 					.collect()
@@ -428,10 +491,22 @@ impl BlameFileComponent {
 		line_number: usize,
 		hunk_and_line: (Option<&BlameHunk>, &str),
 		file_blame: &FileBlame,
+		time_range: Option<(i64, i64)>,
 	) -> Row {
 		let (hunk_for_line, line) = hunk_and_line;
 
-		let show_metadata = if line_number == 0 {
+		let heat_style = hunk_for_line.zip(time_range).map_or_else(
+			|| self.theme.text(true, false),
+			|(hunk, range)| {
+				self.theme.blame_heat(get_age(hunk.time, range))
+			},
+		);
+
+		let show_metadata = if line_number >= file_blame.lines_blamed
+		{
+			// not blamed yet
+			false
+		} else if line_number == 0 {
 			true
 		} else {
 			let hunk_for_previous_line =
@@ -446,7 +521,11 @@ impl BlameFileComponent {
 		};
 
 		let mut cells = if show_metadata {
-			self.get_metadata_for_line_blame(width, hunk_for_line)
+			self.get_metadata_for_line_blame(
+				width,
+				hunk_for_line,
+				heat_style,
+			)
 		} else {
 			vec![Cell::from(""), Cell::from(""), Cell::from("")]
 		};
@@ -459,7 +538,7 @@ impl BlameFileComponent {
 				VERTICAL,
 				line_number_width = line_number_width,
 			))
-			.style(self.theme.text(true, false)),
+			.style(heat_style),
 		);
 		cells.push(
 			Cell::from(tabs_to_spaces(String::from(line)))
@@ -473,6 +552,7 @@ impl BlameFileComponent {
 		&self,
 		width: usize,
 		blame_hunk: Option<&BlameHunk>,
+		heat_style: Style,
 	) -> Vec<Cell> {
 		let commit_hash = blame_hunk.map_or_else(
 			|| NO_COMMIT_ID.into(),
@@ -481,7 +561,7 @@ impl BlameFileComponent {
 		let author_width = get_author_width(width);
 		let truncated_author: String = blame_hunk.map_or_else(
 			|| NO_AUTHOR.into(),
-			|hunk| string_width_align(&hunk.author, author_width),
+			|hunk| fit_to_width(&hunk.author, author_width),
 		);
 		let author = format!(
 			"{:author_width$}",
@@ -506,7 +586,7 @@ impl BlameFileComponent {
 			Cell::from(commit_hash).style(
 				self.theme.commit_hash_in_blame(is_blamed_commit),
 			),
-			Cell::from(time).style(self.theme.commit_time(false)),
+			Cell::from(time).style(heat_style),
 			Cell::from(author).style(self.theme.commit_author(false)),
 		]
 	}
@@ -555,8 +635,12 @@ impl BlameFileComponent {
 	}
 
 	fn set_open_selection(&mut self) {
-		if let Some(selection) =
-			self.open_request.as_ref().and_then(|req| req.selection)
+		// partial blames arrive repeatedly,
+		// only restore the selection once
+		if let Some(selection) = self
+			.open_request
+			.as_mut()
+			.and_then(|req| req.selection.take())
 		{
 			let mut table_state = self.table_state.take();
 			table_state.select(Some(selection));
@@ -611,3 +695,28 @@ const fn number_of_digits(number: usize) -> usize {
 
 	result
 }
+
+/// oldest and newest commit time of all lines in the blame
+fn get_time_range(file_blame: &FileBlame) -> Option<(i64, i64)> {
+	let times = file_blame
+		.lines
+		.iter()
+		.filter_map(|(hunk, _)| hunk.as_ref().map(|hunk| hunk.time));
+
+	times.clone().min().zip(times.max())
+}
+
+/// relative age of `time` inside `range`:
+/// `0.0` for the most recent and `1.0` for the oldest change
+fn get_age(time: i64, (oldest, newest): (i64, i64)) -> f64 {
+	let span = newest.saturating_sub(oldest);
+
+	if span <= 0 {
+		return 0.0;
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let age = newest.saturating_sub(time) as f64 / span as f64;
+
+	age
+}
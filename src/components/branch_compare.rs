@@ -0,0 +1,457 @@
+use super::{
+	utils::scroll_vertical::VerticalScroll, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState, InspectCommitOpen, ScrollType,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue, StackablePopupOpen},
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{
+		self, BranchComparison, CommitId, CommitInfo, RepoPathRef,
+	},
+	DiffLineType,
+};
+use crossterm::event::Event;
+use tui::{
+	backend::Backend,
+	layout::{Constraint, Direction, Layout, Rect},
+	text::{Span, Spans},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+const MAX_MESSAGE_LEN: usize = 100;
+
+///
+#[derive(Clone, Debug)]
+pub struct BranchCompareOpen {
+	/// name of the branch compared to HEAD
+	pub branch: String,
+	///
+	pub commit: CommitId,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focus {
+	OnlyInHead,
+	OnlyInBranch,
+	Files,
+}
+
+impl Focus {
+	const fn next(self) -> Self {
+		match self {
+			Self::OnlyInHead => Self::OnlyInBranch,
+			Self::OnlyInBranch => Self::Files,
+			Self::Files => Self::OnlyInHead,
+		}
+	}
+
+	const fn idx(self) -> usize {
+		match self {
+			Self::OnlyInHead => 0,
+			Self::OnlyInBranch => 1,
+			Self::Files => 2,
+		}
+	}
+}
+
+/// what merging a branch into HEAD would bring in: the commits
+/// only found on either side and the files the branch changed
+pub struct BranchCompareComponent {
+	repo: RepoPathRef,
+	queue: Queue,
+	open_request: Option<BranchCompareOpen>,
+	comparison: BranchComparison,
+	only_in_head: Vec<CommitInfo>,
+	only_in_branch: Vec<CommitInfo>,
+	focus: Focus,
+	selections: [usize; 3],
+	scrolls: [VerticalScroll; 3],
+	visible: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl BranchCompareComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			repo,
+			queue: queue.clone(),
+			open_request: None,
+			comparison: BranchComparison::default(),
+			only_in_head: Vec::new(),
+			only_in_branch: Vec::new(),
+			focus: Focus::OnlyInBranch,
+			selections: [0; 3],
+			scrolls: [
+				VerticalScroll::new(),
+				VerticalScroll::new(),
+				VerticalScroll::new(),
+			],
+			visible: false,
+			theme,
+			key_config,
+		}
+	}
+
+	///
+	pub fn open(&mut self, open: BranchCompareOpen) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+		let head = sync::get_head(&repo)?;
+
+		self.comparison =
+			sync::compare_branches(&repo, head, open.commit)?;
+		self.only_in_head = sync::get_commits_info(
+			&repo,
+			&self.comparison.only_in_head,
+			MAX_MESSAGE_LEN,
+		)?;
+		self.only_in_branch = sync::get_commits_info(
+			&repo,
+			&self.comparison.only_in_other,
+			MAX_MESSAGE_LEN,
+		)?;
+
+		self.open_request = Some(open);
+		self.focus = Focus::OnlyInBranch;
+		self.selections = [0; 3];
+		for scroll in &self.scrolls {
+			scroll.reset();
+		}
+
+		self.show()
+	}
+
+	fn list_len(&self, focus: Focus) -> usize {
+		match focus {
+			Focus::OnlyInHead => self.only_in_head.len(),
+			Focus::OnlyInBranch => self.only_in_branch.len(),
+			Focus::Files => self.comparison.files.len(),
+		}
+	}
+
+	fn selected_commit(&self) -> Option<CommitId> {
+		let selection = self.selections[self.focus.idx()];
+		match self.focus {
+			Focus::OnlyInHead => self.only_in_head.get(selection),
+			Focus::OnlyInBranch => self.only_in_branch.get(selection),
+			Focus::Files => None,
+		}
+		.map(|commit| commit.id)
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) {
+		let idx = self.focus.idx();
+		let max = self.list_len(self.focus).saturating_sub(1);
+		self.selections[idx] = self.scrolls[idx].move_selection(
+			self.selections[idx],
+			scroll,
+			1,
+			max,
+		);
+	}
+
+	fn inspect_selected_commit(&mut self) {
+		if let Some(commit_id) = self.selected_commit() {
+			self.hide_stacked(true);
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::InspectCommit(
+					InspectCommitOpen::new(commit_id),
+				),
+			));
+		}
+	}
+
+	fn hide_stacked(&mut self, stack: bool) {
+		self.hide();
+		if stack {
+			if let Some(request) = self.open_request.clone() {
+				self.queue.push(InternalEvent::PopupStackPush(
+					StackablePopupOpen::CompareBranches(request),
+				));
+			}
+		} else {
+			self.queue.push(InternalEvent::PopupStackPop);
+		}
+	}
+
+	fn commit_rows<'a>(
+		&'a self,
+		commits: &'a [CommitInfo],
+		selection: Option<usize>,
+		top: usize,
+		height: usize,
+	) -> Vec<Spans<'a>> {
+		commits
+			.iter()
+			.enumerate()
+			.skip(top)
+			.take(height)
+			.map(|(idx, commit)| {
+				let selected = selection == Some(idx);
+				Spans::from(vec![
+					Span::styled(
+						format!("{} ", commit.id.get_short_string()),
+						self.theme.commit_hash(selected),
+					),
+					Span::styled(
+						commit.message.as_str(),
+						self.theme.text(true, selected),
+					),
+				])
+			})
+			.collect()
+	}
+
+	fn file_rows(
+		&self,
+		selection: Option<usize>,
+		top: usize,
+		height: usize,
+	) -> Vec<Spans> {
+		self.comparison
+			.files
+			.iter()
+			.enumerate()
+			.skip(top)
+			.take(height)
+			.map(|(idx, file)| {
+				let selected = selection == Some(idx);
+				Spans::from(vec![
+					Span::styled(
+						format!(
+							"{:>5} ",
+							format!("+{}", file.additions)
+						),
+						self.theme
+							.diff_line(DiffLineType::Add, selected),
+					),
+					Span::styled(
+						format!(
+							"{:>5} ",
+							format!("-{}", file.deletions)
+						),
+						self.theme.diff_line(
+							DiffLineType::Delete,
+							selected,
+						),
+					),
+					Span::styled(
+						file.path.as_str(),
+						self.theme.item(file.status, selected),
+					),
+				])
+			})
+			.collect()
+	}
+
+	fn draw_list<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		area: Rect,
+		focus: Focus,
+		title: String,
+	) {
+		let focused = self.focus == focus;
+		let idx = focus.idx();
+
+		let block = Block::default()
+			.title(Span::styled(title, self.theme.title(focused)))
+			.borders(Borders::ALL)
+			.border_style(self.theme.block(focused));
+		let height = usize::from(block.inner(area).height);
+
+		let selection = focused.then(|| self.selections[idx]);
+		let top = self.scrolls[idx].update(
+			self.selections[idx],
+			self.list_len(focus).saturating_sub(1),
+			height,
+		);
+
+		let rows = match focus {
+			Focus::OnlyInHead => self.commit_rows(
+				&self.only_in_head,
+				selection,
+				top,
+				height,
+			),
+			Focus::OnlyInBranch => self.commit_rows(
+				&self.only_in_branch,
+				selection,
+				top,
+				height,
+			),
+			Focus::Files => self.file_rows(selection, top, height),
+		};
+
+		f.render_widget(Paragraph::new(rows).block(block), area);
+		self.scrolls[idx].draw(f, area, &self.theme);
+	}
+}
+
+impl DrawableComponent for BranchCompareComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let branch = self
+				.open_request
+				.as_ref()
+				.map_or("", |open| open.branch.as_str());
+
+			let rows = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Percentage(50),
+						Constraint::Percentage(50),
+					]
+					.as_ref(),
+				)
+				.split(rect);
+			let columns = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[
+						Constraint::Percentage(50),
+						Constraint::Percentage(50),
+					]
+					.as_ref(),
+				)
+				.split(rows[0]);
+
+			f.render_widget(Clear, rect);
+
+			self.draw_list(
+				f,
+				columns[0],
+				Focus::OnlyInHead,
+				strings::branch_compare_only_in(
+					"HEAD",
+					self.comparison.ahead,
+				),
+			);
+			self.draw_list(
+				f,
+				columns[1],
+				Focus::OnlyInBranch,
+				strings::branch_compare_only_in(
+					branch,
+					self.comparison.behind,
+				),
+			);
+			self.draw_list(
+				f,
+				rows[1],
+				Focus::Files,
+				strings::branch_compare_files(
+					branch,
+					&self.comparison.files,
+				),
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for BranchCompareComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::branch_compare_focus_next(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::commit_details_open(
+					&self.key_config,
+				),
+				self.selected_commit().is_some(),
+				self.focus != Focus::Files || force_all,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			let keys = &self.key_config.keys;
+			if key_match(e, keys.exit_popup)
+				|| key_match(e, keys.focus_left)
+			{
+				self.hide_stacked(false);
+			} else if key_match(e, keys.tab_toggle) {
+				self.focus = self.focus.next();
+			} else if key_match(e, keys.move_up) {
+				self.move_selection(ScrollType::Up);
+			} else if key_match(e, keys.move_down) {
+				self.move_selection(ScrollType::Down);
+			} else if key_match(e, keys.shift_up)
+				|| key_match(e, keys.home)
+			{
+				self.move_selection(ScrollType::Home);
+			} else if key_match(e, keys.shift_down)
+				|| key_match(e, keys.end)
+			{
+				self.move_selection(ScrollType::End);
+			} else if key_match(e, keys.page_up) {
+				self.move_selection(ScrollType::PageUp);
+			} else if key_match(e, keys.page_down) {
+				self.move_selection(ScrollType::PageDown);
+			} else if key_match(e, keys.enter)
+				|| key_match(e, keys.focus_right)
+			{
+				self.inspect_selected_commit();
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		Ok(())
+	}
+}
@@ -1,14 +1,19 @@
 use super::{
-	utils::scroll_vertical::VerticalScroll, visibility_blocking,
-	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState, InspectCommitOpen,
+	utils::{
+		scroll_vertical::VerticalScroll, time_to_relative_string,
+	},
+	visibility_blocking, BranchCompareOpen, CommandBlocking,
+	CommandInfo, Component, DrawableComponent, EventState,
+	InspectCommitOpen, ToastKind,
 };
 use crate::{
 	components::ScrollType,
 	keys::{key_match, SharedKeyConfig},
 	queue::{
-		Action, InternalEvent, NeedsUpdate, Queue, StackablePopupOpen,
+		Action, AutoStashOperation, InternalEvent, NeedsUpdate,
+		Queue, StackablePopupOpen,
 	},
+	string_utils::{fit_to_width, truncate_to_width},
 	strings, try_or_popup,
 	ui::{self, Size},
 };
@@ -21,12 +26,13 @@ use asyncgit::{
 			RemoteBranch,
 		},
 		checkout_branch, get_branches_info, BranchInfo, BranchType,
-		CommitId, RepoPathRef, RepoState,
+		CommitId, PostHook, RepoPathRef, RepoState,
 	},
-	AsyncGitNotification,
+	AsyncBranchActivity, AsyncGitNotification, BranchesActivity,
 };
+use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use std::{cell::Cell, convert::TryInto};
+use std::convert::{TryFrom, TryInto};
 use tui::{
 	backend::Backend,
 	layout::{
@@ -37,18 +43,23 @@ use tui::{
 	Frame,
 };
 use ui::style::SharedTheme;
-use unicode_truncate::UnicodeTruncateStr;
+
+const UPSTREAM_SYMBOL: char = '\u{2191}';
+const TRACKING_SYMBOL: char = '\u{2193}';
+const AHEAD_BEHIND_LENGTH: usize = 10; // "↑12 ↓345  "
+const COMMIT_AGE_LENGTH: usize = 9; // "11mo ago "
 
 ///
 pub struct BranchListComponent {
 	repo: RepoPathRef,
 	branches: Vec<BranchInfo>,
+	activity: BranchesActivity,
+	git_activity: AsyncBranchActivity,
 	local: bool,
 	has_remotes: bool,
 	visible: bool,
 	selection: u16,
 	scroll: VerticalScroll,
-	current_height: Cell<u16>,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
@@ -97,7 +108,7 @@ impl DrawableComponent for BranchListComponent {
 				.split(area);
 
 			self.draw_tabs(f, chunks[0]);
-			self.draw_list(f, chunks[1])?;
+			self.draw_list(f, chunks[1]);
 		}
 
 		Ok(())
@@ -143,6 +154,13 @@ impl Component for BranchListComponent {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::compare_branch(&self.key_config),
+				!self.selection_is_cur_branch()
+					&& self.valid_selection(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::toggle_branch_popup(
 					&self.key_config,
@@ -169,6 +187,14 @@ impl Component for BranchListComponent {
 				self.local,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::open_orphan_branch_create_popup(
+					&self.key_config,
+				),
+				true,
+				self.local,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::delete_branch_popup(
 					&self.key_config,
@@ -211,7 +237,7 @@ impl Component for BranchListComponent {
 	}
 
 	//TODO: cleanup
-	#[allow(clippy::cognitive_complexity)]
+	#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if !self.visible {
 			return Ok(EventState::NotConsumed);
@@ -222,11 +248,11 @@ impl Component for BranchListComponent {
 				self.hide();
 			} else if key_match(e, self.key_config.keys.move_down) {
 				return self
-					.move_selection(ScrollType::Up)
+					.move_selection(ScrollType::Down)
 					.map(Into::into);
 			} else if key_match(e, self.key_config.keys.move_up) {
 				return self
-					.move_selection(ScrollType::Down)
+					.move_selection(ScrollType::Up)
 					.map(Into::into);
 			} else if key_match(e, self.key_config.keys.page_down) {
 				return self
@@ -258,6 +284,12 @@ impl Component for BranchListComponent {
 				&& self.local
 			{
 				self.queue.push(InternalEvent::CreateBranch);
+			} else if key_match(
+				e,
+				self.key_config.keys.create_orphan_branch,
+			) && self.local
+			{
+				self.queue.push(InternalEvent::CreateOrphanBranch);
 			} else if key_match(e, self.key_config.keys.rename_branch)
 				&& self.valid_selection()
 			{
@@ -302,6 +334,13 @@ impl Component for BranchListComponent {
 						),
 					));
 				}
+			} else if key_match(
+				e,
+				self.key_config.keys.compare_branch,
+			) && !self.selection_is_cur_branch()
+				&& self.valid_selection()
+			{
+				self.compare_selected_branch();
 			} else if key_match(e, self.key_config.keys.pull)
 				&& !self.local && self.has_remotes
 			{
@@ -337,11 +376,17 @@ impl BranchListComponent {
 	pub fn new(
 		repo: RepoPathRef,
 		queue: Queue,
+		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 	) -> Self {
+		let git_activity =
+			AsyncBranchActivity::new(repo.borrow().clone(), sender);
+
 		Self {
 			branches: Vec::new(),
+			activity: BranchesActivity::new(),
+			git_activity,
 			local: true,
 			has_remotes: false,
 			visible: false,
@@ -350,7 +395,6 @@ impl BranchListComponent {
 			queue,
 			theme,
 			key_config,
-			current_height: Cell::new(0),
 			repo,
 		}
 	}
@@ -375,6 +419,11 @@ impl BranchListComponent {
 	/// fetch list of branches
 	pub fn update_branches(&mut self) -> Result<()> {
 		if self.is_visible() {
+			let selected = self
+				.branches
+				.get(usize::from(self.selection))
+				.map(|b| b.reference.clone());
+
 			self.check_remotes();
 			self.branches =
 				get_branches_info(&self.repo.borrow(), self.local)?;
@@ -385,7 +434,17 @@ impl BranchListComponent {
 					.position(|b| b.name.ends_with("/HEAD"))
 					.map(|idx| self.branches.remove(idx));
 			}
-			self.set_selection(self.selection)?;
+			// keep the same branch selected if it still exists
+			let selection = selected
+				.and_then(|reference| {
+					self.branches
+						.iter()
+						.position(|b| b.reference == reference)
+				})
+				.map_or(Ok(self.selection), u16::try_from)?;
+			self.set_selection(selection)?;
+
+			self.git_activity.request(self.local)?;
 		}
 		Ok(())
 	}
@@ -397,11 +456,24 @@ impl BranchListComponent {
 	) -> Result<()> {
 		if self.is_visible() && ev == AsyncGitNotification::Push {
 			self.update_branches()?;
+		} else if ev == AsyncGitNotification::BranchActivity {
+			if let Some((local, activity)) =
+				self.git_activity.last()?
+			{
+				if local == self.local {
+					self.activity = activity;
+				}
+			}
 		}
 
 		Ok(())
 	}
 
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_activity.is_pending()
+	}
+
 	fn valid_selection(&self) -> bool {
 		!self.branches.is_empty()
 	}
@@ -426,18 +498,123 @@ impl BranchListComponent {
 		if let Some(branch) =
 			self.branches.get(usize::from(self.selection))
 		{
-			sync::rebase_branch(
-				&self.repo.borrow(),
-				&branch.name,
+			let operation = AutoStashOperation::Rebase(
+				branch.name.clone(),
 				self.get_branch_type(),
-			)?;
+			);
 
-			self.hide_and_switch_tab()?;
+			self.run_or_offer_autostash(operation)?;
 		}
 
 		Ok(())
 	}
 
+	fn rebase(
+		&mut self,
+		branch: &str,
+		branch_type: BranchType,
+	) -> Result<()> {
+		sync::rebase_branch(
+			&self.repo.borrow(),
+			branch,
+			branch_type,
+		)?;
+
+		self.hide_and_switch_tab()
+	}
+
+	/// asks to stash local changes first if there are any in the way
+	fn run_or_offer_autostash(
+		&mut self,
+		operation: AutoStashOperation,
+	) -> Result<()> {
+		if sync::autostash_needed(&self.repo.borrow())? {
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::AutoStash(operation),
+			));
+
+			Ok(())
+		} else {
+			self.run_operation(&operation)
+		}
+	}
+
+	fn run_operation(
+		&mut self,
+		operation: &AutoStashOperation,
+	) -> Result<()> {
+		match operation {
+			AutoStashOperation::Checkout(reference) => {
+				self.checkout(reference)
+			}
+			AutoStashOperation::Rebase(branch, branch_type) => {
+				self.rebase(branch, *branch_type)
+			}
+		}
+	}
+
+	/// runs `operation` between stashing and re-applying local
+	/// changes, the stash is kept if that does not work out
+	pub fn run_autostashed(
+		&mut self,
+		operation: &AutoStashOperation,
+	) -> Result<()> {
+		let stash = sync::autostash_save(
+			&self.repo.borrow(),
+			&operation.name(),
+		)?;
+
+		let res = self.run_operation(operation);
+
+		if let Some(stash) = stash {
+			self.restore_autostash(stash)?;
+		}
+
+		res
+	}
+
+	fn restore_autostash(&self, stash: CommitId) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		// a conflicted rebase has to be finished first
+		if sync::repo_state(&repo)? != RepoState::Clean {
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				strings::AUTOSTASH_KEPT_PENDING.to_string(),
+			));
+		} else if sync::autostash_pop(&repo, stash)? {
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Success,
+				strings::AUTOSTASH_RESTORED.to_string(),
+			));
+		} else {
+			self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				strings::AUTOSTASH_KEPT_CONFLICT.to_string(),
+			));
+			self.queue.push(InternalEvent::TabSwitchStashes);
+		}
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn compare_selected_branch(&mut self) {
+		if let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		{
+			let open = BranchCompareOpen {
+				branch: branch.name.clone(),
+				commit: branch.top_commit,
+			};
+			self.hide();
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::CompareBranches(open),
+			));
+		}
+	}
+
 	fn inspect_head_of_branch(&mut self) {
 		if let Some(commit_id) = self.get_selected() {
 			self.hide();
@@ -492,24 +669,14 @@ impl BranchListComponent {
 
 	///
 	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
-		let new_selection = match scroll {
-			ScrollType::Up => self.selection.saturating_add(1),
-			ScrollType::Down => self.selection.saturating_sub(1),
-			ScrollType::PageDown => self
-				.selection
-				.saturating_add(self.current_height.get()),
-			ScrollType::PageUp => self
-				.selection
-				.saturating_sub(self.current_height.get()),
-			ScrollType::Home => 0,
-			ScrollType::End => {
-				let num_branches: u16 =
-					self.branches.len().try_into()?;
-				num_branches.saturating_sub(1)
-			}
-		};
+		let new_selection = self.scroll.move_selection(
+			self.selection.into(),
+			scroll,
+			1,
+			self.branches.len().saturating_sub(1),
+		);
 
-		self.set_selection(new_selection)?;
+		self.set_selection(new_selection.try_into()?)?;
 
 		Ok(true)
 	}
@@ -536,12 +703,8 @@ impl BranchListComponent {
 		width_available: u16,
 		height: usize,
 	) -> Text {
-		const UPSTREAM_SYMBOL: char = '\u{2191}';
-		const TRACKING_SYMBOL: char = '\u{2193}';
 		const HEAD_SYMBOL: char = '*';
 		const EMPTY_SYMBOL: char = ' ';
-		const THREE_DOTS: &str = "...";
-		const THREE_DOTS_LENGTH: usize = THREE_DOTS.len(); // "..."
 		const COMMIT_HASH_LENGTH: usize = 8;
 		const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
 
@@ -552,7 +715,9 @@ impl BranchListComponent {
 			.saturating_sub(COMMIT_HASH_LENGTH)
 			.saturating_sub(branch_name_length)
 			.saturating_sub(IS_HEAD_STAR_LENGTH)
-			.saturating_sub(THREE_DOTS_LENGTH);
+			.saturating_sub(AHEAD_BEHIND_LENGTH)
+			.saturating_sub(COMMIT_AGE_LENGTH);
+		let now = chrono::Local::now().timestamp();
 		let mut txt = Vec::new();
 
 		for (i, displaybranch) in self
@@ -562,29 +727,12 @@ impl BranchListComponent {
 			.take(height)
 			.enumerate()
 		{
-			let mut commit_message =
-				displaybranch.top_commit_message.clone();
-			if commit_message.len() > commit_message_length {
-				commit_message.unicode_truncate(
-					commit_message_length
-						.saturating_sub(THREE_DOTS_LENGTH),
-				);
-				commit_message += THREE_DOTS;
-			}
-
-			let mut branch_name = displaybranch.name.clone();
-			if branch_name.len()
-				> branch_name_length.saturating_sub(THREE_DOTS_LENGTH)
-			{
-				branch_name = branch_name
-					.unicode_truncate(
-						branch_name_length
-							.saturating_sub(THREE_DOTS_LENGTH),
-					)
-					.0
-					.to_string();
-				branch_name += THREE_DOTS;
-			}
+			let commit_message = truncate_to_width(
+				&displaybranch.top_commit_message,
+				commit_message_length,
+			);
+			let branch_name =
+				fit_to_width(&displaybranch.name, branch_name_length);
 
 			let selected = (self.selection as usize
 				- self.scroll.get_top())
@@ -612,6 +760,12 @@ impl BranchListComponent {
 				format!("{}{} ", is_head_str, upstream_tracking_str),
 				theme.commit_author(selected),
 			);
+			let (span_ahead_behind, span_age) = self.activity_spans(
+				&displaybranch.reference,
+				now,
+				theme,
+				selected,
+			);
 			let span_hash = Span::styled(
 				format!(
 					"{} ",
@@ -620,21 +774,19 @@ impl BranchListComponent {
 				theme.commit_hash(selected),
 			);
 			let span_msg = Span::styled(
-				commit_message.to_string(),
+				commit_message.into_owned(),
 				theme.text(true, selected),
 			);
 			let span_name = Span::styled(
-				format!(
-					"{:w$} ",
-					branch_name,
-					w = branch_name_length
-				),
+				format!("{} ", branch_name),
 				theme.branch(selected, is_head),
 			);
 
 			txt.push(Spans::from(vec![
 				span_prefix,
 				span_name,
+				span_ahead_behind,
+				span_age,
 				span_hash,
 				span_msg,
 			]));
@@ -643,27 +795,101 @@ impl BranchListComponent {
 		Text::from(txt)
 	}
 
+	/// ahead/behind its upstream and age of the last commit
+	fn activity_spans(
+		&self,
+		reference: &str,
+		now: i64,
+		theme: &SharedTheme,
+		selected: bool,
+	) -> (Span<'static>, Span<'static>) {
+		let activity = self.activity.get(reference);
+		let ahead_behind = activity
+			.and_then(|activity| activity.upstream)
+			.map(|upstream| {
+				[
+					(UPSTREAM_SYMBOL, upstream.ahead),
+					(TRACKING_SYMBOL, upstream.behind),
+				]
+				.iter()
+				.filter(|(_, count)| *count > 0)
+				.map(|(symbol, count)| format!("{}{}", symbol, count))
+				.collect::<Vec<_>>()
+				.join(" ")
+			})
+			.unwrap_or_default();
+		let age = activity
+			.map(|activity| {
+				time_to_relative_string(
+					activity.last_commit_time,
+					now,
+				)
+			})
+			.unwrap_or_default();
+
+		(
+			Span::styled(
+				format!(
+					"{} ",
+					fit_to_width(
+						&ahead_behind,
+						AHEAD_BEHIND_LENGTH - 1
+					)
+				),
+				theme.commit_author(selected),
+			),
+			Span::styled(
+				format!(
+					"{} ",
+					fit_to_width(&age, COMMIT_AGE_LENGTH - 1)
+				),
+				theme.commit_time(selected),
+			),
+		)
+	}
+
 	///
 	fn switch_to_selected_branch(&mut self) -> Result<()> {
 		if !self.valid_selection() {
 			anyhow::bail!("no valid branch selected");
 		}
 
+		let reference =
+			self.branches[self.selection as usize].reference.clone();
+
+		self.run_or_offer_autostash(AutoStashOperation::Checkout(
+			reference,
+		))
+	}
+
+	fn checkout(&mut self, reference: &str) -> Result<()> {
+		let prev_head =
+			sync::get_head(&self.repo.borrow()).unwrap_or_default();
+
 		if self.local {
-			checkout_branch(
-				&self.repo.borrow(),
-				&self.branches[self.selection as usize].reference,
-			)?;
+			checkout_branch(&self.repo.borrow(), reference)?;
 			self.hide();
 		} else {
-			checkout_remote_branch(
-				&self.repo.borrow(),
-				&self.branches[self.selection as usize],
-			)?;
+			let branch = self
+				.branches
+				.iter()
+				.find(|branch| branch.reference == reference)
+				.ok_or_else(|| {
+					anyhow::anyhow!("branch {} not found", reference)
+				})?;
+
+			checkout_remote_branch(&self.repo.borrow(), branch)?;
 			self.local = true;
 			self.update_branches()?;
 		}
 
+		self.queue.push(InternalEvent::RunPostHook(
+			PostHook::Checkout {
+				prev_head,
+				new_head: sync::get_head(&self.repo.borrow())?,
+				branch: true,
+			},
+		));
 		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 
 		Ok(())
@@ -691,13 +917,8 @@ impl BranchListComponent {
 		);
 	}
 
-	fn draw_list<B: Backend>(
-		&self,
-		f: &mut Frame<B>,
-		r: Rect,
-	) -> Result<()> {
+	fn draw_list<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
 		let height_in_lines = r.height as usize;
-		self.current_height.set(height_in_lines.try_into()?);
 
 		self.scroll.update(
 			self.selection as usize,
@@ -721,8 +942,6 @@ impl BranchListComponent {
 		r.y = r.y.saturating_sub(1);
 
 		self.scroll.draw(f, r, &self.theme);
-
-		Ok(())
 	}
 
 	fn rename_branch(&mut self) {
@@ -1,10 +1,14 @@
 use super::{
 	status_tree::StatusTreeComponent,
-	utils::filetree::{FileTreeItem, FileTreeItemKind},
+	utils::{
+		file_copy::{copy_file, FileCopy},
+		filetree::{FileTreeItem, FileTreeItemKind},
+	},
 	CommandBlocking, DrawableComponent, SharedOptions,
 };
 use crate::{
 	components::{CommandInfo, Component, EventState},
+	file_manager,
 	keys::{key_match, SharedKeyConfig},
 	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
 	strings, try_or_popup,
@@ -12,7 +16,7 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, RepoPathRef},
+	sync::{self, RepoPathRef, StatusGroup},
 	StatusItem, StatusItemType,
 };
 use crossterm::event::Event;
@@ -62,6 +66,10 @@ impl ChangesComponent {
 	///
 	pub fn set_items(&mut self, list: &[StatusItem]) -> Result<()> {
 		self.files.show()?;
+		self.files.set_flat(
+			self.options.borrow().status_order.group
+				== StatusGroup::ChangeType,
+		);
 		self.files.update(list)?;
 		Ok(())
 	}
@@ -88,6 +96,34 @@ impl ChangesComponent {
 	}
 
 	fn index_add_remove(&mut self) -> Result<bool> {
+		let marked = self.files.marked();
+		if !marked.is_empty() {
+			let paths = marked
+				.iter()
+				.map(|item| item.path.as_str())
+				.collect::<Vec<_>>();
+
+			if self.is_working_dir {
+				let paths =
+					paths.iter().map(Path::new).collect::<Vec<_>>();
+				sync::stage_add_files(&self.repo.borrow(), &paths)?;
+
+				if sync::is_workdir_clean(
+					&self.repo.borrow(),
+					self.options.borrow().status_show_untracked,
+				)? {
+					self.queue
+						.push(InternalEvent::StatusLastFileMoved);
+				}
+			} else {
+				sync::reset_stage_paths(&self.repo.borrow(), &paths)?;
+			}
+
+			self.files.clear_marked();
+
+			return Ok(true);
+		}
+
 		if let Some(tree_item) = self.selection() {
 			if self.is_working_dir {
 				if let FileTreeItemKind::File(i) = tree_item.kind {
@@ -158,6 +194,20 @@ impl ChangesComponent {
 	}
 
 	fn dispatch_reset_workdir(&mut self) -> bool {
+		let marked = self.files.marked();
+		if !marked.is_empty() {
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::ResetMarked(
+					marked
+						.into_iter()
+						.map(|item| item.path)
+						.collect(),
+				),
+			));
+
+			return true;
+		}
+
 		if let Some(tree_item) = self.selection() {
 			let is_folder =
 				matches!(tree_item.kind, FileTreeItemKind::Path(_));
@@ -174,6 +224,32 @@ impl ChangesComponent {
 	}
 
 	fn add_to_ignore(&mut self) -> bool {
+		let marked = self.files.marked();
+		if !marked.is_empty() {
+			let paths = marked
+				.iter()
+				.map(|item| item.path.as_str())
+				.collect::<Vec<_>>();
+
+			if let Err(e) =
+				sync::add_paths_to_ignore(&self.repo.borrow(), &paths)
+			{
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"ignore error:\n{}\nfiles:\n{:?}",
+						e, paths
+					),
+				));
+
+				return false;
+			}
+
+			self.files.clear_marked();
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+			return true;
+		}
+
 		if let Some(tree_item) = self.selection() {
 			if let Err(e) = sync::add_to_ignore(
 				&self.repo.borrow(),
@@ -195,6 +271,57 @@ impl ChangesComponent {
 
 		false
 	}
+
+	/// the content is the file in the workdir or the staged blob
+	fn copy(&self, copy: FileCopy) -> bool {
+		self.selection().map_or(false, |item| {
+			let path = item.info.full_path;
+			let is_file =
+				matches!(item.kind, FileTreeItemKind::File(_));
+
+			let repo = self.repo.borrow();
+			copy_file(&self.queue, &repo, &path, copy, || {
+				if !is_file {
+					anyhow::bail!("'{}' is a folder", path);
+				}
+				Ok(if self.is_working_dir {
+					sync::utils::read_file(
+						&Path::new(&sync::utils::repo_work_dir(
+							&repo,
+						)?)
+						.join(&path),
+					)?
+				} else {
+					sync::index_file_content(&repo, &path)?
+				})
+			});
+
+			true
+		})
+	}
+
+	fn reveal(&self) -> Result<()> {
+		if let Some(item) = self.selection() {
+			let root =
+				sync::utils::repo_work_dir(&self.repo.borrow())?;
+			file_manager::reveal(
+				&Path::new(&root).join(item.info.full_path),
+			)?;
+		}
+
+		Ok(())
+	}
+
+	fn commit_item(&self) -> bool {
+		if let Some(item) = self.files.selection_file() {
+			self.queue.push(InternalEvent::OpenCommitPaths(vec![
+				item.path,
+			]));
+			return true;
+		}
+
+		false
+	}
 }
 
 impl DrawableComponent for ChangesComponent {
@@ -219,6 +346,15 @@ impl Component for ChangesComponent {
 
 		let some_selection = self.selection().is_some();
 
+		out.push(CommandInfo::new(
+			strings::commands::mark_item(
+				&self.key_config,
+				self.files.selection_marked(),
+			),
+			true,
+			some_selection && self.focused(),
+		));
+
 		if self.is_working_dir {
 			out.push(CommandInfo::new(
 				strings::commands::stage_all(&self.key_config),
@@ -253,6 +389,25 @@ impl Component for ChangesComponent {
 			));
 		}
 
+		out.push(CommandInfo::new(
+			strings::commands::commit_item(&self.key_config),
+			self.files.selection_file().is_some(),
+			some_selection && self.focused(),
+		));
+
+		FileCopy::commands(
+			out,
+			&self.key_config,
+			some_selection,
+			self.files.selection_file().is_some(),
+			self.focused() || force_all,
+		);
+		out.push(CommandInfo::new(
+			strings::commands::reveal_file(&self.key_config),
+			true,
+			some_selection && self.focused(),
+		));
+
 		CommandBlocking::PassingOn
 	}
 
@@ -307,6 +462,31 @@ impl Component for ChangesComponent {
 					&& !self.is_empty()
 				{
 					Ok(self.add_to_ignore().into())
+				} else if key_match(
+					e,
+					self.key_config.keys.status_commit_item,
+				) {
+					Ok(self.commit_item().into())
+				} else if key_match(
+					e,
+					self.key_config.keys.status_mark_item,
+				) {
+					Ok(self.files.toggle_mark().into())
+				} else if let Some(copy) =
+					FileCopy::from_key(e, &self.key_config)
+				{
+					Ok(self.copy(copy).into())
+				} else if key_match(
+					e,
+					self.key_config.keys.reveal_file,
+				) && !self.is_empty()
+				{
+					try_or_popup!(
+						self,
+						"reveal error:",
+						self.reveal()
+					);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
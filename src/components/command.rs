@@ -34,6 +34,7 @@ impl CommandText {
 }
 
 ///
+#[derive(Clone)]
 pub struct CommandInfo {
 	///
 	pub text: CommandText,
@@ -1,22 +1,31 @@
 use super::{
-	textinput::TextInputComponent, visibility_blocking,
-	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState, ExternalEditorComponent,
+	completion::CompletionChain,
+	issue_completion::IssueCompletion,
+	snippet_completion::SnippetCompletion,
+	textinput::{TextInputComponent, Validation},
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ExternalEditorComponent,
+	InputType, ToastKind,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, NeedsUpdate, Queue},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
 	strings, try_or_popup,
 	ui::style::SharedTheme,
+	AsyncNotification,
 };
 use anyhow::Result;
 use asyncgit::{
 	cached, message_prettify,
 	sync::{
-		self, get_config_string, CommitId, HookResult, RepoPathRef,
-		RepoState,
+		self, get_config_string, CommitAuthor, CommitId, CommitLint,
+		HookResult, LintWarning, PostHook, RepoPathRef, RepoState,
+		UndoOperation,
 	},
+	AsyncGitNotification, Error,
 };
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone};
+use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use easy_cast::Cast;
 use std::{
@@ -40,26 +49,39 @@ enum Mode {
 	Amend(CommitId),
 	Merge(Vec<CommitId>),
 	Revert,
+	/// only these files, see `sync::commit_paths`
+	Paths(Vec<String>),
 }
 
 pub struct CommitComponent {
 	repo: RepoPathRef,
 	input: TextInputComponent,
+	input_passphrase: TextInputComponent,
+	/// message of a commit waiting for the gpg passphrase
+	pending_signed_msg: Option<String>,
+	input_author: TextInputComponent,
+	input_date: TextInputComponent,
+	/// author and date overrides for the next commit
+	author: CommitAuthor,
 	mode: Mode,
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	git_branch_name: cached::BranchName,
+	/// `gitui.commitPrefix` and `commit.template` a new message
+	/// starts with
 	commit_template: Option<String>,
+	lint: CommitLint,
+	/// amend without the undo snapshot that failed before
+	skip_undo_snapshot: bool,
 	theme: SharedTheme,
 }
 
-const FIRST_LINE_LIMIT: usize = 50;
-
 impl CommitComponent {
 	///
 	pub fn new(
 		repo: RepoPathRef,
 		queue: Queue,
+		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 	) -> Self {
@@ -72,10 +94,64 @@ impl CommitComponent {
 				"",
 				&strings::commit_msg(&key_config),
 				true,
-			),
+			)
+			.with_completion(Box::new(CompletionChain::new(vec![
+				Box::new(IssueCompletion::new(repo.clone(), sender)),
+				Box::new(SnippetCompletion::new(repo.clone())),
+			]))),
+			input_passphrase: TextInputComponent::new(
+				theme.clone(),
+				key_config.clone(),
+				&strings::gpg_passphrase_popup_title(&key_config),
+				&strings::gpg_passphrase_popup_msg(&key_config),
+				false,
+			)
+			.with_input_type(InputType::Password),
+			pending_signed_msg: None,
+			input_author: TextInputComponent::new(
+				theme.clone(),
+				key_config.clone(),
+				&strings::commit_author_popup_title(&key_config),
+				&strings::commit_author_popup_msg(&key_config),
+				false,
+			)
+			.with_input_type(InputType::Singleline)
+			.with_validator(|text| {
+				if text.trim().is_empty()
+					|| parse_author(text).is_some()
+				{
+					Validation::Ok
+				} else {
+					Validation::Error(
+						strings::COMMIT_AUTHOR_INVALID.to_string(),
+					)
+				}
+			}),
+			input_date: TextInputComponent::new(
+				theme.clone(),
+				key_config.clone(),
+				&strings::commit_date_popup_title(&key_config),
+				&strings::commit_date_popup_msg(&key_config),
+				false,
+			)
+			.with_input_type(InputType::Singleline)
+			.with_validator(|text| {
+				if text.trim().is_empty()
+					|| parse_date(text).is_some()
+				{
+					Validation::Ok
+				} else {
+					Validation::Error(
+						strings::COMMIT_DATE_INVALID.to_string(),
+					)
+				}
+			}),
+			author: CommitAuthor::default(),
 			key_config,
 			git_branch_name: cached::BranchName::new(repo.clone()),
 			commit_template: None,
+			lint: CommitLint::default(),
+			skip_undo_snapshot: false,
 			theme,
 			repo,
 		}
@@ -86,6 +162,16 @@ impl CommitComponent {
 		self.git_branch_name.lookup().ok();
 	}
 
+	///
+	pub fn update_async(&mut self, ev: AsyncNotification) {
+		self.input.update_completion(ev);
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.input.any_work_pending()
+	}
+
 	fn draw_branch_name<B: Backend>(&self, f: &mut Frame<B>) {
 		if let Some(name) = self.git_branch_name.last() {
 			let w = Paragraph::new(format!("{{{}}}", name))
@@ -102,24 +188,26 @@ impl CommitComponent {
 		}
 	}
 
+	/// lint warnings stacked up from the bottom of the input
 	fn draw_warnings<B: Backend>(&self, f: &mut Frame<B>) {
-		let first_line = self
-			.input
-			.get_text()
-			.lines()
-			.next()
-			.map(str::len)
-			.unwrap_or_default();
-
-		if first_line > FIRST_LINE_LIMIT {
-			let msg = strings::commit_first_line_warning(first_line);
+		let area = self.input.get_area();
+		let warnings = self.lint_warnings();
+
+		for (idx, warning) in warnings
+			.iter()
+			.rev()
+			.take(usize::from(area.height.saturating_sub(2)))
+			.enumerate()
+		{
+			let msg = strings::commit_lint_warning(warning);
 			let msg_length: u16 = msg.len().cast();
+			let idx: u16 = idx.cast();
 			let w =
 				Paragraph::new(msg).style(self.theme.text_danger());
 
 			let rect = {
-				let mut rect = self.input.get_area();
-				rect.y += rect.height.saturating_sub(1);
+				let mut rect = area;
+				rect.y += rect.height.saturating_sub(1 + idx);
 				rect.height = 1;
 				let offset =
 					rect.width.saturating_sub(msg_length + 1);
@@ -133,6 +221,40 @@ impl CommitComponent {
 		}
 	}
 
+	/// the author and date overrides in the bottom left corner
+	fn draw_author<B: Backend>(&self, f: &mut Frame<B>) {
+		if self.author == CommitAuthor::default() {
+			return;
+		}
+
+		let msg = strings::commit_author_override(
+			self.author
+				.identity
+				.as_ref()
+				.map(|(name, email)| format!("{} <{}>", name, email))
+				.as_deref(),
+			self.author
+				.time
+				.and_then(|(seconds, offset)| {
+					format_date(seconds, offset)
+				})
+				.as_deref(),
+		);
+		let w =
+			Paragraph::new(msg).style(self.theme.text(true, false));
+
+		let rect = {
+			let mut rect = self.input.get_area();
+			rect.y += rect.height.saturating_sub(1);
+			rect.height = 1;
+			rect.x += 1;
+			rect.width = rect.width.saturating_sub(2);
+			rect
+		};
+
+		f.render_widget(w, rect);
+	}
+
 	pub fn show_editor(&mut self) -> Result<()> {
 		let file_path = sync::repo_dir(&self.repo.borrow())?
 			.join("COMMIT_EDITMSG");
@@ -168,35 +290,77 @@ impl CommitComponent {
 		Ok(())
 	}
 
+	/// asks first if the current branch is protected
 	fn commit(&mut self) -> Result<()> {
-		let gpgsign =
-			get_config_string(&self.repo.borrow(), "commit.gpgsign")
-				.ok()
-				.flatten()
-				.and_then(|path| path.parse::<bool>().ok())
-				.unwrap_or_default();
+		if let Some(branch) = self.protected_branch() {
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::CommitProtected(branch),
+			));
 
-		if gpgsign {
-			anyhow::bail!("config commit.gpgsign=true detected.\ngpg signing not supported.\ndeactivate in your repo/gitconfig to be able to commit without signing.");
+			return Ok(());
+		}
+
+		self.commit_confirmed()
+	}
+
+	/// current branch if `gitui.protectedBranch` covers it
+	fn protected_branch(&self) -> Option<String> {
+		let branch = self.git_branch_name.last()?;
+
+		sync::protected_branches_config(&self.repo.borrow())
+			.ok()?
+			.protection(&branch)
+			.map(|_| branch)
+	}
+
+	///
+	pub fn commit_confirmed(&mut self) -> Result<()> {
+		let gpgsign = sync::gpgsign_enabled(&self.repo.borrow())?;
+
+		if gpgsign && !matches!(self.mode, Mode::Normal) {
+			anyhow::bail!("config commit.gpgsign=true detected.\ngpg signing is only supported for regular commits.\ndeactivate in your repo/gitconfig to be able to amend, merge or revert.");
 		}
 
 		let msg = self.input.get_text().to_string();
 
 		if matches!(
-			self.commit_with_msg(msg)?,
+			self.commit_with_msg(msg, gpgsign)?,
 			CommitResult::ComitDone
 		) {
-			self.hide();
-			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
-			self.input.clear();
+			self.commit_done();
 		}
 
 		Ok(())
 	}
 
+	/// amends once `Action::Amend` got confirmed, without taking an
+	/// undo snapshot if `skip_undo_snapshot`
+	pub fn amend_confirmed(
+		&mut self,
+		skip_undo_snapshot: bool,
+	) -> Result<()> {
+		self.skip_undo_snapshot = skip_undo_snapshot;
+		let res = self.commit_confirmed();
+		self.skip_undo_snapshot = false;
+
+		res
+	}
+
+	fn commit_done(&mut self) {
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		self.queue.push(InternalEvent::ShowToast(
+			ToastKind::Success,
+			strings::TOAST_COMMITTED.to_string(),
+		));
+		self.input.clear();
+		self.author = CommitAuthor::default();
+	}
+
 	fn commit_with_msg(
 		&mut self,
 		msg: String,
+		gpgsign: bool,
 	) -> Result<CommitResult> {
 		if let HookResult::NotOk(e) =
 			sync::hooks_pre_commit(&self.repo.borrow())?
@@ -221,9 +385,24 @@ impl CommitComponent {
 		}
 
 		match &self.mode {
-			Mode::Normal => sync::commit(&self.repo.borrow(), &msg)?,
+			Mode::Normal if gpgsign => {
+				return self.commit_signed(msg, None);
+			}
+			Mode::Normal => sync::commit_with_author(
+				&self.repo.borrow(),
+				&msg,
+				&self.author,
+			)?,
 			Mode::Amend(amend) => {
-				sync::amend(&self.repo.borrow(), *amend, &msg)?
+				if !self.amend_undo_snapshot() {
+					return Ok(CommitResult::Aborted);
+				}
+				sync::amend_with_author(
+					&self.repo.borrow(),
+					*amend,
+					&msg,
+					&self.author,
+				)?
 			}
 			Mode::Merge(ids) => {
 				sync::merge_commit(&self.repo.borrow(), &msg, ids)?
@@ -231,25 +410,162 @@ impl CommitComponent {
 			Mode::Revert => {
 				sync::commit_revert(&self.repo.borrow(), &msg)?
 			}
+			Mode::Paths(paths) => sync::commit_paths(
+				&self.repo.borrow(),
+				&msg,
+				&self.author,
+				paths,
+			)?,
 		};
 
-		if let HookResult::NotOk(e) =
-			sync::hooks_post_commit(&self.repo.borrow())?
-		{
-			log::error!("post-commit hook error: {}", e);
-			self.queue.push(InternalEvent::ShowErrorMsg(format!(
-				"post-commit hook error:\n{}",
-				e
-			)));
+		self.post_commit();
+
+		Ok(CommitResult::ComitDone)
+	}
+
+	/// takes the undo snapshot before amending, asks to amend
+	/// without one if that fails
+	fn amend_undo_snapshot(&self) -> bool {
+		if self.skip_undo_snapshot {
+			return true;
+		}
+
+		if let Err(e) = sync::undo_snapshot(
+			&self.repo.borrow(),
+			&UndoOperation::Amend,
+		) {
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::WithoutUndo(
+					Box::new(Action::Amend),
+					e.to_string(),
+				),
+			));
+			return false;
 		}
 
+		true
+	}
+
+	/// asks for the passphrase if gpg cannot sign on its own
+	fn commit_signed(
+		&mut self,
+		msg: String,
+		passphrase: Option<&str>,
+	) -> Result<CommitResult> {
+		match sync::commit_signed(
+			&self.repo.borrow(),
+			&msg,
+			&self.author,
+			passphrase,
+		) {
+			Ok(_) => (),
+			Err(Error::SignPassphraseRequired) => {
+				if passphrase.is_some() {
+					self.queue.push(InternalEvent::ShowToast(
+						ToastKind::Warning,
+						strings::TOAST_GPG_BAD_PASSPHRASE.to_string(),
+					));
+				}
+				self.pending_signed_msg = Some(msg);
+				self.input_passphrase.show()?;
+				return Ok(CommitResult::Aborted);
+			}
+			Err(e) => return Err(e.into()),
+		}
+
+		self.post_commit();
+
 		Ok(CommitResult::ComitDone)
 	}
 
-	fn can_commit(&self) -> bool {
+	fn commit_with_passphrase(&mut self) -> Result<()> {
+		let passphrase = self.input_passphrase.get_text().to_string();
+		self.input_passphrase.clear();
+		self.input_passphrase.hide();
+
+		if let Some(msg) = self.pending_signed_msg.take() {
+			if matches!(
+				self.commit_signed(msg, Some(&passphrase))?,
+				CommitResult::ComitDone
+			) {
+				self.commit_done();
+			}
+		}
+
+		Ok(())
+	}
+
+	fn post_commit(&self) {
+		self.queue
+			.push(InternalEvent::RunPostHook(PostHook::Commit));
+
+		if matches!(self.mode, Mode::Merge(_)) {
+			// concludes the merge `merge_branch` left for us
+			self.queue.push(InternalEvent::RunPostHook(
+				PostHook::Merge { squash: false },
+			));
+		}
+	}
+
+	/// merge and revert commits are always authored by the user
+	const fn can_edit_author(&self) -> bool {
+		matches!(
+			self.mode,
+			Mode::Normal | Mode::Amend(_) | Mode::Paths(_)
+		)
+	}
+
+	fn edit_author(&mut self) -> Result<()> {
+		self.input_author.set_text(
+			self.author
+				.identity
+				.as_ref()
+				.map(|(name, email)| format!("{} <{}>", name, email))
+				.unwrap_or_default(),
+		);
+		self.input_author.show()
+	}
+
+	/// takes the author and asks for the date next
+	fn confirm_author(&mut self) -> Result<()> {
+		self.author.identity =
+			parse_author(self.input_author.get_text());
+		self.input_author.hide();
+
+		self.input_date.set_text(
+			self.author
+				.time
+				.and_then(|(seconds, offset)| {
+					format_date(seconds, offset)
+				})
+				.unwrap_or_default(),
+		);
+		self.input_date.show()
+	}
+
+	fn confirm_date(&mut self) {
+		self.author.time = parse_date(self.input_date.get_text());
+		self.input_date.hide();
+	}
+
+	/// merge and revert messages are generated and not linted
+	fn lint_warnings(&self) -> Vec<LintWarning> {
+		match self.mode {
+			Mode::Normal | Mode::Amend(_) | Mode::Paths(_) => {
+				self.lint.lint(self.input.get_text())
+			}
+			Mode::Merge(_) | Mode::Revert => Vec::new(),
+		}
+	}
+
+	fn can_commit_anyway(&self) -> bool {
 		!self.is_empty() && self.is_changed()
 	}
 
+	fn can_commit(&self) -> bool {
+		self.can_commit_anyway() && self.lint_warnings().is_empty()
+	}
+
 	fn can_amend(&self) -> bool {
 		matches!(self.mode, Mode::Normal)
 			&& sync::get_head(&self.repo.borrow()).is_ok()
@@ -265,6 +581,23 @@ impl CommitComponent {
 			!= self.commit_template.as_ref().map(|s| s.trim())
 	}
 
+	/// commit only `paths`, other staged changes are left alone
+	pub fn open_paths(&mut self, paths: Vec<String>) -> Result<()> {
+		self.show()?;
+
+		if matches!(self.mode, Mode::Normal) {
+			self.input.set_title(strings::commit_title_paths(&paths));
+			self.mode = Mode::Paths(paths);
+		} else {
+			self.hide();
+			self.queue.push(InternalEvent::ShowErrorMsg(
+				strings::COMMIT_PATHS_UNAVAILABLE.to_string(),
+			));
+		}
+
+		Ok(())
+	}
+
 	fn amend(&mut self) -> Result<()> {
 		if self.can_amend() {
 			let id = sync::get_head(&self.repo.borrow())?;
@@ -294,6 +627,10 @@ impl DrawableComponent for CommitComponent {
 			self.input.draw(f, rect)?;
 			self.draw_branch_name(f);
 			self.draw_warnings(f);
+			self.draw_author(f);
+			self.input_passphrase.draw(f, rect)?;
+			self.input_author.draw(f, rect)?;
+			self.input_date.draw(f, rect)?;
 		}
 
 		Ok(())
@@ -306,6 +643,28 @@ impl Component for CommitComponent {
 		out: &mut Vec<CommandInfo>,
 		force_all: bool,
 	) -> CommandBlocking {
+		if self.input_passphrase.is_visible() {
+			out.clear();
+			out.push(CommandInfo::new(
+				strings::commands::validate_msg(&self.key_config),
+				true,
+				true,
+			));
+			return self.input_passphrase.commands(out, force_all);
+		}
+
+		for input in [&self.input_author, &self.input_date] {
+			if input.is_visible() {
+				out.clear();
+				out.push(CommandInfo::new(
+					strings::commands::validate_msg(&self.key_config),
+					input.is_valid(),
+					true,
+				));
+				return input.commands(out, force_all);
+			}
+		}
+
 		self.input.commands(out, force_all);
 
 		if self.is_visible() || force_all {
@@ -315,12 +674,24 @@ impl Component for CommitComponent {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::commit_anyway(&self.key_config),
+				true,
+				self.can_commit_anyway() && !self.can_commit(),
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::commit_amend(&self.key_config),
 				self.can_amend(),
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::commit_author(&self.key_config),
+				self.can_edit_author(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::commit_open_editor(
 					&self.key_config,
@@ -335,6 +706,56 @@ impl Component for CommitComponent {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
+			if self.input_passphrase.is_visible() {
+				if self.input_passphrase.event(ev)?.is_consumed() {
+					return Ok(EventState::Consumed);
+				}
+
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.enter) {
+						try_or_popup!(
+							self,
+							"commit error:",
+							self.commit_with_passphrase()
+						);
+					}
+				}
+
+				return Ok(EventState::Consumed);
+			}
+
+			if self.input_author.is_visible() {
+				if self.input_author.event(ev)?.is_consumed() {
+					return Ok(EventState::Consumed);
+				}
+
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.enter)
+						&& self.input_author.is_valid()
+					{
+						self.confirm_author()?;
+					}
+				}
+
+				return Ok(EventState::Consumed);
+			}
+
+			if self.input_date.is_visible() {
+				if self.input_date.event(ev)?.is_consumed() {
+					return Ok(EventState::Consumed);
+				}
+
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.enter)
+						&& self.input_date.is_valid()
+					{
+						self.confirm_date();
+					}
+				}
+
+				return Ok(EventState::Consumed);
+			}
+
 			if self.input.event(ev)?.is_consumed() {
 				return Ok(EventState::Consumed);
 			}
@@ -342,6 +763,16 @@ impl Component for CommitComponent {
 			if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.enter)
 					&& self.can_commit()
+				{
+					try_or_popup!(
+						self,
+						"commit error:",
+						self.commit()
+					);
+				} else if key_match(
+					e,
+					self.key_config.keys.commit_anyway,
+				) && self.can_commit_anyway()
 				{
 					try_or_popup!(
 						self,
@@ -354,6 +785,12 @@ impl Component for CommitComponent {
 				) && self.can_amend()
 				{
 					self.amend()?;
+				} else if key_match(
+					e,
+					self.key_config.keys.commit_author,
+				) && self.can_edit_author()
+				{
+					self.edit_author()?;
 				} else if key_match(
 					e,
 					self.key_config.keys.open_commit_editor,
@@ -390,6 +827,9 @@ impl Component for CommitComponent {
 
 		let repo_state = sync::repo_state(&self.repo.borrow())?;
 
+		self.lint = sync::commit_lint_config(&self.repo.borrow())
+			.unwrap_or_default();
+
 		self.mode = match repo_state {
 			RepoState::Merge => {
 				let ids = sync::mergehead_ids(&self.repo.borrow())?;
@@ -405,7 +845,7 @@ impl Component for CommitComponent {
 				Mode::Revert
 			}
 			_ => {
-				self.commit_template = get_config_string(
+				let template = get_config_string(
 					&self.repo.borrow(),
 					"commit.template",
 				)
@@ -413,6 +853,21 @@ impl Component for CommitComponent {
 				.flatten()
 				.and_then(|path| read_to_string(path).ok());
 
+				let prefix =
+					sync::commit_snippets_config(&self.repo.borrow())
+						.ok()
+						.zip(self.git_branch_name.lookup().ok())
+						.and_then(|(snippets, branch)| {
+							snippets.prefix(&branch)
+						});
+
+				self.commit_template = match (prefix, template) {
+					(Some(prefix), Some(template)) => {
+						Some(prefix + &template)
+					}
+					(prefix, template) => prefix.or(template),
+				};
+
 				if self.is_empty() {
 					if let Some(s) = &self.commit_template {
 						self.input.set_text(s.clone());
@@ -429,3 +884,91 @@ impl Component for CommitComponent {
 		Ok(())
 	}
 }
+
+/// `Name <email>`
+fn parse_author(text: &str) -> Option<(String, String)> {
+	let (name, email) =
+		text.trim().strip_suffix('>')?.split_once('<')?;
+	let (name, email) = (name.trim(), email.trim());
+
+	(!name.is_empty() && !email.is_empty() && !email.contains('>'))
+		.then(|| (name.to_string(), email.to_string()))
+}
+
+/// `YYYY-MM-DD HH:MM[:SS] [+HHMM]` into seconds since the epoch and utc
+/// offset in minutes, the local timezone is used if the offset is left out
+fn parse_date(text: &str) -> Option<(i64, i32)> {
+	const FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+
+	let text = text.trim();
+
+	let date = FORMATS
+		.iter()
+		.find_map(|format| {
+			DateTime::parse_from_str(text, &format!("{} %z", format))
+				.ok()
+		})
+		.or_else(|| {
+			FORMATS.iter().find_map(|format| {
+				let date =
+					NaiveDateTime::parse_from_str(text, format)
+						.ok()?;
+				Local
+					.from_local_datetime(&date)
+					.single()
+					.map(|date| date.with_timezone(date.offset()))
+			})
+		})?;
+
+	Some((date.timestamp(), date.offset().local_minus_utc() / 60))
+}
+
+fn format_date(seconds: i64, offset: i32) -> Option<String> {
+	let offset = FixedOffset::east_opt(offset.checked_mul(60)?)?;
+
+	Some(
+		offset
+			.timestamp_opt(seconds, 0)
+			.single()?
+			.format("%Y-%m-%d %H:%M:%S %z")
+			.to_string(),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_author() {
+		assert_eq!(
+			parse_author(" Jane Doe <jane@example.com> "),
+			Some((
+				String::from("Jane Doe"),
+				String::from("jane@example.com")
+			))
+		);
+		assert_eq!(parse_author("Jane Doe"), None);
+		assert_eq!(parse_author("<jane@example.com>"), None);
+		assert_eq!(parse_author("Jane <>"), None);
+	}
+
+	#[test]
+	fn test_parse_date() {
+		assert_eq!(
+			parse_date("2022-03-04 05:06:07 +0130"),
+			Some((1_646_364_967, 90))
+		);
+		assert_eq!(
+			parse_date("2022-03-04 05:06 -0100"),
+			Some((1_646_373_960, -60))
+		);
+		assert!(parse_date("2022-03-04 05:06").is_some());
+		assert_eq!(parse_date("yesterday"), None);
+
+		assert_eq!(
+			format_date(1_646_364_967, 90).as_deref(),
+			Some("2022-03-04 05:06:07 +0130")
+		);
+	}
+}
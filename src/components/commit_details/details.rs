@@ -2,17 +2,28 @@ use crate::{
 	components::{
 		commit_details::style::style_detail,
 		dialog_paragraph,
-		utils::{scroll_vertical::VerticalScroll, time_to_string},
+		utils::{
+			commit_cache::SharedCommitCache,
+			scroll_vertical::VerticalScroll, time_to_string,
+		},
 		CommandBlocking, CommandInfo, Component, DrawableComponent,
-		EventState, ScrollType,
+		EventState, ScrollType, SharedOptions,
 	},
 	keys::{key_match, SharedKeyConfig},
+	string_utils::bidi_visual_order,
 	strings::{self, order},
-	ui::style::SharedTheme,
+	ui::{
+		avatar::{avatar_rows, AVATAR_SIZE},
+		style::SharedTheme,
+	},
 };
 use anyhow::Result;
-use asyncgit::sync::{
-	self, CommitDetails, CommitId, CommitMessage, RepoPathRef, Tag,
+use asyncgit::{
+	sync::{
+		self, CommitDetails, CommitId, CommitMessage, RepoPathRef,
+		Tag,
+	},
+	CommitDescription, ContainedIn,
 };
 use crossterm::event::Event;
 use std::clone::Clone;
@@ -30,9 +41,14 @@ use super::style::Detail;
 
 pub struct DetailsComponent {
 	repo: RepoPathRef,
+	commit_cache: SharedCommitCache,
 	data: Option<CommitDetails>,
 	tags: Vec<Tag>,
+	contained_in: Option<ContainedIn>,
+	/// `git describe` and notes of the commit in `data`
+	description: Option<CommitDescription>,
 	theme: SharedTheme,
+	options: SharedOptions,
 	focused: bool,
 	current_width: Cell<u16>,
 	scroll: VerticalScroll,
@@ -40,6 +56,10 @@ pub struct DetailsComponent {
 	key_config: SharedKeyConfig,
 }
 
+/// two columns per avatar cell plus borders
+#[allow(clippy::cast_possible_truncation)]
+const AVATAR_WIDTH: u16 = (AVATAR_SIZE * 2 + 2) as u16;
+
 type WrappedCommitMessage<'a> =
 	(Vec<Cow<'a, str>>, Vec<Cow<'a, str>>);
 
@@ -47,15 +67,21 @@ impl DetailsComponent {
 	///
 	pub const fn new(
 		repo: RepoPathRef,
+		commit_cache: SharedCommitCache,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 		focused: bool,
 	) -> Self {
 		Self {
 			repo,
+			commit_cache,
 			data: None,
 			tags: Vec::new(),
+			contained_in: None,
+			description: None,
 			theme,
+			options,
 			focused,
 			scroll_to_bottom_next_draw: Cell::new(false),
 			current_width: Cell::new(0),
@@ -70,9 +96,14 @@ impl DetailsComponent {
 		tags: Option<CommitTags>,
 	) {
 		self.tags.clear();
+		self.contained_in = None;
+		self.description = None;
 
 		self.data = id.and_then(|id| {
-			sync::get_commit_details(&self.repo.borrow(), id).ok()
+			self.commit_cache
+				.borrow_mut()
+				.commit_details(&self.repo.borrow(), id)
+				.ok()
 		});
 
 		self.scroll.reset();
@@ -82,6 +113,46 @@ impl DetailsComponent {
 		}
 	}
 
+	/// describe and note of the commit, once looked up
+	pub fn set_description(
+		&mut self,
+		description: Option<CommitDescription>,
+	) {
+		self.description = description;
+	}
+
+	/// branches and tags containing the commit, once looked up
+	pub fn set_contained_in(
+		&mut self,
+		contained_in: Option<ContainedIn>,
+	) {
+		self.contained_in = contained_in;
+	}
+
+	fn contained_in_spans(&self) -> Option<Spans> {
+		let contained_in = self.contained_in.as_ref()?;
+		let names = contained_in
+			.branches
+			.iter()
+			.cloned()
+			.chain(contained_in.tags.iter().map(|tag| {
+				strings::commit::details_contained_in_tag(tag)
+			}))
+			.collect::<Vec<_>>();
+
+		if names.is_empty() {
+			return None;
+		}
+
+		Some(Spans::from(vec![
+			style_detail(&self.theme, &Detail::ContainedIn),
+			Span::styled(
+				Cow::from(names.join(", ")),
+				self.theme.text(true, false),
+			),
+		]))
+	}
+
 	fn wrap_commit_details(
 		message: &CommitMessage,
 		width: usize,
@@ -129,6 +200,28 @@ impl DetailsComponent {
 		}
 	}
 
+	/// note shown below the message: empty line, header, note
+	fn get_wrapped_note(&self, width: usize) -> Vec<Cow<'_, str>> {
+		let note = self
+			.description
+			.as_ref()
+			.and_then(|description| description.note.as_ref());
+
+		note.map_or_else(Vec::new, |note| {
+			let mut lines = vec![
+				Cow::from(""),
+				Cow::from(strings::commit::details_notes()),
+			];
+			lines.extend(textwrap::wrap(note, width));
+			lines
+		})
+	}
+
+	fn number_of_lines(&self, width: usize) -> usize {
+		Self::get_number_of_lines(&self.data, width)
+			+ self.get_wrapped_note(width).len()
+	}
+
 	fn get_wrapped_text_message(
 		&self,
 		width: usize,
@@ -136,8 +229,11 @@ impl DetailsComponent {
 	) -> Vec<Spans> {
 		let (wrapped_title, wrapped_message) =
 			Self::get_wrapped_lines(&self.data, width);
+		let wrapped_note = self.get_wrapped_note(width);
+		let note_header =
+			wrapped_title.len() + wrapped_message.len() + 1;
 
-		[&wrapped_title[..], &wrapped_message[..]]
+		[&wrapped_title[..], &wrapped_message[..], &wrapped_note[..]]
 			.concat()
 			.iter()
 			.enumerate()
@@ -145,8 +241,12 @@ impl DetailsComponent {
 			.take(height)
 			.map(|(i, line)| {
 				Spans::from(vec![Span::styled(
-					line.clone(),
-					self.get_theme_for_line(i < wrapped_title.len()),
+					bidi_visual_order(line).into_owned(),
+					self.get_theme_for_line(
+						i < wrapped_title.len()
+							|| (!wrapped_note.is_empty()
+								&& i == note_header),
+					),
 				)])
 			})
 			.collect()
@@ -212,6 +312,22 @@ impl DetailsComponent {
 					Cow::from(data.hash.clone()),
 					self.theme.text(true, false),
 				),
+				Span::styled(
+					Cow::from(
+						self.description
+							.as_ref()
+							.and_then(|description| {
+								description.describe.as_ref()
+							})
+							.map(|description| {
+								strings::commit::details_describe(
+									description,
+								)
+							})
+							.unwrap_or_default(),
+					),
+					self.theme.text(false, false),
+				),
 			]));
 
 			if !self.tags.is_empty() {
@@ -237,10 +353,16 @@ impl DetailsComponent {
 				));
 			}
 
+			res.extend(self.contained_in_spans());
+
 			res
 		})
 	}
 
+	fn show_avatar(&self) -> bool {
+		self.options.borrow().log_show_avatars && self.data.is_some()
+	}
+
 	fn move_scroll_top(&mut self, move_type: ScrollType) -> bool {
 		if self.data.is_some() {
 			self.scroll.move_top(move_type)
@@ -262,10 +384,25 @@ impl DrawableComponent for DetailsComponent {
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
-				[Constraint::Length(8), Constraint::Min(10)].as_ref(),
+				[Constraint::Length(9), Constraint::Min(10)].as_ref(),
 			)
 			.split(rect);
 
+		let info_chunks = Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints(
+				[
+					Constraint::Min(0),
+					Constraint::Length(if self.show_avatar() {
+						AVATAR_WIDTH
+					} else {
+						0
+					}),
+				]
+				.as_ref(),
+			)
+			.split(chunks[0]);
+
 		f.render_widget(
 			dialog_paragraph(
 				&strings::commit::details_info_title(
@@ -275,9 +412,25 @@ impl DrawableComponent for DetailsComponent {
 				&self.theme,
 				false,
 			),
-			chunks[0],
+			info_chunks[0],
 		);
 
+		if self.show_avatar() {
+			if let Some(data) = &self.data {
+				f.render_widget(
+					dialog_paragraph(
+						"",
+						self.theme.colored_text(Text::from(
+							avatar_rows(&data.author.email),
+						)),
+						&self.theme,
+						false,
+					),
+					info_chunks[1],
+				);
+			}
+		}
+
 		// We have to take the border into account which is one character on
 		// each side.
 		let border_width: u16 = 2;
@@ -288,7 +441,7 @@ impl DrawableComponent for DetailsComponent {
 		self.current_width.set(width);
 
 		let number_of_lines =
-			Self::get_number_of_lines(&self.data, usize::from(width));
+			self.number_of_lines(usize::from(width));
 
 		self.scroll.update_no_selection(
 			number_of_lines,
@@ -340,8 +493,7 @@ impl Component for DetailsComponent {
 		force_all: bool,
 	) -> CommandBlocking {
 		let width = usize::from(self.current_width.get());
-		let number_of_lines =
-			Self::get_number_of_lines(&self.data, width);
+		let number_of_lines = self.number_of_lines(width);
 
 		out.push(
 			CommandInfo::new(
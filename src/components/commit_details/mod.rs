@@ -8,15 +8,23 @@ use super::{
 };
 use crate::{
 	accessors,
+	components::{
+		utils::{
+			commit_cache::SharedCommitCache,
+			file_copy::{copy_file, FileCopy},
+		},
+		SharedOptions,
+	},
 	keys::{key_match, SharedKeyConfig},
-	queue::Queue,
+	queue::{InternalEvent, Queue},
 	strings,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{CommitTags, RepoPathRef},
-	AsyncCommitFiles, AsyncGitNotification, CommitFilesParams,
+	sync::{self, notes, CommitId, CommitTags, RepoPathRef},
+	AsyncCommitFiles, AsyncContainedIn, AsyncDescribe,
+	AsyncGitNotification, CommitFilesParams, DescribeParams,
 };
 use compare_details::CompareDetailsComponent;
 use crossbeam_channel::Sender;
@@ -28,13 +36,20 @@ use tui::{
 	Frame,
 };
 
+/// commits touching more files than this start with folders collapsed
+const COLLAPSE_FILES_THRESHOLD: usize = 50;
+
 pub struct CommitDetailsComponent {
 	commit: Option<CommitFilesParams>,
 	single_details: DetailsComponent,
 	compare_details: CompareDetailsComponent,
 	file_tree: StatusTreeComponent,
 	git_commit_files: AsyncCommitFiles,
+	git_contained_in: AsyncContainedIn,
+	git_describe: AsyncDescribe,
 	visible: bool,
+	repo: RepoPathRef,
+	queue: Queue,
 	key_config: SharedKeyConfig,
 }
 
@@ -46,14 +61,18 @@ impl CommitDetailsComponent {
 		repo: &RepoPathRef,
 		queue: &Queue,
 		sender: &Sender<AsyncGitNotification>,
+		commit_cache: &SharedCommitCache,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			single_details: DetailsComponent::new(
 				repo.clone(),
+				commit_cache.clone(),
 				theme.clone(),
 				key_config.clone(),
+				options,
 				false,
 			),
 			compare_details: CompareDetailsComponent::new(
@@ -65,6 +84,14 @@ impl CommitDetailsComponent {
 				repo.borrow().clone(),
 				sender,
 			),
+			git_contained_in: AsyncContainedIn::new(
+				repo.borrow().clone(),
+				sender,
+			),
+			git_describe: AsyncDescribe::new(
+				repo.borrow().clone(),
+				sender,
+			),
 			file_tree: StatusTreeComponent::new(
 				"",
 				false,
@@ -74,6 +101,8 @@ impl CommitDetailsComponent {
 			),
 			visible: false,
 			commit: None,
+			repo: repo.clone(),
+			queue: queue.clone(),
 			key_config,
 		}
 	}
@@ -108,13 +137,32 @@ impl CommitDetailsComponent {
 			} else {
 				self.single_details
 					.set_commit(Some(id.id), tags.clone());
+				self.single_details.set_contained_in(
+					self.git_contained_in.request(id.id)?,
+				);
+				self.single_details.set_description(
+					self.git_describe.request(DescribeParams {
+						commit: id.id,
+						notes_tip: notes::notes_tip(
+							&self.repo.borrow(),
+						)
+						.unwrap_or_default(),
+					})?,
+				);
 			}
 
 			if let Some((fetched_id, res)) =
 				self.git_commit_files.current()?
 			{
 				if fetched_id == id {
+					let first_load = self.file_tree.is_empty();
 					self.file_tree.update(res.as_slice())?;
+					if first_load
+						&& self.file_tree.file_count()
+							> COLLAPSE_FILES_THRESHOLD
+					{
+						self.file_tree.collapse_all();
+					}
 					self.file_tree.set_title(self.get_files_title());
 
 					return Ok(());
@@ -133,6 +181,8 @@ impl CommitDetailsComponent {
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.git_commit_files.is_pending()
+			|| self.git_contained_in.is_pending()
+			|| self.git_describe.is_pending()
 	}
 
 	///
@@ -156,6 +206,31 @@ impl CommitDetailsComponent {
 	fn is_compare(&self) -> bool {
 		self.commit.map(|p| p.other.is_some()).unwrap_or_default()
 	}
+
+	/// the single commit shown, `None` while comparing
+	fn single_commit(&self) -> Option<CommitId> {
+		self.commit.filter(|p| p.other.is_none()).map(|p| p.id)
+	}
+
+	/// the content is only known for a single commit
+	fn copy(&self, copy: FileCopy) -> bool {
+		self.file_tree.selection().map_or(false, |item| {
+			let path = item.info.full_path;
+			let commit = self
+				.single_commit()
+				.filter(|_| self.file_tree.is_file_seleted());
+
+			let repo = self.repo.borrow();
+			copy_file(&self.queue, &repo, &path, copy, || {
+				let commit = commit.ok_or_else(|| {
+					anyhow::anyhow!("no content for '{}'", path)
+				})?;
+				Ok(sync::commit_file_content(&repo, commit, &path)?)
+			});
+
+			true
+		})
+	}
 }
 
 impl DrawableComponent for CommitDetailsComponent {
@@ -214,6 +289,31 @@ impl Component for CommitDetailsComponent {
 				force_all,
 				self.components().as_slice(),
 			);
+
+			let can_ref =
+				self.focused() && self.single_commit().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::log_tag_commit(&self.key_config),
+				true,
+				can_ref || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::create_branch_at_commit(
+					&self.key_config,
+				),
+				true,
+				can_ref || force_all,
+			));
+
+			FileCopy::commands(
+				out,
+				&self.key_config,
+				self.file_tree.selection().is_some(),
+				self.file_tree.selection_file().is_some()
+					&& self.single_commit().is_some(),
+				self.file_tree.focused() || force_all,
+			);
 		}
 
 		CommandBlocking::PassingOn
@@ -249,6 +349,36 @@ impl Component for CommitDetailsComponent {
 					self.file_tree.focus(false);
 					self.set_details_focus(true);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.log_tag_commit,
+				) {
+					Ok(self
+						.single_commit()
+						.map(|id| {
+							self.queue
+								.push(InternalEvent::TagCommit(id));
+						})
+						.is_some()
+						.into())
+				} else if key_match(
+					e,
+					self.key_config.keys.create_branch,
+				) {
+					Ok(self
+						.single_commit()
+						.map(|id| {
+							self.queue.push(
+								InternalEvent::CreateBranchAt(id),
+							);
+						})
+						.is_some()
+						.into())
+				} else if let Some(copy) =
+					FileCopy::from_key(e, &self.key_config)
+						.filter(|_| self.file_tree.focused())
+				{
+					Ok(self.copy(copy).into())
 				} else {
 					Ok(EventState::NotConsumed)
 				};
@@ -7,6 +7,7 @@ pub enum Detail {
 	Date,
 	Commiter,
 	Sha,
+	ContainedIn,
 	Message,
 }
 
@@ -31,6 +32,10 @@ pub fn style_detail<'a>(
 			Cow::from(strings::commit::details_tags()),
 			theme.text(false, false),
 		),
+		Detail::ContainedIn => Span::styled(
+			Cow::from(strings::commit::details_contained_in()),
+			theme.text(false, false),
+		),
 		Detail::Message => Span::styled(
 			Cow::from(strings::commit::details_message()),
 			theme.text(false, false),
@@ -1,44 +1,56 @@
 use super::utils::logitems::{ItemBatch, LogEntry};
 use crate::{
 	components::{
-		utils::string_width_align, CommandBlocking, CommandInfo,
-		Component, DrawableComponent, EventState, ScrollType,
+		utils::scroll_vertical::VerticalScroll, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+		ScrollType,
 	},
 	keys::{key_match, SharedKeyConfig},
+	string_utils::{bidi_visual_order, fit_to_width, pad_to_width},
 	strings::{self, symbol},
+	ui::draw_scrollbar,
 	ui::style::{SharedTheme, Theme},
-	ui::{calc_scroll_top, draw_scrollbar},
 };
 use anyhow::Result;
-use asyncgit::sync::{CommitId, Tags};
+use asyncgit::sync::{
+	ci::{CiState, CiStatus},
+	CommitId, Tags,
+};
 use chrono::{DateTime, Local};
 use crossterm::event::Event;
 use itertools::Itertools;
 use std::{
-	borrow::Cow, cell::Cell, cmp, convert::TryFrom, time::Instant,
+	borrow::Cow, cell::Cell, cmp, collections::HashMap,
+	convert::TryFrom, time::Instant,
 };
 use tui::{
 	backend::Backend,
 	layout::{Alignment, Rect},
+	style::{Modifier, Style},
 	text::{Span, Spans},
 	widgets::{Block, Borders, Paragraph},
 	Frame,
 };
 
-const ELEMENTS_PER_LINE: usize = 9;
+const ELEMENTS_PER_LINE: usize = 11;
 
 ///
 pub struct CommitList {
 	title: Box<str>,
 	selection: usize,
 	branch: Option<String>,
+	/// `git describe` of HEAD
+	head_description: Option<String>,
 	count_total: usize,
+	/// commits are still being loaded
+	pending: bool,
 	items: ItemBatch,
 	marked: Vec<CommitId>,
 	scroll_state: (Instant, f32),
 	tags: Option<Tags>,
+	ci_status: HashMap<CommitId, CiStatus>,
 	current_size: Cell<(u16, u16)>,
-	scroll_top: Cell<usize>,
+	scroll: VerticalScroll,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 }
@@ -55,11 +67,14 @@ impl CommitList {
 			marked: Vec::with_capacity(2),
 			selection: 0,
 			branch: None,
+			head_description: None,
 			count_total: 0,
+			pending: false,
 			scroll_state: (Instant::now(), 0_f32),
 			tags: None,
+			ci_status: HashMap::new(),
 			current_size: Cell::new((0, 0)),
-			scroll_top: Cell::new(0),
+			scroll: VerticalScroll::new(),
 			theme,
 			key_config,
 			title: title.into(),
@@ -71,11 +86,24 @@ impl CommitList {
 		&mut self.items
 	}
 
+	/// shows a placeholder while there are no commits yet
+	pub fn set_pending(&mut self, pending: bool) {
+		self.pending = pending;
+	}
+
 	///
 	pub fn set_branch(&mut self, name: Option<String>) {
 		self.branch = name;
 	}
 
+	///
+	pub fn set_head_description(
+		&mut self,
+		description: Option<String>,
+	) {
+		self.head_description = description;
+	}
+
 	///
 	pub const fn selection(&self) -> usize {
 		self.selection
@@ -109,6 +137,19 @@ impl CommitList {
 		self.items.clear();
 	}
 
+	/// badges for the commits in `ci_status`
+	pub fn set_ci_status(
+		&mut self,
+		ci_status: HashMap<CommitId, CiStatus>,
+	) {
+		self.ci_status = ci_status;
+	}
+
+	///
+	pub fn ci_status(&self, id: &CommitId) -> Option<&CiStatus> {
+		self.ci_status.get(id)
+	}
+
 	///
 	pub fn set_tags(&mut self, tags: Tags) {
 		self.tags = Some(tags);
@@ -158,28 +199,12 @@ impl CommitList {
 		#[allow(clippy::cast_possible_truncation)]
 		let speed_int = usize::try_from(self.scroll_state.1 as i64)?.max(1);
 
-		let page_offset =
-			usize::from(self.current_size.get().1).saturating_sub(1);
-
-		let new_selection = match scroll {
-			ScrollType::Up => {
-				self.selection.saturating_sub(speed_int)
-			}
-			ScrollType::Down => {
-				self.selection.saturating_add(speed_int)
-			}
-			ScrollType::PageUp => {
-				self.selection.saturating_sub(page_offset)
-			}
-			ScrollType::PageDown => {
-				self.selection.saturating_add(page_offset)
-			}
-			ScrollType::Home => 0,
-			ScrollType::End => self.selection_max(),
-		};
-
-		let new_selection =
-			cmp::min(new_selection, self.selection_max());
+		let new_selection = self.scroll.move_selection(
+			self.selection,
+			scroll,
+			speed_int,
+			self.selection_max(),
+		);
 
 		let needs_update = new_selection != self.selection;
 
@@ -232,6 +257,7 @@ impl CommitList {
 		}
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	fn get_entry_to_add<'a>(
 		e: &'a LogEntry,
 		selected: bool,
@@ -240,9 +266,13 @@ impl CommitList {
 		width: usize,
 		now: DateTime<Local>,
 		marked: Option<bool>,
+		ci_column: bool,
+		ci: Option<CiState>,
 	) -> Spans<'a> {
 		let mut txt: Vec<Span> = Vec::with_capacity(
-			ELEMENTS_PER_LINE + if marked.is_some() { 2 } else { 0 },
+			ELEMENTS_PER_LINE
+				+ if marked.is_some() { 2 } else { 0 }
+				+ if ci_column { 2 } else { 0 },
 		);
 
 		let splitter_txt = Cow::from(symbol::EMPTY_SPACE);
@@ -262,6 +292,25 @@ impl CommitList {
 			txt.push(splitter.clone());
 		}
 
+		// ci badge
+		if ci_column {
+			txt.push(ci.map_or_else(
+				|| {
+					Span::styled(
+						Cow::from(symbol::EMPTY_SPACE),
+						theme.text(true, selected),
+					)
+				},
+				|state| {
+					Span::styled(
+						Cow::from(ci_symbol(state)),
+						theme.ci_state(state, selected),
+					)
+				},
+			));
+			txt.push(splitter.clone());
+		}
+
 		// commit hash
 		txt.push(Span::styled(
 			Cow::from(&*e.hash_short),
@@ -278,9 +327,24 @@ impl CommitList {
 
 		txt.push(splitter.clone());
 
+		// author badge
+		txt.push(Span::styled(
+			fit_to_width(&e.author_badge.0, 2),
+			theme
+				.commit_author(selected)
+				.patch(
+					theme.colored(
+						Style::default().fg(e.author_badge.1),
+					),
+				)
+				.add_modifier(Modifier::BOLD),
+		));
+
+		txt.push(splitter.clone());
+
 		let author_width =
-			(width.saturating_sub(19) / 3).max(3).min(20);
-		let author = string_width_align(&e.author, author_width);
+			(width.saturating_sub(22) / 3).max(3).min(20);
+		let author = fit_to_width(&e.author, author_width);
 
 		// commit author
 		txt.push(Span::styled::<String>(
@@ -300,13 +364,12 @@ impl CommitList {
 
 		txt.push(splitter);
 
-		let message_width = width.saturating_sub(
-			txt.iter().map(|span| span.content.len()).sum(),
-		);
+		let message_width =
+			width.saturating_sub(txt.iter().map(Span::width).sum());
 
 		// commit msg
 		txt.push(Span::styled(
-			format!("{:w$}", &e.msg, w = message_width),
+			pad_to_width(&bidi_visual_order(&e.msg), message_width),
 			theme.text(true, selected),
 		));
 
@@ -314,6 +377,13 @@ impl CommitList {
 	}
 
 	fn get_text(&self, height: usize, width: usize) -> Vec<Spans> {
+		if self.pending && self.items.iter().next().is_none() {
+			return vec![Spans::from(Span::styled(
+				strings::loading_text(&self.key_config),
+				self.theme.text(false, false),
+			))];
+		}
+
 		let selection = self.relative_selection();
 
 		let mut txt: Vec<Spans> = Vec::with_capacity(height);
@@ -321,11 +391,12 @@ impl CommitList {
 		let now = Local::now();
 
 		let any_marked = !self.marked.is_empty();
+		let any_ci = !self.ci_status.is_empty();
 
 		for (idx, e) in self
 			.items
 			.iter()
-			.skip(self.scroll_top.get())
+			.skip(self.scroll.get_top())
 			.take(height)
 			.enumerate()
 		{
@@ -342,12 +413,14 @@ impl CommitList {
 
 			txt.push(Self::get_entry_to_add(
 				e,
-				idx + self.scroll_top.get() == selection,
+				idx + self.scroll.get_top() == selection,
 				tags,
 				&self.theme,
 				width,
 				now,
 				marked,
+				any_ci,
+				self.ci_status.get(&e.id).map(|status| status.state),
 			));
 		}
 
@@ -364,6 +437,15 @@ impl CommitList {
 	}
 }
 
+/// badge of a commit or check in `state`
+pub const fn ci_symbol(state: CiState) -> &'static str {
+	match state {
+		CiState::Success => symbol::CHECKMARK,
+		CiState::Pending => symbol::CIRCLE,
+		CiState::Failure => symbol::BALLOT_X,
+	}
+}
+
 impl DrawableComponent for CommitList {
 	fn draw<B: Backend>(
 		&self,
@@ -379,14 +461,19 @@ impl DrawableComponent for CommitList {
 		let height_in_lines = self.current_size.get().1 as usize;
 		let selection = self.relative_selection();
 
-		self.scroll_top.set(calc_scroll_top(
-			self.scroll_top.get(),
-			height_in_lines,
+		self.scroll.update(
 			selection,
-		));
+			self.count_total
+				.saturating_sub(self.items.index_offset()),
+			height_in_lines,
+		);
 
-		let branch_post_fix =
-			self.branch.as_ref().map(|b| format!("- {{{}}}", b));
+		let branch_post_fix = self.branch.as_ref().map(|b| {
+			self.head_description.as_ref().map_or_else(
+				|| format!("- {{{}}}", b),
+				|description| format!("- {{{}}} {}", b, description),
+			)
+		});
 
 		let title = format!(
 			"{} {}/{} {}",
@@ -495,22 +582,19 @@ mod tests {
 
 	#[test]
 	fn test_string_width_align() {
-		assert_eq!(string_width_align("123", 3), "123");
-		assert_eq!(string_width_align("123", 2), "..");
-		assert_eq!(string_width_align("123", 3), "123");
-		assert_eq!(string_width_align("12345", 6), "12345 ");
-		assert_eq!(string_width_align("1234556", 4), "12..");
+		assert_eq!(fit_to_width("123", 3), "123");
+		assert_eq!(fit_to_width("123", 2), "..");
+		assert_eq!(fit_to_width("123", 3), "123");
+		assert_eq!(fit_to_width("12345", 6), "12345 ");
+		assert_eq!(fit_to_width("1234556", 4), "12..");
 	}
 
 	#[test]
 	fn test_string_width_align_unicode() {
-		assert_eq!(string_width_align("äste", 3), "ä..");
-		assert_eq!(
-			string_width_align("wüsten äste", 10),
-			"wüsten ä.."
-		);
+		assert_eq!(fit_to_width("äste", 3), "ä..");
+		assert_eq!(fit_to_width("wüsten äste", 10), "wüsten ä..");
 		assert_eq!(
-			string_width_align("Jon Grythe Stødle", 19),
+			fit_to_width("Jon Grythe Stødle", 19),
 			"Jon Grythe Stødle  "
 		);
 	}
@@ -2,6 +2,7 @@ use super::{
 	command_pump, event_pump, visibility_blocking, CommandBlocking,
 	CommandInfo, CommitDetailsComponent, Component, DiffComponent,
 	DrawableComponent, EventState, InspectCommitOpen,
+	SharedCommitCache, SharedOptions,
 };
 use crate::{
 	accessors,
@@ -174,8 +175,10 @@ impl CompareCommitsComponent {
 		repo: &RepoPathRef,
 		queue: &Queue,
 		sender: &Sender<AsyncGitNotification>,
+		commit_cache: &SharedCommitCache,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: &SharedOptions,
 	) -> Self {
 		Self {
 			repo: repo.clone(),
@@ -183,14 +186,17 @@ impl CompareCommitsComponent {
 				repo,
 				queue,
 				sender,
+				commit_cache,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			diff: DiffComponent::new(
 				repo.clone(),
 				queue.clone(),
 				theme,
 				key_config.clone(),
+				options.clone(),
 				true,
 			),
 			open_request: None,
@@ -260,7 +266,10 @@ impl CompareCommitsComponent {
 					let diff_params = DiffParams {
 						path: f.path.clone(),
 						diff_type: DiffType::Commits(ids),
-						options: DiffOptions::default(),
+						options: self.diff.diff_options(
+							&f.path,
+							DiffOptions::default(),
+						),
 					};
 
 					if let Some((params, last)) =
@@ -0,0 +1,429 @@
+use crate::{strings, ui::style::SharedTheme, AsyncNotification};
+use asyncgit::sync::{get_branches_info, RepoPathRef};
+use easy_cast::Cast;
+use std::cell::Cell;
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+const MAX_ROWS: usize = 8;
+
+/// candidate offered by a `CompletionProvider`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+	/// replaces the token left of the cursor
+	pub text: String,
+	/// shown in the popup
+	pub label: String,
+	/// shown dimmed after the `label`
+	pub detail: String,
+}
+
+impl Completion {
+	///
+	pub fn new(text: String) -> Self {
+		Self {
+			label: text.clone(),
+			text,
+			detail: String::new(),
+		}
+	}
+}
+
+/// source of completions for a `TextInputComponent`
+pub trait CompletionProvider {
+	/// byte offset in `text` (everything left of the cursor) where
+	/// the token to complete starts, `None` if there is nothing to
+	/// complete at the cursor
+	fn token_start(&self, text: &str) -> Option<usize>;
+
+	/// candidates for `token`
+	fn candidates(&mut self, token: &str) -> Vec<Completion>;
+
+	/// called whenever the input gets shown
+	fn refresh(&mut self) {}
+
+	/// returns `true` if the candidates changed
+	fn update(&mut self, _ev: AsyncNotification) -> bool {
+		false
+	}
+
+	///
+	fn any_work_pending(&self) -> bool {
+		false
+	}
+
+	/// select the first candidate right away so `enter` inserts it
+	fn preselect(&self) -> bool {
+		false
+	}
+}
+
+/// list of candidates drawn attached to a text input
+pub struct CompletionPopup {
+	provider: Box<dyn CompletionProvider>,
+	items: Vec<Completion>,
+	token_len: usize,
+	selection: Option<usize>,
+	visible: bool,
+}
+
+impl CompletionPopup {
+	///
+	pub fn new(provider: Box<dyn CompletionProvider>) -> Self {
+		Self {
+			provider,
+			items: Vec::new(),
+			token_len: 0,
+			selection: None,
+			visible: false,
+		}
+	}
+
+	///
+	pub const fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	///
+	pub fn hide(&mut self) {
+		self.visible = false;
+		self.selection = None;
+	}
+
+	/// input got shown
+	pub fn reset(&mut self) {
+		self.hide();
+		self.provider.refresh();
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.provider.any_work_pending()
+	}
+
+	/// recomputes the candidates after the text left of the
+	/// cursor (`text`) changed
+	pub fn refresh(&mut self, text: &str) {
+		let token =
+			if let Some(start) = self.provider.token_start(text) {
+				&text[start..]
+			} else {
+				self.hide();
+				return;
+			};
+
+		self.token_len = token.len();
+		self.items = self.provider.candidates(token);
+		self.visible = !self.items.is_empty()
+			|| self.provider.any_work_pending();
+		self.selection = (self.provider.preselect()
+			&& !self.items.is_empty())
+		.then(|| 0);
+	}
+
+	///
+	pub fn update(&mut self, ev: AsyncNotification, text: &str) {
+		if self.provider.update(ev) && self.visible {
+			self.refresh(text);
+		}
+	}
+
+	///
+	pub fn move_selection(&mut self, up: bool) {
+		let max = self.items.len().saturating_sub(1);
+		self.selection = Some(match self.selection {
+			None if up => max,
+			None => 0,
+			Some(idx) if up => idx.saturating_sub(1),
+			Some(idx) => idx.saturating_add(1).min(max),
+		});
+	}
+
+	/// `(token length, replacement)` of the selected candidate,
+	/// falls back to the first one unless `selected_only` is set
+	pub fn accept(
+		&mut self,
+		selected_only: bool,
+	) -> Option<(usize, String)> {
+		let idx = match self.selection {
+			Some(idx) => idx,
+			None if selected_only => return None,
+			None => 0,
+		};
+
+		let text = self.items.get(idx)?.text.clone();
+		self.hide();
+
+		Some((self.token_len, text))
+	}
+
+	/// draws the list below `anchor`, or above if there is no room
+	pub fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		anchor: Rect,
+		theme: &SharedTheme,
+	) {
+		if !self.visible {
+			return;
+		}
+
+		let rows: u16 = self.items.len().clamp(1, MAX_ROWS).cast();
+		let height = rows + 2;
+
+		let below = anchor.y.saturating_add(anchor.height);
+		let y = if below.saturating_add(height) <= f.size().height {
+			below
+		} else {
+			anchor.y.saturating_sub(height)
+		};
+
+		let area = Rect::new(anchor.x, y, anchor.width, height)
+			.intersection(f.size());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Paragraph::new(self.get_text(
+				theme,
+				area.width.saturating_sub(2).into(),
+			))
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick),
+			),
+			area,
+		);
+	}
+
+	fn get_text(
+		&self,
+		theme: &SharedTheme,
+		width: usize,
+	) -> Vec<Spans<'_>> {
+		if self.items.is_empty() {
+			return vec![Spans::from(strings::COMPLETION_LOADING)];
+		}
+
+		let selection = self.selection.unwrap_or_default();
+		let skip = selection.saturating_sub(MAX_ROWS - 1);
+		let label_width = self
+			.items
+			.iter()
+			.map(|item| item.label.chars().count())
+			.max()
+			.unwrap_or_default();
+
+		self.items
+			.iter()
+			.enumerate()
+			.skip(skip)
+			.take(MAX_ROWS)
+			.map(|(idx, item)| {
+				let selected = self.selection == Some(idx);
+				let label =
+					format!("{:<1$} ", item.label, label_width);
+				let detail: String = item
+					.detail
+					.chars()
+					.take(width.saturating_sub(label.chars().count()))
+					.collect();
+
+				Spans::from(vec![
+					Span::styled(label, theme.text(true, selected)),
+					Span::styled(detail, theme.text(false, selected)),
+				])
+			})
+			.collect()
+	}
+}
+
+/// completes the whole input to existing local branch names
+pub struct BranchCompletion {
+	repo: RepoPathRef,
+	branches: Vec<String>,
+}
+
+impl BranchCompletion {
+	///
+	pub const fn new(repo: RepoPathRef) -> Self {
+		Self {
+			repo,
+			branches: Vec::new(),
+		}
+	}
+}
+
+impl CompletionProvider for BranchCompletion {
+	fn token_start(&self, text: &str) -> Option<usize> {
+		(!text.is_empty()).then(|| 0)
+	}
+
+	fn candidates(&mut self, token: &str) -> Vec<Completion> {
+		self.branches
+			.iter()
+			.filter(|name| name.starts_with(token))
+			.map(|name| {
+				let mut item = Completion::new(name.clone());
+				if name == token {
+					item.detail =
+						strings::COMPLETION_BRANCH_EXISTS.to_string();
+				}
+				item
+			})
+			.collect()
+	}
+
+	fn refresh(&mut self) {
+		self.branches = get_branches_info(&self.repo.borrow(), true)
+			.map(|branches| {
+				branches.into_iter().map(|b| b.name).collect()
+			})
+			.unwrap_or_default();
+	}
+}
+
+/// offers the completions of the first provider that has a token
+/// at the cursor
+pub struct CompletionChain {
+	providers: Vec<Box<dyn CompletionProvider>>,
+	/// provider of the last `token_start`
+	active: Cell<usize>,
+}
+
+impl CompletionChain {
+	///
+	pub fn new(providers: Vec<Box<dyn CompletionProvider>>) -> Self {
+		Self {
+			providers,
+			active: Cell::new(0),
+		}
+	}
+}
+
+impl CompletionProvider for CompletionChain {
+	fn token_start(&self, text: &str) -> Option<usize> {
+		self.providers.iter().enumerate().find_map(
+			|(idx, provider)| {
+				let start = provider.token_start(text)?;
+				self.active.set(idx);
+				Some(start)
+			},
+		)
+	}
+
+	fn candidates(&mut self, token: &str) -> Vec<Completion> {
+		self.providers
+			.get_mut(self.active.get())
+			.map(|provider| provider.candidates(token))
+			.unwrap_or_default()
+	}
+
+	fn refresh(&mut self) {
+		self.providers.iter_mut().for_each(|p| p.refresh());
+	}
+
+	// providers wait for distinct notifications, so stopping at the
+	// first one taking `ev` skips none that would
+	fn update(&mut self, ev: AsyncNotification) -> bool {
+		self.providers.iter_mut().any(|p| p.update(ev))
+	}
+
+	fn any_work_pending(&self) -> bool {
+		self.providers.iter().any(|p| p.any_work_pending())
+	}
+
+	fn preselect(&self) -> bool {
+		self.providers
+			.get(self.active.get())
+			.map_or(false, |provider| provider.preselect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct Words(Vec<&'static str>);
+
+	impl CompletionProvider for Words {
+		fn token_start(&self, text: &str) -> Option<usize> {
+			Some(text.rfind(' ').map_or(0, |idx| idx + 1))
+		}
+
+		fn candidates(&mut self, token: &str) -> Vec<Completion> {
+			self.0
+				.iter()
+				.filter(|w| w.starts_with(token))
+				.map(|w| Completion::new((*w).to_string()))
+				.collect()
+		}
+	}
+
+	#[test]
+	fn test_popup() {
+		let mut popup = CompletionPopup::new(Box::new(Words(vec![
+			"feature", "fix", "master",
+		])));
+
+		popup.refresh("push f");
+		assert!(popup.is_visible());
+		assert_eq!(popup.accept(true), None);
+
+		popup.move_selection(false);
+		popup.move_selection(false);
+		assert_eq!(
+			popup.accept(true),
+			Some((1, String::from("fix")))
+		);
+		assert!(!popup.is_visible());
+
+		popup.refresh("push x");
+		assert!(!popup.is_visible());
+
+		popup.refresh("m");
+		assert_eq!(
+			popup.accept(false),
+			Some((1, String::from("master")))
+		);
+	}
+
+	struct Tags;
+
+	impl CompletionProvider for Tags {
+		fn token_start(&self, text: &str) -> Option<usize> {
+			text.rfind('@').map(|idx| idx + 1)
+		}
+
+		fn candidates(&mut self, token: &str) -> Vec<Completion> {
+			vec![Completion::new(format!("{}-tag", token))]
+		}
+	}
+
+	#[test]
+	fn test_chain() {
+		let mut popup = CompletionPopup::new(Box::new(
+			CompletionChain::new(vec![
+				Box::new(Tags),
+				Box::new(Words(vec!["feature"])),
+			]),
+		));
+
+		popup.refresh("push @v1");
+		assert_eq!(
+			popup.accept(false),
+			Some((2, String::from("v1-tag")))
+		);
+
+		popup.refresh("push fea");
+		assert_eq!(
+			popup.accept(false),
+			Some((3, String::from("feature")))
+		);
+	}
+}
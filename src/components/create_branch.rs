@@ -1,7 +1,8 @@
 use super::{
-	textinput::TextInputComponent, visibility_blocking,
-	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState,
+	completion::BranchCompletion,
+	textinput::{TextInputComponent, Validation, Validator},
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ToastKind,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
@@ -10,19 +11,62 @@ use crate::{
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use asyncgit::sync::{self, CommitId, RepoPathRef};
 use crossterm::event::Event;
-use easy_cast::Cast;
-use tui::{
-	backend::Backend, layout::Rect, widgets::Paragraph, Frame,
-};
+use tui::{backend::Backend, layout::Rect, Frame};
 
 pub struct CreateBranchComponent {
 	repo: RepoPathRef,
 	input: TextInputComponent,
+	/// create a branch without history, see `open_orphan`
+	orphan: bool,
+	/// create the branch here without switching, see `open_at`
+	commit: Option<CommitId>,
 	queue: Queue,
 	key_config: SharedKeyConfig,
-	theme: SharedTheme,
+}
+
+/// rejects names `git check-ref-format` does not accept and names
+/// of existing local branches other than `current`
+pub fn branch_name_validator(
+	repo: RepoPathRef,
+	current: Option<String>,
+) -> Validator {
+	Box::new(move |name| {
+		if name.is_empty() || current.as_deref() == Some(name) {
+			return Validation::Ok;
+		}
+
+		if !sync::validate_branch_name(name).unwrap_or_default() {
+			return Validation::Error(
+				strings::branch_name_invalid().to_string(),
+			);
+		}
+
+		let exists = |local: bool| {
+			sync::get_branches_info(&repo.borrow(), local)
+				.unwrap_or_default()
+				.iter()
+				.any(|b| {
+					if local {
+						b.name == name
+					} else {
+						b.name.split_once('/').map(|(_, n)| n)
+							== Some(name)
+					}
+				})
+		};
+
+		if exists(true) {
+			Validation::Error(strings::BRANCH_NAME_EXISTS.to_string())
+		} else if exists(false) {
+			Validation::Warning(
+				strings::BRANCH_NAME_EXISTS_REMOTE.to_string(),
+			)
+		} else {
+			Validation::Ok
+		}
+	})
 }
 
 impl DrawableComponent for CreateBranchComponent {
@@ -33,7 +77,6 @@ impl DrawableComponent for CreateBranchComponent {
 	) -> Result<()> {
 		if self.is_visible() {
 			self.input.draw(f, rect)?;
-			self.draw_warnings(f);
 		}
 
 		Ok(())
@@ -53,7 +96,7 @@ impl Component for CreateBranchComponent {
 				strings::commands::create_branch_confirm_msg(
 					&self.key_config,
 				),
-				true,
+				self.input.is_valid(),
 				true,
 			));
 		}
@@ -68,7 +111,9 @@ impl Component for CreateBranchComponent {
 			}
 
 			if let Event::Key(e) = ev {
-				if key_match(e, self.key_config.keys.enter) {
+				if key_match(e, self.key_config.keys.enter)
+					&& self.input.is_valid()
+				{
 					self.create_branch();
 				}
 
@@ -104,13 +149,21 @@ impl CreateBranchComponent {
 		Self {
 			queue,
 			input: TextInputComponent::new(
-				theme.clone(),
+				theme,
 				key_config.clone(),
 				&strings::create_branch_popup_title(&key_config),
 				&strings::create_branch_popup_msg(&key_config),
 				true,
-			),
-			theme,
+			)
+			.with_completion(Box::new(BranchCompletion::new(
+				repo.clone(),
+			)))
+			.with_validator(branch_name_validator(
+				repo.clone(),
+				None,
+			)),
+			orphan: false,
+			commit: None,
 			key_config,
 			repo,
 		}
@@ -118,6 +171,38 @@ impl CreateBranchComponent {
 
 	///
 	pub fn open(&mut self) -> Result<()> {
+		self.orphan = false;
+		self.commit = None;
+		self.input.set_title(strings::create_branch_popup_title(
+			&self.key_config,
+		));
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// for a branch that starts with an empty index and no history
+	pub fn open_orphan(&mut self) -> Result<()> {
+		self.orphan = true;
+		self.commit = None;
+		self.input.set_title(
+			strings::create_orphan_branch_popup_title(
+				&self.key_config,
+			),
+		);
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// for a branch at `commit`, HEAD stays on the current branch
+	pub fn open_at(&mut self, commit: CommitId) -> Result<()> {
+		self.orphan = false;
+		self.commit = Some(commit);
+		self.input.set_title(strings::create_branch_at_popup_title(
+			&self.key_config,
+			&commit.get_short_string(),
+		));
 		self.show()?;
 
 		Ok(())
@@ -125,10 +210,14 @@ impl CreateBranchComponent {
 
 	///
 	pub fn create_branch(&mut self) {
-		let res = sync::create_branch(
-			&self.repo.borrow(),
-			self.input.get_text(),
-		);
+		let name = self.input.get_text().to_string();
+		let res = if self.orphan {
+			sync::create_orphan_branch(&self.repo.borrow(), &name)
+		} else if let Some(commit) = self.commit {
+			sync::create_branch_at(&self.repo.borrow(), &name, commit)
+		} else {
+			sync::create_branch(&self.repo.borrow(), &name)
+		};
 
 		self.input.clear();
 		self.hide();
@@ -138,6 +227,27 @@ impl CreateBranchComponent {
 				self.queue.push(InternalEvent::Update(
 					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
 				));
+
+				if let Some(commit) = self.commit {
+					self.queue.push(InternalEvent::ShowToast(
+						ToastKind::Success,
+						strings::branch_created_at(
+							&self.key_config,
+							&name,
+							&commit.get_short_string(),
+						),
+					));
+				} else if self.orphan {
+					// nothing exists until the first commit
+					self.queue.push(InternalEvent::TabSwitchStatus);
+					self.queue.push(InternalEvent::ShowToast(
+						ToastKind::Success,
+						strings::orphan_branch_created(
+							&self.key_config,
+							&name,
+						),
+					));
+				}
 			}
 			Err(e) => {
 				log::error!("create branch: {}", e,);
@@ -147,35 +257,4 @@ impl CreateBranchComponent {
 			}
 		}
 	}
-
-	fn draw_warnings<B: Backend>(&self, f: &mut Frame<B>) {
-		let current_text = self.input.get_text();
-
-		if !current_text.is_empty() {
-			let valid = sync::validate_branch_name(current_text)
-				.unwrap_or_default();
-
-			if !valid {
-				let msg = strings::branch_name_invalid();
-				let msg_length: u16 = msg.len().cast();
-				let w = Paragraph::new(msg)
-					.style(self.theme.text_danger());
-
-				let rect = {
-					let mut rect = self.input.get_area();
-					rect.y += rect.height.saturating_sub(1);
-					rect.height = 1;
-					let offset =
-						rect.width.saturating_sub(msg_length + 1);
-					rect.width =
-						rect.width.saturating_sub(offset + 1);
-					rect.x += offset;
-
-					rect
-				};
-
-				f.render_widget(w, rect);
-			}
-		}
-	}
 }
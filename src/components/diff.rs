@@ -1,6 +1,7 @@
 use super::{
-	utils::scroll_vertical::VerticalScroll, CommandBlocking,
-	Direction, DrawableComponent, ScrollType,
+	options_popup::cycle, utils::scroll_vertical::VerticalScroll,
+	CommandBlocking, Direction, DrawableComponent, ScrollType,
+	SharedOptions,
 };
 use crate::{
 	components::{CommandInfo, Component, EventState},
@@ -13,11 +14,20 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	hash,
-	sync::{self, diff::DiffLinePosition, RepoPathRef},
+	sync::{
+		self,
+		binary::{BinaryDiff, BinaryFile},
+		diff::{
+			DiffAlgorithm, DiffConversion, DiffLinePosition,
+			DiffOptions, Hunk,
+		},
+		lfs::LfsPointer,
+		RepoPathRef,
+	},
 	DiffLine, DiffLineType, FileDiff,
 };
 use bytesize::ByteSize;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use std::{borrow::Cow, cell::Cell, cmp, path::Path};
 use tui::{
 	backend::Backend,
@@ -28,6 +38,18 @@ use tui::{
 	Frame,
 };
 
+/// shown for the `\r` of CRLF lines when line endings are shown
+const CARRIAGE_RETURN: char = '␍';
+
+/// raised line limit of a truncated diff
+struct LoadMore {
+	path: String,
+	/// how often `DiffOptions::load_more` was applied
+	steps: u32,
+	/// selection to restore once the longer diff arrives
+	selection: Option<usize>,
+}
+
 #[derive(Default)]
 struct Current {
 	path: String,
@@ -108,10 +130,16 @@ pub struct DiffComponent {
 	current_size: Cell<(u16, u16)>,
 	focused: bool,
 	current: Current,
+	load_more: Option<LoadMore>,
+	/// overrides the algorithm of the options for this view only
+	algorithm: Option<DiffAlgorithm>,
 	scroll: VerticalScroll,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
+	options: SharedOptions,
+	/// path of a masked file the user chose to see anyway
+	revealed: Option<String>,
 	is_immutable: bool,
 }
 
@@ -122,12 +150,15 @@ impl DiffComponent {
 		queue: Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 		is_immutable: bool,
 	) -> Self {
 		Self {
 			focused: false,
 			queue,
 			current: Current::default(),
+			load_more: None,
+			algorithm: None,
 			pending: false,
 			selected_hunk: None,
 			diff: None,
@@ -136,6 +167,8 @@ impl DiffComponent {
 			scroll: VerticalScroll::new(),
 			theme,
 			key_config,
+			options,
+			revealed: None,
 			is_immutable,
 			repo,
 		}
@@ -151,6 +184,133 @@ impl DiffComponent {
 	pub fn current(&self) -> (String, bool) {
 		(self.current.path.clone(), self.current.is_stage)
 	}
+	/// `options` with the line limit raised if the user asked to
+	/// load more of the diff of `path` and the algorithm picked
+	/// for this view
+	pub fn diff_options(
+		&self,
+		path: &str,
+		options: DiffOptions,
+	) -> DiffOptions {
+		let options = DiffOptions {
+			algorithm: self.algorithm.unwrap_or(options.algorithm),
+			..options
+		};
+
+		match &self.load_more {
+			Some(more) if more.path == path => {
+				(0..more.steps).fold(options, |o, _| o.load_more())
+			}
+			_ => options,
+		}
+	}
+	/// keys handled differently while the diff is masked,
+	/// copying the hidden lines is not possible
+	fn masked_event(&mut self, e: &KeyEvent) -> Option<EventState> {
+		self.masked()?;
+
+		if key_match(e, self.key_config.keys.reveal_masked) {
+			self.revealed = Some(self.current.path.clone());
+			Some(EventState::Consumed)
+		} else if key_match(e, self.key_config.keys.copy) {
+			Some(EventState::NotConsumed)
+		} else {
+			None
+		}
+	}
+
+	/// stages or resets hunks and lines, `true` if `e` did so
+	fn staging_event(&mut self, e: &KeyEvent) -> bool {
+		if self.is_immutable || self.is_converted() {
+			return false;
+		}
+
+		if key_match(e, self.key_config.keys.stage_unstage_item) {
+			try_or_popup!(
+				self,
+				"hunk error:",
+				self.stage_unstage_hunk()
+			);
+		} else if key_match(e, self.key_config.keys.status_reset_item)
+			&& !self.is_stage()
+		{
+			if let Some(diff) = &self.diff {
+				if diff.untracked {
+					self.reset_untracked();
+				} else {
+					self.reset_hunk();
+				}
+			}
+		} else if key_match(e, self.key_config.keys.diff_stage_lines)
+		{
+			self.stage_lines();
+		} else if key_match(e, self.key_config.keys.diff_reset_lines)
+			&& !self.is_stage()
+		{
+			if let Some(diff) = &self.diff {
+				//TODO: reset untracked lines
+				if !diff.untracked {
+					self.reset_lines();
+				}
+			}
+		} else {
+			return false;
+		}
+
+		true
+	}
+
+	/// the secret pattern the current file matches unless revealed
+	fn masked(&self) -> Option<String> {
+		if self.revealed.as_ref() == Some(&self.current.path) {
+			return None;
+		}
+
+		self.options
+			.borrow()
+			.secrets
+			.matching(&self.current.path)
+			.map(ToString::to_string)
+	}
+	///
+	fn is_truncated(&self) -> bool {
+		self.diff.as_ref().map_or(false, |diff| diff.truncated)
+	}
+
+	/// converted sides can't be (un)staged or reset in parts
+	fn is_converted(&self) -> bool {
+		self.diff
+			.as_ref()
+			.map_or(false, |diff| diff.conversion.is_some())
+	}
+	///
+	fn load_more(&mut self) {
+		let steps = match &self.load_more {
+			Some(more) if more.path == self.current.path => {
+				more.steps + 1
+			}
+			_ => 1,
+		};
+
+		self.load_more = Some(LoadMore {
+			path: self.current.path.clone(),
+			steps,
+			selection: Some(self.selection.get_start()),
+		});
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::DIFF));
+	}
+	///
+	fn switch_algorithm(&mut self) {
+		let current = self
+			.algorithm
+			.unwrap_or(self.options.borrow().diff.algorithm);
+
+		self.algorithm =
+			Some(cycle(&DiffAlgorithm::ALL, current, true));
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::DIFF));
+	}
 	///
 	pub fn clear(&mut self, pending: bool) {
 		self.current = Current::default();
@@ -173,6 +333,9 @@ impl DiffComponent {
 
 		if self.current.hash != hash {
 			let reset_selection = self.current.path != path;
+			if reset_selection {
+				self.revealed = None;
+			}
 
 			self.current = Current {
 				path,
@@ -180,9 +343,17 @@ impl DiffComponent {
 				hash,
 			};
 
-			self.diff = Some(diff);
-
-			if reset_selection {
+			let restore = self
+				.load_more
+				.as_mut()
+				.filter(|more| more.path == self.current.path)
+				.and_then(|more| more.selection.take());
+
+			if let Some(selection) = restore {
+				self.diff = Some(diff);
+				self.update_selection(selection);
+			} else if reset_selection {
+				self.diff = Some(diff);
 				self.scroll.reset();
 				self.selection = Selection::Single(0);
 				self.update_selection(0);
@@ -191,7 +362,17 @@ impl DiffComponent {
 					Selection::Single(line) => line,
 					Selection::Multiple(start, _) => start,
 				};
-				self.update_selection(old_selection);
+				let new_selection =
+					self.diff.as_ref().map_or(old_selection, |old| {
+						Self::anchor_selection(
+							old,
+							old_selection,
+							&diff,
+						)
+					});
+
+				self.diff = Some(diff);
+				self.update_selection(new_selection);
 			}
 		}
 	}
@@ -200,29 +381,18 @@ impl DiffComponent {
 		if let Some(diff) = &self.diff {
 			let max = diff.lines.saturating_sub(1) as usize;
 
-			let new_start = match move_type {
-				ScrollType::Down => {
-					self.selection.get_bottom().saturating_add(1)
-				}
-				ScrollType::Up => {
-					self.selection.get_top().saturating_sub(1)
-				}
-				ScrollType::Home => 0,
-				ScrollType::End => max,
-				ScrollType::PageDown => {
-					self.selection.get_bottom().saturating_add(
-						self.current_size.get().1.saturating_sub(1)
-							as usize,
-					)
-				}
-				ScrollType::PageUp => {
-					self.selection.get_top().saturating_sub(
-						self.current_size.get().1.saturating_sub(1)
-							as usize,
-					)
+			// moving down leaves a multi line selection at its bottom
+			let current = match move_type {
+				ScrollType::Down | ScrollType::PageDown => {
+					self.selection.get_bottom()
 				}
+				_ => self.selection.get_top(),
 			};
 
+			let new_start = self
+				.scroll
+				.move_selection(current, move_type, 1, max);
+
 			self.update_selection(new_start);
 		}
 	}
@@ -275,6 +445,57 @@ impl DiffComponent {
 		}
 	}
 
+	/// maps `line` of `old` to the same spot in `new`: the same line
+	/// of the matching hunk, or the start of the hunk that took its
+	/// place if the hunk is gone (e.g. because it got staged)
+	fn anchor_selection(
+		old: &FileDiff,
+		line: usize,
+		new: &FileDiff,
+	) -> usize {
+		let old_idx =
+			if let Some(idx) = Self::find_selected_hunk(old, line) {
+				idx
+			} else {
+				return line;
+			};
+		let old_hunk = &old.hunks[old_idx];
+		let offset = line - Self::hunk_start(old, old_idx);
+
+		let matching = new
+			.hunks
+			.iter()
+			.position(|hunk| hunk.header_hash == old_hunk.header_hash)
+			.or_else(|| {
+				// staging shifts one side of the hunk header only
+				let (old_range, new_range) = hunk_ranges(old_hunk)?;
+				new.hunks.iter().position(|hunk| {
+					hunk.lines.len() == old_hunk.lines.len()
+						&& hunk_ranges(hunk)
+							.map_or(false, |(o, n)| {
+								o == old_range || n == new_range
+							})
+				})
+			});
+
+		match matching {
+			Some(idx) => Self::hunk_start(new, idx) + offset,
+			None if new.hunks.is_empty() => 0,
+			None => Self::hunk_start(
+				new,
+				old_idx.min(new.hunks.len() - 1),
+			),
+		}
+	}
+
+	fn hunk_start(diff: &FileDiff, hunk_idx: usize) -> usize {
+		diff.hunks
+			.iter()
+			.take(hunk_idx)
+			.map(|hunk| hunk.lines.len())
+			.sum()
+	}
+
 	fn find_selected_hunk(
 		diff: &FileDiff,
 		line_selected: usize,
@@ -301,100 +522,161 @@ impl DiffComponent {
 	fn get_text(&self, width: u16, height: u16) -> Vec<Spans> {
 		let mut res: Vec<Spans> = Vec::new();
 		if let Some(diff) = &self.diff {
-			if diff.hunks.is_empty() {
-				let is_positive = diff.size_delta >= 0;
-				let delta_byte_size = ByteSize::b(
-					diff.size_delta.unsigned_abs() as u64,
-				);
-				let sign = if is_positive { "+" } else { "-" };
-				res.extend(vec![Spans::from(vec![
-					Span::raw(Cow::from("size: ")),
-					Span::styled(
-						Cow::from(format!(
-							"{}",
-							ByteSize::b(diff.sizes.0)
-						)),
-						self.theme.text(false, false),
-					),
-					Span::raw(Cow::from(" -> ")),
-					Span::styled(
-						Cow::from(format!(
-							"{}",
-							ByteSize::b(diff.sizes.1)
-						)),
-						self.theme.text(false, false),
-					),
-					Span::raw(Cow::from(" (")),
-					Span::styled(
-						Cow::from(format!(
-							"{}{:}",
-							sign, delta_byte_size
-						)),
-						self.theme.diff_line(
-							if is_positive {
-								DiffLineType::Add
-							} else {
-								DiffLineType::Delete
-							},
-							false,
-						),
-					),
-					Span::raw(Cow::from(")")),
-				])]);
+			if let Some(pattern) =
+				self.masked().filter(|_| !diff.hunks.is_empty())
+			{
+				res.push(Spans::from(Span::styled(
+					Cow::from(strings::masked_content(
+						&pattern,
+						&self.key_config,
+					)),
+					self.theme.text(false, false),
+				)));
+			} else if diff.lfs.0.is_some() || diff.lfs.1.is_some() {
+				res.push(self.lfs_line(diff));
+			} else if let Some(summary) = &diff.binary_summary {
+				res.extend(self.binary_lines(summary));
+			} else if diff.hunks.is_empty() {
+				res.push(self.size_line(diff.sizes, diff.size_delta));
 			} else {
-				let min = self.scroll.get_top();
-				let max = min + height as usize;
-
-				let mut line_cursor = 0_usize;
-				let mut lines_added = 0_usize;
-
-				for (i, hunk) in diff.hunks.iter().enumerate() {
-					let hunk_selected = self.focused()
-						&& self
-							.selected_hunk
-							.map_or(false, |s| s == i);
-
-					if lines_added >= height as usize {
-						break;
-					}
-
-					let hunk_len = hunk.lines.len();
-					let hunk_min = line_cursor;
-					let hunk_max = line_cursor + hunk_len;
-
-					if Self::hunk_visible(
-						hunk_min, hunk_max, min, max,
-					) {
-						for (i, line) in hunk.lines.iter().enumerate()
-						{
-							if line_cursor >= min
-								&& line_cursor <= max
-							{
-								res.push(Self::get_line_to_add(
-									width,
-									line,
-									self.focused()
-										&& self
-											.selection
-											.contains(line_cursor),
-									hunk_selected,
-									i == hunk_len as usize - 1,
-									&self.theme,
-								));
-								lines_added += 1;
-							}
-
-							line_cursor += 1;
-						}
-					} else {
-						line_cursor += hunk_len;
-					}
-				}
+				let focused = self.focused();
+				res.extend(
+					visible_lines(
+						diff,
+						self.scroll.get_top(),
+						height.into(),
+					)
+					.map(
+						|(hunk_idx, line_idx, line_cursor, line)| {
+							let hunk = &diff.hunks[hunk_idx];
+							Self::get_line_to_add(
+								width,
+								line,
+								focused
+									&& self
+										.selection
+										.contains(line_cursor),
+								focused
+									&& self.selected_hunk
+										== Some(hunk_idx),
+								line_idx + 1 == hunk.lines.len(),
+								&self.theme,
+							)
+						},
+					),
+				);
 			}
 		}
 		res
 	}
 
+	fn size_line(&self, sizes: (u64, u64), delta: i64) -> Spans<'_> {
+		let is_positive = delta >= 0;
+		let delta_byte_size = ByteSize::b(delta.unsigned_abs());
+		let sign = if is_positive { "+" } else { "-" };
+
+		Spans::from(vec![
+			Span::raw(Cow::from("size: ")),
+			Span::styled(
+				Cow::from(format!("{}", ByteSize::b(sizes.0))),
+				self.theme.text(false, false),
+			),
+			Span::raw(Cow::from(" -> ")),
+			Span::styled(
+				Cow::from(format!("{}", ByteSize::b(sizes.1))),
+				self.theme.text(false, false),
+			),
+			Span::raw(Cow::from(" (")),
+			Span::styled(
+				Cow::from(format!("{}{:}", sign, delta_byte_size)),
+				self.theme.diff_line(
+					if is_positive {
+						DiffLineType::Add
+					} else {
+						DiffLineType::Delete
+					},
+					false,
+				),
+			),
+			Span::raw(Cow::from(")")),
+		])
+	}
+
+	/// file type, size and blob of both sides, `-` for a side that
+	/// does not exist
+	fn binary_lines(&self, summary: &BinaryDiff) -> Vec<Spans<'_>> {
+		let sides = |value: fn(&BinaryFile) -> String| {
+			let side = |file: &Option<BinaryFile>| {
+				file.as_ref().map_or_else(|| String::from("-"), value)
+			};
+			(side(&summary.old), side(&summary.new))
+		};
+		let size = |file: &Option<BinaryFile>| {
+			file.as_ref().map_or(0, |file| file.size)
+		};
+
+		vec![
+			self.sides_line(
+				"binary: ",
+				sides(|file| {
+					file.file_type
+						.unwrap_or("unknown type")
+						.to_string()
+				}),
+			),
+			self.size_line(
+				(size(&summary.old), size(&summary.new)),
+				summary.size_delta(),
+			),
+			self.sides_line(
+				"blob: ",
+				sides(|file| file.short_id().to_string()),
+			),
+		]
+	}
+
+	fn sides_line(
+		&self,
+		label: &'static str,
+		(old, new): (String, String),
+	) -> Spans<'_> {
+		Spans::from(vec![
+			Span::raw(Cow::from(label)),
+			Span::styled(
+				Cow::from(old),
+				self.theme.diff_line(DiffLineType::Delete, false),
+			),
+			Span::raw(Cow::from(" -> ")),
+			Span::styled(
+				Cow::from(new),
+				self.theme.diff_line(DiffLineType::Add, false),
+			),
+		])
+	}
+
+	/// placeholders for the lfs pointers instead of their text,
+	/// a side that is no pointer (e.g. smudged workdir file) shows its size
+	fn lfs_line(&self, diff: &FileDiff) -> Spans<'_> {
+		let side = |pointer: &Option<LfsPointer>, size: u64| {
+			pointer.as_ref().map_or_else(
+				|| ByteSize::b(size).to_string(),
+				strings::lfs_object,
+			)
+		};
+
+		Spans::from(vec![
+			Span::styled(
+				Cow::from(side(&diff.lfs.0, diff.sizes.0)),
+				self.theme.diff_line(DiffLineType::Delete, false),
+			),
+			Span::raw(Cow::from(" -> ")),
+			Span::styled(
+				Cow::from(side(&diff.lfs.1, diff.sizes.1)),
+				self.theme.diff_line(DiffLineType::Add, false),
+			),
+		])
+	}
+
 	fn get_line_to_add<'a>(
 		width: u16,
 		line: &'a DiffLine,
@@ -420,44 +702,45 @@ impl DiffComponent {
 			}
 		};
 
-		let filled = if selected {
-			// selected line
-			format!("{:w$}\n", line.content, w = width as usize)
-		} else {
-			// weird eof missing eol line
-			format!("{}\n", line.content)
-		};
+		let content: &str = &line.content;
+		let filled = content.strip_suffix('\r').map_or_else(
+			|| {
+				if selected {
+					// selected line is padded to fill the whole row
+					Cow::from(tabs_to_spaces(format!(
+						"{:w$}",
+						content,
+						w = width as usize
+					)))
+				} else if content.contains('\t') {
+					Cow::from(tabs_to_spaces(content.to_string()))
+				} else {
+					// avoid allocating for the common case
+					Cow::from(content)
+				}
+			},
+			|content| {
+				// kept only when line endings are shown, make the CR
+				// visible
+				let content =
+					format!("{}{}", content, CARRIAGE_RETURN);
+				Cow::from(tabs_to_spaces(if selected {
+					format!("{:w$}", content, w = width as usize)
+				} else {
+					content
+				}))
+			},
+		);
 
 		Spans::from(vec![
 			left_side_of_line,
 			Span::styled(
-				Cow::from(tabs_to_spaces(filled)),
+				filled,
 				theme.diff_line(line.line_type, selected),
 			),
 		])
 	}
 
-	const fn hunk_visible(
-		hunk_min: usize,
-		hunk_max: usize,
-		min: usize,
-		max: usize,
-	) -> bool {
-		// full overlap
-		if hunk_min <= min && hunk_max >= max {
-			return true;
-		}
-
-		// partly overlap
-		if (hunk_min >= min && hunk_min <= max)
-			|| (hunk_max >= min && hunk_max <= max)
-		{
-			return true;
-		}
-
-		false
-	}
-
 	fn unstage_hunk(&mut self) -> Result<()> {
 		if let Some(diff) = &self.diff {
 			if let Some(hunk) = self.selected_hunk {
@@ -617,9 +900,26 @@ impl DrawableComponent for DiffComponent {
 		);
 
 		let title = format!(
-			"{}{}",
+			"{}{}{}{}{}",
 			strings::title_diff(&self.key_config),
-			self.current.path
+			self.current.path,
+			self.algorithm.map_or_else(String::new, |algorithm| {
+				strings::title_diff_algorithm(algorithm.name())
+			}),
+			match self.diff.as_ref().and_then(|diff| diff.conversion)
+			{
+				Some(DiffConversion::Textconv) => {
+					strings::TITLE_DIFF_TEXTCONV
+				}
+				Some(DiffConversion::Pretty) =>
+					strings::TITLE_DIFF_PRETTY,
+				None => "",
+			},
+			if self.is_truncated() {
+				strings::title_diff_truncated(&self.key_config)
+			} else {
+				String::new()
+			}
 		);
 
 		let txt = if self.pending {
@@ -673,7 +973,19 @@ impl Component for DiffComponent {
 			.hidden(),
 		);
 
-		if !self.is_immutable {
+		out.push(CommandInfo::new(
+			strings::commands::diff_load_more(&self.key_config),
+			true,
+			self.focused() && self.is_truncated(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_algorithm(&self.key_config),
+			true,
+			self.focused(),
+		));
+
+		if !self.is_immutable && !self.is_converted() {
 			out.push(CommandInfo::new(
 				strings::commands::diff_hunk_remove(&self.key_config),
 				self.selected_hunk.is_some(),
@@ -715,10 +1027,16 @@ impl Component for DiffComponent {
 
 		out.push(CommandInfo::new(
 			strings::commands::copy(&self.key_config),
-			true,
+			self.masked().is_none(),
 			self.focused(),
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::reveal_masked(&self.key_config),
+			true,
+			self.focused() && self.masked().is_some(),
+		));
+
 		CommandBlocking::PassingOn
 	}
 
@@ -726,6 +1044,10 @@ impl Component for DiffComponent {
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.focused() {
 			if let Event::Key(e) = ev {
+				if let Some(state) = self.masked_event(e) {
+					return Ok(state);
+				}
+
 				return if key_match(e, self.key_config.keys.move_down)
 				{
 					self.move_selection(ScrollType::Down);
@@ -756,54 +1078,23 @@ impl Component for DiffComponent {
 				{
 					self.move_selection(ScrollType::PageDown);
 					Ok(EventState::Consumed)
-				} else if key_match(
-					e,
-					self.key_config.keys.stage_unstage_item,
-				) && !self.is_immutable
-				{
-					try_or_popup!(
-						self,
-						"hunk error:",
-						self.stage_unstage_hunk()
-					);
-
+				} else if self.staging_event(e) {
 					Ok(EventState::Consumed)
-				} else if key_match(
-					e,
-					self.key_config.keys.status_reset_item,
-				) && !self.is_immutable
-					&& !self.is_stage()
-				{
-					if let Some(diff) = &self.diff {
-						if diff.untracked {
-							self.reset_untracked();
-						} else {
-							self.reset_hunk();
-						}
-					}
+				} else if key_match(e, self.key_config.keys.copy) {
+					self.copy_selection();
 					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
-					self.key_config.keys.diff_stage_lines,
-				) && !self.is_immutable
+					self.key_config.keys.diff_load_more,
+				) && self.is_truncated()
 				{
-					self.stage_lines();
+					self.load_more();
 					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
-					self.key_config.keys.diff_reset_lines,
-				) && !self.is_immutable
-					&& !self.is_stage()
-				{
-					if let Some(diff) = &self.diff {
-						//TODO: reset untracked lines
-						if !diff.untracked {
-							self.reset_lines();
-						}
-					}
-					Ok(EventState::Consumed)
-				} else if key_match(e, self.key_config.keys.copy) {
-					self.copy_selection();
+					self.key_config.keys.diff_algorithm,
+				) {
+					self.switch_algorithm();
 					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
@@ -821,3 +1112,145 @@ impl Component for DiffComponent {
 		self.focused = focus;
 	}
 }
+
+/// old and new line range of a hunk header like `@@ -1,2 +1,3 @@`
+/// lines of `diff` in the window of `height` rows starting at `top`
+/// as `(hunk index, index in hunk, line index, line)`,
+/// only touches the hunks and lines actually visible
+fn visible_lines(
+	diff: &FileDiff,
+	top: usize,
+	height: usize,
+) -> impl Iterator<Item = (usize, usize, usize, &DiffLine)> {
+	let mut hunk_start = 0_usize;
+	diff.hunks
+		.iter()
+		.enumerate()
+		.filter_map(move |(hunk_idx, hunk)| {
+			let start = hunk_start;
+			hunk_start += hunk.lines.len();
+
+			(hunk_start > top).then(|| {
+				let skip = top.saturating_sub(start);
+				hunk.lines[skip..].iter().enumerate().map(
+					move |(idx, line)| {
+						(
+							hunk_idx,
+							skip + idx,
+							start + skip + idx,
+							line,
+						)
+					},
+				)
+			})
+		})
+		.flatten()
+		.take(height)
+}
+
+fn hunk_ranges(hunk: &Hunk) -> Option<(&str, &str)> {
+	let header = hunk
+		.lines
+		.first()
+		.filter(|line| line.line_type == DiffLineType::Header)?;
+	let mut parts = header.content.split_whitespace().skip(1);
+
+	Some((parts.next()?, parts.next()?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn hunk(header: &str, len: usize) -> Hunk {
+		let mut lines = vec![DiffLine {
+			content: header.into(),
+			line_type: DiffLineType::Header,
+			..DiffLine::default()
+		}];
+		lines.resize(len, DiffLine::default());
+
+		Hunk {
+			header_hash: hash(&header),
+			lines,
+		}
+	}
+
+	fn diff(hunks: Vec<Hunk>) -> FileDiff {
+		FileDiff {
+			lines: hunks.iter().map(|h| h.lines.len()).sum(),
+			hunks,
+			..FileDiff::default()
+		}
+	}
+
+	#[test]
+	fn test_anchor_selection() {
+		let old = diff(vec![
+			hunk("@@ -1,2 +1,3 @@", 4),
+			hunk("@@ -10,2 +11,3 @@", 4),
+			hunk("@@ -20,2 +21,3 @@", 4),
+		]);
+
+		// first hunk got staged, the others shifted their old range
+		let new = diff(vec![
+			hunk("@@ -11,2 +11,3 @@", 4),
+			hunk("@@ -21,2 +21,3 @@", 4),
+		]);
+
+		// cursor in the staged hunk lands on the next one
+		assert_eq!(DiffComponent::anchor_selection(&old, 2, &new), 0);
+		// cursor in a remaining hunk stays on its line
+		assert_eq!(DiffComponent::anchor_selection(&old, 6, &new), 2);
+		assert_eq!(
+			DiffComponent::anchor_selection(&old, 11, &new),
+			7
+		);
+
+		// last hunk staged, cursor goes to the new last hunk
+		let new = diff(vec![
+			hunk("@@ -1,2 +1,3 @@", 4),
+			hunk("@@ -10,2 +11,3 @@", 4),
+		]);
+		assert_eq!(DiffComponent::anchor_selection(&old, 9, &new), 4);
+
+		assert_eq!(
+			DiffComponent::anchor_selection(&old, 9, &diff(vec![])),
+			0
+		);
+	}
+
+	#[test]
+	fn test_visible_lines() {
+		let diff = diff(vec![
+			hunk("@@ -1,2 +1,3 @@", 4),
+			hunk("@@ -10,2 +11,3 @@", 100_000),
+			hunk("@@ -20,2 +21,3 @@", 4),
+		]);
+
+		let window = |top, height| {
+			visible_lines(&diff, top, height)
+				.map(|(hunk, idx, line, _)| (hunk, idx, line))
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			window(2, 3),
+			vec![(0, 2, 2), (0, 3, 3), (1, 0, 4)]
+		);
+		assert_eq!(
+			window(50_000, 2),
+			vec![(1, 49_996, 50_000), (1, 49_997, 50_001)]
+		);
+		assert_eq!(
+			window(100_002, 3),
+			vec![
+				(1, 99_998, 100_002),
+				(1, 99_999, 100_003),
+				(2, 0, 100_004)
+			]
+		);
+		assert_eq!(window(100_007, 5), vec![(2, 3, 100_007)]);
+		assert!(window(200_000, 5).is_empty());
+	}
+}
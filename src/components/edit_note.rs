@@ -0,0 +1,149 @@
+use super::{
+	textinput::TextInputComponent, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{notes, CommitId, RepoPathRef};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// adds or amends the note (`git notes`) of a commit
+pub struct EditNoteComponent {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	commit_id: Option<CommitId>,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for EditNoteComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for EditNoteComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::edit_note_confirm_msg(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					try_or_popup!(
+						self,
+						"note error:",
+						self.save_note()
+					);
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl EditNoteComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			queue,
+			input: TextInputComponent::new(
+				theme,
+				key_config.clone(),
+				"",
+				&strings::edit_note_popup_msg(),
+				true,
+			),
+			commit_id: None,
+			key_config,
+			repo,
+		}
+	}
+
+	/// starts with the existing note of `id` if there is one
+	pub fn open(&mut self, id: CommitId) -> Result<()> {
+		let note = notes::get_note(&self.repo.borrow(), id)?;
+
+		self.commit_id = Some(id);
+		self.input.set_title(strings::edit_note_popup_title(
+			&id.get_short_string(),
+			note.is_some(),
+		));
+		self.input.set_text(note.unwrap_or_default());
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// an empty note removes it
+	fn save_note(&mut self) -> Result<()> {
+		if let Some(commit_id) = self.commit_id {
+			notes::set_note(
+				&self.repo.borrow(),
+				commit_id,
+				self.input.get_text(),
+			)?;
+
+			self.input.clear();
+			self.hide();
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+}
@@ -4,21 +4,24 @@ use crate::{
 		DrawableComponent, EventState,
 	},
 	keys::SharedKeyConfig,
-	strings,
+	screen, strings,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::{anyhow, bail, Result};
 use asyncgit::sync::{
-	get_config_string, utils::repo_work_dir, RepoPath,
+	self, get_config_string, utils::repo_work_dir, RepoPath,
 };
 use crossterm::{
 	event::Event,
-	terminal::{EnterAlternateScreen, LeaveAlternateScreen},
-	ExecutableCommand,
+	terminal::{disable_raw_mode, enable_raw_mode},
 };
 use scopeguard::defer;
 use std::ffi::OsStr;
-use std::{env, io, path::Path, process::Command};
+use std::{
+	env,
+	path::{Path, PathBuf},
+	process::Command,
+};
 use tui::{
 	backend::Backend,
 	layout::Rect,
@@ -27,6 +30,17 @@ use tui::{
 	Frame,
 };
 
+/// external diff/merge tool to launch for a file
+#[derive(Debug, Clone)]
+pub enum ExternalTool {
+	/// `diff.tool` on workdir (or staged if `stage` is set) changes
+	Diff { path: String, stage: bool },
+	/// `merge.tool` to resolve a conflicted file
+	Merge { path: String },
+	/// interactive shell in `path` (relative to the repo root)
+	Shell { path: PathBuf },
+}
+
 ///
 pub struct ExternalEditorComponent {
 	visible: bool,
@@ -64,9 +78,9 @@ impl ExternalEditorComponent {
 			bail!("file not found: {:?}", path);
 		}
 
-		io::stdout().execute(LeaveAlternateScreen)?;
+		screen::release()?;
 		defer! {
-			io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+			screen::acquire().expect("reset terminal");
 		}
 
 		let environment_options = ["GIT_EDITOR", "VISUAL", "EDITOR"];
@@ -80,6 +94,27 @@ impl ExternalEditorComponent {
 			.or_else(|| env::var(environment_options[2]).ok())
 			.unwrap_or_else(|| String::from("vi"));
 
+		if editor.trim().is_empty() {
+			bail!(
+				"editor env variable found empty: {}",
+				environment_options.join(" or ")
+			);
+		}
+
+		if let Some(shell) = sync::posix_shell() {
+			// like git: the editor is a shell snippet the file is passed to
+			Command::new(&shell)
+				.current_dir(work_dir)
+				.arg("-c")
+				.arg(format!("{} \"$@\"", editor))
+				.arg(&editor)
+				.arg(&path)
+				.status()
+				.map_err(|e| anyhow!("\"{}\": {}", editor, e))?;
+
+			return Ok(());
+		}
+
 		// TODO: proper handling arguments containing whitespaces
 		// This does not do the right thing if the input is `editor --something "with spaces"`
 
@@ -120,6 +155,122 @@ impl ExternalEditorComponent {
 
 		Ok(())
 	}
+
+	/// launches the configured diff or merge tool on the file
+	/// referenced in `tool`.
+	///
+	/// `GITUI_DIFFTOOL`/`GITUI_MERGETOOL` allow overriding the git config
+	/// with a command using the git style variables
+	/// (`"$LOCAL"`, `"$REMOTE"`, `"$BASE"`, `"$MERGED"`)
+	pub fn open_tool(
+		repo: &RepoPath,
+		tool: &ExternalTool,
+	) -> Result<()> {
+		let work_dir = repo_work_dir(repo)?;
+
+		let (files, template) = match tool {
+			ExternalTool::Diff { path, stage } => (
+				sync::difftool_files(repo, path, *stage)?,
+				env::var("GITUI_DIFFTOOL")
+					.ok()
+					.or(sync::difftool_cmd(repo)?)
+					.ok_or_else(|| {
+						anyhow!("no difftool configured (diff.tool)")
+					})?,
+			),
+			ExternalTool::Shell { path } => {
+				return Self::open_shell(
+					&Path::new(&work_dir).join(path),
+				);
+			}
+			ExternalTool::Merge { path } => {
+				(
+					sync::mergetool_files(repo, path)?,
+					env::var("GITUI_MERGETOOL")
+						.ok()
+						.or(sync::mergetool_cmd(repo)?)
+						.ok_or_else(|| {
+							anyhow!("no mergetool configured (merge.tool)")
+						})?,
+				)
+			}
+		};
+
+		screen::release()?;
+		defer! {
+			screen::acquire().expect("reset terminal");
+		}
+
+		// run through the shell just like git does for
+		// `difftool.<tool>.cmd`, the files are passed as variables
+		let shell = sync::posix_shell()
+			.unwrap_or_else(|| PathBuf::from("sh"));
+
+		let status = Command::new(shell)
+			.current_dir(work_dir)
+			.args(["-c", template.as_str()])
+			.envs(files.env())
+			.status()
+			.map_err(|e| anyhow!("\"{}\": {}", template, e))?;
+
+		if !status.success() {
+			bail!("\"{}\" failed: {}", template, status);
+		}
+
+		Ok(())
+	}
+}
+
+impl ExternalEditorComponent {
+	/// runs `$SHELL` in `dir` on the normal screen until it exits
+	fn open_shell(dir: &Path) -> Result<()> {
+		let shell = if cfg!(windows) {
+			env::var("COMSPEC")
+				.unwrap_or_else(|_| String::from("cmd"))
+		} else {
+			env::var("SHELL").unwrap_or_else(|_| String::from("sh"))
+		};
+
+		Self::leave_tui(|| {
+			println!("{}", strings::SHELL_HINT);
+
+			Command::new(&shell)
+				.current_dir(dir)
+				.status()
+				.map_err(|e| anyhow!("\"{}\": {}", shell, e))?;
+
+			Ok(())
+		})
+	}
+
+	/// stops the process like a `SIGTSTP` would outside of raw mode,
+	/// returns once the shell resumes us (`fg`)
+	pub fn suspend() -> Result<()> {
+		Self::leave_tui(stop_process)
+	}
+
+	/// restores the normal terminal while `f` runs
+	fn leave_tui(f: impl FnOnce() -> Result<()>) -> Result<()> {
+		screen::release()?;
+		disable_raw_mode()?;
+		defer! {
+			enable_raw_mode().expect("reset terminal");
+			screen::acquire().expect("reset terminal");
+		}
+
+		f()
+	}
+}
+
+#[cfg(unix)]
+fn stop_process() -> Result<()> {
+	signal_hook::low_level::raise(signal_hook::consts::SIGTSTP)
+		.map_err(|e| anyhow!("suspend failed: {}", e))
+}
+
+#[cfg(not(unix))]
+fn stop_process() -> Result<()> {
+	bail!("suspend is not supported on this platform")
 }
 
 impl DrawableComponent for ExternalEditorComponent {
@@ -2,45 +2,54 @@ use crate::{
 	components::{
 		cred::CredComponent, visibility_blocking, CommandBlocking,
 		CommandInfo, Component, DrawableComponent, EventState,
+		RemoteProgressComponent, ToastKind,
 	},
-	keys::SharedKeyConfig,
-	queue::{InternalEvent, NeedsUpdate, Queue},
+	keys::{key_match, SharedKeyConfig},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
 	strings,
-	ui::{self, style::SharedTheme},
+	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
+	dry_run::{AsyncDryRunJob, DryRun},
 	sync::{
 		cred::{
 			extract_username_password, need_username_password,
 			BasicAuthCredential,
 		},
+		remotes::dry_run::{dry_run_enabled, RefChange},
 		RepoPathRef,
 	},
-	AsyncFetchJob, AsyncGitNotification, ProgressPercent,
+	AsyncFetch, AsyncGitNotification,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use tui::{
-	backend::Backend,
-	layout::Rect,
-	text::Span,
-	widgets::{Block, BorderType, Borders, Clear, Gauge},
-	Frame,
-};
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// where a fetch is with its dry run, see `gitui.dryRun`
+enum DryRunState {
+	/// fetch right away
+	Off,
+	/// list what would change before fetching
+	Enabled,
+	/// waiting for the listed changes to be confirmed, with the
+	/// credentials to fetch with then
+	Previewed(Option<BasicAuthCredential>),
+}
 
 ///
 pub struct FetchComponent {
 	repo: RepoPathRef,
 	visible: bool,
-	async_fetch: AsyncSingleJob<AsyncFetchJob>,
-	progress: Option<ProgressPercent>,
+	git_fetch: AsyncFetch,
+	progress: RemoteProgressComponent,
 	pending: bool,
 	queue: Queue,
-	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	dry_run: DryRunState,
+	async_dry_run: AsyncSingleJob<AsyncDryRunJob>,
 }
 
 impl FetchComponent {
@@ -52,17 +61,21 @@ impl FetchComponent {
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 	) -> Self {
+		let git_fetch =
+			AsyncFetch::new(repo.borrow().clone(), sender);
+
 		Self {
 			queue: queue.clone(),
 			pending: false,
 			visible: false,
-			async_fetch: AsyncSingleJob::new(sender.clone()),
-			progress: None,
-			input_cred: CredComponent::new(
+			git_fetch,
+			dry_run: DryRunState::Off,
+			async_dry_run: AsyncSingleJob::new(sender.clone()),
+			progress: RemoteProgressComponent::new(
 				theme.clone(),
 				key_config.clone(),
 			),
-			theme,
+			input_cred: CredComponent::new(theme, key_config.clone()),
 			key_config,
 			repo,
 		}
@@ -71,32 +84,69 @@ impl FetchComponent {
 	///
 	pub fn fetch(&mut self) -> Result<()> {
 		self.show()?;
+		self.dry_run = if dry_run_enabled(&self.repo.borrow())
+			.unwrap_or_default()
+		{
+			DryRunState::Enabled
+		} else {
+			DryRunState::Off
+		};
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
 				.unwrap_or_else(|_| {
 					BasicAuthCredential::new(None, None)
 				});
 			if cred.is_complete() {
-				self.fetch_all(Some(cred));
+				self.fetch_all(Some(cred))?;
 			} else {
 				self.input_cred.set_cred(cred);
 				self.input_cred.show()?;
 			}
 		} else {
-			self.fetch_all(None);
+			self.fetch_all(None)?;
 		}
 
 		Ok(())
 	}
 
-	fn fetch_all(&mut self, cred: Option<BasicAuthCredential>) {
+	fn fetch_all(
+		&mut self,
+		cred: Option<BasicAuthCredential>,
+	) -> Result<()> {
 		self.pending = true;
-		self.progress = None;
-		self.progress = Some(ProgressPercent::empty());
-		self.async_fetch.spawn(AsyncFetchJob::new(
-			self.repo.borrow().clone(),
-			cred,
-		));
+
+		if matches!(self.dry_run, DryRunState::Enabled) {
+			self.progress.start(strings::DRY_RUN_POPUP_MSG);
+			self.dry_run = DryRunState::Previewed(cred.clone());
+			self.async_dry_run.spawn(AsyncDryRunJob::new(
+				self.repo.borrow().clone(),
+				DryRun::Fetch,
+				cred,
+			));
+			return Ok(());
+		}
+
+		self.progress.start(strings::FETCH_POPUP_MSG);
+		self.git_fetch.request(cred)?;
+
+		Ok(())
+	}
+
+	/// runs the fetch its dry run previewed
+	pub fn fetch_previewed(&mut self) -> Result<()> {
+		let state =
+			std::mem::replace(&mut self.dry_run, DryRunState::Off);
+		if let DryRunState::Previewed(cred) = state {
+			self.show()?;
+			self.fetch_all(cred)?;
+		}
+
+		Ok(())
+	}
+
+	/// whether the fetch only waits for its dry run
+	const fn previewed(&self) -> bool {
+		matches!(self.dry_run, DryRunState::Previewed(_))
 	}
 
 	///
@@ -107,20 +157,84 @@ impl FetchComponent {
 	///
 	pub fn update_git(&mut self, ev: AsyncGitNotification) {
 		if self.is_visible() && ev == AsyncGitNotification::Fetch {
-			self.update();
+			if let Err(error) = self.update() {
+				self.pending = false;
+				self.hide();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("fetch failed:\n{}", error),
+				));
+			}
+		} else if self.is_visible()
+			&& ev == AsyncGitNotification::DryRun
+		{
+			self.update_dry_run();
+		}
+	}
+
+	/// asks to confirm the refs the dry run found changing
+	fn update_dry_run(&mut self) {
+		self.pending = self.async_dry_run.is_pending();
+		if self.pending {
+			return;
+		}
+
+		self.hide();
+		match self
+			.async_dry_run
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(updates))
+				if updates.iter().all(|update| {
+					update.change == RefChange::UpToDate
+				}) =>
+			{
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Success,
+					strings::TOAST_UP_TO_DATE.to_string(),
+				));
+			}
+			Some(Ok(updates)) => {
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::FetchPreviewed(updates),
+				));
+			}
+			Some(Err(e)) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("fetch dry run failed:\n{}", e),
+				));
+			}
+			None => (),
 		}
 	}
 
 	///
-	fn update(&mut self) {
-		self.pending = self.async_fetch.is_pending();
-		self.progress = self.async_fetch.progress();
+	fn update(&mut self) -> Result<()> {
+		self.pending = self.git_fetch.is_pending()?;
+		self.progress.set_progress(self.git_fetch.progress()?);
 
 		if !self.pending {
 			self.hide();
+			// refs of the remotes fetched before cancelling changed
 			self.queue
 				.push(InternalEvent::Update(NeedsUpdate::BRANCHES));
+
+			if self.git_fetch.is_cancelled() {
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Warning,
+					strings::TOAST_FETCH_CANCELLED.to_string(),
+				));
+			} else if let Some(err) = self.git_fetch.last_result()? {
+				anyhow::bail!(err);
+			} else {
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Success,
+					strings::TOAST_FETCHED.to_string(),
+				));
+			}
 		}
+
+		Ok(())
 	}
 }
 
@@ -131,27 +245,7 @@ impl DrawableComponent for FetchComponent {
 		rect: Rect,
 	) -> Result<()> {
 		if self.visible {
-			let progress = self.progress.unwrap_or_default().progress;
-
-			let area = ui::centered_rect_absolute(30, 3, f.size());
-
-			f.render_widget(Clear, area);
-			f.render_widget(
-				Gauge::default()
-					.block(
-						Block::default()
-							.title(Span::styled(
-								strings::FETCH_POPUP_MSG,
-								self.theme.title(true),
-							))
-							.borders(Borders::ALL)
-							.border_type(BorderType::Thick)
-							.border_style(self.theme.block(true)),
-					)
-					.gauge_style(self.theme.push_gauge())
-					.percent(u16::from(progress)),
-				area,
-			);
+			self.progress.draw(f, rect)?;
 			self.input_cred.draw(f, rect)?;
 		}
 
@@ -174,8 +268,12 @@ impl Component for FetchComponent {
 				return self.input_cred.commands(out, force_all);
 			}
 			out.push(CommandInfo::new(
-				strings::commands::close_msg(&self.key_config),
-				!self.pending,
+				strings::commands::cancel_remote_progress(
+					&self.key_config,
+				),
+				self.pending
+					&& !self.previewed()
+					&& !self.progress.is_cancelling(),
 				self.visible,
 			));
 		}
@@ -185,7 +283,7 @@ impl Component for FetchComponent {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(_) = ev {
+			if let Event::Key(e) = ev {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
@@ -194,9 +292,17 @@ impl Component for FetchComponent {
 					{
 						self.fetch_all(Some(
 							self.input_cred.get_cred().clone(),
-						));
+						))?;
 						self.input_cred.hide();
 					}
+				} else if key_match(
+					e,
+					self.key_config.keys.exit_popup,
+				) && self.pending
+					&& !self.previewed()
+				{
+					self.git_fetch.cancel();
+					self.progress.set_cancelling();
 				}
 			}
 			return Ok(EventState::Consumed);
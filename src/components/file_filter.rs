@@ -0,0 +1,173 @@
+use super::{
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState, TextInputComponent,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use crossterm::event::Event;
+use fuzzy_matcher::FuzzyMatcher;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// inline filter input for file lists, typing fuzzy matches the
+/// paths, a leading `'` matches the rest of the query exactly
+pub struct FileFilterComponent {
+	input: TextInputComponent,
+	key_config: SharedKeyConfig,
+}
+
+impl FileFilterComponent {
+	///
+	pub fn new(
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		let mut input = TextInputComponent::new(
+			theme,
+			key_config.clone(),
+			"",
+			"type to filter..",
+			false,
+		);
+		input.embed();
+
+		Self { input, key_config }
+	}
+
+	///
+	pub fn query(&self) -> &str {
+		self.input.get_text()
+	}
+
+	/// true if a query hides entries
+	pub fn is_filtering(&self) -> bool {
+		!self.query().is_empty()
+	}
+
+	///
+	pub fn matches(&self, path: &str) -> bool {
+		path_matches(self.query(), path)
+	}
+
+	/// `title` with the query appended while filtering
+	pub fn title(&self, title: &str) -> String {
+		if self.is_filtering() {
+			format!("{} [{}]", title, self.query())
+		} else {
+			title.to_string()
+		}
+	}
+
+	///
+	pub fn clear(&mut self) {
+		self.input.clear();
+		self.input.hide();
+	}
+
+	/// draws the input over the bottom border of the list in `area`
+	pub fn draw_in_list<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		area: Rect,
+	) -> Result<()> {
+		if area.height > 2 && area.width > 2 {
+			self.input.draw(
+				f,
+				Rect::new(
+					area.x + 1,
+					area.bottom() - 1,
+					area.width - 2,
+					1,
+				),
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for FileFilterComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		out.push(CommandInfo::new(
+			strings::commands::file_filter(
+				&self.key_config,
+				self.is_filtering(),
+			),
+			true,
+			!self.input.is_visible() || force_all,
+		));
+
+		CommandBlocking::PassingOn
+	}
+
+	/// while typing all text input is consumed, navigation keys
+	/// are passed on so the list can be moved in
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if let Event::Key(e) = ev {
+			if !self.input.is_visible() {
+				if key_match(e, self.key_config.keys.file_filter) {
+					self.input.show()?;
+					return Ok(EventState::Consumed);
+				}
+			} else if key_match(e, self.key_config.keys.exit_popup) {
+				self.clear();
+				return Ok(EventState::Consumed);
+			} else if key_match(e, self.key_config.keys.enter) {
+				self.input.hide();
+				return Ok(EventState::Consumed);
+			} else {
+				return self.input.event(ev);
+			}
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()
+	}
+}
+
+/// fuzzy unless `query` starts with `'`, an empty query matches all
+fn path_matches(query: &str, path: &str) -> bool {
+	query.strip_prefix('\'').map_or_else(
+		|| {
+			query.is_empty()
+				|| fuzzy_matcher::skim::SkimMatcherV2::default()
+					.fuzzy_match(path, query)
+					.is_some()
+		},
+		|exact| path.contains(exact),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_path_matches() {
+		assert!(path_matches("", "src/main.rs"));
+		assert!(path_matches("smr", "src/main.rs"));
+		assert!(!path_matches("rsm", "src/main.rs"));
+
+		assert!(path_matches("'ain.", "src/main.rs"));
+		assert!(!path_matches("'smr", "src/main.rs"));
+		assert!(path_matches("'", "src/main.rs"));
+	}
+}
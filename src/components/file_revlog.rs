@@ -87,6 +87,7 @@ impl FileRevlogComponent {
 				queue.clone(),
 				theme,
 				key_config.clone(),
+				options.clone(),
 				true,
 			),
 			git_log: None,
@@ -191,7 +192,10 @@ impl FileRevlogComponent {
 					let diff_params = DiffParams {
 						path: open_request.file_path.clone(),
 						diff_type: DiffType::Commit(commit_id),
-						options: self.options.borrow().diff,
+						options: self.diff.diff_options(
+							&open_request.file_path,
+							self.options.borrow().diff,
+						),
 					};
 
 					if let Some((params, last)) =
@@ -355,9 +359,13 @@ impl FileRevlogComponent {
 		needs_update
 	}
 
+	/// applies the requested selection only once so later log
+	/// updates do not move the cursor back to it
 	fn set_open_selection(&mut self) {
-		if let Some(selection) =
-			self.open_request.as_ref().and_then(|req| req.selection)
+		if let Some(selection) = self
+			.open_request
+			.as_mut()
+			.and_then(|req| req.selection.take())
 		{
 			let mut table_state = self.table_state.take();
 			table_state.select(Some(selection));
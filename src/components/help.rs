@@ -1,9 +1,10 @@
 use super::{
 	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState,
+	DrawableComponent, EventState, FileFilterComponent, ToastKind,
 };
 use crate::{
-	keys::{key_match, SharedKeyConfig},
+	keys::{key_match, GituiKeyEvent, KeyConfig, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
 	strings, ui,
 	version::Version,
 };
@@ -22,11 +23,44 @@ use tui::{
 };
 use ui::style::SharedTheme;
 
-///
+/// what the help lists
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum HelpMode {
+	/// the commands available where help was opened, described
+	Context,
+	/// all commands grouped by component
+	All,
+	/// every key binding by name, to find conflicts and rebind
+	Keys,
+}
+
+impl HelpMode {
+	const fn next(self) -> Self {
+		match self {
+			Self::Context => Self::All,
+			Self::All => Self::Keys,
+			Self::Keys => Self::Context,
+		}
+	}
+}
+
+/// lists the commands with their keys: all of them grouped by
+/// component, or (opened with `open_context_help`) only the ones
+/// available where help was opened, each with its description;
+/// the key bindings view rebinds the selected key in the key
+/// bindings file
 pub struct HelpComponent {
 	cmds: Vec<CommandInfo>,
+	context_cmds: Vec<CommandInfo>,
+	keys: Vec<(String, GituiKeyEvent)>,
+	conflicts: Vec<(String, Vec<String>)>,
+	mode: HelpMode,
+	/// waiting for the new key of the selected binding
+	rebinding: bool,
+	filter: FileFilterComponent,
 	visible: bool,
 	selection: u16,
+	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 }
@@ -40,8 +74,14 @@ impl DrawableComponent for HelpComponent {
 		if self.visible {
 			const SIZE: (u16, u16) = (65, 24);
 			let scroll_threshold = SIZE.1 / 3;
+			let (text, selected_line) = if self.mode == HelpMode::Keys
+			{
+				self.get_keys_text()
+			} else {
+				self.get_text()
+			};
 			let scroll =
-				self.selection.saturating_sub(scroll_threshold);
+				selected_line.saturating_sub(scroll_threshold);
 
 			let area =
 				ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
@@ -49,7 +89,11 @@ impl DrawableComponent for HelpComponent {
 			f.render_widget(Clear, area);
 			f.render_widget(
 				Block::default()
-					.title(strings::help_title(&self.key_config))
+					.title(self.filter.title(&strings::help_title(
+						&self.key_config,
+						self.mode,
+						self.conflicts.len(),
+					)))
 					.borders(Borders::ALL)
 					.border_type(BorderType::Thick),
 				area,
@@ -66,7 +110,7 @@ impl DrawableComponent for HelpComponent {
 				.split(area);
 
 			f.render_widget(
-				Paragraph::new(self.get_text())
+				Paragraph::new(text)
 					.scroll((scroll, 0))
 					.alignment(Alignment::Left),
 				chunks[0],
@@ -80,6 +124,8 @@ impl DrawableComponent for HelpComponent {
 				.alignment(Alignment::Right),
 				chunks[1],
 			);
+
+			self.filter.draw_in_list(f, area)?;
 		}
 
 		Ok(())
@@ -104,6 +150,34 @@ impl Component for HelpComponent {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::help_search(
+					&self.key_config,
+					self.filter.is_filtering(),
+				),
+				true,
+				!self.filter.is_visible(),
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::help_switch_mode(
+					&self.key_config,
+					self.mode.next(),
+				),
+				true,
+				true,
+			));
+
+			if self.mode == HelpMode::Keys {
+				out.push(CommandInfo::new(
+					strings::commands::help_rebind_key(
+						&self.key_config,
+					),
+					self.selected_key().is_some(),
+					!self.rebinding,
+				));
+			}
+
 			out.push(CommandInfo::new(
 				strings::commands::close_popup(&self.key_config),
 				true,
@@ -120,6 +194,17 @@ impl Component for HelpComponent {
 				)
 				.order(99),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::help_open_context(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(99)
+				.hidden(),
+			);
 		}
 
 		visibility_blocking(self)
@@ -127,7 +212,20 @@ impl Component for HelpComponent {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(e) = ev {
+			if self.rebinding {
+				if let Event::Key(e) = ev {
+					self.rebinding = false;
+					if !key_match(e, self.key_config.keys.exit_popup)
+					{
+						self.rebind(GituiKeyEvent::new(
+							e.code,
+							e.modifiers,
+						));
+					}
+				}
+			} else if self.filter.event(ev)?.is_consumed() {
+				self.selection = 0;
+			} else if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.exit_popup) {
 					self.hide();
 				} else if key_match(e, self.key_config.keys.move_down)
@@ -135,13 +233,31 @@ impl Component for HelpComponent {
 					self.move_selection(true);
 				} else if key_match(e, self.key_config.keys.move_up) {
 					self.move_selection(false);
-				} else {
+				} else if key_match(
+					e,
+					self.key_config.keys.tab_toggle,
+				) {
+					self.mode = self.mode.next();
+					self.selection = 0;
+				} else if self.mode == HelpMode::Keys
+					&& key_match(e, self.key_config.keys.enter)
+					&& self.selected_key().is_some()
+				{
+					self.rebinding = true;
 				}
 			}
 
 			Ok(EventState::Consumed)
 		} else if let Event::Key(k) = ev {
 			if key_match(k, self.key_config.keys.open_help) {
+				self.mode = HelpMode::All;
+				self.show()?;
+				Ok(EventState::Consumed)
+			} else if key_match(
+				k,
+				self.key_config.keys.open_context_help,
+			) {
+				self.mode = HelpMode::Context;
 				self.show()?;
 				Ok(EventState::Consumed)
 			} else {
@@ -162,33 +278,61 @@ impl Component for HelpComponent {
 
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
+		self.selection = 0;
+		self.rebinding = false;
+		self.filter.clear();
 
 		Ok(())
 	}
 }
 
 impl HelpComponent {
-	pub const fn new(
+	pub fn new(
+		queue: &Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 	) -> Self {
 		Self {
 			cmds: vec![],
+			context_cmds: vec![],
+			keys: key_config.keys.entries(),
+			conflicts: key_config.keys.conflicts(),
+			mode: HelpMode::All,
+			rebinding: false,
+			filter: FileFilterComponent::new(
+				theme.clone(),
+				key_config.clone(),
+			),
 			visible: false,
 			selection: 0,
+			queue: queue.clone(),
 			theme,
 			key_config,
 		}
 	}
 	///
 	pub fn set_cmds(&mut self, cmds: Vec<CommandInfo>) {
-		self.cmds = cmds
-			.into_iter()
-			.filter(|e| !e.text.hide_help)
-			.collect::<Vec<_>>();
-		self.cmds.sort_by_key(|e| e.text.clone());
-		self.cmds.dedup_by_key(|e| e.text.clone());
-		self.cmds.sort_by_key(|e| hash(&e.text.group));
+		self.cmds = help_cmds(cmds.into_iter());
+	}
+
+	/// the commands of where help gets opened, to be set while
+	/// help is not visible
+	pub fn set_context_cmds(&mut self, cmds: Vec<CommandInfo>) {
+		self.context_cmds =
+			help_cmds(cmds.into_iter().filter(|e| e.available));
+	}
+
+	fn shown_cmds(&self) -> impl Iterator<Item = &CommandInfo> {
+		if self.mode == HelpMode::Context {
+			&self.context_cmds
+		} else {
+			&self.cmds
+		}
+		.iter()
+		.filter(|e| {
+			self.filter.matches(&e.text.name)
+				|| self.filter.matches(e.text.desc)
+		})
 	}
 
 	fn move_selection(&mut self, inc: bool) {
@@ -201,20 +345,26 @@ impl HelpComponent {
 		};
 		new_selection = cmp::max(new_selection, 0);
 
-		if let Ok(max) =
-			u16::try_from(self.cmds.len().saturating_sub(1))
-		{
+		let count = if self.mode == HelpMode::Keys {
+			self.shown_keys().count()
+		} else {
+			self.shown_cmds().count()
+		};
+
+		if let Ok(max) = u16::try_from(count.saturating_sub(1)) {
 			self.selection = cmp::min(new_selection, max);
 		}
 	}
 
-	fn get_text(&self) -> Vec<Spans> {
+	/// the lines to draw and the one of the selected command
+	fn get_text(&self) -> (Vec<Spans>, u16) {
 		let mut txt: Vec<Spans> = Vec::new();
 
 		let mut processed = 0_u16;
+		let mut selected_line = 0_u16;
 
 		for (key, group) in
-			&self.cmds.iter().group_by(|e| e.text.group)
+			&self.shown_cmds().group_by(|e| e.text.group)
 		{
 			txt.push(Spans::from(Span::styled(
 				Cow::from(key.to_string()),
@@ -226,16 +376,22 @@ impl HelpComponent {
 
 				processed += 1;
 
+				if is_selected {
+					selected_line =
+						u16::try_from(txt.len()).unwrap_or(u16::MAX);
+				}
+
 				txt.push(Spans::from(Span::styled(
 					Cow::from(if is_selected {
 						format!(">{}", command_info.text.name)
 					} else {
 						format!(" {}", command_info.text.name)
 					}),
-					self.theme.text(true, is_selected),
+					self.theme
+						.text(command_info.enabled, is_selected),
 				)));
 
-				if is_selected {
+				if is_selected || self.mode == HelpMode::Context {
 					txt.push(Spans::from(Span::styled(
 						Cow::from(format!(
 							"  {}\n",
@@ -247,6 +403,110 @@ impl HelpComponent {
 			}
 		}
 
-		txt
+		(txt, selected_line)
+	}
+
+	fn shown_keys(
+		&self,
+	) -> impl Iterator<Item = &(String, GituiKeyEvent)> {
+		self.keys
+			.iter()
+			.filter(|(name, _)| self.filter.matches(name))
+	}
+
+	fn selected_key(&self) -> Option<&str> {
+		self.shown_keys()
+			.nth(self.selection.into())
+			.map(|(name, _)| name.as_str())
+	}
+
+	fn rebind(&self, key: GituiKeyEvent) {
+		if let Some(name) = self.selected_key() {
+			self.queue.push(
+				match KeyConfig::save_binding(name, key) {
+					Ok(()) => InternalEvent::ShowToast(
+						ToastKind::Success,
+						strings::key_rebound(
+							name,
+							&self.key_config.get_hint(key),
+						),
+					),
+					Err(e) => InternalEvent::ShowErrorMsg(format!(
+						"rebind key error:\n{}",
+						e
+					)),
+				},
+			);
+		}
+	}
+
+	/// the key bindings, conflicting ones with what they conflict
+	/// with, and the line of the selected one
+	fn get_keys_text(&self) -> (Vec<Spans>, u16) {
+		let mut txt = vec![Spans::from(Span::styled(
+			Cow::from(strings::HELP_KEYS_GROUP),
+			Style::default().add_modifier(Modifier::REVERSED),
+		))];
+		let mut selected_line = 0_u16;
+
+		for (idx, (name, key)) in self.shown_keys().enumerate() {
+			let is_selected = usize::from(self.selection) == idx;
+			let conflict = self
+				.conflicts
+				.iter()
+				.find(|(conflicting, _)| conflicting == name);
+
+			if is_selected {
+				selected_line =
+					u16::try_from(txt.len()).unwrap_or(u16::MAX);
+			}
+
+			txt.push(Spans::from(Span::styled(
+				Cow::from(format!(
+					"{}{} [{}]",
+					if is_selected { ">" } else { " " },
+					name,
+					self.key_config.get_hint(*key)
+				)),
+				if conflict.is_some() && !is_selected {
+					self.theme.text_danger()
+				} else {
+					self.theme.text(true, is_selected)
+				},
+			)));
+
+			if is_selected && self.rebinding {
+				txt.push(Spans::from(Span::styled(
+					Cow::from(strings::help_rebind_prompt(
+						&self.key_config,
+					)),
+					self.theme.text(true, is_selected),
+				)));
+			}
+
+			if let Some((_, others)) = conflict {
+				txt.push(Spans::from(Span::styled(
+					Cow::from(format!(
+						"  {}",
+						strings::key_conflict(name, others)
+					)),
+					self.theme.text_danger(),
+				)));
+			}
+		}
+
+		(txt, selected_line)
 	}
 }
+
+/// without the ones hidden from help, sorted and deduplicated
+fn help_cmds(
+	cmds: impl Iterator<Item = CommandInfo>,
+) -> Vec<CommandInfo> {
+	let mut cmds =
+		cmds.filter(|e| !e.text.hide_help).collect::<Vec<_>>();
+	cmds.sort_by_key(|e| e.text.clone());
+	cmds.dedup_by_key(|e| e.text.clone());
+	cmds.sort_by_key(|e| hash(&e.text.group));
+	cmds
+}
@@ -1,7 +1,8 @@
 use super::{
 	command_pump, event_pump, visibility_blocking, CommandBlocking,
 	CommandInfo, CommitDetailsComponent, Component, DiffComponent,
-	DrawableComponent, EventState, FileTreeOpen,
+	DrawableComponent, EventState, FileTreeOpen, SharedCommitCache,
+	SharedOptions,
 };
 use crate::{
 	accessors,
@@ -226,8 +227,10 @@ impl InspectCommitComponent {
 		repo: &RepoPathRef,
 		queue: &Queue,
 		sender: &Sender<AsyncGitNotification>,
+		commit_cache: &SharedCommitCache,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: &SharedOptions,
 	) -> Self {
 		Self {
 			queue: queue.clone(),
@@ -235,14 +238,17 @@ impl InspectCommitComponent {
 				repo,
 				queue,
 				sender,
+				commit_cache,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			diff: DiffComponent::new(
 				repo.clone(),
 				queue.clone(),
 				theme,
 				key_config.clone(),
+				options.clone(),
 				true,
 			),
 			open_request: None,
@@ -271,7 +277,10 @@ impl InspectCommitComponent {
 		ev: AsyncGitNotification,
 	) -> Result<()> {
 		if self.is_visible() {
-			if ev == AsyncGitNotification::CommitFiles {
+			if ev == AsyncGitNotification::CommitFiles
+				|| ev == AsyncGitNotification::ContainedIn
+				|| ev == AsyncGitNotification::Describe
+			{
 				self.update()?;
 			} else if ev == AsyncGitNotification::Diff {
 				self.update_diff()?;
@@ -292,7 +301,10 @@ impl InspectCommitComponent {
 						diff_type: DiffType::Commit(
 							request.commit_id,
 						),
-						options: DiffOptions::default(),
+						options: self.diff.diff_options(
+							&f.path,
+							DiffOptions::default(),
+						),
 					};
 
 					if let Some((params, last)) =
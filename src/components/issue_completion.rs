@@ -0,0 +1,185 @@
+use super::completion::{Completion, CompletionProvider};
+use crate::{args::get_app_cache_path, AsyncNotification};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	issues::AsyncIssuesJob,
+	sync::{
+		github::{github_repo, Issue},
+		RepoPathRef,
+	},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::File,
+	io::{Read, Write},
+	path::PathBuf,
+};
+#[derive(Serialize, Deserialize)]
+struct CachedIssue {
+	number: u64,
+	title: String,
+	is_pull_request: bool,
+}
+
+/// issues of the last successful fetch, so completion works
+/// offline and right away on startup
+#[derive(Serialize, Deserialize, Default)]
+struct IssueCacheFile {
+	issues: Vec<CachedIssue>,
+}
+
+impl IssueCacheFile {
+	fn path(repo: &RepoPathRef) -> Result<PathBuf> {
+		let (owner, name) = github_repo(&repo.borrow())?;
+		Ok(get_app_cache_path()?
+			.join(format!("issues-{}-{}.ron", owner, name)))
+	}
+
+	fn load(repo: &RepoPathRef) -> Result<Vec<Issue>> {
+		let mut buffer = Vec::new();
+		File::open(Self::path(repo)?)?.read_to_end(&mut buffer)?;
+		let file: Self = ron::de::from_bytes(&buffer)?;
+
+		Ok(file
+			.issues
+			.into_iter()
+			.map(|issue| Issue {
+				number: issue.number,
+				title: issue.title,
+				is_pull_request: issue.is_pull_request,
+			})
+			.collect())
+	}
+
+	fn save(repo: &RepoPathRef, issues: &[Issue]) -> Result<()> {
+		let file = Self {
+			issues: issues
+				.iter()
+				.map(|issue| CachedIssue {
+					number: issue.number,
+					title: issue.title.clone(),
+					is_pull_request: issue.is_pull_request,
+				})
+				.collect(),
+		};
+
+		File::create(Self::path(repo)?)?
+			.write_all(ron::ser::to_string(&file)?.as_bytes())?;
+
+		Ok(())
+	}
+}
+
+/// completes issue and pull request numbers of the github
+/// remote after a `#`
+pub struct IssueCompletion {
+	repo: RepoPathRef,
+	issues: Vec<Issue>,
+	loaded: bool,
+	async_issues: AsyncSingleJob<AsyncIssuesJob>,
+}
+
+impl IssueCompletion {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		Self {
+			repo,
+			issues: Vec::new(),
+			loaded: false,
+			async_issues: AsyncSingleJob::new(sender.clone()),
+		}
+	}
+
+	/// first use reads the cache and starts fetching
+	fn load(&mut self) {
+		if !self.loaded {
+			self.loaded = true;
+			self.issues =
+				IssueCacheFile::load(&self.repo).unwrap_or_default();
+			self.async_issues.spawn(AsyncIssuesJob::new(
+				self.repo.borrow().clone(),
+			));
+		}
+	}
+}
+
+impl CompletionProvider for IssueCompletion {
+	fn token_start(&self, text: &str) -> Option<usize> {
+		if !cfg!(feature = "github") {
+			return None;
+		}
+
+		let start = text.rfind('#')? + 1;
+
+		text[start..]
+			.chars()
+			.all(char::is_alphanumeric)
+			.then(|| start)
+	}
+
+	fn candidates(&mut self, token: &str) -> Vec<Completion> {
+		self.load();
+
+		let needle = token.to_lowercase();
+		self.issues
+			.iter()
+			.filter(|issue| {
+				issue.number.to_string().starts_with(&needle)
+					|| issue.title.to_lowercase().contains(&needle)
+			})
+			.map(|issue| Completion {
+				text: issue.number.to_string(),
+				label: format!(
+					"#{}{}",
+					issue.number,
+					if issue.is_pull_request { " PR" } else { "" }
+				),
+				detail: issue.title.clone(),
+			})
+			.collect()
+	}
+
+	fn update(&mut self, ev: AsyncNotification) -> bool {
+		if !matches!(
+			ev,
+			AsyncNotification::Git(AsyncGitNotification::Issues)
+		) {
+			return false;
+		}
+
+		match self
+			.async_issues
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(issues)) => {
+				if let Err(e) =
+					IssueCacheFile::save(&self.repo, &issues)
+				{
+					log::error!("issue cache error: {}", e);
+				}
+				self.issues = issues;
+			}
+			Some(Err(e)) => {
+				log::error!("fetching issues failed: {}", e);
+			}
+			None => (),
+		}
+
+		true
+	}
+
+	fn any_work_pending(&self) -> bool {
+		self.async_issues.is_pending()
+	}
+
+	fn preselect(&self) -> bool {
+		true
+	}
+}
@@ -0,0 +1,202 @@
+use crate::{
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, ToastKind,
+	},
+	keys::SharedKeyConfig,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	maintenance::AsyncMaintenanceJob,
+	sync::{MaintenanceProgress, MaintenanceTask, RepoPathRef},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	text::Span,
+	widgets::{Block, BorderType, Borders, Clear, Gauge},
+	Frame,
+};
+
+/// gauge of a `MaintenanceTask` running in the background
+pub struct MaintenanceComponent {
+	repo: RepoPathRef,
+	visible: bool,
+	async_maintenance: AsyncSingleJob<AsyncMaintenanceJob>,
+	progress: MaintenanceProgress,
+	pending: bool,
+	queue: Queue,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl MaintenanceComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		sender: &Sender<AsyncGitNotification>,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			queue: queue.clone(),
+			pending: false,
+			visible: false,
+			async_maintenance: AsyncSingleJob::new(sender.clone()),
+			progress: MaintenanceProgress::default(),
+			theme,
+			key_config,
+			repo,
+		}
+	}
+
+	///
+	pub fn run(&mut self, task: MaintenanceTask) -> Result<()> {
+		if self.pending {
+			return Ok(());
+		}
+
+		self.show()?;
+		self.pending = true;
+		self.progress = MaintenanceProgress::default();
+		self.async_maintenance.spawn(AsyncMaintenanceJob::new(
+			self.repo.borrow().clone(),
+			task,
+		));
+
+		Ok(())
+	}
+
+	///
+	pub const fn any_work_pending(&self) -> bool {
+		self.pending
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if self.is_visible()
+			&& ev == AsyncGitNotification::Maintenance
+		{
+			self.update();
+		}
+	}
+
+	fn update(&mut self) {
+		self.pending = self.async_maintenance.is_pending();
+		self.progress =
+			self.async_maintenance.progress().unwrap_or_default();
+
+		if self.pending {
+			return;
+		}
+
+		self.hide();
+		match self
+			.async_maintenance
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(())) => {
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Success,
+					strings::TOAST_MAINTENANCE_DONE.to_string(),
+				));
+			}
+			Some(Err(e)) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("maintenance failed:\n{}", e),
+				));
+			}
+			None => (),
+		}
+	}
+}
+
+impl DrawableComponent for MaintenanceComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		_rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect_absolute(30, 3, f.size());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Gauge::default()
+					.block(
+						Block::default()
+							.title(Span::styled(
+								strings::MAINTENANCE_TITLE,
+								self.theme.title(true),
+							))
+							.borders(Borders::ALL)
+							.border_type(BorderType::Thick)
+							.border_style(self.theme.block(true)),
+					)
+					.label(self.progress.step.as_str())
+					.gauge_style(self.theme.push_gauge())
+					.percent(u16::from(
+						self.progress.progress.progress,
+					)),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for MaintenanceComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::close_msg(&self.key_config),
+				!self.pending,
+				self.visible,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, _ev: &Event) -> Result<EventState> {
+		if self.visible {
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
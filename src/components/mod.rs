@@ -1,4 +1,5 @@
 mod blame_file;
+mod branch_compare;
 mod branchlist;
 mod changes;
 mod command;
@@ -6,68 +7,104 @@ mod commit;
 mod commit_details;
 mod commitlist;
 mod compare_commits;
+mod completion;
 mod create_branch;
 mod cred;
 mod diff;
+mod edit_note;
 mod externaleditor;
 mod fetch;
+mod file_filter;
 mod file_find_popup;
 mod file_revlog;
 mod help;
 mod inspect_commit;
+mod issue_completion;
+mod maintenance;
 mod msg;
+mod number_input;
 mod options_popup;
 mod pull;
+mod pull_requests;
 mod push;
+mod push_options;
+mod push_review;
 mod push_tags;
+mod recent_commits;
+mod remote_progress;
 mod rename_branch;
 mod reset;
 mod revision_files;
 mod revision_files_popup;
+mod search_results;
+mod select_list;
+mod snippet_completion;
 mod stashmsg;
+mod status_bar;
 mod status_tree;
 mod submodules;
 mod syntax_text;
 mod tag_commit;
 mod taglist;
+mod task_list;
 mod textinput;
+mod toast;
+mod tutorial;
 mod utils;
 
 pub use self::status_tree::StatusTreeComponent;
 pub use blame_file::{BlameFileComponent, BlameFileOpen};
+pub use branch_compare::{BranchCompareComponent, BranchCompareOpen};
 pub use branchlist::BranchListComponent;
 pub use changes::ChangesComponent;
 pub use command::{CommandInfo, CommandText};
 pub use commit::CommitComponent;
 pub use commit_details::CommitDetailsComponent;
-pub use commitlist::CommitList;
+pub use commitlist::{ci_symbol, CommitList};
 pub use compare_commits::CompareCommitsComponent;
 pub use create_branch::CreateBranchComponent;
 pub use diff::DiffComponent;
-pub use externaleditor::ExternalEditorComponent;
+pub use edit_note::EditNoteComponent;
+pub use externaleditor::{ExternalEditorComponent, ExternalTool};
 pub use fetch::FetchComponent;
+pub use file_filter::FileFilterComponent;
 pub use file_find_popup::FileFindPopup;
 pub use file_revlog::{FileRevOpen, FileRevlogComponent};
-pub use help::HelpComponent;
+pub use help::{HelpComponent, HelpMode};
 pub use inspect_commit::{InspectCommitComponent, InspectCommitOpen};
+pub use maintenance::MaintenanceComponent;
 pub use msg::MsgComponent;
+pub use number_input::{NumberInputComponent, NumberInputOpen};
 pub use options_popup::{
-	AppOption, OptionsPopupComponent, SharedOptions,
+	AppOption, Options, OptionsPopupComponent, SharedOptions,
 };
 pub use pull::PullComponent;
+pub use pull_requests::PullRequestsComponent;
 pub use push::PushComponent;
+pub use push_options::PushOptionsComponent;
+pub use push_review::PushReviewComponent;
 pub use push_tags::PushTagsComponent;
+pub use recent_commits::RecentCommitsComponent;
+pub use remote_progress::RemoteProgressComponent;
 pub use rename_branch::RenameBranchComponent;
-pub use reset::ConfirmComponent;
+pub use reset::{ConfirmComponent, ConfirmPrefs};
 pub use revision_files::RevisionFilesComponent;
 pub use revision_files_popup::{FileTreeOpen, RevisionFilesPopup};
+pub use search_results::SearchResultsComponent;
+pub use select_list::{SelectListComponent, SelectListOpen};
 pub use stashmsg::StashMsgComponent;
+pub use status_bar::{StatusBarComponent, StatusSummary};
 pub use submodules::SubmodulesListComponent;
 pub use syntax_text::SyntaxTextComponent;
 pub use tag_commit::TagCommitComponent;
 pub use taglist::TagListComponent;
+pub use task_list::TaskListComponent;
 pub use textinput::{InputType, TextInputComponent};
+pub use toast::{ToastComponent, ToastKind};
+pub use tutorial::TutorialComponent;
+pub use utils::commit_cache::SharedCommitCache;
 pub use utils::filetree::FileTreeItemKind;
+pub use utils::time_to_string;
 
 use crate::ui::style::Theme;
 use anyhow::Result;
@@ -305,24 +342,29 @@ fn popup_paragraph<'a, T>(
 	content: T,
 	theme: &Theme,
 	focused: bool,
-	block: bool,
+	borders: Borders,
+	wrap: bool,
 ) -> Paragraph<'a>
 where
 	T: Into<Text<'a>>,
 {
-	let paragraph = Paragraph::new(content.into())
-		.alignment(Alignment::Left)
-		.wrap(Wrap { trim: true });
+	let paragraph =
+		Paragraph::new(content.into()).alignment(Alignment::Left);
+	let paragraph = if wrap {
+		paragraph.wrap(Wrap { trim: true })
+	} else {
+		paragraph
+	};
 
-	if block {
+	if borders.is_empty() {
+		paragraph
+	} else {
 		paragraph.block(
 			Block::default()
 				.title(Span::styled(title, theme.title(focused)))
-				.borders(Borders::ALL)
+				.borders(borders)
 				.border_type(BorderType::Thick)
 				.border_style(theme.block(focused)),
 		)
-	} else {
-		paragraph
 	}
 }
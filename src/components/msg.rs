@@ -1,25 +1,44 @@
 use super::{
-	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState,
+	utils::scroll_vertical::VerticalScroll, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState, ScrollType, TextInputComponent, ToastKind,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
 	strings, ui,
 };
+use asyncgit::sync::PushSummary;
 use crossterm::event::Event;
-use std::convert::TryFrom;
+use easy_cast::Cast;
+use std::cell::Cell;
 use tui::{
 	backend::Backend,
-	layout::{Alignment, Rect},
-	text::Span,
-	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	layout::{Margin, Rect},
+	text::{Span, Spans},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
 	Frame,
 };
 use ui::style::SharedTheme;
+
+/// shows (possibly long) messages like errors or hook output,
+/// supports scrolling, searching and copying the whole message
 pub struct MsgComponent {
 	title: String,
 	msg: String,
 	visible: bool,
+	scroll: VerticalScroll,
+	/// width the message got wrapped to on last draw
+	width: Cell<usize>,
+	/// visible rows on last draw
+	height: Cell<usize>,
+	search: TextInputComponent,
+	searching: bool,
+	/// row of the current search hit
+	hit: Option<usize>,
+	/// commit range offered for copying
+	range: Option<String>,
+	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 }
@@ -36,43 +55,82 @@ impl DrawableComponent for MsgComponent {
 			return Ok(());
 		}
 
-		// determine the maximum width of text block
-		let lens = self
-			.msg
-			.split('\n')
-			.map(str::len)
-			.collect::<Vec<usize>>();
-		let mut max = lens.iter().max().expect("max") + 2;
-		if max > std::u16::MAX as usize {
-			max = std::u16::MAX as usize;
-		}
-		let mut width =
-			u16::try_from(max).expect("cant fail due to check above");
+		// determine the maximum width of text block,
 		// dont overflow screen, and dont get too narrow
-		if width > f.size().width {
-			width = f.size().width;
-		} else if width < 60 {
-			width = 60;
-		}
+		let max = self
+			.msg
+			.lines()
+			.map(|line| line.chars().count())
+			.max()
+			.unwrap_or_default()
+			+ 3;
+		let width: u16 = max
+			.clamp(60, f.size().width.into())
+			.min(f.size().width.into())
+			.cast();
+
+		let rows = self.rows(usize::from(width).saturating_sub(3));
+		let search_height = u16::from(self.search_visible());
+		let height: u16 = (rows.len() + 2)
+			.min(f.size().height.saturating_sub(4).into())
+			.cast();
+		let height = height.saturating_add(search_height).max(5);
 
-		let area = ui::centered_rect_absolute(width, 25, f.size());
+		let area =
+			ui::centered_rect_absolute(width, height, f.size());
 		f.render_widget(Clear, area);
 		f.render_widget(
-			Paragraph::new(self.msg.clone())
-				.block(
-					Block::default()
-						.title(Span::styled(
-							self.title.as_str(),
-							self.theme.text_danger(),
-						))
-						.borders(Borders::ALL)
-						.border_type(BorderType::Thick),
-				)
-				.alignment(Alignment::Left)
-				.wrap(Wrap { trim: true }),
+			Block::default()
+				.title(Span::styled(
+					self.title.as_str(),
+					self.theme.text_danger(),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick),
 			area,
 		);
 
+		let inner = area.inner(&Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+		let text_area = Rect {
+			height: inner.height.saturating_sub(search_height),
+			width: inner.width.saturating_sub(1),
+			..inner
+		};
+
+		self.width.set(text_area.width.into());
+		self.height.set(text_area.height.into());
+		let top = self
+			.scroll
+			.update_no_selection(rows.len(), self.height.get());
+
+		f.render_widget(
+			Paragraph::new(
+				rows.iter()
+					.enumerate()
+					.skip(top)
+					.take(self.height.get())
+					.map(|(idx, row)| self.highlight(idx, row))
+					.collect::<Vec<_>>(),
+			),
+			text_area,
+		);
+
+		self.scroll.draw(f, area, &self.theme);
+
+		if self.search_visible() {
+			self.search.draw(
+				f,
+				Rect {
+					y: text_area.y + text_area.height,
+					height: 1,
+					..inner
+				},
+			)?;
+		}
+
 		Ok(())
 	}
 }
@@ -88,6 +146,29 @@ impl Component for MsgComponent {
 			true,
 			self.visible,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::scroll(&self.key_config),
+			true,
+			self.visible,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::msg_search(
+				&self.key_config,
+				self.searching,
+			),
+			true,
+			self.visible,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::msg_copy(&self.key_config),
+			true,
+			self.visible && !self.searching,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::msg_copy_range(&self.key_config),
+			true,
+			self.visible && !self.searching && self.range.is_some(),
+		));
 
 		visibility_blocking(self)
 	}
@@ -95,8 +176,42 @@ impl Component for MsgComponent {
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
 			if let Event::Key(e) = ev {
-				if key_match(e, self.key_config.keys.enter) {
+				if self.searching {
+					if key_match(e, self.key_config.keys.exit_popup) {
+						self.searching = false;
+					} else if key_match(e, self.key_config.keys.enter)
+					{
+						self.next_hit(
+							self.hit.map_or(0, |hit| hit + 1),
+						);
+					} else if self.search.event(ev)?.is_consumed() {
+						self.next_hit(self.hit.unwrap_or_else(
+							|| self.scroll.get_top(),
+						));
+					}
+				} else if key_match(e, self.key_config.keys.enter)
+					|| key_match(e, self.key_config.keys.exit_popup)
+				{
 					self.hide();
+				} else if key_match(e, self.key_config.keys.file_find)
+				{
+					self.searching = true;
+				} else if key_match(e, self.key_config.keys.copy) {
+					self.copy();
+				} else if key_match(
+					e,
+					self.key_config.keys.copy_range,
+				) {
+					self.copy_range();
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					self.scroll.move_top(ScrollType::Down);
+				} else if key_match(e, self.key_config.keys.move_up) {
+					self.scroll.move_top(ScrollType::Up);
+				} else if key_match(e, self.key_config.keys.home) {
+					self.scroll.move_top(ScrollType::Home);
+				} else if key_match(e, self.key_config.keys.end) {
+					self.scroll.move_top(ScrollType::End);
 				}
 			}
 			Ok(EventState::Consumed)
@@ -115,20 +230,43 @@ impl Component for MsgComponent {
 
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
+		self.searching = false;
+		self.hit = None;
+		self.search.clear();
+		self.search.show()?;
+		self.scroll.reset();
 
 		Ok(())
 	}
 }
 
 impl MsgComponent {
-	pub const fn new(
+	pub fn new(
+		queue: &Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 	) -> Self {
+		let mut search = TextInputComponent::new(
+			theme.clone(),
+			key_config.clone(),
+			"",
+			strings::MSG_SEARCH_HINT,
+			false,
+		);
+		search.embed();
+
 		Self {
 			title: String::new(),
 			msg: String::new(),
 			visible: false,
+			scroll: VerticalScroll::new(),
+			width: Cell::new(0),
+			height: Cell::new(0),
+			search,
+			searching: false,
+			hit: None,
+			range: None,
+			queue: queue.clone(),
 			theme,
 			key_config,
 		}
@@ -138,6 +276,7 @@ impl MsgComponent {
 	pub fn show_error(&mut self, msg: &str) -> Result<()> {
 		self.title = strings::msg_title_error(&self.key_config);
 		self.msg = msg.to_string();
+		self.range = None;
 		self.show()?;
 
 		Ok(())
@@ -147,8 +286,222 @@ impl MsgComponent {
 	pub fn show_info(&mut self, msg: &str) -> Result<()> {
 		self.title = strings::msg_title_info(&self.key_config);
 		self.msg = msg.to_string();
+		self.range = None;
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// the refs a push updated and what the server said, offering
+	/// to copy the new commit range
+	pub fn show_push_summary(
+		&mut self,
+		summary: &PushSummary,
+	) -> Result<()> {
+		let refs = summary
+			.refs
+			.iter()
+			.map(|pushed| {
+				strings::push_summary_ref(
+					&pushed.name,
+					pushed
+						.old
+						.map(|id| id.get_short_string())
+						.as_deref(),
+					pushed
+						.new
+						.map(|id| id.get_short_string())
+						.as_deref(),
+					pushed.fast_forward,
+				)
+			})
+			.collect::<Vec<_>>();
+
+		self.title = strings::msg_title_pushed(&summary.remote);
+		self.msg =
+			strings::push_summary_msg(&refs, &summary.messages);
+		self.range = summary.range();
 		self.show()?;
 
 		Ok(())
 	}
+
+	fn search_visible(&self) -> bool {
+		self.searching || !self.search.get_text().is_empty()
+	}
+
+	/// message wrapped to `width`
+	fn rows(&self, width: usize) -> Vec<String> {
+		self.msg
+			.lines()
+			.flat_map(|line| {
+				if line.is_empty() {
+					vec![String::new()]
+				} else {
+					textwrap::wrap(line, width.max(1))
+						.into_iter()
+						.map(std::borrow::Cow::into_owned)
+						.collect()
+				}
+			})
+			.collect()
+	}
+
+	/// scrolls to the first row at or after `from` containing
+	/// the search term, wrapping around at the end
+	fn next_hit(&mut self, from: usize) {
+		let needle = self.search.get_text().to_ascii_lowercase();
+		if needle.is_empty() {
+			self.hit = None;
+			return;
+		}
+
+		let rows = self.rows(self.width.get());
+		let count = rows.len();
+
+		self.hit =
+			(0..count).map(|idx| (from + idx) % count).find(|idx| {
+				rows[*idx].to_ascii_lowercase().contains(&needle)
+			});
+
+		if let Some(hit) = self.hit {
+			self.scroll.update(hit, count, self.height.get());
+		}
+	}
+
+	fn highlight<'a>(&self, idx: usize, row: &'a str) -> Spans<'a> {
+		let needle = self.search.get_text().to_ascii_lowercase();
+		if needle.is_empty() {
+			return Spans::from(row);
+		}
+
+		let hit_style = self.theme.text(true, self.hit == Some(idx));
+		let lower = row.to_ascii_lowercase();
+		let mut spans = Vec::new();
+		let mut pos = 0;
+
+		while let Some(start) =
+			lower[pos..].find(&needle).map(|start| start + pos)
+		{
+			let end = start + needle.len();
+			spans.push(Span::raw(&row[pos..start]));
+			spans.push(Span::styled(
+				&row[start..end],
+				hit_style
+					.add_modifier(tui::style::Modifier::UNDERLINED),
+			));
+			pos = end;
+		}
+		spans.push(Span::raw(&row[pos..]));
+
+		Spans::from(spans)
+	}
+
+	fn copy(&self) {
+		self.copy_to_clipboard(&self.msg, strings::MSG_COPIED);
+	}
+
+	fn copy_range(&self) {
+		if let Some(range) = &self.range {
+			self.copy_to_clipboard(range, strings::MSG_RANGE_COPIED);
+		}
+	}
+
+	fn copy_to_clipboard(&self, text: &str, copied: &str) {
+		match crate::clipboard::copy_string(text) {
+			Ok(()) => self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Success,
+				copied.to_string(),
+			)),
+			Err(e) => self.queue.push(InternalEvent::ShowToast(
+				ToastKind::Warning,
+				format!("copy to clipboard error: {}", e),
+			)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_search() {
+		let mut comp = MsgComponent::new(
+			&Queue::new(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+		comp.show_error("pre-push hook failed:\nfoo\nbar\nFoo bar")
+			.unwrap();
+		comp.width.set(80);
+		comp.height.set(2);
+
+		comp.search.set_text(String::from("foo"));
+		comp.next_hit(0);
+		assert_eq!(comp.hit, Some(1));
+
+		comp.next_hit(2);
+		assert_eq!(comp.hit, Some(3));
+		assert_eq!(comp.scroll.get_top(), 2);
+
+		comp.next_hit(4);
+		assert_eq!(comp.hit, Some(1));
+
+		comp.search.set_text(String::from("baz"));
+		comp.next_hit(0);
+		assert_eq!(comp.hit, None);
+	}
+
+	#[test]
+	fn test_push_summary() {
+		let mut comp = MsgComponent::new(
+			&Queue::new(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+		comp.show_push_summary(&PushSummary {
+			remote: String::from("origin"),
+			refs: vec![asyncgit::sync::PushedRef {
+				name: String::from("refs/for/master"),
+				old: None,
+				new: Some(asyncgit::sync::CommitId::default()),
+				fast_forward: None,
+			}],
+			messages: vec![String::from("review created")],
+		})
+		.unwrap();
+
+		assert_eq!(comp.title, "Pushed to origin");
+		assert_eq!(
+			comp.msg,
+			"refs/for/master  0000000 (new)\n\nremote:\nreview created"
+		);
+		assert_eq!(comp.range, None);
+
+		assert_eq!(
+			strings::push_summary_ref(
+				"refs/heads/a",
+				Some("1111111"),
+				Some("2222222"),
+				Some(false)
+			),
+			"refs/heads/a  1111111...2222222 (forced update)"
+		);
+	}
+
+	#[test]
+	fn test_wrap() {
+		let mut comp = MsgComponent::new(
+			&Queue::new(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+		comp.show_info("aaa bbb ccc\n\nddd").unwrap();
+
+		assert_eq!(
+			comp.rows(4),
+			vec!["aaa", "bbb", "ccc", "", "ddd"]
+		);
+	}
 }
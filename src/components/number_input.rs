@@ -0,0 +1,276 @@
+use super::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, PickTarget, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use tui::{
+	backend::Backend,
+	layout::{Alignment, Rect},
+	style::Modifier,
+	text::{Span, Spans},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+///
+pub struct NumberInputOpen {
+	///
+	pub target: PickTarget,
+	///
+	pub title: String,
+	///
+	pub value: usize,
+	///
+	pub min: usize,
+	///
+	pub max: usize,
+}
+
+/// edits a number within `min..=max`
+pub struct NumberInputComponent {
+	queue: Queue,
+	target: Option<PickTarget>,
+	title: String,
+	text: String,
+	min: usize,
+	max: usize,
+	visible: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl NumberInputComponent {
+	///
+	pub fn new(
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			queue: queue.clone(),
+			target: None,
+			title: String::new(),
+			text: String::new(),
+			min: 0,
+			max: 0,
+			visible: false,
+			theme,
+			key_config,
+		}
+	}
+
+	///
+	pub fn open(&mut self, open: NumberInputOpen) -> Result<()> {
+		self.target = Some(open.target);
+		self.title = open.title;
+		self.min = open.min;
+		self.max = open.max;
+		self.text = open.value.clamp(open.min, open.max).to_string();
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// entered value if it is within bounds
+	fn value(&self) -> Option<usize> {
+		self.text
+			.parse::<usize>()
+			.ok()
+			.filter(|v| (self.min..=self.max).contains(v))
+	}
+
+	fn step(&mut self, up: bool) {
+		let value = self.text.parse::<usize>().unwrap_or(self.min);
+		let value = if up {
+			value.saturating_add(1)
+		} else {
+			value.saturating_sub(1)
+		};
+
+		self.text = value.clamp(self.min, self.max).to_string();
+	}
+
+	fn confirm(&mut self) {
+		if let (Some(target), Some(value)) =
+			(self.target, self.value())
+		{
+			self.queue.push(InternalEvent::Picked(target, value));
+			self.hide();
+		}
+	}
+}
+
+impl DrawableComponent for NumberInputComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect_absolute(32, 4, rect);
+
+			let value_style = if self.value().is_some() {
+				self.theme.text(true, false)
+			} else {
+				self.theme.text_danger()
+			};
+
+			let txt = vec![
+				Spans::from(Span::styled(
+					self.text.as_str(),
+					value_style.add_modifier(Modifier::UNDERLINED),
+				)),
+				Spans::from(Span::styled(
+					strings::number_input_bounds(self.min, self.max),
+					self.theme.text(false, false),
+				)),
+			];
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(txt)
+					.alignment(Alignment::Center)
+					.block(
+						Block::default()
+							.borders(Borders::all())
+							.title(Span::styled(
+								self.title.as_str(),
+								self.theme.title(true),
+							))
+							.border_style(self.theme.block(true)),
+					),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for NumberInputComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::number_input_step(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::select_list_confirm(
+					&self.key_config,
+				),
+				self.value().is_some(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				} else if key_match(e, self.key_config.keys.popup_up)
+				{
+					self.step(true);
+				} else if key_match(
+					e,
+					self.key_config.keys.popup_down,
+				) {
+					self.step(false);
+				} else {
+					match e.code {
+						KeyCode::Char(c) if c.is_ascii_digit() => {
+							if self.text == "0" {
+								self.text.clear();
+							}
+							self.text.push(c);
+						}
+						KeyCode::Backspace => {
+							self.text.pop();
+						}
+						_ => (),
+					}
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::components::AppOption;
+
+	#[test]
+	fn test_bounds() {
+		let mut comp = NumberInputComponent::new(
+			&Queue::new(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+
+		comp.open(NumberInputOpen {
+			target: PickTarget::Option(AppOption::DiffContextLines),
+			title: String::new(),
+			value: 50,
+			min: 1,
+			max: 10,
+		})
+		.unwrap();
+
+		assert_eq!(comp.value(), Some(10));
+
+		comp.step(true);
+		assert_eq!(comp.value(), Some(10));
+
+		comp.text = String::from("11");
+		assert_eq!(comp.value(), None);
+
+		comp.step(false);
+		assert_eq!(comp.value(), Some(10));
+	}
+}
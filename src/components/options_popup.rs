@@ -2,18 +2,23 @@ use std::{cell::RefCell, rc::Rc};
 
 use super::{
 	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState,
+	DrawableComponent, EventState, NumberInputOpen, SelectListOpen,
 };
 use crate::{
-	components::utils::string_width_align,
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, Queue},
+	queue::{InternalEvent, PickTarget, Queue},
+	secrets::SecretPaths,
+	string_utils::fit_to_width,
 	strings::{self},
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::sync::{diff::DiffOptions, ShowUntrackedFilesConfig};
+use asyncgit::sync::{
+	diff::{DiffAlgorithm, DiffOptions, LineEndings},
+	ShowUntrackedFilesConfig, StatusGroup, StatusOrder, StatusSort,
+};
 use crossterm::event::Event;
+use easy_cast::Cast;
 use tui::{
 	backend::Backend,
 	layout::{Alignment, Rect},
@@ -26,15 +31,38 @@ use tui::{
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AppOption {
 	StatusShowUntracked,
+	StatusSort,
+	StatusGroup,
 	DiffIgnoreWhitespaces,
 	DiffContextLines,
 	DiffInterhunkLines,
+	DiffLineEndings,
+	DiffAlgorithm,
+	LogShowAvatars,
 }
 
-#[derive(Default, Copy, Clone)]
+const UNTRACKED_CHOICES: [Option<ShowUntrackedFilesConfig>; 4] = [
+	None,
+	Some(ShowUntrackedFilesConfig::No),
+	Some(ShowUntrackedFilesConfig::Normal),
+	Some(ShowUntrackedFilesConfig::All),
+];
+
+const LINE_ENDINGS_CHOICES: [LineEndings; 3] =
+	[LineEndings::Hide, LineEndings::Show, LineEndings::Normalize];
+
+const MAX_DIFF_LINES: usize = 100;
+
+#[derive(Default, Clone)]
 pub struct Options {
 	pub status_show_untracked: Option<ShowUntrackedFilesConfig>,
+	/// stored in the repository config once changed here
+	pub status_order: StatusOrder,
 	pub diff: DiffOptions,
+	/// block art avatars of authors in commit details
+	pub log_show_avatars: bool,
+	/// not editable here, comes from `secrets.ron`
+	pub secrets: SecretPaths,
 }
 
 pub type SharedOptions = Rc<RefCell<Options>>;
@@ -81,14 +109,25 @@ impl OptionsPopupComponent {
 			txt,
 			width,
 			"Show untracked",
-			match self.options.borrow().status_show_untracked {
-				None => "Gitconfig",
-				Some(ShowUntrackedFilesConfig::No) => "No",
-				Some(ShowUntrackedFilesConfig::Normal) => "Normal",
-				Some(ShowUntrackedFilesConfig::All) => "All",
-			},
+			untracked_label(
+				self.options.borrow().status_show_untracked,
+			),
 			self.is_select(AppOption::StatusShowUntracked),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Sort by",
+			sort_label(self.options.borrow().status_order.sort),
+			self.is_select(AppOption::StatusSort),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Group by",
+			group_label(self.options.borrow().status_order.group),
+			self.is_select(AppOption::StatusGroup),
+		);
 		Self::add_header(txt, "");
 
 		Self::add_header(txt, "Diff");
@@ -113,6 +152,226 @@ impl OptionsPopupComponent {
 			&self.options.borrow().diff.interhunk_lines.to_string(),
 			self.is_select(AppOption::DiffInterhunkLines),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Line endings (CRLF)",
+			line_endings_label(
+				self.options.borrow().diff.line_endings,
+			),
+			self.is_select(AppOption::DiffLineEndings),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Algorithm",
+			self.options.borrow().diff.algorithm.name(),
+			self.is_select(AppOption::DiffAlgorithm),
+		);
+		Self::add_header(txt, "");
+
+		Self::add_header(txt, "Log");
+		self.add_entry(
+			txt,
+			width,
+			"Show avatars",
+			&self.options.borrow().log_show_avatars.to_string(),
+			self.is_select(AppOption::LogShowAvatars),
+		);
+	}
+
+	/// opens the popup picking a value for the selected option
+	fn edit_option(&mut self) {
+		let diff = self.options.borrow().diff;
+		let order = self.options.borrow().status_order;
+		let number = |title: &str, value: u32| {
+			InternalEvent::OpenNumberInput(NumberInputOpen {
+				target: PickTarget::Option(self.selection),
+				title: title.to_string(),
+				value: value.cast(),
+				min: 0,
+				max: MAX_DIFF_LINES,
+			})
+		};
+
+		let ev = match self.selection {
+			AppOption::StatusShowUntracked => {
+				let current =
+					self.options.borrow().status_show_untracked;
+				InternalEvent::OpenSelectList(SelectListOpen {
+					target: PickTarget::Option(self.selection),
+					title: String::from("Show untracked"),
+					items: UNTRACKED_CHOICES
+						.iter()
+						.map(|c| untracked_label(*c).to_string())
+						.collect(),
+					selected: UNTRACKED_CHOICES
+						.iter()
+						.position(|c| *c == current)
+						.unwrap_or_default(),
+				})
+			}
+			AppOption::StatusSort => {
+				InternalEvent::OpenSelectList(SelectListOpen {
+					target: PickTarget::Option(self.selection),
+					title: String::from("Sort by"),
+					items: StatusSort::ALL
+						.iter()
+						.map(|c| sort_label(*c).to_string())
+						.collect(),
+					selected: StatusSort::ALL
+						.iter()
+						.position(|c| *c == order.sort)
+						.unwrap_or_default(),
+				})
+			}
+			AppOption::StatusGroup => {
+				InternalEvent::OpenSelectList(SelectListOpen {
+					target: PickTarget::Option(self.selection),
+					title: String::from("Group by"),
+					items: StatusGroup::ALL
+						.iter()
+						.map(|c| group_label(*c).to_string())
+						.collect(),
+					selected: StatusGroup::ALL
+						.iter()
+						.position(|c| *c == order.group)
+						.unwrap_or_default(),
+				})
+			}
+			AppOption::DiffIgnoreWhitespaces
+			| AppOption::LogShowAvatars => {
+				self.switch_option(true);
+				return;
+			}
+			AppOption::DiffContextLines => {
+				number("Context lines", diff.context)
+			}
+			AppOption::DiffInterhunkLines => {
+				number("Inter hunk lines", diff.interhunk_lines)
+			}
+			AppOption::DiffLineEndings => {
+				InternalEvent::OpenSelectList(SelectListOpen {
+					target: PickTarget::Option(self.selection),
+					title: String::from("Line endings (CRLF)"),
+					items: LINE_ENDINGS_CHOICES
+						.iter()
+						.map(|c| line_endings_label(*c).to_string())
+						.collect(),
+					selected: LINE_ENDINGS_CHOICES
+						.iter()
+						.position(|c| *c == diff.line_endings)
+						.unwrap_or_default(),
+				})
+			}
+			AppOption::DiffAlgorithm => {
+				InternalEvent::OpenSelectList(SelectListOpen {
+					target: PickTarget::Option(self.selection),
+					title: String::from("Algorithm"),
+					items: DiffAlgorithm::ALL
+						.iter()
+						.map(|c| c.name().to_string())
+						.collect(),
+					selected: DiffAlgorithm::ALL
+						.iter()
+						.position(|c| *c == diff.algorithm)
+						.unwrap_or_default(),
+				})
+			}
+		};
+
+		self.queue.push(ev);
+	}
+
+	/// applies a value picked in the popup opened by `edit_option`
+	pub fn picked(&self, option: AppOption, value: usize) {
+		match option {
+			AppOption::StatusShowUntracked => {
+				if let Some(untracked) = UNTRACKED_CHOICES.get(value)
+				{
+					self.options.borrow_mut().status_show_untracked =
+						*untracked;
+				}
+			}
+			AppOption::StatusSort => {
+				if let Some(sort) = StatusSort::ALL.get(value) {
+					self.options.borrow_mut().status_order.sort =
+						*sort;
+				}
+			}
+			AppOption::StatusGroup => {
+				if let Some(group) = StatusGroup::ALL.get(value) {
+					self.options.borrow_mut().status_order.group =
+						*group;
+				}
+			}
+			AppOption::DiffIgnoreWhitespaces => {
+				self.options.borrow_mut().diff.ignore_whitespace =
+					value != 0;
+			}
+			AppOption::DiffContextLines => {
+				self.options.borrow_mut().diff.context = value.cast();
+			}
+			AppOption::DiffInterhunkLines => {
+				self.options.borrow_mut().diff.interhunk_lines =
+					value.cast();
+			}
+			AppOption::DiffLineEndings => {
+				if let Some(line_endings) =
+					LINE_ENDINGS_CHOICES.get(value)
+				{
+					self.options.borrow_mut().diff.line_endings =
+						*line_endings;
+				}
+			}
+			AppOption::DiffAlgorithm => {
+				if let Some(algorithm) = DiffAlgorithm::ALL.get(value)
+				{
+					self.options.borrow_mut().diff.algorithm =
+						*algorithm;
+				}
+			}
+			AppOption::LogShowAvatars => {
+				self.options.borrow_mut().log_show_avatars =
+					value != 0;
+			}
+		}
+
+		self.queue.push(InternalEvent::OptionSwitched(option));
+	}
+
+	fn cycle_untracked(&self, right: bool) {
+		let current = self.options.borrow().status_show_untracked;
+		self.options.borrow_mut().status_show_untracked =
+			cycle(&UNTRACKED_CHOICES, current, right);
+	}
+
+	fn cycle_line_endings(&self, right: bool) {
+		let current = self.options.borrow().diff.line_endings;
+		self.options.borrow_mut().diff.line_endings =
+			cycle(&LINE_ENDINGS_CHOICES, current, right);
+	}
+
+	fn cycle_status_order(&self, option: AppOption, right: bool) {
+		let mut order = self.options.borrow().status_order;
+		if option == AppOption::StatusSort {
+			order.sort = cycle(&StatusSort::ALL, order.sort, right);
+		} else {
+			order.group =
+				cycle(&StatusGroup::ALL, order.group, right);
+		}
+		self.options.borrow_mut().status_order = order;
+	}
+
+	fn cycle_algorithm(&self, right: bool) {
+		let current = self.options.borrow().diff.algorithm;
+		self.options.borrow_mut().diff.algorithm =
+			cycle(&DiffAlgorithm::ALL, current, right);
+	}
+
+	fn toggle_avatars(&self) {
+		let old = self.options.borrow().log_show_avatars;
+		self.options.borrow_mut().log_show_avatars = !old;
 	}
 
 	fn is_select(&self, kind: AppOption) -> bool {
@@ -138,7 +397,7 @@ impl OptionsPopupComponent {
 		let half = usize::from(width / 2);
 		txt.push(Spans::from(vec![
 			Span::styled(
-				string_width_align(entry, half),
+				fit_to_width(entry, half),
 				self.theme.text(true, false),
 			),
 			Span::styled(
@@ -152,21 +411,36 @@ impl OptionsPopupComponent {
 		if up {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
-					AppOption::DiffInterhunkLines
+					AppOption::LogShowAvatars
 				}
-				AppOption::DiffIgnoreWhitespaces => {
+				AppOption::StatusSort => {
 					AppOption::StatusShowUntracked
 				}
+				AppOption::StatusGroup => AppOption::StatusSort,
+				AppOption::DiffIgnoreWhitespaces => {
+					AppOption::StatusGroup
+				}
 				AppOption::DiffContextLines => {
 					AppOption::DiffIgnoreWhitespaces
 				}
 				AppOption::DiffInterhunkLines => {
 					AppOption::DiffContextLines
 				}
+				AppOption::DiffLineEndings => {
+					AppOption::DiffInterhunkLines
+				}
+				AppOption::DiffAlgorithm => {
+					AppOption::DiffLineEndings
+				}
+				AppOption::LogShowAvatars => AppOption::DiffAlgorithm,
 			};
 		} else {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
+					AppOption::StatusSort
+				}
+				AppOption::StatusSort => AppOption::StatusGroup,
+				AppOption::StatusGroup => {
 					AppOption::DiffIgnoreWhitespaces
 				}
 				AppOption::DiffIgnoreWhitespaces => {
@@ -176,6 +450,13 @@ impl OptionsPopupComponent {
 					AppOption::DiffInterhunkLines
 				}
 				AppOption::DiffInterhunkLines => {
+					AppOption::DiffLineEndings
+				}
+				AppOption::DiffLineEndings => {
+					AppOption::DiffAlgorithm
+				}
+				AppOption::DiffAlgorithm => AppOption::LogShowAvatars,
+				AppOption::LogShowAvatars => {
 					AppOption::StatusShowUntracked
 				}
 			};
@@ -186,24 +467,10 @@ impl OptionsPopupComponent {
 		if right {
 			match self.selection {
 				AppOption::StatusShowUntracked => {
-					let untracked =
-						self.options.borrow().status_show_untracked;
-
-					let untracked = match untracked {
-						None => {
-							Some(ShowUntrackedFilesConfig::Normal)
-						}
-						Some(ShowUntrackedFilesConfig::Normal) => {
-							Some(ShowUntrackedFilesConfig::All)
-						}
-						Some(ShowUntrackedFilesConfig::All) => {
-							Some(ShowUntrackedFilesConfig::No)
-						}
-						Some(ShowUntrackedFilesConfig::No) => None,
-					};
-
-					self.options.borrow_mut().status_show_untracked =
-						untracked;
+					self.cycle_untracked(true);
+				}
+				AppOption::StatusSort | AppOption::StatusGroup => {
+					self.cycle_status_order(self.selection, right);
 				}
 				AppOption::DiffIgnoreWhitespaces => {
 					let old =
@@ -224,28 +491,23 @@ impl OptionsPopupComponent {
 					self.options.borrow_mut().diff.interhunk_lines =
 						old.saturating_add(1);
 				}
+				AppOption::DiffLineEndings => {
+					self.cycle_line_endings(true);
+				}
+				AppOption::DiffAlgorithm => {
+					self.cycle_algorithm(true);
+				}
+				AppOption::LogShowAvatars => {
+					self.toggle_avatars();
+				}
 			};
 		} else {
 			match self.selection {
 				AppOption::StatusShowUntracked => {
-					let untracked =
-						self.options.borrow().status_show_untracked;
-
-					let untracked = match untracked {
-						None => Some(ShowUntrackedFilesConfig::No),
-						Some(ShowUntrackedFilesConfig::No) => {
-							Some(ShowUntrackedFilesConfig::All)
-						}
-						Some(ShowUntrackedFilesConfig::All) => {
-							Some(ShowUntrackedFilesConfig::Normal)
-						}
-						Some(ShowUntrackedFilesConfig::Normal) => {
-							None
-						}
-					};
-
-					self.options.borrow_mut().status_show_untracked =
-						untracked;
+					self.cycle_untracked(false);
+				}
+				AppOption::StatusSort | AppOption::StatusGroup => {
+					self.cycle_status_order(self.selection, right);
 				}
 				AppOption::DiffIgnoreWhitespaces => {
 					let old =
@@ -266,6 +528,15 @@ impl OptionsPopupComponent {
 					self.options.borrow_mut().diff.interhunk_lines =
 						old.saturating_sub(1);
 				}
+				AppOption::DiffLineEndings => {
+					self.cycle_line_endings(false);
+				}
+				AppOption::DiffAlgorithm => {
+					self.cycle_algorithm(false);
+				}
+				AppOption::LogShowAvatars => {
+					self.toggle_avatars();
+				}
 			};
 		}
 
@@ -281,7 +552,7 @@ impl DrawableComponent for OptionsPopupComponent {
 		area: Rect,
 	) -> Result<()> {
 		if self.is_visible() {
-			const SIZE: (u16, u16) = (50, 10);
+			const SIZE: (u16, u16) = (50, 16);
 			let area =
 				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 
@@ -333,6 +604,11 @@ impl Component for OptionsPopupComponent {
 				)
 				.order(1),
 			);
+			out.push(CommandInfo::new(
+				strings::commands::options_edit(&self.key_config),
+				true,
+				true,
+			));
 		}
 
 		visibility_blocking(self)
@@ -364,6 +640,8 @@ impl Component for OptionsPopupComponent {
 					self.key_config.keys.move_left,
 				) {
 					self.switch_option(false);
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.edit_option();
 				}
 			}
 
@@ -387,3 +665,60 @@ impl Component for OptionsPopupComponent {
 		Ok(())
 	}
 }
+
+const fn untracked_label(
+	untracked: Option<ShowUntrackedFilesConfig>,
+) -> &'static str {
+	match untracked {
+		None => "Gitconfig",
+		Some(ShowUntrackedFilesConfig::No) => "No",
+		Some(ShowUntrackedFilesConfig::Normal) => "Normal",
+		Some(ShowUntrackedFilesConfig::All) => "All",
+	}
+}
+
+/// the choice after (or before) `current`, wrapping around
+pub fn cycle<T: Copy + PartialEq>(
+	choices: &[T],
+	current: T,
+	right: bool,
+) -> T {
+	let idx = choices
+		.iter()
+		.position(|c| *c == current)
+		.unwrap_or_default();
+	let len = choices.len();
+	let idx = if right {
+		(idx + 1) % len
+	} else {
+		(idx + len - 1) % len
+	};
+
+	choices[idx]
+}
+
+const fn sort_label(sort: StatusSort) -> &'static str {
+	match sort {
+		StatusSort::Path => "Path",
+		StatusSort::Extension => "Extension",
+		StatusSort::ModificationTime => "Modified time",
+		StatusSort::ChangeSize => "Change size",
+	}
+}
+
+const fn group_label(group: StatusGroup) -> &'static str {
+	match group {
+		StatusGroup::Directory => "Directory",
+		StatusGroup::ChangeType => "Change type",
+	}
+}
+
+const fn line_endings_label(
+	line_endings: LineEndings,
+) -> &'static str {
+	match line_endings {
+		LineEndings::Hide => "Hide",
+		LineEndings::Show => "Show",
+		LineEndings::Normalize => "Ignore",
+	}
+}
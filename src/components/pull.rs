@@ -1,13 +1,13 @@
-use super::PushComponent;
 use crate::{
 	components::{
 		cred::CredComponent, visibility_blocking, CommandBlocking,
 		CommandInfo, Component, DrawableComponent, EventState,
+		RemoteProgressComponent, ToastKind,
 	},
-	keys::SharedKeyConfig,
+	keys::{key_match, SharedKeyConfig},
 	queue::{Action, InternalEvent, Queue},
 	strings, try_or_popup,
-	ui::{self, style::SharedTheme},
+	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
@@ -17,32 +17,30 @@ use asyncgit::{
 			extract_username_password, need_username_password,
 			BasicAuthCredential,
 		},
-		get_default_remote, RepoPathRef,
+		get_default_remote, PostHook, RepoPathRef,
 	},
-	AsyncGitNotification, AsyncPull, FetchRequest, RemoteProgress,
+	AsyncGitNotification, AsyncPull, FetchRequest,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use tui::{
-	backend::Backend,
-	layout::Rect,
-	text::Span,
-	widgets::{Block, BorderType, Borders, Clear, Gauge},
-	Frame,
-};
+use tui::{backend::Backend, layout::Rect, Frame};
 
 ///
 pub struct PullComponent {
 	repo: RepoPathRef,
 	visible: bool,
 	git_fetch: AsyncPull,
-	progress: Option<RemoteProgress>,
+	progress: RemoteProgressComponent,
 	pending: bool,
 	branch: String,
 	queue: Queue,
-	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	/// credentials typed in by the user, offered to be stored
+	/// once the pull succeeded
+	entered_cred: Option<BasicAuthCredential>,
+	/// the remote fetched from, entered credentials are for it
+	remote: String,
 }
 
 impl PullComponent {
@@ -61,12 +59,13 @@ impl PullComponent {
 			visible: false,
 			branch: String::new(),
 			git_fetch: AsyncPull::new(repo.borrow().clone(), sender),
-			progress: None,
-			input_cred: CredComponent::new(
+			progress: RemoteProgressComponent::new(
 				theme.clone(),
 				key_config.clone(),
 			),
-			theme,
+			input_cred: CredComponent::new(theme, key_config.clone()),
+			entered_cred: None,
+			remote: String::new(),
 			key_config,
 		}
 	}
@@ -75,6 +74,7 @@ impl PullComponent {
 	pub fn fetch(&mut self, branch: String) -> Result<()> {
 		self.branch = branch;
 		self.show()?;
+		self.entered_cred = None;
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
 				.unwrap_or_else(|_| {
@@ -96,9 +96,10 @@ impl PullComponent {
 		cred: Option<BasicAuthCredential>,
 	) -> Result<()> {
 		self.pending = true;
-		self.progress = None;
+		self.progress.start(strings::PULL_POPUP_MSG);
+		self.remote = get_default_remote(&self.repo.borrow())?;
 		self.git_fetch.request(FetchRequest {
-			remote: get_default_remote(&self.repo.borrow())?,
+			remote: self.remote.clone(),
 			branch: self.branch.clone(),
 			basic_credential: cred,
 		})?;
@@ -127,14 +128,30 @@ impl PullComponent {
 	///
 	fn update(&mut self) -> Result<()> {
 		self.pending = self.git_fetch.is_pending()?;
-		self.progress = self.git_fetch.progress()?;
+		self.progress.set_progress(self.git_fetch.progress()?);
 
 		if !self.pending {
-			if let Some((_bytes, err)) =
+			if self.git_fetch.is_cancelled() {
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Warning,
+					strings::TOAST_PULL_CANCELLED.to_string(),
+				));
+				self.hide();
+			} else if let Some((_bytes, err)) =
 				self.git_fetch.last_result()?
 			{
 				if err.is_empty() {
 					self.try_ff_merge()?;
+					if let Some(cred) = self.entered_cred.take() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::StoreCredentials(
+									self.remote.clone(),
+									cred,
+								),
+							),
+						);
+					}
 				} else {
 					anyhow::bail!(err);
 				}
@@ -155,9 +172,12 @@ impl PullComponent {
 				&self.repo.borrow(),
 				&self.branch,
 			);
-			if let Err(err) = ff_res {
-				log::trace!("ff failed: {}", err);
-				self.confirm_merge(branch_compare.behind);
+			match ff_res {
+				Ok(()) => self.post_merge(),
+				Err(err) => {
+					log::trace!("ff failed: {}", err);
+					self.confirm_merge(branch_compare.behind);
+				}
 			}
 		}
 
@@ -177,18 +197,32 @@ impl PullComponent {
 				)
 			);
 		} else {
-			try_or_popup!(
-				self,
-				"merge failed:",
-				sync::merge_upstream_commit(
-					&self.repo.borrow(),
-					&self.branch
-				)
-			);
+			match sync::merge_upstream_commit(
+				&self.repo.borrow(),
+				&self.branch,
+			) {
+				// `None` means conflicts, git skips the hook then too
+				Ok(Some(_)) => self.post_merge(),
+				Ok(None) => (),
+				Err(err) => {
+					log::error!("merge failed: {}", err);
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("merge failed:\n{}", err),
+					));
+				}
+			}
 		}
 	}
 
+	fn post_merge(&self) {
+		self.queue.push(InternalEvent::RunPostHook(
+			PostHook::Merge { squash: false },
+		));
+	}
+
 	fn confirm_merge(&mut self, incoming: usize) {
+		// only one confirmation can be shown at a time
+		self.entered_cred = None;
 		self.queue.push(InternalEvent::ConfirmAction(
 			Action::PullMerge {
 				incoming,
@@ -209,29 +243,7 @@ impl DrawableComponent for PullComponent {
 		rect: Rect,
 	) -> Result<()> {
 		if self.visible {
-			let (state, progress) =
-				PushComponent::get_progress(&self.progress);
-
-			let area = ui::centered_rect_absolute(30, 3, f.size());
-
-			f.render_widget(Clear, area);
-			f.render_widget(
-				Gauge::default()
-					.label(state.as_str())
-					.block(
-						Block::default()
-							.title(Span::styled(
-								strings::PULL_POPUP_MSG,
-								self.theme.title(true),
-							))
-							.borders(Borders::ALL)
-							.border_type(BorderType::Thick)
-							.border_style(self.theme.block(true)),
-					)
-					.gauge_style(self.theme.push_gauge())
-					.percent(u16::from(progress)),
-				area,
-			);
+			self.progress.draw(f, rect)?;
 			self.input_cred.draw(f, rect)?;
 		}
 
@@ -254,8 +266,10 @@ impl Component for PullComponent {
 				return self.input_cred.commands(out, force_all);
 			}
 			out.push(CommandInfo::new(
-				strings::commands::close_msg(&self.key_config),
-				!self.pending,
+				strings::commands::cancel_remote_progress(
+					&self.key_config,
+				),
+				self.pending && !self.progress.is_cancelling(),
 				self.visible,
 			));
 		}
@@ -265,18 +279,26 @@ impl Component for PullComponent {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(_) = ev {
+			if let Event::Key(e) = ev {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
 					if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
-						self.fetch_from_remote(Some(
-							self.input_cred.get_cred().clone(),
-						))?;
+						let cred = self.input_cred.get_cred().clone();
+						self.entered_cred = Some(cred.clone())
+							.filter(BasicAuthCredential::is_complete);
+						self.fetch_from_remote(Some(cred))?;
 						self.input_cred.hide();
 					}
+				} else if key_match(
+					e,
+					self.key_config.keys.exit_popup,
+				) && self.pending
+				{
+					self.git_fetch.cancel();
+					self.progress.set_cancelling();
 				}
 			}
 			return Ok(EventState::Consumed);
@@ -0,0 +1,264 @@
+use super::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ToastKind,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, ui, AsyncNotification,
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	pull_requests::AsyncPullRequestsJob,
+	sync::{
+		cred::{extract_username_password, need_username_password},
+		github::{checkout_pull_request, PullRequest},
+		RepoPathRef,
+	},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+/// lists open github pull requests and checks them out
+pub struct PullRequestsComponent {
+	repo: RepoPathRef,
+	queue: Queue,
+	pull_requests: Option<Result<Vec<PullRequest>, String>>,
+	async_pull_requests: AsyncSingleJob<AsyncPullRequestsJob>,
+	selection: usize,
+	visible: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl PullRequestsComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		sender: &Sender<AsyncGitNotification>,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			repo,
+			queue: queue.clone(),
+			pull_requests: None,
+			async_pull_requests: AsyncSingleJob::new(sender.clone()),
+			selection: 0,
+			visible: false,
+			theme,
+			key_config,
+		}
+	}
+
+	///
+	pub fn update(&mut self, ev: AsyncNotification) {
+		if matches!(
+			ev,
+			AsyncNotification::Git(
+				AsyncGitNotification::PullRequests
+			)
+		) {
+			if let Some(job) = self.async_pull_requests.take_last() {
+				self.pull_requests = job
+					.result()
+					.map(|res| res.map_err(|e| e.to_string()));
+			}
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_pull_requests.is_pending()
+	}
+
+	fn list(&self) -> &[PullRequest] {
+		match &self.pull_requests {
+			Some(Ok(list)) => list.as_slice(),
+			_ => &[],
+		}
+	}
+
+	fn checkout_selected(&mut self) {
+		let number = match self.list().get(self.selection) {
+			Some(pr) => pr.number,
+			None => return,
+		};
+
+		let repo = self.repo.borrow().clone();
+		let cred =
+			if need_username_password(&repo).unwrap_or_default() {
+				extract_username_password(&repo).ok()
+			} else {
+				None
+			};
+
+		match checkout_pull_request(&repo, number, cred) {
+			Ok(branch) => {
+				self.hide();
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Success,
+					format!(
+						"checked out #{} as '{}'",
+						number, branch
+					),
+				));
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("checkout pull request error:\n{}", e),
+				));
+			}
+		}
+	}
+
+	fn get_text(&self) -> Vec<Spans<'_>> {
+		match &self.pull_requests {
+			None => vec![Spans::from(strings::PULL_REQUESTS_LOADING)],
+			Some(Err(e)) => vec![Spans::from(Span::styled(
+				e.as_str(),
+				self.theme.text_danger(),
+			))],
+			Some(Ok(list)) if list.is_empty() => {
+				vec![Spans::from(strings::PULL_REQUESTS_EMPTY)]
+			}
+			Some(Ok(list)) => list
+				.iter()
+				.enumerate()
+				.map(|(idx, pr)| {
+					let selected = idx == self.selection;
+					Spans::from(vec![
+						Span::styled(
+							format!("#{:<5} ", pr.number),
+							self.theme.commit_hash(selected),
+						),
+						Span::styled(
+							pr.title.as_str(),
+							self.theme.text(true, selected),
+						),
+						Span::styled(
+							format!(
+								" ({}:{})",
+								pr.author, pr.head_ref
+							),
+							self.theme.commit_author(selected),
+						),
+					])
+				})
+				.collect(),
+		}
+	}
+}
+
+impl DrawableComponent for PullRequestsComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		_rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect_absolute(80, 20, f.size());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(self.get_text()).block(
+					Block::default()
+						.title(strings::pull_requests_title(
+							&self.key_config,
+						))
+						.borders(Borders::ALL)
+						.border_type(BorderType::Thick),
+				),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for PullRequestsComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::checkout_pull_request(
+					&self.key_config,
+				),
+				!self.list().is_empty(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					self.selection = self
+						.selection
+						.saturating_add(1)
+						.min(self.list().len().saturating_sub(1));
+				} else if key_match(e, self.key_config.keys.move_up) {
+					self.selection = self.selection.saturating_sub(1);
+				} else if key_match(e, self.key_config.keys.enter) {
+					self.checkout_selected();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.selection = 0;
+		self.pull_requests = None;
+		self.async_pull_requests.spawn(AsyncPullRequestsJob::new(
+			self.repo.borrow().clone(),
+		));
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -2,33 +2,32 @@ use crate::{
 	components::{
 		cred::CredComponent, visibility_blocking, CommandBlocking,
 		CommandInfo, Component, DrawableComponent, EventState,
+		RemoteProgressComponent, ToastKind,
 	},
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, Queue},
+	queue::{Action, InternalEvent, Queue},
 	strings,
-	ui::{self, style::SharedTheme},
+	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	divergence::AsyncDivergenceJob,
+	dry_run::{AsyncDryRunJob, DryRun},
 	sync::{
 		cred::{
 			extract_username_password, need_username_password,
 			BasicAuthCredential,
 		},
-		get_branch_remote, get_default_remote, RepoPathRef,
+		get_branch_remote, get_default_remote, push_raw_refspec,
+		remotes::dry_run::{dry_run_enabled, RefChange},
+		CommitId, PushCliOptions, RepoPathRef,
 	},
 	AsyncGitNotification, AsyncPush, PushRequest, PushType,
-	RemoteProgress, RemoteProgressState,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use tui::{
-	backend::Backend,
-	layout::Rect,
-	text::Span,
-	widgets::{Block, BorderType, Borders, Clear, Gauge},
-	Frame,
-};
+use tui::{backend::Backend, layout::Rect, Frame};
 
 ///
 #[derive(PartialEq, Eq)]
@@ -54,14 +53,36 @@ pub struct PushComponent {
 	modifier: PushComponentModifier,
 	visible: bool,
 	git_push: AsyncPush,
-	progress: Option<RemoteProgress>,
+	progress: RemoteProgressComponent,
 	pending: bool,
 	branch: String,
 	push_type: PushType,
+	review_refspec: Option<String>,
+	/// the old name of `branch` on its remote, see
+	/// `rename_remote_branch`
+	rename_from: Option<String>,
 	queue: Queue,
-	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	/// credentials typed in by the user, offered to be stored
+	/// once the push succeeded
+	entered_cred: Option<BasicAuthCredential>,
+	/// the remote pushed to, entered credentials are for it
+	remote: String,
+	/// list what would change before pushing, see `gitui.dryRun`
+	dry_run: bool,
+	async_dry_run: AsyncSingleJob<AsyncDryRunJob>,
+	/// credentials and force of the push waiting for its dry run
+	/// or the commits it overwrites to be confirmed
+	previewed: Option<(Option<BasicAuthCredential>, bool)>,
+	async_divergence: AsyncSingleJob<AsyncDivergenceJob>,
+	/// the remote commits a force push overwrites got confirmed
+	force_confirmed: bool,
+	/// the remote tip those commits were listed for, the force
+	/// push only goes ahead while the branch is still there
+	lease: Option<CommitId>,
+	/// push options and signing, pushed via the git cli if any
+	cli_options: PushCliOptions,
 }
 
 impl PushComponent {
@@ -81,13 +102,23 @@ impl PushComponent {
 			visible: false,
 			branch: String::new(),
 			push_type: PushType::Branch,
+			review_refspec: None,
+			rename_from: None,
 			git_push: AsyncPush::new(repo.borrow().clone(), sender),
-			progress: None,
-			input_cred: CredComponent::new(
+			progress: RemoteProgressComponent::new(
 				theme.clone(),
 				key_config.clone(),
 			),
-			theme,
+			input_cred: CredComponent::new(theme, key_config.clone()),
+			entered_cred: None,
+			remote: String::new(),
+			dry_run: false,
+			async_dry_run: AsyncSingleJob::new(sender.clone()),
+			previewed: None,
+			async_divergence: AsyncSingleJob::new(sender.clone()),
+			force_confirmed: false,
+			lease: None,
+			cli_options: PushCliOptions::default(),
 			key_config,
 		}
 	}
@@ -102,6 +133,9 @@ impl PushComponent {
 	) -> Result<()> {
 		self.branch = branch;
 		self.push_type = push_type;
+		self.review_refspec = None;
+		self.rename_from = None;
+		self.cli_options = PushCliOptions::default();
 		self.modifier = match (force, delete) {
 			(true, true) => PushComponentModifier::ForceDelete,
 			(false, true) => PushComponentModifier::Delete,
@@ -109,9 +143,71 @@ impl PushComponent {
 			(false, false) => PushComponentModifier::None,
 		};
 
+		self.start(force)
+	}
+
+	/// pushes `HEAD` of `branch` to `refspec` on the branch's remote
+	pub fn push_review(
+		&mut self,
+		branch: String,
+		refspec: String,
+	) -> Result<()> {
+		self.branch = branch;
+		self.push_type = PushType::Branch;
+		self.review_refspec = Some(refspec);
+		self.rename_from = None;
+		self.cli_options = PushCliOptions::default();
+		self.modifier = PushComponentModifier::None;
+
+		self.start(false)
+	}
+
+	/// renames `old` on the remote of the local `branch` to the name
+	/// `branch` got renamed to and tracks that
+	pub fn rename_remote_branch(
+		&mut self,
+		branch: String,
+		old: String,
+	) -> Result<()> {
+		self.branch = branch;
+		self.push_type = PushType::Branch;
+		self.review_refspec = None;
+		self.rename_from = Some(old);
+		self.cli_options = PushCliOptions::default();
+		self.modifier = PushComponentModifier::None;
+
+		self.start(false)
+	}
+
+	/// pushes `branch` via the git cli to pass push options or sign
+	/// the push, credentials come from the git credential helpers
+	pub fn push_with_options(
+		&mut self,
+		branch: String,
+		options: PushCliOptions,
+	) -> Result<()> {
+		self.branch = branch;
+		self.push_type = PushType::Branch;
+		self.review_refspec = None;
+		self.rename_from = None;
+		self.cli_options = options;
+		self.modifier = PushComponentModifier::None;
+
+		self.start(false)
+	}
+
+	fn start(&mut self, force: bool) -> Result<()> {
 		self.show()?;
+		self.entered_cred = None;
+		self.previewed = None;
+		self.force_confirmed = false;
+		self.lease = None;
+		self.dry_run =
+			dry_run_enabled(&self.repo.borrow()).unwrap_or_default();
 
-		if need_username_password(&self.repo.borrow())? {
+		if !self.cli_options.needs_cli()
+			&& need_username_password(&self.repo.borrow())?
+		{
 			let cred = extract_username_password(&self.repo.borrow())
 				.unwrap_or_else(|_| {
 					BasicAuthCredential::new(None, None)
@@ -147,16 +243,72 @@ impl PushComponent {
 			);
 			remote
 		};
+		self.remote.clone_from(&remote);
 
 		self.pending = true;
-		self.progress = None;
+
+		if force
+			&& !self.force_confirmed
+			&& !self.modifier.delete()
+			&& self.review_refspec.is_none()
+			&& matches!(self.push_type, PushType::Branch)
+		{
+			self.progress.start(strings::DIVERGENCE_POPUP_MSG);
+			self.previewed = Some((cred.clone(), force));
+			self.async_divergence.spawn(AsyncDivergenceJob::new(
+				self.repo.borrow().clone(),
+				remote,
+				self.branch.clone(),
+				cred,
+			));
+			return Ok(());
+		}
+
+		// pushes and deletes, more than a dry run previews
+		if self.dry_run && self.rename_from.is_none() {
+			let refspec =
+				self.review_refspec.clone().unwrap_or_else(|| {
+					push_raw_refspec(
+						&self.branch,
+						self.push_type,
+						force,
+						self.modifier.delete(),
+					)
+				});
+			self.progress.start(strings::DRY_RUN_POPUP_MSG);
+			self.previewed = Some((cred.clone(), force));
+			self.async_dry_run.spawn(AsyncDryRunJob::new(
+				self.repo.borrow().clone(),
+				DryRun::Push { remote, refspec },
+				cred,
+			));
+			return Ok(());
+		}
+
+		self.progress.start(if self.rename_from.is_some() {
+			strings::RENAME_REMOTE_POPUP_MSG
+		} else if self.review_refspec.is_some() {
+			strings::PUSH_REVIEW_POPUP_MSG
+		} else if self.modifier.force() {
+			strings::FORCE_PUSH_POPUP_MSG
+		} else {
+			strings::PUSH_POPUP_MSG
+		});
+		// bound to the remote tip the overwritten commits were listed
+		// for, a branch missing there must not appear meanwhile
+		let lease = self.lease.filter(|_| self.force_confirmed);
 		self.git_push.request(PushRequest {
 			remote,
 			branch: self.branch.clone(),
 			push_type: self.push_type,
-			force,
+			force: force
+				&& !(self.force_confirmed && self.lease.is_none()),
 			delete: self.modifier.delete(),
 			basic_credential: cred,
+			refspec: self.review_refspec.clone(),
+			rename_from: self.rename_from.clone(),
+			lease,
+			cli_options: self.cli_options.clone(),
 		})?;
 		Ok(())
 	}
@@ -168,21 +320,147 @@ impl PushComponent {
 	) -> Result<()> {
 		if self.is_visible() && ev == AsyncGitNotification::Push {
 			self.update()?;
+		} else if self.is_visible()
+			&& ev == AsyncGitNotification::DryRun
+		{
+			self.update_dry_run();
+		} else if self.is_visible()
+			&& ev == AsyncGitNotification::Divergence
+		{
+			self.update_divergence();
 		}
 
 		Ok(())
 	}
 
+	/// runs the push its dry run previewed
+	pub fn push_previewed(&mut self) -> Result<()> {
+		if let Some((cred, force)) = self.previewed.take() {
+			self.dry_run = false;
+			self.show()?;
+			self.push_to_remote(cred, force)?;
+		}
+
+		Ok(())
+	}
+
+	/// runs the force push once the remote commits it overwrites
+	/// got confirmed
+	pub fn force_push_confirmed(&mut self) -> Result<()> {
+		self.force_confirmed = true;
+		self.push_previewed()
+	}
+
+	/// asks to confirm the force push listing the remote commits
+	/// it overwrites
+	fn update_divergence(&mut self) {
+		self.pending = self.async_divergence.is_pending();
+		if self.pending {
+			return;
+		}
+
+		self.hide();
+		match self
+			.async_divergence
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(divergence)) => {
+				self.lease = divergence.tip;
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::ForcePush(
+						self.branch.clone(),
+						divergence.overwritten,
+					),
+				));
+			}
+			Some(Err(e)) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"checking the remote commits failed:\n{}",
+						e
+					),
+				));
+			}
+			None => (),
+		}
+	}
+
+	/// asks to confirm the refs the dry run found changing
+	fn update_dry_run(&mut self) {
+		self.pending = self.async_dry_run.is_pending();
+		if self.pending {
+			return;
+		}
+
+		self.hide();
+		match self
+			.async_dry_run
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(updates))
+				if updates.iter().all(|update| {
+					update.change == RefChange::UpToDate
+				}) =>
+			{
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Success,
+					strings::TOAST_UP_TO_DATE.to_string(),
+				));
+			}
+			Some(Ok(updates)) => {
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::PushPreviewed(updates),
+				));
+			}
+			Some(Err(e)) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("push dry run failed:\n{}", e),
+				));
+			}
+			None => (),
+		}
+	}
+
 	///
 	fn update(&mut self) -> Result<()> {
 		self.pending = self.git_push.is_pending()?;
-		self.progress = self.git_push.progress()?;
+		self.progress.set_progress(self.git_push.progress()?);
 
 		if !self.pending {
-			if let Some(err) = self.git_push.last_result()? {
+			if self.git_push.is_cancelled() {
+				self.queue.push(InternalEvent::ShowToast(
+					ToastKind::Warning,
+					strings::TOAST_PUSH_CANCELLED.to_string(),
+				));
+			} else if let Some(err) = self.git_push.last_result()? {
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("push failed:\n{}", err),
 				));
+			} else {
+				match self.git_push.last_summary()? {
+					Some(summary)
+						if !summary.refs.is_empty()
+							|| !summary.messages.is_empty() =>
+					{
+						self.queue.push(
+							InternalEvent::ShowPushSummary(summary),
+						);
+					}
+					_ => self.queue.push(InternalEvent::ShowToast(
+						ToastKind::Success,
+						strings::TOAST_PUSHED.to_string(),
+					)),
+				}
+				if let Some(cred) = self.entered_cred.take() {
+					self.queue.push(InternalEvent::ConfirmAction(
+						Action::StoreCredentials(
+							self.remote.clone(),
+							cred,
+						),
+					));
+				}
 			}
 			self.hide();
 		}
@@ -194,42 +472,6 @@ impl PushComponent {
 	pub const fn any_work_pending(&self) -> bool {
 		self.pending
 	}
-
-	///
-	pub fn get_progress(
-		progress: &Option<RemoteProgress>,
-	) -> (String, u8) {
-		progress.as_ref().map_or(
-			(strings::PUSH_POPUP_PROGRESS_NONE.into(), 0),
-			|progress| {
-				(
-					Self::progress_state_name(&progress.state),
-					progress.get_progress_percent(),
-				)
-			},
-		)
-	}
-
-	fn progress_state_name(state: &RemoteProgressState) -> String {
-		match state {
-			RemoteProgressState::PackingAddingObject => {
-				strings::PUSH_POPUP_STATES_ADDING
-			}
-			RemoteProgressState::PackingDeltafiction => {
-				strings::PUSH_POPUP_STATES_DELTAS
-			}
-			RemoteProgressState::Pushing => {
-				strings::PUSH_POPUP_STATES_PUSHING
-			}
-			RemoteProgressState::Transfer => {
-				strings::PUSH_POPUP_STATES_TRANSFER
-			}
-			RemoteProgressState::Done => {
-				strings::PUSH_POPUP_STATES_DONE
-			}
-		}
-		.into()
-	}
 }
 
 impl DrawableComponent for PushComponent {
@@ -239,33 +481,7 @@ impl DrawableComponent for PushComponent {
 		rect: Rect,
 	) -> Result<()> {
 		if self.visible {
-			let (state, progress) =
-				Self::get_progress(&self.progress);
-
-			let area = ui::centered_rect_absolute(30, 3, f.size());
-
-			f.render_widget(Clear, area);
-			f.render_widget(
-				Gauge::default()
-					.label(state.as_str())
-					.block(
-						Block::default()
-							.title(Span::styled(
-								if self.modifier.force() {
-									strings::FORCE_PUSH_POPUP_MSG
-								} else {
-									strings::PUSH_POPUP_MSG
-								},
-								self.theme.title(true),
-							))
-							.borders(Borders::ALL)
-							.border_type(BorderType::Thick)
-							.border_style(self.theme.block(true)),
-					)
-					.gauge_style(self.theme.push_gauge())
-					.percent(u16::from(progress)),
-				area,
-			);
+			self.progress.draw(f, rect)?;
 			self.input_cred.draw(f, rect)?;
 		}
 
@@ -287,6 +503,13 @@ impl Component for PushComponent {
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
+			out.push(CommandInfo::new(
+				strings::commands::cancel_remote_progress(
+					&self.key_config,
+				),
+				self.pending && !self.progress.is_cancelling(),
+				self.visible,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::close_msg(&self.key_config),
 				!self.pending,
@@ -306,8 +529,11 @@ impl Component for PushComponent {
 					if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
+						let cred = self.input_cred.get_cred().clone();
+						self.entered_cred = Some(cred.clone())
+							.filter(BasicAuthCredential::is_complete);
 						self.push_to_remote(
-							Some(self.input_cred.get_cred().clone()),
+							Some(cred),
 							self.modifier.force(),
 						)?;
 						self.input_cred.hide();
@@ -315,9 +541,13 @@ impl Component for PushComponent {
 				} else if key_match(
 					e,
 					self.key_config.keys.exit_popup,
-				) && !self.pending
-				{
-					self.hide();
+				) {
+					if self.pending {
+						self.git_push.cancel();
+						self.progress.set_cancelling();
+					} else {
+						self.hide();
+					}
 				}
 			}
 			return Ok(EventState::Consumed);
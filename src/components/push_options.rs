@@ -0,0 +1,168 @@
+use super::{
+	textinput::TextInputComponent, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::PushCliOptions;
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// asks for the push options and whether to sign a push
+pub struct PushOptionsComponent {
+	input: TextInputComponent,
+	branch: Option<String>,
+	signed: bool,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PushOptionsComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for PushOptionsComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::push_options_signed(
+					&self.key_config,
+					self.signed,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::push_options_confirm_msg(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				} else if key_match(
+					e,
+					self.key_config.keys.push_signed_toggle,
+				) {
+					self.signed = !self.signed;
+					self.update_title();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl PushOptionsComponent {
+	///
+	pub fn new(
+		queue: Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			queue,
+			input: TextInputComponent::new(
+				theme,
+				key_config.clone(),
+				&strings::push_options_popup_title(
+					&key_config,
+					false,
+				),
+				&strings::push_options_popup_msg(&key_config),
+				true,
+			),
+			branch: None,
+			signed: false,
+			key_config,
+		}
+	}
+
+	/// keeps the options of the last push to `branch`
+	pub fn open(&mut self, branch: String) -> Result<()> {
+		if self.branch.as_ref() != Some(&branch) {
+			self.input.clear();
+			self.signed = false;
+		}
+		self.branch = Some(branch);
+		self.update_title();
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn update_title(&mut self) {
+		self.input.set_title(strings::push_options_popup_title(
+			&self.key_config,
+			self.signed,
+		));
+	}
+
+	fn confirm(&mut self) {
+		if let Some(branch) = self.branch.clone() {
+			self.queue.push(InternalEvent::PushWithOptions(
+				branch,
+				PushCliOptions {
+					push_options: self
+						.input
+						.get_text()
+						.split_whitespace()
+						.map(String::from)
+						.collect(),
+					signed: self.signed,
+				},
+			));
+		}
+
+		self.hide();
+	}
+}
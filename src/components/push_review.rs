@@ -0,0 +1,158 @@
+use super::{
+	textinput::{TextInputComponent, Validation},
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// asks for the topic of a "push for review"
+pub struct PushReviewComponent {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	branch: Option<String>,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PushReviewComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for PushReviewComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::push_review_confirm_msg(
+					&self.key_config,
+				),
+				self.input.is_valid(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter)
+					&& self.input.is_valid()
+				{
+					self.confirm();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl PushReviewComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			repo,
+			queue,
+			input: TextInputComponent::new(
+				theme,
+				key_config.clone(),
+				&strings::push_review_popup_title(&key_config),
+				&strings::push_review_popup_msg(&key_config),
+				true,
+			)
+			.with_validator(|topic| {
+				if sync::review_topic_valid(topic.trim()) {
+					Validation::Ok
+				} else {
+					Validation::Error(
+						strings::REVIEW_TOPIC_INVALID.to_string(),
+					)
+				}
+			}),
+			branch: None,
+			key_config,
+		}
+	}
+
+	///
+	pub fn open(&mut self, branch: String) -> Result<()> {
+		self.branch = Some(branch);
+		self.input.clear();
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn confirm(&mut self) {
+		if let Some(branch) = self.branch.take() {
+			match sync::review_refspec_template(&self.repo.borrow()) {
+				Ok(template) => {
+					let refspec = sync::review_refspec(
+						&template,
+						&branch,
+						self.input.get_text().trim(),
+					);
+					self.queue.push(InternalEvent::PushReview(
+						branch, refspec,
+					));
+				}
+				Err(e) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("push for review error:\n{}", e),
+					));
+				}
+			}
+		}
+
+		self.hide();
+		self.input.clear();
+	}
+}
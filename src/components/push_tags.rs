@@ -4,7 +4,7 @@ use crate::{
 		CommandInfo, Component, DrawableComponent, EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, Queue},
+	queue::{Action, InternalEvent, Queue},
 	strings::{self},
 	ui::{self, style::SharedTheme},
 };
@@ -41,6 +41,11 @@ pub struct PushTagsComponent {
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	/// credentials typed in by the user, offered to be stored
+	/// once the push succeeded
+	entered_cred: Option<BasicAuthCredential>,
+	/// the remote pushed to, entered credentials are for it
+	remote: String,
 }
 
 impl PushTagsComponent {
@@ -66,6 +71,8 @@ impl PushTagsComponent {
 				theme.clone(),
 				key_config.clone(),
 			),
+			entered_cred: None,
+			remote: String::new(),
 			theme,
 			key_config,
 		}
@@ -74,6 +81,7 @@ impl PushTagsComponent {
 	///
 	pub fn push_tags(&mut self) -> Result<()> {
 		self.show()?;
+		self.entered_cred = None;
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
 				.unwrap_or_else(|_| {
@@ -96,8 +104,9 @@ impl PushTagsComponent {
 	) -> Result<()> {
 		self.pending = true;
 		self.progress = None;
+		self.remote = get_default_remote(&self.repo.borrow())?;
 		self.git_push.request(PushTagsRequest {
-			remote: get_default_remote(&self.repo.borrow())?,
+			remote: self.remote.clone(),
 			basic_credential: cred,
 		})?;
 		Ok(())
@@ -125,6 +134,13 @@ impl PushTagsComponent {
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("push tags failed:\n{}", err),
 				));
+			} else if let Some(cred) = self.entered_cred.take() {
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::StoreCredentials(
+						self.remote.clone(),
+						cred,
+					),
+				));
 			}
 			self.hide();
 		}
@@ -236,9 +252,10 @@ impl Component for PushTagsComponent {
 					if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
-						self.push_to_remote(Some(
-							self.input_cred.get_cred().clone(),
-						))?;
+						let cred = self.input_cred.get_cred().clone();
+						self.entered_cred = Some(cred.clone())
+							.filter(BasicAuthCredential::is_complete);
+						self.push_to_remote(Some(cred))?;
 						self.input_cred.hide();
 					}
 				} else if key_match(
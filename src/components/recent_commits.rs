@@ -0,0 +1,223 @@
+use super::{
+	utils::time_to_relative_string, CommandBlocking, CommandInfo,
+	Component, DrawableComponent, EventState, InspectCommitOpen,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue, StackablePopupOpen},
+	string_utils::truncate_to_width,
+	strings, ui,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, CommitInfo, RepoPathRef};
+use crossterm::event::Event;
+use std::convert::TryFrom;
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans},
+	Frame,
+};
+
+const DEFAULT_COUNT: usize = 5;
+
+/// the last commits of HEAD below the status lists, the number
+/// shown is `gitui.statusRecentCommits` (0 hides the panel)
+pub struct RecentCommitsComponent {
+	repo: RepoPathRef,
+	commits: Vec<CommitInfo>,
+	selection: usize,
+	focused: bool,
+	queue: Queue,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl RecentCommitsComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			repo,
+			commits: Vec::new(),
+			selection: 0,
+			focused: false,
+			queue: queue.clone(),
+			theme,
+			key_config,
+		}
+	}
+
+	///
+	pub fn update(&mut self) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		let count = sync::get_config_string(
+			&repo,
+			"gitui.statusRecentCommits",
+		)?
+		.and_then(|count| count.trim().parse().ok())
+		.unwrap_or(DEFAULT_COUNT);
+
+		self.commits =
+			sync::get_recent_commits_info(&repo, count, 100)?;
+		self.selection =
+			self.selection.min(self.commits.len().saturating_sub(1));
+
+		Ok(())
+	}
+
+	///
+	pub fn is_empty(&self) -> bool {
+		self.commits.is_empty()
+	}
+
+	/// rows needed to draw all commits, 0 if there are none
+	pub fn height(&self) -> u16 {
+		if self.is_empty() {
+			0
+		} else {
+			u16::try_from(self.commits.len() + 2).unwrap_or(u16::MAX)
+		}
+	}
+
+	fn move_selection(&mut self, up: bool) -> bool {
+		let new_selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			(self.selection + 1)
+				.min(self.commits.len().saturating_sub(1))
+		};
+
+		let changed = new_selection != self.selection;
+		self.selection = new_selection;
+		changed
+	}
+
+	fn inspect(&self) -> bool {
+		self.commits.get(self.selection).map_or(false, |commit| {
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::InspectCommit(
+					InspectCommitOpen::new(commit.id),
+				),
+			));
+
+			true
+		})
+	}
+
+	fn get_text(&self, width: usize) -> Vec<Spans> {
+		let now = chrono::Local::now().timestamp();
+
+		self.commits
+			.iter()
+			.enumerate()
+			.map(|(idx, commit)| {
+				let selected = self.focused && idx == self.selection;
+				let hash = commit.id.get_short_string();
+				let time = time_to_relative_string(commit.time, now);
+				let msg_width =
+					width.saturating_sub(hash.len() + time.len() + 2);
+
+				Spans::from(vec![
+					Span::styled(
+						format!("{} ", hash),
+						self.theme.commit_hash(selected),
+					),
+					Span::styled(
+						format!(
+							"{:w$} ",
+							truncate_to_width(
+								&commit.message,
+								msg_width
+							),
+							w = msg_width
+						),
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						time,
+						self.theme.commit_time(selected),
+					),
+				])
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for RecentCommitsComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if !self.is_empty() && rect.height > 2 {
+			ui::draw_list(
+				f,
+				rect,
+				&strings::title_recent_commits(&self.key_config),
+				self.get_text(rect.width.saturating_sub(2).into())
+					.into_iter(),
+				self.focused,
+				&self.theme,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for RecentCommitsComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		out.push(CommandInfo::new(
+			strings::commands::commit_details_open(&self.key_config),
+			!self.is_empty(),
+			self.focused || force_all,
+		));
+
+		CommandBlocking::PassingOn
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.focused {
+			if let Event::Key(e) = ev {
+				return Ok(if key_match(
+					e,
+					self.key_config.keys.move_up,
+				) {
+					self.move_selection(true)
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					self.move_selection(false);
+					true
+				} else if key_match(e, self.key_config.keys.enter)
+					|| key_match(e, self.key_config.keys.focus_right)
+				{
+					self.inspect()
+				} else {
+					false
+				}
+				.into());
+			}
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn focused(&self) -> bool {
+		self.focused
+	}
+
+	fn focus(&mut self, focus: bool) {
+		self.focused = focus;
+	}
+}
@@ -0,0 +1,224 @@
+use crate::{
+	components::DrawableComponent,
+	keys::SharedKeyConfig,
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{RemoteProgress, RemoteProgressState};
+use bytesize::ByteSize;
+use easy_cast::{Conv, ConvFloat};
+use std::{
+	mem::discriminant,
+	time::{Duration, Instant},
+};
+use tui::{
+	backend::Backend,
+	layout::{Constraint, Direction, Layout, Rect},
+	text::Span,
+	widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph},
+	Frame,
+};
+
+/// overlay showing phase, transfer rate and eta of a
+/// push or fetch that is in flight
+pub struct RemoteProgressComponent {
+	title: &'static str,
+	progress: Option<RemoteProgress>,
+	started: Instant,
+	phase_started: Instant,
+	cancelling: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl RemoteProgressComponent {
+	///
+	pub fn new(
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			title: "",
+			progress: None,
+			started: Instant::now(),
+			phase_started: Instant::now(),
+			cancelling: false,
+			theme,
+			key_config,
+		}
+	}
+
+	/// resets the stats for a new transfer
+	pub fn start(&mut self, title: &'static str) {
+		self.title = title;
+		self.progress = None;
+		self.started = Instant::now();
+		self.phase_started = self.started;
+		self.cancelling = false;
+	}
+
+	///
+	pub fn set_progress(&mut self, progress: Option<RemoteProgress>) {
+		let phase_changed = match (&self.progress, &progress) {
+			(Some(old), Some(new)) => {
+				discriminant(&old.state) != discriminant(&new.state)
+			}
+			_ => true,
+		};
+
+		if phase_changed {
+			self.phase_started = Instant::now();
+		}
+
+		self.progress = progress;
+	}
+
+	///
+	pub fn set_cancelling(&mut self) {
+		self.cancelling = true;
+	}
+
+	///
+	pub const fn is_cancelling(&self) -> bool {
+		self.cancelling
+	}
+
+	fn phase_name(&self) -> &'static str {
+		if self.cancelling {
+			return strings::REMOTE_PROGRESS_CANCELLING;
+		}
+
+		self.progress.as_ref().map_or(
+			strings::PUSH_POPUP_PROGRESS_NONE,
+			|progress| match progress.state {
+				RemoteProgressState::PackingAddingObject => {
+					strings::PUSH_POPUP_STATES_ADDING
+				}
+				RemoteProgressState::PackingDeltafiction => {
+					strings::PUSH_POPUP_STATES_DELTAS
+				}
+				RemoteProgressState::Pushing => {
+					strings::PUSH_POPUP_STATES_PUSHING
+				}
+				RemoteProgressState::Transfer => {
+					strings::PUSH_POPUP_STATES_TRANSFER
+				}
+				RemoteProgressState::Resolving => {
+					strings::PUSH_POPUP_STATES_RESOLVING
+				}
+				RemoteProgressState::Done => {
+					strings::PUSH_POPUP_STATES_DONE
+				}
+			},
+		)
+	}
+
+	fn percent(&self) -> u8 {
+		self.progress
+			.as_ref()
+			.map_or(0, RemoteProgress::get_progress_percent)
+	}
+
+	/// bytes per second since the transfer started
+	fn rate(&self) -> Option<u64> {
+		let bytes = self.progress.as_ref()?.bytes;
+		let secs = self.started.elapsed().as_secs_f64();
+
+		(bytes > 0 && secs > 0.0)
+			.then(|| u64::conv_trunc(f64::conv(bytes) / secs))
+	}
+
+	/// extrapolates the time left in the current phase
+	fn eta(&self) -> Option<Duration> {
+		let percent = u32::from(self.percent());
+		if percent == 0 || percent >= 100 {
+			return None;
+		}
+
+		let elapsed = self.phase_started.elapsed();
+		Some(elapsed * (100 - percent) / percent)
+	}
+
+	fn stats_line(&self) -> String {
+		let mut stats = Vec::with_capacity(3);
+
+		if let Some(progress) = &self.progress {
+			if progress.bytes > 0 {
+				stats.push(
+					ByteSize::b(u64::conv(progress.bytes))
+						.to_string_as(true),
+				);
+			}
+		}
+		if let Some(rate) = self.rate() {
+			stats.push(format!(
+				"{}/s",
+				ByteSize::b(rate).to_string_as(true)
+			));
+		}
+		if let Some(eta) = self.eta() {
+			stats.push(format!("ETA {}s", eta.as_secs()));
+		}
+
+		stats.join(" | ")
+	}
+}
+
+impl DrawableComponent for RemoteProgressComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		_rect: Rect,
+	) -> Result<()> {
+		let area = ui::centered_rect_absolute(50, 6, f.size());
+
+		let block = Block::default()
+			.title(Span::styled(self.title, self.theme.title(true)))
+			.borders(Borders::ALL)
+			.border_type(BorderType::Thick)
+			.border_style(self.theme.block(true));
+
+		let chunks = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints(
+				[
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Min(0),
+				]
+				.as_ref(),
+			)
+			.split(block.inner(area));
+
+		f.render_widget(Clear, area);
+		f.render_widget(block, area);
+		f.render_widget(
+			Gauge::default()
+				.label(self.phase_name())
+				.gauge_style(self.theme.push_gauge())
+				.percent(u16::from(self.percent())),
+			chunks[0],
+		);
+		f.render_widget(
+			Paragraph::new(self.stats_line())
+				.style(self.theme.text(true, false)),
+			chunks[1],
+		);
+		if !self.cancelling {
+			f.render_widget(
+				Paragraph::new(
+					strings::commands::cancel_remote_progress(
+						&self.key_config,
+					)
+					.name,
+				)
+				.style(self.theme.text(false, false)),
+				chunks[2],
+			);
+		}
+
+		Ok(())
+	}
+}
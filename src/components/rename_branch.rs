@@ -1,11 +1,13 @@
 use super::{
+	completion::BranchCompletion,
+	create_branch::branch_name_validator,
 	textinput::TextInputComponent, visibility_blocking,
 	CommandBlocking, CommandInfo, Component, DrawableComponent,
 	EventState,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, NeedsUpdate, Queue},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
 	strings,
 	ui::style::SharedTheme,
 };
@@ -47,7 +49,7 @@ impl Component for RenameBranchComponent {
 				strings::commands::rename_branch_confirm_msg(
 					&self.key_config,
 				),
-				true,
+				self.input.is_valid(),
 				true,
 			));
 		}
@@ -62,7 +64,9 @@ impl Component for RenameBranchComponent {
 			}
 
 			if let Event::Key(e) = ev {
-				if key_match(e, self.key_config.keys.enter) {
+				if key_match(e, self.key_config.keys.enter)
+					&& self.input.is_valid()
+				{
 					self.rename_branch();
 				}
 
@@ -96,7 +100,6 @@ impl RenameBranchComponent {
 		key_config: SharedKeyConfig,
 	) -> Self {
 		Self {
-			repo,
 			queue,
 			input: TextInputComponent::new(
 				theme,
@@ -104,9 +107,13 @@ impl RenameBranchComponent {
 				&strings::rename_branch_popup_title(&key_config),
 				&strings::rename_branch_popup_msg(&key_config),
 				true,
-			),
+			)
+			.with_completion(Box::new(BranchCompletion::new(
+				repo.clone(),
+			))),
 			branch_ref: None,
 			key_config,
+			repo,
 		}
 	}
 
@@ -118,6 +125,10 @@ impl RenameBranchComponent {
 	) -> Result<()> {
 		self.branch_ref = None;
 		self.branch_ref = Some(branch_ref);
+		self.input.set_validator(Some(branch_name_validator(
+			self.repo.clone(),
+			Some(cur_name.clone()),
+		)));
 		self.input.set_text(cur_name);
 		self.show()?;
 
@@ -127,10 +138,16 @@ impl RenameBranchComponent {
 	///
 	pub fn rename_branch(&mut self) {
 		if let Some(br) = &self.branch_ref {
+			let new_name = self.input.get_text().to_string();
+			// the local rename carries the upstream over unchanged
+			let upstream =
+				sync::branch_upstream_name(&self.repo.borrow(), br)
+					.ok()
+					.flatten();
 			let res = sync::rename_branch(
 				&self.repo.borrow(),
 				br,
-				self.input.get_text(),
+				&new_name,
 			);
 
 			match res {
@@ -140,6 +157,20 @@ impl RenameBranchComponent {
 					));
 					self.hide();
 					self.queue.push(InternalEvent::SelectBranch);
+
+					if let Some((remote, old)) = upstream {
+						if old != new_name {
+							self.queue.push(
+								InternalEvent::ConfirmAction(
+									Action::RenameRemoteBranch {
+										branch: new_name,
+										remote,
+										old,
+									},
+								),
+							);
+						}
+					}
 				}
 				Err(e) => {
 					log::error!("create branch: {}", e,);
@@ -1,23 +1,243 @@
 use crate::{
+	args::get_app_config_path,
 	components::{
-		popup_paragraph, visibility_blocking, CommandBlocking,
-		CommandInfo, Component, DrawableComponent, EventState,
+		popup_paragraph, utils::time_to_string, visibility_blocking,
+		CommandBlocking, CommandInfo, Component, DrawableComponent,
+		EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
 	queue::{Action, InternalEvent, Queue},
 	strings, ui,
 };
 use anyhow::Result;
-use crossterm::event::Event;
-use std::borrow::Cow;
+use asyncgit::sync::{
+	self,
+	remotes::dry_run::{RefChange, RefUpdate},
+	BranchProtection, RepoPathRef,
+};
+use crossterm::event::{Event, KeyCode};
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::File,
+	io::{Read, Write},
+	path::PathBuf,
+};
 use tui::{
-	backend::Backend, layout::Rect, text::Text, widgets::Clear, Frame,
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans, Text},
+	widgets::{Borders, Clear},
+	Frame,
 };
 use ui::style::SharedTheme;
 
+const MAX_CONSEQUENCES: usize = 10;
+
+/// actions the user chose not to be asked about again
+#[derive(Serialize, Deserialize, Default)]
+pub struct ConfirmPrefs {
+	skip: Vec<String>,
+}
+
+impl ConfirmPrefs {
+	fn get_file() -> Result<PathBuf> {
+		Ok(get_app_config_path()?.join("confirm.ron"))
+	}
+
+	/// reads `confirm.ron` of the gitui config dir
+	pub fn load() -> Result<Self> {
+		let file = Self::get_file()?;
+		if !file.exists() {
+			return Ok(Self::default());
+		}
+
+		let mut buffer = Vec::new();
+		File::open(file)?.read_to_end(&mut buffer)?;
+		Ok(ron::de::from_bytes(&buffer)?)
+	}
+
+	fn save(&self) -> Result<()> {
+		File::create(Self::get_file()?).and_then(|mut file| {
+			file.write_all(
+				to_string_pretty(self, PrettyConfig::default())
+					.unwrap_or_default()
+					.as_bytes(),
+			)
+		})?;
+
+		Ok(())
+	}
+}
+
+/// word that has to be typed to confirm the most destructive actions
+const fn typed_word(action: &Action) -> Option<&'static str> {
+	match action {
+		Action::ForcePush(..) => Some("force"),
+		Action::AbortMerge | Action::AbortRevert => Some("discard"),
+		_ => None,
+	}
+}
+
+/// what `ConfirmComponent::consequences` lists
+const fn consequences_title(action: &Action) -> &'static str {
+	match action {
+		Action::PushPreviewed(_) | Action::FetchPreviewed(_) => {
+			strings::CONFIRM_REF_UPDATES
+		}
+		Action::ForcePush(..) => strings::CONFIRM_OVERWRITTEN,
+		_ => strings::CONFIRM_CONSEQUENCES,
+	}
+}
+
+/// the local branch `action` changes, see `gitui.protectedBranch`
+fn affected_branch(action: &Action) -> Option<&str> {
+	match action {
+		Action::DeleteLocalBranch(branch_ref)
+		| Action::ForcePush(branch_ref, _) => {
+			Some(branch_ref.trim_start_matches("refs/heads/"))
+		}
+		Action::DeleteRemoteBranch(branch_ref) => branch_ref
+			.trim_start_matches("refs/remotes/")
+			.split_once('/')
+			.map(|(_, branch)| branch),
+		Action::RenameRemoteBranch { old, .. } => Some(old),
+		Action::WithoutUndo(action, _) => affected_branch(action),
+		Action::CommitProtected(branch)
+		| Action::UndoCommitProtected(branch) => Some(branch),
+		_ => None,
+	}
+}
+
+/// the files `action` discards out of the currently `changed_files`
+fn file_consequences(
+	action: &Action,
+	changed_files: &[String],
+) -> Vec<String> {
+	let changed_in = |path: &str| {
+		changed_files
+			.iter()
+			.filter(|file| file.starts_with(path))
+			.cloned()
+			.collect::<Vec<_>>()
+	};
+
+	match action {
+		Action::Reset(item) if item.is_folder => {
+			changed_in(&item.path)
+		}
+		Action::Reset(item) => vec![item.path.clone()],
+		Action::ResetMarked(paths) => paths.clone(),
+		Action::ResetHunk(path, _) => {
+			vec![format!("hunk in {}", path)]
+		}
+		Action::ResetLines(path, lines) => {
+			vec![format!("{} lines in {}", lines.len(), path)]
+		}
+		Action::AbortMerge | Action::AbortRevert => changed_in(""),
+		_ => Vec::new(),
+	}
+}
+
+/// the refs a dry run found changing
+fn ref_update_consequences(updates: &[RefUpdate]) -> Vec<String> {
+	updates
+		.iter()
+		.filter(|update| update.change != RefChange::UpToDate)
+		.map(|update| {
+			strings::ref_update(
+				&update.name,
+				update.old.map(|id| id.get_short_string()).as_deref(),
+				update.new.map(|id| id.get_short_string()).as_deref(),
+				update.change,
+			)
+		})
+		.collect()
+}
+
+/// title and message when `action` commits on a protected branch
+fn protected_text(action: &Action) -> Option<(String, String)> {
+	match action {
+		Action::CommitProtected(branch) => Some((
+			strings::confirm_title_commit_protected(),
+			strings::confirm_msg_commit_protected(branch),
+		)),
+		Action::UndoCommitProtected(branch) => Some((
+			strings::confirm_title_undo_commit_protected(),
+			strings::confirm_msg_undo_commit_protected(branch),
+		)),
+		_ => None,
+	}
+}
+
+/// title and message of the confirmations guarding another
+/// operation: undo, dry runs, credentials and autostash
+fn safeguard_text(action: &Action) -> Option<(String, String)> {
+	let text = match action {
+		Action::StoreCredentials(remote, cred) => (
+			strings::confirm_title_store_credentials(),
+			strings::confirm_msg_store_credentials(
+				cred.username.as_deref().unwrap_or_default(),
+				remote,
+			),
+		),
+		Action::AutoStash(operation) => (
+			strings::confirm_title_autostash(),
+			strings::confirm_msg_autostash(&operation.name()),
+		),
+		Action::Undo(entry) => (
+			strings::confirm_title_undo(),
+			strings::confirm_msg_undo(
+				entry,
+				&time_to_string(entry.time, false),
+			),
+		),
+		Action::PushPreviewed(updates) => (
+			strings::confirm_title_push_dry_run(),
+			strings::confirm_msg_push_dry_run(changed_refs(updates)),
+		),
+		Action::FetchPreviewed(updates) => (
+			strings::confirm_title_fetch_dry_run(),
+			strings::confirm_msg_fetch_dry_run(changed_refs(updates)),
+		),
+		Action::RestoreDiscarded(entry) => (
+			strings::confirm_title_restore_discarded(),
+			strings::confirm_msg_restore_discarded(
+				entry.discarded_path().unwrap_or_default(),
+				&time_to_string(entry.time, false),
+			),
+		),
+		Action::RebuildIndex(problem) => (
+			strings::confirm_title_rebuild_index(),
+			strings::confirm_msg_rebuild_index(problem),
+		),
+		Action::WithoutUndo(_, error) => (
+			strings::confirm_title_without_undo(),
+			strings::confirm_msg_without_undo(error),
+		),
+		_ => return None,
+	};
+
+	Some(text)
+}
+
+fn changed_refs(updates: &[RefUpdate]) -> usize {
+	updates
+		.iter()
+		.filter(|update| update.change != RefChange::UpToDate)
+		.count()
+}
+
 ///
 pub struct ConfirmComponent {
+	repo: RepoPathRef,
 	target: Option<Action>,
+	/// protected branch the target changes, its name has to be typed
+	protected: Option<String>,
+	consequences: Vec<String>,
+	typed: String,
+	dont_ask: bool,
+	prefs: ConfirmPrefs,
 	visible: bool,
 	queue: Queue,
 	theme: SharedTheme,
@@ -33,15 +253,20 @@ impl DrawableComponent for ConfirmComponent {
 		if self.visible {
 			let (title, msg) = self.get_text();
 
-			let txt = Text::styled(
-				Cow::from(msg),
-				self.theme.text_danger(),
-			);
+			let mut txt = Text::styled(msg, self.theme.text_danger());
+			txt.extend(self.get_details());
 
-			let area = ui::centered_rect(50, 20, f.size());
+			let area = ui::centered_rect(50, 30, f.size());
 			f.render_widget(Clear, area);
 			f.render_widget(
-				popup_paragraph(&title, txt, &self.theme, true, true),
+				popup_paragraph(
+					&title,
+					txt,
+					&self.theme,
+					true,
+					Borders::ALL,
+					true,
+				),
 				area,
 			);
 		}
@@ -58,9 +283,14 @@ impl Component for ConfirmComponent {
 	) -> CommandBlocking {
 		out.push(CommandInfo::new(
 			strings::commands::confirm_action(&self.key_config),
-			true,
+			self.can_confirm(),
 			self.visible,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::confirm_dont_ask(&self.key_config),
+			true,
+			self.visible && self.can_skip(),
+		));
 		out.push(CommandInfo::new(
 			strings::commands::close_popup(&self.key_config),
 			true,
@@ -76,7 +306,22 @@ impl Component for ConfirmComponent {
 				if key_match(e, self.key_config.keys.exit_popup) {
 					self.hide();
 				} else if key_match(e, self.key_config.keys.enter) {
-					self.confirm();
+					if self.can_confirm() {
+						self.confirm();
+					}
+				} else if self.typed_word().is_some() {
+					match e.code {
+						KeyCode::Char(c) => self.typed.push(c),
+						KeyCode::Backspace => {
+							self.typed.pop();
+						}
+						_ => (),
+					}
+				} else if key_match(
+					e,
+					self.key_config.keys.confirm_dont_ask,
+				) {
+					self.dont_ask = !self.dont_ask;
 				}
 
 				return Ok(EventState::Consumed);
@@ -104,34 +349,256 @@ impl Component for ConfirmComponent {
 impl ConfirmComponent {
 	///
 	pub fn new(
+		repo: RepoPathRef,
 		queue: Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		prefs: ConfirmPrefs,
 	) -> Self {
 		Self {
+			repo,
 			target: None,
+			protected: None,
+			consequences: Vec::new(),
+			typed: String::new(),
+			dont_ask: false,
+			prefs,
 			visible: false,
 			queue,
 			theme,
 			key_config,
 		}
 	}
-	///
-	pub fn open(&mut self, a: Action) -> Result<()> {
+	/// confirms right away if the user opted out of being asked,
+	/// refuses if `a` changes a protected branch and
+	/// `gitui.protectedBranchAction` says so, `changed_files` are the
+	/// files of the last status update
+	pub fn open(
+		&mut self,
+		a: Action,
+		changed_files: &[String],
+	) -> Result<()> {
+		self.protected = None;
+
+		if let Some(branch) = affected_branch(&a) {
+			let protection =
+				sync::protected_branches_config(&self.repo.borrow())
+					.ok()
+					.and_then(|cfg| cfg.protection(branch));
+
+			match protection {
+				Some(BranchProtection::Refuse) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						strings::protected_branch_refused(branch),
+					));
+					return Ok(());
+				}
+				Some(BranchProtection::Confirm) => {
+					self.protected = Some(branch.to_string());
+				}
+				None => (),
+			}
+		}
+
+		let skip = self.protected.is_none()
+			&& typed_word(&a).is_none()
+			&& self.prefs.skip.iter().any(|kind| kind == a.kind());
+
+		self.consequences = self.consequences(&a, changed_files);
+		self.typed.clear();
+		self.dont_ask = false;
 		self.target = Some(a);
-		self.show()?;
+
+		if skip {
+			self.confirm();
+		} else {
+			self.show()?;
+		}
 
 		Ok(())
 	}
 	///
 	pub fn confirm(&mut self) {
 		if let Some(a) = self.target.take() {
+			if self.dont_ask {
+				self.prefs.skip.push(a.kind().to_string());
+				if let Err(e) = self.prefs.save() {
+					log::error!("saving confirm preferences: {}", e);
+				}
+			}
+
 			self.queue.push(InternalEvent::ConfirmedAction(a));
 		}
 
 		self.hide();
 	}
 
+	fn can_confirm(&self) -> bool {
+		self.typed_word().map_or(true, |word| self.typed == word)
+	}
+
+	fn can_skip(&self) -> bool {
+		self.target.is_some() && self.typed_word().is_none()
+	}
+
+	/// the protected branch name or the word for the action
+	fn typed_word(&self) -> Option<&str> {
+		self.protected
+			.as_deref()
+			.or_else(|| self.target.as_ref().and_then(typed_word))
+	}
+
+	/// exactly what gets lost by confirming `action`
+	fn consequences(
+		&self,
+		action: &Action,
+		changed_files: &[String],
+	) -> Vec<String> {
+		match action {
+			Action::Reset(_)
+			| Action::ResetMarked(_)
+			| Action::ResetHunk(..)
+			| Action::ResetLines(..)
+			| Action::AbortMerge
+			| Action::AbortRevert => file_consequences(action, changed_files),
+			Action::DeleteLocalBranch(_)
+			| Action::DeleteRemoteBranch(_)
+			| Action::RenameRemoteBranch { .. }
+			| Action::DeleteTag(_)
+			| Action::DeleteRemoteTag(..)
+			| Action::ForcePush(..) => self.ref_consequences(action),
+			Action::StashDrop(ids) => ids
+				.iter()
+				.map(|id| format!("stash {}", id.get_short_string()))
+				.collect(),
+			Action::RebuildIndex(_) => {
+				vec![strings::REBUILD_INDEX_CONSEQUENCE.to_string()]
+			}
+			Action::WithoutUndo(action, _) => {
+				self.consequences(action, changed_files)
+			}
+			Action::Undo(entry) | Action::RestoreDiscarded(entry) => {
+				entry
+					.files
+					.iter()
+					.map(|file| {
+						format!("current content of {}", file)
+					})
+					.collect()
+			}
+			Action::PushPreviewed(updates)
+			| Action::FetchPreviewed(updates) => {
+				ref_update_consequences(updates)
+			}
+			Action::StashPop(_)
+			| Action::PullMerge { .. }
+			| Action::AbortRebase
+			| Action::StoreCredentials(..)
+			| Action::AutoStash(_)
+			| Action::CommitProtected(_)
+			| Action::UndoCommitProtected(_)
+			| Action::Amend => Vec::new(),
+		}
+	}
+
+	/// the branches, tags and commits `action` removes
+	fn ref_consequences(&self, action: &Action) -> Vec<String> {
+		match action {
+			Action::DeleteLocalBranch(branch_ref) => {
+				let branch =
+					branch_ref.trim_start_matches("refs/heads/");
+				let unpushed = sync::branch_compare_upstream(
+					&self.repo.borrow(),
+					branch,
+				)
+				.map(|c| c.ahead)
+				.unwrap_or_default();
+				let mut res = vec![format!("branch {}", branch)];
+				if unpushed > 0 {
+					res.push(format!(
+						"{} commits not on the upstream",
+						unpushed
+					));
+				}
+				res
+			}
+			Action::DeleteRemoteBranch(branch_ref) => {
+				vec![format!("remote branch {}", branch_ref)]
+			}
+			Action::RenameRemoteBranch { remote, old, .. } => {
+				vec![format!("remote branch {}/{}", remote, old)]
+			}
+			Action::DeleteTag(tag) => vec![format!("tag {}", tag)],
+			Action::DeleteRemoteTag(tag, remote) => {
+				vec![format!("tag {} on {}", tag, remote)]
+			}
+			Action::ForcePush(_, overwritten) => overwritten
+				.iter()
+				.map(|commit| {
+					strings::overwritten_commit(
+						&commit.id.get_short_string(),
+						&commit.message,
+						&commit.author,
+					)
+				})
+				.collect(),
+			_ => Vec::new(),
+		}
+	}
+
+	fn get_details(&self) -> Vec<Spans<'_>> {
+		let mut res = Vec::new();
+
+		if !self.consequences.is_empty() {
+			res.push(Spans::default());
+			res.push(Spans::from(self.target.as_ref().map_or(
+				strings::CONFIRM_CONSEQUENCES,
+				consequences_title,
+			)));
+			res.extend(
+				self.consequences.iter().take(MAX_CONSEQUENCES).map(
+					|item| {
+						Spans::from(Span::styled(
+							format!(" - {}", item),
+							self.theme.text(true, false),
+						))
+					},
+				),
+			);
+			if self.consequences.len() > MAX_CONSEQUENCES {
+				res.push(Spans::from(strings::confirm_more(
+					self.consequences.len() - MAX_CONSEQUENCES,
+				)));
+			}
+		}
+
+		if let Some(branch) = &self.protected {
+			res.push(Spans::default());
+			res.push(Spans::from(Span::styled(
+				strings::confirm_protected_branch(branch),
+				self.theme.text_danger(),
+			)));
+		}
+
+		res.push(Spans::default());
+		if let Some(word) = self.typed_word() {
+			res.push(Spans::from(vec![
+				Span::raw(strings::confirm_type_word(word)),
+				Span::styled(
+					self.typed.as_str(),
+					self.theme.text(self.typed == word, true),
+				),
+			]));
+		} else {
+			res.push(Spans::from(strings::confirm_dont_ask(
+				&self.key_config,
+				self.dont_ask,
+			)));
+		}
+
+		res
+	}
+
 	fn get_text(&self) -> (String, String) {
 		if let Some(ref a) = self.target {
 			return match a {
@@ -139,6 +606,10 @@ impl ConfirmComponent {
                     strings::confirm_title_reset(),
                     strings::confirm_msg_reset(),
                 ),
+                Action::ResetMarked(paths) => (
+                    strings::confirm_title_reset(),
+                    strings::confirm_msg_reset_marked(paths.len()),
+                ),
                 Action::StashDrop(ids) => (
                     strings::confirm_title_stashdrop(
                         &self.key_config,ids.len()>1
@@ -188,7 +659,7 @@ impl ConfirmComponent {
                     strings::confirm_title_delete_tag_remote(),
                     strings::confirm_msg_delete_tag_remote(remote),
                 ),
-                Action::ForcePush(branch, _force) => (
+                Action::ForcePush(branch, _) => (
                     strings::confirm_title_force_push(
                         &self.key_config,
                     ),
@@ -197,6 +668,12 @@ impl ConfirmComponent {
                         branch.rsplit('/').next().expect("There was no / in the head reference which is impossible in git"),
                     ),
                 ),
+                Action::RenameRemoteBranch{branch,remote,old} => (
+                    strings::confirm_title_rename_remote_branch(),
+                    strings::confirm_msg_rename_remote_branch(
+                        remote, old, branch,
+                    ),
+                ),
                 Action::PullMerge{incoming,rebase} => (
                     strings::confirm_title_merge(&self.key_config,*rebase),
                     strings::confirm_msg_merge(&self.key_config,*incoming,*rebase),
@@ -213,9 +690,95 @@ impl ConfirmComponent {
                     strings::confirm_title_abortrevert(),
                     strings::confirm_msg_revertchanges(),
                 ),
+				_ => protected_text(a)
+					.or_else(|| safeguard_text(a))
+					.unwrap_or_default(),
             };
 		}
 
 		(String::new(), String::new())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::queue::ResetItem;
+	use asyncgit::sync::RepoPath;
+	use std::cell::RefCell;
+
+	#[test]
+	fn test_typed_confirm() {
+		let queue = Queue::new();
+		let mut comp = ConfirmComponent::new(
+			RefCell::new(RepoPath::Path(".".into())),
+			queue.clone(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			ConfirmPrefs::default(),
+		);
+
+		comp.open(
+			Action::ForcePush(String::from("master"), Vec::new()),
+			&[],
+		)
+		.unwrap();
+
+		assert!(comp.is_visible());
+		assert!(!comp.can_confirm());
+		assert!(!comp.can_skip());
+
+		comp.typed = String::from("force");
+		assert!(comp.can_confirm());
+
+		comp.confirm();
+		assert!(!comp.is_visible());
+		assert!(matches!(
+			queue.pop(),
+			Some(InternalEvent::ConfirmedAction(Action::ForcePush(
+				..
+			)))
+		));
+	}
+
+	#[test]
+	fn test_file_consequences() {
+		let changed = [
+			String::from("a.txt"),
+			String::from("src/lib.rs"),
+			String::from("src/main.rs"),
+		];
+
+		assert_eq!(
+			file_consequences(
+				&Action::Reset(ResetItem {
+					path: String::from("src/"),
+					is_folder: true,
+				}),
+				&changed,
+			),
+			vec!["src/lib.rs", "src/main.rs"]
+		);
+		assert_eq!(
+			file_consequences(&Action::AbortMerge, &changed).len(),
+			3
+		);
+	}
+
+	#[test]
+	fn test_affected_branch() {
+		assert_eq!(
+			affected_branch(&Action::DeleteLocalBranch(
+				String::from("refs/heads/main")
+			)),
+			Some("main")
+		);
+		assert_eq!(
+			affected_branch(&Action::DeleteRemoteBranch(
+				String::from("refs/remotes/origin/release/1.0")
+			)),
+			Some("release/1.0")
+		);
+		assert_eq!(affected_branch(&Action::AbortRebase), None);
+	}
+}
@@ -1,17 +1,28 @@
 use super::{
-	utils::scroll_vertical::VerticalScroll, BlameFileOpen,
-	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState, FileRevOpen, SyntaxTextComponent,
+	utils::{
+		file_copy::{copy_file, FileCopy},
+		scroll_vertical::VerticalScroll,
+		time_to_string,
+	},
+	BlameFileOpen, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, FileFilterComponent, FileRevOpen,
+	SharedOptions, SyntaxTextComponent,
 };
 use crate::{
+	file_manager,
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, Queue, StackablePopupOpen},
+	string_utils::pad_to_width,
 	strings::{self, order, symbol},
+	try_or_popup,
 	ui::{self, common_nav, style::SharedTheme},
 	AsyncAppNotification, AsyncNotification,
 };
 use anyhow::Result;
-use asyncgit::sync::{self, CommitId, RepoPathRef, TreeFile};
+use asyncgit::{
+	sync::{self, CommitId, RepoPathRef, TreeFile},
+	AsyncBlame, AsyncGitNotification, BlameParams,
+};
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use filetreelist::{FileTree, FileTreeItem};
@@ -27,6 +38,7 @@ use tui::{
 	widgets::{Block, Borders},
 	Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 enum Focus {
 	Tree,
@@ -41,11 +53,15 @@ pub struct RevisionFilesComponent {
 	files: Vec<TreeFile>,
 	current_file: SyntaxTextComponent,
 	tree: FileTree,
+	filter: FileFilterComponent,
 	scroll: VerticalScroll,
 	visible: bool,
 	revision: Option<CommitId>,
 	focus: Focus,
 	key_config: SharedKeyConfig,
+	async_blame: AsyncBlame,
+	/// line of the current file we wait on a blame for
+	blame_line: Option<usize>,
 }
 
 impl RevisionFilesComponent {
@@ -54,18 +70,30 @@ impl RevisionFilesComponent {
 		repo: RepoPathRef,
 		queue: &Queue,
 		sender: &Sender<AsyncAppNotification>,
+		sender_git: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
+		let async_blame =
+			AsyncBlame::new(repo.borrow().clone(), sender_git);
+
 		Self {
+			async_blame,
+			blame_line: None,
 			queue: queue.clone(),
 			tree: FileTree::default(),
+			filter: FileFilterComponent::new(
+				theme.clone(),
+				key_config.clone(),
+			),
 			scroll: VerticalScroll::new(),
 			current_file: SyntaxTextComponent::new(
 				repo.clone(),
 				sender,
 				key_config.clone(),
 				theme.clone(),
+				options,
 			),
 			theme,
 			files: Vec::new(),
@@ -86,16 +114,51 @@ impl RevisionFilesComponent {
 		if !same_id {
 			self.files =
 				sync::tree_files(&self.repo.borrow(), commit)?;
-			let filenames: Vec<&Path> =
-				self.files.iter().map(|f| f.path.as_path()).collect();
-			self.tree = FileTree::new(&filenames, &BTreeSet::new())?;
-			self.tree.collapse_but_root();
+			self.build_tree()?;
 			self.revision = Some(commit);
 		}
 
 		Ok(())
 	}
 
+	/// builds the tree from the files matching the filter, all
+	/// folders are expanded while filtering
+	fn build_tree(&mut self) -> Result<()> {
+		let filenames: Vec<&Path> = self
+			.files
+			.iter()
+			.map(|f| f.path.as_path())
+			.filter(|path| {
+				self.filter.matches(
+					&path
+						.strip_prefix("./")
+						.unwrap_or(path)
+						.to_string_lossy(),
+				)
+			})
+			.collect();
+		self.tree = FileTree::new(&filenames, &BTreeSet::new())?;
+		if !self.filter.is_filtering() {
+			self.tree.collapse_but_root();
+		}
+
+		Ok(())
+	}
+
+	/// keeps the selected file selected if it still matches
+	fn filter_changed(&mut self) -> Result<()> {
+		let selected = self.selected_file_path_with_prefix();
+
+		self.build_tree()?;
+
+		if let Some(selected) = selected {
+			self.tree.select_file(Path::new(&selected));
+		}
+		self.selection_changed();
+
+		Ok(())
+	}
+
 	///
 	pub const fn revision(&self) -> Option<CommitId> {
 		self.revision
@@ -108,12 +171,88 @@ impl RevisionFilesComponent {
 
 	///
 	pub fn update(&mut self, ev: AsyncNotification) {
+		if ev == AsyncNotification::Git(AsyncGitNotification::Blame) {
+			if let Err(e) = self.update_line_blame() {
+				self.blame_line = None;
+				self.queue
+					.push(InternalEvent::ShowErrorMsg(e.to_string()));
+			}
+		}
+
 		self.current_file.update(ev);
 	}
 
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.current_file.any_work_pending()
+			|| self.async_blame.is_pending()
+	}
+
+	fn request_line_blame(&mut self) -> Result<bool> {
+		let file_path = match self.selected_file_path() {
+			Some(path) => path,
+			None => return Ok(false),
+		};
+
+		self.blame_line = Some(self.current_file.top_line());
+		self.async_blame.request(BlameParams {
+			file_path,
+			commit_id: self.revision,
+			ignore_revs: false,
+		})?;
+		self.update_line_blame()?;
+
+		Ok(true)
+	}
+
+	fn update_line_blame(&mut self) -> Result<()> {
+		let line = match self.blame_line {
+			Some(line) => line,
+			None => return Ok(()),
+		};
+
+		if let Some((params, blame)) = self.async_blame.last()? {
+			let matches = params.commit_id == self.revision
+				&& Some(&params.file_path)
+					== self.selected_file_path().as_ref();
+
+			if !matches {
+				return Ok(());
+			}
+
+			self.blame_line = None;
+
+			let hunk = blame
+				.lines
+				.get(line)
+				.and_then(|(hunk, _)| hunk.as_ref());
+
+			let msg = if let Some(hunk) = hunk {
+				let subject = sync::get_commits_info(
+					&self.repo.borrow(),
+					&[hunk.commit_id],
+					100,
+				)?
+				.pop()
+				.map(|info| info.message)
+				.unwrap_or_default();
+
+				format!(
+					"line {}\n\n{}\n{}\n{}\n\n{}",
+					line + 1,
+					hunk.commit_id.get_short_string(),
+					hunk.author,
+					time_to_string(hunk.time, false),
+					subject
+				)
+			} else {
+				format!("line {}: no commit found", line + 1)
+			};
+
+			self.queue.push(InternalEvent::ShowInfoMsg(msg));
+		}
+
+		Ok(())
 	}
 
 	fn tree_item_to_span<'a>(
@@ -142,15 +281,14 @@ impl RevisionFilesComponent {
 			symbol::EMPTY_STR
 		};
 
-		let available_width =
-			width.saturating_sub(indent_str.len() + path_arrow.len());
+		let available_width = width
+			.saturating_sub(indent_str.len() + path_arrow.width());
 
 		let path = format!(
-			"{}{}{:w$}",
+			"{}{}{}",
 			indent_str,
 			path_arrow,
-			path,
-			w = available_width
+			pad_to_width(path, available_width)
 		);
 
 		Span::styled(path, theme.file_tree_item(is_path, selected))
@@ -182,6 +320,40 @@ impl RevisionFilesComponent {
 		})
 	}
 
+	fn copy(&self, copy: FileCopy) -> bool {
+		self.selected_file_path().map_or(false, |path| {
+			//TODO: retrieve TreeFile from tree datastructure
+			let file = self
+				.selected_file_path_with_prefix()
+				.and_then(|file| {
+					self.files
+						.iter()
+						.find(|f| f.path == Path::new(&file))
+				});
+
+			let repo = self.repo.borrow();
+			copy_file(&self.queue, &repo, &path, copy, || {
+				let file = file.ok_or_else(|| {
+					anyhow::anyhow!("'{}' not found", path)
+				})?;
+				Ok(sync::tree_file_content(&repo, file)?)
+			});
+
+			true
+		})
+	}
+
+	/// the file as it is in the workdir, not in the revision
+	fn reveal(&self) -> Result<()> {
+		if let Some(path) = self.selected_file_path() {
+			let root =
+				sync::utils::repo_work_dir(&self.repo.borrow())?;
+			file_manager::reveal(&Path::new(&root).join(path))?;
+		}
+
+		Ok(())
+	}
+
 	fn open_finder(&self) {
 		self.queue
 			.push(InternalEvent::OpenFileFinder(self.files.clone()));
@@ -230,7 +402,11 @@ impl RevisionFilesComponent {
 		}
 	}
 
-	fn draw_tree<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+	fn draw_tree<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		area: Rect,
+	) -> Result<()> {
 		let tree_height = usize::from(area.height.saturating_sub(2));
 		let tree_width = usize::from(area.width);
 
@@ -261,12 +437,12 @@ impl RevisionFilesComponent {
 
 		let is_tree_focused = matches!(self.focus, Focus::Tree);
 
-		let title = format!(
+		let title = self.filter.title(&format!(
 			"Files at [{}]",
 			self.revision
 				.map(|c| c.get_short_string())
 				.unwrap_or_default(),
-		);
+		));
 		ui::draw_list_block(
 			f,
 			area,
@@ -283,6 +459,8 @@ impl RevisionFilesComponent {
 		if is_tree_focused {
 			self.scroll.draw(f, area, &self.theme);
 		}
+
+		self.filter.draw_in_list(f, area)
 	}
 }
 
@@ -304,7 +482,7 @@ impl DrawableComponent for RevisionFilesComponent {
 				)
 				.split(area);
 
-			self.draw_tree(f, chunks[0]);
+			self.draw_tree(f, chunks[0])?;
 
 			self.current_file.draw(f, chunks[1])?;
 		}
@@ -349,7 +527,25 @@ impl Component for RevisionFilesComponent {
 				.order(order::RARE_ACTION),
 			);
 			tree_nav_cmds(&self.tree, &self.key_config, out);
+			self.filter.commands(out, force_all);
+			FileCopy::commands(
+				out,
+				&self.key_config,
+				self.tree.selected_file().is_some(),
+				self.tree.selected_file().is_some(),
+				true,
+			);
+			out.push(CommandInfo::new(
+				strings::commands::reveal_file(&self.key_config),
+				self.tree.selected_file().is_some(),
+				true,
+			));
 		} else {
+			out.push(CommandInfo::new(
+				strings::commands::blame_line(&self.key_config),
+				self.blame_line.is_none(),
+				true,
+			));
 			self.current_file.commands(out, force_all);
 		}
 
@@ -364,6 +560,17 @@ impl Component for RevisionFilesComponent {
 			return Ok(EventState::NotConsumed);
 		}
 
+		if matches!(self.focus, Focus::Tree) {
+			let query = self.filter.query().to_string();
+			if self.filter.event(event)?.is_consumed() {
+				if self.filter.query() != query {
+					self.filter_changed()?;
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+
 		if let Event::Key(key) = event {
 			let is_tree_focused = matches!(self.focus, Focus::Tree);
 			if is_tree_focused
@@ -399,11 +606,31 @@ impl Component for RevisionFilesComponent {
 					self.focus(false);
 					return Ok(EventState::Consumed);
 				}
+			} else if key_match(key, self.key_config.keys.blame_line)
+			{
+				if !is_tree_focused && self.request_line_blame()? {
+					return Ok(EventState::Consumed);
+				}
 			} else if key_match(key, self.key_config.keys.file_find) {
 				if is_tree_focused {
 					self.open_finder();
 					return Ok(EventState::Consumed);
 				}
+			} else if let Some(copy) =
+				FileCopy::from_key(key, &self.key_config)
+					.filter(|_| is_tree_focused)
+			{
+				return Ok(self.copy(copy).into());
+			} else if key_match(key, self.key_config.keys.reveal_file)
+			{
+				if is_tree_focused {
+					try_or_popup!(
+						self,
+						"reveal error:",
+						self.reveal()
+					);
+					return Ok(EventState::Consumed);
+				}
 			} else if key_match(key, self.key_config.keys.edit_file) {
 				if let Some(file) =
 					self.selected_file_path_with_prefix()
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use super::{
 	revision_files::RevisionFilesComponent, visibility_blocking,
 	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState,
+	EventState, SharedOptions,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
@@ -13,7 +13,10 @@ use crate::{
 	AsyncAppNotification, AsyncNotification,
 };
 use anyhow::Result;
-use asyncgit::sync::{CommitId, RepoPathRef};
+use asyncgit::{
+	sync::{CommitId, RepoPathRef},
+	AsyncGitNotification,
+};
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use tui::{backend::Backend, layout::Rect, widgets::Clear, Frame};
@@ -47,16 +50,20 @@ impl RevisionFilesPopup {
 		repo: RepoPathRef,
 		queue: &Queue,
 		sender: &Sender<AsyncAppNotification>,
+		sender_git: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			files: RevisionFilesComponent::new(
 				repo,
 				queue,
 				sender,
+				sender_git,
 				theme,
 				key_config.clone(),
+				options,
 			),
 			visible: false,
 			key_config,
@@ -0,0 +1,409 @@
+use super::{
+	utils::scroll_vertical::VerticalScroll, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState, ScrollType,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	string_utils::tabs_to_spaces,
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{
+		grep::{
+			grep_file_content, GrepMatch, GrepResult, GrepSource,
+		},
+		RepoPathRef,
+	},
+	GrepParams,
+};
+use crossterm::event::Event;
+use std::sync::Arc;
+use tui::{
+	backend::Backend,
+	layout::{Constraint, Direction, Layout, Rect},
+	style::Modifier,
+	text::{Span, Spans},
+	widgets::{Block, Borders, Paragraph},
+	Frame,
+};
+
+/// matches of a content search next to a preview of the file
+/// around the selected one
+pub struct SearchResultsComponent {
+	repo: RepoPathRef,
+	queue: Queue,
+	result: Option<(GrepParams, Arc<GrepResult>)>,
+	selection: usize,
+	scroll: VerticalScroll,
+	/// path and lines of the file shown in the preview
+	preview: Option<(String, Vec<String>)>,
+	pending: bool,
+	focused: bool,
+	visible: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl SearchResultsComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			repo,
+			queue: queue.clone(),
+			result: None,
+			selection: 0,
+			scroll: VerticalScroll::new(),
+			preview: None,
+			pending: false,
+			focused: false,
+			visible: false,
+			theme,
+			key_config,
+		}
+	}
+
+	/// shows `result`, keeps the selection if it is for the
+	/// same search as before (e.g. searched again)
+	pub fn set_result(
+		&mut self,
+		params: GrepParams,
+		result: Arc<GrepResult>,
+	) {
+		let same_search = self
+			.result
+			.as_ref()
+			.map_or(false, |(last, _)| *last == params);
+		if !same_search {
+			self.selection = 0;
+			self.scroll.reset();
+		}
+
+		self.selection = self
+			.selection
+			.min(result.matches.len().saturating_sub(1));
+		self.result = Some((params, result));
+		self.preview = None;
+		self.update_preview();
+	}
+
+	///
+	pub fn clear(&mut self) {
+		self.result = None;
+		self.preview = None;
+		self.selection = 0;
+		self.scroll.reset();
+	}
+
+	/// whether a search for new results is running
+	pub fn set_pending(&mut self, pending: bool) {
+		self.pending = pending;
+	}
+
+	///
+	pub fn focus(&mut self, focus: bool) {
+		self.focused = focus;
+	}
+
+	fn matches(&self) -> &[GrepMatch] {
+		self.result
+			.as_ref()
+			.map_or(&[], |(_, result)| result.matches.as_slice())
+	}
+
+	fn selected_match(&self) -> Option<&GrepMatch> {
+		self.matches().get(self.selection)
+	}
+
+	fn source(&self) -> Option<GrepSource> {
+		self.result.as_ref().map(|(params, _)| params.source)
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) {
+		let max = self.matches().len().saturating_sub(1);
+		self.selection = self.scroll.move_selection(
+			self.selection,
+			scroll,
+			1,
+			max,
+		);
+		self.update_preview();
+	}
+
+	fn update_preview(&mut self) {
+		let (source, path) = if let (Some(source), Some(m)) =
+			(self.source(), self.selected_match())
+		{
+			(source, m.path.clone())
+		} else {
+			self.preview = None;
+			return;
+		};
+
+		if matches!(&self.preview, Some((preview, _)) if *preview == path)
+		{
+			return;
+		}
+
+		self.preview =
+			grep_file_content(&self.repo.borrow(), source, &path)
+				.ok()
+				.map(|content| {
+					let lines = content
+						.lines()
+						.map(|line| tabs_to_spaces(line.to_string()))
+						.collect();
+					(path, lines)
+				});
+	}
+
+	fn get_rows(&self, top: usize, height: usize) -> Vec<Spans> {
+		self.matches()
+			.iter()
+			.enumerate()
+			.skip(top)
+			.take(height)
+			.map(|(idx, m)| {
+				let selected = idx == self.selection;
+				let text = self.theme.text(true, selected);
+				let (before, rest) = m.line.split_at(m.range.start);
+				let (found, after) =
+					rest.split_at(m.range.end - m.range.start);
+
+				Spans::from(vec![
+					Span::styled(
+						format!("{}:{}: ", m.path, m.line_number),
+						self.theme.commit_hash(selected),
+					),
+					Span::styled(
+						tabs_to_spaces(
+							before.trim_start().to_string(),
+						),
+						text,
+					),
+					Span::styled(
+						tabs_to_spaces(found.to_string()),
+						text.add_modifier(
+							Modifier::BOLD | Modifier::UNDERLINED,
+						),
+					),
+					Span::styled(
+						tabs_to_spaces(after.to_string()),
+						text,
+					),
+				])
+			})
+			.collect()
+	}
+
+	fn get_preview_rows(&self, height: usize) -> Vec<Spans> {
+		let (line_idx, lines) = if let (Some(m), Some((_, lines))) =
+			(self.selected_match(), &self.preview)
+		{
+			(m.line_number.saturating_sub(1), lines)
+		} else {
+			return Vec::new();
+		};
+
+		let top = line_idx.saturating_sub(height / 2);
+
+		lines
+			.iter()
+			.enumerate()
+			.skip(top)
+			.take(height)
+			.map(|(idx, line)| {
+				Spans::from(vec![
+					Span::styled(
+						format!("{:>4} ", idx + 1),
+						self.theme.text(false, false),
+					),
+					Span::styled(
+						line.as_str(),
+						self.theme.text(true, idx == line_idx),
+					),
+				])
+			})
+			.collect()
+	}
+
+	fn draw_list<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+		let result = self.result.as_ref().map(|(_, result)| result);
+		let title = strings::search_results_title(
+			self.matches().len(),
+			result.map_or(false, |result| result.truncated),
+			self.pending,
+		);
+
+		let block = Block::default()
+			.title(Span::styled(
+				title,
+				self.theme.title(self.focused),
+			))
+			.borders(Borders::ALL)
+			.border_style(self.theme.block(self.focused));
+		let inner = block.inner(area);
+
+		let height = usize::from(inner.height);
+		let top = self.scroll.update(
+			self.selection,
+			self.matches().len().saturating_sub(1),
+			height,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.get_rows(top, height)).block(block),
+			area,
+		);
+		self.scroll.draw(f, area, &self.theme);
+	}
+
+	fn draw_preview<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+		let title = self
+			.preview
+			.as_ref()
+			.map_or("", |(path, _)| path.as_str());
+
+		let block = Block::default()
+			.title(Span::styled(title, self.theme.title(false)))
+			.borders(Borders::ALL)
+			.border_style(self.theme.block(false));
+		let height = usize::from(block.inner(area).height);
+
+		f.render_widget(
+			Paragraph::new(self.get_preview_rows(height))
+				.block(block),
+			area,
+		);
+	}
+}
+
+impl DrawableComponent for SearchResultsComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[
+						Constraint::Percentage(50),
+						Constraint::Percentage(50),
+					]
+					.as_ref(),
+				)
+				.split(rect);
+
+			self.draw_list(f, chunks[0]);
+			self.draw_preview(f, chunks[1]);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for SearchResultsComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			let available = self.focused || force_all;
+			let selected = self.selected_match().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::search_jump_to_file(
+					&self.key_config,
+				),
+				selected,
+				available,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::edit_item(&self.key_config),
+				selected
+					&& self.source() == Some(GrepSource::WorkDir),
+				available,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible || !self.focused {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			let keys = &self.key_config.keys;
+			let scroll = if key_match(e, keys.move_up) {
+				Some(ScrollType::Up)
+			} else if key_match(e, keys.move_down) {
+				Some(ScrollType::Down)
+			} else if key_match(e, keys.shift_up)
+				|| key_match(e, keys.home)
+			{
+				Some(ScrollType::Home)
+			} else if key_match(e, keys.shift_down)
+				|| key_match(e, keys.end)
+			{
+				Some(ScrollType::End)
+			} else if key_match(e, keys.page_up) {
+				Some(ScrollType::PageUp)
+			} else if key_match(e, keys.page_down) {
+				Some(ScrollType::PageDown)
+			} else {
+				None
+			};
+
+			if let Some(scroll) = scroll {
+				self.move_selection(scroll);
+				return Ok(EventState::Consumed);
+			}
+
+			if let Some(m) = self.selected_match() {
+				if key_match(e, keys.enter) {
+					self.queue.push(InternalEvent::JumpToFile(
+						m.path.clone(),
+					));
+					return Ok(EventState::Consumed);
+				} else if key_match(e, keys.edit_file)
+					&& self.source() == Some(GrepSource::WorkDir)
+				{
+					self.queue.push(
+						InternalEvent::OpenExternalEditor(Some(
+							m.path.clone(),
+						)),
+					);
+					return Ok(EventState::Consumed);
+				}
+			}
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		Ok(())
+	}
+}
@@ -0,0 +1,341 @@
+use super::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, TextInputComponent,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, PickTarget, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use crossterm::event::Event;
+use easy_cast::Cast;
+use fuzzy_matcher::FuzzyMatcher;
+use tui::{
+	backend::Backend,
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Span, Spans},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+const MAX_ROWS: usize = 12;
+
+///
+pub struct SelectListOpen {
+	///
+	pub target: PickTarget,
+	///
+	pub title: String,
+	///
+	pub items: Vec<String>,
+	/// index of the item selected initially
+	pub selected: usize,
+}
+
+/// picks one item of a list, typing fuzzy filters the list
+pub struct SelectListComponent {
+	queue: Queue,
+	target: Option<PickTarget>,
+	title: String,
+	items: Vec<String>,
+	filter: TextInputComponent,
+	filtered: Vec<(usize, Vec<usize>)>,
+	selection: usize,
+	visible: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl SelectListComponent {
+	///
+	pub fn new(
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		let mut filter = TextInputComponent::new(
+			theme.clone(),
+			key_config.clone(),
+			"",
+			"type to filter..",
+			false,
+		);
+		filter.embed();
+
+		Self {
+			queue: queue.clone(),
+			target: None,
+			title: String::new(),
+			items: Vec::new(),
+			filter,
+			filtered: Vec::new(),
+			selection: 0,
+			visible: false,
+			theme,
+			key_config,
+		}
+	}
+
+	///
+	pub fn open(&mut self, open: SelectListOpen) -> Result<()> {
+		self.target = Some(open.target);
+		self.title = open.title;
+		self.items = open.items;
+		self.filter.clear();
+		self.update_filter();
+		self.selection =
+			open.selected.min(self.filtered.len().saturating_sub(1));
+		self.filter.show()?;
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn update_filter(&mut self) {
+		let query = self.filter.get_text();
+
+		self.filtered = if query.is_empty() {
+			(0..self.items.len())
+				.map(|idx| (idx, Vec::new()))
+				.collect()
+		} else {
+			let matcher =
+				fuzzy_matcher::skim::SkimMatcherV2::default();
+
+			let mut hits = self
+				.items
+				.iter()
+				.enumerate()
+				.filter_map(|(idx, item)| {
+					matcher
+						.fuzzy_indices(item, query)
+						.map(|(score, indices)| (score, idx, indices))
+				})
+				.collect::<Vec<_>>();
+
+			hits.sort_by(|(score1, _, _), (score2, _, _)| {
+				score2.cmp(score1)
+			});
+
+			hits.into_iter()
+				.map(|(_, idx, indices)| (idx, indices))
+				.collect()
+		};
+
+		self.selection = 0;
+	}
+
+	fn confirm(&mut self) {
+		if let (Some(target), Some((idx, _))) =
+			(self.target, self.filtered.get(self.selection))
+		{
+			self.queue.push(InternalEvent::Picked(target, *idx));
+		}
+
+		self.hide();
+	}
+
+	fn get_text(&self, height: usize) -> Vec<Spans<'_>> {
+		let skip =
+			self.selection.saturating_sub(height.saturating_sub(1));
+
+		self.filtered
+			.iter()
+			.enumerate()
+			.skip(skip)
+			.take(height)
+			.map(|(row, (idx, indices))| {
+				let selected = row == self.selection;
+				Spans::from(
+					self.items[*idx]
+						.chars()
+						.enumerate()
+						.map(|(c_idx, c)| {
+							Span::styled(
+								c.to_string(),
+								self.theme.text(
+									!indices.contains(&c_idx),
+									selected,
+								),
+							)
+						})
+						.collect::<Vec<_>>(),
+				)
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for SelectListComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let rows: u16 =
+				self.items.len().clamp(1, MAX_ROWS).cast();
+			let area = ui::centered_rect_absolute(40, rows + 3, rect);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::all())
+					.title(Span::styled(
+						self.title.as_str(),
+						self.theme.title(true),
+					))
+					.border_style(self.theme.block(true)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Length(1),
+						Constraint::Percentage(100),
+					]
+					.as_ref(),
+				)
+				.split(area.inner(&Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			self.filter.draw(f, chunks[0])?;
+			f.render_widget(
+				Paragraph::new(
+					self.get_text(chunks[1].height.into()),
+				),
+				chunks[1],
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for SelectListComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::select_list_confirm(
+					&self.key_config,
+				),
+				!self.filtered.is_empty(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				} else if key_match(
+					e,
+					self.key_config.keys.popup_down,
+				) {
+					self.selection = self
+						.selection
+						.saturating_add(1)
+						.min(self.filtered.len().saturating_sub(1));
+				} else if key_match(e, self.key_config.keys.popup_up)
+				{
+					self.selection = self.selection.saturating_sub(1);
+				} else if self.filter.event(ev)?.is_consumed() {
+					self.update_filter();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::components::AppOption;
+
+	#[test]
+	fn test_filter() {
+		let queue = Queue::new();
+		let mut comp = SelectListComponent::new(
+			&queue,
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+
+		comp.open(SelectListOpen {
+			target: PickTarget::Option(
+				AppOption::StatusShowUntracked,
+			),
+			title: String::new(),
+			items: vec![
+				String::from("Gitconfig"),
+				String::from("No"),
+				String::from("Normal"),
+			],
+			selected: 2,
+		})
+		.unwrap();
+
+		assert_eq!(comp.selection, 2);
+
+		comp.filter.set_text(String::from("nrm"));
+		comp.update_filter();
+
+		assert_eq!(comp.filtered.len(), 1);
+		assert_eq!(comp.filtered[0].0, 2);
+
+		comp.confirm();
+
+		assert!(!comp.is_visible());
+		assert!(matches!(
+			queue.pop(),
+			Some(InternalEvent::Picked(_, 2))
+		));
+	}
+}
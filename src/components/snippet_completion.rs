@@ -0,0 +1,70 @@
+use super::completion::{Completion, CompletionProvider};
+use asyncgit::{
+	cached,
+	sync::{self, CommitSnippets, RepoPathRef},
+};
+
+/// expands the `gitui.snippet` abbreviations, e.g. `;t`, with the
+/// variables of the current branch
+pub struct SnippetCompletion {
+	repo: RepoPathRef,
+	snippets: CommitSnippets,
+	branch_name: cached::BranchName,
+	branch: String,
+}
+
+impl SnippetCompletion {
+	///
+	pub fn new(repo: RepoPathRef) -> Self {
+		Self {
+			branch_name: cached::BranchName::new(repo.clone()),
+			repo,
+			snippets: CommitSnippets::default(),
+			branch: String::new(),
+		}
+	}
+}
+
+impl CompletionProvider for SnippetCompletion {
+	fn token_start(&self, text: &str) -> Option<usize> {
+		let token = text.rsplit(char::is_whitespace).next()?;
+
+		(!token.is_empty()
+			&& self.snippets.snippets.iter().any(|snippet| {
+				snippet.abbreviation.starts_with(token)
+			}))
+		.then(|| text.len() - token.len())
+	}
+
+	fn candidates(&mut self, token: &str) -> Vec<Completion> {
+		self.snippets
+			.snippets
+			.iter()
+			.filter(|snippet| snippet.abbreviation.starts_with(token))
+			.map(|snippet| {
+				let text = self
+					.snippets
+					.expand(&snippet.template, &self.branch);
+				Completion {
+					label: snippet.abbreviation.clone(),
+					detail: text.clone(),
+					text,
+				}
+			})
+			.collect()
+	}
+
+	fn refresh(&mut self) {
+		self.snippets =
+			sync::commit_snippets_config(&self.repo.borrow())
+				.unwrap_or_else(|e| {
+					log::error!("snippet config error: {}", e);
+					CommitSnippets::default()
+				});
+		self.branch = self.branch_name.lookup().unwrap_or_default();
+	}
+
+	fn preselect(&self) -> bool {
+		true
+	}
+}
@@ -1,7 +1,7 @@
 use super::{
 	textinput::TextInputComponent, visibility_blocking,
 	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState,
+	EventState, ToastKind,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
@@ -82,6 +82,13 @@ impl Component for StashMsgComponent {
 							self.queue.push(InternalEvent::Update(
 								NeedsUpdate::ALL,
 							));
+							self.queue.push(
+								InternalEvent::ShowToast(
+									ToastKind::Success,
+									strings::TOAST_STASHED
+										.to_string(),
+								),
+							);
 						}
 						Err(e) => {
 							self.hide();
@@ -0,0 +1,161 @@
+use super::DrawableComponent;
+use crate::{strings, ui::style::SharedTheme};
+use anyhow::Result;
+use asyncgit::{
+	cached,
+	sync::{self, BranchCompare, RepoPathRef, RepoState},
+};
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans},
+	widgets::Paragraph,
+	Frame,
+};
+
+/// what the status tab found out last, see `Status::summary`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusSummary {
+	///
+	pub staged: usize,
+	///
+	pub unstaged: usize,
+	///
+	pub repo_state: RepoState,
+	///
+	pub branch_state: Option<BranchCompare>,
+}
+
+impl Default for StatusSummary {
+	fn default() -> Self {
+		Self {
+			staged: 0,
+			unstaged: 0,
+			repo_state: RepoState::Clean,
+			branch_state: None,
+		}
+	}
+}
+
+/// one line summary of the repository shown above the command bar,
+/// fed by the status tab and cached lookups instead of querying git
+/// on its own
+pub struct StatusBarComponent {
+	repo: RepoPathRef,
+	branch_name: cached::BranchName,
+	stash_count: cached::StashCount,
+	pull_request: Option<u64>,
+	stashes: usize,
+	summary: StatusSummary,
+	theme: SharedTheme,
+}
+
+impl StatusBarComponent {
+	///
+	pub fn new(repo: RepoPathRef, theme: SharedTheme) -> Self {
+		Self {
+			branch_name: cached::BranchName::new(repo.clone()),
+			stash_count: cached::StashCount::new(repo.clone()),
+			pull_request: None,
+			stashes: 0,
+			summary: StatusSummary::default(),
+			theme,
+			repo,
+		}
+	}
+
+	///
+	pub fn update(&mut self) {
+		let branch = self.branch_name.lookup().ok();
+		self.pull_request = branch.and_then(|branch| {
+			sync::github::branch_pull_request(
+				&self.repo.borrow(),
+				&branch,
+			)
+			.ok()
+			.flatten()
+		});
+		self.stashes = self.stash_count.lookup().unwrap_or_default();
+	}
+
+	///
+	pub fn set_summary(&mut self, summary: StatusSummary) {
+		self.summary = summary;
+	}
+
+	fn spans(&self) -> Vec<Span<'_>> {
+		let text = self.theme.text(true, false);
+		let separator =
+			Span::styled(" | ", self.theme.text(false, false));
+
+		let mut spans = vec![Span::styled(
+			format!(
+				" {}",
+				self.branch_name.last().unwrap_or_else(|| {
+					strings::STATUS_BAR_NO_BRANCH.into()
+				})
+			),
+			self.theme.branch(false, true),
+		)];
+
+		if let Some(number) = self.pull_request {
+			spans.push(Span::styled(
+				format!(" #{}", number),
+				self.theme.commit_hash(false),
+			));
+		}
+
+		if let Some(state) = &self.summary.branch_state {
+			spans.push(separator.clone());
+			spans.push(Span::styled(
+				format!(
+					"\u{2191}{} \u{2193}{}",
+					state.ahead, state.behind
+				),
+				text,
+			));
+		}
+
+		if self.summary.repo_state != RepoState::Clean {
+			spans.push(separator.clone());
+			spans.push(Span::styled(
+				format!("{:?}", self.summary.repo_state),
+				self.theme.text_danger(),
+			));
+		}
+
+		spans.push(separator.clone());
+		spans.push(Span::styled(
+			format!(
+				"+{} ~{}",
+				self.summary.staged, self.summary.unstaged
+			),
+			text,
+		));
+
+		if self.stashes > 0 {
+			spans.push(separator);
+			spans.push(Span::styled(
+				format!("stashes: {}", self.stashes),
+				text,
+			));
+		}
+
+		spans
+	}
+}
+
+impl DrawableComponent for StatusBarComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		f.render_widget(
+			Paragraph::new(Spans::from(self.spans())),
+			rect,
+		);
+
+		Ok(())
+	}
+}
@@ -1,22 +1,25 @@
 use super::{
 	utils::{
 		filetree::{FileTreeItem, FileTreeItemKind},
+		scroll_vertical::VerticalScroll,
 		statustree::{MoveSelection, StatusTree},
 	},
-	BlameFileOpen, CommandBlocking, DrawableComponent, FileRevOpen,
+	BlameFileOpen, CommandBlocking, DrawableComponent,
+	FileFilterComponent, FileRevOpen,
 };
 use crate::{
 	components::{CommandInfo, Component, EventState},
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
-	strings::{self, order},
+	string_utils::{bidi_visual_order, pad_to_width},
+	strings::{self, order, symbol},
 	ui,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{hash, StatusItem, StatusItemType};
 use crossterm::event::Event;
-use std::{borrow::Cow, cell::Cell, convert::From, path::Path};
+use std::{borrow::Cow, convert::From};
 use tui::{backend::Backend, layout::Rect, text::Span, Frame};
 
 //TODO: use new `filetreelist` crate
@@ -26,6 +29,8 @@ use tui::{backend::Backend, layout::Rect, text::Span, Frame};
 pub struct StatusTreeComponent {
 	title: String,
 	tree: StatusTree,
+	items: Vec<StatusItem>,
+	filter: FileFilterComponent,
 	pending: bool,
 	current_hash: u64,
 	focused: bool,
@@ -33,8 +38,9 @@ pub struct StatusTreeComponent {
 	queue: Option<Queue>,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
-	scroll_top: Cell<usize>,
+	scroll: VerticalScroll,
 	visible: bool,
+	marked: Vec<String>,
 }
 
 impl StatusTreeComponent {
@@ -49,15 +55,21 @@ impl StatusTreeComponent {
 		Self {
 			title: title.to_string(),
 			tree: StatusTree::default(),
+			items: Vec::new(),
+			filter: FileFilterComponent::new(
+				theme.clone(),
+				key_config.clone(),
+			),
 			current_hash: 0,
 			focused: focus,
 			show_selection: focus,
 			queue,
 			theme,
 			key_config,
-			scroll_top: Cell::new(0),
+			scroll: VerticalScroll::new(),
 			pending: true,
 			visible: false,
+			marked: Vec::new(),
 		}
 	}
 
@@ -66,13 +78,42 @@ impl StatusTreeComponent {
 		self.pending = false;
 		let new_hash = hash(list);
 		if self.current_hash != new_hash {
-			self.tree.update(list)?;
+			self.items = list.to_vec();
+			self.apply_filter()?;
 			self.current_hash = new_hash;
+			self.marked.retain(|path| {
+				list.iter().any(|item| &item.path == path)
+			});
 		}
 
 		Ok(())
 	}
 
+	/// builds the tree from the items matching the filter
+	fn apply_filter(&mut self) -> Result<()> {
+		if self.filter.is_filtering() {
+			let items = self
+				.items
+				.iter()
+				.filter(|item| self.filter.matches(&item.path))
+				.cloned()
+				.collect::<Vec<_>>();
+
+			self.tree.update(&items)
+		} else {
+			self.tree.update(&self.items)
+		}
+	}
+
+	/// list the files with their full path instead of the folder
+	/// tree, the items are expected grouped differently then
+	pub fn set_flat(&mut self, flat: bool) {
+		if self.tree.flat != flat {
+			self.tree.flat = flat;
+			self.current_hash = 0;
+		}
+	}
+
 	///
 	pub fn selection(&self) -> Option<FileTreeItem> {
 		self.tree.selected_item()
@@ -89,6 +130,95 @@ impl StatusTreeComponent {
 		})
 	}
 
+	/// marks or unmarks the selected file, or all files in the
+	/// selected folder
+	pub fn toggle_mark(&mut self) -> bool {
+		let files = self.selected_files();
+
+		if files.is_empty() {
+			return false;
+		}
+
+		if files.iter().all(|file| self.marked.contains(file)) {
+			self.marked.retain(|marked| !files.contains(marked));
+		} else {
+			for file in files {
+				if !self.marked.contains(&file) {
+					self.marked.push(file);
+				}
+			}
+		}
+
+		true
+	}
+
+	/// the marked files in tree order
+	pub fn marked(&self) -> Vec<StatusItem> {
+		self.tree
+			.tree
+			.items()
+			.iter()
+			.filter_map(|item| match &item.kind {
+				FileTreeItemKind::File(file)
+					if self.marked.contains(&file.path) =>
+				{
+					Some(file.clone())
+				}
+				_ => None,
+			})
+			.collect()
+	}
+
+	///
+	pub fn selection_marked(&self) -> bool {
+		let files = self.selected_files();
+
+		!files.is_empty()
+			&& files.iter().all(|file| self.marked.contains(file))
+	}
+
+	///
+	pub fn clear_marked(&mut self) {
+		self.marked.clear();
+	}
+
+	/// paths of the selected file or of the files in the selected
+	/// folder
+	fn selected_files(&self) -> Vec<String> {
+		self.tree.selected_item().map_or_else(Vec::new, |item| {
+			match item.kind {
+				FileTreeItemKind::File(file) => vec![file.path],
+				FileTreeItemKind::Path(_) => {
+					let folder = format!("{}/", item.info.full_path);
+					self.tree
+						.tree
+						.items()
+						.iter()
+						.filter_map(|item| match &item.kind {
+							FileTreeItemKind::File(file)
+								if file.path.starts_with(&folder) =>
+							{
+								Some(file.path.clone())
+							}
+							_ => None,
+						})
+						.collect()
+				}
+			}
+		})
+	}
+
+	/// `None` if nothing is marked, folders are never marked
+	fn is_marked(&self, kind: &FileTreeItemKind) -> Option<bool> {
+		if self.marked.is_empty() {
+			None
+		} else if let FileTreeItemKind::File(file) = kind {
+			Some(self.marked.contains(&file.path))
+		} else {
+			Some(false)
+		}
+	}
+
 	///
 	pub fn show_selection(&mut self, show: bool) {
 		self.show_selection = show;
@@ -104,6 +234,12 @@ impl StatusTreeComponent {
 		self.tree.tree.file_count()
 	}
 
+	///
+	pub fn collapse_all(&mut self) {
+		self.tree.collapse_all();
+		self.scroll.reset();
+	}
+
 	///
 	pub fn set_title(&mut self, title: String) {
 		self.title = title;
@@ -113,6 +249,7 @@ impl StatusTreeComponent {
 	pub fn clear(&mut self) -> Result<()> {
 		self.current_hash = 0;
 		self.pending = true;
+		self.items.clear();
 		self.tree.update(&[])
 	}
 
@@ -152,10 +289,9 @@ impl StatusTreeComponent {
 	fn item_to_text<'b>(
 		string: &str,
 		indent: usize,
-		visible: bool,
 		file_item_kind: &FileTreeItemKind,
 		width: u16,
-		selected: bool,
+		flags: ItemDrawFlags,
 		theme: &'b SharedTheme,
 	) -> Option<Span<'b>> {
 		let indent_str = if indent == 0 {
@@ -164,34 +300,40 @@ impl StatusTreeComponent {
 			format!("{:w$}", " ", w = (indent as usize) * 2)
 		};
 
-		if !visible {
+		if !flags.visible {
 			return None;
 		}
 
+		let mark_prefix = match flags.marked {
+			Some(true) => format!("{} ", symbol::CHECKMARK),
+			Some(false) => String::from("  "),
+			None => String::new(),
+		};
+
 		match file_item_kind {
 			FileTreeItemKind::File(status_item) => {
 				let status_char =
 					Self::item_status_char(status_item.status);
-				let file = Path::new(&status_item.path)
-					.file_name()
-					.and_then(std::ffi::OsStr::to_str)
-					.expect("invalid path.");
+				let file = bidi_visual_order(string);
 
-				let txt = if selected {
+				let txt = if flags.selected {
 					format!(
-						"{} {}{:w$}",
+						"{}{} {}{}",
+						mark_prefix,
 						status_char,
 						indent_str,
-						file,
-						w = width as usize
+						pad_to_width(&file, width as usize)
 					)
 				} else {
-					format!("{} {}{}", status_char, indent_str, file)
+					format!(
+						"{}{} {}{}",
+						mark_prefix, status_char, indent_str, file
+					)
 				};
 
 				Some(Span::styled(
 					Cow::from(txt),
-					theme.item(status_item.status, selected),
+					theme.item(status_item.status, flags.selected),
 				))
 			}
 
@@ -199,24 +341,28 @@ impl StatusTreeComponent {
 				let collapse_char =
 					if path_collapsed.0 { '▸' } else { '▾' };
 
-				let txt = if selected {
+				let string = bidi_visual_order(string);
+				let txt = if flags.selected {
 					format!(
-						"  {}{}{:w$}",
+						"{}  {}{}{}",
+						mark_prefix,
 						indent_str,
 						collapse_char,
-						string,
-						w = width as usize
+						pad_to_width(&string, width as usize)
 					)
 				} else {
 					format!(
-						"  {}{}{}",
-						indent_str, collapse_char, string,
+						"{}  {}{}{}",
+						mark_prefix,
+						indent_str,
+						collapse_char,
+						string,
 					)
 				};
 
 				Some(Span::styled(
 					Cow::from(txt),
-					theme.text(true, selected),
+					theme.text(true, flags.selected),
 				))
 			}
 		}
@@ -308,6 +454,15 @@ struct TextDrawInfo<'a> {
 	item_kind: &'a FileTreeItemKind,
 }
 
+/// how `StatusTreeComponent::item_to_text` draws an item
+#[derive(Clone, Copy)]
+struct ItemDrawFlags {
+	visible: bool,
+	selected: bool,
+	/// `None` unless items can get marked
+	marked: Option<bool>,
+}
+
 impl DrawableComponent for StatusTreeComponent {
 	fn draw<B: Backend>(
 		&self,
@@ -346,11 +501,16 @@ impl DrawableComponent for StatusTreeComponent {
 				.unwrap_or_default();
 			let tree_height = r.height.saturating_sub(2) as usize;
 
-			self.scroll_top.set(ui::calc_scroll_top(
-				self.scroll_top.get(),
-				tree_height,
+			let visible_count = vec_draw_text_info
+				.iter()
+				.filter(|item| item.visible)
+				.count();
+
+			let scroll_top = self.scroll.update(
 				select.saturating_sub(selection_offset_visible),
-			));
+				visible_count,
+				tree_height,
+			);
 
 			let items = vec_draw_text_info
 				.iter()
@@ -359,22 +519,29 @@ impl DrawableComponent for StatusTreeComponent {
 					Self::item_to_text(
 						&draw_text_info.name,
 						draw_text_info.indent as usize,
-						draw_text_info.visible,
 						draw_text_info.item_kind,
 						r.width,
-						self.show_selection && select == index,
+						ItemDrawFlags {
+							visible: draw_text_info.visible,
+							selected: self.show_selection
+								&& select == index,
+							marked: self
+								.is_marked(draw_text_info.item_kind),
+						},
 						&self.theme,
 					)
 				})
-				.skip(self.scroll_top.get());
+				.skip(scroll_top);
 			ui::draw_list(
 				f,
 				r,
-				self.title.as_str(),
+				self.filter.title(&self.title).as_str(),
 				items,
 				self.focused,
 				&self.theme,
 			);
+
+			self.filter.draw_in_list(f, r)?;
 		}
 
 		Ok(())
@@ -414,11 +581,29 @@ impl Component for StatusTreeComponent {
 			.order(order::RARE_ACTION),
 		);
 
+		if self.focused || force_all {
+			self.filter.commands(out, force_all);
+		}
+
 		CommandBlocking::PassingOn
 	}
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.focused {
+			let query = self.filter.query().to_string();
+			if self.filter.event(ev)?.is_consumed() {
+				if self.filter.query() != query {
+					self.apply_filter()?;
+					if let Some(ref queue) = self.queue {
+						queue.push(InternalEvent::Update(
+							NeedsUpdate::DIFF,
+						));
+					}
+				}
+
+				return Ok(EventState::Consumed);
+			}
+
 			if let Event::Key(e) = ev {
 				return if key_match(e, self.key_config.keys.blame) {
 					if let Some(status_item) = self.selection_file() {
@@ -567,7 +752,7 @@ mod tests {
 		ftc.draw(&mut frame, Rect::new(0, 0, 10, 5))
 			.expect("Draw failed");
 
-		assert_eq!(ftc.scroll_top.get(), 0); // should still be at top
+		assert_eq!(ftc.scroll.get_top(), 0); // should still be at top
 	}
 
 	#[test]
@@ -607,6 +792,43 @@ mod tests {
 		ftc.draw(&mut frame, Rect::new(0, 0, 10, 5))
 			.expect("Draw failed");
 
-		assert_eq!(ftc.scroll_top.get(), 0); // should still be at top
+		assert_eq!(ftc.scroll.get_top(), 0); // should still be at top
+	}
+
+	#[test]
+	fn test_mark_folder() {
+		let items = string_vec_to_status(&[
+			"a/b/b1", //
+			"a/b/b2", //
+			"a/c/c1", //
+		]);
+
+		let mut ftc = StatusTreeComponent::new(
+			"title",
+			true,
+			None,
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+		ftc.update(&items)
+			.expect("Updating FileTreeComponent failed");
+
+		ftc.move_selection(MoveSelection::Down); // Move to b/
+		ftc.move_selection(MoveSelection::Down); // Move to b1
+		assert!(ftc.toggle_mark());
+		ftc.move_selection(MoveSelection::Up); // Move to b/
+		assert!(!ftc.selection_marked());
+
+		// marks the rest of the folder first, then unmarks it
+		ftc.toggle_mark();
+		assert!(ftc.selection_marked());
+		assert_eq!(ftc.marked(), items[..2].to_vec());
+		ftc.toggle_mark();
+		assert!(ftc.marked().is_empty());
+
+		// marks of files that are gone get dropped
+		ftc.toggle_mark();
+		ftc.update(&items[1..]).expect("Updating failed");
+		assert_eq!(ftc.marked(), items[1..2].to_vec());
 	}
 }
@@ -1,11 +1,12 @@
 use super::{
 	utils::scroll_vertical::VerticalScroll, visibility_blocking,
 	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState, ScrollType,
+	EventState, ExternalTool, ScrollType,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, NeedsUpdate, Queue},
+	string_utils::fit_to_width,
 	strings, try_or_popup,
 	ui::{self, Size},
 };
@@ -27,7 +28,6 @@ use tui::{
 	Frame,
 };
 use ui::style::SharedTheme;
-use unicode_truncate::UnicodeTruncateStr;
 
 ///
 pub struct SubmodulesListComponent {
@@ -137,6 +137,12 @@ impl Component for SubmodulesListComponent {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::open_shell(&self.key_config),
+				self.is_valid_selection(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::open_submodule_parent(
 					&self.key_config,
@@ -186,6 +192,14 @@ impl Component for SubmodulesListComponent {
 						path: submodule.path.clone(),
 					});
 				}
+			} else if key_match(e, self.key_config.keys.open_shell) {
+				if let Some(submodule) = self.selected_entry() {
+					self.queue.push(InternalEvent::OpenExternalTool(
+						ExternalTool::Shell {
+							path: submodule.path.clone(),
+						},
+					));
+				}
 			} else if key_match(
 				e,
 				self.key_config.keys.update_submodule,
@@ -341,15 +355,12 @@ impl SubmodulesListComponent {
 		width_available: u16,
 		height: usize,
 	) -> Text {
-		const THREE_DOTS: &str = "...";
-		const THREE_DOTS_LENGTH: usize = THREE_DOTS.len(); // "..."
 		const COMMIT_HASH_LENGTH: usize = 8;
 
 		let mut txt = Vec::with_capacity(3);
 
 		let name_length: usize = (width_available as usize)
-			.saturating_sub(COMMIT_HASH_LENGTH)
-			.saturating_sub(THREE_DOTS_LENGTH);
+			.saturating_sub(COMMIT_HASH_LENGTH);
 
 		for (i, submodule) in self
 			.submodules
@@ -358,18 +369,10 @@ impl SubmodulesListComponent {
 			.take(height)
 			.enumerate()
 		{
-			let mut module_path = submodule
-				.path
-				.as_os_str()
-				.to_string_lossy()
-				.to_string();
-
-			if module_path.len() > name_length {
-				module_path.unicode_truncate(
-					name_length.saturating_sub(THREE_DOTS_LENGTH),
-				);
-				module_path += THREE_DOTS;
-			}
+			let module_path = fit_to_width(
+				&submodule.path.as_os_str().to_string_lossy(),
+				name_length,
+			);
 
 			let selected = (self.selection as usize
 				- self.scroll.get_top())
@@ -387,7 +390,7 @@ impl SubmodulesListComponent {
 			);
 
 			let span_name = Span::styled(
-				format!("{:w$} ", module_path, w = name_length),
+				format!("{} ", module_path),
 				theme.text(true, selected),
 			);
 
@@ -1,9 +1,9 @@
 use super::{
 	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState,
+	EventState, SharedOptions,
 };
 use crate::{
-	keys::SharedKeyConfig,
+	keys::{key_match, SharedKeyConfig},
 	string_utils::tabs_to_spaces,
 	strings,
 	ui::{
@@ -15,7 +15,11 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
-	sync::{self, RepoPathRef, TreeFile},
+	sync::{
+		self,
+		lfs::{lfs_available, lfs_smudge, LfsPointer},
+		RepoPathRef, TreeFile,
+	},
 	ProgressPercent,
 };
 use crossbeam_channel::Sender;
@@ -34,12 +38,17 @@ use tui::{
 pub struct SyntaxTextComponent {
 	repo: RepoPathRef,
 	current_file: Option<(String, Either<ui::SyntaxText, String>)>,
+	/// the current file is a git lfs pointer not previewed yet
+	lfs: Option<LfsPointer>,
+	/// the current file matches a secret pattern and is not shown
+	masked: Option<TreeFile>,
 	async_highlighting: AsyncSingleJob<AsyncSyntaxJob>,
 	syntax_progress: Option<ProgressPercent>,
 	key_config: SharedKeyConfig,
 	paragraph_state: Cell<ParagraphState>,
 	focused: bool,
 	theme: SharedTheme,
+	options: SharedOptions,
 }
 
 impl SyntaxTextComponent {
@@ -49,16 +58,20 @@ impl SyntaxTextComponent {
 		sender: &Sender<AsyncAppNotification>,
 		key_config: SharedKeyConfig,
 		theme: SharedTheme,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			async_highlighting: AsyncSingleJob::new(sender.clone()),
 			syntax_progress: None,
 			current_file: None,
+			lfs: None,
+			masked: None,
 			paragraph_state: Cell::new(ParagraphState::default()),
 			focused: false,
 			key_config,
 			theme,
 			repo,
+			options,
 		}
 	}
 
@@ -98,9 +111,15 @@ impl SyntaxTextComponent {
 		self.async_highlighting.is_pending()
 	}
 
+	/// 0-based index of the topmost visible line
+	pub fn top_line(&self) -> usize {
+		usize::from(self.paragraph_state.get().scroll().y)
+	}
+
 	///
 	pub fn clear(&mut self) {
 		self.current_file = None;
+		self.lfs = None;
 	}
 
 	///
@@ -112,32 +131,126 @@ impl SyntaxTextComponent {
 			.unwrap_or_default();
 
 		if !already_loaded {
-			//TODO: fetch file content async aswell
-			match sync::tree_file_content(&self.repo.borrow(), item) {
-				Ok(content) => {
-					let content = tabs_to_spaces(content);
-					self.syntax_progress =
-						Some(ProgressPercent::empty());
-					self.async_highlighting.spawn(
-						AsyncSyntaxJob::new(
-							content.clone(),
-							path.clone(),
-						),
-					);
-
-					self.current_file =
-						Some((path, Either::Right(content)));
-				}
-				Err(e) => {
+			self.lfs = None;
+			self.masked = None;
+
+			let pattern = self
+				.options
+				.borrow()
+				.secrets
+				.matching(&path)
+				.map(ToString::to_string);
+
+			if let Some(pattern) = pattern {
+				self.current_file = Some((
+					path,
+					Either::Right(strings::masked_content(
+						&pattern,
+						&self.key_config,
+					)),
+				));
+				self.masked = Some(item.clone());
+			} else {
+				self.load_content(path, item);
+			}
+		}
+	}
+
+	fn load_content(&mut self, path: String, item: &TreeFile) {
+		//TODO: fetch file content async aswell
+		let content =
+			sync::tree_file_content(&self.repo.borrow(), item);
+		match content {
+			Ok(content) => {
+				if let Some(pointer) =
+					LfsPointer::parse(content.as_bytes())
+				{
 					self.current_file = Some((
 						path,
-						Either::Right(format!(
-							"error loading file: {}",
-							e
+						Either::Right(strings::lfs_file(
+							&pointer,
+							&self.key_config,
 						)),
 					));
+					self.lfs = Some(pointer);
+				} else {
+					self.set_content(path, content);
 				}
 			}
+			Err(e) => {
+				self.current_file = Some((
+					path,
+					Either::Right(format!(
+						"error loading file: {}",
+						e
+					)),
+				));
+			}
+		}
+	}
+
+	/// shows the masked file after all
+	fn reveal(&mut self) {
+		if let Some(item) = self.masked.take() {
+			let path = self
+				.current_file
+				.as_ref()
+				.map(|(path, _)| path.clone())
+				.unwrap_or_default();
+
+			self.load_content(path, &item);
+		}
+	}
+
+	fn set_content(&mut self, path: String, content: String) {
+		let content = tabs_to_spaces(content);
+		self.syntax_progress = Some(ProgressPercent::empty());
+		self.async_highlighting.spawn(AsyncSyntaxJob::new(
+			content.clone(),
+			path.clone(),
+		));
+
+		self.current_file = Some((path, Either::Right(content)));
+	}
+
+	/// replaces the pointer text with the actual content
+	fn preview_lfs(&mut self) {
+		let pointer = match self.lfs.take() {
+			Some(pointer) => pointer,
+			None => return,
+		};
+		let path = self
+			.current_file
+			.as_ref()
+			.map(|(path, _)| path.clone())
+			.unwrap_or_default();
+
+		let content = lfs_smudge(&self.repo.borrow(), &pointer);
+		match content {
+			Ok(content) if !content.contains(&0) => {
+				self.set_content(
+					path,
+					String::from_utf8_lossy(&content).to_string(),
+				);
+			}
+			Ok(_) => {
+				self.current_file = Some((
+					path,
+					Either::Right(format!(
+						"{}\n\nbinary content",
+						strings::lfs_object(&pointer)
+					)),
+				));
+			}
+			Err(e) => {
+				self.current_file = Some((
+					path,
+					Either::Right(format!(
+						"error loading lfs object: {}",
+						e
+					)),
+				));
+			}
 		}
 	}
 
@@ -197,7 +310,9 @@ impl DrawableComponent for SyntaxTextComponent {
 		let text = self.current_file.as_ref().map_or_else(
 			|| Text::from(""),
 			|(_, content)| match content {
-				Either::Left(syn) => syn.into(),
+				Either::Left(syn) => {
+					self.theme.colored_text(syn.into())
+				}
 				Either::Right(s) => Text::from(s.as_str()),
 			},
 		);
@@ -262,6 +377,20 @@ impl Component for SyntaxTextComponent {
 				.order(strings::order::NAV),
 			);
 		}
+		if self.masked.is_some() || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::reveal_masked(&self.key_config),
+				true,
+				self.focused() || force_all,
+			));
+		}
+		if self.lfs.is_some() || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::lfs_preview(&self.key_config),
+				lfs_available(),
+				self.focused() || force_all,
+			));
+		}
 		CommandBlocking::PassingOn
 	}
 
@@ -270,6 +399,21 @@ impl Component for SyntaxTextComponent {
 		event: &crossterm::event::Event,
 	) -> Result<EventState> {
 		if let Event::Key(key) = event {
+			if key_match(key, self.key_config.keys.reveal_masked)
+				&& self.masked.is_some()
+			{
+				self.reveal();
+				return Ok(EventState::Consumed);
+			}
+
+			if key_match(key, self.key_config.keys.lfs_preview)
+				&& self.lfs.is_some()
+				&& lfs_available()
+			{
+				self.preview_lfs();
+				return Ok(EventState::Consumed);
+			}
+
 			if let Some(nav) = common_nav(key, &self.key_config) {
 				return Ok(self
 					.scroll(nav)
@@ -1,7 +1,7 @@
 use super::{
-	textinput::TextInputComponent, visibility_blocking,
-	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState,
+	textinput::{TextInputComponent, Validation},
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
@@ -86,6 +86,7 @@ impl Component for TagCommitComponent {
 					let tag_name: String =
 						self.input.get_text().into();
 
+					self.input.set_validator(None);
 					self.input.clear();
 					self.input.set_title(
 						strings::tag_popup_annotation_title(
@@ -114,6 +115,7 @@ impl Component for TagCommitComponent {
 
 	fn show(&mut self) -> Result<()> {
 		self.mode = Mode::Name;
+		self.set_name_validator();
 		self.input.set_title(strings::tag_popup_name_title());
 		self.input.set_default_msg(strings::tag_popup_name_msg());
 		self.input.show()?;
@@ -155,7 +157,23 @@ impl TagCommitComponent {
 	}
 
 	fn is_valid_tag(&self) -> bool {
-		!self.input.get_text().is_empty()
+		!self.input.get_text().is_empty() && self.input.is_valid()
+	}
+
+	fn set_name_validator(&mut self) {
+		let repo = self.repo.clone();
+		self.input.set_validator(Some(Box::new(move |name| {
+			if !name.is_empty()
+				&& sync::tag_exists(&repo.borrow(), name)
+					.unwrap_or_default()
+			{
+				Validation::Error(
+					strings::TAG_NAME_EXISTS.to_string(),
+				)
+			} else {
+				Validation::Ok
+			}
+		})));
 	}
 
 	fn tag_info(&self) -> (String, Option<String>) {
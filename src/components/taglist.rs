@@ -25,7 +25,7 @@ use asyncgit::{
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use std::convert::TryInto;
+use std::{cell::RefCell, collections::HashMap, convert::TryInto};
 use tui::{
 	backend::Backend,
 	layout::{Constraint, Margin, Rect},
@@ -44,6 +44,8 @@ pub struct TagListComponent {
 	theme: SharedTheme,
 	queue: Queue,
 	tags: Option<Vec<TagWithMetadata>>,
+	/// annotations by tag name, loaded lazily for visible tags only
+	annotations: RefCell<HashMap<String, Option<String>>>,
 	visible: bool,
 	table_state: std::cell::Cell<TableState>,
 	current_height: std::cell::Cell<usize>,
@@ -302,6 +304,7 @@ impl TagListComponent {
 			theme,
 			queue: queue.clone(),
 			tags: None,
+			annotations: RefCell::new(HashMap::new()),
 			visible: false,
 			has_remotes: false,
 			table_state: std::cell::Cell::new(TableState::default()),
@@ -379,6 +382,7 @@ impl TagListComponent {
 		let tags = get_tags_with_metadata(&self.repo.borrow())?;
 
 		self.tags = Some(tags);
+		self.annotations.get_mut().clear();
 
 		Ok(())
 	}
@@ -426,30 +430,67 @@ impl TagListComponent {
 	}
 
 	fn show_annotation(&self) {
-		if let Some(tag) = self.selected_tag() {
-			if let Some(annotation) = &tag.annotation {
-				self.queue.push(InternalEvent::ShowInfoMsg(
-					annotation.clone(),
-				));
-			}
+		if let Some(annotation) =
+			self.selected_tag().and_then(|tag| self.annotation(tag))
+		{
+			self.queue.push(InternalEvent::ShowInfoMsg(annotation));
 		}
 	}
 
 	fn can_show_annotation(&self) -> bool {
 		self.selected_tag()
-			.and_then(|t| t.annotation.as_ref())
+			.and_then(|tag| self.annotation(tag))
 			.is_some()
 	}
 
+	/// loads the annotation of `tag` on first use
+	fn annotation(&self, tag: &TagWithMetadata) -> Option<String> {
+		self.annotations
+			.borrow_mut()
+			.entry(tag.name.clone())
+			.or_insert_with(|| {
+				sync::get_tag_annotation(
+					&self.repo.borrow(),
+					&tag.name,
+				)
+				.ok()
+				.flatten()
+			})
+			.clone()
+	}
+
 	///
 	fn get_rows(&self) -> Vec<Row> {
+		// rows possibly on screen, the table scrolls
+		// just enough to keep the selection visible
+		let table_state = self.table_state.take();
+		let selected = table_state.selected().unwrap_or_default();
+		self.table_state.set(table_state);
+		let height = self.current_height.get();
+		let visible =
+			selected.saturating_sub(height)..=selected + height;
+
 		self.tags.as_ref().map_or_else(Vec::new, |tags| {
-			tags.iter().map(|tag| self.get_row(tag)).collect()
+			tags.iter()
+				.enumerate()
+				.map(|(idx, tag)| {
+					let annotated = if visible.contains(&idx) {
+						self.annotation(tag).is_some()
+					} else {
+						self.annotations
+							.borrow()
+							.get(&tag.name)
+							.map_or(false, Option::is_some)
+					};
+
+					self.get_row(tag, annotated)
+				})
+				.collect()
 		})
 	}
 
 	///
-	fn get_row(&self, tag: &TagWithMetadata) -> Row {
+	fn get_row(&self, tag: &TagWithMetadata, annotated: bool) -> Row {
 		const UPSTREAM_SYMBOL: &str = "\u{2191}";
 		const ATTACHEMENT_SYMBOL: &str = "@";
 		const EMPTY_SYMBOL: &str = " ";
@@ -469,7 +510,7 @@ impl TagListComponent {
 			EMPTY_SYMBOL
 		};
 
-		let has_attachement_str = if tag.annotation.is_some() {
+		let has_attachement_str = if annotated {
 			ATTACHEMENT_SYMBOL
 		} else {
 			EMPTY_SYMBOL
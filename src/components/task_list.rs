@@ -0,0 +1,199 @@
+use super::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	strings, ui,
+};
+use anyhow::Result;
+use asyncgit::tasks::{self, TaskInfo};
+use crossterm::event::Event;
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+/// popup listing the background jobs asyncgit is running
+pub struct TaskListComponent {
+	tasks: Vec<TaskInfo>,
+	selection: usize,
+	visible: bool,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl TaskListComponent {
+	///
+	pub const fn new(
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			tasks: Vec::new(),
+			selection: 0,
+			visible: false,
+			theme,
+			key_config,
+		}
+	}
+
+	/// re-reads the task registry, returns `true` if a redraw is needed
+	pub fn update(&mut self) -> bool {
+		if !self.visible {
+			return false;
+		}
+
+		self.tasks = tasks::running();
+		self.selection =
+			self.selection.min(self.tasks.len().saturating_sub(1));
+
+		true
+	}
+
+	fn cancel_selected(&mut self) {
+		if let Some(task) = self.tasks.get(self.selection) {
+			tasks::cancel(task.id);
+		}
+		self.update();
+	}
+
+	fn get_text(&self) -> Vec<Spans<'_>> {
+		self.tasks
+			.iter()
+			.enumerate()
+			.map(|(idx, task)| {
+				let selected = idx == self.selection;
+				let state = if task.is_cancelled() {
+					strings::TASKS_CANCELLING
+				} else {
+					""
+				};
+
+				Spans::from(vec![
+					Span::styled(
+						format!("{:<8}", task.kind),
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						format!(
+							"{:>5.1}s ",
+							task.runtime().as_secs_f32()
+						),
+						self.theme.commit_time(selected),
+					),
+					Span::styled(
+						format!("{}{}", task.params, state),
+						self.theme.text(true, selected),
+					),
+				])
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for TaskListComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		_rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect_absolute(65, 15, f.size());
+
+			let text = if self.tasks.is_empty() {
+				vec![Spans::from(strings::TASKS_EMPTY)]
+			} else {
+				self.get_text()
+			};
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(text).block(
+					Block::default()
+						.title(strings::tasks_title(&self.key_config))
+						.borders(Borders::ALL)
+						.border_type(BorderType::Thick),
+				),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for TaskListComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::cancel_task(&self.key_config),
+				!self.tasks.is_empty(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					self.selection = self
+						.selection
+						.saturating_add(1)
+						.min(self.tasks.len().saturating_sub(1));
+				} else if key_match(e, self.key_config.keys.move_up) {
+					self.selection = self.selection.saturating_sub(1);
+				} else if key_match(
+					e,
+					self.key_config.keys.cancel_task,
+				) {
+					self.cancel_selected();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.selection = 0;
+		self.visible = true;
+		self.update();
+
+		Ok(())
+	}
+}
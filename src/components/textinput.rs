@@ -3,15 +3,19 @@ use crate::strings::symbol;
 use crate::ui::Size;
 use crate::{
 	components::{
-		popup_paragraph, visibility_blocking, CommandBlocking,
-		CommandInfo, Component, DrawableComponent, EventState,
+		completion::{CompletionPopup, CompletionProvider},
+		popup_paragraph,
+		utils::text_buffer::TextBuffer,
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
 	},
 	keys::SharedKeyConfig,
 	strings,
 	ui::{self, style::SharedTheme},
+	AsyncNotification,
 };
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode, KeyModifiers};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use itertools::Itertools;
 use std::{cell::Cell, collections::HashMap, ops::Range};
 use tui::{
@@ -19,9 +23,11 @@ use tui::{
 	layout::{Alignment, Rect},
 	style::Modifier,
 	text::{Spans, Text},
-	widgets::{Clear, Paragraph},
+	widgets::{Block, Borders, Clear, Paragraph},
 	Frame,
 };
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(PartialEq, Eq)]
 pub enum InputType {
@@ -30,19 +36,37 @@ pub enum InputType {
 	Password,
 }
 
+/// result of validating the input text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Validation {
+	Ok,
+	Warning(String),
+	Error(String),
+}
+
+///
+pub type Validator = Box<dyn Fn(&str) -> Validation>;
+
 /// primarily a subcomponet for user input of text (used in `CommitComponent`)
 pub struct TextInputComponent {
 	title: String,
 	default_msg: String,
-	msg: String,
+	buffer: TextBuffer,
+	/// first line shown, keeps the cursor line visible
+	scroll_top: Cell<usize>,
+	/// first column shown of a single line input
+	scroll_left: Cell<usize>,
 	visible: bool,
 	show_char_count: bool,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
-	cursor_position: usize,
 	input_type: InputType,
 	current_area: Cell<Rect>,
 	embed: bool,
+	borders: Borders,
+	completion: Option<CompletionPopup>,
+	validator: Option<Validator>,
+	validation: Validation,
 }
 
 impl TextInputComponent {
@@ -55,17 +79,22 @@ impl TextInputComponent {
 		show_char_count: bool,
 	) -> Self {
 		Self {
-			msg: String::new(),
+			buffer: TextBuffer::default(),
+			scroll_top: Cell::new(0),
+			scroll_left: Cell::new(0),
 			visible: false,
 			theme,
 			key_config,
 			show_char_count,
 			title: title.to_string(),
 			default_msg: default_msg.to_string(),
-			cursor_position: 0,
 			input_type: InputType::Multiline,
 			current_area: Cell::new(Rect::default()),
 			embed: false,
+			borders: Borders::ALL,
+			completion: None,
+			validator: None,
+			validation: Validation::Ok,
 		}
 	}
 
@@ -77,15 +106,74 @@ impl TextInputComponent {
 		self
 	}
 
+	/// offer completions from `provider` while typing
+	pub fn with_completion(
+		mut self,
+		provider: Box<dyn CompletionProvider>,
+	) -> Self {
+		self.completion = Some(CompletionPopup::new(provider));
+		self
+	}
+
+	/// check the text with `validator` whenever it changes
+	pub fn with_validator(
+		mut self,
+		validator: impl Fn(&str) -> Validation + 'static,
+	) -> Self {
+		self.set_validator(Some(Box::new(validator)));
+		self
+	}
+
+	///
+	pub fn set_validator(&mut self, validator: Option<Validator>) {
+		self.validator = validator;
+		self.validate();
+	}
+
+	/// `false` if the validator reported an error
+	pub const fn is_valid(&self) -> bool {
+		!matches!(self.validation, Validation::Error(_))
+	}
+
+	fn validate(&mut self) {
+		self.validation = self
+			.validator
+			.as_ref()
+			.map_or(Validation::Ok, |validator| {
+				validator(self.buffer.text())
+			});
+	}
+
+	/// feeds async results to the completion provider
+	pub fn update_completion(&mut self, ev: AsyncNotification) {
+		if let Some(completion) = &mut self.completion {
+			completion.update(ev, self.buffer.before_cursor());
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.completion
+			.as_ref()
+			.map_or(false, CompletionPopup::any_work_pending)
+	}
+
 	/// Clear the `msg`.
 	pub fn clear(&mut self) {
-		self.msg.clear();
-		self.cursor_position = 0;
+		self.buffer.clear();
+		self.validate();
 	}
 
 	/// Get the `msg`.
 	pub fn get_text(&self) -> &str {
-		self.msg.as_str()
+		self.buffer.text()
+	}
+
+	/// replaces the `len` bytes left of the cursor with `text`
+	/// and moves the cursor behind the inserted text
+	fn replace_before_cursor(&mut self, len: usize, text: &str) {
+		self.buffer.replace_before_cursor(len, text);
+		self.validate();
 	}
 
 	/// screen area (last time we got drawn)
@@ -95,52 +183,22 @@ impl TextInputComponent {
 
 	/// embed into parent draw area
 	pub fn embed(&mut self) {
-		self.embed = true;
-	}
-
-	/// Move the cursor right one char.
-	fn incr_cursor(&mut self) {
-		if let Some(pos) = self.next_char_position() {
-			self.cursor_position = pos;
-		}
-	}
-
-	/// Move the cursor left one char.
-	fn decr_cursor(&mut self) {
-		let mut index = self.cursor_position.saturating_sub(1);
-		while index > 0 && !self.msg.is_char_boundary(index) {
-			index -= 1;
-		}
-		self.cursor_position = index;
+		self.embed_with_borders(Borders::NONE);
 	}
 
-	/// Get the position of the next char, or, if the cursor points
-	/// to the last char, the `msg.len()`.
-	/// Returns None when the cursor is already at `msg.len()`.
-	fn next_char_position(&self) -> Option<usize> {
-		if self.cursor_position >= self.msg.len() {
-			return None;
-		}
-		let mut index = self.cursor_position.saturating_add(1);
-		while index < self.msg.len()
-			&& !self.msg.is_char_boundary(index)
-		{
-			index += 1;
-		}
-		Some(index)
-	}
-
-	fn backspace(&mut self) {
-		if self.cursor_position > 0 {
-			self.decr_cursor();
-			self.msg.remove(self.cursor_position);
-		}
+	/// embed into parent draw area, drawing `borders`
+	/// (and the title if the top one is drawn) inside it
+	pub fn embed_with_borders(&mut self, borders: Borders) {
+		self.embed = true;
+		self.borders = borders;
 	}
 
 	/// Set the `msg`.
 	pub fn set_text(&mut self, msg: String) {
-		self.msg = msg;
-		self.cursor_position = 0;
+		self.buffer.set_text(msg);
+		self.scroll_top.set(0);
+		self.scroll_left.set(0);
+		self.validate();
 	}
 
 	/// Set the `title`.
@@ -153,15 +211,131 @@ impl TextInputComponent {
 		self.default_msg = v;
 	}
 
+	/// draws the validation message below `area`,
+	/// returns `area` extended by the message line
+	fn draw_validation<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		area: Rect,
+	) -> Rect {
+		let (msg, style) = match &self.validation {
+			Validation::Ok => return area,
+			Validation::Warning(msg) => {
+				(msg, self.theme.text(false, false))
+			}
+			Validation::Error(msg) => (msg, self.theme.text_danger()),
+		};
+
+		let rect = Rect::new(
+			area.x,
+			area.y.saturating_add(area.height),
+			area.width,
+			1,
+		)
+		.intersection(f.size());
+
+		f.render_widget(Clear, rect);
+		f.render_widget(
+			Paragraph::new(msg.as_str()).style(style),
+			rect,
+		);
+
+		area.union(rect)
+	}
+
+	/// keys navigating and accepting the completion popup,
+	/// returns `true` if consumed
+	fn completion_event(&mut self, e: &KeyEvent) -> bool {
+		let completion = match &mut self.completion {
+			Some(completion) if completion.is_visible() => completion,
+			_ => return false,
+		};
+
+		let accepted =
+			if key_match(e, self.key_config.keys.exit_popup) {
+				completion.hide();
+				return true;
+			} else if key_match(e, self.key_config.keys.move_up)
+				|| key_match(e, self.key_config.keys.move_down)
+			{
+				completion.move_selection(key_match(
+					e,
+					self.key_config.keys.move_up,
+				));
+				return true;
+			} else if key_match(
+				e,
+				self.key_config.keys.accept_completion,
+			) {
+				completion.accept(false)
+			} else if key_match(e, self.key_config.keys.enter) {
+				completion.accept(true)
+			} else {
+				return false;
+			};
+
+		match accepted {
+			Some((len, text)) => {
+				self.replace_before_cursor(len, &text);
+				true
+			}
+			None => {
+				key_match(e, self.key_config.keys.accept_completion)
+			}
+		}
+	}
+
+	/// text editing keys, returns `true` if consumed
+	fn edit_event(&mut self, e: &KeyEvent) -> bool {
+		let is_ctrl = e.modifiers.contains(KeyModifiers::CONTROL);
+
+		match e.code {
+			KeyCode::Char(c) if !is_ctrl => {
+				self.buffer.insert_char(c);
+			}
+			KeyCode::Delete => {
+				self.buffer.delete();
+			}
+			KeyCode::Backspace => {
+				self.buffer.backspace();
+			}
+			KeyCode::Left => {
+				self.buffer.move_left();
+			}
+			KeyCode::Right => {
+				self.buffer.move_right();
+			}
+			KeyCode::Up
+				if self.input_type == InputType::Multiline =>
+			{
+				self.buffer.move_up();
+			}
+			KeyCode::Down
+				if self.input_type == InputType::Multiline =>
+			{
+				self.buffer.move_down();
+			}
+			KeyCode::Home => {
+				self.buffer.move_home();
+			}
+			KeyCode::End => {
+				self.buffer.move_end();
+			}
+			_ => return false,
+		};
+
+		true
+	}
+
 	fn get_draw_text(&self) -> Text {
 		let style = self.theme.text(true, false);
 
 		let mut txt = Text::default();
 		// The portion of the text before the cursor is added
 		// if the cursor is not at the first character.
-		if self.cursor_position > 0 {
-			let text_before_cursor =
-				self.get_msg(0..self.cursor_position);
+		let cursor = self.buffer.cursor();
+		if cursor > 0 {
+			let text_before_cursor = self.get_msg(0..cursor);
 			let ends_in_nl = text_before_cursor.ends_with('\n');
 			txt = text_append(
 				txt,
@@ -173,12 +347,14 @@ impl TextInputComponent {
 		}
 
 		let cursor_str = self
-			.next_char_position()
+			.buffer
+			.next_grapheme_end()
 			// if the cursor is at the end of the msg
 			// a whitespace is used to underline
-			.map_or(" ".to_owned(), |pos| {
-				self.get_msg(self.cursor_position..pos)
-			});
+			.map_or_else(
+				|| " ".to_owned(),
+				|pos| self.get_msg(cursor..pos),
+			);
 
 		let cursor_highlighting = {
 			let mut h = HashMap::with_capacity(2);
@@ -211,14 +387,12 @@ impl TextInputComponent {
 
 		// The final portion of the text is added if there are
 		// still remaining characters.
-		if let Some(pos) = self.next_char_position() {
-			if pos < self.msg.len() {
+		if let Some(pos) = self.buffer.next_grapheme_end() {
+			let len = self.buffer.text().len();
+			if pos < len {
 				txt = text_append(
 					txt,
-					Text::styled(
-						self.get_msg(pos..self.msg.len()),
-						style,
-					),
+					Text::styled(self.get_msg(pos..len), style),
 				);
 			}
 		}
@@ -226,15 +400,63 @@ impl TextInputComponent {
 		txt
 	}
 
+	/// moves `scroll_top` so the cursor line is visible in `height`
+	/// rows of `width` columns, long lines take up several rows when
+	/// soft-wrapped. returns the new top line
+	fn update_scroll_top(
+		&self,
+		height: usize,
+		width: usize,
+	) -> usize {
+		let (line, _) = self.buffer.cursor_line_col();
+		let width = width.max(1);
+		let rows = |idx: usize| {
+			let line_width = self.buffer.line(idx).width();
+			((line_width + width - 1) / width).max(1)
+		};
+
+		let mut top = self.scroll_top.get().min(line);
+		while top < line
+			&& (top..=line).map(rows).sum::<usize>() > height
+		{
+			top += 1;
+		}
+
+		self.scroll_top.set(top);
+		top
+	}
+
+	/// moves `scroll_left` so the cursor is one of the `width`
+	/// columns shown of a single line input, returns the new offset
+	fn update_scroll_left(&self, width: usize) -> usize {
+		let column = self.get_msg(0..self.buffer.cursor()).width();
+		let width = width.max(1);
+		let left = self.scroll_left.get();
+
+		let left = if column < left {
+			column
+		} else if column >= left + width {
+			column + 1 - width
+		} else {
+			left
+		};
+
+		self.scroll_left.set(left);
+		left
+	}
+
 	fn get_msg(&self, range: Range<usize>) -> String {
 		match self.input_type {
-			InputType::Password => range.map(|_| "*").join(""),
-			_ => self.msg[range].to_owned(),
+			InputType::Password => {
+				let text = &self.buffer.text()[range];
+				text.graphemes(true).map(|_| "*").join("")
+			}
+			_ => self.buffer.text()[range].to_owned(),
 		}
 	}
 
 	fn draw_char_count<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
-		let count = self.msg.len();
+		let count = self.buffer.text().len();
 		if count > 0 {
 			let w = Paragraph::new(format!("[{} chars]", count))
 				.alignment(Alignment::Right);
@@ -283,15 +505,6 @@ impl DrawableComponent for TextInputComponent {
 		rect: Rect,
 	) -> Result<()> {
 		if self.visible {
-			let txt = if self.msg.is_empty() {
-				Text::styled(
-					self.default_msg.as_str(),
-					self.theme.text(false, false),
-				)
-			} else {
-				self.get_draw_text()
-			};
-
 			let area = if self.embed {
 				rect
 			} else {
@@ -309,6 +522,33 @@ impl DrawableComponent for TextInputComponent {
 				}
 			};
 
+			let inner =
+				Block::default().borders(self.borders).inner(area);
+			let multiline = self.input_type == InputType::Multiline;
+
+			let (txt, scroll_x) = if self.buffer.is_empty() {
+				(
+					Text::styled(
+						self.default_msg.as_str(),
+						self.theme.text(false, false),
+					),
+					0,
+				)
+			} else if multiline {
+				let top = self.update_scroll_top(
+					inner.height.into(),
+					inner.width.into(),
+				);
+
+				let mut txt = self.get_draw_text();
+				txt.lines.drain(..top.min(txt.lines.len()));
+				(txt, 0)
+			} else {
+				let left =
+					self.update_scroll_left(inner.width.into());
+				(self.get_draw_text(), left)
+			};
+
 			f.render_widget(Clear, area);
 			f.render_widget(
 				popup_paragraph(
@@ -316,8 +556,10 @@ impl DrawableComponent for TextInputComponent {
 					txt,
 					&self.theme,
 					true,
-					!self.embed,
-				),
+					self.borders,
+					multiline,
+				)
+				.scroll((0, scroll_x.try_into().unwrap_or(u16::MAX))),
 				area,
 			);
 
@@ -325,6 +567,12 @@ impl DrawableComponent for TextInputComponent {
 				self.draw_char_count(f, area);
 			}
 
+			let anchor = self.draw_validation(f, area);
+
+			if let Some(completion) = &self.completion {
+				completion.draw(f, anchor, &self.theme);
+			}
+
 			self.current_area.set(area);
 		}
 
@@ -346,54 +594,38 @@ impl Component for TextInputComponent {
 			)
 			.order(1),
 		);
+		if let Some(completion) = &self.completion {
+			out.push(CommandInfo::new(
+				strings::commands::accept_completion(
+					&self.key_config,
+				),
+				true,
+				self.visible && completion.is_visible(),
+			));
+		}
 		visibility_blocking(self)
 	}
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
 			if let Event::Key(e) = ev {
+				if self.completion_event(e) {
+					return Ok(EventState::Consumed);
+				}
+
 				if key_match(e, self.key_config.keys.exit_popup) {
 					self.hide();
 					return Ok(EventState::Consumed);
 				}
 
-				let is_ctrl =
-					e.modifiers.contains(KeyModifiers::CONTROL);
-
-				match e.code {
-					KeyCode::Char(c) if !is_ctrl => {
-						self.msg.insert(self.cursor_position, c);
-						self.incr_cursor();
-						return Ok(EventState::Consumed);
-					}
-					KeyCode::Delete => {
-						if self.cursor_position < self.msg.len() {
-							self.msg.remove(self.cursor_position);
-						}
-						return Ok(EventState::Consumed);
-					}
-					KeyCode::Backspace => {
-						self.backspace();
-						return Ok(EventState::Consumed);
-					}
-					KeyCode::Left => {
-						self.decr_cursor();
-						return Ok(EventState::Consumed);
-					}
-					KeyCode::Right => {
-						self.incr_cursor();
-						return Ok(EventState::Consumed);
-					}
-					KeyCode::Home => {
-						self.cursor_position = 0;
-						return Ok(EventState::Consumed);
+				if self.edit_event(e) {
+					self.validate();
+					if let Some(completion) = &mut self.completion {
+						completion
+							.refresh(self.buffer.before_cursor());
 					}
-					KeyCode::End => {
-						self.cursor_position = self.msg.len();
-						return Ok(EventState::Consumed);
-					}
-					_ => (),
-				};
+					return Ok(EventState::Consumed);
+				}
 			}
 		}
 		Ok(EventState::NotConsumed)
@@ -404,10 +636,17 @@ impl Component for TextInputComponent {
 	}
 
 	fn hide(&mut self) {
+		if let Some(completion) = &mut self.completion {
+			completion.hide();
+		}
 		self.visible = false;
 	}
 
 	fn show(&mut self) -> Result<()> {
+		if let Some(completion) = &mut self.completion {
+			completion.reset();
+		}
+		self.validate();
 		self.visible = true;
 
 		Ok(())
@@ -431,13 +670,13 @@ mod tests {
 
 		comp.set_text(String::from("a\nb"));
 
-		assert_eq!(comp.cursor_position, 0);
+		assert_eq!(comp.buffer.cursor(), 0);
 
-		comp.incr_cursor();
-		assert_eq!(comp.cursor_position, 1);
+		comp.buffer.move_right();
+		assert_eq!(comp.buffer.cursor(), 1);
 
-		comp.decr_cursor();
-		assert_eq!(comp.cursor_position, 0);
+		comp.buffer.move_left();
+		assert_eq!(comp.buffer.cursor(), 0);
 	}
 
 	#[test]
@@ -480,7 +719,7 @@ mod tests {
 		let not_underlined = Style::default();
 
 		comp.set_text(String::from("a"));
-		comp.incr_cursor();
+		comp.buffer.move_right();
 
 		let txt = comp.get_draw_text();
 
@@ -516,7 +755,7 @@ mod tests {
 			.add_modifier(Modifier::UNDERLINED);
 
 		comp.set_text(String::from("a\nb"));
-		comp.incr_cursor();
+		comp.buffer.move_right();
 
 		let txt = comp.get_draw_text();
 
@@ -558,6 +797,140 @@ mod tests {
 		assert_eq!(get_text(&txt.lines[1].0[0]), Some("b"));
 	}
 
+	#[test]
+	fn test_replace_before_cursor() {
+		let mut comp = TextInputComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			"",
+			"",
+			false,
+		);
+
+		comp.set_text(String::from("fix #fo bar"));
+		comp.buffer.set_cursor_line_col(0, 7);
+
+		comp.replace_before_cursor(2, "42");
+
+		assert_eq!(comp.get_text(), "fix #42 bar");
+		assert_eq!(comp.buffer.cursor(), 7);
+	}
+
+	#[test]
+	fn test_grapheme_editing() {
+		let mut comp = TextInputComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			"",
+			"",
+			false,
+		);
+
+		// thumbs up with skin tone modifier, 'e' with combining accent
+		comp.set_text(String::from("a\u{1f44d}\u{1f3fd}e\u{301}b"));
+
+		comp.buffer.move_right();
+		comp.buffer.move_right();
+		assert_eq!(comp.buffer.cursor(), 9);
+		assert_eq!(comp.buffer.next_grapheme_end(), Some(12));
+
+		comp.buffer.backspace();
+		assert_eq!(comp.get_text(), "ae\u{301}b");
+		assert_eq!(comp.buffer.cursor(), 1);
+
+		comp.buffer.delete();
+		assert_eq!(comp.get_text(), "ab");
+
+		comp.input_type = InputType::Password;
+		comp.set_text(String::from("e\u{301}\u{1f44d}"));
+		assert_eq!(comp.get_msg(0..comp.get_text().len()), "**");
+	}
+
+	#[test]
+	fn test_scroll_long_lines() {
+		let mut comp = TextInputComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			"",
+			"",
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+
+		comp.set_text(String::from("0123456789"));
+		comp.buffer.move_end();
+		assert_eq!(comp.update_scroll_left(4), 7);
+
+		comp.buffer.move_home();
+		assert_eq!(comp.update_scroll_left(4), 0);
+
+		comp.input_type = InputType::Multiline;
+		comp.set_text(String::from("0123456789\nab\ncd"));
+		comp.buffer.move_down();
+		comp.buffer.move_down();
+		// the first line wraps into three rows of four
+		assert_eq!(comp.update_scroll_top(3, 4), 1);
+		assert_eq!(comp.update_scroll_top(5, 4), 1);
+
+		comp.buffer.move_up();
+		comp.buffer.move_up();
+		assert_eq!(comp.update_scroll_top(5, 4), 0);
+	}
+
+	#[test]
+	fn test_embed_with_borders() {
+		let mut comp = TextInputComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			"",
+			"",
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		comp.embed_with_borders(Borders::ALL);
+		comp.set_text(String::from("abc"));
+		comp.show().unwrap();
+
+		let mut terminal = tui::Terminal::new(
+			tui::backend::TestBackend::new(20, 10),
+		)
+		.unwrap();
+		let rect = Rect::new(2, 3, 8, 3);
+		terminal.draw(|f| comp.draw(f, rect).unwrap()).unwrap();
+
+		let buffer = terminal.backend().buffer();
+		assert_eq!(buffer.get(2, 3).symbol, "\u{250f}");
+		assert_eq!(buffer.get(3, 4).symbol, "a");
+		assert_eq!(buffer.get(1, 4).symbol, " ");
+		assert_eq!(comp.get_area(), rect);
+	}
+
+	#[test]
+	fn test_validation() {
+		let mut comp = TextInputComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			"",
+			"",
+			false,
+		)
+		.with_validator(|text| {
+			if text.contains(' ') {
+				Validation::Error(String::from("no spaces"))
+			} else {
+				Validation::Ok
+			}
+		});
+
+		assert!(comp.is_valid());
+
+		comp.set_text(String::from("a b"));
+		assert!(!comp.is_valid());
+
+		comp.clear();
+		assert!(comp.is_valid());
+	}
+
 	fn get_text<'a>(t: &'a Span) -> Option<&'a str> {
 		Some(&t.content)
 	}
@@ -0,0 +1,289 @@
+use super::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	strings, ui,
+};
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use crossterm::event::Event;
+use std::{
+	collections::VecDeque,
+	convert::TryFrom,
+	time::{Duration, Instant},
+};
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	style::Style,
+	text::{Span, Spans},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+use ui::style::SharedTheme;
+
+/// how long a toast stays on screen
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+/// how many toasts are shown at once
+const TOAST_MAX_VISIBLE: usize = 3;
+/// how many past notifications the log keeps
+const HISTORY_SIZE: usize = 100;
+
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastKind {
+	///
+	Success,
+	/// non-fatal problem that does not warrant a modal error
+	Warning,
+}
+
+struct Toast {
+	kind: ToastKind,
+	msg: String,
+	time: DateTime<Local>,
+	shown: Instant,
+}
+
+/// shows short lived notifications in the bottom right corner
+/// and doubles as popup to review past ones
+pub struct ToastComponent {
+	active: VecDeque<Toast>,
+	history: VecDeque<Toast>,
+	visible: bool,
+	scroll: u16,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ToastComponent {
+	///
+	pub fn new(
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			active: VecDeque::new(),
+			history: VecDeque::new(),
+			visible: false,
+			scroll: 0,
+			theme,
+			key_config,
+		}
+	}
+
+	///
+	pub fn push(&mut self, kind: ToastKind, msg: String) {
+		let toast = Toast {
+			kind,
+			msg,
+			time: Local::now(),
+			shown: Instant::now(),
+		};
+
+		self.active.push_back(Toast {
+			msg: toast.msg.clone(),
+			..toast
+		});
+		while self.active.len() > TOAST_MAX_VISIBLE {
+			self.active.pop_front();
+		}
+
+		self.history.push_front(toast);
+		self.history.truncate(HISTORY_SIZE);
+	}
+
+	/// drops toasts that timed out, returns `true` if a redraw is needed
+	pub fn expire(&mut self) -> bool {
+		let count = self.active.len();
+		self.active.retain(|t| t.shown.elapsed() < TOAST_DURATION);
+		count != self.active.len()
+	}
+
+	fn style(&self, kind: ToastKind) -> Style {
+		match kind {
+			ToastKind::Success => self.theme.text(true, false),
+			ToastKind::Warning => self.theme.text_danger(),
+		}
+	}
+
+	/// draws the active toasts stacked into the bottom right of `area`
+	pub fn draw_toasts<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		area: Rect,
+	) {
+		let mut bottom = area.bottom();
+
+		for toast in self.active.iter().rev() {
+			let width = u16::try_from(toast.msg.chars().count())
+				.unwrap_or(u16::MAX)
+				.saturating_add(4)
+				.min(area.width.saturating_sub(2));
+
+			if bottom < area.top() + 3 || width < 5 {
+				break;
+			}
+
+			let rect = Rect::new(
+				area.right().saturating_sub(width + 1),
+				bottom - 3,
+				width,
+				3,
+			);
+			bottom -= 3;
+
+			f.render_widget(Clear, rect);
+			f.render_widget(
+				Paragraph::new(toast.msg.as_str())
+					.style(self.style(toast.kind))
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.border_type(BorderType::Rounded)
+							.border_style(self.style(toast.kind)),
+					),
+				rect,
+			);
+		}
+	}
+
+	fn get_text(&self) -> Vec<Spans<'_>> {
+		self.history
+			.iter()
+			.map(|toast| {
+				Spans::from(vec![
+					Span::styled(
+						toast.time.format("%X ").to_string(),
+						self.theme.commit_time(false),
+					),
+					Span::styled(
+						toast.msg.as_str(),
+						self.style(toast.kind),
+					),
+				])
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for ToastComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		_rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect_absolute(65, 20, f.size());
+
+			let text = if self.history.is_empty() {
+				vec![Spans::from(strings::NOTIFICATIONS_EMPTY)]
+			} else {
+				self.get_text()
+			};
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(text)
+					.block(
+						Block::default()
+							.title(strings::notifications_title(
+								&self.key_config,
+							))
+							.borders(Borders::ALL)
+							.border_type(BorderType::Thick),
+					)
+					.wrap(Wrap { trim: false })
+					.scroll((self.scroll, 0)),
+				area,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ToastComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					let max = u16::try_from(self.history.len())
+						.unwrap_or(u16::MAX)
+						.saturating_sub(1);
+					self.scroll =
+						self.scroll.saturating_add(1).min(max);
+				} else if key_match(e, self.key_config.keys.move_up) {
+					self.scroll = self.scroll.saturating_sub(1);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.scroll = 0;
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::rc::Rc;
+
+	#[test]
+	fn test_push_limits() {
+		let mut toasts =
+			ToastComponent::new(Rc::default(), Rc::default());
+
+		for i in 0..5 {
+			toasts.push(ToastKind::Success, format!("toast {}", i));
+		}
+
+		assert_eq!(toasts.active.len(), TOAST_MAX_VISIBLE);
+		assert_eq!(toasts.active[0].msg, "toast 2");
+		assert_eq!(toasts.history.len(), 5);
+		assert_eq!(toasts.history[0].msg, "toast 4");
+		assert!(!toasts.expire());
+	}
+}
@@ -0,0 +1,323 @@
+use super::{
+	popup_paragraph, visibility_blocking, CommandBlocking,
+	CommandInfo, Component, DrawableComponent, EventState,
+};
+use crate::{
+	args::get_app_config_path,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	tabs::AppTab,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use crossterm::event::Event;
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::File,
+	io::{Read, Write},
+	path::PathBuf,
+};
+use tui::{
+	backend::Backend,
+	layout::Rect,
+	widgets::{Block, BorderType, Borders, Clear},
+	Frame,
+};
+
+/// whether the tour was finished or skipped already
+#[derive(Serialize, Deserialize, Default)]
+struct TutorialPrefs {
+	done: bool,
+}
+
+impl TutorialPrefs {
+	fn get_file() -> Result<PathBuf> {
+		Ok(get_app_config_path()?.join("tutorial.ron"))
+	}
+
+	fn load() -> Result<Self> {
+		let file = Self::get_file()?;
+		if !file.exists() {
+			return Ok(Self::default());
+		}
+
+		let mut buffer = Vec::new();
+		File::open(file)?.read_to_end(&mut buffer)?;
+		Ok(ron::de::from_bytes(&buffer)?)
+	}
+
+	fn save(&self) -> Result<()> {
+		File::create(Self::get_file()?).and_then(|mut file| {
+			file.write_all(
+				to_string_pretty(self, PrettyConfig::default())
+					.unwrap_or_default()
+					.as_bytes(),
+			)
+		})?;
+
+		Ok(())
+	}
+}
+
+/// the part of the screen a step explains
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Target {
+	Tabs,
+	Tab(AppTab),
+	CommandBar,
+}
+
+impl Target {
+	fn text(self, key_config: &SharedKeyConfig) -> String {
+		match self {
+			Self::Tabs => strings::tutorial::tabs(key_config),
+			Self::Tab(AppTab::Status) => {
+				strings::tutorial::status(key_config)
+			}
+			Self::Tab(AppTab::Log) => {
+				strings::tutorial::log(key_config)
+			}
+			Self::Tab(AppTab::Files) => {
+				strings::tutorial::files(key_config)
+			}
+			Self::Tab(AppTab::Stashing) => {
+				strings::tutorial::stashing(key_config)
+			}
+			Self::Tab(AppTab::Stashes) => {
+				strings::tutorial::stashes(key_config)
+			}
+			Self::Tab(AppTab::Search) => {
+				strings::tutorial::search(key_config)
+			}
+			Self::CommandBar => {
+				strings::tutorial::command_bar(key_config)
+			}
+		}
+	}
+}
+
+/// guided tour shown on the first start: it steps through the tabs
+/// bar, every configured tab and the command bar, outlining each and
+/// explaining it in a box next to it
+pub struct TutorialComponent {
+	steps: Vec<Target>,
+	step: usize,
+	visible: bool,
+	queue: Queue,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl TutorialComponent {
+	///
+	pub fn new(
+		tabs: &[AppTab],
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		let steps = std::iter::once(Target::Tabs)
+			.chain(tabs.iter().copied().map(Target::Tab))
+			.chain(std::iter::once(Target::CommandBar))
+			.collect();
+
+		Self {
+			steps,
+			step: 0,
+			visible: false,
+			queue: queue.clone(),
+			theme,
+			key_config,
+		}
+	}
+
+	/// starts the tour unless it was seen before
+	pub fn open_first_run(&mut self) -> Result<()> {
+		if !TutorialPrefs::load()?.done {
+			self.show()?;
+		}
+
+		Ok(())
+	}
+
+	fn go_to(&mut self, step: usize) {
+		self.step = step;
+
+		if let Some(Target::Tab(tab)) = self.steps.get(step) {
+			self.queue.push(InternalEvent::TabSwitch(*tab));
+		}
+	}
+
+	fn next(&mut self) {
+		if self.step + 1 < self.steps.len() {
+			self.go_to(self.step + 1);
+		} else {
+			self.finish();
+		}
+	}
+
+	fn finish(&mut self) {
+		self.hide();
+
+		if let Err(e) = (TutorialPrefs { done: true }).save() {
+			log::error!("tutorial prefs error: {}", e);
+		}
+	}
+
+	/// `area` is what popups get: tabs, main area and status bar
+	fn target_area(&self, area: Rect, screen: Rect) -> Rect {
+		match self.steps.get(self.step) {
+			Some(Target::Tabs) => Rect {
+				height: area.height.min(2),
+				..area
+			},
+			Some(Target::CommandBar)
+				if screen.bottom() > area.bottom() =>
+			{
+				Rect {
+					y: area.bottom(),
+					height: screen.bottom() - area.bottom(),
+					..area
+				}
+			}
+			Some(Target::CommandBar) => Rect {
+				y: area.bottom().saturating_sub(1),
+				height: area.height.min(1),
+				..area
+			},
+			_ => Rect {
+				y: area.y + area.height.min(2),
+				height: area.height.saturating_sub(3),
+				..area
+			},
+		}
+	}
+}
+
+impl DrawableComponent for TutorialComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let target = self.target_area(rect, f.size());
+		if target.width > 0 && target.height > 0 {
+			f.render_widget(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.tutorial_highlight()),
+				target,
+			);
+		}
+
+		let width = rect.width.saturating_sub(4).min(60);
+		let height = rect.height.saturating_sub(4).min(7);
+		let x = rect.x + (rect.width - width) / 2;
+		// next to the outlined part so it stays visible
+		let y = if target.y > rect.y + rect.height / 2 {
+			target.y.saturating_sub(height + 1).max(rect.y)
+		} else {
+			(target.y + target.height.min(rect.height / 2) + 1)
+				.min(rect.bottom().saturating_sub(height))
+		};
+		let area = Rect::new(x, y, width, height);
+
+		let text = self
+			.steps
+			.get(self.step)
+			.map(|target| target.text(&self.key_config))
+			.unwrap_or_default();
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			popup_paragraph(
+				&strings::tutorial::title(
+					self.step,
+					self.steps.len(),
+				),
+				text,
+				&self.theme,
+				true,
+				Borders::ALL,
+				true,
+			),
+			area,
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for TutorialComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		_force_all: bool,
+	) -> CommandBlocking {
+		if self.visible {
+			out.push(CommandInfo::new(
+				strings::commands::tutorial_next(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::tutorial_back(&self.key_config),
+				self.step > 0,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::tutorial_end(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.finish();
+				} else if key_match(e, self.key_config.keys.enter)
+					|| key_match(e, self.key_config.keys.focus_right)
+				{
+					self.next();
+				} else if key_match(
+					e,
+					self.key_config.keys.focus_left,
+				) && self.step > 0
+				{
+					self.go_to(self.step - 1);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.go_to(0);
+
+		Ok(())
+	}
+}
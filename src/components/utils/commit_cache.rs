@@ -0,0 +1,148 @@
+use anyhow::Result;
+use asyncgit::sync::{
+	self, CommitDetails, CommitId, CommitInfo, RepoPath,
+};
+use std::{
+	cell::RefCell,
+	collections::{HashMap, VecDeque},
+	hash::Hash,
+	rc::Rc,
+};
+
+/// commit summaries kept around, a few screens worth of log
+const MAX_INFOS: usize = 5000;
+/// full commit details kept around
+const MAX_DETAILS: usize = 100;
+
+/// map dropping its oldest entries once it grows over `capacity`
+struct BoundedMap<K, V> {
+	map: HashMap<K, V>,
+	order: VecDeque<K>,
+	capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> BoundedMap<K, V> {
+	fn new(capacity: usize) -> Self {
+		Self {
+			map: HashMap::new(),
+			order: VecDeque::new(),
+			capacity,
+		}
+	}
+
+	fn get(&self, key: &K) -> Option<&V> {
+		self.map.get(key)
+	}
+
+	fn insert(&mut self, key: K, value: V) {
+		if self.map.insert(key.clone(), value).is_none() {
+			self.order.push_back(key);
+		}
+
+		while self.order.len() > self.capacity {
+			if let Some(oldest) = self.order.pop_front() {
+				self.map.remove(&oldest);
+			}
+		}
+	}
+
+	fn clear(&mut self) {
+		self.map.clear();
+		self.order.clear();
+	}
+}
+
+/// caches commit summaries (author, time, subject) and details by id
+/// so scrolling the log or moving back and forth between commits
+/// does not look them up again. commits are immutable, entries
+/// never go stale.
+pub struct CommitCache {
+	infos: BoundedMap<CommitId, CommitInfo>,
+	/// subject length the cached infos got truncated to
+	message_limit: usize,
+	details: BoundedMap<CommitId, CommitDetails>,
+}
+
+pub type SharedCommitCache = Rc<RefCell<CommitCache>>;
+
+impl Default for CommitCache {
+	fn default() -> Self {
+		Self {
+			infos: BoundedMap::new(MAX_INFOS),
+			message_limit: 0,
+			details: BoundedMap::new(MAX_DETAILS),
+		}
+	}
+}
+
+impl CommitCache {
+	/// like `sync::get_commits_info` but only looks up uncached commits
+	pub fn commits_info(
+		&mut self,
+		repo: &RepoPath,
+		ids: &[CommitId],
+		message_limit: usize,
+	) -> Result<Vec<CommitInfo>> {
+		if message_limit != self.message_limit {
+			self.infos.clear();
+			self.message_limit = message_limit;
+		}
+
+		let missing: Vec<CommitId> = ids
+			.iter()
+			.filter(|id| self.infos.get(id).is_none())
+			.copied()
+			.collect();
+
+		if !missing.is_empty() {
+			for info in
+				sync::get_commits_info(repo, &missing, message_limit)?
+			{
+				self.infos.insert(info.id, info);
+			}
+		}
+
+		Ok(ids
+			.iter()
+			.filter_map(|id| self.infos.get(id).cloned())
+			.collect())
+	}
+
+	/// like `sync::get_commit_details` but cached
+	pub fn commit_details(
+		&mut self,
+		repo: &RepoPath,
+		id: CommitId,
+	) -> Result<CommitDetails> {
+		if let Some(details) = self.details.get(&id) {
+			return Ok(details.clone());
+		}
+
+		let details = sync::get_commit_details(repo, id)?;
+		self.details.insert(id, details.clone());
+
+		Ok(details)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_bounded_map() {
+		let mut map = BoundedMap::new(2);
+
+		map.insert(1, "a");
+		map.insert(2, "b");
+		map.insert(1, "c");
+		assert_eq!(map.order.len(), 2);
+		assert_eq!(map.get(&1), Some(&"c"));
+
+		map.insert(3, "d");
+		assert_eq!(map.map.len(), 2);
+		assert_eq!(map.get(&1), None);
+		assert_eq!(map.get(&2), Some(&"b"));
+		assert_eq!(map.get(&3), Some(&"d"));
+	}
+}
@@ -0,0 +1,108 @@
+use crate::{
+	components::{CommandInfo, ToastKind},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPath};
+use crossterm::event::KeyEvent;
+use std::path::Path;
+
+/// what of the selected file goes to the clipboard
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FileCopy {
+	/// relative to the repository root
+	Path,
+	///
+	AbsolutePath,
+	/// the whole file, as of the viewed revision
+	Content,
+}
+
+impl FileCopy {
+	/// the copy bound to `key` (`copy`, `copy_path_absolute`,
+	/// `copy_file_content`)
+	pub fn from_key(
+		key: &KeyEvent,
+		key_config: &SharedKeyConfig,
+	) -> Option<Self> {
+		if key_match(key, key_config.keys.copy) {
+			Some(Self::Path)
+		} else if key_match(key, key_config.keys.copy_path_absolute) {
+			Some(Self::AbsolutePath)
+		} else if key_match(key, key_config.keys.copy_file_content) {
+			Some(Self::Content)
+		} else {
+			None
+		}
+	}
+
+	/// pushes the three copy commands, content only for files
+	pub fn commands(
+		out: &mut Vec<CommandInfo>,
+		key_config: &SharedKeyConfig,
+		path_selected: bool,
+		file_selected: bool,
+		visible: bool,
+	) {
+		out.push(CommandInfo::new(
+			strings::commands::copy_file_path(key_config),
+			path_selected,
+			visible,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::copy_file_path_absolute(key_config),
+			path_selected,
+			visible,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::copy_file_content(key_config),
+			file_selected,
+			visible,
+		));
+	}
+}
+
+/// copies `path` (relative to the repository root) or the content
+/// `load` returns for it, the outcome is shown as a toast
+pub fn copy_file<F>(
+	queue: &Queue,
+	repo: &RepoPath,
+	path: &str,
+	copy: FileCopy,
+	load: F,
+) where
+	F: FnOnce() -> Result<String>,
+{
+	let text = match copy {
+		FileCopy::Path => Ok(path.to_string()),
+		FileCopy::AbsolutePath => sync::utils::repo_work_dir(repo)
+			.map(|root| {
+				Path::new(&root)
+					.join(path)
+					.to_string_lossy()
+					.to_string()
+			})
+			.map_err(Into::into),
+		FileCopy::Content => load(),
+	};
+
+	let res =
+		text.and_then(|text| crate::clipboard::copy_string(&text));
+
+	queue.push(match res {
+		Ok(()) => InternalEvent::ShowToast(
+			ToastKind::Success,
+			if copy == FileCopy::Content {
+				strings::file_content_copied(path)
+			} else {
+				strings::PATH_COPIED.to_string()
+			},
+		),
+		Err(e) => InternalEvent::ShowToast(
+			ToastKind::Warning,
+			format!("copy to clipboard error: {}", e),
+		),
+	});
+}
@@ -167,6 +167,24 @@ impl FileTreeItems {
 		})
 	}
 
+	/// a file item per entry, no folders
+	pub(crate) fn new_flat(list: &[StatusItem]) -> Self {
+		Self {
+			items: list
+				.iter()
+				.map(|item| FileTreeItem {
+					info: TreeItemInfo::new(
+						0,
+						item.path.clone(),
+						item.path.clone(),
+					),
+					kind: FileTreeItemKind::File(item.clone()),
+				})
+				.collect(),
+			file_count: list.len(),
+		}
+	}
+
 	///
 	pub(crate) const fn items(&self) -> &Vec<FileTreeItem> {
 		&self.items
@@ -307,6 +325,28 @@ mod tests {
 		assert_eq!(res.items[1].info.path, items[1].path);
 	}
 
+	#[test]
+	fn test_flat() {
+		let items = string_vec_to_status(&[
+			"b/file.txt", //
+			"a/c/file.txt",
+		]);
+
+		let res = FileTreeItems::new_flat(&items);
+
+		assert_eq!(res.file_count(), 2);
+		assert_eq!(
+			res.items
+				.iter()
+				.map(|item| (
+					item.info.indent,
+					item.info.path.as_str()
+				))
+				.collect::<Vec<_>>(),
+			vec![(0, "b/file.txt"), (0, "a/c/file.txt")]
+		);
+	}
+
 	#[test]
 	fn test_folder() {
 		let items = string_vec_to_status(&[
@@ -1,6 +1,8 @@
+use crate::ui::avatar::{author_color, author_initials};
 use asyncgit::sync::{CommitId, CommitInfo};
 use chrono::{DateTime, Duration, Local, NaiveDateTime, Utc};
-use std::slice::Iter;
+use std::{cell::RefCell, slice::Iter};
+use tui::style::Color;
 
 #[cfg(feature = "ghemoji")]
 use super::emoji::emojifi_string;
@@ -10,10 +12,14 @@ static SLICE_OFFSET_RELOAD_THRESHOLD: usize = 100;
 type BoxStr = Box<str>;
 
 pub struct LogEntry {
-	//TODO: cache string representation
 	pub time: DateTime<Local>,
+	/// formatted `time` and the minute it was formatted in,
+	/// only ever filled for rows that got drawn
+	time_str: RefCell<Option<(i64, Box<str>)>>,
 	//TODO: use tinyvec here
 	pub author: BoxStr,
+	/// initials of the author and their color
+	pub author_badge: (BoxStr, Color),
 	pub msg: BoxStr,
 	//TODO: use tinyvec here
 	pub hash_short: BoxStr,
@@ -28,6 +34,10 @@ impl From<CommitInfo> for LogEntry {
 				Utc,
 			));
 
+		let author_badge = (
+			author_initials(&c.author).into(),
+			author_color(&c.author_email),
+		);
 		let author = c.author;
 		#[allow(unused_mut)]
 		let mut msg = c.message;
@@ -38,8 +48,10 @@ impl From<CommitInfo> for LogEntry {
 
 		Self {
 			author: author.into(),
+			author_badge,
 			msg: msg.into(),
 			time,
+			time_str: RefCell::new(None),
 			hash_short: c.id.get_short_string().into(),
 			id: c.id,
 		}
@@ -47,7 +59,26 @@ impl From<CommitInfo> for LogEntry {
 }
 
 impl LogEntry {
+	/// formatted relative to `now`, reused within the same minute
 	pub fn time_to_string(&self, now: DateTime<Local>) -> String {
+		let minute = now.timestamp() / 60;
+
+		if let Some((cached_minute, time_str)) =
+			&*self.time_str.borrow()
+		{
+			if *cached_minute == minute {
+				return time_str.to_string();
+			}
+		}
+
+		let time_str = self.format_time(now);
+		*self.time_str.borrow_mut() =
+			Some((minute, time_str.clone().into()));
+
+		time_str
+	}
+
+	fn format_time(&self, now: DateTime<Local>) -> String {
 		let delta = now - self.time;
 		if delta < Duration::minutes(30) {
 			let delta_str = if delta < Duration::minutes(1) {
@@ -1,12 +1,14 @@
 use chrono::{DateTime, Local, NaiveDateTime, Utc};
-use unicode_width::UnicodeWidthStr;
 
+pub mod commit_cache;
 #[cfg(feature = "ghemoji")]
 pub mod emoji;
+pub mod file_copy;
 pub mod filetree;
 pub mod logitems;
 pub mod scroll_vertical;
 pub mod statustree;
+pub mod text_buffer;
 
 /// macro to simplify running code that might return Err.
 /// It will show a popup in that case
@@ -37,25 +39,44 @@ pub fn time_to_string(secs: i64, short: bool) -> String {
 	.to_string()
 }
 
-#[inline]
-pub fn string_width_align(s: &str, width: usize) -> String {
-	static POSTFIX: &str = "..";
+/// unix time since epoch as a short age relative to `now`
+/// (also unix time), like `5m ago` or `3d ago`
+pub fn time_to_relative_string(secs: i64, now: i64) -> String {
+	let minutes = now.saturating_sub(secs).max(0) / 60;
+	let hours = minutes / 60;
+	let days = hours / 24;
 
-	let len = UnicodeWidthStr::width(s);
-	let width_wo_postfix = width.saturating_sub(POSTFIX.len());
-
-	if (len >= width_wo_postfix && len <= width)
-		|| (len <= width_wo_postfix)
-	{
-		format!("{:w$}", s, w = width)
+	if minutes < 1 {
+		String::from("<1m ago")
+	} else if hours < 1 {
+		format!("{}m ago", minutes)
+	} else if days < 1 {
+		format!("{}h ago", hours)
+	} else if days < 30 {
+		format!("{}d ago", days)
+	} else if days < 365 {
+		format!("{}mo ago", days / 30)
 	} else {
-		let mut s = s.to_string();
-		s.truncate(find_truncate_point(&s, width_wo_postfix));
-		format!("{}{}", s, POSTFIX)
+		format!("{}y ago", days / 365)
 	}
 }
 
-#[inline]
-fn find_truncate_point(s: &str, chars: usize) -> usize {
-	s.chars().take(chars).map(char::len_utf8).sum()
+#[cfg(test)]
+mod tests {
+	use super::time_to_relative_string;
+
+	#[test]
+	fn test_relative_time() {
+		let now = 1_000_000_000;
+		let ago =
+			|secs: i64| time_to_relative_string(now - secs, now);
+
+		assert_eq!(ago(-10), "<1m ago");
+		assert_eq!(ago(59), "<1m ago");
+		assert_eq!(ago(5 * 60), "5m ago");
+		assert_eq!(ago(3 * 3600 + 59), "3h ago");
+		assert_eq!(ago(2 * 86400), "2d ago");
+		assert_eq!(ago(65 * 86400), "2mo ago");
+		assert_eq!(ago(800 * 86400), "2y ago");
+	}
 }
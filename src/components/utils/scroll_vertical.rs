@@ -7,9 +7,12 @@ use crate::{
 	ui::{draw_scrollbar, style::SharedTheme},
 };
 
+/// scroll position of a list with a selection, shared by all lists
+/// so paging and keeping the selection in view behave the same
 pub struct VerticalScroll {
 	top: Cell<usize>,
 	max_top: Cell<usize>,
+	visual_height: Cell<usize>,
 }
 
 impl VerticalScroll {
@@ -17,6 +20,7 @@ impl VerticalScroll {
 		Self {
 			top: Cell::new(0),
 			max_top: Cell::new(0),
+			visual_height: Cell::new(0),
 		}
 	}
 
@@ -51,12 +55,44 @@ impl VerticalScroll {
 		true
 	}
 
+	/// lines a page up/down moves, based on the last `update`
+	pub fn page_size(&self) -> usize {
+		self.visual_height.get().saturating_sub(1).max(1)
+	}
+
+	/// `selection` after moving it by `move_type`, clamped to
+	/// `selection_max`. `step` is how far a single up/down moves.
+	pub fn move_selection(
+		&self,
+		selection: usize,
+		move_type: ScrollType,
+		step: usize,
+		selection_max: usize,
+	) -> usize {
+		let new_selection = match move_type {
+			ScrollType::Up => selection.saturating_sub(step),
+			ScrollType::Down => selection.saturating_add(step),
+			ScrollType::PageUp => {
+				selection.saturating_sub(self.page_size())
+			}
+			ScrollType::PageDown => {
+				selection.saturating_add(self.page_size())
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => selection_max,
+		};
+
+		new_selection.min(selection_max)
+	}
+
 	pub fn update(
 		&self,
 		selection: usize,
 		selection_max: usize,
 		visual_height: usize,
 	) -> usize {
+		self.visual_height.set(visual_height);
+
 		let new_top = calc_scroll_top(
 			self.get_top(),
 			visual_height,
@@ -135,4 +171,35 @@ mod tests {
 	fn test_scroll_zero_height() {
 		assert_eq!(calc_scroll_top(4, 0, 4, 3), 0);
 	}
+
+	#[test]
+	fn test_move_selection() {
+		let scroll = VerticalScroll::new();
+		scroll.update(0, 100, 10);
+
+		assert_eq!(
+			scroll.move_selection(5, ScrollType::Up, 1, 99),
+			4
+		);
+		assert_eq!(
+			scroll.move_selection(0, ScrollType::Up, 1, 99),
+			0
+		);
+		assert_eq!(
+			scroll.move_selection(5, ScrollType::PageDown, 1, 99),
+			14
+		);
+		assert_eq!(
+			scroll.move_selection(95, ScrollType::PageDown, 1, 99),
+			99
+		);
+		assert_eq!(
+			scroll.move_selection(5, ScrollType::Down, 3, 99),
+			8
+		);
+		assert_eq!(
+			scroll.move_selection(5, ScrollType::End, 1, 99),
+			99
+		);
+	}
 }
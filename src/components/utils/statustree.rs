@@ -12,6 +12,8 @@ use std::{cmp, collections::BTreeSet};
 pub struct StatusTree {
 	pub tree: FileTreeItems,
 	pub selection: Option<usize>,
+	/// files with their full path, without folders
+	pub flat: bool,
 
 	// some folders may be folded up, this allows jumping
 	// over folders which are folded into their parent
@@ -49,7 +51,11 @@ impl StatusTree {
 			self.selected_item().map(|e| e.info.full_path);
 		let last_selection_index = self.selection.unwrap_or(0);
 
-		self.tree = FileTreeItems::new(list, &last_collapsed)?;
+		self.tree = if self.flat {
+			FileTreeItems::new_flat(list)
+		} else {
+			FileTreeItems::new(list, &last_collapsed)?
+		};
 		self.selection = last_selection.as_ref().map_or_else(
 			|| self.tree.items().first().map(|_| 0),
 			|last_selection| {
@@ -72,6 +78,22 @@ impl StatusTree {
 		Ok(())
 	}
 
+	/// collapses every folder so only top level entries stay visible
+	pub fn collapse_all(&mut self) {
+		for i in 0..self.tree.len() {
+			if let FileTreeItemKind::Path(PathCollapsed(
+				ref mut collapsed,
+			)) = self.tree[i].kind
+			{
+				*collapsed = true;
+			}
+		}
+
+		self.update_visibility(None, 0, true);
+		self.available_selections = self.setup_available_selections();
+		self.selection = self.tree.items().first().map(|_| 0);
+	}
+
 	/// Return which indices can be selected, taking into account that
 	/// some folders may be folded up into their parent
 	///
@@ -189,9 +211,14 @@ impl StatusTree {
 			return None;
 		}
 
-		if let Ok(i) = self.tree.items().binary_search_by(|e| {
-			e.info.full_path.as_str().cmp(last_selection)
-		}) {
+		// tree order is not plain path order (`a/b` sorts after `a-c`
+		// in a string compare, but its folder `a` comes before it)
+		if let Some(i) = self
+			.tree
+			.items()
+			.iter()
+			.position(|e| e.info.full_path == last_selection)
+		{
 			return Some(i);
 		}
 
@@ -482,6 +509,22 @@ mod tests {
 		assert_eq!(res.selection, Some(1));
 	}
 
+	#[test]
+	fn test_keep_selected_item_out_of_path_order() {
+		let mut res = StatusTree::default();
+		res.update(&string_vec_to_status(&["a-c", "a/b"])).unwrap();
+
+		assert_eq!(res.selection, Some(0));
+
+		res.update(&string_vec_to_status(&["0", "a-c", "a/b"]))
+			.unwrap();
+
+		assert_eq!(
+			res.selected_item().map(|item| item.info.full_path),
+			Some(String::from("a-c"))
+		);
+	}
+
 	#[test]
 	fn test_keep_selected_index() {
 		let mut res = StatusTree::default();
@@ -901,4 +944,30 @@ mod tests {
 		assert!(res.move_selection(MoveSelection::Left)); // jump to 0
 		assert_eq!(res.selection, Some(0));
 	}
+
+	#[test]
+	fn test_collapse_all() {
+		let mut res = StatusTree::default();
+		res.update(&string_vec_to_status(&[
+			"a/b/c", //
+			"a/d",   //
+			"e",
+		]))
+		.unwrap();
+		res.selection = Some(3);
+
+		res.collapse_all();
+
+		assert_eq!(
+			get_visibles(&res),
+			vec![
+				true,  //
+				false, //
+				false, //
+				false, //
+				true,  //
+			]
+		);
+		assert_eq!(res.selection, Some(0));
+	}
 }
@@ -0,0 +1,330 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// text of an input plus its cursor.
+/// keeps the whole string (so it can be handed out as `&str`) and
+/// the start offset of every line so the cursor can be addressed
+/// and moved by (line, column).
+pub struct TextBuffer {
+	text: String,
+	/// byte offset of every line start, first one is always 0
+	line_starts: Vec<usize>,
+	/// byte offset into `text`, always on a grapheme boundary
+	cursor: usize,
+	/// display column moving up/down tries to stay in
+	goal_column: Option<usize>,
+}
+
+impl Default for TextBuffer {
+	fn default() -> Self {
+		Self {
+			text: String::new(),
+			line_starts: vec![0],
+			cursor: 0,
+			goal_column: None,
+		}
+	}
+}
+
+impl TextBuffer {
+	/// replaces the text and moves the cursor to the start
+	pub fn set_text(&mut self, text: String) {
+		self.text = text;
+		self.cursor = 0;
+		self.goal_column = None;
+		self.reindex();
+	}
+
+	///
+	pub fn clear(&mut self) {
+		self.set_text(String::new());
+	}
+
+	///
+	pub fn text(&self) -> &str {
+		self.text.as_str()
+	}
+
+	///
+	pub fn is_empty(&self) -> bool {
+		self.text.is_empty()
+	}
+
+	///
+	pub fn line_count(&self) -> usize {
+		self.line_starts.len()
+	}
+
+	/// line `idx` without its trailing newline
+	pub fn line(&self, idx: usize) -> &str {
+		&self.text[self.line_range(idx)]
+	}
+
+	/// cursor as byte offset into `text`
+	pub const fn cursor(&self) -> usize {
+		self.cursor
+	}
+
+	/// cursor as line and byte column within that line
+	pub fn cursor_line_col(&self) -> (usize, usize) {
+		let line = self.line_of(self.cursor);
+		(line, self.cursor - self.line_starts[line])
+	}
+
+	/// moves the cursor to `col` (bytes) in `line`,
+	/// both get clamped and `col` snapped to a grapheme start
+	pub fn set_cursor_line_col(&mut self, line: usize, col: usize) {
+		let line = line.min(self.line_count() - 1);
+		let text = self.line(line);
+		let col = if col >= text.len() {
+			text.len()
+		} else {
+			text.grapheme_indices(true)
+				.map(|(idx, _)| idx)
+				.take_while(|idx| *idx <= col)
+				.last()
+				.unwrap_or_default()
+		};
+
+		self.cursor = self.line_starts[line] + col;
+		self.goal_column = None;
+	}
+
+	/// text left of the cursor
+	pub fn before_cursor(&self) -> &str {
+		&self.text[..self.cursor]
+	}
+
+	/// end of the grapheme under the cursor,
+	/// `None` if the cursor is at the end of the text
+	pub fn next_grapheme_end(&self) -> Option<usize> {
+		self.text[self.cursor..]
+			.graphemes(true)
+			.next()
+			.map(|grapheme| self.cursor + grapheme.len())
+	}
+
+	fn prev_grapheme_start(&self) -> usize {
+		self.text[..self.cursor]
+			.grapheme_indices(true)
+			.next_back()
+			.map_or(0, |(idx, _)| idx)
+	}
+
+	///
+	pub fn move_right(&mut self) {
+		if let Some(pos) = self.next_grapheme_end() {
+			self.cursor = pos;
+		}
+		self.goal_column = None;
+	}
+
+	///
+	pub fn move_left(&mut self) {
+		self.cursor = self.prev_grapheme_start();
+		self.goal_column = None;
+	}
+
+	/// start of the cursor line
+	pub fn move_home(&mut self) {
+		let (line, _) = self.cursor_line_col();
+		self.set_cursor_line_col(line, 0);
+	}
+
+	/// end of the cursor line
+	pub fn move_end(&mut self) {
+		let (line, _) = self.cursor_line_col();
+		self.set_cursor_line_col(line, usize::MAX);
+	}
+
+	/// returns `false` if already in the first line
+	pub fn move_up(&mut self) -> bool {
+		let (line, _) = self.cursor_line_col();
+		if line == 0 {
+			return false;
+		}
+		self.move_to_line(line - 1);
+		true
+	}
+
+	/// returns `false` if already in the last line
+	pub fn move_down(&mut self) -> bool {
+		let (line, _) = self.cursor_line_col();
+		if line + 1 >= self.line_count() {
+			return false;
+		}
+		self.move_to_line(line + 1);
+		true
+	}
+
+	/// moves to `line` staying as close as possible to the display
+	/// column the vertical movement started in
+	fn move_to_line(&mut self, line: usize) {
+		let goal = self.goal_column.unwrap_or_else(|| {
+			let (line, col) = self.cursor_line_col();
+			self.line(line)[..col].width()
+		});
+
+		let mut width = 0;
+		let mut col = 0;
+		for grapheme in self.line(line).graphemes(true) {
+			width += grapheme.width();
+			if width > goal {
+				break;
+			}
+			col += grapheme.len();
+		}
+
+		self.cursor = self.line_starts[line] + col;
+		self.goal_column = Some(goal);
+	}
+
+	/// inserts `c` at the cursor and moves behind it
+	pub fn insert_char(&mut self, c: char) {
+		self.text.insert(self.cursor, c);
+		self.cursor += c.len_utf8();
+		self.edited();
+	}
+
+	/// removes the grapheme left of the cursor
+	pub fn backspace(&mut self) {
+		let end = self.cursor;
+		self.cursor = self.prev_grapheme_start();
+		self.text.replace_range(self.cursor..end, "");
+		self.edited();
+	}
+
+	/// removes the grapheme under the cursor
+	pub fn delete(&mut self) {
+		if let Some(end) = self.next_grapheme_end() {
+			self.text.replace_range(self.cursor..end, "");
+			self.edited();
+		}
+	}
+
+	/// replaces the `len` bytes left of the cursor with `text`
+	/// and moves the cursor behind the inserted text
+	pub fn replace_before_cursor(&mut self, len: usize, text: &str) {
+		let start = self.cursor.saturating_sub(len);
+		self.text.replace_range(start..self.cursor, text);
+		self.cursor = start + text.len();
+		self.edited();
+	}
+
+	fn edited(&mut self) {
+		self.goal_column = None;
+		self.reindex();
+	}
+
+	fn reindex(&mut self) {
+		self.line_starts = std::iter::once(0)
+			.chain(
+				self.text.match_indices('\n').map(|(idx, _)| idx + 1),
+			)
+			.collect();
+	}
+
+	fn line_of(&self, offset: usize) -> usize {
+		match self.line_starts.binary_search(&offset) {
+			Ok(line) => line,
+			Err(line) => line - 1,
+		}
+	}
+
+	fn line_range(&self, idx: usize) -> std::ops::Range<usize> {
+		let start = self.line_starts[idx];
+		let end = self
+			.line_starts
+			.get(idx + 1)
+			.map_or(self.text.len(), |next| next - 1);
+		start..end
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn buffer(text: &str) -> TextBuffer {
+		let mut buffer = TextBuffer::default();
+		buffer.set_text(text.to_string());
+		buffer
+	}
+
+	#[test]
+	fn test_lines() {
+		let buffer = buffer("foo\n\nbar\n");
+
+		assert_eq!(buffer.line_count(), 4);
+		assert_eq!(buffer.line(0), "foo");
+		assert_eq!(buffer.line(1), "");
+		assert_eq!(buffer.line(2), "bar");
+		assert_eq!(buffer.line(3), "");
+	}
+
+	#[test]
+	fn test_cursor_line_col() {
+		let mut buffer = buffer("foo\nbar");
+
+		buffer.set_cursor_line_col(1, 2);
+		assert_eq!(buffer.cursor(), 6);
+		assert_eq!(buffer.cursor_line_col(), (1, 2));
+
+		buffer.set_cursor_line_col(0, 10);
+		assert_eq!(buffer.cursor_line_col(), (0, 3));
+
+		buffer.move_right();
+		assert_eq!(buffer.cursor_line_col(), (1, 0));
+
+		buffer.move_left();
+		assert_eq!(buffer.cursor_line_col(), (0, 3));
+
+		buffer.move_home();
+		assert_eq!(buffer.cursor(), 0);
+
+		buffer.move_down();
+		buffer.move_end();
+		assert_eq!(buffer.cursor_line_col(), (1, 3));
+	}
+
+	#[test]
+	fn test_move_up_down() {
+		let mut buffer = buffer("long line\nab\n\u{1f44d}xyz");
+
+		buffer.set_cursor_line_col(0, 4);
+		assert!(buffer.move_down());
+		assert_eq!(buffer.cursor_line_col(), (1, 2));
+
+		// keeps the column it started in across the short line
+		assert!(buffer.move_down());
+		assert_eq!(buffer.cursor_line_col(), (2, 6));
+		assert!(!buffer.move_down());
+
+		assert!(buffer.move_up());
+		assert!(buffer.move_up());
+		assert_eq!(buffer.cursor_line_col(), (0, 4));
+		assert!(!buffer.move_up());
+	}
+
+	#[test]
+	fn test_graphemes() {
+		// thumbs up with skin tone modifier, 'e' with combining accent
+		let mut buffer = buffer("a\u{1f44d}\u{1f3fd}e\u{301}b");
+
+		buffer.move_right();
+		buffer.move_right();
+		assert_eq!(buffer.cursor(), 9);
+		assert_eq!(buffer.next_grapheme_end(), Some(12));
+
+		buffer.backspace();
+		assert_eq!(buffer.text(), "ae\u{301}b");
+		assert_eq!(buffer.cursor(), 1);
+
+		buffer.delete();
+		assert_eq!(buffer.text(), "ab");
+
+		buffer.insert_char('\n');
+		assert_eq!(buffer.cursor_line_col(), (1, 0));
+		assert_eq!(buffer.line_count(), 2);
+	}
+}
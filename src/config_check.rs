@@ -0,0 +1,365 @@
+//! strict checks of the ron config files: loading them stays lenient
+//! (unknown keys are ignored, broken files fall back to defaults),
+//! this finds what got ignored so it can be reported on startup or
+//! with `--check-config`
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+	fmt, fs,
+	ops::Range,
+	path::{Path, PathBuf},
+};
+
+/// a field of the outermost struct in a ron file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RonField {
+	pub key: String,
+	/// 1-based
+	pub line: usize,
+	/// byte range of the value without surrounding whitespace
+	pub value: Range<usize>,
+}
+
+/// a problem in a config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+	pub file: PathBuf,
+	/// 1-based, 0 if it concerns the whole file
+	pub line: usize,
+	pub msg: String,
+}
+
+impl fmt::Display for ConfigIssue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.line > 0 {
+			write!(
+				f,
+				"{}:{}: {}",
+				self.file.display(),
+				self.line,
+				self.msg
+			)
+		} else {
+			write!(f, "{}: {}", self.file.display(), self.msg)
+		}
+	}
+}
+
+/// checks `file` against the fields of `T`, `deprecated` maps old
+/// key names to the ones replacing them; a missing file is fine
+pub fn check_file<T>(
+	file: &Path,
+	deprecated: &[(&str, &str)],
+) -> Vec<ConfigIssue>
+where
+	T: Serialize + DeserializeOwned + Default,
+{
+	let content = match fs::read_to_string(file) {
+		Ok(content) => content,
+		Err(_) if !file.exists() => return Vec::new(),
+		Err(e) => {
+			return vec![ConfigIssue {
+				file: file.to_path_buf(),
+				line: 0,
+				msg: e.to_string(),
+			}]
+		}
+	};
+
+	let known = ron::to_string(&T::default())
+		.map(|default| {
+			top_level_fields(&default)
+				.into_iter()
+				.map(|field| field.key)
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+
+	let mut issues = check_keys(&content, &known, deprecated)
+		.into_iter()
+		.map(|(line, msg)| ConfigIssue {
+			file: file.to_path_buf(),
+			line,
+			msg,
+		})
+		.collect::<Vec<_>>();
+
+	if let Err(e) = ron::de::from_str::<T>(&content) {
+		issues.push(ConfigIssue {
+			file: file.to_path_buf(),
+			line: e.position.line,
+			msg: format!("{}, the whole file is ignored", e.code),
+		});
+	}
+
+	issues
+}
+
+/// unknown and deprecated keys in `content` as `(line, message)`
+fn check_keys(
+	content: &str,
+	known: &[String],
+	deprecated: &[(&str, &str)],
+) -> Vec<(usize, String)> {
+	top_level_fields(content)
+		.into_iter()
+		.filter_map(|RonField { key, line, .. }| {
+			if let Some((_, new)) =
+				deprecated.iter().find(|(old, _)| *old == key)
+			{
+				Some((
+					line,
+					format!("`{}` is deprecated, use `{}`", key, new),
+				))
+			} else if known.contains(&key) {
+				None
+			} else {
+				Some((line, unknown_key_msg(&key, known)))
+			}
+		})
+		.collect()
+}
+
+fn unknown_key_msg(key: &str, known: &[String]) -> String {
+	let closest = known
+		.iter()
+		.map(|k| (strsim::jaro_winkler(key, k), k))
+		.filter(|(similarity, _)| *similarity > 0.8)
+		.max_by(|a, b| {
+			a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)
+		});
+
+	closest.map_or_else(
+		|| format!("unknown key `{}`, it is ignored", key),
+		|(_, k)| {
+			format!(
+				"unknown key `{}`, it is ignored (did you mean `{}`?)",
+				key, k
+			)
+		},
+	)
+}
+
+/// the fields of the outermost struct, in file order
+pub fn top_level_fields(content: &str) -> Vec<RonField> {
+	let mut fields: Vec<RonField> = Vec::new();
+	let mut chars = content.char_indices().peekable();
+	let mut depth = 0_usize;
+	let mut line = 1;
+	let mut ident = String::new();
+	// an identifier followed only by whitespace so far
+	let mut pending: Option<(String, usize)> = None;
+	// where the value of the last field starts while it is read
+	let mut value_start: Option<usize> = None;
+
+	while let Some((i, c)) = chars.next() {
+		if c.is_alphanumeric() || c == '_' {
+			ident.push(c);
+			continue;
+		}
+
+		if !ident.is_empty() {
+			pending = Some((std::mem::take(&mut ident), line));
+		}
+
+		match c {
+			'\n' => line += 1,
+			c if c.is_whitespace() => (),
+			':' => {
+				if let Some((key, key_line)) = pending.take() {
+					if depth == 1 {
+						fields.push(RonField {
+							key,
+							line: key_line,
+							value: i + 1..i + 1,
+						});
+						value_start = Some(i + 1);
+					}
+				}
+			}
+			',' if depth == 1 => {
+				end_value(
+					&mut fields,
+					value_start.take(),
+					i,
+					content,
+				);
+			}
+			'(' | '[' | '{' => depth += 1,
+			')' | ']' | '}' => {
+				if depth == 1 {
+					end_value(
+						&mut fields,
+						value_start.take(),
+						i,
+						content,
+					);
+				}
+				depth = depth.saturating_sub(1);
+			}
+			'"' | '\'' => {
+				while let Some((_, s)) = chars.next() {
+					match s {
+						'\\' => {
+							chars.next();
+						}
+						'\n' => line += 1,
+						s if s == c => break,
+						_ => (),
+					}
+				}
+			}
+			'/' if matches!(chars.peek(), Some((_, '/'))) => {
+				for (_, s) in chars.by_ref() {
+					if s == '\n' {
+						line += 1;
+						break;
+					}
+				}
+			}
+			'/' if matches!(chars.peek(), Some((_, '*'))) => {
+				chars.next();
+				let mut last = ' ';
+				for (_, s) in chars.by_ref() {
+					if s == '\n' {
+						line += 1;
+					} else if last == '*' && s == '/' {
+						break;
+					}
+					last = s;
+				}
+			}
+			_ => (),
+		}
+
+		if !c.is_whitespace() && c != ':' {
+			pending = None;
+		}
+	}
+
+	end_value(&mut fields, value_start, content.len(), content);
+
+	fields
+}
+
+/// `content` with the value of the top level field `key` replaced by
+/// `value`, a missing field is appended; `None` if `content` is no
+/// struct
+pub fn set_top_level_field(
+	content: &str,
+	key: &str,
+	value: &str,
+) -> Option<String> {
+	if let Some(field) =
+		top_level_fields(content).into_iter().find(|f| f.key == key)
+	{
+		let mut res = content.to_string();
+		res.replace_range(field.value, value);
+		return Some(res);
+	}
+
+	let end = content.rfind(')')?;
+	let before = content[..end].trim_end();
+	let separator = if before.ends_with(',') || before.ends_with('(')
+	{
+		""
+	} else {
+		","
+	};
+
+	Some(format!(
+		"{}{}\n\t{}: {},\n{}",
+		before,
+		separator,
+		key,
+		value,
+		&content[end..]
+	))
+}
+
+/// sets the value range of the last field to `start..end` trimmed
+fn end_value(
+	fields: &mut [RonField],
+	start: Option<usize>,
+	end: usize,
+	content: &str,
+) {
+	if let (Some(start), Some(field)) = (start, fields.last_mut()) {
+		let raw = &content[start..end];
+		let start = start + raw.len() - raw.trim_start().len();
+		field.value = start..start + raw.trim().len();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_top_level_fields() {
+		let content = "#![enable(implicit_some)]\n(\n\t// a: b\n\tfoo: Some(( code: Char(':'), modifiers: ( bits: 0,),)),\n\tbar : \"x: (\"\n)";
+
+		let fields = top_level_fields(content);
+
+		assert_eq!(
+			fields
+				.iter()
+				.map(|field| (field.key.as_str(), field.line))
+				.collect::<Vec<_>>(),
+			vec![("foo", 4), ("bar", 5)]
+		);
+		assert_eq!(
+			&content[fields[0].value.clone()],
+			"Some(( code: Char(':'), modifiers: ( bits: 0,),))"
+		);
+		assert_eq!(&content[fields[1].value.clone()], "\"x: (\"");
+	}
+
+	#[test]
+	fn test_set_top_level_field() {
+		assert_eq!(
+			set_top_level_field("(\n\ta: 1, // one\n)", "a", "2")
+				.unwrap(),
+			"(\n\ta: 2, // one\n)"
+		);
+		assert_eq!(
+			set_top_level_field("(\n\ta: 1\n)\n", "b", "2").unwrap(),
+			"(\n\ta: 1,\n\tb: 2,\n)\n"
+		);
+		assert_eq!(
+			set_top_level_field("(\n)", "b", "2").unwrap(),
+			"(\n\tb: 2,\n)"
+		);
+		assert_eq!(set_top_level_field("", "b", "2"), None);
+	}
+
+	#[test]
+	fn test_check_keys() {
+		let known = vec![
+			String::from("open_help"),
+			String::from("toggle_workarea"),
+		];
+
+		assert_eq!(
+			check_keys(
+				"(\nopen_hlep: None,\nfocus_stage: None,\nzzz: None,\nopen_help: None)",
+				&known,
+				&[("focus_stage", "toggle_workarea")],
+			),
+			vec![
+				(
+					2,
+					String::from("unknown key `open_hlep`, it is ignored (did you mean `open_help`?)")
+				),
+				(
+					3,
+					String::from(
+						"`focus_stage` is deprecated, use `toggle_workarea`"
+					)
+				),
+				(4, String::from("unknown key `zzz`, it is ignored")),
+			]
+		);
+	}
+}
@@ -0,0 +1,80 @@
+//! showing files in the file manager of the os, the file manager is
+//! spawned detached with its output dropped so it can't draw over
+//! the tui
+
+use anyhow::{anyhow, Result};
+use std::{
+	ffi::OsStr,
+	path::Path,
+	process::{Command, Stdio},
+};
+
+/// opens the file manager on the folder containing `path`, selecting
+/// `path` where the platform allows it; a `path` that does not exist
+/// (deleted or only in another revision) opens the closest existing
+/// folder instead
+pub fn reveal(path: &Path) -> Result<()> {
+	if path.exists() {
+		return reveal_existing(path);
+	}
+
+	let folder = path
+		.ancestors()
+		.find(|p| p.is_dir())
+		.ok_or_else(|| anyhow!("{:?} not found", path))?;
+
+	open_folder(folder)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal_existing(path: &Path) -> Result<()> {
+	// `xdg-open` can't select a file, open its folder instead
+	match path.parent() {
+		Some(parent) if !path.is_dir() => open_folder(parent),
+		_ => open_folder(path),
+	}
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn open_folder(path: &Path) -> Result<()> {
+	spawn_detached("xdg-open", &[path.as_os_str()])
+}
+
+#[cfg(target_os = "macos")]
+fn reveal_existing(path: &Path) -> Result<()> {
+	spawn_detached("open", &[OsStr::new("-R"), path.as_os_str()])
+}
+
+#[cfg(target_os = "macos")]
+fn open_folder(path: &Path) -> Result<()> {
+	spawn_detached("open", &[path.as_os_str()])
+}
+
+#[cfg(windows)]
+fn reveal_existing(path: &Path) -> Result<()> {
+	let mut select = std::ffi::OsString::from("/select,");
+	select.push(path);
+
+	spawn_detached("explorer", &[select.as_os_str()])
+}
+
+#[cfg(windows)]
+fn open_folder(path: &Path) -> Result<()> {
+	spawn_detached("explorer", &[path.as_os_str()])
+}
+
+/// the child is waited on in the background so it does not linger as
+/// a zombie, its exit status is of no interest
+fn spawn_detached(command: &str, args: &[&OsStr]) -> Result<()> {
+	let mut child = Command::new(command)
+		.args(args)
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn()
+		.map_err(|e| anyhow!("`{}`: {}", command, e))?;
+
+	std::thread::spawn(move || child.wait());
+
+	Ok(())
+}
@@ -1,14 +1,25 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crossterm::event::{KeyCode, KeyModifiers};
-use std::{path::PathBuf, rc::Rc};
+use std::{fs, path::PathBuf, rc::Rc};
 
-use crate::{args::get_app_config_path, strings::symbol};
+use crate::{
+	args::get_app_config_path,
+	config_check::{check_file, set_top_level_field, ConfigIssue},
+	strings::{self, symbol},
+};
 
 use super::{
 	key_list::{GituiKeyEvent, KeysList},
-	symbols::KeySymbols,
+	key_list_file::KeysListFile,
+	symbols::{KeySymbols, KeySymbolsFile},
 };
 
+/// key names replaced by others, old name first
+const DEPRECATED_KEYS: &[(&str, &str)] = &[
+	("focus_workdir", "toggle_workarea"),
+	("focus_stage", "toggle_workarea"),
+];
+
 pub type SharedKeyConfig = Rc<KeyConfig>;
 
 #[derive(Default, Clone)]
@@ -34,6 +45,72 @@ impl KeyConfig {
 		Ok(Self { keys, symbols })
 	}
 
+	/// problems in the key bindings and key symbols files
+	pub fn check() -> Result<Vec<ConfigIssue>> {
+		let mut issues = check_file::<KeysListFile>(
+			&Self::get_config_file()?,
+			DEPRECATED_KEYS,
+		);
+		issues.extend(check_file::<KeySymbolsFile>(
+			&Self::get_symbols_file()?,
+			&[],
+		));
+
+		Ok(issues)
+	}
+
+	/// keys of the key bindings file bound like others, each pair
+	/// once
+	pub fn check_conflicts() -> Result<Vec<ConfigIssue>> {
+		let file = Self::get_config_file()?;
+
+		Ok(KeysList::init(file.clone())
+			.conflicts()
+			.into_iter()
+			.filter_map(|(key, others)| {
+				let others = others
+					.into_iter()
+					.filter(|other| *other > key)
+					.collect::<Vec<_>>();
+
+				(!others.is_empty()).then(|| ConfigIssue {
+					file: file.clone(),
+					line: 0,
+					msg: strings::key_conflict(&key, &others),
+				})
+			})
+			.collect())
+	}
+
+	/// writes `key` as binding of the key `name` into the key
+	/// bindings file, the rest of the file is kept as it is
+	pub fn save_binding(
+		name: &str,
+		key: GituiKeyEvent,
+	) -> Result<()> {
+		let file = Self::get_config_file()?;
+		let content = if file.exists() {
+			fs::read_to_string(&file)?
+		} else {
+			String::from("(\n)\n")
+		};
+
+		let content = set_top_level_field(
+			&content,
+			name,
+			&format!("Some({})", ron::to_string(&key)?),
+		)
+		.ok_or_else(|| {
+			anyhow!("{:?} is no key bindings file", file)
+		})?;
+
+		// never write what would not load anymore
+		ron::de::from_str::<KeysListFile>(&content)?;
+		fs::write(file, content)?;
+
+		Ok(())
+	}
+
 	fn get_key_symbol(&self, k: KeyCode) -> &str {
 		match k {
 			KeyCode::Enter => &self.symbols.enter,
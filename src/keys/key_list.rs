@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use super::key_list_file::KeysListFile;
+use crate::config_check::top_level_fields;
 
 #[derive(Debug, PartialOrd, Clone, Copy, Serialize, Deserialize)]
 pub struct GituiKeyEvent {
@@ -34,13 +35,14 @@ impl From<&GituiKeyEvent> for KeyEvent {
 	}
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 pub struct KeysList {
 	pub tab_status: GituiKeyEvent,
 	pub tab_log: GituiKeyEvent,
 	pub tab_files: GituiKeyEvent,
 	pub tab_stashing: GituiKeyEvent,
 	pub tab_stashes: GituiKeyEvent,
+	pub tab_search: GituiKeyEvent,
 	pub tab_toggle: GituiKeyEvent,
 	pub tab_toggle_reverse: GituiKeyEvent,
 	pub toggle_workarea: GituiKeyEvent,
@@ -54,7 +56,18 @@ pub struct KeysList {
 	pub open_commit: GituiKeyEvent,
 	pub open_commit_editor: GituiKeyEvent,
 	pub open_help: GituiKeyEvent,
+	pub open_context_help: GituiKeyEvent,
 	pub open_options: GituiKeyEvent,
+	pub open_notifications: GituiKeyEvent,
+	pub open_tasks: GituiKeyEvent,
+	pub undo: GituiKeyEvent,
+	pub open_discarded: GituiKeyEvent,
+	pub open_action_log: GituiKeyEvent,
+	pub open_repo_stats: GituiKeyEvent,
+	pub open_maintenance: GituiKeyEvent,
+	pub open_shell: GituiKeyEvent,
+	pub suspend: GituiKeyEvent,
+	pub open_pull_requests: GituiKeyEvent,
 	pub move_left: GituiKeyEvent,
 	pub move_right: GituiKeyEvent,
 	pub tree_collapse_recursive: GituiKeyEvent,
@@ -71,13 +84,24 @@ pub struct KeysList {
 	pub shift_down: GituiKeyEvent,
 	pub enter: GituiKeyEvent,
 	pub blame: GituiKeyEvent,
+	pub blame_ignore_revs: GituiKeyEvent,
+	pub blame_line: GituiKeyEvent,
 	pub file_history: GituiKeyEvent,
 	pub edit_file: GituiKeyEvent,
+	pub open_difftool: GituiKeyEvent,
+	pub open_mergetool: GituiKeyEvent,
 	pub status_stage_all: GituiKeyEvent,
 	pub status_reset_item: GituiKeyEvent,
 	pub status_ignore_file: GituiKeyEvent,
+	pub status_show_ignored: GituiKeyEvent,
+	pub status_commit_item: GituiKeyEvent,
+	pub status_mark_item: GituiKeyEvent,
 	pub diff_stage_lines: GituiKeyEvent,
 	pub diff_reset_lines: GituiKeyEvent,
+	pub diff_load_more: GituiKeyEvent,
+	pub diff_algorithm: GituiKeyEvent,
+	pub lfs_preview: GituiKeyEvent,
+	pub reveal_masked: GituiKeyEvent,
 	pub stashing_save: GituiKeyEvent,
 	pub stashing_toggle_untracked: GituiKeyEvent,
 	pub stashing_toggle_index: GituiKeyEvent,
@@ -86,23 +110,40 @@ pub struct KeysList {
 	pub stash_drop: GituiKeyEvent,
 	pub cmd_bar_toggle: GituiKeyEvent,
 	pub log_tag_commit: GituiKeyEvent,
+	pub log_edit_note: GituiKeyEvent,
 	pub log_mark_commit: GituiKeyEvent,
+	pub log_ci_status: GituiKeyEvent,
 	pub commit_amend: GituiKeyEvent,
+	pub commit_anyway: GituiKeyEvent,
+	pub commit_author: GituiKeyEvent,
 	pub copy: GituiKeyEvent,
+	pub copy_describe: GituiKeyEvent,
+	pub copy_path_absolute: GituiKeyEvent,
+	pub copy_file_content: GituiKeyEvent,
+	pub copy_range: GituiKeyEvent,
+	pub reveal_file: GituiKeyEvent,
 	pub create_branch: GituiKeyEvent,
+	pub create_orphan_branch: GituiKeyEvent,
 	pub rename_branch: GituiKeyEvent,
 	pub select_branch: GituiKeyEvent,
 	pub delete_branch: GituiKeyEvent,
 	pub merge_branch: GituiKeyEvent,
 	pub rebase_branch: GituiKeyEvent,
 	pub compare_commits: GituiKeyEvent,
+	pub compare_branch: GituiKeyEvent,
 	pub tags: GituiKeyEvent,
 	pub delete_tag: GituiKeyEvent,
+	pub cancel_task: GituiKeyEvent,
 	pub select_tag: GituiKeyEvent,
 	pub push: GituiKeyEvent,
 	pub open_file_tree: GituiKeyEvent,
 	pub file_find: GituiKeyEvent,
 	pub force_push: GituiKeyEvent,
+	pub push_review: GituiKeyEvent,
+	pub push_options: GituiKeyEvent,
+	pub push_signed_toggle: GituiKeyEvent,
+	pub accept_completion: GituiKeyEvent,
+	pub confirm_dont_ask: GituiKeyEvent,
 	pub pull: GituiKeyEvent,
 	pub abort_merge: GituiKeyEvent,
 	pub undo_commit: GituiKeyEvent,
@@ -111,10 +152,16 @@ pub struct KeysList {
 	pub view_submodules: GituiKeyEvent,
 	pub view_submodule_parent: GituiKeyEvent,
 	pub update_submodule: GituiKeyEvent,
+	pub search_focus_input: GituiKeyEvent,
+	pub search_toggle_regex: GituiKeyEvent,
+	pub search_toggle_case: GituiKeyEvent,
+	pub search_toggle_source: GituiKeyEvent,
+	pub file_filter: GituiKeyEvent,
 }
 
 #[rustfmt::skip]
 impl Default for KeysList {
+	#[allow(clippy::too_many_lines)]
 	fn default() -> Self {
 		Self {
 			tab_status: GituiKeyEvent::new(KeyCode::Char('1'), KeyModifiers::empty()),
@@ -122,6 +169,7 @@ impl Default for KeysList {
 			tab_files: GituiKeyEvent::new(KeyCode::Char('3'),  KeyModifiers::empty()),
 			tab_stashing: GituiKeyEvent::new(KeyCode::Char('4'),  KeyModifiers::empty()),
 			tab_stashes: GituiKeyEvent::new(KeyCode::Char('5'),  KeyModifiers::empty()),
+			tab_search: GituiKeyEvent::new(KeyCode::Char('6'),  KeyModifiers::empty()),
 			tab_toggle: GituiKeyEvent::new(KeyCode::Tab,  KeyModifiers::empty()),
 			tab_toggle_reverse: GituiKeyEvent::new(KeyCode::BackTab,  KeyModifiers::SHIFT),
 			toggle_workarea: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::empty()),
@@ -135,7 +183,18 @@ impl Default for KeysList {
 			open_commit: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			open_commit_editor: GituiKeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
 			open_help: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::empty()),
+			open_context_help: GituiKeyEvent::new(KeyCode::Char('?'),  KeyModifiers::empty()),
 			open_options: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			open_notifications: GituiKeyEvent::new(KeyCode::Char('N'),  KeyModifiers::SHIFT),
+			open_tasks: GituiKeyEvent::new(KeyCode::Char('W'),  KeyModifiers::SHIFT),
+			undo: GituiKeyEvent::new(KeyCode::Char('Z'),  KeyModifiers::SHIFT),
+			open_discarded: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::CONTROL),
+			open_action_log: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::CONTROL),
+			open_repo_stats: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::CONTROL),
+			open_maintenance: GituiKeyEvent::new(KeyCode::Char('k'),  KeyModifiers::CONTROL),
+			open_shell: GituiKeyEvent::new(KeyCode::Char('!'),  KeyModifiers::empty()),
+			suspend: GituiKeyEvent::new(KeyCode::Char('z'),  KeyModifiers::CONTROL),
+			open_pull_requests: GituiKeyEvent::new(KeyCode::Char('O'),  KeyModifiers::SHIFT),
 			move_left: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::empty()),
 			move_right: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			tree_collapse_recursive: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::SHIFT),
@@ -152,13 +211,24 @@ impl Default for KeysList {
 			shift_down: GituiKeyEvent::new(KeyCode::Down,  KeyModifiers::SHIFT),
 			enter: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			blame: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
+			blame_ignore_revs: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			blame_line: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
 			file_history: GituiKeyEvent::new(KeyCode::Char('H'),  KeyModifiers::SHIFT),
 			edit_file: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			open_difftool: GituiKeyEvent::new(KeyCode::Char('E'),  KeyModifiers::SHIFT),
+			open_mergetool: GituiKeyEvent::new(KeyCode::Char('M'),  KeyModifiers::SHIFT),
 			status_stage_all: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			status_reset_item: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			diff_reset_lines: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
 			status_ignore_file: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			status_show_ignored: GituiKeyEvent::new(KeyCode::Char('I'),  KeyModifiers::SHIFT),
+			status_commit_item: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
+			status_mark_item: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
 			diff_stage_lines: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			diff_load_more: GituiKeyEvent::new(KeyCode::Char('L'),  KeyModifiers::SHIFT),
+			diff_algorithm: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::CONTROL),
+			lfs_preview: GituiKeyEvent::new(KeyCode::Char('V'),  KeyModifiers::SHIFT),
+			reveal_masked: GituiKeyEvent::new(KeyCode::Char('X'),  KeyModifiers::SHIFT),
 			stashing_save: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			stashing_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
 			stashing_toggle_index: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
@@ -167,21 +237,38 @@ impl Default for KeysList {
 			stash_drop: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			cmd_bar_toggle: GituiKeyEvent::new(KeyCode::Char('.'),  KeyModifiers::empty()),
 			log_tag_commit: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			log_edit_note: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
 			log_mark_commit: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
+			log_ci_status: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
 			commit_amend: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
+			commit_anyway: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::CONTROL),
+			commit_author: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::CONTROL),
 			copy: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::empty()),
+			copy_describe: GituiKeyEvent::new(KeyCode::Char('Y'),  KeyModifiers::SHIFT),
+			copy_path_absolute: GituiKeyEvent::new(KeyCode::Char('Y'),  KeyModifiers::SHIFT),
+			copy_file_content: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::CONTROL),
+			copy_range: GituiKeyEvent::new(KeyCode::Char('Y'),  KeyModifiers::SHIFT),
+			reveal_file: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::CONTROL),
 			create_branch: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
+			create_orphan_branch: GituiKeyEvent::new(KeyCode::Char('O'),  KeyModifiers::SHIFT),
 			rename_branch: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
 			select_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
 			delete_branch: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			merge_branch: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
 			rebase_branch: GituiKeyEvent::new(KeyCode::Char('R'),  KeyModifiers::SHIFT),
 			compare_commits: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
+			compare_branch: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
 			tags: GituiKeyEvent::new(KeyCode::Char('T'),  KeyModifiers::SHIFT),
 			delete_tag: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			cancel_task: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			select_tag: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			push: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			force_push: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
+			push_review: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::CONTROL),
+			push_options: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::ALT),
+			push_signed_toggle: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
+			accept_completion: GituiKeyEvent::new(KeyCode::Tab,  KeyModifiers::empty()),
+			confirm_dont_ask: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
 			undo_commit: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
 			pull: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			abort_merge: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
@@ -192,6 +279,11 @@ impl Default for KeysList {
 			view_submodules: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
 			view_submodule_parent: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			update_submodule: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
+			search_focus_input: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::empty()),
+			search_toggle_regex: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::CONTROL),
+			search_toggle_case: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::CONTROL),
+			search_toggle_source: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
+			file_filter: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::empty()),
 		}
 	}
 }
@@ -206,4 +298,88 @@ impl KeysList {
 			Self::default()
 		}
 	}
+
+	/// every key by its name in the key bindings file
+	pub fn entries(&self) -> Vec<(String, GituiKeyEvent)> {
+		let content = ron::to_string(self).unwrap_or_default();
+
+		top_level_fields(&content)
+			.into_iter()
+			.filter_map(|field| {
+				ron::from_str(&content[field.value])
+					.ok()
+					.map(|key| (field.key, key))
+			})
+			.collect()
+	}
+
+	/// the keys bound like other keys they differ from by default,
+	/// with the names of those; the defaults only share bindings
+	/// between keys that are never active at the same time
+	pub fn conflicts(&self) -> Vec<(String, Vec<String>)> {
+		let entries = self.entries();
+		let defaults = Self::default().entries();
+		let default_of = |name: &str| {
+			defaults
+				.iter()
+				.find(|(default, _)| default == name)
+				.map(|(_, key)| *key)
+		};
+
+		entries
+			.iter()
+			.filter_map(|(name, key)| {
+				let others = entries
+					.iter()
+					.filter(|(other, other_key)| {
+						other != name
+							&& other_key == key && default_of(other)
+							!= default_of(name)
+					})
+					.map(|(other, _)| other.clone())
+					.collect::<Vec<_>>();
+
+				(!others.is_empty()).then(|| (name.clone(), others))
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_entries() {
+		let keys = KeysList::default();
+		let entries = keys.entries();
+
+		assert!(entries
+			.iter()
+			.any(|(name, key)| name == "open_help"
+				&& *key == keys.open_help));
+		assert!(entries.len() > 100);
+	}
+
+	#[test]
+	fn test_conflicts() {
+		let mut keys = KeysList::default();
+		assert!(keys.conflicts().is_empty());
+
+		keys.open_help = keys.quit;
+
+		assert_eq!(
+			keys.conflicts(),
+			vec![
+				(
+					String::from("quit"),
+					vec![String::from("open_help")]
+				),
+				(
+					String::from("open_help"),
+					vec![String::from("quit")]
+				),
+			]
+		);
+	}
 }
@@ -12,6 +12,7 @@ pub struct KeysListFile {
 	pub tab_files: Option<GituiKeyEvent>,
 	pub tab_stashing: Option<GituiKeyEvent>,
 	pub tab_stashes: Option<GituiKeyEvent>,
+	pub tab_search: Option<GituiKeyEvent>,
 	pub tab_toggle: Option<GituiKeyEvent>,
 	pub tab_toggle_reverse: Option<GituiKeyEvent>,
 	pub toggle_workarea: Option<GituiKeyEvent>,
@@ -25,7 +26,18 @@ pub struct KeysListFile {
 	pub open_commit: Option<GituiKeyEvent>,
 	pub open_commit_editor: Option<GituiKeyEvent>,
 	pub open_help: Option<GituiKeyEvent>,
+	pub open_context_help: Option<GituiKeyEvent>,
 	pub open_options: Option<GituiKeyEvent>,
+	pub open_notifications: Option<GituiKeyEvent>,
+	pub open_tasks: Option<GituiKeyEvent>,
+	pub undo: Option<GituiKeyEvent>,
+	pub open_discarded: Option<GituiKeyEvent>,
+	pub open_action_log: Option<GituiKeyEvent>,
+	pub open_repo_stats: Option<GituiKeyEvent>,
+	pub open_maintenance: Option<GituiKeyEvent>,
+	pub open_shell: Option<GituiKeyEvent>,
+	pub suspend: Option<GituiKeyEvent>,
+	pub open_pull_requests: Option<GituiKeyEvent>,
 	pub move_left: Option<GituiKeyEvent>,
 	pub move_right: Option<GituiKeyEvent>,
 	pub tree_collapse_recursive: Option<GituiKeyEvent>,
@@ -42,13 +54,24 @@ pub struct KeysListFile {
 	pub shift_down: Option<GituiKeyEvent>,
 	pub enter: Option<GituiKeyEvent>,
 	pub blame: Option<GituiKeyEvent>,
+	pub blame_ignore_revs: Option<GituiKeyEvent>,
+	pub blame_line: Option<GituiKeyEvent>,
 	pub edit_file: Option<GituiKeyEvent>,
+	pub open_difftool: Option<GituiKeyEvent>,
+	pub open_mergetool: Option<GituiKeyEvent>,
 	pub file_history: Option<GituiKeyEvent>,
 	pub status_stage_all: Option<GituiKeyEvent>,
 	pub status_reset_item: Option<GituiKeyEvent>,
 	pub status_ignore_file: Option<GituiKeyEvent>,
+	pub status_show_ignored: Option<GituiKeyEvent>,
+	pub status_commit_item: Option<GituiKeyEvent>,
+	pub status_mark_item: Option<GituiKeyEvent>,
 	pub diff_stage_lines: Option<GituiKeyEvent>,
 	pub diff_reset_lines: Option<GituiKeyEvent>,
+	pub diff_load_more: Option<GituiKeyEvent>,
+	pub diff_algorithm: Option<GituiKeyEvent>,
+	pub lfs_preview: Option<GituiKeyEvent>,
+	pub reveal_masked: Option<GituiKeyEvent>,
 	pub stashing_save: Option<GituiKeyEvent>,
 	pub stashing_toggle_untracked: Option<GituiKeyEvent>,
 	pub stashing_toggle_index: Option<GituiKeyEvent>,
@@ -57,23 +80,40 @@ pub struct KeysListFile {
 	pub stash_drop: Option<GituiKeyEvent>,
 	pub cmd_bar_toggle: Option<GituiKeyEvent>,
 	pub log_tag_commit: Option<GituiKeyEvent>,
+	pub log_edit_note: Option<GituiKeyEvent>,
 	pub log_mark_commit: Option<GituiKeyEvent>,
+	pub log_ci_status: Option<GituiKeyEvent>,
 	pub commit_amend: Option<GituiKeyEvent>,
+	pub commit_anyway: Option<GituiKeyEvent>,
+	pub commit_author: Option<GituiKeyEvent>,
 	pub copy: Option<GituiKeyEvent>,
+	pub copy_describe: Option<GituiKeyEvent>,
+	pub copy_path_absolute: Option<GituiKeyEvent>,
+	pub copy_file_content: Option<GituiKeyEvent>,
+	pub copy_range: Option<GituiKeyEvent>,
+	pub reveal_file: Option<GituiKeyEvent>,
 	pub create_branch: Option<GituiKeyEvent>,
+	pub create_orphan_branch: Option<GituiKeyEvent>,
 	pub rename_branch: Option<GituiKeyEvent>,
 	pub select_branch: Option<GituiKeyEvent>,
 	pub delete_branch: Option<GituiKeyEvent>,
 	pub merge_branch: Option<GituiKeyEvent>,
 	pub rebase_branch: Option<GituiKeyEvent>,
 	pub compare_commits: Option<GituiKeyEvent>,
+	pub compare_branch: Option<GituiKeyEvent>,
 	pub tags: Option<GituiKeyEvent>,
 	pub delete_tag: Option<GituiKeyEvent>,
+	pub cancel_task: Option<GituiKeyEvent>,
 	pub select_tag: Option<GituiKeyEvent>,
 	pub push: Option<GituiKeyEvent>,
 	pub open_file_tree: Option<GituiKeyEvent>,
 	pub file_find: Option<GituiKeyEvent>,
 	pub force_push: Option<GituiKeyEvent>,
+	pub push_review: Option<GituiKeyEvent>,
+	pub push_options: Option<GituiKeyEvent>,
+	pub push_signed_toggle: Option<GituiKeyEvent>,
+	pub accept_completion: Option<GituiKeyEvent>,
+	pub confirm_dont_ask: Option<GituiKeyEvent>,
 	pub pull: Option<GituiKeyEvent>,
 	pub abort_merge: Option<GituiKeyEvent>,
 	pub undo_commit: Option<GituiKeyEvent>,
@@ -82,6 +122,11 @@ pub struct KeysListFile {
 	pub view_submodules: Option<GituiKeyEvent>,
 	pub view_submodule_parent: Option<GituiKeyEvent>,
 	pub update_dubmodule: Option<GituiKeyEvent>,
+	pub search_focus_input: Option<GituiKeyEvent>,
+	pub search_toggle_regex: Option<GituiKeyEvent>,
+	pub search_toggle_case: Option<GituiKeyEvent>,
+	pub search_toggle_source: Option<GituiKeyEvent>,
+	pub file_filter: Option<GituiKeyEvent>,
 }
 
 impl KeysListFile {
@@ -93,6 +138,7 @@ impl KeysListFile {
 	}
 
 	#[rustfmt::skip]
+	#[allow(clippy::too_many_lines)]
 	pub fn get_list(self) -> KeysList {
 		let default = KeysList::default();
 
@@ -102,6 +148,7 @@ impl KeysListFile {
 			tab_files: self.tab_files.unwrap_or(default.tab_files),
 			tab_stashing: self.tab_stashing.unwrap_or(default.tab_stashing),
 			tab_stashes: self.tab_stashes.unwrap_or(default.tab_stashes),
+			tab_search: self.tab_search.unwrap_or(default.tab_search),
 			tab_toggle: self.tab_toggle.unwrap_or(default.tab_toggle),
 			tab_toggle_reverse: self.tab_toggle_reverse.unwrap_or(default.tab_toggle_reverse),
 			toggle_workarea: self.toggle_workarea.unwrap_or(default.toggle_workarea),
@@ -115,7 +162,18 @@ impl KeysListFile {
 			open_commit: self.open_commit.unwrap_or(default.open_commit),
 			open_commit_editor: self.open_commit_editor.unwrap_or(default.open_commit_editor),
 			open_help: self.open_help.unwrap_or(default.open_help),
+			open_context_help: self.open_context_help.unwrap_or(default.open_context_help),
 			open_options: self.open_options.unwrap_or(default.open_options),
+			open_notifications: self.open_notifications.unwrap_or(default.open_notifications),
+			open_tasks: self.open_tasks.unwrap_or(default.open_tasks),
+			undo: self.undo.unwrap_or(default.undo),
+			open_discarded: self.open_discarded.unwrap_or(default.open_discarded),
+			open_action_log: self.open_action_log.unwrap_or(default.open_action_log),
+			open_repo_stats: self.open_repo_stats.unwrap_or(default.open_repo_stats),
+			open_maintenance: self.open_maintenance.unwrap_or(default.open_maintenance),
+			open_shell: self.open_shell.unwrap_or(default.open_shell),
+			suspend: self.suspend.unwrap_or(default.suspend),
+			open_pull_requests: self.open_pull_requests.unwrap_or(default.open_pull_requests),
 			move_left: self.move_left.unwrap_or(default.move_left),
 			move_right: self.move_right.unwrap_or(default.move_right),
 			tree_collapse_recursive: self.tree_collapse_recursive.unwrap_or(default.tree_collapse_recursive),
@@ -132,13 +190,24 @@ impl KeysListFile {
 			shift_down: self.shift_down.unwrap_or(default.shift_down),
 			enter: self.enter.unwrap_or(default.enter),
 			blame: self.blame.unwrap_or(default.blame),
+			blame_ignore_revs: self.blame_ignore_revs.unwrap_or(default.blame_ignore_revs),
+			blame_line: self.blame_line.unwrap_or(default.blame_line),
 			edit_file: self.edit_file.unwrap_or(default.edit_file),
+			open_difftool: self.open_difftool.unwrap_or(default.open_difftool),
+			open_mergetool: self.open_mergetool.unwrap_or(default.open_mergetool),
 			file_history: self.file_history.unwrap_or(default.file_history),
 			status_stage_all: self.status_stage_all.unwrap_or(default.status_stage_all),
 			status_reset_item: self.status_reset_item.unwrap_or(default.status_reset_item),
 			status_ignore_file: self.status_ignore_file.unwrap_or(default.status_ignore_file),
+			status_show_ignored: self.status_show_ignored.unwrap_or(default.status_show_ignored),
+			status_commit_item: self.status_commit_item.unwrap_or(default.status_commit_item),
+			status_mark_item: self.status_mark_item.unwrap_or(default.status_mark_item),
 			diff_stage_lines: self.diff_stage_lines.unwrap_or(default.diff_stage_lines),
 			diff_reset_lines: self.diff_reset_lines.unwrap_or(default.diff_reset_lines),
+			diff_load_more: self.diff_load_more.unwrap_or(default.diff_load_more),
+			diff_algorithm: self.diff_algorithm.unwrap_or(default.diff_algorithm),
+			lfs_preview: self.lfs_preview.unwrap_or(default.lfs_preview),
+			reveal_masked: self.reveal_masked.unwrap_or(default.reveal_masked),
 			stashing_save: self.stashing_save.unwrap_or(default.stashing_save),
 			stashing_toggle_untracked: self.stashing_toggle_untracked.unwrap_or(default.stashing_toggle_untracked),
 			stashing_toggle_index: self.stashing_toggle_index.unwrap_or(default.stashing_toggle_index),
@@ -147,23 +216,40 @@ impl KeysListFile {
 			stash_drop: self.stash_drop.unwrap_or(default.stash_drop),
 			cmd_bar_toggle: self.cmd_bar_toggle.unwrap_or(default.cmd_bar_toggle),
 			log_tag_commit: self.log_tag_commit.unwrap_or(default.log_tag_commit),
+			log_edit_note: self.log_edit_note.unwrap_or(default.log_edit_note),
 			log_mark_commit: self.log_mark_commit.unwrap_or(default.log_mark_commit),
+			log_ci_status: self.log_ci_status.unwrap_or(default.log_ci_status),
 			commit_amend: self.commit_amend.unwrap_or(default.commit_amend),
+			commit_anyway: self.commit_anyway.unwrap_or(default.commit_anyway),
+			commit_author: self.commit_author.unwrap_or(default.commit_author),
 			copy: self.copy.unwrap_or(default.copy),
+			copy_describe: self.copy_describe.unwrap_or(default.copy_describe),
+			copy_path_absolute: self.copy_path_absolute.unwrap_or(default.copy_path_absolute),
+			copy_file_content: self.copy_file_content.unwrap_or(default.copy_file_content),
+			copy_range: self.copy_range.unwrap_or(default.copy_range),
+			reveal_file: self.reveal_file.unwrap_or(default.reveal_file),
 			create_branch: self.create_branch.unwrap_or(default.create_branch),
+			create_orphan_branch: self.create_orphan_branch.unwrap_or(default.create_orphan_branch),
 			rename_branch: self.rename_branch.unwrap_or(default.rename_branch),
 			select_branch: self.select_branch.unwrap_or(default.select_branch),
 			delete_branch: self.delete_branch.unwrap_or(default.delete_branch),
 			merge_branch: self.merge_branch.unwrap_or(default.merge_branch),
 			rebase_branch: self.rebase_branch.unwrap_or(default.rebase_branch),
 			compare_commits: self.compare_commits.unwrap_or(default.compare_commits),
+			compare_branch: self.compare_branch.unwrap_or(default.compare_branch),
 			tags: self.tags.unwrap_or(default.tags),
 			delete_tag: self.delete_tag.unwrap_or(default.delete_tag),
+			cancel_task: self.cancel_task.unwrap_or(default.cancel_task),
 			select_tag: self.select_tag.unwrap_or(default.select_tag),
 			push: self.push.unwrap_or(default.push),
 			open_file_tree: self.open_file_tree.unwrap_or(default.open_file_tree),
 			file_find: self.file_find.unwrap_or(default.file_find),
 			force_push: self.force_push.unwrap_or(default.force_push),
+			push_review: self.push_review.unwrap_or(default.push_review),
+			push_options: self.push_options.unwrap_or(default.push_options),
+			push_signed_toggle: self.push_signed_toggle.unwrap_or(default.push_signed_toggle),
+			accept_completion: self.accept_completion.unwrap_or(default.accept_completion),
+			confirm_dont_ask: self.confirm_dont_ask.unwrap_or(default.confirm_dont_ask),
 			pull: self.pull.unwrap_or(default.pull),
 			abort_merge: self.abort_merge.unwrap_or(default.abort_merge),
 			undo_commit: self.undo_commit.unwrap_or(default.undo_commit),
@@ -172,6 +258,11 @@ impl KeysListFile {
 			view_submodules: self.view_submodules.unwrap_or(default.view_submodules),
 			view_submodule_parent: self.view_submodule_parent.unwrap_or(default.view_submodule_parent),
 			update_submodule: self.update_dubmodule.unwrap_or(default.update_submodule),
+			search_focus_input: self.search_focus_input.unwrap_or(default.search_focus_input),
+			search_toggle_regex: self.search_toggle_regex.unwrap_or(default.search_toggle_regex),
+			search_toggle_case: self.search_toggle_case.unwrap_or(default.search_toggle_case),
+			search_toggle_source: self.search_toggle_source.unwrap_or(default.search_toggle_source),
+			file_filter: self.file_filter.unwrap_or(default.file_filter),
 		}
 	}
 }
@@ -190,4 +281,13 @@ mod tests {
 			true
 		);
 	}
+
+	#[test]
+	fn test_check_vim_style_example() {
+		assert!(crate::config_check::check_file::<KeysListFile>(
+			"vim_style_key_config.ron".as_ref(),
+			&[]
+		)
+		.is_empty());
+	}
 }
@@ -4,4 +4,4 @@ mod key_list_file;
 mod symbols;
 
 pub use key_config::{KeyConfig, SharedKeyConfig};
-pub use key_list::key_match;
+pub use key_list::{key_match, GituiKeyEvent};
@@ -24,21 +24,31 @@ mod bug_report;
 mod clipboard;
 mod cmdbar;
 mod components;
+mod config_check;
+mod file_manager;
 mod input;
 mod keys;
 mod notify_mutex;
 mod popup_stack;
+mod print;
 mod profiler;
 mod queue;
+mod refresh;
+mod screen;
+mod secrets;
 mod spinner;
+mod startup;
 mod string_utils;
 mod strings;
 mod tabs;
+mod trash;
 mod ui;
 mod version;
 mod watcher;
 
-use crate::{app::App, args::process_cmdline};
+use crate::{
+	app::App, args::process_cmdline, config_check::ConfigIssue,
+};
 use anyhow::{bail, Result};
 use app::QuitState;
 use asyncgit::{
@@ -47,16 +57,12 @@ use asyncgit::{
 };
 use backtrace::Backtrace;
 use crossbeam_channel::{tick, unbounded, Receiver, Select};
-use crossterm::{
-	terminal::{
-		disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
-		LeaveAlternateScreen,
-	},
-	ExecutableCommand,
-};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use input::{Input, InputEvent, InputState};
 use keys::KeyConfig;
 use profiler::Profiler;
+use refresh::RefreshConfig;
+use secrets::SecretPaths;
 use scopeguard::defer;
 use scopetime::scope_time;
 use spinner::Spinner;
@@ -64,17 +70,17 @@ use std::{
 	cell::RefCell,
 	io::{self, Write},
 	panic, process,
-	time::{Duration, Instant},
+	time::Instant,
 };
+use tabs::TabsConfig;
 use tui::{
 	backend::{Backend, CrosstermBackend},
-	Terminal,
+	Terminal, TerminalOptions, Viewport,
 };
+use screen::ViewportBackend;
 use ui::style::Theme;
 use watcher::RepoWatcher;
 
-static SPINNER_INTERVAL: Duration = Duration::from_millis(80);
-
 ///
 #[derive(Clone)]
 pub enum QueueEvent {
@@ -111,27 +117,78 @@ fn main() -> Result<()> {
 
 	asyncgit::register_tracing_logging();
 
+	if let Some(action) = cliargs.print {
+		print!("{}", print::run(action, &cliargs.repo_path)?);
+		return Ok(());
+	}
+
+	let mut config_issues = KeyConfig::check()
+		.map_err(|e| eprintln!("KeyConfig check error: {}", e))
+		.unwrap_or_default();
+	config_issues.extend(Theme::check(&cliargs.theme));
+	let key_conflicts = KeyConfig::check_conflicts()
+		.map_err(|e| eprintln!("KeyConfig check error: {}", e))
+		.unwrap_or_default();
+
+	if cliargs.check_config {
+		for issue in config_issues.iter().chain(&key_conflicts) {
+			println!("{}", issue);
+		}
+		if !config_issues.is_empty() || !key_conflicts.is_empty() {
+			process::exit(1);
+		}
+		println!("no problems found");
+		return Ok(());
+	}
+
+	// listed in the help, ignoring them can be on purpose
+	for conflict in &key_conflicts {
+		log::warn!("{}", conflict);
+	}
+
 	if !valid_path(&cliargs.repo_path) {
 		eprintln!("invalid path\nplease run gitui inside of a non-bare git repository");
 		return Ok(());
 	}
+	let mut repo_path = cliargs.repo_path.discover()?;
 
 	let key_config = KeyConfig::init()
 		.map_err(|e| eprintln!("KeyConfig loading error: {}", e))
 		.unwrap_or_default();
-	let theme = Theme::init(&cliargs.theme)
-		.map_err(|e| eprintln!("Theme loading error: {}", e))
+	let theme = if cliargs.no_color {
+		Theme::monochrome()
+	} else {
+		Theme::init(&cliargs.theme)
+			.map_err(|e| eprintln!("Theme loading error: {}", e))
+			.unwrap_or_default()
+	};
+	let tabs_config = TabsConfig::init()
+		.map_err(|e| eprintln!("TabsConfig loading error: {}", e))
+		.unwrap_or_default();
+	let refresh_config = RefreshConfig::init()
+		.map_err(|e| eprintln!("RefreshConfig loading error: {}", e))
 		.unwrap_or_default();
+	let secrets = SecretPaths::init()
+		.map_err(|e| eprintln!("SecretPaths loading error: {}", e))
+		.unwrap_or_default();
+
+	startup::mark("config loaded");
+
+	if cliargs.inline.is_some() {
+		screen::set_inline();
+	}
 
 	setup_terminal()?;
 	defer! {
 		shutdown_terminal();
+		if let Some(report) = startup::report() {
+			eprintln!("startup timings:\n{}", report);
+		}
 	}
 
 	set_panic_handlers()?;
 
-	let mut terminal = start_terminal(io::stdout())?;
-	let mut repo_path = cliargs.repo_path;
+	let mut terminal = start_terminal(io::stdout(), cliargs.inline)?;
 	let input = Input::new();
 
 	loop {
@@ -139,6 +196,10 @@ fn main() -> Result<()> {
 			repo_path.clone(),
 			theme,
 			key_config.clone(),
+			tabs_config.clone(),
+			refresh_config,
+			secrets.clone(),
+			&std::mem::take(&mut config_issues),
 			&input,
 			&mut terminal,
 		)?;
@@ -151,23 +212,36 @@ fn main() -> Result<()> {
 		}
 	}
 
+	if screen::is_inline() {
+		screen::leave_area(terminal.get_frame().size())?;
+	}
+
 	Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_app(
 	repo: RepoPath,
 	theme: Theme,
 	key_config: KeyConfig,
+	tabs_config: TabsConfig,
+	refresh: RefreshConfig,
+	secrets: SecretPaths,
+	config_issues: &[ConfigIssue],
 	input: &Input,
-	terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+	terminal: &mut Terminal<ViewportBackend<CrosstermBackend<io::Stdout>>>,
 ) -> Result<QuitState, anyhow::Error> {
 	let (tx_git, rx_git) = unbounded();
 	let (tx_app, rx_app) = unbounded();
 
 	let rx_input = input.receiver();
-	let watcher = RepoWatcher::new(repo_work_dir(&repo)?.as_str())?;
+	let watcher = RepoWatcher::new(
+		repo_work_dir(&repo)?.as_str(),
+		refresh.watcher_debounce,
+	)?;
 	let rx_watcher = watcher.receiver();
-	let spinner_ticker = tick(SPINNER_INTERVAL);
+	let spinner_ticker = tick(refresh.tick);
+	startup::mark("watcher started");
 
 	let mut app = App::new(
 		RefCell::new(repo),
@@ -176,9 +250,22 @@ fn run_app(
 		input.clone(),
 		theme,
 		key_config,
+		tabs_config,
+		secrets,
 	);
+	startup::mark("app created");
+
+	if !config_issues.is_empty() {
+		app.show_config_issues(config_issues)?;
+	}
+
+	// paint the skeleton right away, the tabs stream in their
+	// data (showing placeholders until then)
+	draw(terminal, &app)?;
+	startup::mark("first frame");
+	let mut last_draw = Instant::now();
 
-	let mut spinner = Spinner::default();
+	let mut spinner = Spinner::new(refresh.animations);
 	let mut first_update = true;
 
 	loop {
@@ -197,6 +284,9 @@ fn run_app(
 
 		{
 			if let QueueEvent::SpinnerUpdate = event {
+				app.update_tick();
+				draw(terminal, &app)?;
+				last_draw = Instant::now();
 				spinner.update();
 				spinner.draw(terminal)?;
 				continue;
@@ -204,6 +294,8 @@ fn run_app(
 
 			scope_time!("loop");
 
+			let is_input = matches!(event, QueueEvent::InputEvent(_));
+
 			match event {
 				QueueEvent::InputEvent(ev) => {
 					if let InputEvent::State(InputState::Polling) = ev
@@ -227,7 +319,11 @@ fn run_app(
 				QueueEvent::SpinnerUpdate => unreachable!(),
 			}
 
-			draw(terminal, &app)?;
+			// throttled redraws stay dirty until the next tick
+			if is_input || last_draw.elapsed() >= refresh.min_redraw {
+				draw(terminal, &app)?;
+				last_draw = Instant::now();
+			}
 
 			spinner.set_state(app.any_work_pending());
 			spinner.draw(terminal)?;
@@ -243,13 +339,12 @@ fn run_app(
 
 fn setup_terminal() -> Result<()> {
 	enable_raw_mode()?;
-	io::stdout().execute(EnterAlternateScreen)?;
+	screen::enter()?;
 	Ok(())
 }
 
 fn shutdown_terminal() {
-	let leave_screen =
-		io::stdout().execute(LeaveAlternateScreen).map(|_f| ());
+	let leave_screen = screen::leave();
 
 	if let Err(e) = leave_screen {
 		eprintln!("leave_screen failed:\n{}", e);
@@ -262,12 +357,22 @@ fn shutdown_terminal() {
 	}
 }
 
+/// draws only if anything changed since the last frame
 fn draw<B: Backend>(
 	terminal: &mut Terminal<B>,
 	app: &App,
 ) -> io::Result<()> {
+	let dirty = app.take_dirty();
+
 	if app.requires_redraw() {
-		terminal.resize(terminal.size()?)?;
+		let area = if screen::is_inline() {
+			terminal.get_frame().size()
+		} else {
+			terminal.size()?
+		};
+		terminal.resize(area)?;
+	} else if !dirty {
+		return Ok(());
 	}
 
 	terminal.draw(|f| {
@@ -317,11 +422,24 @@ fn select_event(
 	Ok(ev)
 }
 
+/// `inline` is the height of the viewport in the normal screen,
+/// otherwise the whole (alternate) screen is used
 fn start_terminal<W: Write>(
 	buf: W,
-) -> io::Result<Terminal<CrosstermBackend<W>>> {
+	inline: Option<u16>,
+) -> io::Result<Terminal<ViewportBackend<CrosstermBackend<W>>>> {
 	let backend = CrosstermBackend::new(buf);
-	let mut terminal = Terminal::new(backend)?;
+	let mut terminal = if let Some(height) = inline {
+		let area = screen::inline_area(height)?;
+		Terminal::with_options(
+			ViewportBackend::new(backend, Some(area)),
+			TerminalOptions {
+				viewport: Viewport::fixed(area),
+			},
+		)?
+	} else {
+		Terminal::new(ViewportBackend::new(backend, None))?
+	};
 	terminal.hide_cursor()?;
 	terminal.clear()?;
 
@@ -0,0 +1,126 @@
+//! quick actions printing what gitui would show and exiting right
+//! away, for shell prompts and scripts
+
+use anyhow::Result;
+use asyncgit::sync::{
+	branch::get_branch_name,
+	status::{get_status, StatusItemType, StatusType},
+	RepoPath,
+};
+use itertools::{EitherOrBoth, Itertools};
+use std::path::Path;
+
+/// what to print instead of starting the ui
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrintAction {
+	/// the branch head points to
+	Branch,
+	/// the changes like `git status --porcelain`
+	StatusPorcelain,
+}
+
+/// the output of `action`, ending with a newline unless empty
+pub fn run(action: PrintAction, repo: &RepoPath) -> Result<String> {
+	match action {
+		PrintAction::Branch => {
+			Ok(format!("{}\n", get_branch_name(repo)?))
+		}
+		PrintAction::StatusPorcelain => status_porcelain(repo),
+	}
+}
+
+/// one `XY path` line per changed file, `X` being the staged and `Y`
+/// the unstaged change, untracked files are `??`; unlike git paths
+/// are never quoted and renames lack the original path
+fn status_porcelain(repo: &RepoPath) -> Result<String> {
+	let stage = get_status(repo, StatusType::Stage, None)?;
+	let workdir = get_status(repo, StatusType::WorkingDir, None)?;
+
+	// both are sorted by path
+	Ok(stage
+		.into_iter()
+		.merge_join_by(workdir, |a, b| {
+			Path::new(&a.path).cmp(Path::new(&b.path))
+		})
+		.map(|item| match item {
+			EitherOrBoth::Left(staged) => porcelain_line(
+				&staged.path,
+				Some(staged.status),
+				None,
+			),
+			EitherOrBoth::Right(unstaged) => porcelain_line(
+				&unstaged.path,
+				None,
+				Some(unstaged.status),
+			),
+			EitherOrBoth::Both(staged, unstaged) => porcelain_line(
+				&staged.path,
+				Some(staged.status),
+				Some(unstaged.status),
+			),
+		})
+		.collect())
+}
+
+fn porcelain_line(
+	path: &str,
+	staged: Option<StatusItemType>,
+	unstaged: Option<StatusItemType>,
+) -> String {
+	let code = |status: Option<StatusItemType>| match status {
+		None => ' ',
+		Some(StatusItemType::New) => 'A',
+		Some(StatusItemType::Modified) => 'M',
+		Some(StatusItemType::Deleted) => 'D',
+		Some(StatusItemType::Renamed) => 'R',
+		Some(StatusItemType::Typechange) => 'T',
+		Some(StatusItemType::Conflicted) => 'U',
+	};
+
+	match (staged, unstaged) {
+		(None, Some(StatusItemType::New)) => format!("?? {}\n", path),
+		(Some(StatusItemType::Conflicted), _)
+		| (_, Some(StatusItemType::Conflicted)) => {
+			format!("UU {}\n", path)
+		}
+		_ => format!("{}{} {}\n", code(staged), code(unstaged), path),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_porcelain_line() {
+		assert_eq!(
+			porcelain_line("a", None, Some(StatusItemType::New)),
+			"?? a\n"
+		);
+		assert_eq!(
+			porcelain_line(
+				"a",
+				Some(StatusItemType::New),
+				Some(StatusItemType::Modified)
+			),
+			"AM a\n"
+		);
+		assert_eq!(
+			porcelain_line("a", None, Some(StatusItemType::Deleted)),
+			" D a\n"
+		);
+		assert_eq!(
+			porcelain_line("a", Some(StatusItemType::Renamed), None),
+			"R  a\n"
+		);
+		assert_eq!(
+			porcelain_line(
+				"a",
+				None,
+				Some(StatusItemType::Conflicted)
+			),
+			"UU a\n"
+		);
+	}
+}
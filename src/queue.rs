@@ -1,12 +1,18 @@
 use crate::{
 	components::{
-		AppOption, BlameFileOpen, FileRevOpen, FileTreeOpen,
-		InspectCommitOpen,
+		AppOption, BlameFileOpen, BranchCompareOpen, ExternalTool,
+		FileRevOpen, FileTreeOpen, InspectCommitOpen,
+		NumberInputOpen, SelectListOpen, ToastKind,
 	},
-	tabs::StashingOptions,
+	tabs::{AppTab, StashingOptions},
 };
 use asyncgit::{
-	sync::{diff::DiffLinePosition, CommitId, TreeFile},
+	sync::{
+		cred::BasicAuthCredential, diff::DiffLinePosition,
+		remotes::dry_run::RefUpdate, BranchType, CommitId,
+		CommitInfo, IndexProblem, PostHook, PushCliOptions,
+		PushSummary, TreeFile, UndoEntry,
+	},
 	PushType,
 };
 use bitflags::bitflags;
@@ -36,9 +42,36 @@ pub struct ResetItem {
 	pub is_folder: bool,
 }
 
+/// operations that need a clean worktree, see `Action::AutoStash`
+pub enum AutoStashOperation {
+	/// branch reference
+	Checkout(String),
+	/// branch name
+	Rebase(String, BranchType),
+}
+
+impl AutoStashOperation {
+	/// used in the stash message
+	pub fn name(&self) -> String {
+		match self {
+			Self::Checkout(reference) => format!(
+				"checkout {}",
+				reference
+					.trim_start_matches("refs/heads/")
+					.trim_start_matches("refs/remotes/")
+			),
+			Self::Rebase(branch, _) => {
+				format!("rebase on {}", branch)
+			}
+		}
+	}
+}
+
 ///
 pub enum Action {
 	Reset(ResetItem),
+	/// discard the changes of the files marked in the status tab
+	ResetMarked(Vec<String>),
 	ResetHunk(String, u64),
 	ResetLines(String, Vec<DiffLinePosition>),
 	StashDrop(Vec<CommitId>),
@@ -47,11 +80,75 @@ pub enum Action {
 	DeleteRemoteBranch(String),
 	DeleteTag(String),
 	DeleteRemoteTag(String, String),
-	ForcePush(String, bool),
+	/// with the commits on the remote it overwrites
+	ForcePush(String, Vec<CommitInfo>),
+	/// follow up the local rename of `branch` on its upstream `old`
+	RenameRemoteBranch {
+		branch: String,
+		remote: String,
+		old: String,
+	},
 	PullMerge { incoming: usize, rebase: bool },
 	AbortMerge,
 	AbortRebase,
 	AbortRevert,
+	/// remember what was typed in for the url of the remote
+	StoreCredentials(String, BasicAuthCredential),
+	/// stash local changes, run the operation and re-apply them
+	AutoStash(AutoStashOperation),
+	/// commit to a branch protected via `gitui.protectedBranch`
+	CommitProtected(String),
+	/// undo the last commit on a protected branch
+	UndoCommitProtected(String),
+	/// amend the last commit with the message in the commit popup
+	Amend,
+	/// restore the state before the last destructive operation
+	Undo(UndoEntry),
+	/// write back changes a discard snapshot kept
+	RestoreDiscarded(UndoEntry),
+	/// push after its dry run (`gitui.dryRun`) listed the refs
+	PushPreviewed(Vec<RefUpdate>),
+	/// fetch after its dry run (`gitui.dryRun`) listed the refs
+	FetchPreviewed(Vec<RefUpdate>),
+	/// replace an index status fails to read with the tree of `HEAD`
+	RebuildIndex(IndexProblem),
+	/// run an action whose undo snapshot failed (the error) anyway
+	WithoutUndo(Box<Self>, String),
+}
+
+impl Action {
+	/// stable name used to persist per action preferences
+	pub const fn kind(&self) -> &'static str {
+		match self {
+			Self::Reset(_) => "reset",
+			Self::ResetMarked(_) => "reset_marked",
+			Self::ResetHunk(..) => "reset_hunk",
+			Self::ResetLines(..) => "reset_lines",
+			Self::StashDrop(_) => "stash_drop",
+			Self::StashPop(_) => "stash_pop",
+			Self::DeleteLocalBranch(_) => "delete_branch",
+			Self::DeleteRemoteBranch(_) => "delete_remote_branch",
+			Self::DeleteTag(_) => "delete_tag",
+			Self::DeleteRemoteTag(..) => "delete_remote_tag",
+			Self::ForcePush(..) => "force_push",
+			Self::RenameRemoteBranch { .. } => "rename_remote_branch",
+			Self::PullMerge { .. } => "pull_merge",
+			Self::AbortMerge => "abort_merge",
+			Self::AbortRebase => "abort_rebase",
+			Self::AbortRevert => "abort_revert",
+			Self::StoreCredentials(..) => "store_credentials",
+			Self::AutoStash(_) => "autostash",
+			Self::CommitProtected(_) => "commit_protected",
+			Self::UndoCommitProtected(_) => "undo_commit_protected",
+			Self::Amend => "amend",
+			Self::Undo(_) => "undo",
+			Self::RestoreDiscarded(_) => "restore_discarded",
+			Self::PushPreviewed(_) => "push_dry_run",
+			Self::FetchPreviewed(_) => "fetch_dry_run",
+			Self::RebuildIndex(_) => "rebuild_index",
+			Self::WithoutUndo(..) => "without_undo",
+		}
+	}
 }
 
 #[derive(Debug)]
@@ -66,6 +163,23 @@ pub enum StackablePopupOpen {
 	InspectCommit(InspectCommitOpen),
 	///
 	CompareCommits(InspectCommitOpen),
+	///
+	CompareBranches(BranchCompareOpen),
+}
+
+/// what a value picked in `SelectListComponent` or
+/// `NumberInputComponent` gets applied to
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PickTarget {
+	Option(AppOption),
+	/// index into `sync::undo_entries` that are discards
+	Discarded,
+	/// index into `sync::action_log`
+	ActionLog,
+	/// index into `sync::get_ignored_files`
+	IgnoredFiles,
+	/// index into `MaintenanceTask::ALL`
+	Maintenance,
 }
 
 ///
@@ -78,32 +192,60 @@ pub enum InternalEvent {
 	ShowErrorMsg(String),
 	///
 	ShowInfoMsg(String),
+	/// what a push did, shown once it finished
+	ShowPushSummary(PushSummary),
+	/// transient notification that does not interrupt the user
+	ShowToast(ToastKind, String),
+	/// runs a post-* hook in the background
+	RunPostHook(PostHook),
 	///
 	Update(NeedsUpdate),
 	///
 	StatusLastFileMoved,
 	/// open commit msg input
 	OpenCommit,
+	/// open commit msg input to commit only these paths
+	OpenCommitPaths(Vec<String>),
 	///
 	PopupStashing(StashingOptions),
 	///
 	TabSwitchStatus,
 	///
+	TabSwitchStashes,
+	/// show the tab, if it is configured
+	TabSwitch(AppTab),
+	///
 	SelectCommitInRevlog(CommitId),
 	///
 	TagCommit(CommitId),
+	/// add or amend the note of a commit
+	EditNote(CommitId),
 	///
 	Tags,
 	///
 	CreateBranch,
 	///
+	CreateOrphanBranch,
+	/// create a branch at the commit without switching to it
+	CreateBranchAt(CommitId),
+	///
 	RenameBranch(String, String),
 	///
 	SelectBranch,
 	///
 	OpenExternalEditor(Option<String>),
 	///
+	OpenExternalTool(ExternalTool),
+	///
 	Push(String, PushType, bool, bool),
+	/// open topic popup to push branch for review
+	OpenPushReview(String),
+	/// push branch using the given refspec
+	PushReview(String, String),
+	/// open popup to push branch with push options or signed
+	OpenPushOptions(String),
+	/// push branch via the git cli with the given options
+	PushWithOptions(String, PushCliOptions),
 	///
 	Pull(String),
 	///
@@ -111,9 +253,17 @@ pub enum InternalEvent {
 	///
 	OptionSwitched(AppOption),
 	///
+	OpenSelectList(SelectListOpen),
+	///
+	OpenNumberInput(NumberInputOpen),
+	/// index of the list item selected or the number entered
+	Picked(PickTarget, usize),
+	///
 	OpenFileFinder(Vec<TreeFile>),
 	///
 	FileFinderChanged(Option<PathBuf>),
+	/// show file (path relative to the repo root) in the files tab
+	JumpToFile(String),
 	///
 	FetchRemotes,
 	///
@@ -124,6 +274,8 @@ pub enum InternalEvent {
 	PopupStackPush(StackablePopupOpen),
 	///
 	ViewSubmodules,
+	/// lists the ignored files with their ignore rules
+	ViewIgnored,
 	///
 	OpenRepo { path: PathBuf },
 }
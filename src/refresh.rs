@@ -0,0 +1,95 @@
+use crate::args::get_app_config_path;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Read, path::PathBuf, time::Duration};
+
+/// how often the ui updates, see `low_refresh` in `refresh.ron`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshConfig {
+	/// spinner and periodic ui refresh
+	pub tick: Duration,
+	pub animations: bool,
+	/// changes in the workdir are collected this long
+	pub watcher_debounce: Duration,
+	/// async results redraw at most this often, input always redraws
+	pub min_redraw: Duration,
+}
+
+impl Default for RefreshConfig {
+	fn default() -> Self {
+		Self {
+			tick: Duration::from_millis(80),
+			animations: true,
+			watcher_debounce: Duration::from_secs(2),
+			min_redraw: Duration::ZERO,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct RefreshConfigFile {
+	low_refresh: Option<bool>,
+}
+
+impl RefreshConfig {
+	/// for high latency (ssh) connections where every redraw hurts
+	pub const fn low() -> Self {
+		Self {
+			tick: Duration::from_secs(1),
+			animations: false,
+			watcher_debounce: Duration::from_secs(10),
+			min_redraw: Duration::from_millis(500),
+		}
+	}
+
+	fn get_config_file() -> Result<PathBuf> {
+		let app_home = get_app_config_path()?;
+		Ok(app_home.join("refresh.ron"))
+	}
+
+	fn read_file(config_file: PathBuf) -> Result<RefreshConfigFile> {
+		let mut f = File::open(config_file)?;
+		let mut buffer = Vec::new();
+		f.read_to_end(&mut buffer)?;
+		Ok(ron::de::from_bytes(&buffer)?)
+	}
+
+	pub fn init() -> Result<Self> {
+		let file = Self::get_config_file()?;
+		if file.exists() {
+			Ok(Self::from_file(&Self::read_file(file)?))
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	fn from_file(file: &RefreshConfigFile) -> Self {
+		if file.low_refresh.unwrap_or_default() {
+			Self::low()
+		} else {
+			Self::default()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		let file: RefreshConfigFile =
+			ron::de::from_str("(low_refresh: Some(true))").unwrap();
+		assert_eq!(
+			RefreshConfig::from_file(&file),
+			RefreshConfig::low()
+		);
+
+		let file: RefreshConfigFile =
+			ron::de::from_str("()").unwrap();
+		assert_eq!(
+			RefreshConfig::from_file(&file),
+			RefreshConfig::default()
+		);
+	}
+}
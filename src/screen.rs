@@ -0,0 +1,178 @@
+//! the screen gitui draws on: the alternate screen or, with
+//! `--inline`, a fixed number of lines of the normal screen below the
+//! prompt so the scrollback stays untouched
+
+use crossterm::{
+	cursor::{self, MoveTo},
+	terminal::{
+		self, Clear, ClearType, EnterAlternateScreen,
+		LeaveAlternateScreen,
+	},
+	ExecutableCommand, QueueableCommand,
+};
+use std::{
+	io::{self, Write},
+	sync::atomic::{AtomicBool, Ordering},
+};
+use tui::{backend::Backend, buffer::Cell, layout::Rect};
+
+static INLINE: AtomicBool = AtomicBool::new(false);
+
+/// draws in the normal screen from now on, to be set before `enter`
+pub fn set_inline() {
+	INLINE.store(true, Ordering::Relaxed);
+}
+
+///
+pub fn is_inline() -> bool {
+	INLINE.load(Ordering::Relaxed)
+}
+
+/// switches to the screen gitui draws on
+pub fn enter() -> io::Result<()> {
+	if !is_inline() {
+		io::stdout().execute(EnterAlternateScreen)?;
+	}
+
+	Ok(())
+}
+
+/// back to the screen gitui was started on
+pub fn leave() -> io::Result<()> {
+	if !is_inline() {
+		io::stdout().execute(LeaveAlternateScreen)?;
+	}
+
+	Ok(())
+}
+
+/// hands the terminal to another program (editor, shell): inline the
+/// program gets the alternate screen so it can't scroll our lines away
+pub fn release() -> io::Result<()> {
+	if is_inline() {
+		io::stdout().execute(EnterAlternateScreen)?;
+	} else {
+		io::stdout().execute(LeaveAlternateScreen)?;
+	}
+
+	Ok(())
+}
+
+/// takes the terminal back after `release`
+pub fn acquire() -> io::Result<()> {
+	if is_inline() {
+		io::stdout().execute(LeaveAlternateScreen)?;
+	} else {
+		io::stdout().execute(EnterAlternateScreen)?;
+	}
+
+	Ok(())
+}
+
+/// makes room for `height` lines (at most the terminal height) from
+/// the cursor on, scrolling the screen if needed, and clears them;
+/// expects raw mode
+pub fn inline_area(height: u16) -> io::Result<Rect> {
+	let (width, rows) = terminal::size()?;
+	let height = height.clamp(1, rows.max(1));
+
+	let mut stdout = io::stdout();
+	stdout.queue(cursor::MoveToColumn(0))?;
+	for _ in 1..height {
+		stdout.queue(crossterm::style::Print("\n"))?;
+	}
+	stdout.flush()?;
+
+	// the last line of the area is where the cursor ended up
+	let (_, bottom) = cursor::position()?;
+	let area = Rect::new(
+		0,
+		(bottom + 1).saturating_sub(height),
+		width,
+		height,
+	);
+
+	stdout
+		.queue(MoveTo(0, area.y))?
+		.queue(Clear(ClearType::FromCursorDown))?
+		.flush()?;
+
+	Ok(area)
+}
+
+/// forwards to `B`, drawing the cells of an inline viewport at its
+/// position: tui passes them relative to the top left corner
+pub struct ViewportBackend<B: Backend> {
+	backend: B,
+	inline: Option<Rect>,
+}
+
+impl<B: Backend> ViewportBackend<B> {
+	///
+	pub const fn new(backend: B, inline: Option<Rect>) -> Self {
+		Self { backend, inline }
+	}
+}
+
+impl<B: Backend> Backend for ViewportBackend<B> {
+	fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+	where
+		I: Iterator<Item = (u16, u16, &'a Cell)>,
+	{
+		let (dx, dy) =
+			self.inline.map_or((0, 0), |area| (area.x, area.y));
+
+		self.backend
+			.draw(content.map(|(x, y, cell)| (x + dx, y + dy, cell)))
+	}
+
+	fn hide_cursor(&mut self) -> io::Result<()> {
+		self.backend.hide_cursor()
+	}
+
+	fn show_cursor(&mut self) -> io::Result<()> {
+		self.backend.show_cursor()
+	}
+
+	fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+		self.backend.get_cursor()
+	}
+
+	fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+		self.backend.set_cursor(x, y)
+	}
+
+	/// inline only the viewport gets cleared
+	fn clear(&mut self) -> io::Result<()> {
+		if let Some(area) = self.inline {
+			let blank = Cell::default();
+			self.backend.draw(
+				(area.top()..area.bottom())
+					.flat_map(|y| {
+						(area.left()..area.right())
+							.map(move |x| (x, y))
+					})
+					.map(|(x, y)| (x, y, &blank)),
+			)?;
+			self.backend.flush()
+		} else {
+			self.backend.clear()
+		}
+	}
+
+	fn size(&self) -> io::Result<Rect> {
+		self.backend.size()
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.backend.flush()
+	}
+}
+
+/// puts the cursor on a new line below `area` for the prompt
+pub fn leave_area(area: Rect) -> io::Result<()> {
+	io::stdout()
+		.queue(MoveTo(0, area.bottom().saturating_sub(1)))?
+		.queue(crossterm::style::Print("\r\n"))?
+		.flush()
+}
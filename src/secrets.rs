@@ -0,0 +1,160 @@
+use crate::args::get_app_config_path;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Read, path::PathBuf};
+
+/// patterns used unless `secrets.ron` says otherwise
+const DEFAULT_PATTERNS: &[&str] = &[
+	".env",
+	".env.*",
+	"*.pem",
+	"*.key",
+	"*.p12",
+	"id_rsa",
+	"id_ecdsa",
+	"id_ed25519",
+];
+
+/// files whose content is masked in diffs and file previews
+/// until revealed, see `patterns` in `secrets.ron`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretPaths {
+	/// `*` and `?` wildcards, matched against the file name
+	/// unless the pattern contains a `/`
+	patterns: Vec<String>,
+}
+
+impl Default for SecretPaths {
+	fn default() -> Self {
+		Self {
+			patterns: DEFAULT_PATTERNS
+				.iter()
+				.map(ToString::to_string)
+				.collect(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct SecretPathsFile {
+	patterns: Option<Vec<String>>,
+}
+
+impl SecretPaths {
+	fn get_config_file() -> Result<PathBuf> {
+		let app_home = get_app_config_path()?;
+		Ok(app_home.join("secrets.ron"))
+	}
+
+	fn read_file(config_file: PathBuf) -> Result<SecretPathsFile> {
+		let mut f = File::open(config_file)?;
+		let mut buffer = Vec::new();
+		f.read_to_end(&mut buffer)?;
+		Ok(ron::de::from_bytes(&buffer)?)
+	}
+
+	pub fn init() -> Result<Self> {
+		let file = Self::get_config_file()?;
+		if file.exists() {
+			Ok(Self::from_file(Self::read_file(file)?))
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	fn from_file(file: SecretPathsFile) -> Self {
+		file.patterns
+			.map_or_else(Self::default, |patterns| Self { patterns })
+	}
+
+	/// the pattern matching `path` (relative to the repo root)
+	pub fn matching(&self, path: &str) -> Option<&str> {
+		let file_name = path.rsplit('/').next().unwrap_or(path);
+
+		self.patterns
+			.iter()
+			.find(|pattern| {
+				if pattern.contains('/') {
+					wildcard_match(
+						pattern.trim_start_matches('/'),
+						path,
+					)
+				} else {
+					wildcard_match(pattern, file_name)
+				}
+			})
+			.map(String::as_str)
+	}
+}
+
+/// `*` matches any run of characters (including `/`), `?` any single one
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+	let pattern = pattern.chars().collect::<Vec<_>>();
+	let text = text.chars().collect::<Vec<_>>();
+
+	let (mut p, mut t) = (0, 0);
+	// position of the last `*` and the text position it matched up to
+	let mut backtrack = None;
+
+	while t < text.len() {
+		match pattern.get(p) {
+			Some('*') => {
+				backtrack = Some((p, t));
+				p += 1;
+			}
+			Some(c) if *c == '?' || *c == text[t] => {
+				p += 1;
+				t += 1;
+			}
+			_ => match backtrack {
+				Some((star, matched)) => {
+					p = star + 1;
+					t = matched + 1;
+					backtrack = Some((star, matched + 1));
+				}
+				None => return false,
+			},
+		}
+	}
+
+	pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_wildcard_match() {
+		assert!(wildcard_match("*.pem", "cert.pem"));
+		assert!(wildcard_match(".env.*", ".env.local"));
+		assert!(wildcard_match("id_?sa", "id_rsa"));
+		assert!(wildcard_match("*a*b", "xaxxb"));
+		assert!(!wildcard_match("*.pem", "cert.pem.txt"));
+		assert!(!wildcard_match(".env", ".env.local"));
+	}
+
+	#[test]
+	fn test_matching() {
+		let secrets = SecretPaths::default();
+
+		assert_eq!(secrets.matching("config/.env"), Some(".env"));
+		assert_eq!(
+			secrets.matching("certs/server.pem"),
+			Some("*.pem")
+		);
+		assert_eq!(secrets.matching("src/env.rs"), None);
+
+		let file: SecretPathsFile =
+			ron::de::from_str(r#"(patterns: Some(["/deploy/*"]))"#)
+				.unwrap();
+		let secrets = SecretPaths::from_file(file);
+
+		assert_eq!(
+			secrets.matching("deploy/vars"),
+			Some("/deploy/*")
+		);
+		assert_eq!(secrets.matching("other/deploy/vars"), None);
+		assert_eq!(secrets.matching(".env"), None);
+	}
+}
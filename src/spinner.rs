@@ -10,24 +10,28 @@ static SPINNER_CHARS: &[char] =
 pub struct Spinner {
 	idx: usize,
 	active: bool,
+	/// a still spinner only shows that work is pending
+	animated: bool,
 	last_char: Cell<char>,
 }
 
-impl Default for Spinner {
-	fn default() -> Self {
+impl Spinner {
+	///
+	pub const fn new(animated: bool) -> Self {
 		Self {
 			idx: 0,
 			active: false,
+			animated,
 			last_char: Cell::new(' '),
 		}
 	}
-}
 
-impl Spinner {
 	/// increment spinner graphic by one
 	pub fn update(&mut self) {
-		self.idx += 1;
-		self.idx %= SPINNER_CHARS.len();
+		if self.animated {
+			self.idx += 1;
+			self.idx %= SPINNER_CHARS.len();
+		}
 	}
 
 	///
@@ -0,0 +1,75 @@
+//! timings of the startup phases, reported on exit with `--debug-startup`
+
+use once_cell::sync::Lazy;
+use std::{
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+struct Timings {
+	start: Instant,
+	phases: Vec<(&'static str, Duration)>,
+}
+
+static TIMINGS: Lazy<Mutex<Option<Timings>>> =
+	Lazy::new(|| Mutex::new(None));
+
+/// starts recording, phases before this are not tracked
+pub fn enable() {
+	if let Ok(mut timings) = TIMINGS.lock() {
+		*timings = Some(Timings {
+			start: Instant::now(),
+			phases: Vec::new(),
+		});
+	}
+}
+
+/// records the time since `enable` the first time `phase` is reached
+pub fn mark(phase: &'static str) {
+	if let Ok(mut timings) = TIMINGS.lock() {
+		if let Some(timings) = timings.as_mut() {
+			if timings.phases.iter().all(|(name, _)| *name != phase) {
+				let elapsed = timings.start.elapsed();
+				log::info!("startup: {} after {:?}", phase, elapsed);
+				timings.phases.push((phase, elapsed));
+			}
+		}
+	}
+}
+
+/// one line per recorded phase, `None` if not enabled
+pub fn report() -> Option<String> {
+	let phases = TIMINGS.lock().ok()?.as_ref()?.phases.clone();
+
+	Some(format_phases(&phases))
+}
+
+fn format_phases(phases: &[(&'static str, Duration)]) -> String {
+	phases
+		.iter()
+		.map(|(name, elapsed)| {
+			format!(
+				"{:>8.1}ms  {}",
+				elapsed.as_secs_f64() * 1000.0,
+				name
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_format_phases() {
+		assert_eq!(
+			format_phases(&[
+				("app", Duration::from_micros(1500)),
+				("first frame", Duration::from_millis(20)),
+			]),
+			"     1.5ms  app\n    20.0ms  first frame"
+		);
+	}
+}
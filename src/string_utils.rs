@@ -1,15 +1,96 @@
-///
+use std::borrow::Cow;
+use unicode_bidi::BidiInfo;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// appended to strings cut short by `truncate_to_width`
+const ELLIPSIS: &str = "..";
+
+/// longest suffix of `s` that fits into `width` display columns,
+/// never splits a grapheme
 pub fn trim_length_left(s: &str, width: usize) -> &str {
-	let len = s.len();
-	if len > width {
-		for i in len - width..len {
-			if s.is_char_boundary(i) {
-				return &s[i..];
+	let mut used = 0;
+	let mut start = s.len();
+
+	for (idx, grapheme) in s.grapheme_indices(true).rev() {
+		used += grapheme.width();
+		if used > width {
+			break;
+		}
+		start = idx;
+	}
+
+	&s[start..]
+}
+
+/// longest prefix of `s` that fits into `width` display columns,
+/// never splits a grapheme
+pub fn trim_length_right(s: &str, width: usize) -> &str {
+	let mut used = 0;
+	let mut end = 0;
+
+	for (idx, grapheme) in s.grapheme_indices(true) {
+		used += grapheme.width();
+		if used > width {
+			break;
+		}
+		end = idx + grapheme.len();
+	}
+
+	&s[..end]
+}
+
+/// cuts `s` down to at most `width` display columns, marking a cut
+/// with a trailing `..`
+pub fn truncate_to_width(s: &str, width: usize) -> Cow<'_, str> {
+	if s.width() <= width {
+		return Cow::Borrowed(s);
+	}
+
+	let head =
+		trim_length_right(s, width.saturating_sub(ELLIPSIS.len()));
+	let ellipsis = trim_length_right(ELLIPSIS, width);
+
+	Cow::Owned(format!("{}{}", head, ellipsis))
+}
+
+/// pads `s` with spaces to `width` display columns,
+/// unlike `format!("{:w$}")` this counts columns not chars
+pub fn pad_to_width(s: &str, width: usize) -> String {
+	let padding = width.saturating_sub(s.width());
+	format!("{}{:padding$}", s, "", padding = padding)
+}
+
+/// `s` truncated and padded to exactly `width` display columns
+pub fn fit_to_width(s: &str, width: usize) -> String {
+	pad_to_width(&truncate_to_width(s, width), width)
+}
+
+/// `s` in display order, so right-to-left scripts (arabic, hebrew)
+/// read correctly in a terminal that prints left to right.
+/// rtl runs get reversed by grapheme to keep combining marks
+/// attached, bracket mirroring is not done.
+pub fn bidi_visual_order(s: &str) -> Cow<'_, str> {
+	let info = BidiInfo::new(s, None);
+	if !info.has_rtl() {
+		return Cow::Borrowed(s);
+	}
+
+	let mut result = String::with_capacity(s.len());
+	for para in &info.paragraphs {
+		let (levels, runs) =
+			info.visual_runs(para, para.range.clone());
+		for run in runs {
+			let text = &s[run.clone()];
+			if levels[run.start].is_rtl() {
+				result.extend(text.graphemes(true).rev());
+			} else {
+				result.push_str(text);
 			}
 		}
 	}
 
-	s
+	Cow::Owned(result)
 }
 
 //TODO: allow customize tabsize
@@ -24,12 +105,66 @@ pub fn tabs_to_spaces(input: String) -> String {
 #[cfg(test)]
 mod test {
 	use pretty_assertions::assert_eq;
+	use unicode_width::UnicodeWidthStr;
 
-	use crate::string_utils::trim_length_left;
+	use crate::string_utils::{
+		bidi_visual_order, fit_to_width, trim_length_left,
+		trim_length_right, truncate_to_width,
+	};
 
 	#[test]
 	fn test_trim() {
 		assert_eq!(trim_length_left("👍foo", 3), "foo");
 		assert_eq!(trim_length_left("👍foo", 4), "foo");
+		assert_eq!(trim_length_left("👍foo", 5), "👍foo");
+		assert_eq!(trim_length_right("👍foo", 1), "");
+		assert_eq!(trim_length_right("👍foo", 3), "👍f");
+	}
+
+	#[test]
+	fn test_trim_graphemes() {
+		// 'e' followed by a combining acute accent
+		assert_eq!(trim_length_right("e\u{301}x", 1), "e\u{301}");
+		assert_eq!(trim_length_left("xe\u{301}", 1), "e\u{301}");
+		assert_eq!(trim_length_right("日本語", 3), "日");
+		assert_eq!(trim_length_left("日本語", 5), "本語");
+	}
+
+	#[test]
+	fn test_truncate_to_width() {
+		assert_eq!(truncate_to_width("main", 4), "main");
+		assert_eq!(truncate_to_width("feature", 5), "fea..");
+		assert_eq!(truncate_to_width("feature", 1), ".");
+		assert_eq!(truncate_to_width("日本語ブランチ", 7), "日本..");
+	}
+
+	#[test]
+	fn test_fit_to_width() {
+		for s in [
+			"日本語ブランチ",
+			"👍👍👍",
+			"abc",
+			"e\u{301}e\u{301}e\u{301}",
+		] {
+			for width in 0..10 {
+				assert_eq!(fit_to_width(s, width).width(), width);
+			}
+		}
+		assert_eq!(fit_to_width("日本語", 5), "日.. ");
+	}
+
+	#[test]
+	fn test_bidi_visual_order() {
+		assert!(matches!(
+			bidi_visual_order("fix typo"),
+			std::borrow::Cow::Borrowed("fix typo")
+		));
+		assert_eq!(bidi_visual_order("שלום"), "םולש");
+		assert_eq!(bidi_visual_order("fix שלום bug"), "fix םולש bug");
+		// combining mark (dagesh) stays behind its base letter
+		assert_eq!(
+			bidi_visual_order("\u{5d0}\u{5d1}\u{5bc}"),
+			"\u{5d1}\u{5bc}\u{5d0}"
+		);
 	}
 }
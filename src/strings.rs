@@ -1,6 +1,15 @@
-use asyncgit::sync::CommitId;
+use asyncgit::sync::{
+	lfs::LfsPointer, remotes::dry_run::RefChange,
+	repo_stats::RepoStats, CommitId, FileDiffStat, IndexProblem,
+	LintWarning, MaintenanceTask, UndoEntry, UndoOperation,
+};
+use bytesize::ByteSize;
+use itertools::Itertools;
 
-use crate::keys::SharedKeyConfig;
+use crate::{
+	components::HelpMode, config_check::ConfigIssue,
+	keys::SharedKeyConfig,
+};
 
 pub mod order {
 	pub static NAV: i8 = 2;
@@ -9,13 +18,87 @@ pub mod order {
 
 pub static PUSH_POPUP_MSG: &str = "Push";
 pub static FORCE_PUSH_POPUP_MSG: &str = "Force Push";
+pub static PUSH_REVIEW_POPUP_MSG: &str = "Push for review";
+pub static RENAME_REMOTE_POPUP_MSG: &str = "Rename remote branch";
 pub static PULL_POPUP_MSG: &str = "Pull";
 pub static FETCH_POPUP_MSG: &str = "Fetch";
+pub static DRY_RUN_POPUP_MSG: &str = "Checking remote";
+pub static DIVERGENCE_POPUP_MSG: &str = "Checking remote commits";
+pub static MAINTENANCE_TITLE: &str = "Maintenance";
+pub static STATUS_BAR_NO_BRANCH: &str = "(no branch)";
+pub static NOTIFICATIONS_EMPTY: &str = "no notifications yet";
+pub static TASKS_EMPTY: &str = "no background tasks running";
+pub static TASKS_CANCELLING: &str = " (cancelling)";
+pub static PULL_REQUESTS_LOADING: &str = "loading pull requests...";
+pub static PULL_REQUESTS_EMPTY: &str = "no open pull requests";
+pub static COMPLETION_LOADING: &str = "loading...";
+pub static HEAD_CHANGED_EXTERNALLY: &str =
+	"HEAD changed outside of gitui";
+pub static SHELL_HINT: &str = "gitui: exit the shell to return";
+pub static MSG_SEARCH_HINT: &str = "type to search..";
+pub static MSG_COPIED: &str = "copied message to clipboard";
+pub static MSG_RANGE_COPIED: &str =
+	"copied commit range to clipboard";
+pub static PATH_COPIED: &str = "copied path to clipboard";
+pub static CONFIRM_CONSEQUENCES: &str = "This will discard:";
+pub static CONFIRM_REF_UPDATES: &str = "This will change:";
+pub static CONFIRM_OVERWRITTEN: &str =
+	"This will drop these commits from the remote:";
+pub static UNDO_NOTHING: &str = "nothing to undo";
+pub static UNDO_DONE: &str = "undone";
+pub static DISCARDED_TITLE: &str = "Discarded Changes";
+pub static DISCARDED_EMPTY: &str = "no discarded changes kept";
+pub static DISCARDED_RESTORED: &str = "restored discarded changes";
+pub static REBUILD_INDEX_CONSEQUENCE: &str =
+	"what is staged, the working directory stays as it is";
+pub static TOAST_INDEX_REBUILT: &str =
+	"index rebuilt from HEAD, the broken one is in .git/index.broken";
+pub static ACTION_LOG_TITLE: &str = "Action Log";
+pub static ACTION_LOG_EMPTY: &str = "no git actions logged yet";
+pub static ACTION_LOG_COPIED: &str = "copied action to clipboard";
+pub static CI_STATUS_NONE: &str = "no ci status for this commit";
+pub static TITLE_DIFF_TEXTCONV: &str = " (textconv)";
+pub static TITLE_DIFF_PRETTY: &str = " (pretty printed)";
+pub static IGNORED_FILES_TITLE: &str = "Ignored Files";
+pub static IGNORED_FILES_EMPTY: &str = "no ignored files";
+pub static IGNORED_FILES_COPIED: &str =
+	"copied ignore rule to clipboard";
+pub static COMPLETION_BRANCH_EXISTS: &str = "(exists)";
+pub static BRANCH_NAME_EXISTS: &str = "[branch exists]";
+pub static BRANCH_NAME_EXISTS_REMOTE: &str =
+	"[exists on remote, consider checking it out]";
+pub static TAG_NAME_EXISTS: &str = "[tag exists]";
+pub static REVIEW_TOPIC_INVALID: &str = "[no spaces, commas or %]";
+pub static COMMIT_AUTHOR_INVALID: &str = "[expected: Name <email>]";
+pub static COMMIT_DATE_INVALID: &str = "[invalid date]";
+pub static COMMIT_PATHS_UNAVAILABLE: &str =
+	"cannot commit single files while merging or reverting";
+pub static TOAST_COMMITTED: &str = "committed";
+pub static AUTOSTASH_RESTORED: &str =
+	"local changes stashed and re-applied";
+pub static AUTOSTASH_KEPT_PENDING: &str =
+	"local changes kept in the stash list, pop them when done";
+pub static AUTOSTASH_KEPT_CONFLICT: &str =
+	"local changes conflict, kept in the stash list";
+pub static TOAST_GPG_BAD_PASSPHRASE: &str = "gpg: bad passphrase";
+pub static TOAST_PUSHED: &str = "pushed";
+pub static TOAST_STASHED: &str = "stashed";
+pub static TOAST_FETCHED: &str = "fetched";
+pub static TOAST_UP_TO_DATE: &str = "everything up-to-date";
+pub static TOAST_REPO_STATS: &str =
+	"gathering repository statistics..";
+pub static TOAST_MAINTENANCE_DONE: &str = "maintenance done";
+pub static TOAST_PUSH_CANCELLED: &str = "push cancelled";
+pub static TOAST_PULL_CANCELLED: &str = "pull cancelled";
+pub static TOAST_FETCH_CANCELLED: &str = "fetch cancelled";
 pub static PUSH_POPUP_PROGRESS_NONE: &str = "preparing...";
-pub static PUSH_POPUP_STATES_ADDING: &str = "adding objects (1/3)";
-pub static PUSH_POPUP_STATES_DELTAS: &str = "deltas (2/3)";
+pub static PUSH_POPUP_STATES_ADDING: &str = "counting objects (1/3)";
+pub static PUSH_POPUP_STATES_DELTAS: &str =
+	"compressing objects (2/3)";
 pub static PUSH_POPUP_STATES_PUSHING: &str = "pushing (3/3)";
-pub static PUSH_POPUP_STATES_TRANSFER: &str = "transfer";
+pub static PUSH_POPUP_STATES_TRANSFER: &str = "receiving objects";
+pub static PUSH_POPUP_STATES_RESOLVING: &str = "resolving deltas";
+pub static REMOTE_PROGRESS_CANCELLING: &str = "cancelling...";
 pub static PUSH_POPUP_STATES_DONE: &str = "done";
 
 pub static PUSH_TAGS_POPUP_MSG: &str = "Push Tags";
@@ -29,6 +112,8 @@ pub static POPUP_TITLE_FUZZY_FIND: &str = "Fuzzy Finder";
 pub mod symbol {
 	pub const WHITESPACE: &str = "\u{00B7}"; //·
 	pub const CHECKMARK: &str = "\u{2713}"; //✓
+	pub const BALLOT_X: &str = "\u{2717}"; //✗
+	pub const CIRCLE: &str = "\u{25cf}"; //●
 	pub const SPACE: &str = "\u{02FD}"; //˽
 	pub const EMPTY_SPACE: &str = " ";
 	pub const FOLDER_ICON_COLLAPSED: &str = "\u{25b8}"; //▸
@@ -48,9 +133,21 @@ pub fn title_status(_key_config: &SharedKeyConfig) -> String {
 pub fn title_diff(_key_config: &SharedKeyConfig) -> String {
 	"Diff: ".to_string()
 }
+pub fn title_diff_truncated(key_config: &SharedKeyConfig) -> String {
+	format!(
+		" (truncated, [{}] to load more)",
+		key_config.get_hint(key_config.keys.diff_load_more)
+	)
+}
+pub fn title_diff_algorithm(algorithm: &str) -> String {
+	format!(" ({})", algorithm)
+}
 pub fn title_index(_key_config: &SharedKeyConfig) -> String {
 	"Staged Changes".to_string()
 }
+pub fn title_recent_commits(_key_config: &SharedKeyConfig) -> String {
+	"Recent Commits".to_string()
+}
 pub fn tab_status(key_config: &SharedKeyConfig) -> String {
 	format!(
 		"Status [{}]",
@@ -78,6 +175,12 @@ pub fn tab_stashes(key_config: &SharedKeyConfig) -> String {
 		key_config.get_hint(key_config.keys.tab_stashes)
 	)
 }
+pub fn tab_search(key_config: &SharedKeyConfig) -> String {
+	format!(
+		"Search [{}]",
+		key_config.get_hint(key_config.keys.tab_search)
+	)
+}
 pub fn tab_divider(_key_config: &SharedKeyConfig) -> String {
 	" | ".to_string()
 }
@@ -93,6 +196,47 @@ pub fn msg_title_error(_key_config: &SharedKeyConfig) -> String {
 pub fn msg_title_info(_key_config: &SharedKeyConfig) -> String {
 	"Info".to_string()
 }
+pub fn msg_title_pushed(remote: &str) -> String {
+	format!("Pushed to {}", remote)
+}
+/// `name  old..new (how)` like the summary `git push` prints
+pub fn push_summary_ref(
+	name: &str,
+	old: Option<&str>,
+	new: Option<&str>,
+	fast_forward: Option<bool>,
+) -> String {
+	match (old, new, fast_forward) {
+		(Some(old), Some(new), _) if old == new => {
+			format!("{}  {} (up to date)", name, new)
+		}
+		(Some(old), Some(new), Some(true)) => {
+			format!("{}  {}..{} (fast-forward)", name, old, new)
+		}
+		(Some(old), Some(new), _) => {
+			format!("{}  {}...{} (forced update)", name, old, new)
+		}
+		(_, Some(new), _) => format!("{}  {} (new)", name, new),
+		(Some(old), None, _) => {
+			format!("{}  {} (deleted)", name, old)
+		}
+		(None, None, _) => format!("{} (deleted)", name),
+	}
+}
+pub fn push_summary_msg(
+	refs: &[String],
+	messages: &[String],
+) -> String {
+	if messages.is_empty() {
+		refs.join("\n")
+	} else {
+		format!(
+			"{}\n\nremote:\n{}",
+			refs.join("\n"),
+			messages.join("\n")
+		)
+	}
+}
 pub fn commit_title() -> String {
 	"Commit".to_string()
 }
@@ -106,11 +250,30 @@ pub fn commit_title_revert() -> String {
 pub fn commit_title_amend() -> String {
 	"Commit (Amend)".to_string()
 }
+pub fn commit_title_paths(paths: &[String]) -> String {
+	format!("Commit (only {})", paths.join(", "))
+}
 pub fn commit_msg(_key_config: &SharedKeyConfig) -> String {
 	"type commit message..".to_string()
 }
-pub fn commit_first_line_warning(count: usize) -> String {
-	format!("[subject length: {}]", count)
+pub fn commit_lint_warning(warning: &LintWarning) -> String {
+	match warning {
+		LintWarning::SubjectTooLong(count) => {
+			format!("[subject length: {}]", count)
+		}
+		LintWarning::NoBlankLine => {
+			"[no blank line after subject]".to_string()
+		}
+		LintWarning::BodyLineTooLong(line, count) => {
+			format!("[line {} length: {}]", line, count)
+		}
+		LintWarning::NotImperative(word) => {
+			format!("[not imperative: {}]", word)
+		}
+		LintWarning::Forbidden(pattern) => {
+			format!("[forbidden: {}]", pattern)
+		}
+	}
 }
 pub const fn branch_name_invalid() -> &'static str {
 	"[invalid name]"
@@ -176,6 +339,172 @@ pub fn confirm_msg_revertchanges() -> String {
 pub fn confirm_title_abortrebase() -> String {
 	"Abort rebase?".to_string()
 }
+pub fn confirm_title_autostash() -> String {
+	"Stash local changes?".to_string()
+}
+pub fn confirm_msg_autostash(operation: &str) -> String {
+	format!(
+		"There are uncommitted changes. Stash them before {} and re-apply them afterwards?",
+		operation
+	)
+}
+pub fn confirm_title_commit_protected() -> String {
+	"Commit to protected branch?".to_string()
+}
+pub fn confirm_msg_commit_protected(branch: &str) -> String {
+	format!("Commit directly to {}?", branch)
+}
+pub fn confirm_title_undo_commit_protected() -> String {
+	"Undo commit on protected branch?".to_string()
+}
+pub fn confirm_msg_undo_commit_protected(branch: &str) -> String {
+	format!("Reset {} to the previous commit?", branch)
+}
+pub fn confirm_title_undo() -> String {
+	"Undo?".to_string()
+}
+pub fn confirm_msg_undo(entry: &UndoEntry, time: &str) -> String {
+	let target = entry
+		.target
+		.map(|id| id.get_short_string())
+		.unwrap_or_default();
+
+	match &entry.operation {
+		UndoOperation::Discard(path) => format!(
+			"Restore the changes in {} discarded {}? Files that did not exist back then get removed.",
+			path, time
+		),
+		UndoOperation::DiscardMarked(files) => format!(
+			"Restore the changes in the {} marked files discarded {}? Files that did not exist back then get removed.",
+			files.len(), time
+		),
+		UndoOperation::DeleteBranch(branch) => format!(
+			"Recreate the branch {} deleted {}, pointing to {}?",
+			branch, time, target
+		),
+		UndoOperation::Amend => format!(
+			"Reset HEAD to {}, the commit before the amend {}? The amended changes stay staged.",
+			target, time
+		),
+		UndoOperation::UndoCommit => format!(
+			"Reset HEAD to {} again, the commit undone {}? The staged changes are kept.",
+			target, time
+		),
+	}
+}
+pub fn confirm_title_without_undo() -> String {
+	"No Undo".to_string()
+}
+pub fn confirm_msg_without_undo(error: &str) -> String {
+	format!(
+		"Saving the current state to undo this failed:\n{}\n\nGo ahead anyway? It cannot be undone.",
+		error
+	)
+}
+pub fn moved_to_trash(files: usize) -> String {
+	if files == 1 {
+		"moved 1 untracked file to the trash".to_string()
+	} else {
+		format!("moved {} untracked files to the trash", files)
+	}
+}
+pub fn discarded_item(
+	time: &str,
+	path: &str,
+	files: usize,
+) -> String {
+	format!("{}  {} ({} files)", time, path, files)
+}
+pub fn config_issues_msg(issues: &[ConfigIssue]) -> String {
+	format!(
+		"Problems in the config files, run `gitui --check-config` to list them again:\n\n{}",
+		issues.iter().map(ToString::to_string).join("\n")
+	)
+}
+pub fn key_conflict(key: &str, others: &[String]) -> String {
+	format!("`{}` is bound like `{}`", key, others.join("`, `"))
+}
+pub fn action_log_item(
+	time: &str,
+	action: &str,
+	details: &str,
+) -> String {
+	format!("{}  {}  {}", time, action, details)
+}
+pub fn ignored_file_item(
+	path: &str,
+	source: Option<(&str, usize, &str)>,
+) -> String {
+	source.map_or_else(
+		|| path.to_string(),
+		|(file, line, pattern)| {
+			format!("{}  {}:{}: {}", path, file, line, pattern)
+		},
+	)
+}
+pub fn ci_status_msg(
+	hash: &str,
+	checks: &[(&str, &str, Option<&str>)],
+) -> String {
+	format!(
+		"CI status of {}:\n\n{}",
+		hash,
+		checks
+			.iter()
+			.map(|(symbol, name, url)| {
+				url.map_or_else(
+					|| format!("{} {}", symbol, name),
+					|url| format!("{} {}  {}", symbol, name, url),
+				)
+			})
+			.join("\n")
+	)
+}
+pub fn confirm_title_rebuild_index() -> String {
+	"Index unreadable".to_string()
+}
+pub fn confirm_msg_rebuild_index(problem: &IndexProblem) -> String {
+	let problem = match problem {
+		IndexProblem::Truncated => "it is truncated".to_string(),
+		IndexProblem::BadSignature => {
+			"it is no index file, something overwrote it".to_string()
+		}
+		IndexProblem::UnsupportedVersion(version) => format!(
+			"its version {} is none git writes (2 to 4)",
+			version
+		),
+		IndexProblem::Unreadable(error) => error.clone(),
+	};
+
+	format!(
+		"The index (.git/index) cannot be read: {}.\nRebuild it from HEAD like `git reset --mixed`? The broken one gets kept.",
+		problem
+	)
+}
+pub fn confirm_title_restore_discarded() -> String {
+	"Restore discarded changes?".to_string()
+}
+pub fn confirm_msg_restore_discarded(
+	path: &str,
+	time: &str,
+) -> String {
+	format!(
+		"Write back {} as it was before discarding it {}? Files that did not exist back then get removed.",
+		path, time
+	)
+}
+pub fn confirm_title_store_credentials() -> String {
+	"Store credentials?".to_string()
+}
+pub fn confirm_msg_store_credentials(
+	username: &str,
+	remote: &str,
+) -> String {
+	format!(
+		"Remember the password of '{}' for '{}' in the credential store?",
+		username, remote
+	)
+}
 pub fn confirm_msg_abortrebase() -> String {
 	"This will revert all uncommitted changes. Are you sure?"
 		.to_string()
@@ -183,6 +512,9 @@ pub fn confirm_msg_abortrebase() -> String {
 pub fn confirm_msg_reset() -> String {
 	"confirm file reset?".to_string()
 }
+pub fn confirm_msg_reset_marked(files: usize) -> String {
+	format!("confirm reset of {} marked files?", files)
+}
 pub fn confirm_msg_reset_lines(lines: usize) -> String {
 	format!(
 		"are you sure you want to discard {} selected lines?",
@@ -255,6 +587,99 @@ pub fn confirm_title_delete_tag_remote() -> String {
 pub fn confirm_msg_delete_tag_remote(remote_name: &str) -> String {
 	format!("Confirm deleting tag on remote '{}'?", remote_name)
 }
+/// a ref a push or fetch would change, like `push_summary_ref`
+pub fn ref_update(
+	name: &str,
+	old: Option<&str>,
+	new: Option<&str>,
+	change: RefChange,
+) -> String {
+	let (old, new) =
+		(old.unwrap_or_default(), new.unwrap_or_default());
+	match change {
+		RefChange::Create => format!("{}  {} (new)", name, new),
+		RefChange::FastForward => {
+			format!("{}  {}..{} (fast-forward)", name, old, new)
+		}
+		RefChange::Forced => {
+			format!("{}  {}...{} (forced update)", name, old, new)
+		}
+		RefChange::Rejected => format!(
+			"{}  {}...{} (rejected, no fast-forward)",
+			name, old, new
+		),
+		RefChange::Unknown => format!(
+			"{}  {}...{} (new commits, not fetched yet)",
+			name, old, new
+		),
+		RefChange::Delete => format!("{}  {} (deleted)", name, old),
+		RefChange::UpToDate => format!("{} (up to date)", name),
+	}
+}
+/// meant to be pasted into issues about performance
+pub fn repo_stats_msg(stats: &RepoStats) -> String {
+	format!(
+		"Repository statistics:\n\n\
+		commits (HEAD):  {}\n\
+		refs:            {}\n\
+		loose objects:   {} ({})\n\
+		packed objects:  {} in {} packs ({})\n\
+		index:           {} files ({})\n\
+		worktree files:  {} (ignored included)",
+		stats.commits,
+		stats.refs,
+		stats.loose_objects,
+		ByteSize::b(stats.loose_size),
+		stats.packed_objects,
+		stats.packs,
+		ByteSize::b(stats.pack_size),
+		stats.index_entries,
+		ByteSize::b(stats.index_size),
+		stats.worktree_files,
+	)
+}
+pub const fn maintenance_task(task: MaintenanceTask) -> &'static str {
+	match task {
+		MaintenanceTask::Gc => {
+			"gc: pack objects and refs, prune unreachable objects"
+		}
+		MaintenanceTask::PrunePacked => {
+			"prune-packed: remove loose objects that are packed"
+		}
+		MaintenanceTask::Maintenance => {
+			"maintenance run: the tasks configured for the repo"
+		}
+	}
+}
+pub fn gc_suggested_toast(
+	loose_objects: usize,
+	key_config: &SharedKeyConfig,
+) -> String {
+	format!(
+		"about {} loose objects slow git down, consider a gc [{}]",
+		loose_objects,
+		key_config.get_hint(key_config.keys.open_maintenance),
+	)
+}
+pub fn confirm_title_push_dry_run() -> String {
+	"Push".to_string()
+}
+pub fn confirm_msg_push_dry_run(refs: usize) -> String {
+	format!("Push {} ref(s) like this?", refs)
+}
+pub fn confirm_title_fetch_dry_run() -> String {
+	"Fetch".to_string()
+}
+pub fn confirm_msg_fetch_dry_run(refs: usize) -> String {
+	format!("Fetch updating {} ref(s) like this?", refs)
+}
+pub fn overwritten_commit(
+	id: &str,
+	message: &str,
+	author: &str,
+) -> String {
+	format!("{} {} ({})", id, message, author)
+}
 pub fn confirm_title_force_push(
 	_key_config: &SharedKeyConfig,
 ) -> String {
@@ -269,6 +694,19 @@ pub fn confirm_msg_force_push(
         branch_ref
     )
 }
+pub fn confirm_title_rename_remote_branch() -> String {
+	"Rename Remote Branch".to_string()
+}
+pub fn confirm_msg_rename_remote_branch(
+	remote: &str,
+	old: &str,
+	new: &str,
+) -> String {
+	format!(
+		"The branch still tracks '{0}/{1}'. Push it as '{0}/{2}', delete '{0}/{1}' and track the new name?",
+		remote, old, new
+	)
+}
 pub fn log_title(_key_config: &SharedKeyConfig) -> String {
 	"Commit".to_string()
 }
@@ -282,9 +720,66 @@ pub fn file_log_title(
 		file_path, selected, revisions
 	)
 }
+pub fn search_input_title(
+	in_workdir: bool,
+	regex: bool,
+	ignore_case: bool,
+) -> String {
+	format!(
+		"Search in {}{}{}",
+		if in_workdir { "workdir" } else { "HEAD" },
+		if regex { " [regex]" } else { "" },
+		if ignore_case { " [ignore case]" } else { "" },
+	)
+}
+pub fn search_input_msg() -> String {
+	"type to search..".to_string()
+}
+pub fn search_results_title(
+	matches: usize,
+	truncated: bool,
+	pending: bool,
+) -> String {
+	if pending {
+		"Results (searching..)".to_string()
+	} else if truncated {
+		format!("Results ({}+)", matches)
+	} else {
+		format!("Results ({})", matches)
+	}
+}
+pub fn branch_compare_only_in(name: &str, count: usize) -> String {
+	format!("Only in {} ({})", name, count)
+}
+pub fn branch_compare_files(
+	branch: &str,
+	files: &[FileDiffStat],
+) -> String {
+	let (additions, deletions) =
+		files.iter().fold((0, 0), |(additions, deletions), file| {
+			(additions + file.additions, deletions + file.deletions)
+		});
+	format!(
+		"Changed by {} ({} files, +{} -{})",
+		branch,
+		files.len(),
+		additions,
+		deletions
+	)
+}
 pub fn blame_title(_key_config: &SharedKeyConfig) -> String {
 	"Blame".to_string()
 }
+pub fn edit_note_popup_title(commit: &str, exists: bool) -> String {
+	format!(
+		"{} Note: {}",
+		if exists { "Edit" } else { "Add" },
+		commit
+	)
+}
+pub fn edit_note_popup_msg() -> String {
+	"type note".to_string()
+}
 pub fn tag_popup_name_title() -> String {
 	"Tag".to_string()
 }
@@ -300,8 +795,78 @@ pub fn tag_popup_annotation_msg() -> String {
 pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
 	"Stashes".to_string()
 }
-pub fn help_title(_key_config: &SharedKeyConfig) -> String {
-	"Help: all commands".to_string()
+pub static HELP_KEYS_GROUP: &str = "-- Key bindings --";
+pub fn help_title(
+	_key_config: &SharedKeyConfig,
+	mode: HelpMode,
+	conflicts: usize,
+) -> String {
+	match mode {
+		HelpMode::Context => "Help: available commands".to_string(),
+		HelpMode::All => "Help: all commands".to_string(),
+		HelpMode::Keys if conflicts > 0 => {
+			format!("Help: key bindings ({} conflicting)", conflicts)
+		}
+		HelpMode::Keys => "Help: key bindings".to_string(),
+	}
+}
+pub fn help_rebind_prompt(key_config: &SharedKeyConfig) -> String {
+	format!(
+		"  press the new key, [{}] cancels",
+		key_config.get_hint(key_config.keys.exit_popup),
+	)
+}
+pub fn key_rebound(key: &str, hint: &str) -> String {
+	format!("`{}` bound to [{}], restart gitui to apply", key, hint)
+}
+pub fn notifications_title(_key_config: &SharedKeyConfig) -> String {
+	"Notifications".to_string()
+}
+pub fn tasks_title(_key_config: &SharedKeyConfig) -> String {
+	"Background tasks".to_string()
+}
+pub fn confirm_more(count: usize) -> String {
+	format!(" ... and {} more", count)
+}
+pub fn confirm_type_word(word: &str) -> String {
+	format!("Type '{}' to confirm: ", word)
+}
+pub fn confirm_protected_branch(branch: &str) -> String {
+	format!(
+		"{} is a protected branch (gitui.protectedBranch)",
+		branch
+	)
+}
+pub fn protected_branch_refused(branch: &str) -> String {
+	format!(
+		"{} is a protected branch, see gitui.protectedBranch and gitui.protectedBranchAction in git-config",
+		branch
+	)
+}
+pub fn confirm_dont_ask(
+	key_config: &SharedKeyConfig,
+	checked: bool,
+) -> String {
+	format!(
+		"[{}] don't ask again [{}]",
+		if checked { "x" } else { " " },
+		key_config.get_hint(key_config.keys.confirm_dont_ask),
+	)
+}
+pub fn terminal_too_small(
+	size: (u16, u16),
+	min: (u16, u16),
+) -> String {
+	format!(
+		"terminal too small ({}x{}), needs at least {}x{}",
+		size.0, size.1, min.0, min.1
+	)
+}
+pub fn number_input_bounds(min: usize, max: usize) -> String {
+	format!("[{}..{}]", min, max)
+}
+pub fn pull_requests_title(_key_config: &SharedKeyConfig) -> String {
+	"Pull Requests".to_string()
 }
 pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
 	"Files to Stash".to_string()
@@ -314,6 +879,33 @@ pub fn stashing_options_title(
 pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
 	"Loading ...".to_string()
 }
+pub fn lfs_object(pointer: &LfsPointer) -> String {
+	format!(
+		"LFS object ({}, {})",
+		ByteSize::b(pointer.size),
+		pointer.short_oid()
+	)
+}
+pub fn masked_content(
+	pattern: &str,
+	key_config: &SharedKeyConfig,
+) -> String {
+	format!(
+		"content hidden, the file matches the secret pattern `{}`\n\nreveal it with [{}]",
+		pattern,
+		key_config.get_hint(key_config.keys.reveal_masked),
+	)
+}
+pub fn lfs_file(
+	pointer: &LfsPointer,
+	key_config: &SharedKeyConfig,
+) -> String {
+	format!(
+		"{}\n\npreview the content with [{}] (needs `git lfs`)",
+		lfs_object(pointer),
+		key_config.get_hint(key_config.keys.lfs_preview),
+	)
+}
 pub fn create_branch_popup_title(
 	_key_config: &SharedKeyConfig,
 ) -> String {
@@ -324,6 +916,37 @@ pub fn create_branch_popup_msg(
 ) -> String {
 	"type branch name".to_string()
 }
+pub fn create_orphan_branch_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Orphan Branch".to_string()
+}
+pub fn file_content_copied(path: &str) -> String {
+	format!("copied content of '{}' to clipboard", path)
+}
+pub fn create_branch_at_popup_title(
+	_key_config: &SharedKeyConfig,
+	commit: &str,
+) -> String {
+	format!("Branch at {}", commit)
+}
+pub fn branch_created_at(
+	_key_config: &SharedKeyConfig,
+	name: &str,
+	commit: &str,
+) -> String {
+	format!("created branch {} at {}", name, commit)
+}
+pub fn orphan_branch_created(
+	key_config: &SharedKeyConfig,
+	name: &str,
+) -> String {
+	format!(
+		"switched to orphan branch {}, stage files and commit [{}] to start its history",
+		name,
+		key_config.get_hint(key_config.keys.open_commit),
+	)
+}
 pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
 	"Username".to_string()
 }
@@ -336,7 +959,75 @@ pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
 pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
 	"type password".to_string()
 }
+pub fn gpg_passphrase_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"GPG Passphrase".to_string()
+}
+pub fn gpg_passphrase_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"type passphrase of signing key".to_string()
+}
+pub fn commit_author_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Author".to_string()
+}
+pub fn commit_author_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Name <email> (empty for default)".to_string()
+}
+pub fn commit_date_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Author Date".to_string()
+}
+pub fn commit_date_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"YYYY-MM-DD HH:MM[:SS] [+HHMM] (empty for default)".to_string()
+}
+pub fn commit_author_override(
+	author: Option<&str>,
+	date: Option<&str>,
+) -> String {
+	match (author, date) {
+		(Some(author), Some(date)) => {
+			format!("author: {} at {}", author, date)
+		}
+		(Some(author), None) => format!("author: {}", author),
+		(None, Some(date)) => format!("author date: {}", date),
+		(None, None) => String::new(),
+	}
+}
 
+pub fn push_review_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Push for review".to_string()
+}
+pub fn push_review_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"topic (optional)".to_string()
+}
+pub fn push_options_popup_title(
+	_key_config: &SharedKeyConfig,
+	signed: bool,
+) -> String {
+	if signed {
+		"Push with options (signed)".to_string()
+	} else {
+		"Push with options".to_string()
+	}
+}
+pub fn push_options_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"push options, separated by spaces".to_string()
+}
 pub fn rename_branch_popup_title(
 	_key_config: &SharedKeyConfig,
 ) -> String {
@@ -366,6 +1057,18 @@ pub mod commit {
 	pub fn details_tags() -> String {
 		"Tags: ".to_string()
 	}
+	pub fn details_describe(description: &str) -> String {
+		format!(" ({})", description)
+	}
+	pub fn details_notes() -> String {
+		"Notes:".to_string()
+	}
+	pub fn details_contained_in() -> String {
+		"Contained in: ".to_string()
+	}
+	pub fn details_contained_in_tag(tag: &str) -> String {
+		format!("tag {}", tag)
+	}
 	pub fn details_message() -> String {
 		"Subject: ".to_string()
 	}
@@ -392,8 +1095,69 @@ pub mod commit {
 	}
 }
 
+pub mod tutorial {
+	use crate::keys::SharedKeyConfig;
+
+	pub fn title(step: usize, steps: usize) -> String {
+		format!("Tour {}/{}", step + 1, steps)
+	}
+	pub fn tabs(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"Welcome to gitui! Your repository is shown in tabs, jump to one with its number or cycle through them with [{}].",
+			key_config.get_hint(key_config.keys.tab_toggle),
+		)
+	}
+	pub fn status(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"Status lists your changes. Stage or unstage the selected file with [{}] and commit the staged ones with [{}].",
+			key_config.get_hint(key_config.keys.stage_unstage_item),
+			key_config.get_hint(key_config.keys.open_commit),
+		)
+	}
+	pub fn log(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"Log is the commit history. Open the selected commit with [{}], tag it with [{}].",
+			key_config.get_hint(key_config.keys.enter),
+			key_config.get_hint(key_config.keys.log_tag_commit),
+		)
+	}
+	pub fn files(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"Files browses the tree of HEAD, find a file by name with [{}] or blame the selected one with [{}].",
+			key_config.get_hint(key_config.keys.file_find),
+			key_config.get_hint(key_config.keys.blame),
+		)
+	}
+	pub fn stashing(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"Stashing puts your changes aside, save a stash with [{}].",
+			key_config.get_hint(key_config.keys.stashing_save),
+		)
+	}
+	pub fn stashes(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"Stashes lists the saved stashes, apply the selected one with [{}].",
+			key_config.get_hint(key_config.keys.stash_apply),
+		)
+	}
+	pub fn search(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"Search greps the files of the repository, type into the input focused with [{}].",
+			key_config.get_hint(key_config.keys.search_focus_input),
+		)
+	}
+	pub fn command_bar(key_config: &SharedKeyConfig) -> String {
+		format!(
+			"The bar at the bottom shows what can be done right now, expand it with [{}]. [{}] explains these commands, [{}] lists all of them.",
+			key_config.get_hint(key_config.keys.cmd_bar_toggle),
+			key_config.get_hint(key_config.keys.open_context_help),
+			key_config.get_hint(key_config.keys.open_help),
+		)
+	}
+}
+
 pub mod commands {
-	use crate::components::CommandText;
+	use crate::components::{CommandText, HelpMode};
 	use crate::keys::SharedKeyConfig;
 
 	static CMD_GROUP_GENERAL: &str = "-- General --";
@@ -404,6 +1168,7 @@ pub mod commands {
 	static CMD_GROUP_STASHES: &str = "-- Stashes --";
 	static CMD_GROUP_LOG: &str = "-- Log --";
 	static CMD_GROUP_BRANCHES: &str = "-- Branches --";
+	static CMD_GROUP_SEARCH: &str = "-- Search --";
 
 	pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
@@ -430,12 +1195,13 @@ pub mod commands {
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Tab [{}{}{}{}{}]",
+				"Tab [{}{}{}{}{}{}]",
 				key_config.get_hint(key_config.keys.tab_status),
 				key_config.get_hint(key_config.keys.tab_log),
 				key_config.get_hint(key_config.keys.tab_files),
 				key_config.get_hint(key_config.keys.tab_stashing),
 				key_config.get_hint(key_config.keys.tab_stashes),
+				key_config.get_hint(key_config.keys.tab_search),
 			),
 			"switch top level tabs directly",
 			CMD_GROUP_GENERAL,
@@ -453,6 +1219,190 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn notifications_open(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Notifications [{}]",
+				key_config
+					.get_hint(key_config.keys.open_notifications),
+			),
+			"review past notifications",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn open_shell(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Shell [{}]",
+				key_config.get_hint(key_config.keys.open_shell),
+			),
+			"open a shell in the repository (or selected submodule)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn suspend(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Suspend [{}]",
+				key_config.get_hint(key_config.keys.suspend),
+			),
+			"suspend gitui, resume with `fg`",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn tasks_open(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Tasks [{}]",
+				key_config.get_hint(key_config.keys.open_tasks),
+			),
+			"list running background tasks",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn open_discarded(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Discarded [{}]",
+				key_config.get_hint(key_config.keys.open_discarded),
+			),
+			"recover recently discarded changes",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn open_action_log(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Action Log [{}]",
+				key_config.get_hint(key_config.keys.open_action_log),
+			),
+			"list the git actions gitui performed in this repository",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn open_repo_stats(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Repo Stats [{}]",
+				key_config.get_hint(key_config.keys.open_repo_stats),
+			),
+			"show object, ref and file counts and sizes of the repository",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn open_maintenance(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Maintenance [{}]",
+				key_config.get_hint(key_config.keys.open_maintenance),
+			),
+			"run gc, prune-packed or git maintenance",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn undo(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Undo [{}]",
+				key_config.get_hint(key_config.keys.undo),
+			),
+			"undo the last discard, branch deletion, amend or undone commit",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn pull_requests_open(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pull Requests [{}]",
+				key_config
+					.get_hint(key_config.keys.open_pull_requests),
+			),
+			"list open github pull requests",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn checkout_pull_request(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Checkout [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"fetch and checkout selected pull request",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn select_list_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Confirm [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"apply selected value",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn number_input_step(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Change [{}{}]",
+				key_config.get_hint(key_config.keys.popup_up),
+				key_config.get_hint(key_config.keys.popup_down),
+			),
+			"increase or decrease value",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn options_edit(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"pick value of selected option",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn accept_completion(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Complete [{}]",
+				key_config
+					.get_hint(key_config.keys.accept_completion),
+			),
+			"insert selected completion",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn cancel_task(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cancel [{}]",
+				key_config.get_hint(key_config.keys.cancel_task),
+			),
+			"cancel selected task",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -463,6 +1413,63 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn help_open_context(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Help here [{}]",
+				key_config
+					.get_hint(key_config.keys.open_context_help)
+			),
+			"explain the commands available in the focused view",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn help_search(
+		key_config: &SharedKeyConfig,
+		searching: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if searching { "Edit search" } else { "Search" },
+				key_config.get_hint(key_config.keys.file_filter),
+			),
+			"hide the commands not matching the typed text",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn help_switch_mode(
+		key_config: &SharedKeyConfig,
+		next: HelpMode,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				match next {
+					HelpMode::Context => "Available here",
+					HelpMode::All => "All commands",
+					HelpMode::Keys => "Key bindings",
+				},
+				key_config.get_hint(key_config.keys.tab_toggle),
+			),
+			"switch between the commands available where help was opened, all commands and the key bindings",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn help_rebind_key(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Rebind [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"bind the selected key anew in the key bindings file",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn navigate_commit_message(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -526,6 +1533,18 @@ pub mod commands {
 			CMD_GROUP_DIFF,
 		)
 	}
+	pub fn copy_describe(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Describe [{}]",
+				key_config.get_hint(key_config.keys.copy_describe),
+			),
+			"copy `git describe --tags` of the selected commit to clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn copy_hash(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -635,6 +1654,52 @@ pub mod commands {
 			CMD_GROUP_DIFF,
 		)
 	}
+	pub fn diff_load_more(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Load more [{}]",
+				key_config.get_hint(key_config.keys.diff_load_more),
+			),
+			"load more of a truncated diff",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_algorithm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Algorithm [{}]",
+				key_config.get_hint(key_config.keys.diff_algorithm),
+			),
+			"switch the diff algorithm of this view (myers, minimal, patience, histogram)",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn reveal_masked(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reveal [{}]",
+				key_config.get_hint(key_config.keys.reveal_masked),
+			),
+			"show the content of a file matching a secret pattern",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn lfs_preview(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"LFS preview [{}]",
+				key_config.get_hint(key_config.keys.lfs_preview),
+			),
+			"show the content of a git lfs file (git lfs smudge)",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn diff_hunk_remove(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -658,6 +1723,52 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn tutorial_next(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"continue the tour",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn tutorial_back(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Back [{}]",
+				key_config.get_hint(key_config.keys.focus_left),
+			),
+			"previous step of the tour",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn tutorial_end(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"End tour [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+			),
+			"close the tour, it is not shown again",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn cancel_remote_progress(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cancel [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+			),
+			"abort the running transfer",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -669,6 +1780,48 @@ pub mod commands {
 		)
 		.hide_help()
 	}
+	pub fn msg_search(
+		key_config: &SharedKeyConfig,
+		searching: bool,
+	) -> CommandText {
+		CommandText::new(
+			if searching {
+				format!(
+					"Next match [{}]",
+					key_config.get_hint(key_config.keys.enter),
+				)
+			} else {
+				format!(
+					"Search [{}]",
+					key_config.get_hint(key_config.keys.file_find),
+				)
+			},
+			"search in message",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn msg_copy_range(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy range [{}]",
+				key_config.get_hint(key_config.keys.copy_range),
+			),
+			"copy the pushed commit range to clipboard",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn msg_copy(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy [{}]",
+				key_config.get_hint(key_config.keys.copy),
+			),
+			"copy message to clipboard",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -854,17 +2007,65 @@ pub mod commands {
 				"Amend [{}]",
 				key_config.get_hint(key_config.keys.commit_amend),
 			),
-			"amend last commit (available in commit popup)",
-			CMD_GROUP_COMMIT,
+			"amend last commit (available in commit popup)",
+			CMD_GROUP_COMMIT,
+		)
+	}
+	pub fn commit_anyway(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Commit anyway [{}]",
+				key_config.get_hint(key_config.keys.commit_anyway),
+			),
+			"commit despite commit message warnings (see gitui.lint* in git-config)",
+			CMD_GROUP_COMMIT,
+		)
+	}
+	pub fn commit_author(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Author [{}]",
+				key_config.get_hint(key_config.keys.commit_author),
+			),
+			"set author and date of the commit (available in commit popup)",
+			CMD_GROUP_COMMIT,
+		)
+	}
+	pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit [{}]",
+				key_config.get_hint(key_config.keys.edit_file),
+			),
+			"edit the currently selected file in an external editor",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn open_difftool(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Difftool [{}]",
+				key_config.get_hint(key_config.keys.open_difftool),
+			),
+			"open the selected file in the configured `diff.tool`",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn open_mergetool(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Edit [{}]",
-				key_config.get_hint(key_config.keys.edit_file),
+				"Mergetool [{}]",
+				key_config.get_hint(key_config.keys.open_mergetool),
 			),
-			"edit the currently selected file in an external editor",
+			"resolve the selected conflict in the configured `merge.tool`",
 			CMD_GROUP_CHANGES,
 		)
 	}
@@ -932,6 +2133,105 @@ pub mod commands {
 			CMD_GROUP_CHANGES,
 		)
 	}
+	pub fn show_ignored(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ignored [{}]",
+				key_config
+					.get_hint(key_config.keys.status_show_ignored),
+			),
+			"list ignored files and the rule ignoring each",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn commit_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Commit File [{}]",
+				key_config
+					.get_hint(key_config.keys.status_commit_item),
+			),
+			"commit just this file, other staged changes stay staged",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn mark_item(
+		key_config: &SharedKeyConfig,
+		marked: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if marked { "Unmark" } else { "Mark" },
+				key_config.get_hint(key_config.keys.status_mark_item),
+			),
+			"mark multiple files to stage, unstage, reset or ignore them together",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn file_filter(
+		key_config: &SharedKeyConfig,
+		filtering: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if filtering { "Edit filter" } else { "Filter" },
+				key_config.get_hint(key_config.keys.file_filter),
+			),
+			"hide the files not matching the typed text",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn copy_file_path(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy path [{}]",
+				key_config.get_hint(key_config.keys.copy),
+			),
+			"copy path of the selected file relative to the repository",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn copy_file_path_absolute(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy abs. path [{}]",
+				key_config
+					.get_hint(key_config.keys.copy_path_absolute),
+			),
+			"copy absolute path of the selected file",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn copy_file_content(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy content [{}]",
+				key_config
+					.get_hint(key_config.keys.copy_file_content),
+			),
+			"copy the whole content of the selected file",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn reveal_file(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reveal [{}]",
+				key_config.get_hint(key_config.keys.reveal_file),
+			),
+			"show the selected file in the file manager",
+			CMD_GROUP_GENERAL,
+		)
+	}
 
 	pub fn diff_focus_left(
 		key_config: &SharedKeyConfig,
@@ -979,6 +2279,18 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn confirm_dont_ask(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Don't ask again [{}]",
+				key_config.get_hint(key_config.keys.confirm_dont_ask),
+			),
+			"do not ask for confirmation of this action again",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn stashing_save(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1120,6 +2432,30 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn blame_toggle_ignore_revs(
+		key_config: &SharedKeyConfig,
+		enabled: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} ignore-revs [{}]",
+				if enabled { "Disable" } else { "Enable" },
+				key_config.get_hint(key_config.keys.blame_ignore_revs),
+			),
+			"toggle skipping revisions listed in blame.ignoreRevsFile",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn blame_line(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Blame line [{}]",
+				key_config.get_hint(key_config.keys.blame_line),
+			),
+			"show commit that last changed the top visible line",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn open_file_history(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1169,6 +2505,42 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn log_edit_note(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Note [{}]",
+				key_config.get_hint(key_config.keys.log_edit_note),
+			),
+			"add or edit the note (git notes) of the commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_ci_status(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"CI [{}]",
+				key_config.get_hint(key_config.keys.log_ci_status),
+			),
+			"show the ci checks of the commit",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn edit_note_confirm_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Save [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"save note, an empty one removes it",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn tag_commit_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1220,6 +2592,31 @@ pub mod commands {
 			CMD_GROUP_BRANCHES,
 		)
 	}
+	pub fn create_branch_at_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Branch [{}]",
+				key_config.get_hint(key_config.keys.create_branch),
+			),
+			"create a branch at the commit (stays on the current branch)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn open_orphan_branch_create_popup(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Create orphan [{}]",
+				key_config
+					.get_hint(key_config.keys.create_orphan_branch),
+			),
+			"create a branch without history, e.g. for gh-pages",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn rename_branch_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1283,6 +2680,30 @@ pub mod commands {
 		)
 	}
 
+	pub fn compare_branch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Compare branch [{}]",
+				key_config.get_hint(key_config.keys.compare_branch),
+			),
+			"show commits and changes merging this branch brings in",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn branch_compare_focus_next(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next list [{}]",
+				key_config.get_hint(key_config.keys.tab_toggle),
+			),
+			"focus next list",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn compare_with_head(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1405,6 +2826,71 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn status_push_review(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push for review [{}]",
+				key_config.get_hint(key_config.keys.push_review),
+			),
+			"push HEAD to the review refspec (gitui.reviewRefspec)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn status_push_options(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push with options [{}]",
+				key_config.get_hint(key_config.keys.push_options),
+			),
+			"push via git with push options or signed",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn push_options_signed(
+		key_config: &SharedKeyConfig,
+		signed: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if signed { "Unsigned" } else { "Signed" },
+				key_config
+					.get_hint(key_config.keys.push_signed_toggle),
+			),
+			"toggle signing the push (--signed)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn push_options_confirm_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"push with the options",
+			CMD_GROUP_GENERAL,
+		)
+		.hide_help()
+	}
+	pub fn push_review_confirm_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"push for review",
+			CMD_GROUP_GENERAL,
+		)
+		.hide_help()
+	}
 	pub fn status_pull(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1428,4 +2914,79 @@ pub mod commands {
 			CMD_GROUP_BRANCHES,
 		)
 	}
+
+	pub fn search_start(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Search [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"search file contents for the entered text",
+			CMD_GROUP_SEARCH,
+		)
+	}
+	pub fn search_focus_input(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit search [{}]",
+				key_config
+					.get_hint(key_config.keys.search_focus_input),
+			),
+			"change the text searched for",
+			CMD_GROUP_SEARCH,
+		)
+	}
+	pub fn search_toggle_regex(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Regex [{}]",
+				key_config
+					.get_hint(key_config.keys.search_toggle_regex),
+			),
+			"toggle searching for a regular expression",
+			CMD_GROUP_SEARCH,
+		)
+	}
+	pub fn search_toggle_case(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ignore case [{}]",
+				key_config
+					.get_hint(key_config.keys.search_toggle_case),
+			),
+			"toggle case insensitive search",
+			CMD_GROUP_SEARCH,
+		)
+	}
+	pub fn search_toggle_source(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Workdir/HEAD [{}]",
+				key_config
+					.get_hint(key_config.keys.search_toggle_source),
+			),
+			"toggle searching the workdir or the HEAD commit",
+			CMD_GROUP_SEARCH,
+		)
+	}
+	pub fn search_jump_to_file(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Jump to file [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"show file of the selected match in the files tab",
+			CMD_GROUP_SEARCH,
+		)
+	}
 }
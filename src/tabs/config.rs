@@ -0,0 +1,143 @@
+use crate::args::get_app_config_path;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Read, path::PathBuf, time::Duration};
+
+///
+#[derive(
+	Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum AppTab {
+	Status,
+	Log,
+	Files,
+	Stashing,
+	Stashes,
+	Search,
+}
+
+/// which tabs are shown and in what order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabsConfig {
+	pub tabs: Vec<AppTab>,
+	/// idle time after which hidden tabs start loading their data
+	pub warmup: Option<Duration>,
+}
+
+impl Default for TabsConfig {
+	fn default() -> Self {
+		Self {
+			tabs: vec![
+				AppTab::Status,
+				AppTab::Log,
+				AppTab::Files,
+				AppTab::Stashing,
+				AppTab::Stashes,
+				AppTab::Search,
+			],
+			warmup: None,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct TabsConfigFile {
+	tabs: Option<Vec<AppTab>>,
+	warmup_secs: Option<u64>,
+}
+
+impl TabsConfig {
+	fn get_config_file() -> Result<PathBuf> {
+		let app_home = get_app_config_path()?;
+		Ok(app_home.join("tabs.ron"))
+	}
+
+	fn read_file(config_file: PathBuf) -> Result<TabsConfigFile> {
+		let mut f = File::open(config_file)?;
+		let mut buffer = Vec::new();
+		f.read_to_end(&mut buffer)?;
+		Ok(ron::de::from_bytes(&buffer)?)
+	}
+
+	pub fn init() -> Result<Self> {
+		let file = Self::get_config_file()?;
+		if file.exists() {
+			Ok(Self::from_file(Self::read_file(file)?))
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	fn from_file(file: TabsConfigFile) -> Self {
+		Self {
+			warmup: file
+				.warmup_secs
+				.filter(|secs| *secs > 0)
+				.map(Duration::from_secs),
+			..Self::from_list(file.tabs)
+		}
+	}
+
+	/// drops duplicates and makes sure `Status` is always
+	/// available since other parts of the ui jump back to it
+	fn from_list(tabs: Option<Vec<AppTab>>) -> Self {
+		let mut result: Vec<AppTab> = Vec::new();
+		for tab in tabs.unwrap_or_else(|| Self::default().tabs) {
+			if !result.contains(&tab) {
+				result.push(tab);
+			}
+		}
+
+		if !result.contains(&AppTab::Status) {
+			result.insert(0, AppTab::Status);
+		}
+
+		Self {
+			tabs: result,
+			warmup: None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_from_list() {
+		let config = TabsConfig::from_list(Some(vec![
+			AppTab::Log,
+			AppTab::Files,
+			AppTab::Log,
+		]));
+
+		assert_eq!(
+			config.tabs,
+			vec![AppTab::Status, AppTab::Log, AppTab::Files]
+		);
+
+		assert_eq!(
+			TabsConfig::from_list(None),
+			TabsConfig::default()
+		);
+	}
+
+	#[test]
+	fn test_parse() {
+		let file: TabsConfigFile =
+			ron::de::from_str("(tabs: Some([Files, Status]))")
+				.unwrap();
+
+		assert_eq!(
+			TabsConfig::from_list(file.tabs).tabs,
+			vec![AppTab::Files, AppTab::Status]
+		);
+
+		let file: TabsConfigFile =
+			ron::de::from_str("(warmup_secs: Some(5))").unwrap();
+		let config = TabsConfig::from_file(file);
+
+		assert_eq!(config.warmup, Some(Duration::from_secs(5)));
+		assert_eq!(config.tabs, TabsConfig::default().tabs);
+	}
+}
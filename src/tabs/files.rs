@@ -4,6 +4,7 @@ use crate::{
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
 		DrawableComponent, EventState, RevisionFilesComponent,
+		SharedOptions,
 	},
 	keys::SharedKeyConfig,
 	queue::Queue,
@@ -11,7 +12,10 @@ use crate::{
 	AsyncAppNotification, AsyncNotification,
 };
 use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use asyncgit::{
+	sync::{self, RepoPathRef},
+	AsyncGitNotification,
+};
 use crossbeam_channel::Sender;
 
 pub struct FilesTab {
@@ -25,9 +29,11 @@ impl FilesTab {
 	pub fn new(
 		repo: RepoPathRef,
 		sender: &Sender<AsyncAppNotification>,
+		sender_git: &Sender<AsyncGitNotification>,
 		queue: &Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			visible: false,
@@ -35,8 +41,10 @@ impl FilesTab {
 				repo.clone(),
 				queue,
 				sender,
+				sender_git,
 				theme,
 				key_config,
+				options,
 			),
 			repo,
 		}
@@ -1,11 +1,15 @@
+mod config;
 mod files;
 mod revlog;
+mod search;
 mod stashing;
 mod stashlist;
 mod status;
 
+pub use config::{AppTab, TabsConfig};
 pub use files::FilesTab;
 pub use revlog::Revlog;
+pub use search::SearchTab;
 pub use stashing::{Stashing, StashingOptions};
 pub use stashlist::StashList;
 pub use status::Status;
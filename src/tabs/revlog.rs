@@ -1,9 +1,9 @@
 use crate::{
 	components::{
-		visibility_blocking, CommandBlocking, CommandInfo,
+		ci_symbol, visibility_blocking, CommandBlocking, CommandInfo,
 		CommitDetailsComponent, CommitList, Component,
 		DrawableComponent, EventState, FileTreeOpen,
-		InspectCommitOpen,
+		InspectCommitOpen, SharedCommitCache, SharedOptions,
 	},
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, Queue, StackablePopupOpen},
@@ -12,14 +12,16 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	cached,
-	sync::{self, CommitId, RepoPathRef},
+	ci_status::AsyncCiStatusJob,
+	sync::{self, CommitId, DescribeOptions, RepoPathRef},
 	AsyncGitNotification, AsyncLog, AsyncTags, CommitFilesParams,
 	FetchStatus,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use sync::CommitTags;
 use tui::{
 	backend::Backend,
@@ -28,17 +30,29 @@ use tui::{
 };
 
 const SLICE_SIZE: usize = 1200;
+/// how many of the newest commits get their ci status fetched
+const CI_STATUS_COMMITS: usize = 10;
+/// how long the fetched ci status is kept before fetching it again
+const CI_STATUS_INTERVAL: Duration = Duration::from_secs(60);
 
 ///
 pub struct Revlog {
 	repo: RepoPathRef,
+	commit_cache: SharedCommitCache,
 	commit_details: CommitDetailsComponent,
 	list: CommitList,
 	git_log: AsyncLog,
 	git_tags: AsyncTags,
+	async_ci_status: AsyncSingleJob<AsyncCiStatusJob>,
+	/// `sync::ci::ci_status_enabled`, looked up when shown
+	ci_enabled: bool,
+	/// the commits the ci status was last requested for and when
+	ci_requested: Option<(Vec<CommitId>, Instant)>,
+	ci_error: Option<String>,
 	queue: Queue,
 	visible: bool,
 	branch_name: cached::BranchName,
+	head_description: cached::HeadDescription,
 	key_config: SharedKeyConfig,
 }
 
@@ -48,18 +62,23 @@ impl Revlog {
 		repo: &RepoPathRef,
 		queue: &Queue,
 		sender: &Sender<AsyncGitNotification>,
+		commit_cache: &SharedCommitCache,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			repo: repo.clone(),
 			queue: queue.clone(),
+			commit_cache: commit_cache.clone(),
 			commit_details: CommitDetailsComponent::new(
 				repo,
 				queue,
 				sender,
+				commit_cache,
 				theme.clone(),
 				key_config.clone(),
+				options,
 			),
 			list: CommitList::new(
 				&strings::log_title(&key_config),
@@ -72,26 +91,47 @@ impl Revlog {
 				None,
 			),
 			git_tags: AsyncTags::new(repo.borrow().clone(), sender),
+			async_ci_status: AsyncSingleJob::new(sender.clone()),
+			ci_enabled: false,
+			ci_requested: None,
+			ci_error: None,
 			visible: false,
 			branch_name: cached::BranchName::new(repo.clone()),
+			head_description: cached::HeadDescription::new(
+				repo.clone(),
+			),
 			key_config,
 		}
 	}
 
+	/// starts walking the log while hidden so it is ready once shown
+	pub fn warmup(&mut self) -> Result<()> {
+		if !self.visible {
+			self.git_log.fetch()?;
+			self.git_log.set_background();
+		}
+
+		Ok(())
+	}
+
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.git_log.is_pending()
 			|| self.git_tags.is_pending()
 			|| self.commit_details.any_work_pending()
+			|| self.async_ci_status.is_pending()
 	}
 
 	///
 	pub fn update(&mut self) -> Result<()> {
 		if self.is_visible() {
+			let selected = self.selected_commit();
+
 			let log_changed =
 				self.git_log.fetch()? == FetchStatus::Started;
 
 			self.list.set_count_total(self.git_log.count()?);
+			self.list.set_pending(self.git_log.is_pending());
 
 			let selection = self.list.selection();
 			let selection_max = self.list.selection_max();
@@ -101,11 +141,17 @@ impl Revlog {
 				self.fetch_commits()?;
 			}
 
+			self.keep_selection(selected)?;
+
 			self.git_tags.request(Duration::from_secs(3), false)?;
+			self.request_ci_status()?;
 
 			self.list.set_branch(
 				self.branch_name.lookup().map(Some).unwrap_or(None),
 			);
+			self.list.set_head_description(
+				self.head_description.lookup().unwrap_or(None),
+			);
 
 			if self.commit_details.is_visible() {
 				let commit = self.selected_commit();
@@ -126,9 +172,15 @@ impl Revlog {
 		&mut self,
 		ev: AsyncGitNotification,
 	) -> Result<()> {
+		if ev == AsyncGitNotification::CiStatus {
+			self.take_ci_status();
+		}
+
 		if self.visible {
 			match ev {
 				AsyncGitNotification::CommitFiles
+				| AsyncGitNotification::ContainedIn
+				| AsyncGitNotification::Describe
 				| AsyncGitNotification::Log => self.update()?,
 				AsyncGitNotification::Tags => {
 					if let Some(tags) = self.git_tags.last()? {
@@ -143,11 +195,85 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// for the newest commits, again once they change or after
+	/// `CI_STATUS_INTERVAL`
+	fn request_ci_status(&mut self) -> Result<()> {
+		if !self.ci_enabled || self.async_ci_status.is_pending() {
+			return Ok(());
+		}
+
+		let commits = self.git_log.get_slice(0, CI_STATUS_COMMITS)?;
+		let due = self.ci_requested.as_ref().map_or(
+			true,
+			|(requested, time)| {
+				*requested != commits
+					|| time.elapsed() >= CI_STATUS_INTERVAL
+			},
+		);
+
+		if due && !commits.is_empty() {
+			self.async_ci_status.spawn(AsyncCiStatusJob::new(
+				self.repo.borrow().clone(),
+				commits.clone(),
+			));
+			self.ci_requested = Some((commits, Instant::now()));
+		}
+
+		Ok(())
+	}
+
+	fn take_ci_status(&mut self) {
+		match self
+			.async_ci_status
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(statuses)) => {
+				self.ci_error = None;
+				self.list
+					.set_ci_status(statuses.into_iter().collect());
+			}
+			Some(Err(e)) => {
+				log::error!("ci status error: {}", e);
+				self.ci_error = Some(e.to_string());
+			}
+			None => (),
+		}
+	}
+
+	fn show_ci_status(&self) {
+		if let Some(entry) = self.list.selected_entry() {
+			let msg = match (
+				self.list.ci_status(&entry.id),
+				&self.ci_error,
+			) {
+				(Some(status), _) => strings::ci_status_msg(
+					&entry.hash_short,
+					&status
+						.checks
+						.iter()
+						.map(|check| {
+							(
+								ci_symbol(check.state),
+								check.name.as_str(),
+								check.url.as_deref(),
+							)
+						})
+						.collect::<Vec<_>>(),
+				),
+				(None, Some(e)) => e.clone(),
+				(None, None) => strings::CI_STATUS_NONE.to_string(),
+			};
+
+			self.queue.push(InternalEvent::ShowInfoMsg(msg));
+		}
+	}
+
 	fn fetch_commits(&mut self) -> Result<()> {
 		let want_min =
 			self.list.selection().saturating_sub(SLICE_SIZE / 2);
 
-		let commits = sync::get_commits_info(
+		let commits = self.commit_cache.borrow_mut().commits_info(
 			&self.repo.borrow(),
 			&self.git_log.get_slice(want_min, SLICE_SIZE)?,
 			self.list.current_size().0.into(),
@@ -160,6 +286,24 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// new commits shift the log, follow the previously selected
+	/// commit instead of keeping its index
+	fn keep_selection(
+		&mut self,
+		selected: Option<CommitId>,
+	) -> Result<()> {
+		if let Some(id) = selected {
+			if self.selected_commit() != Some(id) {
+				if let Some(position) = self.git_log.position(id)? {
+					self.list.select_entry(position);
+					self.fetch_commits()?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
 	fn selected_commit(&self) -> Option<CommitId> {
 		self.list.selected_entry().map(|e| e.id)
 	}
@@ -169,6 +313,23 @@ impl Revlog {
 		Ok(())
 	}
 
+	fn copy_commit_description(&self) -> Result<()> {
+		if let Some(id) = self.selected_commit() {
+			let description = sync::describe(
+				&self.repo.borrow(),
+				id,
+				&DescribeOptions {
+					always: true,
+					..DescribeOptions::default()
+				},
+			)?;
+			if let Some(description) = description {
+				crate::clipboard::copy_string(&description)?;
+			}
+		}
+		Ok(())
+	}
+
 	fn selected_commit_tags(
 		&self,
 		commit: &Option<CommitId>,
@@ -259,9 +420,34 @@ impl Component for Revlog {
 				} else if key_match(k, self.key_config.keys.copy) {
 					self.copy_commit_hash()?;
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.copy_describe,
+				) {
+					self.copy_commit_description()?;
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_edit_note,
+				) {
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue
+								.push(InternalEvent::EditNote(id));
+							Ok(EventState::Consumed)
+						},
+					);
 				} else if key_match(k, self.key_config.keys.push) {
 					self.queue.push(InternalEvent::PushTags);
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_ci_status,
+				) && self.ci_enabled
+				{
+					self.show_ci_status();
+					return Ok(EventState::Consumed);
 				} else if key_match(
 					k,
 					self.key_config.keys.log_tag_commit,
@@ -404,12 +590,30 @@ impl Component for Revlog {
 			self.visible || force_all,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::copy_describe(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::log_tag_commit(&self.key_config),
 			self.selected_commit().is_some(),
 			self.visible || force_all,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::log_edit_note(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::log_ci_status(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && self.ci_enabled) || force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::open_tags_popup(&self.key_config),
 			true,
@@ -448,6 +652,9 @@ impl Component for Revlog {
 
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
+		self.ci_enabled =
+			sync::ci::ci_status_enabled(&self.repo.borrow())
+				.unwrap_or_default();
 		self.list.clear();
 		self.update()?;
 
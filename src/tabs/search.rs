@@ -0,0 +1,300 @@
+use crate::{
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, SearchResultsComponent,
+		TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::Queue,
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{
+		self,
+		grep::{GrepOptions, GrepSource},
+		RepoPathRef,
+	},
+	AsyncGitNotification, AsyncGrep, GrepParams,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+	backend::Backend,
+	layout::{Constraint, Direction, Layout, Rect},
+	widgets::Borders,
+	Frame,
+};
+
+/// searches the content of all files (like `git grep`)
+pub struct SearchTab {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	results: SearchResultsComponent,
+	grep: AsyncGrep,
+	input_focused: bool,
+	in_workdir: bool,
+	/// regex and case options, `pattern` is set when searching
+	options: GrepOptions,
+	visible: bool,
+	key_config: SharedKeyConfig,
+}
+
+impl SearchTab {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		sender: &Sender<AsyncGitNotification>,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		let mut input = TextInputComponent::new(
+			theme.clone(),
+			key_config.clone(),
+			"",
+			&strings::search_input_msg(),
+			false,
+		);
+		input.embed_with_borders(Borders::ALL);
+
+		let grep = AsyncGrep::new(repo.borrow().clone(), sender);
+
+		let mut tab = Self {
+			grep,
+			results: SearchResultsComponent::new(
+				repo.clone(),
+				queue,
+				theme,
+				key_config.clone(),
+			),
+			repo,
+			input,
+			input_focused: true,
+			in_workdir: true,
+			options: GrepOptions::default(),
+			visible: false,
+			key_config,
+		};
+		tab.update_title();
+		tab
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if ev == AsyncGitNotification::Grep {
+			if let Some((params, result)) = self.grep.last()? {
+				self.results.set_result(params, result);
+			}
+			self.results.set_pending(self.grep.is_pending());
+		}
+
+		Ok(())
+	}
+
+	///
+	pub fn anything_pending(&self) -> bool {
+		self.grep.is_pending()
+	}
+
+	/// `true` while typing into the search input, single key
+	/// shortcuts (like quitting) are not available then
+	pub const fn is_typing(&self) -> bool {
+		self.visible && self.input_focused
+	}
+
+	fn search(&mut self) -> Result<()> {
+		self.options.pattern = self.input.get_text().to_string();
+		if self.options.pattern.is_empty() {
+			self.grep.cancel()?;
+			self.results.clear();
+			self.results.set_pending(false);
+			return Ok(());
+		}
+
+		let source = if self.in_workdir {
+			GrepSource::WorkDir
+		} else {
+			GrepSource::Commit(sync::get_head(&self.repo.borrow())?)
+		};
+
+		self.grep.request(GrepParams {
+			source,
+			options: self.options.clone(),
+		})?;
+		self.results.set_pending(true);
+
+		Ok(())
+	}
+
+	fn focus_input(&mut self, focus: bool) {
+		self.input_focused = focus;
+		self.results.focus(!focus);
+	}
+
+	fn update_title(&mut self) {
+		self.input.set_title(strings::search_input_title(
+			self.in_workdir,
+			self.options.regex,
+			self.options.ignore_case,
+		));
+	}
+
+	/// keys changing the search options, searches again with them
+	fn option_event(&mut self, ev: &Event) -> Result<bool> {
+		if let Event::Key(e) = ev {
+			let keys = &self.key_config.keys;
+			if key_match(e, keys.search_toggle_regex) {
+				self.options.regex = !self.options.regex;
+			} else if key_match(e, keys.search_toggle_case) {
+				self.options.ignore_case = !self.options.ignore_case;
+			} else if key_match(e, keys.search_toggle_source) {
+				self.in_workdir = !self.in_workdir;
+			} else {
+				return Ok(false);
+			}
+
+			self.update_title();
+			self.search()?;
+			return Ok(true);
+		}
+
+		Ok(false)
+	}
+
+	fn input_event(&mut self, ev: &Event) -> Result<EventState> {
+		if let Event::Key(e) = ev {
+			let keys = &self.key_config.keys;
+			if key_match(e, keys.enter) {
+				self.search()?;
+				self.focus_input(false);
+				return Ok(EventState::Consumed);
+			} else if key_match(e, keys.exit_popup)
+				|| key_match(e, keys.move_down)
+			{
+				self.focus_input(false);
+				return Ok(EventState::Consumed);
+			}
+		}
+
+		self.input.event(ev)
+	}
+}
+
+impl DrawableComponent for SearchTab {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[Constraint::Length(3), Constraint::Min(1)]
+						.as_ref(),
+				)
+				.split(rect);
+
+			self.input.draw(f, chunks[0])?;
+			self.results.draw(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for SearchTab {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::search_start(&self.key_config),
+				true,
+				self.input_focused || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::search_focus_input(
+					&self.key_config,
+				),
+				true,
+				!self.input_focused || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::search_toggle_regex(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::search_toggle_case(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::search_toggle_source(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
+			self.results.commands(out, force_all);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if self.option_event(ev)? {
+			return Ok(EventState::Consumed);
+		}
+
+		if self.input_focused {
+			return self.input_event(ev);
+		}
+
+		if self.results.event(ev)?.is_consumed() {
+			return Ok(EventState::Consumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.search_focus_input) {
+				self.focus_input(true);
+				return Ok(EventState::Consumed);
+			}
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.input.show()?;
+		self.results.show()?;
+		self.focus_input(self.input_focused);
+		Ok(())
+	}
+}
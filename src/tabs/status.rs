@@ -3,12 +3,14 @@ use crate::{
 	components::{
 		command_pump, event_pump, visibility_blocking,
 		ChangesComponent, CommandBlocking, CommandInfo, Component,
-		DiffComponent, DrawableComponent, EventState,
-		FileTreeItemKind, SharedOptions,
+		DiffComponent, DrawableComponent, EventState, ExternalTool,
+		FileTreeItemKind, RecentCommitsComponent, SharedOptions,
+		StatusSummary, ToastKind,
 	},
 	keys::{key_match, SharedKeyConfig},
 	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
-	strings, try_or_popup,
+	string_utils::truncate_to_width,
+	strings, trash, try_or_popup,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
@@ -16,10 +18,11 @@ use asyncgit::{
 	cached,
 	sync::{
 		self, status::StatusType, RepoPath, RepoPathRef, RepoState,
+		ShowUntrackedFilesConfig, UndoOperation,
 	},
 	sync::{BranchCompare, CommitId},
 	AsyncDiff, AsyncGitNotification, AsyncStatus, DiffParams,
-	DiffType, PushType, StatusParams,
+	DiffType, PushType, StatusItemType, StatusParams,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -37,6 +40,7 @@ enum Focus {
 	WorkDir,
 	Diff,
 	Stage,
+	Commits,
 }
 
 /// focus can toggle between workdir and stage
@@ -44,7 +48,7 @@ impl Focus {
 	const fn toggled_focus(&self) -> Self {
 		match self {
 			Self::WorkDir => Self::Stage,
-			Self::Stage => Self::WorkDir,
+			Self::Stage | Self::Commits => Self::WorkDir,
 			Self::Diff => Self::Diff,
 		}
 	}
@@ -64,6 +68,7 @@ pub struct Status {
 	diff_target: DiffTarget,
 	index: ChangesComponent,
 	index_wd: ChangesComponent,
+	recent_commits: RecentCommitsComponent,
 	diff: DiffComponent,
 	git_diff: AsyncDiff,
 	has_remotes: bool,
@@ -72,9 +77,12 @@ pub struct Status {
 	git_status_stage: AsyncStatus,
 	git_branch_state: Option<BranchCompare>,
 	git_branch_name: cached::BranchName,
+	/// staged and unstaged files
+	changes: (usize, usize),
 	queue: Queue,
 	git_action_executed: bool,
 	options: SharedOptions,
+	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 }
 
@@ -115,6 +123,17 @@ impl DrawableComponent for Status {
 			)
 			.split(rects[0]);
 
+		let commits_chunks = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints(
+				[
+					Constraint::Min(0),
+					Constraint::Length(self.recent_commits.height()),
+				]
+				.as_ref(),
+			)
+			.split(chunks[0]);
+
 		let left_chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
@@ -131,10 +150,11 @@ impl DrawableComponent for Status {
 				}
 				.as_ref(),
 			)
-			.split(chunks[0]);
+			.split(commits_chunks[0]);
 
 		self.index_wd.draw(f, left_chunks[0])?;
 		self.index.draw(f, left_chunks[1])?;
+		self.recent_commits.draw(f, commits_chunks[1])?;
 		self.diff.draw(f, chunks[1])?;
 		self.draw_branch_state(f, &left_chunks);
 
@@ -147,7 +167,7 @@ impl DrawableComponent for Status {
 }
 
 impl Status {
-	accessors!(self, [index, index_wd, diff]);
+	accessors!(self, [index, index_wd, recent_commits, diff]);
 
 	///
 	pub fn new(
@@ -186,11 +206,18 @@ impl Status {
 				key_config.clone(),
 				options.clone(),
 			),
+			recent_commits: RecentCommitsComponent::new(
+				repo.clone(),
+				queue,
+				theme.clone(),
+				key_config.clone(),
+			),
 			diff: DiffComponent::new(
 				repo.clone(),
 				queue.clone(),
-				theme,
+				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 				false,
 			),
 			git_diff: AsyncDiff::new(repo_clone.clone(), sender),
@@ -205,6 +232,8 @@ impl Status {
 			git_action_executed: false,
 			git_branch_state: None,
 			git_branch_name: cached::BranchName::new(repo.clone()),
+			changes: (0, 0),
+			theme,
 			key_config,
 			options,
 			repo,
@@ -227,12 +256,6 @@ impl Status {
 					)
 				});
 
-			let w = Paragraph::new(format!(
-				"{}{{{}}}",
-				ahead_behind, branch_name
-			))
-			.alignment(Alignment::Right);
-
 			let mut rect = if self.index_wd.focused() {
 				let mut rect = chunks[0];
 				rect.y += rect.height.saturating_sub(1);
@@ -247,6 +270,15 @@ impl Status {
 				.height
 				.saturating_sub(rect.height.saturating_sub(1));
 
+			let w = Paragraph::new(
+				truncate_to_width(
+					&format!("{}{{{}}}", ahead_behind, branch_name),
+					rect.width.into(),
+				)
+				.into_owned(),
+			)
+			.alignment(Alignment::Right);
+
 			f.render_widget(w, rect);
 		}
 	}
@@ -309,15 +341,18 @@ impl Status {
 					Block::default()
 						.border_type(BorderType::Plain)
 						.borders(Borders::all())
-						.border_style(
+						.border_style(self.theme.colored(
 							Style::default().fg(Color::Yellow),
-						)
+						))
 						.title(format!(
 							"Pending {:?}",
 							self.git_state
 						)),
 				)
-				.style(Style::default().fg(Color::Red))
+				.style(
+					self.theme
+						.colored(Style::default().fg(Color::Red)),
+				)
 				.alignment(Alignment::Left);
 
 			f.render_widget(w, r);
@@ -332,7 +367,7 @@ impl Status {
 		match self.focus {
 			Focus::WorkDir => self.index_wd.is_file_seleted(),
 			Focus::Stage => self.index.is_file_seleted(),
-			Focus::Diff => false,
+			Focus::Diff | Focus::Commits => false,
 		}
 	}
 
@@ -348,17 +383,27 @@ impl Status {
 				Focus::WorkDir => {
 					self.set_diff_target(DiffTarget::WorkingDir);
 					self.diff.focus(false);
+					self.recent_commits.focus(false);
 				}
 				Focus::Stage => {
 					self.set_diff_target(DiffTarget::Stage);
 					self.diff.focus(false);
+					self.recent_commits.focus(false);
 				}
 				Focus::Diff => {
 					self.index.focus(false);
 					self.index_wd.focus(false);
+					self.recent_commits.focus(false);
 
 					self.diff.focus(true);
 				}
+				Focus::Commits => {
+					self.index.focus(false);
+					self.index_wd.focus(false);
+					self.diff.focus(false);
+
+					self.recent_commits.focus(true);
+				}
 			};
 
 			self.update_diff()?;
@@ -391,32 +436,69 @@ impl Status {
 		None
 	}
 
+	fn selected_is_conflicted(&self) -> bool {
+		let idx = match self.diff_target {
+			DiffTarget::Stage => &self.index,
+			DiffTarget::WorkingDir => &self.index_wd,
+		};
+
+		idx.selection().map_or(false, |item| {
+			matches!(
+				item.kind,
+				FileTreeItemKind::File(i)
+					if i.status == StatusItemType::Conflicted
+			)
+		})
+	}
+
+	fn can_open_tool(&self) -> bool {
+		self.can_focus_diff() || self.is_focus_on_diff()
+	}
+
 	///
 	pub fn update(&mut self) -> Result<()> {
 		self.git_branch_name.lookup().map(Some).unwrap_or(None);
 
 		if self.is_visible() {
 			let config = self.options.borrow().status_show_untracked;
+			let order = self.options.borrow().status_order;
 
 			self.git_diff.refresh()?;
-			self.git_status_workdir.fetch(&StatusParams::new(
-				StatusType::WorkingDir,
-				config,
-			))?;
-			self.git_status_stage.fetch(&StatusParams::new(
-				StatusType::Stage,
-				config,
-			))?;
+			self.git_status_workdir.fetch(
+				&StatusParams::new(StatusType::WorkingDir, config)
+					.order(order),
+			)?;
+			self.git_status_stage.fetch(
+				&StatusParams::new(StatusType::Stage, config)
+					.order(order),
+			)?;
 
 			self.git_state = sync::repo_state(&self.repo.borrow())
 				.unwrap_or(RepoState::Clean);
 
+			self.recent_commits.update()?;
+			if self.focus == Focus::Commits
+				&& self.recent_commits.is_empty()
+			{
+				self.switch_focus(Focus::WorkDir)?;
+			}
+
 			self.branch_compare();
 		}
 
 		Ok(())
 	}
 
+	/// stores the picked sort and grouping for this repository
+	pub fn order_switched(&mut self) -> Result<()> {
+		sync::set_status_order_config(
+			&self.repo.borrow(),
+			self.options.borrow().status_order,
+		)?;
+
+		self.update()
+	}
+
 	///
 	pub fn anything_pending(&self) -> bool {
 		self.git_diff.is_pending()
@@ -441,6 +523,7 @@ impl Status {
 			AsyncGitNotification::Status => self.update_status()?,
 			AsyncGitNotification::Push
 			| AsyncGitNotification::Pull
+			| AsyncGitNotification::Fetch
 			| AsyncGitNotification::CommitFiles => {
 				self.branch_compare();
 			}
@@ -457,6 +540,9 @@ impl Status {
 		let workdir_status = self.git_status_workdir.last()?;
 		self.index_wd.set_items(&workdir_status.items)?;
 
+		self.changes =
+			(stage_status.items.len(), workdir_status.items.len());
+
 		self.update_diff()?;
 		self.check_remotes();
 
@@ -490,7 +576,9 @@ impl Status {
 			let diff_params = DiffParams {
 				path: path.clone(),
 				diff_type,
-				options: self.options.borrow().diff,
+				options: self
+					.diff
+					.diff_options(&path, self.options.borrow().diff),
 			};
 
 			if self.diff.current() == (path.clone(), is_stage) {
@@ -537,10 +625,42 @@ impl Status {
 
 	/// called after confirmation
 	pub fn reset(&mut self, item: &ResetItem) -> bool {
-		if let Err(e) = sync::reset_workdir(
-			&self.repo.borrow(),
-			item.path.as_str(),
-		) {
+		let folder = format!("{}/", item.path.trim_end_matches('/'));
+
+		self.reset_paths(&[item.path.as_str()], |path| {
+			path == item.path
+				|| (item.is_folder && path.starts_with(&folder))
+		})
+	}
+
+	/// discards the files marked in the workdir list in one go
+	pub fn reset_marked(&mut self, paths: &[String]) -> bool {
+		let paths =
+			paths.iter().map(String::as_str).collect::<Vec<_>>();
+
+		self.reset_paths(&paths, |path| paths.contains(&path))
+	}
+
+	/// `untracked` tells which untracked files belong to `paths`,
+	/// those get moved to the trash if enabled
+	fn reset_paths(
+		&mut self,
+		paths: &[&str],
+		untracked: impl Fn(&str) -> bool,
+	) -> bool {
+		if trash::trash_enabled(&self.repo.borrow()) {
+			if let Err(e) = self.trash_untracked(untracked) {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("moving to trash failed:\n{}", e),
+				));
+
+				return false;
+			}
+		}
+
+		if let Err(e) =
+			sync::reset_workdir_paths(&self.repo.borrow(), paths)
+		{
 			self.queue.push(InternalEvent::ShowErrorMsg(format!(
 				"reset failed:\n{}",
 				e
@@ -552,6 +672,65 @@ impl Status {
 		}
 	}
 
+	/// moves the untracked files `matches` accepts to the trash, so
+	/// `reset_workdir` only has tracked changes left to discard.
+	/// a folder untracked as a whole goes in one piece unless some
+	/// of it is to be kept
+	fn trash_untracked(
+		&self,
+		matches: impl Fn(&str) -> bool,
+	) -> Result<()> {
+		let repo = self.repo.borrow();
+		let work_dir = sync::utils::repo_work_dir(&repo)?;
+
+		let untracked = |config| -> Result<Vec<String>> {
+			Ok(sync::status::get_status(
+				&repo,
+				StatusType::WorkingDir,
+				Some(config),
+			)?
+			.into_iter()
+			.filter(|status| status.status == StatusItemType::New)
+			.map(|status| status.path)
+			.collect())
+		};
+
+		let (files, kept): (Vec<_>, Vec<_>) =
+			untracked(ShowUntrackedFilesConfig::All)?
+				.into_iter()
+				.partition(|path| matches(path));
+		if files.is_empty() {
+			return Ok(());
+		}
+
+		// untracked folders are listed as `folder/` unless recursing
+		let folders = untracked(ShowUntrackedFilesConfig::Normal)?
+			.into_iter()
+			.filter(|path| {
+				path.ends_with('/')
+					&& files.iter().any(|file| file.starts_with(path))
+					&& !kept.iter().any(|file| file.starts_with(path))
+			})
+			.collect::<Vec<_>>();
+
+		let single = files.iter().filter(|file| {
+			!folders.iter().any(|folder| file.starts_with(folder))
+		});
+		for path in folders.iter().chain(single) {
+			trash::move_to_trash(
+				&std::path::Path::new(&work_dir)
+					.join(path.trim_end_matches('/')),
+			)?;
+		}
+
+		self.queue.push(InternalEvent::ShowToast(
+			ToastKind::Success,
+			strings::moved_to_trash(files.len()),
+		));
+
+		Ok(())
+	}
+
 	pub fn last_file_moved(&mut self) -> Result<()> {
 		if !self.is_focus_on_diff() && self.is_visible() {
 			self.switch_focus(self.focus.toggled_focus())?;
@@ -562,36 +741,134 @@ impl Status {
 	fn push(&self, force: bool) {
 		if self.can_push() {
 			if let Some(branch) = self.git_branch_name.last() {
-				if force {
-					self.queue.push(InternalEvent::ConfirmAction(
-						Action::ForcePush(branch, force),
-					));
-				} else {
-					self.queue.push(InternalEvent::Push(
-						branch,
-						PushType::Branch,
-						force,
-						false,
-					));
-				}
+				// a force push gets confirmed once the push popup
+				// knows what it overwrites
+				self.queue.push(InternalEvent::Push(
+					branch,
+					PushType::Branch,
+					force,
+					false,
+				));
+			}
+		}
+	}
+
+	fn push_review(&self) {
+		if self.has_remotes {
+			if let Some(branch) = self.git_branch_name.last() {
+				self.queue
+					.push(InternalEvent::OpenPushReview(branch));
+			}
+		}
+	}
+
+	fn push_options(&self) {
+		if self.has_remotes {
+			if let Some(branch) = self.git_branch_name.last() {
+				self.queue
+					.push(InternalEvent::OpenPushOptions(branch));
 			}
 		}
 	}
 
+	fn remote_commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		focus_on_diff: bool,
+	) {
+		out.push(CommandInfo::new(
+			strings::commands::status_push(&self.key_config),
+			self.can_push(),
+			!focus_on_diff,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::status_force_push(&self.key_config),
+			true,
+			self.can_push() && !focus_on_diff,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::status_push_review(&self.key_config),
+			self.has_remotes,
+			!focus_on_diff,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::status_push_options(&self.key_config),
+			self.has_remotes,
+			!focus_on_diff,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::status_pull(&self.key_config),
+			self.can_pull(),
+			!focus_on_diff,
+		));
+	}
+
 	fn pull(&self) {
 		if let Some(branch) = self.git_branch_name.last() {
 			self.queue.push(InternalEvent::Pull(branch));
 		}
 	}
 
+	/// asks first if the current branch is protected
 	fn undo_last_commit(&self) {
+		let protected =
+			self.git_branch_name.last().filter(|branch| {
+				sync::protected_branches_config(&self.repo.borrow())
+					.ok()
+					.and_then(|cfg| cfg.protection(branch))
+					.is_some()
+			});
+
+		if let Some(branch) = protected {
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::UndoCommitProtected(branch),
+			));
+		} else {
+			self.undo_last_commit_confirmed();
+		}
+	}
+
+	///
+	pub fn undo_last_commit_confirmed(&self) {
 		try_or_popup!(
 			self,
 			"undo commit failed:",
-			sync::utils::undo_last_commit(&self.repo.borrow())
+			sync::undo_snapshot(
+				&self.repo.borrow(),
+				&UndoOperation::UndoCommit
+			)
+			.and_then(|_| sync::utils::undo_last_commit(
+				&self.repo.borrow()
+			))
 		);
 	}
 
+	/// staged and unstaged paths of the last update
+	pub fn changed_files(&mut self) -> Vec<String> {
+		let mut files: Vec<String> = [
+			self.git_status_stage.last(),
+			self.git_status_workdir.last(),
+		]
+		.into_iter()
+		.flatten()
+		.flat_map(|status| status.items)
+		.map(|item| item.path)
+		.collect();
+		files.sort();
+		files.dedup();
+		files
+	}
+
+	/// what the status bar shows of the last update
+	pub const fn summary(&self) -> StatusSummary {
+		StatusSummary {
+			staged: self.changes.0,
+			unstaged: self.changes.1,
+			repo_state: self.git_state,
+			branch_state: self.git_branch_state,
+		}
+	}
+
 	fn branch_compare(&mut self) {
 		self.git_branch_state =
 			self.git_branch_name.last().and_then(|branch| {
@@ -733,23 +1010,7 @@ impl Component for Status {
 				!focus_on_diff,
 			));
 
-			out.push(CommandInfo::new(
-				strings::commands::status_push(&self.key_config),
-				self.can_push(),
-				!focus_on_diff,
-			));
-			out.push(CommandInfo::new(
-				strings::commands::status_force_push(
-					&self.key_config,
-				),
-				true,
-				self.can_push() && !focus_on_diff,
-			));
-			out.push(CommandInfo::new(
-				strings::commands::status_pull(&self.key_config),
-				self.can_pull(),
-				!focus_on_diff,
-			));
+			self.remote_commands(out, focus_on_diff);
 
 			out.push(CommandInfo::new(
 				strings::commands::undo_commit(&self.key_config),
@@ -787,6 +1048,12 @@ impl Component for Status {
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::show_ignored(&self.key_config),
+				true,
+				true,
+			));
 		}
 
 		{
@@ -800,6 +1067,19 @@ impl Component for Status {
 				self.visible || force_all,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::open_difftool(&self.key_config),
+				self.can_open_tool(),
+				self.visible || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::open_mergetool(&self.key_config),
+				true,
+				(self.visible && self.selected_is_conflicted())
+					|| force_all,
+			));
+
 			self.commands_nav(out, force_all);
 		}
 
@@ -832,6 +1112,33 @@ impl Component for Status {
 						);
 					}
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.open_difftool,
+				) && self.can_open_tool()
+				{
+					if let Some((path, stage)) = self.selected_path()
+					{
+						self.queue.push(
+							InternalEvent::OpenExternalTool(
+								ExternalTool::Diff { path, stage },
+							),
+						);
+					}
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.open_mergetool,
+				) && self.selected_is_conflicted()
+				{
+					if let Some((path, _)) = self.selected_path() {
+						self.queue.push(
+							InternalEvent::OpenExternalTool(
+								ExternalTool::Merge { path },
+							),
+						);
+					}
+					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.open_commit,
@@ -866,11 +1173,27 @@ impl Component for Status {
 					&& !self.index.is_empty()
 				{
 					self.switch_focus(Focus::Stage).map(Into::into)
+				} else if key_match(k, self.key_config.keys.move_down)
+					&& (self.focus == Focus::Stage
+						|| (self.focus == Focus::WorkDir
+							&& self.index.is_empty()))
+					&& !self.recent_commits.is_empty()
+				{
+					self.switch_focus(Focus::Commits).map(Into::into)
 				} else if key_match(k, self.key_config.keys.move_up)
 					&& self.focus == Focus::Stage
 					&& !self.index_wd.is_empty()
 				{
 					self.switch_focus(Focus::WorkDir).map(Into::into)
+				} else if key_match(k, self.key_config.keys.move_up)
+					&& self.focus == Focus::Commits
+				{
+					self.switch_focus(if self.index.is_empty() {
+						Focus::WorkDir
+					} else {
+						Focus::Stage
+					})
+					.map(Into::into)
 				} else if key_match(
 					k,
 					self.key_config.keys.select_branch,
@@ -891,6 +1214,22 @@ impl Component for Status {
 				{
 					self.push(false);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.push_review,
+				) && !self.is_focus_on_diff()
+					&& self.has_remotes
+				{
+					self.push_review();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.push_options,
+				) && !self.is_focus_on_diff()
+					&& self.has_remotes
+				{
+					self.push_options();
+					Ok(EventState::Consumed)
 				} else if key_match(k, self.key_config.keys.pull)
 					&& !self.is_focus_on_diff()
 					&& self.can_pull()
@@ -948,6 +1287,12 @@ impl Component for Status {
 				) {
 					self.queue.push(InternalEvent::ViewSubmodules);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_show_ignored,
+				) {
+					self.queue.push(InternalEvent::ViewIgnored);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
@@ -0,0 +1,287 @@
+//! deleting untracked files by moving them to the trash of the os,
+//! needs the `trash` feature and can be turned off with
+//! `gitui.useTrash=false` in git-config
+
+use anyhow::Result;
+use asyncgit::sync::{get_config_string, RepoPath};
+use std::path::Path;
+
+/// `gitui.useTrash`, on unless set to a false value
+pub fn trash_enabled(repo: &RepoPath) -> bool {
+	cfg!(feature = "trash")
+		&& get_config_string(repo, "gitui.useTrash")
+			.ok()
+			.flatten()
+			.map_or(true, |value| {
+				!matches!(
+					value.to_lowercase().as_str(),
+					"false" | "no" | "off" | "0"
+				)
+			})
+}
+
+/// moves the file or folder at `path` to the trash
+#[cfg(not(feature = "trash"))]
+pub fn move_to_trash(_path: &Path) -> Result<()> {
+	anyhow::bail!("gitui was built without the trash feature")
+}
+
+/// moves the file or folder at `path` to the trash
+#[cfg(all(feature = "trash", unix, not(target_os = "macos")))]
+pub fn move_to_trash(path: &Path) -> Result<()> {
+	let trash = dirs_next::data_dir()
+		.ok_or_else(|| anyhow::anyhow!("no data directory"))?
+		.join("Trash");
+
+	freedesktop::trash(&trash, path)
+}
+
+/// moves the file or folder at `path` to the trash
+#[cfg(all(feature = "trash", target_os = "macos"))]
+pub fn move_to_trash(path: &Path) -> Result<()> {
+	let output = std::process::Command::new("osascript")
+		.args([
+			"-e",
+			"on run argv",
+			"-e",
+			"tell application \"Finder\" to delete POSIX file (item 1 of argv)",
+			"-e",
+			"end run",
+		])
+		.arg(path.canonicalize()?)
+		.output()?;
+
+	if !output.status.success() {
+		anyhow::bail!(
+			"moving to trash failed: {}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+
+	Ok(())
+}
+
+/// moves the file or folder at `path` to the trash
+#[cfg(all(feature = "trash", windows))]
+pub fn move_to_trash(path: &Path) -> Result<()> {
+	const SCRIPT: &str = "Add-Type -AssemblyName Microsoft.VisualBasic; \
+		if (Test-Path -LiteralPath $args[0] -PathType Container) { \
+		[Microsoft.VisualBasic.FileIO.FileSystem]::DeleteDirectory($args[0], 'OnlyErrorDialogs', 'SendToRecycleBin') \
+		} else { \
+		[Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile($args[0], 'OnlyErrorDialogs', 'SendToRecycleBin') \
+		}";
+
+	let path =
+		path.canonicalize()?.to_string_lossy().replace('\'', "''");
+	let output = std::process::Command::new("powershell")
+		.args(["-NoProfile", "-NonInteractive", "-Command"])
+		.arg(format!("& {{ {} }} '{}'", SCRIPT, path))
+		.output()?;
+
+	if !output.status.success() {
+		anyhow::bail!(
+			"moving to trash failed: {}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+
+	Ok(())
+}
+
+/// the home trash of the freedesktop.org trash spec and the
+/// `.Trash-$uid` ones at the top of other file systems, see
+/// <https://specifications.freedesktop.org/trash-spec/trashspec-latest.html>
+#[cfg(all(feature = "trash", unix, not(target_os = "macos")))]
+mod freedesktop {
+	use anyhow::Result;
+	use std::{
+		fs::{self, DirBuilder, OpenOptions},
+		io::{ErrorKind, Write},
+		os::unix::fs::{DirBuilderExt, MetadataExt},
+		path::{Path, PathBuf},
+	};
+
+	/// trashes `path` into `home_trash`, or into `.Trash-$uid` at the
+	/// top of its file system if that is another one: moving there
+	/// would need a copy
+	pub fn trash(home_trash: &Path, path: &Path) -> Result<()> {
+		create_private_dir(home_trash)?;
+
+		let dir = parent_dir(path);
+		let home = fs::metadata(home_trash)?;
+		if fs::metadata(dir)?.dev() == home.dev() {
+			return trash_into(home_trash, path, None);
+		}
+
+		// the home trash belongs to the user
+		let top = top_dir(&fs::canonicalize(dir)?)?;
+		let trash = top.join(format!(".Trash-{}", home.uid()));
+		create_private_dir(&trash)?;
+
+		trash_into(&trash, path, Some(&top))
+	}
+
+	fn create_private_dir(dir: &Path) -> Result<()> {
+		Ok(DirBuilder::new()
+			.recursive(true)
+			.mode(0o700)
+			.create(dir)?)
+	}
+
+	/// where `path` is, `.` for a plain file name
+	fn parent_dir(path: &Path) -> &Path {
+		path.parent()
+			.filter(|dir| !dir.as_os_str().is_empty())
+			.unwrap_or_else(|| Path::new("."))
+	}
+
+	/// the mount point of the file system `dir` is on
+	fn top_dir(dir: &Path) -> Result<PathBuf> {
+		let dev = fs::metadata(dir)?.dev();
+
+		let mut top = dir;
+		while let Some(parent) = top.parent() {
+			if fs::metadata(parent)?.dev() != dev {
+				break;
+			}
+			top = parent;
+		}
+
+		Ok(top.to_path_buf())
+	}
+
+	/// `top` is the dir a trash on another file system is at, paths
+	/// are stored relative to it
+	pub fn trash_into(
+		trash: &Path,
+		path: &Path,
+		top: Option<&Path>,
+	) -> Result<()> {
+		let files = trash.join("files");
+		let info = trash.join("info");
+		fs::create_dir_all(&files)?;
+		fs::create_dir_all(&info)?;
+
+		// the link itself, not what it points to
+		let name = path.file_name().ok_or_else(|| {
+			anyhow::anyhow!("cannot trash {}", path.display())
+		})?;
+		let path = fs::canonicalize(parent_dir(path))?.join(name);
+		let stored = top
+			.and_then(|top| path.strip_prefix(top).ok())
+			.unwrap_or(&path);
+		let name = name.to_string_lossy();
+
+		// creating the info file reserves the name in the trash
+		for idx in 1.. {
+			let trashed = if idx == 1 {
+				name.to_string()
+			} else {
+				format!("{}.{}", name, idx)
+			};
+			let info_file =
+				info.join(format!("{}.trashinfo", trashed));
+
+			let mut file = match OpenOptions::new()
+				.write(true)
+				.create_new(true)
+				.open(&info_file)
+			{
+				Ok(file) => file,
+				Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+					continue
+				}
+				Err(e) => return Err(e.into()),
+			};
+
+			write!(
+				file,
+				"[Trash Info]\nPath={}\nDeletionDate={}\n",
+				encode(&stored.to_string_lossy()),
+				chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+			)?;
+
+			// fails across file systems, the file is kept then
+			if let Err(e) = fs::rename(&path, files.join(&trashed)) {
+				fs::remove_file(&info_file).ok();
+				return Err(e.into());
+			}
+
+			break;
+		}
+
+		Ok(())
+	}
+
+	/// percent encoding of everything but unreserved characters and `/`
+	fn encode(path: &str) -> String {
+		path.bytes()
+			.map(|b| {
+				if b.is_ascii_alphanumeric() || b"/-_.~".contains(&b)
+				{
+					char::from(b).to_string()
+				} else {
+					format!("%{:02X}", b)
+				}
+			})
+			.collect()
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		#[test]
+		fn test_trash_into() {
+			let dir = std::env::temp_dir().join(format!(
+				"gitui-trash-test-{}",
+				std::process::id()
+			));
+			let trash = dir.join("Trash");
+			fs::create_dir_all(&dir).unwrap();
+
+			for content in ["first", "second"] {
+				fs::write(dir.join("new file"), content).unwrap();
+				trash_into(&trash, &dir.join("new file"), None)
+					.unwrap();
+			}
+
+			assert!(!dir.join("new file").exists());
+			assert_eq!(
+				fs::read_to_string(trash.join("files/new file.2"))
+					.unwrap(),
+				"second"
+			);
+
+			let info = fs::read_to_string(
+				trash.join("info/new file.trashinfo"),
+			)
+			.unwrap();
+			assert!(info.starts_with("[Trash Info]\nPath=/"));
+			assert!(info.contains("/new%20file\nDeletionDate="));
+
+			fs::write(dir.join("other"), "").unwrap();
+			trash_into(&trash, &dir.join("other"), Some(&dir))
+				.unwrap();
+			assert!(fs::read_to_string(
+				trash.join("info/other.trashinfo")
+			)
+			.unwrap()
+			.starts_with("[Trash Info]\nPath=other\n"));
+
+			fs::remove_dir_all(dir).unwrap();
+		}
+
+		#[test]
+		fn test_top_dir() {
+			let dir = fs::canonicalize(std::env::temp_dir()).unwrap();
+			let top = top_dir(&dir).unwrap();
+
+			assert!(dir.starts_with(&top));
+			assert_eq!(
+				fs::metadata(&top).unwrap().dev(),
+				fs::metadata(&dir).unwrap().dev()
+			);
+		}
+	}
+}
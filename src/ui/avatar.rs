@@ -0,0 +1,137 @@
+//! author badges and avatars derived from the gravatar hash
+//! (md5 of the trimmed, lowercased email) so the same author always
+//! gets the same color and pattern
+
+use md5::{Digest, Md5};
+use tui::{
+	style::{Color, Style},
+	text::{Span, Spans},
+};
+
+/// selection uses blue as background, so no (dark) blue here
+const PALETTE: [Color; 11] = [
+	Color::Red,
+	Color::Green,
+	Color::Yellow,
+	Color::Magenta,
+	Color::Cyan,
+	Color::LightRed,
+	Color::LightGreen,
+	Color::LightYellow,
+	Color::LightBlue,
+	Color::LightMagenta,
+	Color::LightCyan,
+];
+
+/// avatars are `AVATAR_SIZE` x `AVATAR_SIZE` cells, mirrored
+/// horizontally like identicons
+pub const AVATAR_SIZE: usize = 5;
+/// terminal columns per cell to make cells roughly square
+const CELL: &str = "██";
+const EMPTY_CELL: &str = "  ";
+
+/// up to two letters: the first letters of the first and last word
+/// of `name` or the first two of a single word
+pub fn author_initials(name: &str) -> String {
+	let mut words = name.split_whitespace();
+	let first = words.next().unwrap_or_default();
+
+	let initials: String = words.last().map_or_else(
+		|| first.chars().take(2).collect(),
+		|last| {
+			first
+				.chars()
+				.take(1)
+				.chain(last.chars().take(1))
+				.collect()
+		},
+	);
+
+	initials.to_uppercase()
+}
+
+/// color of the author with `email`
+pub fn author_color(email: &str) -> Color {
+	color(&gravatar_hash(email))
+}
+
+/// block art of the avatar of `email`, `AVATAR_SIZE` lines
+pub fn avatar_rows(email: &str) -> Vec<Spans<'static>> {
+	let hash = gravatar_hash(email);
+	let style = Style::default().fg(color(&hash));
+
+	(0..AVATAR_SIZE)
+		.map(|row| {
+			Spans::from(
+				(0..AVATAR_SIZE)
+					.map(|col| {
+						// mirror the left half
+						let col = col.min(AVATAR_SIZE - 1 - col);
+						let bit = row * ((AVATAR_SIZE + 1) / 2) + col;
+						let filled =
+							hash[bit / 8] & (1 << (bit % 8)) != 0;
+
+						Span::styled(
+							if filled { CELL } else { EMPTY_CELL },
+							style,
+						)
+					})
+					.collect::<Vec<_>>(),
+			)
+		})
+		.collect()
+}
+
+fn color(hash: &[u8; 16]) -> Color {
+	PALETTE[usize::from(hash[15]) % PALETTE.len()]
+}
+
+fn gravatar_hash(email: &str) -> [u8; 16] {
+	Md5::digest(email.trim().to_lowercase().as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fmt::Write;
+
+	fn hex(bytes: &[u8]) -> String {
+		bytes.iter().fold(String::new(), |mut s, b| {
+			let _ = write!(s, "{:02x}", b);
+			s
+		})
+	}
+
+	#[test]
+	fn test_gravatar_hash() {
+		assert_eq!(
+			hex(&gravatar_hash(" MyEmailAddress@example.com ")),
+			"0bc83cb571cd1c50ba6f3e8a78ef1346"
+		);
+	}
+
+	#[test]
+	fn test_initials() {
+		assert_eq!(author_initials("Stephan Dilly"), "SD");
+		assert_eq!(author_initials("Jane van der Berg"), "JB");
+		assert_eq!(author_initials("extrawurst"), "EX");
+		assert_eq!(author_initials(""), "");
+	}
+
+	#[test]
+	fn test_avatar_is_mirrored() {
+		let rows = avatar_rows("someone@example.com");
+
+		assert_eq!(rows.len(), AVATAR_SIZE);
+		for row in rows {
+			let cells: Vec<_> = row
+				.0
+				.iter()
+				.map(|span| span.content.clone())
+				.collect();
+			let mut mirrored = cells.clone();
+			mirrored.reverse();
+			assert_eq!(cells, mirrored);
+		}
+	}
+}
@@ -1,3 +1,4 @@
+pub mod avatar;
 mod reflow;
 mod scrollbar;
 mod scrolllist;
@@ -16,21 +17,6 @@ use tui::layout::{Constraint, Direction, Layout, Rect};
 
 use crate::keys::{key_match, SharedKeyConfig};
 
-/// return the scroll position (line) necessary to have the `selection` in view if it is not already
-pub const fn calc_scroll_top(
-	current_top: usize,
-	height_in_lines: usize,
-	selection: usize,
-) -> usize {
-	if current_top + height_in_lines <= selection {
-		selection.saturating_sub(height_in_lines) + 1
-	} else if current_top > selection {
-		selection
-	} else {
-		current_top
-	}
-}
-
 /// ui component size representation
 #[derive(Copy, Clone)]
 pub struct Size {
@@ -92,9 +78,12 @@ pub fn rect_inside(min: Size, max: Size, r: Rect) -> Rect {
 	let diff_width = new_width.saturating_sub(r.width);
 	let diff_height = new_height.saturating_sub(r.height);
 
+	// growing around the center must not push it out of `max`
 	Rect::new(
-		r.x.saturating_sub(diff_width / 2),
-		r.y.saturating_sub(diff_height / 2),
+		r.x.saturating_sub(diff_width / 2)
+			.min(max.width.saturating_sub(new_width)),
+		r.y.saturating_sub(diff_height / 2)
+			.min(max.height.saturating_sub(new_height)),
 		new_width,
 		new_height,
 	)
@@ -106,8 +95,8 @@ pub fn centered_rect_absolute(
 	r: Rect,
 ) -> Rect {
 	Rect::new(
-		(r.width.saturating_sub(width)) / 2,
-		(r.height.saturating_sub(height)) / 2,
+		r.x + (r.width.saturating_sub(width)) / 2,
+		r.y + (r.height.saturating_sub(height)) / 2,
 		width.min(r.width),
 		height.min(r.height),
 	)
@@ -142,3 +131,19 @@ pub fn common_nav(
 		None
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rect_inside_stays_in_max() {
+		let r = rect_inside(
+			Size::new(20, 3),
+			Size::new(40, 10),
+			Rect::new(30, 8, 10, 1),
+		);
+
+		assert_eq!(r, Rect::new(20, 7, 20, 3));
+	}
+}
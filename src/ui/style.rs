@@ -1,5 +1,6 @@
+use crate::config_check::{check_file, ConfigIssue};
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{sync::ci::CiState, DiffLineType, StatusItemType};
 use ron::{
 	de::from_bytes,
 	ser::{to_string_pretty, PrettyConfig},
@@ -8,10 +9,13 @@ use serde::{Deserialize, Serialize};
 use std::{
 	fs::{self, File},
 	io::{Read, Write},
-	path::PathBuf,
+	path::{Path, PathBuf},
 	rc::Rc,
 };
-use tui::style::{Color, Modifier, Style};
+use tui::{
+	style::{Color, Modifier, Style},
+	text::Text,
+};
 
 pub type SharedTheme = Rc<Theme>;
 
@@ -52,6 +56,14 @@ pub struct Theme {
 	push_gauge_bg: Color,
 	#[serde(with = "Color")]
 	push_gauge_fg: Color,
+	#[serde(with = "Color")]
+	blame_heat_recent: Color,
+	#[serde(with = "Color")]
+	blame_heat_old: Color,
+	/// colors off: selection, disabled and danger text are told apart
+	/// by modifiers instead
+	#[serde(skip)]
+	monochrome: bool,
 }
 
 impl Theme {
@@ -63,7 +75,7 @@ impl Theme {
 		if focus {
 			Style::default()
 		} else {
-			Style::default().fg(self.disabled_fg)
+			self.disabled()
 		}
 	}
 
@@ -71,7 +83,7 @@ impl Theme {
 		if focused {
 			Style::default().add_modifier(Modifier::BOLD)
 		} else {
-			Style::default().fg(self.disabled_fg)
+			self.disabled()
 		}
 	}
 
@@ -83,11 +95,7 @@ impl Theme {
 		};
 
 		if selected {
-			branch.patch(
-				Style::default()
-					.fg(self.command_fg)
-					.bg(self.selection_bg),
-			)
+			self.apply_select(branch.fg(self.command_fg), true)
 		} else {
 			branch
 		}
@@ -104,24 +112,26 @@ impl Theme {
 	}
 
 	pub fn tags(&self, selected: bool) -> Style {
-		Style::default()
-			.fg(self.selected_tab)
-			.add_modifier(Modifier::BOLD)
-			.bg(if selected {
-				self.selection_bg
-			} else {
-				Color::Reset
-			})
+		self.apply_select(
+			Style::default()
+				.fg(self.selected_tab)
+				.add_modifier(Modifier::BOLD)
+				.bg(Color::Reset),
+			selected,
+		)
 	}
 
 	pub fn text(&self, enabled: bool, selected: bool) -> Style {
 		match (enabled, selected) {
-			(false, false) => Style::default().fg(self.disabled_fg),
-			(false, true) => Style::default().bg(self.selection_bg),
+			(false, false) => self.disabled(),
+			(false, true) => {
+				self.apply_select(Style::default(), true)
+			}
 			(true, false) => Style::default(),
-			(true, true) => Style::default()
-				.fg(self.command_fg)
-				.bg(self.selection_bg),
+			(true, true) => self.apply_select(
+				Style::default().fg(self.command_fg),
+				true,
+			),
 		}
 	}
 
@@ -163,13 +173,52 @@ impl Theme {
 	}
 
 	fn apply_select(&self, style: Style, selected: bool) -> Style {
-		if selected {
+		if !selected {
+			style
+		} else if self.monochrome {
+			style.add_modifier(Modifier::REVERSED)
+		} else {
 			style.bg(self.selection_bg)
+		}
+	}
+
+	fn disabled(&self) -> Style {
+		if self.monochrome {
+			Style::default().add_modifier(Modifier::DIM)
+		} else {
+			Style::default().fg(self.disabled_fg)
+		}
+	}
+
+	/// `style` for colors that don't come from the theme, like
+	/// syntax highlighting: only its modifiers when colors are off
+	pub const fn colored(&self, style: Style) -> Style {
+		if self.monochrome {
+			Style {
+				fg: None,
+				bg: None,
+				..style
+			}
 		} else {
 			style
 		}
 	}
 
+	/// `text` with every span passed through [`Self::colored`]
+	pub fn colored_text<'a>(&self, mut text: Text<'a>) -> Text<'a> {
+		if self.monochrome {
+			for span in text
+				.lines
+				.iter_mut()
+				.flat_map(|line| line.0.iter_mut())
+			{
+				span.style = self.colored(span.style);
+			}
+		}
+
+		text
+	}
+
 	pub fn option(&self, on: bool) -> Style {
 		if on {
 			Style::default().fg(self.diff_line_add)
@@ -180,9 +229,9 @@ impl Theme {
 
 	pub fn diff_hunk_marker(&self, selected: bool) -> Style {
 		if selected {
-			Style::default().bg(self.selection_bg)
+			self.apply_select(Style::default(), true)
 		} else {
-			Style::default().fg(self.disabled_fg)
+			self.disabled()
 		}
 	}
 
@@ -198,9 +247,9 @@ impl Theme {
 			DiffLineType::Delete => {
 				Style::default().fg(self.diff_line_delete)
 			}
-			DiffLineType::Header => Style::default()
-				.fg(self.disabled_fg)
-				.add_modifier(Modifier::BOLD),
+			DiffLineType::Header => {
+				self.disabled().add_modifier(Modifier::BOLD)
+			}
 			DiffLineType::None => Style::default().fg(if selected {
 				self.command_fg
 			} else {
@@ -211,15 +260,33 @@ impl Theme {
 		self.apply_select(style, selected)
 	}
 
+	pub fn ci_state(&self, state: CiState, selected: bool) -> Style {
+		let fg = match state {
+			CiState::Success => self.diff_line_add,
+			CiState::Pending => self.diff_file_modified,
+			CiState::Failure => self.danger_fg,
+		};
+
+		self.apply_select(
+			self.colored(Style::default().fg(fg))
+				.add_modifier(Modifier::BOLD),
+			selected,
+		)
+	}
+
 	pub fn text_danger(&self) -> Style {
-		Style::default().fg(self.danger_fg)
+		if self.monochrome {
+			Style::default().add_modifier(Modifier::BOLD)
+		} else {
+			Style::default().fg(self.danger_fg)
+		}
 	}
 
 	pub fn commandbar(&self, enabled: bool, line: usize) -> Style {
 		if enabled {
 			Style::default().fg(self.command_fg)
 		} else {
-			Style::default().fg(self.disabled_fg)
+			self.disabled()
 		}
 		.bg(if line == 0 {
 			self.cmdbar_bg
@@ -228,6 +295,12 @@ impl Theme {
 		})
 	}
 
+	pub fn tutorial_highlight(&self) -> Style {
+		Style::default()
+			.fg(self.selected_tab)
+			.add_modifier(Modifier::BOLD)
+	}
+
 	pub fn commit_hash(&self, selected: bool) -> Style {
 		self.apply_select(
 			Style::default().fg(self.commit_hash),
@@ -272,6 +345,16 @@ impl Theme {
 		}
 	}
 
+	/// `age` is expected in the range of `0.0` (most recent
+	/// change in the file) to `1.0` (oldest change in the file)
+	pub fn blame_heat(&self, age: f64) -> Style {
+		Style::default().fg(heat_color(
+			self.blame_heat_recent,
+			self.blame_heat_old,
+			age,
+		))
+	}
+
 	pub fn push_gauge(&self) -> Style {
 		Style::default()
 			.fg(self.push_gauge_fg)
@@ -293,6 +376,39 @@ impl Theme {
 		Ok(from_bytes(&buffer)?)
 	}
 
+	/// problems in the theme file, to be checked before `init`
+	/// replaces a broken one with the defaults
+	pub fn check(file: &Path) -> Vec<ConfigIssue> {
+		check_file::<Self>(file, &[])
+	}
+
+	/// without any colors, for terminals or users that don't want them
+	pub const fn monochrome() -> Self {
+		Self {
+			selected_tab: Color::Reset,
+			command_fg: Color::Reset,
+			selection_bg: Color::Reset,
+			cmdbar_bg: Color::Reset,
+			cmdbar_extra_lines_bg: Color::Reset,
+			disabled_fg: Color::Reset,
+			diff_line_add: Color::Reset,
+			diff_line_delete: Color::Reset,
+			diff_file_added: Color::Reset,
+			diff_file_removed: Color::Reset,
+			diff_file_moved: Color::Reset,
+			diff_file_modified: Color::Reset,
+			commit_hash: Color::Reset,
+			commit_time: Color::Reset,
+			commit_author: Color::Reset,
+			danger_fg: Color::Reset,
+			push_gauge_bg: Color::Reset,
+			push_gauge_fg: Color::Reset,
+			blame_heat_recent: Color::Reset,
+			blame_heat_old: Color::Reset,
+			monochrome: true,
+		}
+	}
+
 	pub fn init(file: &PathBuf) -> Result<Self> {
 		if file.exists() {
 			match Self::read_file(file.clone()) {
@@ -319,6 +435,37 @@ impl Theme {
 	}
 }
 
+/// interpolates between two rgb colors, other colors can not be
+/// mixed so it falls back to picking the closer one
+fn heat_color(recent: Color, old: Color, age: f64) -> Color {
+	let age = age.clamp(0.0, 1.0);
+
+	match (recent, old) {
+		(Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+			let mix = |a: u8, b: u8| {
+				let v = (f64::from(b) - f64::from(a))
+					.mul_add(age, f64::from(a));
+				// value is within `a` and `b` so it always fits
+				#[allow(
+					clippy::cast_possible_truncation,
+					clippy::cast_sign_loss
+				)]
+				let v = v.round() as u8;
+				v
+			};
+
+			Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+		}
+		_ => {
+			if age < 0.5 {
+				recent
+			} else {
+				old
+			}
+		}
+	}
+}
+
 impl Default for Theme {
 	fn default() -> Self {
 		Self {
@@ -340,6 +487,60 @@ impl Default for Theme {
 			danger_fg: Color::Red,
 			push_gauge_bg: Color::Blue,
 			push_gauge_fg: Color::Reset,
+			blame_heat_recent: Color::LightYellow,
+			blame_heat_old: Color::DarkGray,
+			monochrome: false,
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_heat_color_rgb() {
+		let recent = Color::Rgb(200, 0, 100);
+		let old = Color::Rgb(0, 200, 100);
+
+		assert_eq!(heat_color(recent, old, 0.0), recent);
+		assert_eq!(heat_color(recent, old, 1.0), old);
+		assert_eq!(
+			heat_color(recent, old, 0.5),
+			Color::Rgb(100, 100, 100)
+		);
+	}
+
+	#[test]
+	fn test_heat_color_named() {
+		let recent = Color::Yellow;
+		let old = Color::DarkGray;
+
+		assert_eq!(heat_color(recent, old, 0.2), recent);
+		assert_eq!(heat_color(recent, old, 0.8), old);
+	}
+
+	#[test]
+	fn test_monochrome() {
+		let theme = Theme::monochrome();
+
+		assert_eq!(
+			theme.text(true, true),
+			Style::default()
+				.fg(Color::Reset)
+				.add_modifier(Modifier::REVERSED)
+		);
+		assert_eq!(
+			theme.colored(
+				Style::default()
+					.fg(Color::Red)
+					.add_modifier(Modifier::BOLD)
+			),
+			Style::default().add_modifier(Modifier::BOLD)
+		);
+		assert_eq!(
+			Theme::default().colored(Style::default().fg(Color::Red)),
+			Style::default().fg(Color::Red)
+		);
+	}
+}
@@ -15,11 +15,10 @@ pub struct RepoWatcher {
 }
 
 impl RepoWatcher {
-	pub fn new(workdir: &str) -> Result<Self> {
+	pub fn new(workdir: &str, debounce: Duration) -> Result<Self> {
 		let (tx, rx) = std::sync::mpsc::channel();
 
-		let mut debouncer =
-			new_debouncer(Duration::from_secs(2), None, tx)?;
+		let mut debouncer = new_debouncer(debounce, None, tx)?;
 
 		debouncer
 			.watcher()